@@ -0,0 +1,274 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::pde::{Exercise, FiniteDifference, Scheme};
+use crate::tree::BinomialTree;
+
+/// Relative tolerance on the critical-price equation in Barone-Adesi-Whaley
+const CRITICAL_TOLERANCE: f64 = 1e-10;
+
+/// Iteration cap for the critical-price solver
+const CRITICAL_MAX_ITERATIONS: usize = 200;
+
+/// Barone-Adesi-Whaley (1987) quadratic approximation for American options
+///
+/// Splits the American price into the Black-Scholes value plus an early
+/// exercise premium A·(S/S*)^q, where S* is the critical exercise price.
+pub fn barone_adesi_whaley(model: &BlackScholes, option_type: OptionType) -> Result<f64, BlackScholesError> {
+    let european = model.price(option_type);
+    let r = model.risk_free_rate;
+    let b = r - model.dividend_yield;
+    // An American call on an asset that pays no yield is never exercised early
+    if option_type == OptionType::Call && b >= r {
+        return Ok(european);
+    }
+    if r <= 0.0 {
+        return Err(BlackScholesError::invalid(
+            "Barone-Adesi-Whaley needs a positive rate for early exercise",
+        ));
+    }
+
+    let s = model.spot_price;
+    let critical = critical_price(model, option_type)?;
+    let t = model.time_to_expiry;
+    let sigma = model.calendar_volatility();
+    let carry_discount = ((b - r) * t).exp();
+    let (m, n, k) = (2.0 * r / (sigma * sigma), 2.0 * b / (sigma * sigma), 1.0 - (-r * t).exp());
+    let d1 = |spot: f64| ((spot / model.strike_price).ln() + (b + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+
+    Ok(match option_type {
+        OptionType::Call => {
+            if s >= critical {
+                return Ok(s - model.strike_price);
+            }
+            let q2 = (-(n - 1.0) + ((n - 1.0).powi(2) + 4.0 * m / k).sqrt()) / 2.0;
+            let a2 = critical / q2 * (1.0 - carry_discount * BlackScholes::norm_cdf(d1(critical)));
+            european + a2 * (s / critical).powf(q2)
+        }
+        OptionType::Put => {
+            if s <= critical {
+                return Ok(model.strike_price - s);
+            }
+            let q1 = (-(n - 1.0) - ((n - 1.0).powi(2) + 4.0 * m / k).sqrt()) / 2.0;
+            let a1 = -critical / q1 * (1.0 - carry_discount * BlackScholes::norm_cdf(-d1(critical)));
+            european + a1 * (s / critical).powf(q1)
+        }
+    })
+}
+
+/// Critical exercise price S* by Newton iteration (seeds as in Haug's handbook)
+fn critical_price(model: &BlackScholes, option_type: OptionType) -> Result<f64, BlackScholesError> {
+    let k = model.strike_price;
+    let t = model.time_to_expiry;
+    let r = model.risk_free_rate;
+    let b = r - model.dividend_yield;
+    let sigma = model.calendar_volatility();
+    let sqrt_t = t.sqrt();
+    let carry_discount = ((b - r) * t).exp();
+    let (m, n, kk) = (2.0 * r / (sigma * sigma), 2.0 * b / (sigma * sigma), 1.0 - (-r * t).exp());
+    let european_at = |spot: f64| BlackScholes { spot_price: spot, ..*model }.price(option_type);
+    let d1 = |spot: f64| ((spot / k).ln() + (b + 0.5 * sigma * sigma) * t) / (sigma * sqrt_t);
+
+    let mut si = match option_type {
+        OptionType::Call => {
+            let q2u = (-(n - 1.0) + ((n - 1.0).powi(2) + 4.0 * m).sqrt()) / 2.0;
+            let su = k / (1.0 - 1.0 / q2u);
+            let h2 = -(b * t + 2.0 * sigma * sqrt_t) * k / (su - k);
+            k + (su - k) * (1.0 - h2.exp())
+        }
+        OptionType::Put => {
+            let q1u = (-(n - 1.0) - ((n - 1.0).powi(2) + 4.0 * m).sqrt()) / 2.0;
+            let su = k / (1.0 - 1.0 / q1u);
+            let h1 = (b * t - 2.0 * sigma * sqrt_t) * k / (k - su);
+            su + (k - su) * h1.exp()
+        }
+    };
+
+    for _ in 0..CRITICAL_MAX_ITERATIONS {
+        let d = d1(si);
+        match option_type {
+            OptionType::Call => {
+                let q2 = (-(n - 1.0) + ((n - 1.0).powi(2) + 4.0 * m / kk).sqrt()) / 2.0;
+                let rhs = european_at(si) + (1.0 - carry_discount * BlackScholes::norm_cdf(d)) * si / q2;
+                if ((si - k) - rhs).abs() / k < CRITICAL_TOLERANCE {
+                    return Ok(si);
+                }
+                let slope = carry_discount * BlackScholes::norm_cdf(d) * (1.0 - 1.0 / q2)
+                    + (1.0 - carry_discount * BlackScholes::norm_pdf(d) / (sigma * sqrt_t)) / q2;
+                si = (k + rhs - slope * si) / (1.0 - slope);
+            }
+            OptionType::Put => {
+                let q1 = (-(n - 1.0) - ((n - 1.0).powi(2) + 4.0 * m / kk).sqrt()) / 2.0;
+                let rhs = european_at(si) - (1.0 - carry_discount * BlackScholes::norm_cdf(-d)) * si / q1;
+                if ((k - si) - rhs).abs() / k < CRITICAL_TOLERANCE {
+                    return Ok(si);
+                }
+                let slope = -carry_discount * BlackScholes::norm_cdf(-d) * (1.0 - 1.0 / q1)
+                    - (1.0 + carry_discount * BlackScholes::norm_pdf(-d) / (sigma * sqrt_t)) / q1;
+                si = (k - rhs + slope * si) / (1.0 + slope);
+            }
+        }
+        if !si.is_finite() || si <= 0.0 {
+            break;
+        }
+    }
+    Err(BlackScholesError::no_convergence("Critical exercise price did not converge"))
+}
+
+/// Engine used to value the American option in a premium decomposition
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AmericanEngine {
+    /// Cox-Ross-Rubinstein tree with the given number of steps
+    Tree(usize),
+    /// Crank-Nicolson PDE with PSOR on the given (spot nodes, time steps) grid
+    Pde(usize, usize),
+    /// Barone-Adesi-Whaley quadratic approximation
+    BaroneAdesiWhaley,
+}
+
+impl AmericanEngine {
+    /// (American, European) values from the same engine
+    fn values(&self, model: &BlackScholes, option_type: OptionType) -> Result<(f64, f64), BlackScholesError> {
+        match *self {
+            AmericanEngine::Tree(steps) => {
+                let tree = BinomialTree::new(steps)?;
+                Ok((
+                    tree.price(model, option_type, Exercise::American)?.price,
+                    tree.price(model, option_type, Exercise::European)?.price,
+                ))
+            }
+            AmericanEngine::Pde(grid_points, time_steps) => {
+                let fd = FiniteDifference::new(grid_points, time_steps, Scheme::CrankNicolson)?;
+                Ok((
+                    fd.solve(model, option_type, Exercise::American, &[])?.price,
+                    fd.solve(model, option_type, Exercise::European, &[])?.price,
+                ))
+            }
+            AmericanEngine::BaroneAdesiWhaley => Ok((barone_adesi_whaley(model, option_type)?, model.price(option_type))),
+        }
+    }
+}
+
+/// American option value split into European value and early-exercise premium
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PremiumDecomposition {
+    /// Analytic Black-Scholes European value
+    pub european: f64,
+    /// Early-exercise premium (engine American minus engine European)
+    pub early_exercise_premium: f64,
+    /// `european + early_exercise_premium`
+    pub american: f64,
+    /// Engine European value minus analytic value (discretization error)
+    pub engine_bias: f64,
+    /// For calls, the part of the premium caused by the dividend yield
+    ///
+    /// Measured as the premium minus the premium of the same call with the
+    /// yield set to zero; `None` for puts.
+    pub dividend_component: Option<f64>,
+    /// Immediate exercise value max(±(S - K), 0)
+    pub intrinsic: f64,
+}
+
+impl PremiumDecomposition {
+    /// Early-exercise premium as a fraction of the American value
+    pub fn premium_share(&self) -> f64 {
+        if self.american > 0.0 {
+            self.early_exercise_premium / self.american
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Decompose an American option into European value plus early-exercise premium
+///
+/// The premium is the difference of the engine's American and European
+/// values, so discretization error largely cancels; it is then added to
+/// the analytic European price.
+pub fn decompose(
+    model: &BlackScholes,
+    option_type: OptionType,
+    engine: AmericanEngine,
+) -> Result<PremiumDecomposition, BlackScholesError> {
+    let european = model.price(option_type);
+    let (american_engine, european_engine) = engine.values(model, option_type)?;
+    let premium = (american_engine - european_engine).max(0.0);
+
+    let dividend_component = match option_type {
+        OptionType::Call => {
+            let no_yield = BlackScholes {
+                dividend_yield: 0.0,
+                ..*model
+            };
+            let (a, e) = engine.values(&no_yield, option_type)?;
+            Some(premium - (a - e).max(0.0))
+        }
+        OptionType::Put => None,
+    };
+    let intrinsic = match option_type {
+        OptionType::Call => (model.spot_price - model.strike_price).max(0.0),
+        OptionType::Put => (model.strike_price - model.spot_price).max(0.0),
+    };
+
+    Ok(PremiumDecomposition {
+        european,
+        early_exercise_premium: premium,
+        american: european + premium,
+        engine_bias: european_engine - european,
+        dividend_component,
+        intrinsic,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_barone_adesi_whaley_reference_values() {
+        // Haug, b = 0: K = 100, T = 0.1, r = 10%, σ = 15%
+        for (spot, expected) in [(90.0, 0.0206), (100.0, 1.8771)] {
+            let bs = BlackScholes::new(spot, 100.0, 0.1, 0.10, 0.15, 0.10).unwrap();
+            let price = barone_adesi_whaley(&bs, OptionType::Call).unwrap();
+            assert!((price - expected).abs() < 1e-3, "S={} {} vs {}", spot, price, expected);
+        }
+
+        // Just below the critical price the approximation stays close to a fine tree
+        let bs = BlackScholes::new(110.0, 100.0, 0.1, 0.10, 0.15, 0.10).unwrap();
+        let baw = barone_adesi_whaley(&bs, OptionType::Call).unwrap();
+        let tree = BinomialTree::new(2000).unwrap().price(&bs, OptionType::Call, Exercise::American).unwrap();
+        assert!(baw > 10.0 && (baw - tree.price).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_engines_agree_on_decomposition() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.25, 0.03).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let tree = decompose(&bs, option_type, AmericanEngine::Tree(1000)).unwrap();
+            let pde = decompose(&bs, option_type, AmericanEngine::Pde(301, 300)).unwrap();
+            let baw = decompose(&bs, option_type, AmericanEngine::BaroneAdesiWhaley).unwrap();
+            assert!(tree.early_exercise_premium > 0.0);
+            assert!((tree.american - pde.american).abs() < 0.01);
+            assert!((tree.american - baw.american).abs() < 0.05);
+            assert!(tree.american >= tree.intrinsic);
+        }
+    }
+
+    #[test]
+    fn test_call_premium_is_dividend_driven() {
+        let no_div = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let plain = decompose(&no_div, OptionType::Call, AmericanEngine::Tree(500)).unwrap();
+        assert!(plain.early_exercise_premium < 1e-10);
+        assert!(plain.dividend_component.unwrap().abs() < 1e-10);
+
+        let dividend = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.06).unwrap();
+        let report = decompose(&dividend, OptionType::Call, AmericanEngine::Tree(500)).unwrap();
+        assert!(report.early_exercise_premium > 0.0);
+        assert!((report.dividend_component.unwrap() - report.early_exercise_premium).abs() < 1e-10);
+        assert!(report.premium_share() > 0.0 && report.premium_share() < 1.0);
+
+        let put = decompose(&dividend, OptionType::Put, AmericanEngine::BaroneAdesiWhaley).unwrap();
+        assert!(put.dividend_component.is_none());
+    }
+}
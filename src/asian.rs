@@ -0,0 +1,256 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::error::BlackScholesError;
+use crate::monte_carlo::{uniform_times, McResult, MonteCarlo};
+use crate::validation;
+
+/// How the fixings are averaged
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AverageType {
+    Geometric,
+    Arithmetic,
+}
+
+/// Fixing schedule of the average
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Averaging {
+    /// Continuous averaging from today to expiry
+    Continuous,
+    /// Discrete fixings at the given times in years (ascending, up to expiry)
+    Discrete(Vec<f64>),
+}
+
+/// European average-rate (Asian) option paying max(φ·(A - K), 0)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AsianOption {
+    /// Underlying market parameters and strike
+    pub model: BlackScholes,
+    /// Geometric or arithmetic average
+    pub average_type: AverageType,
+    /// Continuous or discrete fixings
+    pub averaging: Averaging,
+}
+
+impl AsianOption {
+    /// Create a new Asian option
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes parameters (spot, strike, expiry, rate, vol, dividend)
+    /// * `average_type` - Geometric or arithmetic average
+    /// * `averaging` - Continuous or discrete fixing schedule
+    pub fn new(model: BlackScholes, average_type: AverageType, averaging: Averaging) -> Result<Self, BlackScholesError> {
+        if let Averaging::Discrete(times) = &averaging {
+            validation::all_finite("Fixing time", times)?;
+            if times.is_empty() {
+                return Err(BlackScholesError::invalid("Discrete averaging needs at least one fixing"));
+            }
+            if times[0] <= 0.0 || times.windows(2).any(|w| w[1] <= w[0]) {
+                return Err(BlackScholesError::invalid("Fixing times must be positive and strictly increasing"));
+            }
+            if times[times.len() - 1] > model.time_to_expiry {
+                return Err(BlackScholesError::invalid("Fixing times must not exceed expiry"));
+            }
+        }
+        Ok(AsianOption {
+            model,
+            average_type,
+            averaging,
+        })
+    }
+
+    /// Calculate option price
+    ///
+    /// Geometric averages are priced exactly (Kemna-Vorst for continuous
+    /// fixings, the lognormal average for discrete fixings); arithmetic
+    /// averages use Turnbull-Wakeman/Levy two-moment lognormal matching.
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    ///
+    /// # Returns
+    /// Option price
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        Self::price_with(&self.model, self.average_type, &self.averaging, option_type)
+    }
+
+    /// Calculate Greeks by finite differences on the analytic price
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        Greeks::from_finite_differences(&self.model, |m| {
+            Self::price_with(m, self.average_type, &self.averaging, option_type)
+        })
+    }
+
+    /// Price by Monte Carlo simulation, as a validation path for the analytic formulas
+    ///
+    /// Continuous averaging is approximated by daily fixings.
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    /// * `engine` - Monte Carlo engine settings
+    pub fn monte_carlo_price(&self, option_type: OptionType, engine: &MonteCarlo) -> Result<McResult, BlackScholesError> {
+        let m = &self.model;
+        let times = match &self.averaging {
+            Averaging::Continuous => uniform_times(m.time_to_expiry, ((m.time_to_expiry * 365.0) as usize).max(1)),
+            Averaging::Discrete(times) => times.clone(),
+        };
+        let discount = (-m.risk_free_rate * m.time_to_expiry).exp();
+        let phi = sign(option_type);
+        let strike = m.strike_price;
+        let average_type = self.average_type;
+
+        engine.price(m, &times, discount, |path| {
+            let n = path.len() as f64;
+            let average = match average_type {
+                AverageType::Arithmetic => path.iter().sum::<f64>() / n,
+                AverageType::Geometric => (path.iter().map(|s| s.ln()).sum::<f64>() / n).exp(),
+            };
+            (phi * (average - strike)).max(0.0)
+        })
+    }
+
+    fn price_with(m: &BlackScholes, average_type: AverageType, averaging: &Averaging, option_type: OptionType) -> f64 {
+        let s = m.spot_price;
+        let t = m.time_to_expiry;
+        let b = m.risk_free_rate - m.dividend_yield;
+        let v = m.calendar_volatility();
+        let discount = (-m.risk_free_rate * t).exp();
+
+        // Express every case as a lognormal average with given mean and log-variance
+        let (mean, log_variance) = match (average_type, averaging) {
+            (AverageType::Geometric, Averaging::Continuous) => {
+                let mu = s.ln() + (b - 0.5 * v * v) * t / 2.0;
+                let var = v * v * t / 3.0;
+                ((mu + 0.5 * var).exp(), var)
+            }
+            (AverageType::Geometric, Averaging::Discrete(times)) => {
+                let n = times.len() as f64;
+                let mu = s.ln() + (b - 0.5 * v * v) * times.iter().sum::<f64>() / n;
+                let var = v * v * sum_min(times) / (n * n);
+                ((mu + 0.5 * var).exp(), var)
+            }
+            (AverageType::Arithmetic, Averaging::Continuous) => {
+                let (m1, m2) = continuous_arithmetic_moments(s, b, v, t);
+                (m1, (m2 / (m1 * m1)).ln())
+            }
+            (AverageType::Arithmetic, Averaging::Discrete(times)) => {
+                let n = times.len() as f64;
+                let m1 = times.iter().map(|ti| s * (b * ti).exp()).sum::<f64>() / n;
+                let mut m2 = 0.0;
+                for ti in times {
+                    for tj in times {
+                        m2 += (b * (ti + tj) + v * v * ti.min(*tj)).exp();
+                    }
+                }
+                m2 *= s * s / (n * n);
+                (m1, (m2 / (m1 * m1)).ln())
+            }
+        };
+
+        black(mean, m.strike_price, log_variance, discount, option_type)
+    }
+}
+
+/// First two moments of the continuous arithmetic average (Turnbull-Wakeman)
+fn continuous_arithmetic_moments(s: f64, b: f64, v: f64, t: f64) -> (f64, f64) {
+    let v2 = v * v;
+    if b.abs() < 1e-8 {
+        let m2 = 2.0 * s * s * ((v2 * t).exp() - 1.0 - v2 * t) / (v2 * v2 * t * t);
+        return (s, m2);
+    }
+    let m1 = s * ((b * t).exp() - 1.0) / (b * t);
+    let m2 = 2.0 * s * s * ((2.0 * b + v2) * t).exp() / ((b + v2) * (2.0 * b + v2) * t * t)
+        + 2.0 * s * s / (b * t * t) * (1.0 / (2.0 * b + v2) - (b * t).exp() / (b + v2));
+    (m1, m2)
+}
+
+/// Σi Σj min(ti, tj) over a fixing schedule
+fn sum_min(times: &[f64]) -> f64 {
+    times
+        .iter()
+        .map(|ti| times.iter().map(|tj| ti.min(*tj)).sum::<f64>())
+        .sum()
+}
+
+/// Black formula on a lognormal quantity with given mean and log-variance
+fn black(mean: f64, strike: f64, log_variance: f64, discount: f64, option_type: OptionType) -> f64 {
+    let sd = log_variance.max(0.0).sqrt();
+    if sd < 1e-12 {
+        return discount * (sign(option_type) * (mean - strike)).max(0.0);
+    }
+    let d1 = ((mean / strike).ln() + 0.5 * log_variance) / sd;
+    let d2 = d1 - sd;
+    match option_type {
+        OptionType::Call => discount * (mean * BlackScholes::norm_cdf(d1) - strike * BlackScholes::norm_cdf(d2)),
+        OptionType::Put => discount * (strike * BlackScholes::norm_cdf(-d2) - mean * BlackScholes::norm_cdf(-d1)),
+    }
+}
+
+fn sign(option_type: OptionType) -> f64 {
+    match option_type {
+        OptionType::Call => 1.0,
+        OptionType::Put => -1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> BlackScholes {
+        BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.25, 0.01).unwrap()
+    }
+
+    fn monthly() -> Averaging {
+        Averaging::Discrete((1..=12).map(|i| i as f64 / 12.0).collect())
+    }
+
+    #[test]
+    fn test_geometric_matches_monte_carlo() {
+        let engine = MonteCarlo::new(60_000, 11).unwrap();
+        for averaging in [monthly(), Averaging::Continuous] {
+            let asian = AsianOption::new(model(), AverageType::Geometric, averaging).unwrap();
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let mc = asian.monte_carlo_price(option_type, &engine).unwrap();
+                let analytic = asian.price(option_type);
+                assert!((mc.price - analytic).abs() < 4.0 * mc.std_error + 0.02, "{} vs {}", analytic, mc.price);
+            }
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_approximation_close_to_monte_carlo() {
+        let engine = MonteCarlo::new(60_000, 5).unwrap();
+        let asian = AsianOption::new(model(), AverageType::Arithmetic, monthly()).unwrap();
+        let mc = asian.monte_carlo_price(OptionType::Call, &engine).unwrap();
+        assert!((mc.price - asian.price(OptionType::Call)).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_average_ordering() {
+        // Geometric ≤ arithmetic average, and averaging reduces option value
+        let geometric = AsianOption::new(model(), AverageType::Geometric, Averaging::Continuous).unwrap();
+        let arithmetic = AsianOption::new(model(), AverageType::Arithmetic, Averaging::Continuous).unwrap();
+        let call_geo = geometric.price(OptionType::Call);
+        let call_arith = arithmetic.price(OptionType::Call);
+        assert!(call_geo < call_arith);
+        assert!(call_arith < model().price(OptionType::Call));
+    }
+
+    #[test]
+    fn test_single_fixing_at_expiry_is_vanilla() {
+        let asian = AsianOption::new(model(), AverageType::Arithmetic, Averaging::Discrete(vec![1.0])).unwrap();
+        assert!((asian.price(OptionType::Put) - model().price(OptionType::Put)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        assert!(AsianOption::new(model(), AverageType::Geometric, Averaging::Discrete(vec![])).is_err());
+        assert!(AsianOption::new(model(), AverageType::Geometric, Averaging::Discrete(vec![0.5, 0.4])).is_err());
+        assert!(AsianOption::new(model(), AverageType::Geometric, Averaging::Discrete(vec![1.5])).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! Worst-of autocallable notes on correlated underlyings
+
+use crate::error::BlackScholesError;
+use crate::monte_carlo::{validate_times, MonteCarlo};
+use crate::multi_asset::CorrelatedPaths;
+use crate::rate_notes::NoteValuation;
+use crate::validation;
+
+/// Worst-of autocallable note with optional memory coupons
+///
+/// On each observation date the worst performance is the lowest
+/// S_i(t)/S_i(0) across the underlyings. At or above `coupon_barrier` it
+/// pays `coupon`, plus every coupon missed since the last one paid when the
+/// note has memory. Before maturity, at or above `autocall_barrier` the note
+/// redeems at par. At maturity it repays par unless the worst performance is
+/// below `protection_barrier`, when it repays notional times that
+/// performance. All amounts are paid on their observation date.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorstOfAutocallable {
+    /// Strictly increasing observation times in years; the last is maturity
+    pub observation_times: Vec<f64>,
+    /// Coupon per observation as a fraction of notional
+    pub coupon: f64,
+    /// Worst performance needed for a coupon
+    pub coupon_barrier: f64,
+    /// Worst performance that redeems the note early
+    pub autocall_barrier: f64,
+    /// Worst performance at maturity below which capital is lost
+    pub protection_barrier: f64,
+    /// Whether missed coupons are recovered at the next coupon paid
+    pub memory: bool,
+    pub notional: f64,
+}
+
+impl WorstOfAutocallable {
+    pub fn new(
+        observation_times: Vec<f64>,
+        coupon: f64,
+        coupon_barrier: f64,
+        autocall_barrier: f64,
+        protection_barrier: f64,
+        notional: f64,
+    ) -> Result<Self, BlackScholesError> {
+        validate_times(&observation_times)?;
+        Ok(WorstOfAutocallable {
+            observation_times,
+            coupon: validation::non_negative("Coupon", coupon)?,
+            coupon_barrier: validation::positive("Coupon barrier", coupon_barrier)?,
+            autocall_barrier: validation::positive("Autocall barrier", autocall_barrier)?,
+            protection_barrier: validation::positive("Protection barrier", protection_barrier)?,
+            memory: false,
+            notional: validation::positive("Notional", notional)?,
+        })
+    }
+
+    pub fn with_memory(mut self) -> Self {
+        self.memory = true;
+        self
+    }
+
+    /// Value to the holder on paths from `model`, discounted at its rate
+    pub fn price(&self, model: &CorrelatedPaths, mc: &MonteCarlo) -> Result<NoteValuation, BlackScholesError> {
+        let times = &self.observation_times;
+        let last = times.len() - 1;
+        let discounts: Vec<f64> = times.iter().map(|t| (-model.rate * t).exp()).collect();
+        let mut paths = vec![vec![0.0; times.len()]; model.assets.len()];
+        let (mut called, mut life) = (0usize, 0.0);
+        let value = mc.estimate(self.notional, |rng| {
+            model.simulate(times, rng, &mut paths);
+            let (mut value, mut missed) = (0.0, 0.0);
+            for (k, (&t, &df)) in times.iter().zip(&discounts).enumerate() {
+                let worst = paths
+                    .iter()
+                    .zip(&model.assets)
+                    .map(|(path, asset)| path[k] / asset.spot)
+                    .fold(f64::INFINITY, f64::min);
+                if worst >= self.coupon_barrier {
+                    value += df * self.coupon * (1.0 + missed);
+                    missed = 0.0;
+                } else if self.memory {
+                    missed += 1.0;
+                }
+                if k < last && worst >= self.autocall_barrier {
+                    called += 1;
+                    life += t;
+                    return value + df;
+                }
+                if k == last {
+                    life += t;
+                    let redemption = if worst >= self.protection_barrier { 1.0 } else { worst };
+                    value += df * redemption;
+                }
+            }
+            value
+        });
+        Ok(NoteValuation {
+            value,
+            knock_out_probability: called as f64 / mc.paths as f64,
+            expected_life: life / mc.paths as f64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::{BlackScholes, OptionType};
+    use crate::heston::HestonParams;
+    use crate::multi_asset::{Asset, LeverageSurface};
+
+    fn quarterly() -> Vec<f64> {
+        (1..=12).map(|q| q as f64 * 0.25).collect()
+    }
+
+    #[test]
+    fn test_limits_match_bond_and_short_put() {
+        let model = CorrelatedPaths::single(0.03, Asset::lognormal(100.0, 0.01, 0.25).unwrap()).unwrap();
+        let mc = MonteCarlo::new(50_000, 2).unwrap();
+
+        // Barriers never or always hit: a fixed-coupon bond
+        let bond = WorstOfAutocallable::new(quarterly(), 0.02, 1e-9, 1e9, 1e-9, 100.0).unwrap();
+        let valuation = bond.price(&model, &mc).unwrap();
+        let exact: f64 = quarterly().iter().map(|t| 2.0 * (-0.03 * t).exp()).sum::<f64>() + 100.0 * (-0.09_f64).exp();
+        assert!((valuation.value.price - exact).abs() < 1e-9);
+        assert_eq!(valuation.knock_out_probability, 0.0);
+        assert!((valuation.expected_life - 3.0).abs() < 1e-12);
+
+        // No coupon and protection at the strike: par less an at-the-money put
+        let capital_at_risk = WorstOfAutocallable::new(quarterly(), 0.0, 1.0, 1e9, 1.0, 100.0).unwrap();
+        let valuation = capital_at_risk.price(&model, &mc).unwrap();
+        let put = BlackScholes::new(100.0, 100.0, 3.0, 0.03, 0.25, 0.01).unwrap().price(OptionType::Put);
+        let exact = 100.0 * (-0.09_f64).exp() - put;
+        assert!((valuation.value.price - exact).abs() < 4.0 * valuation.value.std_error);
+    }
+
+    #[test]
+    fn test_memory_and_dispersion_on_stochastic_volatility_basket() {
+        let params = HestonParams::new(0.04, 1.5, 0.04, 0.4, -0.6).unwrap();
+        let skew = LeverageSurface::new(vec![0.0], vec![0.7, 1.0, 1.3], vec![vec![1.3, 1.0, 0.85]]).unwrap();
+        let basket = |rho: f64| {
+            let assets = vec![
+                Asset::heston(100.0, 0.02, params).unwrap(),
+                Asset::local_stochastic(50.0, 0.01, params, skew.clone()).unwrap(),
+                Asset::lognormal(20.0, 0.0, 0.3).unwrap(),
+            ];
+            let correlation = vec![vec![1.0, rho, rho], vec![rho, 1.0, rho], vec![rho, rho, 1.0]];
+            CorrelatedPaths::new(0.03, assets, correlation).unwrap().with_steps_per_year(24).unwrap()
+        };
+        let mc = MonteCarlo::new(10_000, 6).unwrap();
+        let note = WorstOfAutocallable::new(quarterly(), 0.025, 0.7, 1.0, 0.6, 100.0).unwrap();
+
+        let plain = note.price(&basket(0.6), &mc).unwrap();
+        let memory = note.clone().with_memory().price(&basket(0.6), &mc).unwrap();
+        assert!(memory.value.price > plain.value.price);
+        assert_eq!(memory.knock_out_probability, plain.knock_out_probability);
+        assert!(plain.knock_out_probability > 0.2 && plain.knock_out_probability < 0.9, "{plain:?}");
+        assert!(plain.expected_life < 3.0);
+
+        // Less correlated underlyings make the worst performer worse
+        let dispersed = note.price(&basket(0.1), &mc).unwrap();
+        assert!(dispersed.value.price < plain.value.price);
+        assert!(dispersed.knock_out_probability < plain.knock_out_probability);
+    }
+
+    #[test]
+    fn test_invalid_notes() {
+        assert!(WorstOfAutocallable::new(vec![], 0.02, 0.7, 1.0, 0.6, 100.0).is_err());
+        assert!(WorstOfAutocallable::new(vec![1.0, 0.5], 0.02, 0.7, 1.0, 0.6, 100.0).is_err());
+        assert!(WorstOfAutocallable::new(vec![1.0], -0.02, 0.7, 1.0, 0.6, 100.0).is_err());
+        assert!(WorstOfAutocallable::new(vec![1.0], 0.02, 0.7, 0.0, 0.6, 100.0).is_err());
+    }
+}
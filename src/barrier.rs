@@ -0,0 +1,253 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Barrier direction and knock behaviour
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BarrierType {
+    DownAndIn,
+    DownAndOut,
+    UpAndIn,
+    UpAndOut,
+}
+
+impl BarrierType {
+    fn is_down(&self) -> bool {
+        matches!(self, BarrierType::DownAndIn | BarrierType::DownAndOut)
+    }
+
+    fn is_knock_in(&self) -> bool {
+        matches!(self, BarrierType::DownAndIn | BarrierType::UpAndIn)
+    }
+}
+
+/// Continuously monitored single-barrier option (Reiner-Rubinstein, 1991)
+///
+/// Knock-in options pay the rebate at expiry if the barrier was never hit;
+/// knock-out options pay the rebate immediately when the barrier is hit.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BarrierOption {
+    /// Underlying market parameters and strike
+    pub model: BlackScholes,
+    /// Barrier direction and knock behaviour
+    pub barrier_type: BarrierType,
+    /// Barrier level (H)
+    pub barrier: f64,
+    /// Cash rebate (K)
+    pub rebate: f64,
+}
+
+impl BarrierOption {
+    /// Create a new barrier option
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes parameters (spot, strike, expiry, rate, vol, dividend)
+    /// * `barrier_type` - Up/down and in/out behaviour
+    /// * `barrier` - Barrier level (H)
+    /// * `rebate` - Cash rebate (K), zero for none
+    pub fn new(
+        model: BlackScholes,
+        barrier_type: BarrierType,
+        barrier: f64,
+        rebate: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let barrier = validation::positive("Barrier", barrier)?;
+        let rebate = validation::non_negative("Rebate", rebate)?;
+
+        Ok(BarrierOption {
+            model,
+            barrier_type,
+            barrier,
+            rebate,
+        })
+    }
+
+    /// Whether the barrier is already breached at the current spot
+    pub fn is_breached(&self) -> bool {
+        if self.barrier_type.is_down() {
+            self.model.spot_price <= self.barrier
+        } else {
+            self.model.spot_price >= self.barrier
+        }
+    }
+
+    /// Calculate option price
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    ///
+    /// # Returns
+    /// Option price
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        Self::price_with(&self.model, self.barrier_type, self.barrier, self.rebate, option_type)
+    }
+
+    /// Calculate Greeks by finite differences on the closed-form price
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        Greeks::from_finite_differences(&self.model, |m| {
+            Self::price_with(m, self.barrier_type, self.barrier, self.rebate, option_type)
+        })
+    }
+
+    /// Closed-form price following Haug's formulation of Reiner-Rubinstein
+    fn price_with(
+        m: &BlackScholes,
+        barrier_type: BarrierType,
+        h: f64,
+        rebate: f64,
+        option_type: OptionType,
+    ) -> f64 {
+        let s = m.spot_price;
+        let x = m.strike_price;
+        let t = m.time_to_expiry;
+        let r = m.risk_free_rate;
+        let b = r - m.dividend_yield;
+        let v = m.calendar_volatility();
+
+        let breached = if barrier_type.is_down() { s <= h } else { s >= h };
+        if breached {
+            return if barrier_type.is_knock_in() {
+                m.price(option_type)
+            } else {
+                rebate
+            };
+        }
+
+        let n = BlackScholes::norm_cdf;
+        let v_sqrt_t = v * t.sqrt();
+        let mu = (b - 0.5 * v * v) / (v * v);
+        let lambda = (mu * mu + 2.0 * r / (v * v)).sqrt();
+
+        let x1 = (s / x).ln() / v_sqrt_t + (1.0 + mu) * v_sqrt_t;
+        let x2 = (s / h).ln() / v_sqrt_t + (1.0 + mu) * v_sqrt_t;
+        let y1 = (h * h / (s * x)).ln() / v_sqrt_t + (1.0 + mu) * v_sqrt_t;
+        let y2 = (h / s).ln() / v_sqrt_t + (1.0 + mu) * v_sqrt_t;
+        let z = (h / s).ln() / v_sqrt_t + lambda * v_sqrt_t;
+
+        let phi = match option_type {
+            OptionType::Call => 1.0,
+            OptionType::Put => -1.0,
+        };
+        let eta = if barrier_type.is_down() { 1.0 } else { -1.0 };
+
+        let carry = ((b - r) * t).exp();
+        let discount = (-r * t).exp();
+        let hs = h / s;
+
+        let a = phi * s * carry * n(phi * x1) - phi * x * discount * n(phi * x1 - phi * v_sqrt_t);
+        let bb = phi * s * carry * n(phi * x2) - phi * x * discount * n(phi * x2 - phi * v_sqrt_t);
+        let c = phi * s * carry * hs.powf(2.0 * (mu + 1.0)) * n(eta * y1)
+            - phi * x * discount * hs.powf(2.0 * mu) * n(eta * y1 - eta * v_sqrt_t);
+        let d = phi * s * carry * hs.powf(2.0 * (mu + 1.0)) * n(eta * y2)
+            - phi * x * discount * hs.powf(2.0 * mu) * n(eta * y2 - eta * v_sqrt_t);
+        let e = rebate * discount * (n(eta * x2 - eta * v_sqrt_t) - hs.powf(2.0 * mu) * n(eta * y2 - eta * v_sqrt_t));
+        let f = rebate
+            * (hs.powf(mu + lambda) * n(eta * z) + hs.powf(mu - lambda) * n(eta * z - 2.0 * eta * lambda * v_sqrt_t));
+
+        let strike_above = x > h;
+        match (barrier_type, option_type, strike_above) {
+            (BarrierType::DownAndIn, OptionType::Call, true) => c + e,
+            (BarrierType::DownAndIn, OptionType::Call, false) => a - bb + d + e,
+            (BarrierType::UpAndIn, OptionType::Call, true) => a + e,
+            (BarrierType::UpAndIn, OptionType::Call, false) => bb - c + d + e,
+            (BarrierType::DownAndIn, OptionType::Put, true) => bb - c + d + e,
+            (BarrierType::DownAndIn, OptionType::Put, false) => a + e,
+            (BarrierType::UpAndIn, OptionType::Put, true) => a - bb + d + e,
+            (BarrierType::UpAndIn, OptionType::Put, false) => c + e,
+            (BarrierType::DownAndOut, OptionType::Call, true) => a - c + f,
+            (BarrierType::DownAndOut, OptionType::Call, false) => bb - d + f,
+            (BarrierType::UpAndOut, OptionType::Call, true) => f,
+            (BarrierType::UpAndOut, OptionType::Call, false) => a - bb + c - d + f,
+            (BarrierType::DownAndOut, OptionType::Put, true) => a - bb + c - d + f,
+            (BarrierType::DownAndOut, OptionType::Put, false) => f,
+            (BarrierType::UpAndOut, OptionType::Put, true) => bb - d + f,
+            (BarrierType::UpAndOut, OptionType::Put, false) => a - c + f,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn haug_model(strike: f64) -> BlackScholes {
+        // Haug (2007), table 4-13: S = 100, T = 0.5, r = 8%, b = 4%, σ = 25%
+        BlackScholes::new(100.0, strike, 0.5, 0.08, 0.25, 0.04).unwrap()
+    }
+
+    #[test]
+    fn test_haug_reference_values() {
+        let cases = [
+            (BarrierType::DownAndOut, OptionType::Call, 90.0, 95.0, 9.0246),
+            (BarrierType::DownAndOut, OptionType::Call, 100.0, 95.0, 6.7924),
+            (BarrierType::DownAndOut, OptionType::Call, 110.0, 95.0, 4.8759),
+            (BarrierType::DownAndIn, OptionType::Call, 90.0, 95.0, 7.7627),
+            (BarrierType::DownAndIn, OptionType::Call, 100.0, 95.0, 4.0109),
+            (BarrierType::UpAndOut, OptionType::Call, 90.0, 105.0, 2.6789),
+            (BarrierType::DownAndOut, OptionType::Put, 100.0, 95.0, 2.2947),
+        ];
+        for (barrier_type, option_type, strike, barrier, expected) in cases {
+            let option = BarrierOption::new(haug_model(strike), barrier_type, barrier, 3.0).unwrap();
+            let price = option.price(option_type);
+            assert!(
+                (price - expected).abs() < 1e-3,
+                "{:?} {:?} K={} expected {} got {}",
+                barrier_type,
+                option_type,
+                strike,
+                expected,
+                price
+            );
+        }
+    }
+
+    #[test]
+    fn test_in_out_parity() {
+        let model = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.3, 0.02).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let vanilla = model.price(option_type);
+            for (knock_in, knock_out, barrier) in [
+                (BarrierType::DownAndIn, BarrierType::DownAndOut, 85.0),
+                (BarrierType::UpAndIn, BarrierType::UpAndOut, 120.0),
+            ] {
+                let i = BarrierOption::new(model, knock_in, barrier, 0.0).unwrap();
+                let o = BarrierOption::new(model, knock_out, barrier, 0.0).unwrap();
+                assert!((i.price(option_type) + o.price(option_type) - vanilla).abs() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_breached_barrier() {
+        let model = BlackScholes::new(90.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let knock_out = BarrierOption::new(model, BarrierType::DownAndOut, 95.0, 1.5).unwrap();
+        let knock_in = BarrierOption::new(model, BarrierType::DownAndIn, 95.0, 1.5).unwrap();
+
+        assert!(knock_out.is_breached());
+        assert_eq!(knock_out.price(OptionType::Call), 1.5);
+        assert_eq!(knock_in.price(OptionType::Call), model.price(OptionType::Call));
+    }
+
+    #[test]
+    fn test_barrier_greeks() {
+        let model = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let option = BarrierOption::new(model, BarrierType::DownAndOut, 90.0, 0.0).unwrap();
+        let greeks = option.greeks(OptionType::Call);
+
+        // Knocking out near spot makes the down-and-out call more sensitive to spot than the vanilla
+        assert!(greeks.delta > model.greeks(OptionType::Call).delta);
+        assert!(greeks.vega < model.greeks(OptionType::Call).vega);
+    }
+
+    #[test]
+    fn test_invalid_barrier() {
+        let model = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        assert!(BarrierOption::new(model, BarrierType::UpAndOut, -1.0, 0.0).is_err());
+        assert!(BarrierOption::new(model, BarrierType::UpAndOut, 110.0, -1.0).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//! Options on weighted baskets of lognormal assets
+
+use crate::black_scholes::OptionType;
+use crate::error::BlackScholesError;
+use crate::math::{norm_cdf, norm_pdf};
+use crate::monte_carlo::{McResult, MonteCarlo};
+use crate::multi_asset::MultiAssetMc;
+use crate::risk::correlation_factor;
+use crate::validation;
+
+/// Total volatility below which the basket is treated as deterministic
+const MIN_TOTAL_VOL: f64 = 1e-12;
+
+/// European option on Σ w_i·S_i(T) with correlated lognormal assets
+///
+/// A call pays max(B - K, 0) on the basket value B at expiry and a put
+/// max(K - B, 0). Weights are positive, so the closed-form approximations
+/// below apply; baskets with short legs go through [`monte_carlo`](Self::monte_carlo)
+/// on a [`MultiAssetMc`] directly.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BasketOption {
+    /// Units of each asset in the basket
+    pub weights: Vec<f64>,
+    pub spots: Vec<f64>,
+    /// Continuous dividend yield of each asset (annual)
+    pub dividend_yields: Vec<f64>,
+    /// Volatility of each asset (annual)
+    pub volatilities: Vec<f64>,
+    /// Correlation matrix of the assets' log-returns
+    pub correlation: Vec<Vec<f64>>,
+    pub strike: f64,
+    /// Time to expiration in years
+    pub time_to_expiry: f64,
+    /// Continuously compounded risk-free rate (annual)
+    pub risk_free_rate: f64,
+}
+
+impl BasketOption {
+    /// Create a new basket option
+    ///
+    /// # Arguments
+    /// * `weights`, `spots`, `dividend_yields`, `volatilities` - One entry per asset
+    /// * `correlation` - Correlation matrix of the assets' log-returns
+    /// * `strike` - Strike on the basket value (K)
+    /// * `time_to_expiry` - Time to expiration in years (T)
+    /// * `risk_free_rate` - Continuously compounded rate (r)
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        weights: Vec<f64>,
+        spots: Vec<f64>,
+        dividend_yields: Vec<f64>,
+        volatilities: Vec<f64>,
+        correlation: Vec<Vec<f64>>,
+        strike: f64,
+        time_to_expiry: f64,
+        risk_free_rate: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let n = weights.len();
+        if n == 0 {
+            return Err(BlackScholesError::invalid("A basket needs at least one asset"));
+        }
+        if spots.len() != n || dividend_yields.len() != n || volatilities.len() != n {
+            return Err(BlackScholesError::invalid("Weights, spots, dividend yields and volatilities must match"));
+        }
+        if correlation.len() != n || correlation.iter().any(|row| row.len() != n) {
+            return Err(BlackScholesError::invalid("Correlation must match the assets"));
+        }
+        for i in 0..n {
+            validation::positive("Basket weight", weights[i])?;
+            validation::positive("Spot price", spots[i])?;
+            validation::finite("Dividend yield", dividend_yields[i])?;
+            validation::positive("Volatility", volatilities[i])?;
+        }
+        correlation_factor(&correlation)?;
+        Ok(BasketOption {
+            weights,
+            spots,
+            dividend_yields,
+            volatilities,
+            correlation,
+            strike: validation::positive("Strike price", strike)?,
+            time_to_expiry: validation::positive("Time to expiry", time_to_expiry)?,
+            risk_free_rate: validation::finite("Risk-free rate", risk_free_rate)?,
+        })
+    }
+
+    fn discount(&self) -> f64 {
+        (-self.risk_free_rate * self.time_to_expiry).exp()
+    }
+
+    /// Weighted forward of each asset, w_i·F_i
+    fn weighted_forwards(&self) -> Vec<f64> {
+        (0..self.weights.len())
+            .map(|i| {
+                let carry = (self.risk_free_rate - self.dividend_yields[i]) * self.time_to_expiry;
+                self.weights[i] * self.spots[i] * carry.exp()
+            })
+            .collect()
+    }
+
+    /// Covariance of the log-returns to expiry, ρ_ij·σ_i·σ_j·T
+    fn covariance(&self) -> Vec<Vec<f64>> {
+        let (vols, t) = (&self.volatilities, self.time_to_expiry);
+        self.correlation
+            .iter()
+            .enumerate()
+            .map(|(i, row)| row.iter().enumerate().map(|(j, rho)| rho * vols[i] * vols[j] * t).collect())
+            .collect()
+    }
+
+    /// Forward value of the basket
+    pub fn forward(&self) -> f64 {
+        self.weighted_forwards().iter().sum()
+    }
+
+    /// Put from call by parity: C - P = e^(-rT)·(F_B - K)
+    fn put_call_parity(&self, call: f64, option_type: OptionType) -> f64 {
+        match option_type {
+            OptionType::Call => call,
+            OptionType::Put => call - self.discount() * (self.forward() - self.strike),
+        }
+    }
+
+    /// Gentle's (1993) geometric approximation
+    ///
+    /// Replaces the basket by the geometric average of the assets with
+    /// forward-value weights a_i = w_i·F_i/F_B, which is lognormal, and
+    /// lowers the strike by the gap between the arithmetic and geometric
+    /// forwards. Quick and accurate for similar, highly correlated assets;
+    /// it misses the skew of dispersed baskets.
+    pub fn gentle(&self, option_type: OptionType) -> f64 {
+        let forwards = self.weighted_forwards();
+        let cov = self.covariance();
+        let basket: f64 = forwards.iter().sum();
+        let a: Vec<f64> = forwards.iter().map(|f| f / basket).collect();
+        let variance: f64 = (0..a.len()).map(|i| a[i] * (0..a.len()).map(|j| a[j] * cov[i][j]).sum::<f64>()).sum();
+        let own: f64 = (0..a.len()).map(|i| a[i] * cov[i][i]).sum();
+        let geometric = basket * (0.5 * (variance - own)).exp();
+        let strike = self.strike - (basket - geometric);
+        let call = if strike <= 0.0 || variance < MIN_TOTAL_VOL * MIN_TOTAL_VOL {
+            (geometric - strike).max(0.0)
+        } else {
+            lognormal_call(geometric, strike, variance)
+        };
+        self.put_call_parity(self.discount() * call, option_type)
+    }
+
+    /// Ju's (2002) Taylor-expansion approximation
+    ///
+    /// Starts from the lognormal matching the basket's first two moments and
+    /// corrects it with the third and fourth cumulants of the log-basket,
+    /// expanded to third order in the covariance. With weights a_i = w_i·F_i/F_B
+    /// and covariance c, the moments are E[(B/F_B)^s] = Σ a_i₁…a_iₛ·exp(Σ_{k<l} c_{i_k i_l});
+    /// the log of that sum, expanded in c, is a polynomial in s whose
+    /// coefficients give the cumulants. Much closer than Gentle for dispersed
+    /// baskets and long expiries.
+    pub fn ju(&self, option_type: OptionType) -> f64 {
+        let forwards = self.weighted_forwards();
+        let cov = self.covariance();
+        let basket: f64 = forwards.iter().sum();
+        let a: Vec<f64> = forwards.iter().map(|f| f / basket).collect();
+        let n = a.len();
+        let second: f64 = (0..n).map(|i| a[i] * (0..n).map(|j| a[j] * cov[i][j].exp()).sum::<f64>()).sum();
+        let variance = second.ln();
+        let discount = self.discount();
+        if variance < MIN_TOTAL_VOL * MIN_TOTAL_VOL {
+            return self.put_call_parity(discount * (basket - self.strike).max(0.0), option_type);
+        }
+
+        // Pair terms c_ij drawn with probabilities a_i·a_j, centred on their mean
+        let mean: f64 = (0..n).map(|i| a[i] * (0..n).map(|j| a[j] * cov[i][j]).sum::<f64>()).sum();
+        let d: Vec<Vec<f64>> = cov.iter().map(|row| row.iter().map(|c| c - mean).collect()).collect();
+        let g: Vec<f64> = d.iter().map(|row| row.iter().zip(&a).map(|(d, a)| a * d).sum()).collect();
+        let h: Vec<f64> = d.iter().map(|row| row.iter().zip(&a).map(|(d, a)| a * d * d).sum()).collect();
+        let expect = |f: &dyn Fn(usize) -> f64| (0..n).map(|i| a[i] * f(i)).sum::<f64>();
+        let tau = expect(&|i| g[i] * g[i]);
+        let shared = expect(&|i| h[i] * g[i]);
+        let star = expect(&|i| g[i].powi(3));
+        let path = expect(&|i| g[i] * (0..n).map(|j| a[j] * d[i][j] * g[j]).sum::<f64>());
+        let triangle = expect(&|i| (0..n).map(|j| a[j] * d[i][j] * expect(&|k| d[j][k] * d[i][k])).sum::<f64>());
+
+        // κ3/6 and κ4/24 of ln(B/F_B), from the s³ and s⁴ coefficients
+        let quartic = (star + 3.0 * path) / 6.0;
+        let cubic = 0.5 * tau + (3.0 * shared + triangle) / 6.0 - 6.0 * quartic;
+        let alpha = cubic + 3.0 * quartic;
+        let beta = quartic;
+
+        // Density of the moment-matched log-basket at the log-strike, and its derivatives
+        let x = (self.strike / basket).ln() + 0.5 * variance;
+        let p = norm_pdf(x / variance.sqrt()) / variance.sqrt();
+        let dp = -x / variance * p;
+        let d2p = (x * x / (variance * variance) - 1.0 / variance) * p;
+        let correction = self.strike * (-2.0 * alpha * p + (2.0 * beta - alpha) * dp + beta * d2p);
+        let call = lognormal_call(basket, self.strike, variance) + correction;
+        self.put_call_parity(discount * call, option_type)
+    }
+
+    /// Monte Carlo price on exact correlated lognormal paths, as a cross-check
+    pub fn monte_carlo(&self, option_type: OptionType, mc: MonteCarlo) -> Result<McResult, BlackScholesError> {
+        let engine = MultiAssetMc::gbm(
+            self.risk_free_rate,
+            &self.spots,
+            &self.dividend_yields,
+            &self.volatilities,
+            self.correlation.clone(),
+            mc,
+        )?;
+        engine.price(&[self.time_to_expiry], self.discount(), |paths| {
+            let value: f64 = paths.iter().zip(&self.weights).map(|(p, w)| w * p[0]).sum();
+            match option_type {
+                OptionType::Call => (value - self.strike).max(0.0),
+                OptionType::Put => (self.strike - value).max(0.0),
+            }
+        })
+    }
+}
+
+/// Undiscounted call on a lognormal with the given forward and total log-variance
+fn lognormal_call(forward: f64, strike: f64, variance: f64) -> f64 {
+    let s = variance.sqrt();
+    let d1 = ((forward / strike).ln() + 0.5 * variance) / s;
+    forward * norm_cdf(d1) - strike * norm_cdf(d1 - s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+
+    fn correlation(rho: f64, n: usize) -> Vec<Vec<f64>> {
+        (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { rho }).collect()).collect()
+    }
+
+    #[test]
+    fn test_single_asset_is_black_scholes() {
+        let basket =
+            BasketOption::new(vec![2.0], vec![50.0], vec![0.01], vec![0.3], vec![vec![1.0]], 105.0, 1.5, 0.03).unwrap();
+        let bs = BlackScholes::new(100.0, 105.0, 1.5, 0.03, 0.3, 0.01).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            assert!((basket.gentle(option_type) - bs.price(option_type)).abs() < 1e-10);
+            assert!((basket.ju(option_type) - bs.price(option_type)).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_ju_tracks_monte_carlo_on_dispersed_baskets() {
+        let mc = MonteCarlo::new(400_000, 17).unwrap();
+        for (rho, vols, strike) in [
+            (0.5, vec![0.2, 0.3, 0.4, 0.25], 100.0),
+            (0.1, vec![0.5, 0.2, 0.35, 0.3], 90.0),
+            (0.8, vec![0.3, 0.3, 0.3, 0.3], 115.0),
+        ] {
+            let basket = BasketOption::new(
+                vec![0.25, 0.5, 0.25, 1.0],
+                vec![100.0, 40.0, 120.0, 25.0],
+                vec![0.0, 0.02, 0.01, 0.0],
+                vols,
+                correlation(rho, 4),
+                strike,
+                3.0,
+                0.03,
+            )
+            .unwrap();
+            let call = basket.monte_carlo(OptionType::Call, mc).unwrap();
+            let (ju, gentle) = (basket.ju(OptionType::Call), basket.gentle(OptionType::Call));
+            assert!((ju - call.price).abs() < 4.0 * call.std_error + 0.002 * call.price, "{ju} vs {call:?}");
+            assert!((gentle - call.price).abs() < 0.1 * call.price, "{gentle} vs {call:?}");
+            assert!((ju - call.price).abs() < (gentle - call.price).abs() + 4.0 * call.std_error);
+            let put = basket.monte_carlo(OptionType::Put, mc).unwrap();
+            assert!((basket.ju(OptionType::Put) - put.price).abs() < 4.0 * put.std_error + 0.002 * put.price);
+        }
+    }
+
+    #[test]
+    fn test_invalid_baskets() {
+        let ok = |weights: Vec<f64>, correlation: Vec<Vec<f64>>| {
+            BasketOption::new(weights, vec![100.0; 2], vec![0.0; 2], vec![0.2; 2], correlation, 100.0, 1.0, 0.03)
+        };
+        assert!(ok(vec![0.5, 0.5], correlation(0.3, 2)).is_ok());
+        assert!(ok(vec![1.0, -1.0], correlation(0.3, 2)).is_err());
+        assert!(ok(vec![0.5, 0.5], correlation(1.5, 2)).is_err());
+        assert!(ok(vec![0.5], correlation(0.3, 2)).is_err());
+        assert!(ok(vec![0.5, 0.5], correlation(0.3, 3)).is_err());
+    }
+}
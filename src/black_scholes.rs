@@ -1,7 +1,20 @@
-use std::f64::consts::{PI, SQRT_2};
+use crate::cross_greeks::CrossGreeks;
+use crate::curves::{DiscountCurve, ForwardCurve};
+use crate::error::BlackScholesError;
+use crate::math::{distributions, kernel};
+use crate::math::dual::{Dual, Real};
+use crate::time_scale::{DaySchedule, TimeScale};
+use crate::validation;
+
+/// Options priced per pass of the vectorized kernel in `price_batch`
+const BATCH_CHUNK: usize = 256;
+
+/// Remaining life at which an option is treated as expired
+pub(crate) const EXPIRY_EPSILON: f64 = 1e-10;
 
 /// Type of option: Call or Put
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OptionType {
     Call,
     Put,
@@ -9,6 +22,7 @@ pub enum OptionType {
 
 /// Greeks for option sensitivity analysis
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Greeks {
     pub delta: f64,
     pub gamma: f64,
@@ -17,14 +31,250 @@ pub struct Greeks {
     pub rho: f64,
 }
 
+impl Greeks {
+    /// Estimate Greeks of an arbitrary pricer by central finite differences
+    ///
+    /// Bumps spot, volatility, time and rate on the given model and reprices
+    /// with `pricer`. Results follow the same conventions as the analytic
+    /// Greeks: vega and rho per 1% move, theta per day of the model's time scale.
+    ///
+    /// # Arguments
+    /// * `model` - Market parameters to bump
+    /// * `pricer` - Function pricing the instrument under a given model
+    pub fn from_finite_differences<F: Fn(&BlackScholes) -> f64>(model: &BlackScholes, pricer: F) -> Greeks {
+        let base = pricer(model);
+        let bumped = |f: &dyn Fn(&mut BlackScholes)| {
+            let mut m = *model;
+            f(&mut m);
+            pricer(&m)
+        };
+
+        let ds = 1e-3 * model.spot_price;
+        let up = bumped(&|m| m.spot_price += ds);
+        let down = bumped(&|m| m.spot_price -= ds);
+        let delta = (up - down) / (2.0 * ds);
+        let gamma = (up - 2.0 * base + down) / (ds * ds);
+
+        let dv = 1e-4;
+        let vega = (bumped(&|m| m.volatility += dv) - bumped(&|m| m.volatility -= dv)) / (2.0 * dv) / 100.0;
+
+        // Both clocks advance together when a day passes
+        let dt = (1e-4_f64).min(0.5 * model.time_to_expiry.min(model.vol_time));
+        let later = bumped(&|m| {
+            m.time_to_expiry += dt;
+            m.vol_time += dt;
+        });
+        let sooner = bumped(&|m| {
+            m.time_to_expiry -= dt;
+            m.vol_time -= dt;
+        });
+        let theta = -(later - sooner) / (2.0 * dt) / model.time_scale.days_per_year();
+
+        let dr = 1e-4;
+        let rho = (bumped(&|m| m.risk_free_rate += dr) - bumped(&|m| m.risk_free_rate -= dr)) / (2.0 * dr) / 100.0;
+
+        Greeks {
+            delta,
+            gamma,
+            vega,
+            theta,
+            rho,
+        }
+    }
+
+    /// Realized volatility at which a delta-hedged position breaks even today
+    ///
+    /// Solves θ + ½·Γ·S²·σ²/days = 0, the balance of gamma gains against time
+    /// decay. Fails when gamma and theta do not offset, e.g. a deep in-the-money
+    /// put whose theta is positive.
+    ///
+    /// # Arguments
+    /// * `spot` - Underlying price the Greeks were computed at
+    /// * `days_per_year` - Days per year of the clock theta is quoted on
+    pub fn breakeven_volatility(&self, spot: f64, days_per_year: f64) -> Result<f64, BlackScholesError> {
+        let variance = -2.0 * self.theta * days_per_year / (self.gamma * spot * spot);
+        if !variance.is_finite() || variance <= 0.0 {
+            return Err(BlackScholesError::invalid("Gamma and theta do not offset; no breakeven volatility"));
+        }
+        Ok(variance.sqrt())
+    }
+
+    /// Realized volatility at which a delta-hedged position breaks even over `horizon`
+    ///
+    /// Accumulates theta and dollar gamma day by day with spot held at its
+    /// current level while expiry shrinks, so the answer weights each day
+    /// by the gamma it carries.
+    ///
+    /// # Arguments
+    /// * `spot` - Current underlying price
+    /// * `days_per_year` - Days per year of the clock theta is quoted on
+    /// * `horizon` - Holding period in years
+    /// * `greeks_at` - Greeks of the position after a given number of years
+    pub fn breakeven_volatility_over<F: Fn(f64) -> Greeks>(
+        spot: f64,
+        days_per_year: f64,
+        horizon: f64,
+        greeks_at: F,
+    ) -> Result<f64, BlackScholesError> {
+        let horizon = validation::positive("Horizon", horizon)?;
+        let days = (horizon * days_per_year).ceil() as usize;
+        let dt = horizon / days as f64;
+        let (mut decay, mut dollar_gamma) = (0.0, 0.0);
+        for day in 0..days {
+            let g = greeks_at(day as f64 * dt);
+            decay += g.theta;
+            dollar_gamma += 0.5 * g.gamma * spot * spot;
+        }
+        let total = Greeks {
+            delta: 0.0,
+            gamma: 2.0 * dollar_gamma / (spot * spot),
+            vega: 0.0,
+            theta: decay,
+            rho: 0.0,
+        };
+        total.breakeven_volatility(spot, days_per_year)
+    }
+
+    /// Convert to currency sensitivities of a position
+    ///
+    /// # Arguments
+    /// * `spot` - Underlying price the Greeks were computed at
+    /// * `multiplier` - Contract multiplier times the number of contracts
+    pub fn to_cash(&self, spot: f64, multiplier: f64) -> CashGreeks {
+        CashGreeks {
+            delta: self.delta * spot * multiplier,
+            gamma: self.gamma * spot * spot / 100.0 * multiplier,
+            vega: self.vega * multiplier,
+            theta: self.theta * multiplier,
+            rho: self.rho / 100.0 * multiplier,
+        }
+    }
+}
+
+/// Price and Greeks from a single evaluation
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PricingResult {
+    pub price: f64,
+    pub greeks: Greeks,
+}
+
+/// Greeks in currency, the units risk reports aggregate
+///
+/// Unlike per-unit Greeks these are additive across underlyings, so a
+/// portfolio's figures are the sum of its positions'.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CashGreeks {
+    /// Dollar delta: value change per unit relative spot move (delta × spot)
+    pub delta: f64,
+    /// Delta change over a 1% spot move, in currency (gamma × spot² / 100)
+    pub gamma: f64,
+    /// Value change per vol point
+    pub vega: f64,
+    /// Value change per day of the model's time scale
+    pub theta: f64,
+    /// Value change per basis point of rate (DV01-style)
+    pub rho: f64,
+}
+
+impl std::ops::Add for CashGreeks {
+    type Output = CashGreeks;
+
+    fn add(self, other: CashGreeks) -> CashGreeks {
+        CashGreeks {
+            delta: self.delta + other.delta,
+            gamma: self.gamma + other.gamma,
+            vega: self.vega + other.vega,
+            theta: self.theta + other.theta,
+            rho: self.rho + other.rho,
+        }
+    }
+}
+
+impl std::iter::Sum for CashGreeks {
+    fn sum<I: Iterator<Item = CashGreeks>>(iter: I) -> CashGreeks {
+        iter.fold(CashGreeks::default(), |acc, g| acc + g)
+    }
+}
+
+/// Exposure and leverage of a position relative to the capital it ties up
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Leverage {
+    /// Elasticity Ω = Δ·S / V: percentage value change for a 1% spot move
+    pub lambda: f64,
+    /// Delta-equivalent holding in the underlying, Δ·S
+    pub effective_exposure: f64,
+    /// Underlying controlled by the position's contracts, Σ|quantity|·S
+    pub notional: f64,
+    /// Notional per unit of position value (S / V for a single option)
+    pub gearing: f64,
+}
+
+impl Leverage {
+    /// Leverage figures from a position's value, delta and controlled notional
+    ///
+    /// Fails when the value is too close to zero for ratios to be meaningful,
+    /// as for a zero-cost collar.
+    pub fn from_position(value: f64, delta: f64, notional: f64, spot: f64) -> Result<Self, BlackScholesError> {
+        let value = validation::finite("Position value", value)?;
+        if value.abs() < 1e-12 * notional.abs().max(1.0) {
+            return Err(BlackScholesError::invalid("Position value is zero; leverage is undefined"));
+        }
+        let effective_exposure = delta * spot;
+        Ok(Leverage {
+            lambda: effective_exposure / value,
+            effective_exposure,
+            notional,
+            gearing: notional / value.abs(),
+        })
+    }
+}
+
+/// Black-Scholes inputs over a generic scalar
+///
+/// With `f64` this reproduces `BlackScholes::price`; with `Dual` numbers seeded
+/// on chosen inputs the price carries its exact derivatives along.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelInputs<T> {
+    pub spot_price: T,
+    pub strike_price: T,
+    pub time_to_expiry: T,
+    pub risk_free_rate: T,
+    pub volatility: T,
+    pub dividend_yield: T,
+    pub vol_time: T,
+}
+
+impl<T: Real> ModelInputs<T> {
+    /// European option price
+    pub fn price(&self, option_type: OptionType) -> T {
+        let sd = self.volatility * self.vol_time.sqrt();
+        let d1 = ((self.spot_price / self.strike_price).ln()
+            + (self.risk_free_rate - self.dividend_yield) * self.time_to_expiry
+            + sd * sd * 0.5)
+            / sd;
+        let d2 = d1 - sd;
+        let forward_value = self.spot_price * (-(self.dividend_yield * self.time_to_expiry)).exp();
+        let strike_value = self.strike_price * (-(self.risk_free_rate * self.time_to_expiry)).exp();
+        match option_type {
+            OptionType::Call => forward_value * d1.norm_cdf() - strike_value * d2.norm_cdf(),
+            OptionType::Put => strike_value * (-d2).norm_cdf() - forward_value * (-d1).norm_cdf(),
+        }
+    }
+}
+
 /// Black-Scholes Option Pricing Model
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlackScholes {
     /// Current price of the underlying asset
     pub spot_price: f64,
     /// Strike price of the option
     pub strike_price: f64,
-    /// Time to expiration in years
+    /// Time to expiration in years (calendar time, used for discounting)
     pub time_to_expiry: f64,
     /// Risk-free interest rate (annual)
     pub risk_free_rate: f64,
@@ -32,9 +282,21 @@ pub struct BlackScholes {
     pub volatility: f64,
     /// Dividend yield (annual, optional - defaults to 0)
     pub dividend_yield: f64,
+    /// Time to expiration in years on the volatility clock (defaults to `time_to_expiry`)
+    pub vol_time: f64,
+    /// Clock on which volatility accrues and theta is quoted
+    pub time_scale: TimeScale,
 }
 
 impl BlackScholes {
+    /// Start building a model with named setters
+    ///
+    /// Spot, strike, expiry, rate and volatility are required; the dividend
+    /// yield defaults to zero.
+    pub fn builder() -> BlackScholesBuilder {
+        BlackScholesBuilder::default()
+    }
+
     /// Create a new Black-Scholes model instance
     ///
     /// # Arguments
@@ -44,6 +306,9 @@ impl BlackScholes {
     /// * `risk_free_rate` - Risk-free interest rate as decimal (r)
     /// * `volatility` - Volatility of underlying as decimal (σ)
     /// * `dividend_yield` - Dividend yield as decimal (q), optional
+    ///
+    /// Inputs pass through the `validation` layer: NaN/infinite values and
+    /// non-positive spot, strike, expiry or volatility are rejected.
     pub fn new(
         spot_price: f64,
         strike_price: f64,
@@ -51,19 +316,13 @@ impl BlackScholes {
         risk_free_rate: f64,
         volatility: f64,
         dividend_yield: f64,
-    ) -> Result<Self, String> {
-        if spot_price <= 0.0 {
-            return Err("Spot price must be positive".to_string());
-        }
-        if strike_price <= 0.0 {
-            return Err("Strike price must be positive".to_string());
-        }
-        if time_to_expiry <= 0.0 {
-            return Err("Time to expiry must be positive".to_string());
-        }
-        if volatility <= 0.0 {
-            return Err("Volatility must be positive".to_string());
-        }
+    ) -> Result<Self, BlackScholesError> {
+        let spot_price = validation::positive("Spot price", spot_price)?;
+        let strike_price = validation::positive("Strike price", strike_price)?;
+        let time_to_expiry = validation::positive("Time to expiry", time_to_expiry)?;
+        let risk_free_rate = validation::finite("Risk-free rate", risk_free_rate)?;
+        let volatility = validation::positive("Volatility", volatility)?;
+        let dividend_yield = validation::finite("Dividend yield", dividend_yield)?;
 
         Ok(BlackScholes {
             spot_price,
@@ -72,51 +331,104 @@ impl BlackScholes {
             risk_free_rate,
             volatility,
             dividend_yield,
+            vol_time: time_to_expiry,
+            time_scale: TimeScale::Calendar,
+        })
+    }
+
+    /// Create a model from a discount curve and a forward curve instead of flat carry
+    ///
+    /// The curves are collapsed to the zero rate r = -ln D(T)/T and the yield
+    /// q implied by F(T) = S·e^((r - q)T), which prices European options
+    /// exactly under deterministic rates.
+    ///
+    /// # Arguments
+    /// * `strike_price` - Strike price of the option (K)
+    /// * `time_to_expiry` - Time to expiration in years (T)
+    /// * `volatility` - Volatility of underlying as decimal (σ)
+    /// * `discount` - Discount curve providing D(T)
+    /// * `forward` - Forward curve of the underlying providing S and F(T)
+    pub fn from_curves(
+        strike_price: f64,
+        time_to_expiry: f64,
+        volatility: f64,
+        discount: &dyn DiscountCurve,
+        forward: &dyn ForwardCurve,
+    ) -> Result<Self, BlackScholesError> {
+        let time_to_expiry = validation::positive("Time to expiry", time_to_expiry)?;
+        let spot = forward.spot();
+        let rate = discount.zero_rate(time_to_expiry);
+        let growth = validation::positive("Forward price", forward.forward(time_to_expiry))? / spot;
+        BlackScholes::new(spot, strike_price, time_to_expiry, rate, volatility, rate - growth.ln() / time_to_expiry)
+    }
+
+    /// Re-time the model so variance accrues on `time_scale` over `schedule`
+    ///
+    /// Discounting uses the calendar length of the schedule while d1/d2,
+    /// gamma, vega and theta use the weighted variance time.
+    ///
+    /// # Arguments
+    /// * `time_scale` - Volatility clock (calendar, trading or custom)
+    /// * `schedule` - Per-day variance weights up to expiry
+    pub fn with_time_scale(&self, time_scale: TimeScale, schedule: &DaySchedule) -> Result<Self, BlackScholesError> {
+        let time_scale = time_scale.validate()?;
+        let time_to_expiry = schedule.rate_time();
+        let vol_time = time_scale.vol_time(schedule);
+        if time_to_expiry <= 0.0 || vol_time <= 0.0 {
+            return Err(BlackScholesError::invalid("Schedule must contain variance-carrying days"));
+        }
+        Ok(BlackScholes {
+            time_to_expiry,
+            vol_time,
+            time_scale,
+            ..*self
         })
     }
 
+    /// Volatility scaled to the calendar clock, σ·√(vol_time / time_to_expiry)
+    ///
+    /// Single-clock formulas (barriers, trees) price consistently with the
+    /// configured time scale when given this volatility over `time_to_expiry`.
+    pub fn calendar_volatility(&self) -> f64 {
+        self.volatility * (self.vol_time / self.time_to_expiry).sqrt()
+    }
+
+    /// The same model with `remaining` calendar years to expiry
+    ///
+    /// The volatility clock shrinks in proportion, so business-time models
+    /// keep their variance per calendar year; held an instant before expiry
+    /// at the latest.
+    pub(crate) fn aged(&self, remaining: f64) -> BlackScholes {
+        let remaining = remaining.max(EXPIRY_EPSILON);
+        BlackScholes {
+            time_to_expiry: remaining,
+            vol_time: self.vol_time * remaining / self.time_to_expiry,
+            ..*self
+        }
+    }
+
     /// Calculate d1 parameter in Black-Scholes formula
     fn d1(&self) -> f64 {
         let numerator = (self.spot_price / self.strike_price).ln()
-            + (self.risk_free_rate - self.dividend_yield + 0.5 * self.volatility.powi(2))
-                * self.time_to_expiry;
-        let denominator = self.volatility * self.time_to_expiry.sqrt();
+            + (self.risk_free_rate - self.dividend_yield) * self.time_to_expiry
+            + 0.5 * self.volatility.powi(2) * self.vol_time;
+        let denominator = self.volatility * self.vol_time.sqrt();
         numerator / denominator
     }
 
     /// Calculate d2 parameter in Black-Scholes formula
     fn d2(&self) -> f64 {
-        self.d1() - self.volatility * self.time_to_expiry.sqrt()
+        self.d1() - self.volatility * self.vol_time.sqrt()
     }
 
     /// Standard normal cumulative distribution function (CDF)
-    /// Approximation using the error function
-    fn norm_cdf(x: f64) -> f64 {
-        0.5 * (1.0 + Self::erf(x / SQRT_2))
+    pub(crate) fn norm_cdf(x: f64) -> f64 {
+        distributions::norm_cdf(x)
     }
 
     /// Standard normal probability density function (PDF)
-    fn norm_pdf(x: f64) -> f64 {
-        (-0.5 * x.powi(2)).exp() / (2.0 * PI).sqrt()
-    }
-
-    /// Error function approximation using Abramowitz and Stegun formula
-    /// Accurate to 1.5 × 10^-7
-    fn erf(x: f64) -> f64 {
-        let a1 = 0.254829592;
-        let a2 = -0.284496736;
-        let a3 = 1.421413741;
-        let a4 = -1.453152027;
-        let a5 = 1.061405429;
-        let p = 0.3275911;
-
-        let sign = if x < 0.0 { -1.0 } else { 1.0 };
-        let x = x.abs();
-
-        let t = 1.0 / (1.0 + p * x);
-        let y = 1.0 - (((((a5 * t + a4) * t) + a3) * t + a2) * t + a1) * t * (-x * x).exp();
-
-        sign * y
+    pub(crate) fn norm_pdf(x: f64) -> f64 {
+        distributions::norm_pdf(x)
     }
 
     /// Calculate option price
@@ -142,6 +454,93 @@ impl BlackScholes {
         }
     }
 
+    /// Price many options of one type at once
+    ///
+    /// Evaluates the same formula as `price` with the vectorized math kernel,
+    /// so large chains reprice without a scalar exp/ln/erf call per option.
+    /// Results agree with `price` to about 1e-15 relative.
+    ///
+    /// # Arguments
+    /// * `models` - One model per option, each with its own inputs
+    /// * `option_type` - Type of every option (Call or Put)
+    pub fn price_batch(models: &[BlackScholes], option_type: OptionType) -> Vec<f64> {
+        let sign = match option_type {
+            OptionType::Call => 1.0,
+            OptionType::Put => -1.0,
+        };
+        let mut prices = Vec::with_capacity(models.len());
+        let mut log_moneyness = [0.0; BATCH_CHUNK];
+        let mut dividend_discount = [0.0; BATCH_CHUNK];
+        let mut discount = [0.0; BATCH_CHUNK];
+        let mut n1 = [0.0; BATCH_CHUNK];
+        let mut n2 = [0.0; BATCH_CHUNK];
+        for chunk in models.chunks(BATCH_CHUNK) {
+            let len = chunk.len();
+            for (i, m) in chunk.iter().enumerate() {
+                log_moneyness[i] = m.spot_price / m.strike_price;
+                dividend_discount[i] = -m.dividend_yield * m.time_to_expiry;
+                discount[i] = -m.risk_free_rate * m.time_to_expiry;
+            }
+            kernel::ln(&mut log_moneyness[..len]);
+            kernel::exp(&mut dividend_discount[..len]);
+            kernel::exp(&mut discount[..len]);
+            for (i, m) in chunk.iter().enumerate() {
+                let sd = m.volatility * m.vol_time.sqrt();
+                let d1 = (log_moneyness[i] + (m.risk_free_rate - m.dividend_yield) * m.time_to_expiry + 0.5 * sd * sd) / sd;
+                n1[i] = sign * d1;
+                n2[i] = sign * (d1 - sd);
+            }
+            kernel::norm_cdf(&mut n1[..len]);
+            kernel::norm_cdf(&mut n2[..len]);
+            prices.extend(chunk.iter().enumerate().map(|(i, m)| {
+                sign * (m.spot_price * dividend_discount[i] * n1[i] - m.strike_price * discount[i] * n2[i])
+            }));
+        }
+        prices
+    }
+
+    /// Model inputs lifted to a generic scalar, all held constant
+    pub fn inputs<T: Real>(&self) -> ModelInputs<T> {
+        ModelInputs {
+            spot_price: T::constant(self.spot_price),
+            strike_price: T::constant(self.strike_price),
+            time_to_expiry: T::constant(self.time_to_expiry),
+            risk_free_rate: T::constant(self.risk_free_rate),
+            volatility: T::constant(self.volatility),
+            dividend_yield: T::constant(self.dividend_yield),
+            vol_time: T::constant(self.vol_time),
+        }
+    }
+
+    /// Greeks by forward-mode automatic differentiation of the price
+    ///
+    /// One evaluation over nested dual numbers seeded on spot, volatility,
+    /// time and rate gives every Greek exactly; it matches `greeks` and serves
+    /// as the reference for formulas without closed-form sensitivities.
+    pub fn greeks_ad(&self, option_type: OptionType) -> Greeks {
+        type Ad = Dual<Dual<f64, 1>, 4>;
+        let mut inputs = self.inputs::<Ad>();
+        inputs.spot_price = Ad::variable(Dual::variable(self.spot_price, 0), 0);
+        inputs.volatility = Ad::variable(Dual::lift(self.volatility), 1);
+        // Both clocks advance together when a day passes
+        inputs.time_to_expiry = Ad::variable(Dual::lift(self.time_to_expiry), 2);
+        inputs.vol_time = Ad::variable(Dual::lift(self.vol_time), 2);
+        inputs.risk_free_rate = Ad::variable(Dual::lift(self.risk_free_rate), 3);
+        let price = inputs.price(option_type);
+        Greeks {
+            delta: price.eps[0].re,
+            gamma: price.eps[0].eps[0],
+            vega: price.eps[1].re / 100.0,
+            theta: -price.eps[2].re / self.time_scale.days_per_year(),
+            rho: price.eps[3].re / 100.0,
+        }
+    }
+
+    /// Price with the full gradient and Hessian over spot, vol, time, rate and dividend
+    pub fn cross_greeks(&self, option_type: OptionType) -> CrossGreeks {
+        CrossGreeks::new(self, option_type)
+    }
+
     /// Calculate all Greeks for the option
     ///
     /// # Arguments
@@ -150,60 +549,75 @@ impl BlackScholes {
     /// # Returns
     /// Greeks struct containing delta, gamma, vega, theta, and rho
     pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        self.evaluate(option_type).greeks
+    }
+
+    /// Price and Greeks together, sharing d1, d2, the discount factors and N(d1)/N(d2)
+    ///
+    /// Cheaper than `price` followed by `greeks`, which recompute all of them.
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    pub fn evaluate(&self, option_type: OptionType) -> PricingResult {
         let d1 = self.d1();
         let d2 = self.d2();
-        let sqrt_t = self.time_to_expiry.sqrt();
+        let sqrt_t = self.vol_time.sqrt();
+        let days_per_year = self.time_scale.days_per_year();
         let discount = (-self.risk_free_rate * self.time_to_expiry).exp();
         let dividend_discount = (-self.dividend_yield * self.time_to_expiry).exp();
+        let pdf_d1 = Self::norm_pdf(d1);
 
-        // Delta: Rate of change of option price with respect to underlying price
-        let delta = match option_type {
-            OptionType::Call => dividend_discount * Self::norm_cdf(d1),
-            OptionType::Put => -dividend_discount * Self::norm_cdf(-d1),
+        // Calls use N(d1), N(d2); puts use N(-d1), N(-d2) with the signs flipped
+        let sign = match option_type {
+            OptionType::Call => 1.0,
+            OptionType::Put => -1.0,
         };
+        let n1 = Self::norm_cdf(sign * d1);
+        let n2 = Self::norm_cdf(sign * d2);
+        let asset_leg = self.spot_price * dividend_discount * n1;
+        let cash_leg = self.strike_price * discount * n2;
 
-        // Gamma: Rate of change of delta with respect to underlying price
-        let gamma = (dividend_discount * Self::norm_pdf(d1)) 
-            / (self.spot_price * self.volatility * sqrt_t);
+        // Gamma, vega and the decay term are the same for calls and puts
+        let time_decay = -(self.spot_price * pdf_d1 * self.volatility * dividend_discount) / (2.0 * sqrt_t);
+        let theta = time_decay + sign * (self.dividend_yield * asset_leg - self.risk_free_rate * cash_leg);
 
-        // Vega: Rate of change of option price with respect to volatility (divided by 100)
-        let vega = (self.spot_price * dividend_discount * Self::norm_pdf(d1) * sqrt_t) / 100.0;
+        PricingResult {
+            price: sign * (asset_leg - cash_leg),
+            greeks: Greeks {
+                delta: sign * dividend_discount * n1,
+                gamma: (dividend_discount * pdf_d1) / (self.spot_price * self.volatility * sqrt_t),
+                vega: (self.spot_price * dividend_discount * pdf_d1 * sqrt_t) / 100.0,
+                theta: theta / days_per_year,
+                rho: sign * (self.time_to_expiry * cash_leg) / 100.0,
+            },
+        }
+    }
 
-        // Theta: Rate of change of option price with respect to time (per day)
-        let theta = match option_type {
-            OptionType::Call => {
-                let term1 = -(self.spot_price * Self::norm_pdf(d1) * self.volatility * dividend_discount)
-                    / (2.0 * sqrt_t);
-                let term2 = self.dividend_yield * self.spot_price * Self::norm_cdf(d1) * dividend_discount;
-                let term3 = self.risk_free_rate * self.strike_price * discount * Self::norm_cdf(d2);
-                (term1 - term2 + term3) / 365.0
-            }
-            OptionType::Put => {
-                let term1 = -(self.spot_price * Self::norm_pdf(d1) * self.volatility * dividend_discount)
-                    / (2.0 * sqrt_t);
-                let term2 = self.dividend_yield * self.spot_price * Self::norm_cdf(-d1) * dividend_discount;
-                let term3 = self.risk_free_rate * self.strike_price * discount * Self::norm_cdf(-d2);
-                (term1 + term2 - term3) / 365.0
-            }
-        };
+    /// Elasticity, exposure and gearing of one option
+    pub fn leverage(&self, option_type: OptionType) -> Result<Leverage, BlackScholesError> {
+        let PricingResult { price, greeks } = self.evaluate(option_type);
+        Leverage::from_position(price, greeks.delta, self.spot_price, self.spot_price)
+    }
 
-        // Rho: Rate of change of option price with respect to interest rate (divided by 100)
-        let rho = match option_type {
-            OptionType::Call => {
-                (self.strike_price * self.time_to_expiry * discount * Self::norm_cdf(d2)) / 100.0
-            }
-            OptionType::Put => {
-                -(self.strike_price * self.time_to_expiry * discount * Self::norm_cdf(-d2)) / 100.0
-            }
-        };
+    /// Realized volatility a delta-hedged option needs today to break even
+    pub fn breakeven_volatility(&self, option_type: OptionType) -> Result<f64, BlackScholesError> {
+        self.greeks(option_type).breakeven_volatility(self.spot_price, self.time_scale.days_per_year())
+    }
 
-        Greeks {
-            delta,
-            gamma,
-            vega,
-            theta,
-            rho,
+    /// Realized volatility a delta-hedged option needs over `horizon` years to break even
+    pub fn breakeven_volatility_over(&self, option_type: OptionType, horizon: f64) -> Result<f64, BlackScholesError> {
+        if horizon > self.time_to_expiry {
+            return Err(BlackScholesError::invalid("Horizon is beyond expiry"));
         }
+        let vol_per_calendar = self.vol_time / self.time_to_expiry;
+        Greeks::breakeven_volatility_over(self.spot_price, self.time_scale.days_per_year(), horizon, |t| {
+            BlackScholes {
+                time_to_expiry: self.time_to_expiry - t,
+                vol_time: (self.time_to_expiry - t) * vol_per_calendar,
+                ..*self
+            }
+            .greeks(option_type)
+        })
     }
 
     /// Calculate implied volatility using Newton-Raphson method
@@ -222,18 +636,20 @@ impl BlackScholes {
         market_price: f64,
         max_iterations: usize,
         tolerance: f64,
-    ) -> Result<f64, String> {
+    ) -> Result<f64, BlackScholesError> {
+        let market_price = validation::non_negative("Market price", market_price)?;
+        let tolerance = validation::positive("Tolerance", tolerance)?;
         let mut vol = 0.3; // Initial guess
         
         for _ in 0..max_iterations {
             let mut bs = *self;
             bs.volatility = vol;
             
-            let price = bs.price(option_type);
-            let vega = bs.greeks(option_type).vega * 100.0; // Adjust for scaling
+            let PricingResult { price, greeks } = bs.evaluate(option_type);
+            let vega = greeks.vega * 100.0; // Adjust for scaling
             
             if vega.abs() < 1e-10 {
-                return Err("Vega too small, cannot converge".to_string());
+                return Err(BlackScholesError::no_convergence("Vega too small, cannot converge"));
             }
             
             let diff = market_price - price;
@@ -250,7 +666,74 @@ impl BlackScholes {
             }
         }
         
-        Err("Failed to converge".to_string())
+        Err(BlackScholesError::no_convergence("Failed to converge"))
+    }
+}
+
+/// Fluent builder for `BlackScholes`, created with `BlackScholes::builder()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlackScholesBuilder {
+    spot: Option<f64>,
+    strike: Option<f64>,
+    expiry_years: Option<f64>,
+    rate: Option<f64>,
+    vol: Option<f64>,
+    dividend_yield: f64,
+}
+
+impl BlackScholesBuilder {
+    /// Current price of the underlying asset (S)
+    pub fn spot(mut self, spot: f64) -> Self {
+        self.spot = Some(spot);
+        self
+    }
+
+    /// Strike price of the option (K)
+    pub fn strike(mut self, strike: f64) -> Self {
+        self.strike = Some(strike);
+        self
+    }
+
+    /// Time to expiration in years (T)
+    pub fn expiry_years(mut self, expiry_years: f64) -> Self {
+        self.expiry_years = Some(expiry_years);
+        self
+    }
+
+    /// Risk-free interest rate as decimal (r)
+    pub fn rate(mut self, rate: f64) -> Self {
+        self.rate = Some(rate);
+        self
+    }
+
+    /// Volatility of underlying as decimal (σ)
+    pub fn vol(mut self, vol: f64) -> Self {
+        self.vol = Some(vol);
+        self
+    }
+
+    /// Dividend yield as decimal (q), zero if not set
+    pub fn dividend_yield(mut self, dividend_yield: f64) -> Self {
+        self.dividend_yield = dividend_yield;
+        self
+    }
+
+    /// Validate the inputs and create the model
+    ///
+    /// Fails if a required field was never set, or with the same errors as
+    /// `BlackScholes::new`.
+    pub fn build(self) -> Result<BlackScholes, BlackScholesError> {
+        let required = |value: Option<f64>, setter: &str| {
+            value.ok_or_else(|| BlackScholesError::InvalidInput(format!("Builder is missing `{}`", setter)))
+        };
+        BlackScholes::new(
+            required(self.spot, "spot")?,
+            required(self.strike, "strike")?,
+            required(self.expiry_years, "expiry_years")?,
+            required(self.rate, "rate")?,
+            required(self.vol, "vol")?,
+            self.dividend_yield,
+        )
     }
 }
 
@@ -276,6 +759,23 @@ mod tests {
         assert!((put_price - 5.57).abs() < 0.1);
     }
 
+    #[test]
+    fn test_price_batch_matches_scalar() {
+        let models: Vec<BlackScholes> = (0..257)
+            .map(|i| {
+                let i = i as f64;
+                BlackScholes::new(100.0, 40.0 + 0.5 * i, 0.02 + 0.01 * i, 0.05 - 0.0003 * i, 0.05 + 0.002 * i, 0.01).unwrap()
+            })
+            .collect();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let batch = BlackScholes::price_batch(&models, option_type);
+            for (model, price) in models.iter().zip(&batch) {
+                assert!((price - model.price(option_type)).abs() < 1e-12, "{model:?}");
+            }
+        }
+        assert!(BlackScholes::price_batch(&[], OptionType::Call).is_empty());
+    }
+
     #[test]
     fn test_put_call_parity() {
         // Put-Call Parity: C - P = S * e^(-qT) - K * e^(-rT)
@@ -313,6 +813,20 @@ mod tests {
         assert!(BlackScholes::new(100.0, 100.0, 1.0, 0.05, -0.2, 0.0).is_err());
     }
 
+    #[test]
+    fn test_non_finite_parameters() {
+        assert!(matches!(
+            BlackScholes::new(f64::NAN, 100.0, 1.0, 0.05, 0.2, 0.0),
+            Err(BlackScholesError::NotFinite { .. })
+        ));
+        assert!(BlackScholes::new(100.0, 100.0, f64::INFINITY, 0.05, 0.2, 0.0).is_err());
+        assert!(BlackScholes::new(100.0, 100.0, 1.0, f64::NAN, 0.2, 0.0).is_err());
+        assert!(BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, f64::NEG_INFINITY).is_err());
+
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        assert!(bs.implied_volatility(OptionType::Call, f64::NAN, 100, 1e-6).is_err());
+    }
+
     #[test]
     fn test_implied_volatility() {
         let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
@@ -323,4 +837,241 @@ mod tests {
         // Should recover the original volatility
         assert!((implied_vol - 0.2).abs() < 0.001);
     }
+
+    #[test]
+    fn test_finite_difference_greeks_match_analytic() {
+        let bs = BlackScholes::new(100.0, 95.0, 0.75, 0.04, 0.25, 0.01).unwrap();
+        let analytic = bs.greeks(OptionType::Put);
+        let numeric = Greeks::from_finite_differences(&bs, |m| m.price(OptionType::Put));
+
+        assert!((analytic.delta - numeric.delta).abs() < 1e-4);
+        assert!((analytic.gamma - numeric.gamma).abs() < 1e-5);
+        assert!((analytic.vega - numeric.vega).abs() < 1e-5);
+        assert!((analytic.theta - numeric.theta).abs() < 1e-5);
+        assert!((analytic.rho - numeric.rho).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_theta_matches_one_day_decay() {
+        let bs = BlackScholes::new(100.0, 95.0, 0.75, 0.04, 0.25, 0.01).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let mut tomorrow = bs;
+            tomorrow.time_to_expiry -= 1.0 / 365.0;
+            tomorrow.vol_time -= 1.0 / 365.0;
+            let decay = tomorrow.price(option_type) - bs.price(option_type);
+            assert!((bs.greeks(option_type).theta - decay).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_business_time_scaling() {
+        // Friday to the following Friday: 7 calendar days, 5 trading days
+        let schedule = DaySchedule::weekdays(7, 5);
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let business = bs.with_time_scale(TimeScale::Trading, &schedule).unwrap();
+
+        assert!((business.time_to_expiry - 7.0 / 365.0).abs() < 1e-15);
+        assert!((business.vol_time - 5.0 / 252.0).abs() < 1e-15);
+
+        // Same price as a calendar-time model with the equivalent calendar volatility
+        let calendar = BlackScholes::new(100.0, 100.0, 7.0 / 365.0, 0.05, business.calendar_volatility(), 0.0).unwrap();
+        assert!((business.price(OptionType::Call) - calendar.price(OptionType::Call)).abs() < 1e-12);
+
+        // Theta is quoted per trading day
+        let numeric = Greeks::from_finite_differences(&business, |m| m.price(OptionType::Call));
+        assert!((business.greeks(OptionType::Call).theta - numeric.theta).abs() < 1e-4);
+
+        for days_per_year in [0.0, -252.0, f64::NAN] {
+            assert!(bs.with_time_scale(TimeScale::Custom { days_per_year }, &schedule).is_err());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let bs = BlackScholes::new(100.0, 105.0, 0.5, 0.03, 0.22, 0.01).unwrap();
+        let json = serde_json::to_string(&bs).unwrap();
+        let restored: BlackScholes = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.price(OptionType::Call), bs.price(OptionType::Call));
+
+        let greeks = bs.greeks(OptionType::Put);
+        let restored: Greeks = serde_json::from_str(&serde_json::to_string(&greeks).unwrap()).unwrap();
+        assert_eq!(restored.delta, greeks.delta);
+        assert_eq!(serde_json::to_string(&OptionType::Call).unwrap(), "\"Call\"");
+    }
+
+    #[test]
+    fn test_builder_matches_constructor() {
+        let built = BlackScholes::builder()
+            .strike(105.0)
+            .spot(100.0)
+            .vol(0.2)
+            .rate(0.05)
+            .expiry_years(0.5)
+            .build()
+            .unwrap();
+        let direct = BlackScholes::new(100.0, 105.0, 0.5, 0.05, 0.2, 0.0).unwrap();
+        assert_eq!(built.price(OptionType::Call), direct.price(OptionType::Call));
+        assert_eq!(built.dividend_yield, 0.0);
+
+        let with_yield = BlackScholes::builder()
+            .spot(100.0)
+            .strike(105.0)
+            .expiry_years(0.5)
+            .rate(0.05)
+            .vol(0.2)
+            .dividend_yield(0.02)
+            .build()
+            .unwrap();
+        assert_eq!(with_yield.dividend_yield, 0.02);
+    }
+
+    #[test]
+    fn test_builder_rejects_missing_and_invalid_fields() {
+        let missing = BlackScholes::builder().spot(100.0).strike(100.0).rate(0.05).vol(0.2).build();
+        match missing {
+            Err(BlackScholesError::InvalidInput(message)) => assert!(message.contains("expiry_years")),
+            other => panic!("unexpected {:?}", other),
+        }
+        let negative = BlackScholes::builder()
+            .spot(100.0)
+            .strike(-1.0)
+            .expiry_years(1.0)
+            .rate(0.05)
+            .vol(0.2)
+            .build();
+        assert!(negative.is_err());
+    }
+
+    #[test]
+    fn test_from_curves_matches_flat_inputs() {
+        use crate::curves::{CarryForward, FlatCurve, InterpolatedCurve, InterpolatedForward, Interpolation};
+        let discount = FlatCurve::new(0.05).unwrap();
+        let forward = CarryForward {
+            spot: 100.0,
+            discount,
+            dividend: FlatCurve::new(0.02).unwrap(),
+        };
+        let curved = BlackScholes::from_curves(105.0, 0.75, 0.25, &discount, &forward).unwrap();
+        let flat = BlackScholes::new(100.0, 105.0, 0.75, 0.05, 0.25, 0.02).unwrap();
+        assert!((curved.price(OptionType::Call) - flat.price(OptionType::Call)).abs() < 1e-12);
+
+        // Term structures: the price only depends on D(T) and F(T)
+        let curve = InterpolatedCurve::from_zero_rates(&[(0.5, 0.02), (1.0, 0.04)], Interpolation::LogLinear).unwrap();
+        let quoted = InterpolatedForward::new(100.0, &[(0.5, 100.5), (1.0, 102.0)]).unwrap();
+        let model = BlackScholes::from_curves(100.0, 1.0, 0.2, &curve, &quoted).unwrap();
+        let call = model.price(OptionType::Call);
+        let put = model.price(OptionType::Put);
+        assert!((call - put - (-0.04_f64).exp() * (102.0 - 100.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cash_greeks_units() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let greeks = bs.greeks(OptionType::Call);
+        let cash = greeks.to_cash(100.0, 10.0);
+        assert!((cash.delta - greeks.delta * 1000.0).abs() < 1e-12);
+
+        // Dollar gamma is the delta change over a 1% spot move, in currency
+        let up = BlackScholes::new(100.5, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let down = BlackScholes::new(99.5, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let bumped = (up.greeks(OptionType::Call).delta - down.greeks(OptionType::Call).delta) * 100.0 * 10.0;
+        assert!((bumped - cash.gamma).abs() < 1e-3 * cash.gamma);
+
+        // Rho per basis point matches a one-bp repricing
+        let bp = BlackScholes::new(100.0, 100.0, 1.0, 0.0501, 0.2, 0.0).unwrap();
+        let repriced = (bp.price(OptionType::Call) - bs.price(OptionType::Call)) * 10.0;
+        assert!((repriced - cash.rho).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_cash_greeks_add_across_underlyings() {
+        let index = BlackScholes::new(4000.0, 4100.0, 0.5, 0.04, 0.18, 0.015).unwrap();
+        let stock = BlackScholes::new(50.0, 45.0, 0.5, 0.04, 0.35, 0.0).unwrap();
+        let positions = [
+            index.greeks(OptionType::Call).to_cash(4000.0, 50.0),
+            stock.greeks(OptionType::Put).to_cash(50.0, -100.0),
+        ];
+        let total: CashGreeks = positions.iter().copied().sum();
+        assert!((total.delta - positions[0].delta - positions[1].delta).abs() < 1e-9);
+        assert!((total.vega - positions[0].vega - positions[1].vega).abs() < 1e-9);
+        assert_eq!(CashGreeks::default() + positions[0], positions[0]);
+    }
+
+    #[test]
+    fn test_automatic_differentiation_matches_analytic_greeks() {
+        for (spot, q, ot) in [(90.0, 0.0, OptionType::Call), (110.0, 0.03, OptionType::Put)] {
+            let bs = BlackScholes::new(spot, 100.0, 0.75, 0.04, 0.3, q).unwrap();
+            assert!((bs.inputs::<f64>().price(ot) - bs.price(ot)).abs() < 1e-12);
+            let (ad, analytic) = (bs.greeks_ad(ot), bs.greeks(ot));
+            for (a, b) in [
+                (ad.delta, analytic.delta),
+                (ad.gamma, analytic.gamma),
+                (ad.vega, analytic.vega),
+                (ad.theta, analytic.theta),
+                (ad.rho, analytic.rho),
+            ] {
+                assert!((a - b).abs() < 1e-12, "{} vs {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dual_cross_greeks() {
+        // Vanna d²V/dS dσ = -e^(-qT) φ(d1) d2 / σ
+        let bs = BlackScholes::new(100.0, 95.0, 0.5, 0.03, 0.25, 0.01).unwrap();
+        let mut inputs = bs.inputs::<Dual<Dual<f64, 1>, 1>>();
+        inputs.spot_price = Dual::lift(Dual::variable(100.0, 0));
+        inputs.volatility = Dual::variable(Dual::lift(0.25), 0);
+        let vanna = inputs.price(OptionType::Call).eps[0].eps[0];
+        let expected = -(-0.01_f64 * 0.5).exp() * BlackScholes::norm_pdf(bs.d1()) * bs.d2() / 0.25;
+        assert!((vanna - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_evaluate_matches_price_and_greeks() {
+        for ot in [OptionType::Call, OptionType::Put] {
+            let bs = BlackScholes::new(105.0, 100.0, 0.4, 0.05, 0.22, 0.02).unwrap();
+            let result = bs.evaluate(ot);
+            assert_eq!(result.price, bs.price(ot));
+            let fd = Greeks::from_finite_differences(&bs, |m| m.price(ot));
+            assert!((result.greeks.delta - fd.delta).abs() < 1e-5);
+            assert!((result.greeks.theta - fd.theta).abs() < 1e-6);
+            assert!((result.greeks.rho - fd.rho).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_leverage_of_single_options() {
+        let bs = BlackScholes::new(100.0, 110.0, 0.25, 0.03, 0.25, 0.0).unwrap();
+        let call = bs.leverage(OptionType::Call).unwrap();
+        let price = bs.price(OptionType::Call);
+        assert!((call.gearing - 100.0 / price).abs() < 1e-9);
+        assert!(call.lambda > 1.0 && call.lambda < call.gearing);
+
+        // Elasticity is the percentage move for a 1% spot move
+        let up = BlackScholes::new(100.01, 110.0, 0.25, 0.03, 0.25, 0.0).unwrap().price(OptionType::Call);
+        let down = BlackScholes::new(99.99, 110.0, 0.25, 0.03, 0.25, 0.0).unwrap().price(OptionType::Call);
+        assert!(((up - down) / 0.0002 / price - call.lambda).abs() < 1e-4);
+
+        let put = bs.leverage(OptionType::Put).unwrap();
+        assert!(put.lambda < 0.0 && put.effective_exposure < 0.0);
+        assert!(Leverage::from_position(0.0, 0.5, 100.0, 100.0).is_err());
+    }
+
+    #[test]
+    fn test_breakeven_volatility() {
+        // Without carry, theta is exactly the gamma cost at implied volatility
+        let flat = BlackScholes::new(100.0, 105.0, 0.5, 0.0, 0.3, 0.0).unwrap();
+        assert!((flat.breakeven_volatility(OptionType::Call).unwrap() - 0.3).abs() < 1e-12);
+        assert!((flat.breakeven_volatility_over(OptionType::Put, 0.25).unwrap() - 0.3).abs() < 1e-12);
+
+        // Rates add to a call's theta, so it needs more realized volatility
+        let carry = BlackScholes::new(100.0, 105.0, 0.5, 0.05, 0.3, 0.0).unwrap();
+        assert!(carry.breakeven_volatility(OptionType::Call).unwrap() > 0.3);
+        assert!(carry.breakeven_volatility_over(OptionType::Call, 1.0).is_err());
+
+        let deep_put = BlackScholes::new(50.0, 100.0, 1.0, 0.08, 0.2, 0.0).unwrap();
+        assert!(deep_put.breakeven_volatility(OptionType::Put).is_err());
+    }
 }
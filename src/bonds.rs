@@ -0,0 +1,387 @@
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Bisection steps when solving for yield to maturity
+const YIELD_ITERATIONS: usize = 200;
+
+/// Yield bump for DV01 (one basis point)
+const BASIS_POINT: f64 = 1e-4;
+
+/// Calendar date, proleptic Gregorian
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, BlackScholesError> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(BlackScholesError::invalid("Not a valid calendar date"));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    /// Days since 1970-01-01
+    pub fn serial(&self) -> i64 {
+        // Hinnant's days_from_civil with years starting in March
+        let y = self.year as i64 - i64::from(self.month <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let mp = (self.month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// Date `serial` days after 1970-01-01 (inverse of `serial`)
+    pub fn from_serial(serial: i64) -> Date {
+        // Hinnant's civil_from_days
+        let z = serial + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        Date {
+            year: (yoe + era * 400 + i64::from(month <= 2)) as i32,
+            month,
+            day,
+        }
+    }
+
+    /// Day of the week, 0 = Monday to 6 = Sunday
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.serial() + 3).rem_euclid(7) as u32
+    }
+
+    /// Shift by whole calendar days
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_serial(self.serial() + days)
+    }
+
+    /// Actual days from `self` to `other`
+    pub fn days_until(&self, other: Date) -> i64 {
+        other.serial() - self.serial()
+    }
+
+    /// Shift by whole months, clamping the day to the end of the target month
+    pub fn add_months(&self, months: i32) -> Date {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+        Date {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+}
+
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Day-count convention for accrued interest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DayCount {
+    /// Actual days over 360 (money markets)
+    Actual360,
+    /// Actual days over a fixed 365
+    Actual365Fixed,
+    /// Actual days over actual days in the coupon period, times the frequency (ICMA)
+    ActualActualIcma,
+    /// 30/360 US bond basis
+    Thirty360,
+}
+
+impl DayCount {
+    /// Year fraction from `start` to `end` within the coupon period [period_start, period_end]
+    ///
+    /// # Arguments
+    /// * `start`, `end` - Accrual dates
+    /// * `period_start`, `period_end` - Coupon period containing the accrual, used by ICMA
+    /// * `frequency` - Coupons per year, used by ICMA
+    pub fn year_fraction(&self, start: Date, end: Date, period_start: Date, period_end: Date, frequency: u32) -> f64 {
+        match self {
+            DayCount::Actual360 => start.days_until(end) as f64 / 360.0,
+            DayCount::Actual365Fixed => start.days_until(end) as f64 / 365.0,
+            DayCount::ActualActualIcma => {
+                start.days_until(end) as f64 / (frequency as f64 * period_start.days_until(period_end) as f64)
+            }
+            DayCount::Thirty360 => {
+                let d1 = start.day.min(30);
+                let d2 = if end.day == 31 && d1 == 30 { 30 } else { end.day };
+                let days = 360 * (end.year - start.year) + 30 * (end.month as i32 - start.month as i32)
+                    + (d2 as i32 - d1 as i32);
+                days as f64 / 360.0
+            }
+        }
+    }
+}
+
+/// Price, yield and risk measures of a bond at one settlement date
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BondAnalytics {
+    /// Quoted price excluding accrued interest
+    pub clean_price: f64,
+    /// Invoice price including accrued interest
+    pub dirty_price: f64,
+    pub accrued_interest: f64,
+    /// Yield to maturity, compounded at the coupon frequency
+    pub yield_to_maturity: f64,
+    /// Present-value-weighted average time to the cash flows in years
+    pub macaulay_duration: f64,
+    /// -(dP/dy)/P
+    pub modified_duration: f64,
+    /// (d²P/dy²)/P
+    pub convexity: f64,
+    /// Fall in dirty price for a one basis point rise in yield
+    pub dv01: f64,
+}
+
+/// Bullet bond paying a fixed coupon `frequency` times a year
+///
+/// Coupon dates step back from maturity in whole months, so every period is
+/// regular except a possible first one. Each coupon pays coupon/frequency of
+/// face; the day count only governs accrued interest and where settlement
+/// falls inside the current period. Yields follow the street convention,
+/// compounding at the coupon frequency with fractional first periods.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedRateBond {
+    pub maturity: Date,
+    /// Annual coupon rate as decimal
+    pub coupon: f64,
+    /// Coupons per year: 1, 2, 3, 4, 6 or 12
+    pub frequency: u32,
+    pub day_count: DayCount,
+    /// Redemption amount, which prices are quoted against (e.g. 100)
+    pub face: f64,
+}
+
+impl FixedRateBond {
+    pub fn new(
+        maturity: Date,
+        coupon: f64,
+        frequency: u32,
+        day_count: DayCount,
+        face: f64,
+    ) -> Result<Self, BlackScholesError> {
+        if frequency == 0 || 12 % frequency != 0 {
+            return Err(BlackScholesError::invalid("Coupon frequency must divide twelve months"));
+        }
+        Ok(FixedRateBond {
+            maturity,
+            coupon: validation::non_negative("Coupon", coupon)?,
+            frequency,
+            day_count,
+            face: validation::positive("Face value", face)?,
+        })
+    }
+
+    /// Last coupon date on or before `settlement` and the coupon dates after it
+    fn schedule(&self, settlement: Date) -> Result<(Date, Vec<Date>), BlackScholesError> {
+        if settlement >= self.maturity {
+            return Err(BlackScholesError::invalid("Settlement must be before maturity"));
+        }
+        let months = 12 / self.frequency as i32;
+        let mut remaining = Vec::new();
+        let mut k = 0;
+        loop {
+            let date = self.maturity.add_months(-months * k);
+            if date <= settlement {
+                remaining.reverse();
+                return Ok((date, remaining));
+            }
+            remaining.push(date);
+            k += 1;
+        }
+    }
+
+    /// Fraction of the current coupon period elapsed at `settlement`
+    fn elapsed(&self, previous: Date, next: Date, settlement: Date) -> f64 {
+        let dc = self.day_count;
+        dc.year_fraction(previous, settlement, previous, next, self.frequency)
+            / dc.year_fraction(previous, next, previous, next, self.frequency)
+    }
+
+    /// Interest accrued since the last coupon date
+    pub fn accrued_interest(&self, settlement: Date) -> Result<f64, BlackScholesError> {
+        let (previous, remaining) = self.schedule(settlement)?;
+        let fraction = self
+            .day_count
+            .year_fraction(previous, settlement, previous, remaining[0], self.frequency);
+        Ok(self.face * self.coupon * fraction)
+    }
+
+    /// Invoice price at a yield to maturity
+    pub fn dirty_price(&self, settlement: Date, yield_to_maturity: f64) -> Result<f64, BlackScholesError> {
+        Ok(self.discounted_flows(settlement, yield_to_maturity)?.iter().map(|f| f.1).sum())
+    }
+
+    /// Quoted price at a yield to maturity
+    pub fn clean_price(&self, settlement: Date, yield_to_maturity: f64) -> Result<f64, BlackScholesError> {
+        Ok(self.dirty_price(settlement, yield_to_maturity)? - self.accrued_interest(settlement)?)
+    }
+
+    /// Yield at which the bond's clean price equals `clean_price`
+    pub fn yield_to_maturity(&self, settlement: Date, clean_price: f64) -> Result<f64, BlackScholesError> {
+        let target = validation::positive("Clean price", clean_price)? + self.accrued_interest(settlement)?;
+        let f = self.frequency as f64;
+        // Price falls as yield rises; (1 + y/f) must stay positive
+        let (mut lo, mut hi) = (-0.99 * f, 1.0);
+        let price = |y: f64| self.dirty_price(settlement, y);
+        while price(hi)? > target {
+            hi *= 2.0;
+            if hi > 1e3 {
+                return Err(BlackScholesError::no_convergence("Price too low for any yield"));
+            }
+        }
+        if price(lo)? < target {
+            return Err(BlackScholesError::no_convergence("Price too high for any yield"));
+        }
+        for _ in 0..YIELD_ITERATIONS {
+            let mid = 0.5 * (lo + hi);
+            if price(mid)? > target {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+            if hi - lo < 1e-15 {
+                break;
+            }
+        }
+        Ok(0.5 * (lo + hi))
+    }
+
+    /// Full analytics from a quoted clean price
+    pub fn analytics(&self, settlement: Date, clean_price: f64) -> Result<BondAnalytics, BlackScholesError> {
+        let y = self.yield_to_maturity(settlement, clean_price)?;
+        let f = self.frequency as f64;
+        let flows = self.discounted_flows(settlement, y)?;
+        let dirty: f64 = flows.iter().map(|p| p.1).sum();
+        let macaulay = flows.iter().map(|&(t, pv)| t * pv).sum::<f64>() / dirty;
+        let modified = macaulay / (1.0 + y / f);
+        let convexity =
+            flows.iter().map(|&(t, pv)| pv * t * (t + 1.0 / f)).sum::<f64>() / (dirty * (1.0 + y / f).powi(2));
+        Ok(BondAnalytics {
+            clean_price,
+            dirty_price: dirty,
+            accrued_interest: self.accrued_interest(settlement)?,
+            yield_to_maturity: y,
+            macaulay_duration: macaulay,
+            modified_duration: modified,
+            convexity,
+            dv01: modified * dirty * BASIS_POINT,
+        })
+    }
+
+    /// `(time in years, present value)` of each remaining cash flow
+    fn discounted_flows(&self, settlement: Date, yield_to_maturity: f64) -> Result<Vec<(f64, f64)>, BlackScholesError> {
+        let y = validation::finite("Yield", yield_to_maturity)?;
+        let f = self.frequency as f64;
+        if 1.0 + y / f <= 0.0 {
+            return Err(BlackScholesError::invalid("Yield must exceed minus the coupon frequency"));
+        }
+        let (previous, remaining) = self.schedule(settlement)?;
+        let to_next = 1.0 - self.elapsed(previous, remaining[0], settlement);
+        let coupon = self.face * self.coupon / f;
+        let last = remaining.len() - 1;
+        Ok((0..remaining.len())
+            .map(|k| {
+                let periods = to_next + k as f64;
+                let amount = if k == last { coupon + self.face } else { coupon };
+                (periods / f, amount / (1.0 + y / f).powf(periods))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    fn treasury() -> FixedRateBond {
+        FixedRateBond::new(date(2034, 11, 15), 0.045, 2, DayCount::ActualActualIcma, 100.0).unwrap()
+    }
+
+    #[test]
+    fn test_dates_and_day_counts() {
+        assert_eq!(date(1970, 1, 1).serial(), 0);
+        assert_eq!(date(2000, 3, 1).serial() - date(2000, 2, 28).serial(), 2);
+        assert_eq!(date(2024, 8, 31).add_months(-6), date(2024, 2, 29));
+        assert!(Date::new(2023, 2, 29).is_err());
+        assert_eq!(Date::from_serial(date(2024, 2, 29).serial()), date(2024, 2, 29));
+        assert_eq!(date(1999, 12, 31).add_days(1), date(2000, 1, 1));
+        assert_eq!(date(2024, 6, 21).weekday(), 4);
+
+        let (start, end) = (date(2024, 1, 31), date(2024, 7, 31));
+        assert!((DayCount::Actual360.year_fraction(start, end, start, end, 2) - 182.0 / 360.0).abs() < 1e-15);
+        assert!((DayCount::Thirty360.year_fraction(start, end, start, end, 2) - 0.5).abs() < 1e-15);
+        let icma = DayCount::ActualActualIcma.year_fraction(start, date(2024, 4, 30), start, end, 2);
+        assert!((icma - 90.0 / (2.0 * 182.0)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_par_bond_and_accrued_interest() {
+        let bond = treasury();
+        // On a coupon date a bond yielding its coupon is priced at par with no accrued
+        let on_coupon = date(2024, 11, 15);
+        assert_eq!(bond.accrued_interest(on_coupon).unwrap(), 0.0);
+        assert!((bond.clean_price(on_coupon, 0.045).unwrap() - 100.0).abs() < 1e-10);
+        assert!((bond.yield_to_maturity(on_coupon, 100.0).unwrap() - 0.045).abs() < 1e-12);
+
+        // 30/360: three months into a 6% semiannual period accrues 1.5
+        let corporate = FixedRateBond::new(date(2030, 6, 15), 0.06, 2, DayCount::Thirty360, 100.0).unwrap();
+        assert!((corporate.accrued_interest(date(2025, 3, 15)).unwrap() - 1.5).abs() < 1e-12);
+        assert!(corporate.accrued_interest(date(2030, 6, 15)).is_err());
+        assert!(FixedRateBond::new(date(2030, 6, 15), 0.06, 5, DayCount::Thirty360, 100.0).is_err());
+    }
+
+    #[test]
+    fn test_yield_round_trip_between_coupons() {
+        let bond = treasury();
+        let settlement = date(2025, 2, 3);
+        let clean = bond.clean_price(settlement, 0.0512).unwrap();
+        assert!(clean < 100.0);
+        assert!((bond.yield_to_maturity(settlement, clean).unwrap() - 0.0512).abs() < 1e-12);
+        // Dirty price stays continuous across a coupon date once the coupon is paid
+        let before = bond.dirty_price(date(2025, 5, 14), 0.05).unwrap();
+        let after = bond.dirty_price(date(2025, 5, 15), 0.05).unwrap();
+        assert!((before - after - 2.25).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_risk_measures_match_finite_differences() {
+        let bond = treasury();
+        let settlement = date(2025, 2, 3);
+        let a = bond.analytics(settlement, 97.25).unwrap();
+        let y = a.yield_to_maturity;
+        let h = 1e-5;
+        let up = bond.dirty_price(settlement, y + h).unwrap();
+        let down = bond.dirty_price(settlement, y - h).unwrap();
+        let modified = (down - up) / (2.0 * h * a.dirty_price);
+        let convexity = (up - 2.0 * a.dirty_price + down) / (h * h * a.dirty_price);
+        assert!((a.modified_duration - modified).abs() < 1e-7);
+        assert!((a.convexity - convexity).abs() < 1e-3 * a.convexity);
+        assert!((a.dv01 - (down - up) / (2.0 * h) * BASIS_POINT).abs() < 1e-8);
+        assert!(a.macaulay_duration > a.modified_duration && a.macaulay_duration < 10.0);
+        assert!((a.dirty_price - a.clean_price - a.accrued_interest).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,484 @@
+use crate::black_scholes::OptionType;
+use crate::chain::OptionChain;
+use crate::error::BlackScholesError;
+use crate::heston::HestonParams;
+use crate::validation;
+use crate::vol_surface::{SmileQuotes, SsviSurface, SviSlice, SviSurface};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Log-moneyness padding beyond the quotes for the SVI calendar-arbitrage check
+const CALENDAR_GRID_PADDING: f64 = 1.0;
+
+/// Number of points on the SVI calendar-arbitrage check grid
+const CALENDAR_GRID_POINTS: usize = 81;
+
+/// Model fitted by a calibration job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CalibrationModel {
+    /// Independent raw SVI smile per expiry
+    SviPerExpiry,
+    /// One SSVI surface across expiries
+    Ssvi,
+    /// Heston stochastic volatility across expiries
+    Heston,
+}
+
+impl CalibrationModel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CalibrationModel::SviPerExpiry => "SVI",
+            CalibrationModel::Ssvi => "SSVI",
+            CalibrationModel::Heston => "Heston",
+        }
+    }
+}
+
+/// Parameters produced by a calibration
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CalibratedParameters {
+    SviPerExpiry(Vec<SviSlice>),
+    Ssvi(SsviSurface),
+    Heston(HestonParams),
+}
+
+/// Quality of a calibration against the quotes it was fitted to
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FitDiagnostics {
+    /// Number of quotes fitted
+    pub quotes: usize,
+    /// Root-mean-square implied volatility error
+    pub rmse: f64,
+    /// Largest absolute implied volatility error
+    pub max_error: f64,
+    /// Whether the fitted surface passes the model's static-arbitrage checks
+    ///
+    /// Butterfly and calendar checks for SVI, the parameter condition for
+    /// SSVI; always true for Heston, whose prices come from a model.
+    pub arbitrage_free: bool,
+}
+
+/// One calibrated parameter set, stamped with when and for what it was produced
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalibrationRecord {
+    pub underlying: String,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub model: CalibrationModel,
+    pub parameters: CalibratedParameters,
+    pub diagnostics: FitDiagnostics,
+}
+
+/// A calibration job that did not produce parameters
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalibrationFailure {
+    pub underlying: String,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub model: CalibrationModel,
+    /// Rendered error, kept as text so failures can be persisted alongside records
+    pub error: String,
+}
+
+/// Outcome of one scheduler run, in (underlying, model) submission order
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalibrationRun {
+    pub records: Vec<CalibrationRecord>,
+    pub failures: Vec<CalibrationFailure>,
+}
+
+/// Calibrates a set of models for many underlyings in parallel
+///
+/// Every (underlying, model) pair is an independent job. Jobs are pulled
+/// from a shared queue by a fixed pool of scoped worker threads, so a slow
+/// Heston fit on one name does not hold up the SVI fits on the others. The
+/// caller's validation strictness applies on every worker.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalibrationScheduler {
+    pub models: Vec<CalibrationModel>,
+    /// Number of worker threads
+    pub threads: usize,
+}
+
+impl CalibrationScheduler {
+    /// Create a scheduler using all available cores
+    pub fn new(models: Vec<CalibrationModel>) -> Result<Self, BlackScholesError> {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_threads(models, threads)
+    }
+
+    /// Create a scheduler with an explicit worker count
+    pub fn with_threads(models: Vec<CalibrationModel>, threads: usize) -> Result<Self, BlackScholesError> {
+        if models.is_empty() {
+            return Err(BlackScholesError::invalid("Calibration needs at least one model"));
+        }
+        if threads == 0 {
+            return Err(BlackScholesError::invalid("Calibration needs at least one worker thread"));
+        }
+        Ok(CalibrationScheduler { models, threads })
+    }
+
+    /// Calibrate every model for every `(underlying, chain)` pair, stamped with the current time
+    pub fn run(&self, chains: &[(String, OptionChain)]) -> CalibrationRun {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        self.run_at(chains, timestamp)
+    }
+
+    /// Calibrate every model for every `(underlying, chain)` pair with an explicit timestamp
+    ///
+    /// A failed job is reported in `failures` and does not affect the others.
+    pub fn run_at(&self, chains: &[(String, OptionChain)], timestamp: u64) -> CalibrationRun {
+        self.execute(chains, timestamp, None)
+    }
+
+    /// Recalibrate starting from the latest stored parameters, stamped with the current time
+    pub fn run_warm(&self, chains: &[(String, OptionChain)], previous: &CalibrationStore) -> CalibrationRun {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        self.run_warm_at(chains, previous, timestamp)
+    }
+
+    /// Recalibrate starting from the latest stored parameters, with an explicit timestamp
+    ///
+    /// Each job warm-starts from the most recent record in `previous` for its
+    /// underlying and model: one local search instead of the multi-start
+    /// search of a cold fit. SVI slices start from the stored slice nearest in
+    /// expiry. Jobs without a stored record run cold.
+    pub fn run_warm_at(
+        &self,
+        chains: &[(String, OptionChain)],
+        previous: &CalibrationStore,
+        timestamp: u64,
+    ) -> CalibrationRun {
+        self.execute(chains, timestamp, Some(previous))
+    }
+
+    fn execute(
+        &self,
+        chains: &[(String, OptionChain)],
+        timestamp: u64,
+        previous: Option<&CalibrationStore>,
+    ) -> CalibrationRun {
+        let jobs: Vec<(usize, CalibrationModel)> = (0..chains.len())
+            .flat_map(|i| self.models.iter().map(move |&m| (i, m)))
+            .collect();
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<(usize, Result<_, BlackScholesError>)>> = Mutex::new(Vec::with_capacity(jobs.len()));
+        let strictness = validation::strictness();
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.threads.min(jobs.len()) {
+                scope.spawn(|| {
+                    validation::with_strictness(strictness, || loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&(chain, model)) = jobs.get(index) else {
+                            break;
+                        };
+                        let (underlying, chain) = &chains[chain];
+                        let warm_start = previous.and_then(|store| store.latest(underlying, model));
+                        let outcome = calibrate(chain, model, warm_start.map(|r| &r.parameters));
+                        results.lock().unwrap_or_else(|e| e.into_inner()).push((index, outcome));
+                    })
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+        results.sort_by_key(|r| r.0);
+        let mut run = CalibrationRun::default();
+        for (index, outcome) in results {
+            let (chain, model) = jobs[index];
+            let underlying = chains[chain].0.clone();
+            match outcome {
+                Ok((parameters, diagnostics)) => run.records.push(CalibrationRecord {
+                    underlying,
+                    timestamp,
+                    model,
+                    parameters,
+                    diagnostics,
+                }),
+                Err(error) => run.failures.push(CalibrationFailure {
+                    underlying,
+                    timestamp,
+                    model,
+                    error: error.to_string(),
+                }),
+            }
+        }
+        run
+    }
+}
+
+/// Calibrated parameter sets accumulated across runs
+///
+/// Serializable (with the `serde` feature) so a daily job can persist the
+/// store and reload it for lookups of the latest or historical parameters.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalibrationStore {
+    records: Vec<CalibrationRecord>,
+}
+
+impl CalibrationStore {
+    pub fn new() -> Self {
+        CalibrationStore::default()
+    }
+
+    /// Add the successful records of a run
+    pub fn insert(&mut self, run: &CalibrationRun) {
+        self.records.extend(run.records.iter().cloned());
+    }
+
+    /// All stored records, in insertion order
+    pub fn records(&self) -> &[CalibrationRecord] {
+        &self.records
+    }
+
+    /// Records for one underlying and model, oldest first
+    pub fn history(&self, underlying: &str, model: CalibrationModel) -> Vec<&CalibrationRecord> {
+        let mut history: Vec<&CalibrationRecord> = self
+            .records
+            .iter()
+            .filter(|r| r.underlying == underlying && r.model == model)
+            .collect();
+        history.sort_by_key(|r| r.timestamp);
+        history
+    }
+
+    /// Most recent record for one underlying and model
+    pub fn latest(&self, underlying: &str, model: CalibrationModel) -> Option<&CalibrationRecord> {
+        self.history(underlying, model).pop()
+    }
+}
+
+/// Calibrate one model to one chain, warm-started from parameters of the same model
+fn calibrate(
+    chain: &OptionChain,
+    model: CalibrationModel,
+    warm_start: Option<&CalibratedParameters>,
+) -> Result<(CalibratedParameters, FitDiagnostics), BlackScholesError> {
+    match model {
+        CalibrationModel::SviPerExpiry => {
+            let mut slices = Vec::new();
+            let (mut quotes, mut squared, mut max_error) = (0, 0.0, 0.0_f64);
+            let mut butterfly_free = true;
+            let (mut k_min, mut k_max) = (f64::INFINITY, f64::NEG_INFINITY);
+            for smile in smiles(chain) {
+                let nearest = match warm_start {
+                    Some(CalibratedParameters::SviPerExpiry(previous)) => previous
+                        .iter()
+                        .min_by(|a, b| (a.expiry - smile.expiry).abs().total_cmp(&(b.expiry - smile.expiry).abs())),
+                    _ => None,
+                };
+                let fit = match nearest {
+                    Some(previous) => SviSlice::fit_from(smile.expiry, smile.forward, &smile.quotes, &previous.params)?,
+                    None => SviSlice::fit(smile.expiry, smile.forward, &smile.quotes)?,
+                };
+                for &(strike, _) in &smile.quotes {
+                    let k = (strike / smile.forward).ln();
+                    k_min = k_min.min(k);
+                    k_max = k_max.max(k);
+                }
+                quotes += smile.quotes.len();
+                squared += fit.rmse * fit.rmse * smile.quotes.len() as f64;
+                max_error = max_error.max(fit.max_error);
+                butterfly_free &= fit.butterfly_free;
+                slices.push(fit.slice);
+            }
+            let surface = SviSurface::new(slices.clone())?;
+            let calendar_free = surface.is_calendar_free(
+                k_min - CALENDAR_GRID_PADDING,
+                k_max + CALENDAR_GRID_PADDING,
+                CALENDAR_GRID_POINTS,
+            );
+            let diagnostics = FitDiagnostics {
+                quotes,
+                rmse: (squared / quotes as f64).sqrt(),
+                max_error,
+                arbitrage_free: butterfly_free && calendar_free,
+            };
+            Ok((CalibratedParameters::SviPerExpiry(slices), diagnostics))
+        }
+        CalibrationModel::Ssvi => {
+            let smiles = smiles(chain);
+            let fit = match warm_start {
+                Some(CalibratedParameters::Ssvi(previous)) => SsviSurface::fit_from(&smiles, &previous.params)?,
+                _ => SsviSurface::fit(&smiles)?,
+            };
+            let diagnostics = FitDiagnostics {
+                quotes: smiles.iter().map(|s| s.quotes.len()).sum(),
+                rmse: fit.rmse,
+                max_error: fit.max_error,
+                arbitrage_free: fit.surface.params.is_arbitrage_free(),
+            };
+            Ok((CalibratedParameters::Ssvi(fit.surface), diagnostics))
+        }
+        CalibrationModel::Heston => {
+            let fit = match warm_start {
+                Some(CalibratedParameters::Heston(previous)) => HestonParams::calibrate_from(chain, previous)?,
+                _ => HestonParams::calibrate(chain)?,
+            };
+            let diagnostics = FitDiagnostics {
+                quotes: smiles(chain).iter().map(|s| s.quotes.len()).sum(),
+                rmse: fit.rmse,
+                max_error: fit.max_error,
+                arbitrage_free: true,
+            };
+            Ok((CalibratedParameters::Heston(fit.params), diagnostics))
+        }
+    }
+}
+
+/// Out-of-the-money implied volatility quotes per expiry, with each expiry's forward
+///
+/// Quotes whose implied volatility does not solve are dropped, as are
+/// expiries left with no quotes.
+fn smiles(chain: &OptionChain) -> Vec<SmileQuotes> {
+    let analytics = chain.analyze();
+    chain
+        .slices
+        .iter()
+        .filter_map(|slice| {
+            let forward = slice.forward(chain.spot);
+            let quotes: Vec<(f64, f64)> = analytics
+                .iter()
+                .filter(|r| r.expiry == slice.expiry)
+                .filter(|r| match r.option_type {
+                    OptionType::Call => r.strike >= forward,
+                    OptionType::Put => r.strike < forward,
+                })
+                .filter_map(|r| r.implied_vol.map(|vol| (r.strike, vol)))
+                .collect();
+            (!quotes.is_empty()).then_some(SmileQuotes {
+                expiry: slice.expiry,
+                forward,
+                quotes,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::heston::Heston;
+    use crate::synthetic::{generate_chain, ChainSpec, StrikeGrid};
+
+    fn spec(expiries: Vec<f64>) -> ChainSpec {
+        ChainSpec {
+            expiries,
+            strikes: StrikeGrid::Moneyness((0..11).map(|i| 0.75 + 0.05 * i as f64).collect()),
+            relative_half_spread: 0.0,
+            min_half_spread: 0.0,
+        }
+    }
+
+    fn heston_chain(spot: f64, rho: f64) -> OptionChain {
+        let params = HestonParams::new(0.04, 2.0, 0.05, 0.5, rho).unwrap();
+        let model = Heston::new(spot, 0.03, 0.01, params).unwrap();
+        generate_chain(&model, &spec(vec![0.25, 0.5, 1.0])).unwrap()
+    }
+
+    #[test]
+    fn test_batch_calibrates_every_model_and_underlying() {
+        let chains = vec![
+            (String::from("AAA"), heston_chain(100.0, -0.7)),
+            (String::from("BBB"), heston_chain(50.0, -0.3)),
+        ];
+        let models = vec![CalibrationModel::SviPerExpiry, CalibrationModel::Ssvi, CalibrationModel::Heston];
+        let scheduler = CalibrationScheduler::with_threads(models, 3).unwrap();
+        let run = scheduler.run_at(&chains, 1_700_000_000);
+
+        assert!(run.failures.is_empty(), "{:?}", run.failures);
+        assert_eq!(run.records.len(), 6);
+        // Submission order is preserved whatever order the workers finish in
+        assert_eq!(run.records[3].underlying, "BBB");
+        assert_eq!(run.records[3].model, CalibrationModel::SviPerExpiry);
+        for record in &run.records {
+            assert_eq!(record.timestamp, 1_700_000_000);
+            assert_eq!(record.diagnostics.quotes, 33);
+            let tolerance = if record.model == CalibrationModel::Ssvi { 1e-2 } else { 2e-3 };
+            assert!(record.diagnostics.rmse < tolerance, "{:?}", record);
+        }
+        match &run.records[5].parameters {
+            CalibratedParameters::Heston(p) => assert!((p.rho + 0.3).abs() < 0.1),
+            other => panic!("unexpected parameters {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_failures_are_isolated() {
+        let sparse = OptionChain::new(100.0, vec![]).unwrap();
+        let chains = vec![
+            (String::from("EMPTY"), sparse),
+            (String::from("AAA"), heston_chain(100.0, -0.7)),
+        ];
+        let scheduler = CalibrationScheduler::with_threads(vec![CalibrationModel::Ssvi], 2).unwrap();
+        let run = scheduler.run_at(&chains, 1);
+        assert_eq!(run.failures.len(), 1);
+        assert_eq!(run.failures[0].underlying, "EMPTY");
+        assert_eq!(run.records.len(), 1);
+        assert!(CalibrationScheduler::with_threads(vec![], 2).is_err());
+        assert!(CalibrationScheduler::with_threads(vec![CalibrationModel::Ssvi], 0).is_err());
+    }
+
+    #[test]
+    fn test_store_keeps_history_by_timestamp() {
+        let market = BlackScholes::new(100.0, 100.0, 1.0, 0.02, 0.25, 0.0).unwrap();
+        let chains = vec![(String::from("AAA"), generate_chain(&market, &spec(vec![0.5])).unwrap())];
+        let scheduler = CalibrationScheduler::with_threads(vec![CalibrationModel::Ssvi], 1).unwrap();
+
+        let mut store = CalibrationStore::new();
+        store.insert(&scheduler.run_at(&chains, 200));
+        store.insert(&scheduler.run_at(&chains, 100));
+        assert_eq!(store.history("AAA", CalibrationModel::Ssvi).len(), 2);
+        assert_eq!(store.latest("AAA", CalibrationModel::Ssvi).unwrap().timestamp, 200);
+        assert!(store.latest("AAA", CalibrationModel::Heston).is_none());
+    }
+
+    #[test]
+    fn test_warm_run_starts_from_latest_record() {
+        let models = vec![CalibrationModel::SviPerExpiry, CalibrationModel::Ssvi];
+        let scheduler = CalibrationScheduler::with_threads(models, 2).unwrap();
+        let mut store = CalibrationStore::new();
+        store.insert(&scheduler.run_at(&[(String::from("AAA"), heston_chain(100.0, -0.7))], 100));
+
+        // Intraday the spot and skew drift; BBB has no history and runs cold
+        let chains = vec![
+            (String::from("AAA"), heston_chain(101.0, -0.65)),
+            (String::from("BBB"), heston_chain(50.0, -0.3)),
+        ];
+        let warm = scheduler.run_warm_at(&chains, &store, 200);
+        assert!(warm.failures.is_empty(), "{:?}", warm.failures);
+        let cold = scheduler.run_at(&chains, 200);
+        for (warm, cold) in warm.records.iter().zip(&cold.records) {
+            assert_eq!((warm.model, &warm.underlying), (cold.model, &cold.underlying));
+            assert!(warm.diagnostics.rmse < cold.diagnostics.rmse + 1e-4, "{:?}", warm);
+        }
+        store.insert(&warm);
+        assert_eq!(store.latest("AAA", CalibrationModel::Ssvi).unwrap().timestamp, 200);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_store_round_trips_through_json() {
+        let chains = vec![(String::from("AAA"), heston_chain(100.0, -0.5))];
+        let scheduler = CalibrationScheduler::with_threads(vec![CalibrationModel::SviPerExpiry], 1).unwrap();
+        let mut store = CalibrationStore::new();
+        store.insert(&scheduler.run_at(&chains, 42));
+
+        let json = serde_json::to_string(&store).unwrap();
+        let restored: CalibrationStore = serde_json::from_str(&json).unwrap();
+        let record = restored.latest("AAA", CalibrationModel::SviPerExpiry).unwrap();
+        assert_eq!(record.timestamp, 42);
+        assert_eq!(record.diagnostics, store.records()[0].diagnostics);
+    }
+}
@@ -0,0 +1,364 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::error::BlackScholesError;
+use crate::math::{nelder_mead, noncentral_chi_square_cdf, noncentral_chi_square_sf, Rng};
+use crate::model::EuropeanModel;
+use crate::monte_carlo::PathModel;
+use crate::validation;
+
+/// |1 - β| below which the lognormal (Black-Scholes) limit is used
+const LOGNORMAL_TOLERANCE: f64 = 1e-3;
+
+/// |r - q| below which the variance clock is taken as δ²T
+const CARRY_TOLERANCE: f64 = 1e-12;
+
+/// Largest elasticity accepted
+const MAX_BETA: f64 = 2.0;
+
+/// Euler steps per year when simulating paths
+const STEPS_PER_YEAR: f64 = 250.0;
+
+/// Maximum Newton iterations when reporting calibration errors in implied volatility
+const IV_MAX_ITERATIONS: usize = 100;
+
+/// Price tolerance when reporting calibration errors in implied volatility
+const IV_TOLERANCE: f64 = 1e-10;
+
+/// Constant elasticity of variance (Cox 1975): dS = (r - q)·S·dt + δ·S^β·dW
+///
+/// The model's `volatility` is the local volatility at today's spot, so
+/// δ = σ·S₀^(1-β) and local volatility is σ·(S/S₀)^(β-1). β < 1 gives the
+/// equity leverage skew, β = 1 is Black-Scholes and β > 1 an upward skew.
+/// For β < 1 the spot can reach zero, where it is absorbed.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cev {
+    /// Market parameters, strike, expiry and local volatility at today's spot
+    pub model: BlackScholes,
+    /// Elasticity β of volatility to spot, in [0, 2]
+    pub beta: f64,
+}
+
+/// Result of fitting CEV to one expiry's smile
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CevFit {
+    /// The calibrated model, struck at the market's strike
+    pub model: Cev,
+    /// Root-mean-square implied volatility error over the quotes
+    pub rmse: f64,
+    /// Largest absolute implied volatility error over the quotes
+    pub max_error: f64,
+}
+
+impl Cev {
+    /// Create a new CEV model
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes parameters; `volatility` is the local volatility at the spot
+    /// * `beta` - Elasticity β in [0, 2]
+    pub fn new(model: BlackScholes, beta: f64) -> Result<Self, BlackScholesError> {
+        Ok(Cev {
+            model,
+            beta: validation::in_range("CEV beta", beta, 0.0, MAX_BETA, "in [0, 2]")?,
+        })
+    }
+
+    /// Volatility scale δ = σ·S₀^(1-β) of the diffusion term
+    pub fn delta(&self) -> f64 {
+        self.model.calendar_volatility() * self.model.spot_price.powf(1.0 - self.beta)
+    }
+
+    /// Calculate option price with Schroder's noncentral chi-square formulas
+    ///
+    /// With v = δ²·(e^(2(r-q)(β-1)T) - 1)/(2(r-q)(β-1)), the transformed
+    /// strike a = (K·e^(-(r-q)T))^(2(1-β))/((1-β)²v), spot c = S^(2(1-β))/((1-β)²v)
+    /// and b = 1/(1-β), the call for β < 1 is
+    /// S·e^(-qT)·(1 - χ²(a; b + 2, c)) - K·e^(-rT)·χ²(c; b, a),
+    /// and for β > 1 S·e^(-qT)·(1 - χ²(c; -b, a)) - K·e^(-rT)·χ²(a; 2 - b, c).
+    /// Within 10⁻³ of β = 1 the Black-Scholes price is returned.
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        let m = &self.model;
+        let one_minus = 1.0 - self.beta;
+        if one_minus.abs() < LOGNORMAL_TOLERANCE {
+            return m.price(option_type);
+        }
+        let t = m.time_to_expiry;
+        let (s, k) = (m.spot_price, m.strike_price);
+        let carry = m.risk_free_rate - m.dividend_yield;
+        let delta = self.delta();
+        let v = if carry.abs() < CARRY_TOLERANCE {
+            delta * delta * t
+        } else {
+            let growth = -2.0 * carry * one_minus;
+            delta * delta * ((growth * t).exp() - 1.0) / growth
+        };
+        let scale = one_minus * one_minus * v;
+        let a = (k * (-carry * t).exp()).powf(2.0 * one_minus) / scale;
+        let c = s.powf(2.0 * one_minus) / scale;
+        let b = 1.0 / one_minus;
+
+        // Exercise probabilities under the share and money measures, upper tails for the call
+        let call = option_type == OptionType::Call;
+        let probability = |x: f64, df: f64, nc: f64, upper: bool| {
+            if upper {
+                noncentral_chi_square_sf(x, df, nc)
+            } else {
+                noncentral_chi_square_cdf(x, df, nc)
+            }
+        };
+        let (share, money) = if self.beta < 1.0 {
+            (probability(a, b + 2.0, c, call), probability(c, b, a, !call))
+        } else {
+            (probability(c, -b, a, call), probability(a, 2.0 - b, c, !call))
+        };
+        let spot_leg = s * (-m.dividend_yield * t).exp();
+        let strike_leg = k * (-m.risk_free_rate * t).exp();
+        match option_type {
+            OptionType::Call => spot_leg * share - strike_leg * money,
+            OptionType::Put => strike_leg * money - spot_leg * share,
+        }
+    }
+
+    /// Calculate all Greeks by finite differences; vega is to the local volatility at the spot
+    pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        Greeks::from_finite_differences(&self.model, |m| Cev { model: *m, ..*self }.price(option_type))
+    }
+
+    /// Elasticity implied by an at-the-money skew, β ≈ 1 + 2·skew/σ_ATM
+    ///
+    /// Hagan and Woodward's expansion gives an implied volatility slope of
+    /// -(1 - β)/2·σ_ATM per unit of log-strike at the money.
+    ///
+    /// # Arguments
+    /// * `atm_vol` - At-the-money implied volatility
+    /// * `skew` - Slope of implied volatility in log-strike, e.g. -0.1
+    pub fn implied_beta(atm_vol: f64, skew: f64) -> Result<f64, BlackScholesError> {
+        let atm_vol = validation::positive("ATM volatility", atm_vol)?;
+        let skew = validation::finite("Skew", skew)?;
+        Ok((1.0 + 2.0 * skew / atm_vol).clamp(0.0, MAX_BETA))
+    }
+
+    /// Fit local volatility and elasticity to one expiry's smile
+    ///
+    /// Starts from the skew of a straight line through the quotes in
+    /// log-moneyness and minimizes vega-scaled price errors of out-of-the-money
+    /// options (implied volatility errors to first order) with Nelder-Mead.
+    ///
+    /// # Arguments
+    /// * `market` - Spot, rates and expiry; its strike and volatility are ignored
+    /// * `quotes` - `(strike, implied_vol)` pairs at the market's expiry, at least two
+    pub fn calibrate(market: &BlackScholes, quotes: &[(f64, f64)]) -> Result<CevFit, BlackScholesError> {
+        if quotes.len() < 2 {
+            return Err(BlackScholesError::invalid("CEV calibration needs at least two quotes"));
+        }
+        let forward = market.spot_price
+            * ((market.risk_free_rate - market.dividend_yield) * market.time_to_expiry).exp();
+        let mut targets = Vec::with_capacity(quotes.len());
+        for &(strike, vol) in quotes {
+            let quoted = BlackScholes {
+                strike_price: validation::positive("Strike price", strike)?,
+                volatility: validation::positive("Implied volatility", vol)?,
+                ..*market
+            };
+            let option_type = if strike < forward { OptionType::Put } else { OptionType::Call };
+            let vega = quoted.greeks(option_type).vega * 100.0;
+            targets.push((quoted, option_type, quoted.price(option_type), vega));
+        }
+
+        // Least-squares line vol = level + skew·ln(K/F) for the starting point
+        let n = quotes.len() as f64;
+        let xs: Vec<f64> = quotes.iter().map(|&(k, _)| (k / forward).ln()).collect();
+        let (mean_x, mean_y) = (xs.iter().sum::<f64>() / n, quotes.iter().map(|q| q.1).sum::<f64>() / n);
+        let sxx: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+        let sxy: f64 = xs.iter().zip(quotes).map(|(x, q)| (x - mean_x) * (q.1 - mean_y)).sum();
+        let skew = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+        let level = mean_y - skew * mean_x;
+        let start = [level.ln(), Cev::implied_beta(level, skew)?];
+
+        let build = |x: &[f64]| Cev {
+            model: BlackScholes {
+                volatility: x[0].exp(),
+                ..*market
+            },
+            beta: x[1],
+        };
+        let objective = |x: &[f64]| {
+            if !(0.0..=MAX_BETA).contains(&x[1]) {
+                return 1e10 * (1.0 + x[1].abs());
+            }
+            let cev = build(x);
+            targets
+                .iter()
+                .map(|(quoted, option_type, price, vega)| {
+                    let model = Cev {
+                        model: BlackScholes {
+                            strike_price: quoted.strike_price,
+                            ..cev.model
+                        },
+                        ..cev
+                    };
+                    ((model.price(*option_type) - price) / vega.max(1e-8)).powi(2)
+                })
+                .sum::<f64>()
+        };
+        let best = nelder_mead(objective, &start, 0.1, 1e-18, 2000);
+        let fitted = build(&best.x);
+
+        let mut squared = 0.0;
+        let mut max_error: f64 = 0.0;
+        for ((quoted, option_type, _, _), &(_, vol)) in targets.iter().zip(quotes) {
+            let price = Cev {
+                model: BlackScholes {
+                    strike_price: quoted.strike_price,
+                    ..fitted.model
+                },
+                ..fitted
+            }
+            .price(*option_type);
+            let implied = quoted.implied_volatility(*option_type, price, IV_MAX_ITERATIONS, IV_TOLERANCE)?;
+            squared += (implied - vol).powi(2);
+            max_error = max_error.max((implied - vol).abs());
+        }
+        Ok(CevFit {
+            model: fitted,
+            rmse: (squared / n).sqrt(),
+            max_error,
+        })
+    }
+}
+
+impl PathModel for Cev {
+    fn initial_spot(&self) -> f64 {
+        self.model.spot_price
+    }
+
+    /// Euler steps on the spot, absorbed at zero
+    fn simulate_path(&self, times: &[f64], rng: &mut Rng, path: &mut [f64]) {
+        let m = &self.model;
+        let carry = m.risk_free_rate - m.dividend_yield;
+        let delta = self.delta();
+        let mut spot = m.spot_price;
+        let mut previous = 0.0;
+        for (t, s) in times.iter().zip(path.iter_mut()) {
+            let steps = ((t - previous) * STEPS_PER_YEAR).ceil().max(1.0);
+            let dt = (t - previous) / steps;
+            for _ in 0..steps as usize {
+                if spot <= 0.0 {
+                    break;
+                }
+                spot += carry * spot * dt + delta * spot.powf(self.beta) * dt.sqrt() * rng.normal();
+            }
+            spot = spot.max(0.0);
+            *s = spot;
+            previous = *t;
+        }
+    }
+}
+
+impl EuropeanModel for Cev {
+    fn spot(&self) -> f64 {
+        self.model.spot_price
+    }
+
+    fn rate(&self, _expiry: f64) -> f64 {
+        self.model.risk_free_rate
+    }
+
+    fn dividend_yield(&self, _expiry: f64) -> f64 {
+        self.model.dividend_yield
+    }
+
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        let vol = self.model.calendar_volatility();
+        let model = BlackScholes {
+            strike_price: strike,
+            time_to_expiry: expiry,
+            vol_time: expiry,
+            volatility: vol,
+            ..self.model
+        };
+        Cev { model, ..*self }.price(option_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monte_carlo::MonteCarlo;
+
+    fn cev(strike: f64, beta: f64) -> Cev {
+        let bs = BlackScholes::new(100.0, strike, 1.0, 0.05, 0.25, 0.02).unwrap();
+        Cev::new(bs, beta).unwrap()
+    }
+
+    fn implied(model: &Cev, option_type: OptionType) -> f64 {
+        let price = model.price(option_type);
+        model.model.implied_volatility(option_type, price, 100, 1e-12).unwrap()
+    }
+
+    #[test]
+    fn test_lognormal_limit_and_parity() {
+        let bs = cev(110.0, 1.0).model;
+        assert_eq!(cev(110.0, 1.0).price(OptionType::Call), bs.price(OptionType::Call));
+        // Both branches of the chi-square formula approach Black-Scholes
+        for beta in [0.98, 1.02] {
+            let m = cev(110.0, beta);
+            assert!((m.price(OptionType::Call) - bs.price(OptionType::Call)).abs() < 0.02);
+            let parity = 100.0 * (-0.02_f64).exp() - 110.0 * (-0.05_f64).exp();
+            assert!((m.price(OptionType::Call) - m.price(OptionType::Put) - parity).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_elasticity_sets_skew() {
+        // β < 1: downside puts richer than upside calls in implied volatility
+        let low = implied(&cev(80.0, 0.5), OptionType::Put);
+        let high = implied(&cev(120.0, 0.5), OptionType::Call);
+        assert!(low > 0.26 && high < 0.245, "{low} {high}");
+        let slope = (high - low) / (120.0_f64 / 80.0).ln();
+        assert!((Cev::implied_beta(0.25, slope).unwrap() - 0.5).abs() < 0.1);
+
+        let low = implied(&cev(80.0, 1.5), OptionType::Put);
+        let high = implied(&cev(120.0, 1.5), OptionType::Call);
+        assert!(low < high);
+    }
+
+    #[test]
+    fn test_monte_carlo_agrees_with_formula() {
+        let m = cev(95.0, 0.5);
+        let engine = MonteCarlo::new(50_000, 5).unwrap();
+        let discount = (-0.05_f64).exp();
+        let mc = engine.price(&m, &[0.5, 1.0], discount, |path| (95.0 - path[1]).max(0.0)).unwrap();
+        let formula = m.price(OptionType::Put);
+        assert!((mc.price - formula).abs() < 4.0 * mc.std_error + 0.02, "{} vs {}", mc.price, formula);
+    }
+
+    #[test]
+    fn test_calibration_recovers_elasticity() {
+        let truth = cev(100.0, 0.6);
+        let quotes: Vec<(f64, f64)> = [80.0, 90.0, 100.0, 110.0, 120.0]
+            .iter()
+            .map(|&k| {
+                let m = cev(k, 0.6);
+                let option_type = if k < 103.0 { OptionType::Put } else { OptionType::Call };
+                (k, implied(&m, option_type))
+            })
+            .collect();
+        let fit = Cev::calibrate(&truth.model, &quotes).unwrap();
+        assert!((fit.model.beta - 0.6).abs() < 1e-3, "{}", fit.model.beta);
+        assert!((fit.model.model.volatility - 0.25).abs() < 1e-4);
+        assert!(fit.rmse < 1e-5 && fit.max_error < 1e-5);
+        assert!((fit.model.greeks(OptionType::Call).delta - truth.greeks(OptionType::Call).delta).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_validation() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.25, 0.0).unwrap();
+        assert!(Cev::new(bs, -0.1).is_err());
+        assert!(Cev::new(bs, 2.5).is_err());
+        assert!(Cev::new(bs, f64::NAN).is_err());
+        assert!(Cev::implied_beta(0.0, -0.1).is_err());
+        assert!(Cev::calibrate(&bs, &[(100.0, 0.2)]).is_err());
+        assert!(Cev::calibrate(&bs, &[(100.0, 0.2), (-1.0, 0.2)]).is_err());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Maximum Newton iterations when solving chain implied volatilities
+const IV_MAX_ITERATIONS: usize = 100;
+
+/// Price tolerance when solving chain implied volatilities
+const IV_TOLERANCE: f64 = 1e-8;
+
+/// Bid/ask quote for one listed option
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptionQuote {
+    pub strike: f64,
+    pub option_type: OptionType,
+    pub bid: f64,
+    pub ask: f64,
+}
+
+impl OptionQuote {
+    pub fn new(strike: f64, option_type: OptionType, bid: f64, ask: f64) -> Result<Self, BlackScholesError> {
+        let strike = validation::positive("Strike price", strike)?;
+        let bid = validation::non_negative("Bid", bid)?;
+        let ask = validation::finite("Ask", ask)?;
+        if ask < bid {
+            return Err(BlackScholesError::invalid("Quote must satisfy 0 <= bid <= ask"));
+        }
+        Ok(OptionQuote {
+            strike,
+            option_type,
+            bid,
+            ask,
+        })
+    }
+
+    /// Mid price
+    pub fn mid(&self) -> f64 {
+        0.5 * (self.bid + self.ask)
+    }
+}
+
+/// All quotes for one expiry together with the carry assumptions used to value them
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExpirySlice {
+    /// Expiry in years
+    pub expiry: f64,
+    /// Continuously compounded risk-free rate to this expiry
+    pub rate: f64,
+    /// Continuous dividend/borrow yield to this expiry
+    pub dividend_yield: f64,
+    pub quotes: Vec<OptionQuote>,
+}
+
+impl ExpirySlice {
+    pub fn new(expiry: f64, rate: f64, dividend_yield: f64, quotes: Vec<OptionQuote>) -> Result<Self, BlackScholesError> {
+        let expiry = validation::positive("Time to expiry", expiry)?;
+        let rate = validation::finite("Risk-free rate", rate)?;
+        let dividend_yield = validation::finite("Dividend yield", dividend_yield)?;
+        Ok(ExpirySlice {
+            expiry,
+            rate,
+            dividend_yield,
+            quotes,
+        })
+    }
+
+    /// Forward price implied by this slice's carry
+    pub fn forward(&self, spot: f64) -> f64 {
+        spot * ((self.rate - self.dividend_yield) * self.expiry).exp()
+    }
+
+    /// Call-minus-put mid prices at strikes quoted on both sides
+    pub(crate) fn parity_pairs(&self) -> Vec<(f64, f64)> {
+        let mut pairs = Vec::new();
+        for call in self.quotes.iter().filter(|q| q.option_type == OptionType::Call) {
+            if let Some(put) = self
+                .quotes
+                .iter()
+                .find(|q| q.option_type == OptionType::Put && q.strike == call.strike)
+            {
+                pairs.push((call.strike, call.mid() - put.mid()));
+            }
+        }
+        pairs
+    }
+
+    /// Dividend/borrow yield implied by put-call parity given the slice rate
+    ///
+    /// Solves C - P = S·e^(-qT) - K·e^(-rT) at every strike quoted on both
+    /// sides and averages the implied e^(-qT).
+    pub fn implied_dividend_yield(&self, spot: f64) -> Result<f64, BlackScholesError> {
+        let pairs = self.parity_pairs();
+        if pairs.is_empty() {
+            return Err(BlackScholesError::invalid("Need a call and put at the same strike"));
+        }
+        let discount = (-self.rate * self.expiry).exp();
+        let dividend_discount = pairs
+            .iter()
+            .map(|&(k, diff)| (diff + k * discount) / spot)
+            .sum::<f64>()
+            / pairs.len() as f64;
+        if dividend_discount <= 0.0 {
+            return Err(BlackScholesError::invalid("Quotes imply a non-positive forward"));
+        }
+        Ok(-dividend_discount.ln() / self.expiry)
+    }
+
+    /// Rate and dividend yield jointly implied by put-call parity
+    ///
+    /// Regresses C - P on K: the slope is -e^(-rT) and the intercept S·e^(-qT).
+    ///
+    /// # Returns
+    /// `(rate, dividend_yield)` implied by the quotes
+    pub fn implied_carry(&self, spot: f64) -> Result<(f64, f64), BlackScholesError> {
+        let pairs = self.parity_pairs();
+        if pairs.len() < 2 {
+            return Err(BlackScholesError::invalid("Need call/put pairs at two or more strikes"));
+        }
+        let n = pairs.len() as f64;
+        let mean_k = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+        let sxx: f64 = pairs.iter().map(|p| (p.0 - mean_k).powi(2)).sum();
+        let sxy: f64 = pairs.iter().map(|p| (p.0 - mean_k) * (p.1 - mean_y)).sum();
+        let slope = sxy / sxx;
+        let intercept = mean_y - slope * mean_k;
+
+        if slope >= 0.0 || intercept <= 0.0 {
+            return Err(BlackScholesError::invalid("Quotes violate put-call parity bounds"));
+        }
+        let rate = -(-slope).ln() / self.expiry;
+        let dividend_yield = -(intercept / spot).ln() / self.expiry;
+        Ok((rate, dividend_yield))
+    }
+}
+
+/// Implied volatility and Greeks for one quote
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuoteAnalytics {
+    pub expiry: f64,
+    pub strike: f64,
+    pub option_type: OptionType,
+    pub mid: f64,
+    /// Implied volatility of the mid, if it could be solved
+    pub implied_vol: Option<f64>,
+    /// Greeks at the implied volatility, if it could be solved
+    pub greeks: Option<Greeks>,
+}
+
+/// Listed option chain for one underlying, organised by expiry
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptionChain {
+    pub spot: f64,
+    pub slices: Vec<ExpirySlice>,
+}
+
+impl OptionChain {
+    pub fn new(spot: f64, mut slices: Vec<ExpirySlice>) -> Result<Self, BlackScholesError> {
+        let spot = validation::positive("Spot price", spot)?;
+        slices.sort_by(|a, b| a.expiry.total_cmp(&b.expiry));
+        Ok(OptionChain { spot, slices })
+    }
+
+    /// Set each slice's rate and dividend yield from term structures
+    ///
+    /// # Arguments
+    /// * `carry` - Maps an expiry to its `(rate, dividend_yield)`, e.g. read off curves
+    pub fn apply_carry<F: Fn(f64) -> (f64, f64)>(&mut self, carry: F) {
+        for slice in &mut self.slices {
+            let (rate, dividend_yield) = carry(slice.expiry);
+            slice.rate = rate;
+            slice.dividend_yield = dividend_yield;
+        }
+    }
+
+    /// Replace each slice's dividend yield with the one implied by put-call parity
+    ///
+    /// Slices without call/put pairs keep their existing assumption.
+    pub fn imply_dividends(&mut self) {
+        for slice in &mut self.slices {
+            if let Ok(q) = slice.implied_dividend_yield(self.spot) {
+                slice.dividend_yield = q;
+            }
+        }
+    }
+
+    /// Solve implied volatility and Greeks for every quote using its own slice's carry
+    pub fn analyze(&self) -> Vec<QuoteAnalytics> {
+        let mut rows = Vec::new();
+        for slice in &self.slices {
+            for quote in &slice.quotes {
+                let mid = quote.mid();
+                let solved = BlackScholes::new(self.spot, quote.strike, slice.expiry, slice.rate, 0.2, slice.dividend_yield)
+                    .and_then(|bs| {
+                        let vol = bs.implied_volatility(quote.option_type, mid, IV_MAX_ITERATIONS, IV_TOLERANCE)?;
+                        let solved = BlackScholes { volatility: vol, ..bs };
+                        Ok((vol, solved.greeks(quote.option_type)))
+                    })
+                    .ok();
+
+                rows.push(QuoteAnalytics {
+                    expiry: slice.expiry,
+                    strike: quote.strike,
+                    option_type: quote.option_type,
+                    mid,
+                    implied_vol: solved.map(|s| s.0),
+                    greeks: solved.map(|s| s.1),
+                });
+            }
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slice_from_model(spot: f64, expiry: f64, rate: f64, q: f64, vol: f64) -> ExpirySlice {
+        let quotes = [90.0, 100.0, 110.0]
+            .iter()
+            .flat_map(|&k| {
+                [OptionType::Call, OptionType::Put].map(|ot| {
+                    let p = BlackScholes::new(spot, k, expiry, rate, vol, q).unwrap().price(ot);
+                    OptionQuote::new(k, ot, p - 0.01, p + 0.01).unwrap()
+                })
+            })
+            .collect();
+        ExpirySlice::new(expiry, rate, q, quotes).unwrap()
+    }
+
+    #[test]
+    fn test_per_expiry_carry_recovers_vols() {
+        let chain = OptionChain::new(
+            100.0,
+            vec![
+                slice_from_model(100.0, 0.25, 0.01, 0.0, 0.3),
+                slice_from_model(100.0, 2.0, 0.045, 0.02, 0.22),
+            ],
+        )
+        .unwrap();
+
+        for row in chain.analyze() {
+            let expected = if row.expiry < 1.0 { 0.3 } else { 0.22 };
+            assert!((row.implied_vol.unwrap() - expected).abs() < 1e-4);
+            assert!(row.greeks.is_some());
+        }
+    }
+
+    #[test]
+    fn test_flat_rate_skews_long_dated_vols() {
+        let mut chain = OptionChain::new(100.0, vec![slice_from_model(100.0, 2.0, 0.045, 0.02, 0.22)]).unwrap();
+        chain.apply_carry(|_| (0.01, 0.0));
+
+        let call = chain
+            .analyze()
+            .into_iter()
+            .find(|r| r.option_type == OptionType::Call && r.strike == 100.0)
+            .unwrap();
+        assert!((call.implied_vol.unwrap() - 0.22).abs() > 0.01);
+    }
+
+    #[test]
+    fn test_implied_carry_from_parity() {
+        let mut slice = slice_from_model(100.0, 1.0, 0.04, 0.015, 0.25);
+        let (rate, q) = slice.implied_carry(100.0).unwrap();
+        assert!((rate - 0.04).abs() < 1e-6);
+        assert!((q - 0.015).abs() < 1e-6);
+
+        slice.dividend_yield = 0.0;
+        assert!((slice.implied_dividend_yield(100.0).unwrap() - 0.015).abs() < 1e-6);
+
+        let mut chain = OptionChain::new(100.0, vec![slice]).unwrap();
+        chain.imply_dividends();
+        assert!((chain.slices[0].dividend_yield - 0.015).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_quotes() {
+        assert!(OptionQuote::new(100.0, OptionType::Call, 2.0, 1.0).is_err());
+        assert!(OptionQuote::new(-1.0, OptionType::Call, 1.0, 2.0).is_err());
+        assert!(ExpirySlice::new(0.0, 0.01, 0.0, vec![]).is_err());
+        assert!(OptionQuote::new(100.0, OptionType::Call, f64::NAN, 2.0).is_err());
+        assert!(ExpirySlice::new(1.0, f64::INFINITY, 0.0, vec![]).is_err());
+    }
+
+    #[test]
+    fn test_clamp_policy_repairs_negative_bid() {
+        let quote = validation::with_strictness(validation::Strictness::Clamp, || {
+            OptionQuote::new(100.0, OptionType::Put, -0.01, 0.05)
+        })
+        .unwrap();
+        assert_eq!(quote.bid, 0.0);
+        // NaN is never repaired, whatever the policy
+        assert!(validation::with_strictness(validation::Strictness::Clamp, || {
+            OptionQuote::new(100.0, OptionType::Put, f64::NAN, 0.05)
+        })
+        .is_err());
+    }
+}
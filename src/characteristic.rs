@@ -0,0 +1,57 @@
+use crate::black_scholes::BlackScholes;
+use crate::math::Complex;
+
+/// Risk-neutral characteristic function of the log-return ln(S_t / S_0)
+///
+/// Any model exposing its characteristic function can be plugged into the
+/// moment extraction and transform-based tools without further work.
+pub trait CharacteristicFunction {
+    /// Evaluate φ(u) = E[exp(i·u·ln(S_t / S_0))] at horizon `t` (in years)
+    fn char_fn(&self, u: Complex, t: f64) -> Complex;
+}
+
+impl CharacteristicFunction for BlackScholes {
+    /// Gaussian log-return with drift (r - q - σ²/2) and variance σ²t, σ on the calendar clock
+    fn char_fn(&self, u: Complex, t: f64) -> Complex {
+        let sigma = self.calendar_volatility();
+        let drift = (self.risk_free_rate - self.dividend_yield - 0.5 * sigma * sigma) * t;
+        let variance = sigma * sigma * t;
+        (Complex::I * u * drift - 0.5 * variance * u * u).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::OptionType;
+    use crate::fourier::{CosMethod, TransformPricer};
+    use crate::time_scale::{DaySchedule, TimeScale};
+
+    #[test]
+    fn test_char_fn_at_zero_is_one() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let phi = bs.char_fn(Complex::real(0.0), 1.0);
+        assert!((phi.re - 1.0).abs() < 1e-15 && phi.im.abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_martingale_condition() {
+        // E[S_t / S_0] = exp((r - q)t), i.e. φ(-i) = exp((r - q)t)
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.02).unwrap();
+        let phi = bs.char_fn(-Complex::I, 2.0);
+        assert!((phi.re - (0.03_f64 * 2.0).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_business_time_fourier_price_matches_closed_form() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.01).unwrap();
+        let business = bs.with_time_scale(TimeScale::Trading, &DaySchedule::weekdays(30, 4)).unwrap();
+        for ot in [OptionType::Call, OptionType::Put] {
+            let fourier = CosMethod::default().prices(&business, ot, business.time_to_expiry, &[97.0, 100.0, 104.0]);
+            for (strike, price) in [97.0, 100.0, 104.0].iter().zip(fourier) {
+                let exact = BlackScholes { strike_price: *strike, ..business }.price(ot);
+                assert!((price - exact).abs() < 1e-10, "{strike}: {price} vs {exact}");
+            }
+        }
+    }
+}
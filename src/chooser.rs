@@ -0,0 +1,263 @@
+//! Chooser options: Rubinstein (1991) simple and complex choosers
+
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::math::{bivariate_norm_cdf, norm_cdf};
+use crate::validation;
+
+/// Relative width at which the critical-spot bisection stops
+const CRITICAL_SPOT_TOLERANCE: f64 = 1e-13;
+
+/// Maximum bisection steps for the critical spot
+const CRITICAL_SPOT_ITERATIONS: usize = 200;
+
+/// Option to decide at `choice_time` whether it is a call or a put
+///
+/// The call has the strike and expiry of `underlying`; the put has its own.
+/// A simple chooser shares them, and by put-call parity is a call to T plus
+/// a put to the choice time. A complex chooser picks the call above the
+/// critical spot where both are worth the same, and is priced with two
+/// bivariate normals.
+///
+/// Fields are private so the inputs validated on construction stay valid.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChooserOption {
+    underlying: BlackScholes,
+    put_strike: f64,
+    put_expiry: f64,
+    choice_time: f64,
+}
+
+impl ChooserOption {
+    /// Chooser between a call and a put with the underlying's strike and expiry
+    pub fn simple(underlying: BlackScholes, choice_time: f64) -> Result<Self, BlackScholesError> {
+        ChooserOption::complex(underlying, underlying.strike_price, underlying.time_to_expiry, choice_time)
+    }
+
+    /// Chooser between the underlying's call and a put with its own strike and expiry
+    ///
+    /// # Arguments
+    /// * `underlying` - Market inputs with the call's strike and expiry
+    /// * `put_strike` - Strike of the put
+    /// * `put_expiry` - Expiry of the put in years
+    /// * `choice_time` - Time of the choice in years, before both expiries
+    pub fn complex(
+        underlying: BlackScholes,
+        put_strike: f64,
+        put_expiry: f64,
+        choice_time: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let put_strike = validation::positive("Put strike", put_strike)?;
+        let put_expiry = validation::positive("Put expiry", put_expiry)?;
+        let choice_time = validation::positive("Choice time", choice_time)?;
+        if choice_time >= underlying.time_to_expiry || choice_time >= put_expiry {
+            return Err(BlackScholesError::invalid("Choice must be made before both options expire"));
+        }
+        Ok(ChooserOption {
+            underlying,
+            put_strike,
+            put_expiry,
+            choice_time,
+        })
+    }
+
+    /// Market inputs with the call's strike and expiry
+    pub fn underlying(&self) -> &BlackScholes {
+        &self.underlying
+    }
+
+    pub fn put_strike(&self) -> f64 {
+        self.put_strike
+    }
+
+    /// Expiry of the put in years
+    pub fn put_expiry(&self) -> f64 {
+        self.put_expiry
+    }
+
+    /// Time of the choice in years, before both expiries
+    pub fn choice_time(&self) -> f64 {
+        self.choice_time
+    }
+
+    /// Whether the call and put share strike and expiry
+    pub fn is_simple(&self) -> bool {
+        self.put_strike == self.underlying.strike_price && self.put_expiry == self.underlying.time_to_expiry
+    }
+
+    /// Values of the call and the put at the choice time if the spot is then `spot`
+    fn values_at_choice(&self, spot: f64) -> (f64, f64) {
+        let call_remaining = self.underlying.time_to_expiry - self.choice_time;
+        let put_remaining = self.put_expiry - self.choice_time;
+        let call = BlackScholes {
+            spot_price: spot,
+            ..self.underlying.aged(call_remaining)
+        };
+        let put = BlackScholes {
+            spot_price: spot,
+            strike_price: self.put_strike,
+            ..self.underlying.aged(put_remaining)
+        };
+        (call.price(OptionType::Call), put.price(OptionType::Put))
+    }
+
+    /// Spot at the choice time where the call and the put are worth the same
+    ///
+    /// Always exists: the call rises from zero and the put falls to zero.
+    pub fn critical_spot(&self) -> f64 {
+        let gap = |spot: f64| {
+            let (call, put) = self.values_at_choice(spot);
+            call - put
+        };
+        let (mut lo, mut hi) = (0.0, self.underlying.strike_price.max(self.put_strike));
+        while gap(hi) < 0.0 {
+            lo = hi;
+            hi *= 2.0;
+        }
+        for _ in 0..CRITICAL_SPOT_ITERATIONS {
+            let mid = 0.5 * (lo + hi);
+            if gap(mid) < 0.0 {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+            if hi - lo <= CRITICAL_SPOT_TOLERANCE * hi {
+                break;
+            }
+        }
+        0.5 * (lo + hi)
+    }
+
+    /// Price of the chooser, Rubinstein's closed form for simple choosers
+    ///
+    /// All three dates are read on the calendar clock, with the underlying's
+    /// variance spread evenly over it through its calendar volatility.
+    pub fn price(&self) -> f64 {
+        let bs = &self.underlying;
+        let t = self.choice_time;
+        let carry = bs.risk_free_rate - bs.dividend_yield;
+        let sigma = bs.calendar_volatility();
+        let (w, wt) = (sigma * bs.time_to_expiry.sqrt(), sigma * t.sqrt());
+
+        if self.is_simple() {
+            // Call to T plus a put to t struck at K·e^(-b(T-t))
+            let big_t = bs.time_to_expiry;
+            let d = ((bs.spot_price / bs.strike_price).ln() + carry * big_t) / w + 0.5 * w;
+            let y = ((bs.spot_price / bs.strike_price).ln() + carry * big_t) / wt + 0.5 * wt;
+            let asset = bs.spot_price * (-bs.dividend_yield * big_t).exp();
+            let strike = bs.strike_price * (-bs.risk_free_rate * big_t).exp();
+            return asset * (norm_cdf(d) - norm_cdf(-y)) - strike * (norm_cdf(d - w) - norm_cdf(-y + wt));
+        }
+
+        let (tc, tp) = (bs.time_to_expiry, self.put_expiry);
+        let wp = sigma * tp.sqrt();
+        let d1 = ((bs.spot_price / self.critical_spot()).ln() + carry * t) / wt + 0.5 * wt;
+        let d2 = d1 - wt;
+        let y1 = ((bs.spot_price / bs.strike_price).ln() + carry * tc) / w + 0.5 * w;
+        let y2 = ((bs.spot_price / self.put_strike).ln() + carry * tp) / wp + 0.5 * wp;
+        let (rho1, rho2) = ((t / tc).sqrt(), (t / tp).sqrt());
+        let m = bivariate_norm_cdf;
+
+        bs.spot_price * (-bs.dividend_yield * tc).exp() * m(d1, y1, rho1)
+            - bs.strike_price * (-bs.risk_free_rate * tc).exp() * m(d2, y1 - w, rho1)
+            - bs.spot_price * (-bs.dividend_yield * tp).exp() * m(-d1, -y2, rho2)
+            + self.put_strike * (-bs.risk_free_rate * tp).exp() * m(-d2, -y2 + wp, rho2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::norm_pdf;
+    use crate::time_scale::{DaySchedule, TimeScale};
+
+    #[test]
+    fn test_reference_values() {
+        // Haug, The Complete Guide to Option Pricing Formulas
+        let simple = BlackScholes::new(50.0, 50.0, 0.5, 0.08, 0.25, 0.0).unwrap();
+        let price = ChooserOption::simple(simple, 0.25).unwrap().price();
+        assert!((price - 6.1071).abs() < 5e-4, "{price}");
+
+        let complex = BlackScholes::new(50.0, 55.0, 0.5, 0.1, 0.35, 0.05).unwrap();
+        let price = ChooserOption::complex(complex, 48.0, 0.5833, 0.25).unwrap().price();
+        assert!((price - 6.0508).abs() < 5e-4, "{price}");
+    }
+
+    #[test]
+    fn test_complex_formula_reduces_to_simple() {
+        let bs = BlackScholes::new(100.0, 105.0, 1.0, 0.04, 0.3, 0.02).unwrap();
+        let simple = ChooserOption::simple(bs, 0.4).unwrap();
+        // Nudge the put strike so the bivariate branch runs on effectively the same contract
+        let complex = ChooserOption::complex(bs, 105.0 * (1.0 + 1e-12), 1.0, 0.4).unwrap();
+        assert!(simple.is_simple() && !complex.is_simple());
+        assert!((simple.price() - complex.price()).abs() < 1e-6);
+        // At the critical spot the call and put are worth the same
+        let (call, put) = simple.values_at_choice(simple.critical_spot());
+        assert!((call - put).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_matches_integration_over_the_choice_time() {
+        let bs = BlackScholes::new(100.0, 110.0, 1.0, 0.05, 0.25, 0.02).unwrap();
+        for chooser in [
+            ChooserOption::simple(bs, 0.3).unwrap(),
+            ChooserOption::complex(bs, 95.0, 0.75, 0.3).unwrap(),
+        ] {
+            let t = chooser.choice_time;
+            let drift = (bs.risk_free_rate - bs.dividend_yield - 0.5 * bs.volatility.powi(2)) * t;
+            // Trapezoid over the standard normal driving S(t), ±8 standard deviations
+            let n = 16_000;
+            let expected: f64 = (0..=n)
+                .map(|i| {
+                    let z = -8.0 + 16.0 * i as f64 / n as f64;
+                    let spot = bs.spot_price * (drift + bs.volatility * t.sqrt() * z).exp();
+                    let (call, put) = chooser.values_at_choice(spot);
+                    let weight = if i == 0 || i == n { 0.5 } else { 1.0 };
+                    weight * call.max(put) * norm_pdf(z) * 16.0 / n as f64
+                })
+                .sum::<f64>()
+                * (-bs.risk_free_rate * t).exp();
+            assert!((chooser.price() - expected).abs() < 1e-5, "{} vs {expected}", chooser.price());
+        }
+    }
+
+    #[test]
+    fn test_simple_chooser_bounds() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.2, 0.0).unwrap();
+        let (call, put) = (bs.price(OptionType::Call), bs.price(OptionType::Put));
+        let early = ChooserOption::simple(bs, 1e-6).unwrap().price();
+        let late = ChooserOption::simple(bs, 1.0 - 1e-9).unwrap().price();
+        let middle = ChooserOption::simple(bs, 0.5).unwrap().price();
+        // Choosing now is the dearer vanilla; choosing at expiry is the straddle
+        assert!((early - call.max(put)).abs() < 1e-3);
+        assert!((late - (call + put)).abs() < 1e-3);
+        assert!(early < middle && middle < late);
+    }
+
+    #[test]
+    fn test_business_time_uses_calendar_variance() {
+        let market = BlackScholes::new(50.0, 55.0, 0.5, 0.1, 0.35, 0.05).unwrap();
+        let bs = market.with_time_scale(TimeScale::Trading, &DaySchedule::weekdays(182, 0)).unwrap();
+        let calendar = BlackScholes::new(50.0, 55.0, bs.time_to_expiry, 0.1, bs.calendar_volatility(), 0.05).unwrap();
+        for (business, expected) in [
+            (ChooserOption::simple(bs, 0.25), ChooserOption::simple(calendar, 0.25)),
+            (ChooserOption::complex(bs, 48.0, 0.4, 0.25), ChooserOption::complex(calendar, 48.0, 0.4, 0.25)),
+        ] {
+            assert!((business.unwrap().price() - expected.unwrap().price()).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_invalid_choosers() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        assert!(ChooserOption::simple(bs, 1.0).is_err());
+        assert!(ChooserOption::simple(bs, 0.0).is_err());
+        assert!(ChooserOption::complex(bs, 100.0, 0.5, 0.5).is_err());
+        assert!(ChooserOption::complex(bs, -1.0, 1.0, 0.5).is_err());
+
+        let chooser = ChooserOption::complex(bs, 95.0, 0.75, 0.5).unwrap();
+        assert_eq!((chooser.put_strike(), chooser.put_expiry(), chooser.choice_time()), (95.0, 0.75, 0.5));
+        assert_eq!(chooser.underlying().strike_price, 100.0);
+    }
+}
@@ -0,0 +1,312 @@
+//! Forward-start options and cliquets (ratchets) built from them
+
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::monte_carlo::{McResult, MonteCarlo};
+use crate::multi_asset::CorrelatedPaths;
+use crate::time_scale::TimeScale;
+use crate::validation;
+
+/// European option struck at a fraction of the spot on a future date
+///
+/// At `start` the strike is fixed at `strike_ratio`·S(start). Black-Scholes
+/// prices are homogeneous of degree one in spot and strike, so the option is
+/// worth S(start) times an option on a unit spot; that is a traded quantity
+/// worth S·e^(-q·start) today (Rubinstein, 1991).
+///
+/// Fields are private so the inputs validated on construction stay valid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForwardStartOption {
+    spot: f64,
+    strike_ratio: f64,
+    start: f64,
+    expiry: f64,
+    risk_free_rate: f64,
+    volatility: f64,
+    dividend_yield: f64,
+}
+
+impl ForwardStartOption {
+    /// Create a new forward-start option
+    ///
+    /// # Arguments
+    /// * `spot` - Current price of the underlying (S)
+    /// * `strike_ratio` - Strike as a fraction of the spot at `start` (α)
+    /// * `start` - Strike-setting date in years (t), zero for a vanilla struck at α·S
+    /// * `expiry` - Expiry in years (T > t)
+    /// * `risk_free_rate` - Continuously compounded rate (r)
+    /// * `volatility` - Volatility of the underlying (σ)
+    /// * `dividend_yield` - Continuous dividend yield (q)
+    pub fn new(
+        spot: f64,
+        strike_ratio: f64,
+        start: f64,
+        expiry: f64,
+        risk_free_rate: f64,
+        volatility: f64,
+        dividend_yield: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let start = validation::non_negative("Start date", start)?;
+        if expiry <= start {
+            return Err(BlackScholesError::invalid("Expiry must be after the start date"));
+        }
+        let spot = validation::positive("Spot price", spot)?;
+        // Validates the remaining inputs on the option struck at the start date
+        let unit = BlackScholes::new(1.0, strike_ratio, expiry - start, risk_free_rate, volatility, dividend_yield)?;
+        Ok(ForwardStartOption {
+            spot,
+            strike_ratio: unit.strike_price,
+            start,
+            expiry,
+            risk_free_rate: unit.risk_free_rate,
+            volatility: unit.volatility,
+            dividend_yield: unit.dividend_yield,
+        })
+    }
+
+    pub fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    /// Strike as a fraction of the spot at the start date (1.0 = at-the-money)
+    pub fn strike_ratio(&self) -> f64 {
+        self.strike_ratio
+    }
+
+    /// Strike-setting date in years
+    pub fn start(&self) -> f64 {
+        self.start
+    }
+
+    /// Expiry in years, after the start
+    pub fn expiry(&self) -> f64 {
+        self.expiry
+    }
+
+    /// Continuously compounded risk-free rate (annual)
+    pub fn risk_free_rate(&self) -> f64 {
+        self.risk_free_rate
+    }
+
+    /// Volatility of the underlying (annual)
+    pub fn volatility(&self) -> f64 {
+        self.volatility
+    }
+
+    /// Continuous dividend yield (annual)
+    pub fn dividend_yield(&self) -> f64 {
+        self.dividend_yield
+    }
+
+    /// Option on a unit spot over the period after the start date
+    fn unit_option(&self) -> BlackScholes {
+        let period = self.expiry - self.start;
+        BlackScholes {
+            spot_price: 1.0,
+            strike_price: self.strike_ratio,
+            time_to_expiry: period,
+            risk_free_rate: self.risk_free_rate,
+            volatility: self.volatility,
+            dividend_yield: self.dividend_yield,
+            vol_time: period,
+            time_scale: TimeScale::Calendar,
+        }
+    }
+
+    /// Rubinstein's price, S·e^(-q·t)·V(1, α, T - t)
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        self.start_discount() * self.unit_option().price(option_type)
+    }
+
+    /// Sensitivity to today's spot, which scales the whole price
+    pub fn delta(&self, option_type: OptionType) -> f64 {
+        self.price(option_type) / self.spot
+    }
+
+    /// Sensitivity to volatility per 1% move, which only acts after the start date
+    pub fn vega(&self) -> f64 {
+        self.start_discount() * self.unit_option().greeks(OptionType::Call).vega
+    }
+
+    /// Today's value of one unit of spot delivered at the start date, S·e^(-q·t)
+    fn start_discount(&self) -> f64 {
+        self.spot * (-self.dividend_yield * self.start).exp()
+    }
+}
+
+/// Cliquet paying the sum of clamped periodic returns at maturity
+///
+/// Each period's return R_i = S_i/S_(i-1) - 1 is clamped to
+/// [local_floor, local_cap]; the sum is then clamped to
+/// [global_floor, global_cap] and paid per unit notional at the last reset.
+/// With a zero local floor and no caps it is a ratchet, a strip of
+/// at-the-money forward-start calls on returns.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cliquet {
+    /// Increasing end of each period in years; the first period starts today
+    pub reset_times: Vec<f64>,
+    pub local_floor: f64,
+    pub local_cap: f64,
+    pub global_floor: f64,
+    pub global_cap: f64,
+}
+
+impl Cliquet {
+    /// Ratchet with `periods` equal periods to `maturity`: local floor zero, no caps
+    pub fn new(maturity: f64, periods: usize) -> Result<Self, BlackScholesError> {
+        let maturity = validation::positive("Maturity", maturity)?;
+        if periods == 0 {
+            return Err(BlackScholesError::invalid("A cliquet needs at least one period"));
+        }
+        Cliquet::with_resets((1..=periods).map(|i| maturity * i as f64 / periods as f64).collect())
+    }
+
+    /// Ratchet resetting on the given dates, the last being maturity
+    pub fn with_resets(reset_times: Vec<f64>) -> Result<Self, BlackScholesError> {
+        if reset_times.is_empty() || reset_times[0] <= 0.0 || reset_times.windows(2).any(|t| t[1] <= t[0]) {
+            return Err(BlackScholesError::invalid("Reset times must be positive and strictly increasing"));
+        }
+        validation::all_finite("Reset times", &reset_times)?;
+        Ok(Cliquet {
+            reset_times,
+            local_floor: 0.0,
+            local_cap: f64::INFINITY,
+            global_floor: f64::NEG_INFINITY,
+            global_cap: f64::INFINITY,
+        })
+    }
+
+    /// Clamp each period's return to [floor, cap]; infinities remove a bound
+    pub fn with_local_bounds(mut self, floor: f64, cap: f64) -> Result<Self, BlackScholesError> {
+        (self.local_floor, self.local_cap) = Cliquet::bounds("Local", floor, cap)?;
+        Ok(self)
+    }
+
+    /// Clamp the summed returns to [floor, cap]; infinities remove a bound
+    pub fn with_global_bounds(mut self, floor: f64, cap: f64) -> Result<Self, BlackScholesError> {
+        (self.global_floor, self.global_cap) = Cliquet::bounds("Global", floor, cap)?;
+        Ok(self)
+    }
+
+    fn bounds(scope: &str, floor: f64, cap: f64) -> Result<(f64, f64), BlackScholesError> {
+        if floor.is_nan() || cap.is_nan() || cap < floor {
+            return Err(BlackScholesError::invalid(&format!("{scope} cap must not be below the floor")));
+        }
+        Ok((floor, cap))
+    }
+
+    /// Last reset, when the payoff is paid
+    pub fn maturity(&self) -> f64 {
+        self.reset_times[self.reset_times.len() - 1]
+    }
+
+    /// Payoff per unit notional given the price at each reset, starting from `spot`
+    pub fn payoff(&self, spot: f64, fixings: &[f64]) -> f64 {
+        let mut previous = spot;
+        let mut sum = 0.0;
+        for &price in fixings {
+            sum += (price / previous - 1.0).clamp(self.local_floor, self.local_cap);
+            previous = price;
+        }
+        sum.clamp(self.global_floor, self.global_cap)
+    }
+
+    /// Value per unit notional on simulated paths of a single asset
+    pub fn price(&self, model: &CorrelatedPaths, mc: &MonteCarlo) -> Result<McResult, BlackScholesError> {
+        if model.assets.len() != 1 {
+            return Err(BlackScholesError::invalid("Cliquets need a single-asset model"));
+        }
+        let spot = model.assets[0].spot;
+        let mut paths = vec![vec![0.0; self.reset_times.len()]];
+        let discount = (-model.rate * self.maturity()).exp();
+        Ok(mc.estimate(discount, |rng| {
+            model.simulate(&self.reset_times, rng, &mut paths);
+            self.payoff(spot, &paths[0])
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heston::HestonParams;
+    use crate::multi_asset::Asset;
+
+    #[test]
+    fn test_forward_start_homogeneity() {
+        // Starting today it is a vanilla struck at α·S
+        let today = ForwardStartOption::new(100.0, 1.1, 0.0, 1.0, 0.05, 0.2, 0.02).unwrap();
+        let vanilla = BlackScholes::new(100.0, 110.0, 1.0, 0.05, 0.2, 0.02).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            assert!((today.price(option_type) - vanilla.price(option_type)).abs() < 1e-12);
+        }
+        // Without dividends the start date only moves the strike-setting forward
+        let later = ForwardStartOption::new(100.0, 1.0, 0.5, 1.5, 0.05, 0.2, 0.0).unwrap();
+        let atm = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        assert!((later.price(OptionType::Call) - atm.price(OptionType::Call)).abs() < 1e-12);
+        assert!((later.delta(OptionType::Call) * 100.0 - later.price(OptionType::Call)).abs() < 1e-12);
+        assert!((later.vega() - atm.greeks(OptionType::Call).vega).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_forward_start_matches_simulation() {
+        let option = ForwardStartOption::new(100.0, 0.95, 0.5, 1.25, 0.04, 0.3, 0.02).unwrap();
+        let model = CorrelatedPaths::single(0.04, Asset::lognormal(100.0, 0.02, 0.3).unwrap()).unwrap();
+        let mc = MonteCarlo::new(40_000, 5).unwrap();
+        let mut paths = vec![vec![0.0; 2]];
+        let simulated = mc.estimate((-0.04 * 1.25_f64).exp(), |rng| {
+            model.simulate(&[0.5, 1.25], rng, &mut paths);
+            (0.95 * paths[0][0] - paths[0][1]).max(0.0)
+        });
+        let exact = option.price(OptionType::Put);
+        assert!((simulated.price - exact).abs() < 4.0 * simulated.std_error, "{exact} vs {simulated:?}");
+    }
+
+    #[test]
+    fn test_ratchet_is_a_strip_of_forward_start_calls() {
+        let (rate, q, vol) = (0.03, 0.01, 0.25);
+        let model = CorrelatedPaths::single(rate, Asset::lognormal(100.0, q, vol).unwrap()).unwrap();
+        let ratchet = Cliquet::new(1.0, 4).unwrap();
+        let result = ratchet.price(&model, &MonteCarlo::new(40_000, 9).unwrap()).unwrap();
+        // E[max(R_i, 0)] is the forward value of an at-the-money call on a unit spot
+        let unit = BlackScholes::new(1.0, 1.0, 0.25, rate, vol, q).unwrap().price(OptionType::Call);
+        let exact = 4.0 * (-rate * 0.75_f64).exp() * unit;
+        assert!((result.price - exact).abs() < 4.0 * result.std_error, "{exact} vs {result:?}");
+    }
+
+    #[test]
+    fn test_caps_and_floors_bound_the_payoff() {
+        let ratchet = Cliquet::new(1.0, 4).unwrap();
+        let capped = ratchet.clone().with_local_bounds(-0.05, 0.05).unwrap();
+        let guaranteed = capped.clone().with_global_bounds(0.02, f64::INFINITY).unwrap();
+        let fixings = [110.0, 99.0, 104.0, 90.0];
+        assert!((ratchet.payoff(100.0, &fixings) - (0.1 + 5.0 / 99.0)).abs() < 1e-12);
+        assert!(capped.payoff(100.0, &fixings).abs() < 1e-12);
+        assert_eq!(guaranteed.payoff(100.0, &fixings), 0.02);
+
+        let params = HestonParams::new(0.04, 1.5, 0.04, 0.5, -0.7).unwrap();
+        let model = CorrelatedPaths::single(0.03, Asset::heston(100.0, 0.0, params).unwrap()).unwrap();
+        let mc = MonteCarlo::new(10_000, 3).unwrap();
+        let prices: Vec<f64> =
+            [ratchet, capped, guaranteed].iter().map(|c| c.price(&model, &mc).unwrap().price).collect();
+        // The same paths: caps cut the value, a global floor restores some of it
+        assert!(prices[1] < prices[0] && prices[2] > prices[1], "{prices:?}");
+        assert!(prices[2] >= 0.02 * (-0.03_f64).exp() - 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_contracts() {
+        assert!(ForwardStartOption::new(100.0, 1.0, 1.0, 1.0, 0.05, 0.2, 0.0).is_err());
+        assert!(ForwardStartOption::new(100.0, 0.0, 0.5, 1.0, 0.05, 0.2, 0.0).is_err());
+        assert!(ForwardStartOption::new(100.0, 1.0, -0.5, 1.0, 0.05, 0.2, 0.0).is_err());
+        assert!(ForwardStartOption::new(-100.0, 1.0, 0.5, 1.0, 0.05, 0.2, 0.0).is_err());
+        let option = ForwardStartOption::new(100.0, 0.9, 0.5, 1.0, 0.05, 0.2, 0.01).unwrap();
+        assert_eq!((option.strike_ratio(), option.volatility(), option.expiry()), (0.9, 0.2, 1.0));
+        assert!(Cliquet::new(1.0, 0).is_err());
+        assert!(Cliquet::with_resets(vec![0.5, 0.5]).is_err());
+        assert!(Cliquet::new(1.0, 4).unwrap().with_local_bounds(0.05, -0.05).is_err());
+        assert!(Cliquet::new(1.0, 4).unwrap().with_global_bounds(f64::NAN, 0.1).is_err());
+    }
+}
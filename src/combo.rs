@@ -0,0 +1,330 @@
+use crate::black_scholes::BlackScholes;
+use crate::chain::OptionQuote;
+use crate::error::BlackScholesError;
+use crate::market::MarketContext;
+use crate::validation;
+
+/// Bisection steps when solving the package volatility shift
+const SHIFT_ITERATIONS: usize = 200;
+
+/// Lowest volatility any leg may be shifted to
+const MIN_LEG_VOL: f64 = 1e-4;
+
+/// First step of the outward search for a bracketing shift
+const INITIAL_STEP: f64 = 0.01;
+
+/// Largest upward package volatility shift searched
+const MAX_SHIFT: f64 = 5.0;
+
+/// One leg of a multi-leg order
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComboLeg {
+    /// Listed bid/ask for the leg
+    pub quote: OptionQuote,
+    /// Expiry in years
+    pub expiry: f64,
+    /// Signed units per package (positive to buy, negative to sell)
+    pub ratio: f64,
+}
+
+impl ComboLeg {
+    pub fn new(quote: OptionQuote, expiry: f64, ratio: f64) -> Result<Self, BlackScholesError> {
+        let expiry = validation::positive("Time to expiry", expiry)?;
+        let ratio = validation::finite("Leg ratio", ratio)?;
+        if ratio == 0.0 {
+            return Err(BlackScholesError::invalid("Leg ratio must be non-zero"));
+        }
+        Ok(ComboLeg { quote, expiry, ratio })
+    }
+
+    /// Price paid per package for this leg when crossing its spread
+    fn natural(&self, buying: bool) -> f64 {
+        let take_ask = (self.ratio > 0.0) == buying;
+        self.ratio * if take_ask { self.quote.ask } else { self.quote.bid }
+    }
+}
+
+/// A multi-leg option order quoted and worked as a single package
+///
+/// Net prices are per package and signed from the buyer's side: positive is
+/// a net debit paid, negative a net credit received.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComboQuote {
+    pub legs: Vec<ComboLeg>,
+}
+
+/// Package prices and the leg volatilities implied at the net mid
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComboPrice {
+    /// Sum of the leg mids
+    pub net_mid: f64,
+    /// Net price received selling the package by hitting every leg's market
+    pub natural_bid: f64,
+    /// Net price paid buying the package by lifting every leg's market
+    pub natural_ask: f64,
+    /// Package value on the market surface
+    pub theo: f64,
+    /// Uniform volatility shift over the surface that reprices the package to `net_mid`
+    pub vol_shift: f64,
+    /// Leg implied volatilities at the net mid (surface volatility plus `vol_shift`)
+    pub leg_vols: Vec<f64>,
+}
+
+impl ComboPrice {
+    /// Whether the package costs money to buy at mid
+    pub fn is_debit(&self) -> bool {
+        self.net_mid > 0.0
+    }
+}
+
+impl ComboQuote {
+    pub fn new(legs: Vec<ComboLeg>) -> Result<Self, BlackScholesError> {
+        if legs.is_empty() {
+            return Err(BlackScholesError::invalid("Combo needs at least one leg"));
+        }
+        Ok(ComboQuote { legs })
+    }
+
+    /// Net mid price of the package
+    pub fn net_mid(&self) -> f64 {
+        self.legs.iter().map(|leg| leg.ratio * leg.quote.mid()).sum()
+    }
+
+    /// Net price available selling the package leg by leg: bids on bought legs, asks on sold legs
+    pub fn natural_bid(&self) -> f64 {
+        self.legs.iter().map(|leg| leg.natural(false)).sum()
+    }
+
+    /// Net price to buy the package leg by leg: asks on bought legs, bids on sold legs
+    pub fn natural_ask(&self) -> f64 {
+        self.legs.iter().map(|leg| leg.natural(true)).sum()
+    }
+
+    /// Implied volatility of each leg's own mid, using the market's spot and carry
+    pub fn leg_mid_vols(&self, market: &MarketContext) -> Result<Vec<f64>, BlackScholesError> {
+        self.legs
+            .iter()
+            .map(|leg| {
+                market
+                    .model(leg.quote.strike, leg.expiry)?
+                    .implied_volatility(leg.quote.option_type, leg.quote.mid(), 100, 1e-10)
+            })
+            .collect()
+    }
+
+    /// Package value on the market surface
+    pub fn theo(&self, market: &MarketContext) -> Result<f64, BlackScholesError> {
+        Ok(self.package_value(&self.models(market)?, 0.0))
+    }
+
+    /// Uniform shift to every leg's surface volatility that makes the package worth `target`
+    ///
+    /// Package value need not be monotone in the shift (a call spread loses
+    /// value at very high volatility), so the search steps outward from the
+    /// surface in both directions and returns the root nearest zero shift,
+    /// refined by bisection. Shifts range from taking the lowest-vol leg to
+    /// near zero up to +500 vol points.
+    ///
+    /// # Arguments
+    /// * `market` - Spot, carry and reference volatility surface
+    /// * `target` - Net package price (positive debit, negative credit)
+    ///
+    /// # Returns
+    /// Volatility shift as a decimal (0.01 = one vol point)
+    pub fn implied_shift(&self, market: &MarketContext, target: f64) -> Result<f64, BlackScholesError> {
+        let target = validation::finite("Target price", target)?;
+        let models = self.models(market)?;
+        let min_vol = models.iter().map(|m| m.volatility).fold(f64::INFINITY, f64::min);
+        let floor = MIN_LEG_VOL - min_vol;
+        let error = |shift: f64| self.package_value(&models, shift) - target;
+
+        let start = error(0.0);
+        if start == 0.0 {
+            return Ok(0.0);
+        }
+        let (mut up, mut down, mut step) = (0.0, 0.0, INITIAL_STEP);
+        let (mut lo, mut hi) = loop {
+            let (next_up, next_down) = ((up + step).min(MAX_SHIFT), (down - step).max(floor));
+            if (error(next_down) > 0.0) != (start > 0.0) {
+                break (next_down, down);
+            }
+            if (error(next_up) > 0.0) != (start > 0.0) {
+                break (up, next_up);
+            }
+            if next_up == MAX_SHIFT && next_down == floor {
+                return Err(BlackScholesError::no_convergence("Target price is outside the package's volatility range"));
+            }
+            (up, down, step) = (next_up, next_down, 2.0 * step);
+        };
+        let lo_above = error(lo) > 0.0;
+        for _ in 0..SHIFT_ITERATIONS {
+            let mid = 0.5 * (lo + hi);
+            if (error(mid) > 0.0) == lo_above {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+            if hi - lo < 1e-12 {
+                break;
+            }
+        }
+        Ok(0.5 * (lo + hi))
+    }
+
+    /// Leg implied volatilities when the package trades at `package_price`
+    ///
+    /// Every leg moves off the surface by the same shift, so the legs keep
+    /// the surface's skew and term structure while summing to the package price.
+    pub fn leg_vols_at(&self, market: &MarketContext, package_price: f64) -> Result<Vec<f64>, BlackScholesError> {
+        let shift = self.implied_shift(market, package_price)?;
+        Ok(self.models(market)?.iter().map(|m| m.volatility + shift).collect())
+    }
+
+    /// Price the package against the market: net mid, naturals, theo and leg volatilities at mid
+    pub fn price(&self, market: &MarketContext) -> Result<ComboPrice, BlackScholesError> {
+        let net_mid = self.net_mid();
+        let models = self.models(market)?;
+        let vol_shift = self.implied_shift(market, net_mid)?;
+        Ok(ComboPrice {
+            net_mid,
+            natural_bid: self.natural_bid(),
+            natural_ask: self.natural_ask(),
+            theo: self.package_value(&models, 0.0),
+            vol_shift,
+            leg_vols: models.iter().map(|m| m.volatility + vol_shift).collect(),
+        })
+    }
+
+    fn models(&self, market: &MarketContext) -> Result<Vec<BlackScholes>, BlackScholesError> {
+        self.legs.iter().map(|leg| market.model(leg.quote.strike, leg.expiry)).collect()
+    }
+
+    /// Package value with every leg's volatility moved by `shift`
+    fn package_value(&self, models: &[BlackScholes], shift: f64) -> f64 {
+        self.legs
+            .iter()
+            .zip(models)
+            .map(|(leg, model)| {
+                let mut model = *model;
+                model.volatility += shift;
+                leg.ratio * model.price(leg.quote.option_type)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::OptionType;
+    use crate::vol_surface::FlatVol;
+
+    fn market() -> MarketContext {
+        MarketContext::new(100.0, 0.03, 0.01, FlatVol::new(0.2)).unwrap()
+    }
+
+    /// Quote centred on the price at `vol`, `width` wide
+    fn leg(strike: f64, option_type: OptionType, expiry: f64, ratio: f64, vol: f64, width: f64) -> ComboLeg {
+        let mut model = market().model(strike, expiry).unwrap();
+        model.volatility = vol;
+        let mid = model.price(option_type);
+        let quote = OptionQuote::new(strike, option_type, mid - 0.5 * width, mid + 0.5 * width).unwrap();
+        ComboLeg::new(quote, expiry, ratio).unwrap()
+    }
+
+    #[test]
+    fn test_net_and_natural_prices() {
+        let combo = ComboQuote::new(vec![
+            leg(100.0, OptionType::Call, 0.5, 1.0, 0.2, 0.2),
+            leg(110.0, OptionType::Call, 0.5, -1.0, 0.2, 0.1),
+        ])
+        .unwrap();
+        let (long, short) = (combo.legs[0].quote, combo.legs[1].quote);
+        assert!((combo.net_mid() - (long.mid() - short.mid())).abs() < 1e-12);
+        assert!((combo.natural_ask() - (long.ask - short.bid)).abs() < 1e-12);
+        assert!((combo.natural_bid() - (long.bid - short.ask)).abs() < 1e-12);
+        assert!((combo.natural_ask() - combo.natural_bid() - 0.3).abs() < 1e-12);
+
+        // Selling the package flips the debit into a credit
+        let reversed = ComboQuote::new(combo.legs.iter().map(|l| ComboLeg { ratio: -l.ratio, ..*l }).collect()).unwrap();
+        assert!((reversed.natural_ask() + combo.natural_bid()).abs() < 1e-12);
+        assert!(reversed.price(&market()).unwrap().net_mid < 0.0);
+    }
+
+    #[test]
+    fn test_implied_shift_hits_target() {
+        let combo = ComboQuote::new(vec![
+            leg(95.0, OptionType::Put, 0.25, -1.0, 0.2, 0.1),
+            leg(105.0, OptionType::Call, 0.25, -1.0, 0.2, 0.1),
+            leg(100.0, OptionType::Call, 1.0, 2.0, 0.2, 0.1),
+        ])
+        .unwrap();
+        let market = market();
+        let models = combo.models(&market).unwrap();
+        let target = combo.package_value(&models, 0.03);
+        let shift = combo.implied_shift(&market, target).unwrap();
+        assert!((shift - 0.03).abs() < 1e-8, "{shift}");
+        for vol in combo.leg_vols_at(&market, target).unwrap() {
+            assert!((vol - 0.23).abs() < 1e-8);
+        }
+        assert!(combo.implied_shift(&market, combo.theo(&market).unwrap()).unwrap().abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_price_reprices_net_mid() {
+        // Legs quoted off a skew; the package trades 1.5 points rich to a flat surface
+        let combo = ComboQuote::new(vec![
+            leg(90.0, OptionType::Put, 0.5, 1.0, 0.25, 0.2),
+            leg(110.0, OptionType::Call, 0.5, 1.0, 0.18, 0.2),
+        ])
+        .unwrap();
+        let market = market();
+        let price = combo.price(&market).unwrap();
+        assert!(price.is_debit());
+        assert!(price.natural_bid < price.net_mid && price.net_mid < price.natural_ask);
+        assert!(price.theo < price.net_mid && price.vol_shift > 0.0);
+
+        let repriced: f64 = combo
+            .legs
+            .iter()
+            .zip(&price.leg_vols)
+            .map(|(leg, &vol)| {
+                let mut model = market.model(leg.quote.strike, leg.expiry).unwrap();
+                model.volatility = vol;
+                leg.ratio * model.price(leg.quote.option_type)
+            })
+            .sum();
+        assert!((repriced - price.net_mid).abs() < 1e-8);
+
+        let mid_vols = combo.leg_mid_vols(&market).unwrap();
+        assert!((mid_vols[0] - 0.25).abs() < 1e-8 && (mid_vols[1] - 0.18).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let quote = OptionQuote::new(100.0, OptionType::Call, 5.0, 5.5).unwrap();
+        assert!(ComboLeg::new(quote, 0.5, 0.0).is_err());
+        assert!(ComboLeg::new(quote, 0.0, 1.0).is_err());
+        assert!(ComboQuote::new(Vec::new()).is_err());
+
+        // A call spread can never be worth more than its discounted strike width
+        let spread = ComboQuote::new(vec![
+            leg(100.0, OptionType::Call, 0.5, 1.0, 0.2, 0.1),
+            leg(110.0, OptionType::Call, 0.5, -1.0, 0.2, 0.1),
+        ])
+        .unwrap();
+        assert!(spread.implied_shift(&market(), 12.0).is_err());
+        assert!(spread.implied_shift(&market(), f64::NAN).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let combo = ComboQuote::new(vec![leg(100.0, OptionType::Put, 0.5, -2.0, 0.2, 0.1)]).unwrap();
+        let json = serde_json::to_string(&combo).unwrap();
+        assert_eq!(serde_json::from_str::<ComboQuote>(&json).unwrap(), combo);
+    }
+}
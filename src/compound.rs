@@ -0,0 +1,272 @@
+//! Compound options (options on options), Geske (1979)
+
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::math::{bivariate_norm_cdf, norm_cdf};
+use crate::validation;
+
+/// Relative width at which the critical-spot bisection stops
+const CRITICAL_SPOT_TOLERANCE: f64 = 1e-13;
+
+/// Maximum bisection steps for the critical spot
+const CRITICAL_SPOT_ITERATIONS: usize = 200;
+
+/// European option to buy or sell a European option
+///
+/// At `expiry` (t₁) the holder may buy (compound call) or sell (compound put)
+/// the `underlying` option, expiring later at T₂, for `strike`. Geske's
+/// formula integrates the underlying's Black-Scholes value over the spot at
+/// t₁, which is exercised above or below the critical spot S* where the
+/// underlying is worth exactly the compound strike.
+///
+/// Fields are private so the inputs validated on construction stay valid.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompoundOption {
+    underlying: BlackScholes,
+    underlying_type: OptionType,
+    strike: f64,
+    expiry: f64,
+}
+
+impl CompoundOption {
+    /// Create a new compound option
+    ///
+    /// # Arguments
+    /// * `underlying` - The option on the asset, expiring at T₂
+    /// * `underlying_type` - Whether the underlying is a call or a put
+    /// * `strike` - Compound strike, the price of the underlying at exercise
+    /// * `expiry` - Compound expiry t₁ in years, with t₁ < T₂
+    pub fn new(
+        underlying: BlackScholes,
+        underlying_type: OptionType,
+        strike: f64,
+        expiry: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let strike = validation::positive("Compound strike", strike)?;
+        let expiry = validation::positive("Compound expiry", expiry)?;
+        if expiry >= underlying.time_to_expiry {
+            return Err(BlackScholesError::invalid("Compound option must expire before its underlying"));
+        }
+        Ok(CompoundOption {
+            underlying,
+            underlying_type,
+            strike,
+            expiry,
+        })
+    }
+
+    /// The option delivered at exercise, with its own strike and expiry
+    pub fn underlying(&self) -> &BlackScholes {
+        &self.underlying
+    }
+
+    pub fn underlying_type(&self) -> OptionType {
+        self.underlying_type
+    }
+
+    /// Premium paid (call) or received (put) for the underlying at exercise
+    pub fn strike(&self) -> f64 {
+        self.strike
+    }
+
+    /// Exercise time of the compound option in years, before the underlying's expiry
+    pub fn expiry(&self) -> f64 {
+        self.expiry
+    }
+
+    /// Value of the underlying at the compound expiry if the spot is then `spot`
+    fn underlying_value(&self, spot: f64) -> f64 {
+        let remaining = self.underlying.time_to_expiry - self.expiry;
+        BlackScholes {
+            spot_price: spot,
+            ..self.underlying.aged(remaining)
+        }
+        .price(self.underlying_type)
+    }
+
+    /// Spot at the compound expiry where the underlying is worth the compound strike
+    ///
+    /// Always exists on a call. A put is worth at most K·e^(-r(T₂-t₁)), so
+    /// there is none when the compound strike is at or above that.
+    pub fn critical_spot(&self) -> Result<f64, BlackScholesError> {
+        let gap = |spot: f64| self.underlying_value(spot) - self.strike;
+        let (mut lo, mut hi) = (0.0, self.underlying.strike_price);
+        match self.underlying_type {
+            OptionType::Call => {
+                while gap(hi) < 0.0 {
+                    lo = hi;
+                    hi *= 2.0;
+                }
+            }
+            OptionType::Put => {
+                let remaining = self.underlying.time_to_expiry - self.expiry;
+                let ceiling = self.underlying.strike_price * (-self.underlying.risk_free_rate * remaining).exp();
+                if self.strike >= ceiling {
+                    return Err(BlackScholesError::invalid("Compound strike exceeds the underlying put's value"));
+                }
+                while gap(hi) > 0.0 {
+                    lo = hi;
+                    hi *= 2.0;
+                }
+            }
+        }
+        // The gap is monotone in spot, falling for puts and rising for calls
+        let rising = self.underlying_type == OptionType::Call;
+        for _ in 0..CRITICAL_SPOT_ITERATIONS {
+            let mid = 0.5 * (lo + hi);
+            if (gap(mid) < 0.0) == rising {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+            if hi - lo <= CRITICAL_SPOT_TOLERANCE * hi {
+                break;
+            }
+        }
+        Ok(0.5 * (lo + hi))
+    }
+
+    /// Geske's price of the compound option
+    ///
+    /// Both expiries are read on the calendar clock, with the underlying's
+    /// variance spread evenly over it through its calendar volatility.
+    pub fn price(&self, option_type: OptionType) -> Result<f64, BlackScholesError> {
+        let bs = &self.underlying;
+        let (t1, t2) = (self.expiry, bs.time_to_expiry);
+        let compound_discount = (-bs.risk_free_rate * t1).exp();
+        let critical = match self.critical_spot() {
+            Ok(critical) => critical,
+            // A put never worth the strike is never bought; selling it is a forward sale
+            Err(_) => {
+                return Ok(match option_type {
+                    OptionType::Call => 0.0,
+                    OptionType::Put => self.strike * compound_discount - bs.price(self.underlying_type),
+                })
+            }
+        };
+
+        let sigma = bs.calendar_volatility();
+        let (w1, w2) = (sigma * t1.sqrt(), sigma * t2.sqrt());
+        let carry = bs.risk_free_rate - bs.dividend_yield;
+        let y1 = ((bs.spot_price / critical).ln() + carry * t1) / w1 + 0.5 * w1;
+        let z1 = ((bs.spot_price / bs.strike_price).ln() + carry * t2) / w2 + 0.5 * w2;
+        let (y2, z2) = (y1 - w1, z1 - w2);
+        let rho = (t1 / t2).sqrt();
+        let asset = bs.spot_price * (-bs.dividend_yield * t2).exp();
+        let strike = bs.strike_price * (-bs.risk_free_rate * t2).exp();
+        let premium = self.strike * compound_discount;
+        let m = bivariate_norm_cdf;
+
+        Ok(match (option_type, self.underlying_type) {
+            (OptionType::Call, OptionType::Call) => {
+                asset * m(z1, y1, rho) - strike * m(z2, y2, rho) - premium * norm_cdf(y2)
+            }
+            (OptionType::Put, OptionType::Call) => {
+                strike * m(z2, -y2, -rho) - asset * m(z1, -y1, -rho) + premium * norm_cdf(-y2)
+            }
+            (OptionType::Call, OptionType::Put) => {
+                strike * m(-z2, -y2, rho) - asset * m(-z1, -y1, rho) - premium * norm_cdf(-y2)
+            }
+            (OptionType::Put, OptionType::Put) => {
+                asset * m(-z1, y1, -rho) - strike * m(-z2, y2, -rho) + premium * norm_cdf(y2)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::norm_pdf;
+    use crate::time_scale::{DaySchedule, TimeScale};
+
+    fn compound(underlying_type: OptionType, strike: f64) -> CompoundOption {
+        let underlying = BlackScholes::new(500.0, 520.0, 0.5, 0.08, 0.35, 0.03).unwrap();
+        CompoundOption::new(underlying, underlying_type, strike, 0.25).unwrap()
+    }
+
+    #[test]
+    fn test_reference_value() {
+        // Haug, The Complete Guide to Option Pricing Formulas: put on call = 21.1965
+        let price = compound(OptionType::Call, 50.0).price(OptionType::Put).unwrap();
+        assert!((price - 21.1965).abs() < 5e-4, "{price}");
+    }
+
+    #[test]
+    fn test_matches_integration_over_the_first_expiry() {
+        for underlying_type in [OptionType::Call, OptionType::Put] {
+            let option = compound(underlying_type, 30.0);
+            let bs = &option.underlying;
+            let t1 = option.expiry;
+            let sigma = bs.calendar_volatility();
+            let drift = (bs.risk_free_rate - bs.dividend_yield - 0.5 * sigma.powi(2)) * t1;
+            for option_type in [OptionType::Call, OptionType::Put] {
+                // Trapezoid over the standard normal driving S(t₁), ±8 standard deviations
+                let n = 16_000;
+                let expected: f64 = (0..=n)
+                    .map(|i| {
+                        let z = -8.0 + 16.0 * i as f64 / n as f64;
+                        let spot = bs.spot_price * (drift + sigma * t1.sqrt() * z).exp();
+                        let value = option.underlying_value(spot);
+                        let payoff = match option_type {
+                            OptionType::Call => (value - option.strike).max(0.0),
+                            OptionType::Put => (option.strike - value).max(0.0),
+                        };
+                        let weight = if i == 0 || i == n { 0.5 } else { 1.0 };
+                        weight * payoff * norm_pdf(z) * 16.0 / n as f64
+                    })
+                    .sum::<f64>()
+                    * (-bs.risk_free_rate * t1).exp();
+                let price = option.price(option_type).unwrap();
+                assert!((price - expected).abs() < 1e-5, "{option_type:?} on {underlying_type:?}: {price}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parity_and_critical_spot() {
+        for underlying_type in [OptionType::Call, OptionType::Put] {
+            let option = compound(underlying_type, 40.0);
+            let call = option.price(OptionType::Call).unwrap();
+            let put = option.price(OptionType::Put).unwrap();
+            let forward_premium = 40.0 * (-0.08 * 0.25_f64).exp();
+            let parity = option.underlying.price(underlying_type) - forward_premium;
+            assert!((call - put - parity).abs() < 1e-8);
+            let critical = option.critical_spot().unwrap();
+            assert!((option.underlying_value(critical) - 40.0).abs() < 1e-8);
+        }
+
+        // A put worth at most 520·e^(-0.02) can never be bought for 600
+        let unreachable = compound(OptionType::Put, 600.0);
+        assert!(unreachable.critical_spot().is_err());
+        assert_eq!(unreachable.price(OptionType::Call).unwrap(), 0.0);
+        let sale = 600.0 * (-0.02_f64).exp() - unreachable.underlying.price(OptionType::Put);
+        assert!((unreachable.price(OptionType::Put).unwrap() - sale).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_business_time_uses_calendar_variance() {
+        let market = BlackScholes::new(500.0, 520.0, 0.5, 0.08, 0.35, 0.03).unwrap();
+        let underlying = market.with_time_scale(TimeScale::Trading, &DaySchedule::weekdays(182, 0)).unwrap();
+        let calendar = BlackScholes::new(500.0, 520.0, underlying.time_to_expiry, 0.08, 0.35, 0.03).unwrap();
+        let calendar = BlackScholes { volatility: underlying.calendar_volatility(), ..calendar };
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let business = CompoundOption::new(underlying, OptionType::Call, 50.0, 0.25).unwrap();
+            let expected = CompoundOption::new(calendar, OptionType::Call, 50.0, 0.25).unwrap();
+            assert!((business.price(option_type).unwrap() - expected.price(option_type).unwrap()).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_invalid_compounds() {
+        let underlying = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        assert!(CompoundOption::new(underlying, OptionType::Call, 5.0, 1.0).is_err());
+        assert!(CompoundOption::new(underlying, OptionType::Call, 0.0, 0.5).is_err());
+        assert!(CompoundOption::new(underlying, OptionType::Call, 5.0, -0.5).is_err());
+
+        let option = CompoundOption::new(underlying, OptionType::Put, 5.0, 0.5).unwrap();
+        assert_eq!((option.underlying_type(), option.strike(), option.expiry()), (OptionType::Put, 5.0, 0.5));
+        assert_eq!(option.underlying().time_to_expiry, 1.0);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::american::AmericanEngine;
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::monte_carlo::MonteCarlo;
+use crate::pde::{FiniteDifference, Scheme};
+use crate::time_scale::TimeScale;
+use crate::validation::{self, Strictness};
+use std::hash::{Hash, Hasher};
+
+/// Schema version written into every new configuration
+///
+/// Bump whenever a field is added or a default changes meaning, so stored
+/// results can be matched to the settings that produced them.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Engine choices, numerical settings, conventions and seeds for a pricing run
+///
+/// Together with the positions and the market snapshot, a config fully
+/// determines a result. `fingerprint` gives a hash of it that is stable across
+/// platforms and releases, for stamping results in audit logs.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelConfig {
+    /// Schema version (`CONFIG_VERSION` when created)
+    pub version: u32,
+    /// Free-form name, e.g. the approval reference of this configuration
+    pub label: String,
+    /// Engine for American exercise
+    pub american_engine: AmericanEngine,
+    /// Grid for PDE pricing
+    pub pde: FiniteDifference,
+    /// Path count and seed for Monte Carlo pricing
+    pub monte_carlo: MonteCarlo,
+    /// Clock on which volatility accrues
+    pub time_scale: TimeScale,
+    /// Validation policy applied while the run executes
+    pub strictness: Strictness,
+    /// Newton iteration cap for implied volatility
+    pub iv_max_iterations: usize,
+    /// Price tolerance for implied volatility
+    pub iv_tolerance: f64,
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        ModelConfig {
+            version: CONFIG_VERSION,
+            label: String::from("default"),
+            american_engine: AmericanEngine::Tree(500),
+            pde: FiniteDifference {
+                grid_points: 201,
+                time_steps: 200,
+                scheme: Scheme::CrankNicolson,
+                std_devs: 5.0,
+            },
+            monte_carlo: MonteCarlo {
+                paths: 100_000,
+                seed: 42,
+            },
+            time_scale: TimeScale::Calendar,
+            strictness: Strictness::Strict,
+            iv_max_iterations: 100,
+            iv_tolerance: 1e-8,
+        }
+    }
+}
+
+impl ModelConfig {
+    /// Stable 64-bit hash of every setting (FNV-1a over a canonical encoding)
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Run a pricing closure under this configuration
+    ///
+    /// The configured strictness applies to the current thread for the
+    /// duration of the run, and the result is stamped with the fingerprint.
+    pub fn run<T, F>(&self, f: F) -> Result<PricingRun<T>, BlackScholesError>
+    where
+        F: FnOnce(&ModelConfig) -> Result<T, BlackScholesError>,
+    {
+        let value = validation::with_strictness(self.strictness, || f(self))?;
+        Ok(PricingRun {
+            value,
+            config_version: self.version,
+            config_fingerprint: self.fingerprint(),
+        })
+    }
+
+    /// Implied volatility with the configured iteration cap and tolerance
+    pub fn implied_volatility(
+        &self,
+        model: &BlackScholes,
+        option_type: OptionType,
+        market_price: f64,
+    ) -> Result<f64, BlackScholesError> {
+        model.implied_volatility(option_type, market_price, self.iv_max_iterations, self.iv_tolerance)
+    }
+}
+
+/// Hashes a canonical encoding: integers as u64, floats by bit pattern with -0.0
+/// folded into 0.0, enums by explicit tag, so the result does not depend on
+/// the platform's `usize` width or std's hashing of composite types.
+impl Hash for ModelConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let float = |state: &mut H, x: f64| state.write_u64((x + 0.0).to_bits());
+        state.write_u64(self.version as u64);
+        state.write(self.label.as_bytes());
+        state.write_u8(0xff);
+        match self.american_engine {
+            AmericanEngine::Tree(steps) => {
+                state.write_u8(0);
+                state.write_u64(steps as u64);
+            }
+            AmericanEngine::Pde(grid_points, time_steps) => {
+                state.write_u8(1);
+                state.write_u64(grid_points as u64);
+                state.write_u64(time_steps as u64);
+            }
+            AmericanEngine::BaroneAdesiWhaley => state.write_u8(2),
+        }
+        state.write_u64(self.pde.grid_points as u64);
+        state.write_u64(self.pde.time_steps as u64);
+        state.write_u8(match self.pde.scheme {
+            Scheme::Explicit => 0,
+            Scheme::Implicit => 1,
+            Scheme::CrankNicolson => 2,
+        });
+        float(state, self.pde.std_devs);
+        state.write_u64(self.monte_carlo.paths as u64);
+        state.write_u64(self.monte_carlo.seed);
+        match self.time_scale {
+            TimeScale::Calendar => state.write_u8(0),
+            TimeScale::Trading => state.write_u8(1),
+            TimeScale::Custom { days_per_year } => {
+                state.write_u8(2);
+                float(state, days_per_year);
+            }
+        }
+        state.write_u8(match self.strictness {
+            Strictness::Strict => 0,
+            Strictness::Clamp => 1,
+        });
+        state.write_u64(self.iv_max_iterations as u64);
+        float(state, self.iv_tolerance);
+    }
+}
+
+/// A result stamped with the configuration that produced it
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PricingRun<T> {
+    pub value: T,
+    pub config_version: u32,
+    pub config_fingerprint: u64,
+}
+
+impl<T> PricingRun<T> {
+    /// Whether this result was produced under `config`
+    pub fn produced_by(&self, config: &ModelConfig) -> bool {
+        self.config_version == config.version && self.config_fingerprint == config.fingerprint()
+    }
+}
+
+/// 64-bit FNV-1a, fixed so fingerprints never change between Rust releases
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    // The default goes through native-endian bytes
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::american::decompose;
+
+    #[test]
+    fn test_fingerprint_tracks_every_setting() {
+        let base = ModelConfig::default();
+        assert_eq!(base.fingerprint(), ModelConfig::default().fingerprint());
+
+        let mut reseeded = base.clone();
+        reseeded.monte_carlo.seed += 1;
+        let mut retuned = base.clone();
+        retuned.iv_tolerance = 1e-10;
+        let mut engine = base.clone();
+        engine.american_engine = AmericanEngine::Pde(500, 1);
+        for changed in [reseeded, retuned, engine] {
+            assert_ne!(changed.fingerprint(), base.fingerprint());
+        }
+
+        let mut signed_zero = base.clone();
+        signed_zero.pde.std_devs = 0.0;
+        let mut negative_zero = base.clone();
+        negative_zero.pde.std_devs = -0.0;
+        assert_eq!(signed_zero.fingerprint(), negative_zero.fingerprint());
+    }
+
+    #[test]
+    fn test_fingerprint_is_pinned() {
+        // Changing this value breaks the link to every stored result: bump CONFIG_VERSION instead
+        assert_eq!(ModelConfig::default().fingerprint(), 0xc69d_e27d_c6b4_062d);
+    }
+
+    #[test]
+    fn test_run_applies_settings_and_stamps_result() {
+        let config = ModelConfig {
+            american_engine: AmericanEngine::BaroneAdesiWhaley,
+            strictness: Strictness::Clamp,
+            ..ModelConfig::default()
+        };
+        let model = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let run = config
+            .run(|c| {
+                assert_eq!(validation::strictness(), Strictness::Clamp);
+                Ok(decompose(&model, OptionType::Put, c.american_engine)?.american)
+            })
+            .unwrap();
+        assert!(run.produced_by(&config));
+        assert!(!run.produced_by(&ModelConfig::default()));
+
+        let again = config.run(|c| Ok(decompose(&model, OptionType::Put, c.american_engine)?.american)).unwrap();
+        assert_eq!(again, run);
+    }
+}
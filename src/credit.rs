@@ -0,0 +1,347 @@
+//! Single-name credit: hazard curves and credit default swaps
+
+use crate::curves::bootstrap::swap_schedule;
+use crate::curves::DiscountCurve;
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Premium payments per year of standard CDS contracts
+const STANDARD_FREQUENCY: u32 = 4;
+
+/// Search range for a bootstrapped hazard rate
+const HAZARD_BRACKET: (f64, f64) = (0.0, 50.0);
+
+/// Bisection steps when solving one hazard rate
+const HAZARD_ITERATIONS: usize = 200;
+
+/// Spread bump for CS01 (one basis point)
+const BASIS_POINT: f64 = 1e-4;
+
+/// Piecewise-constant default intensity
+///
+/// `hazards[i]` applies from the previous pillar (or today) up to and
+/// including `times[i]`; the last one extends beyond the last pillar.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HazardCurve {
+    /// Pillar times in years, strictly increasing and positive
+    pub times: Vec<f64>,
+    /// Default intensity on each segment, per year
+    pub hazards: Vec<f64>,
+}
+
+impl HazardCurve {
+    /// Build a curve from `(time, hazard rate)` pillars
+    pub fn new(pillars: &[(f64, f64)]) -> Result<Self, BlackScholesError> {
+        if pillars.is_empty() {
+            return Err(BlackScholesError::invalid("Hazard curve needs at least one pillar"));
+        }
+        let mut times = Vec::with_capacity(pillars.len());
+        let mut hazards = Vec::with_capacity(pillars.len());
+        for &(t, h) in pillars {
+            let t = validation::positive("Pillar time", t)?;
+            if times.last().is_some_and(|&last| t <= last) {
+                return Err(BlackScholesError::invalid("Pillar times must be strictly increasing"));
+            }
+            times.push(t);
+            hazards.push(validation::non_negative("Hazard rate", h)?);
+        }
+        Ok(HazardCurve { times, hazards })
+    }
+
+    /// Flat intensity at every horizon
+    pub fn flat(hazard: f64) -> Result<Self, BlackScholesError> {
+        Self::new(&[(1.0, hazard)])
+    }
+
+    /// Default intensity at `t`
+    pub fn hazard_rate(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&p| p < t).min(self.times.len() - 1);
+        self.hazards[i]
+    }
+
+    /// Probability of no default by `t`
+    pub fn survival(&self, t: f64) -> f64 {
+        let mut integral = 0.0;
+        let mut previous = 0.0;
+        for (&pillar, &h) in self.times.iter().zip(&self.hazards) {
+            if t <= pillar {
+                return (-(integral + h * (t - previous).max(0.0))).exp();
+            }
+            integral += h * (pillar - previous);
+            previous = pillar;
+        }
+        (-(integral + self.hazards[self.hazards.len() - 1] * (t - previous))).exp()
+    }
+
+    /// Probability of default by `t`
+    pub fn default_probability(&self, t: f64) -> f64 {
+        1.0 - self.survival(t)
+    }
+}
+
+/// Quoted par spread of a standard CDS to `maturity`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CdsQuote {
+    pub maturity: f64,
+    /// Par spread as decimal (0.01 = 100bp)
+    pub spread: f64,
+}
+
+/// Bootstrap a piecewise-constant hazard curve repricing every CDS quote
+///
+/// Quotes are sorted by maturity and each adds a pillar whose hazard rate
+/// makes a quarterly-paying CDS at the quoted spread worth zero, earlier
+/// pillars held fixed.
+///
+/// # Arguments
+/// * `discount` - Risk-free discount curve
+/// * `quotes` - Par spreads with distinct maturities
+/// * `recovery` - Assumed recovery rate as a fraction of notional
+pub fn bootstrap_hazard(
+    discount: &dyn DiscountCurve,
+    quotes: &[CdsQuote],
+    recovery: f64,
+) -> Result<HazardCurve, BlackScholesError> {
+    if quotes.is_empty() {
+        return Err(BlackScholesError::invalid("Bootstrap needs at least one CDS quote"));
+    }
+    let mut sorted = quotes.to_vec();
+    sorted.sort_by(|a, b| a.maturity.total_cmp(&b.maturity));
+    if sorted.windows(2).any(|w| w[1].maturity - w[0].maturity < 1e-9) {
+        return Err(BlackScholesError::invalid("Two quotes share a maturity"));
+    }
+
+    let mut curve = HazardCurve {
+        times: Vec::with_capacity(sorted.len()),
+        hazards: Vec::with_capacity(sorted.len()),
+    };
+    for quote in &sorted {
+        let cds = CreditDefaultSwap::new(quote.maturity, quote.spread, STANDARD_FREQUENCY, recovery, 1.0)?;
+        curve.times.push(cds.maturity);
+        curve.hazards.push(0.0);
+        let i = curve.hazards.len() - 1;
+        // The protection buyer's value rises with the hazard rate
+        let mut value = |h: f64| {
+            curve.hazards[i] = h;
+            cds.npv(discount, &curve)
+        };
+        let (mut lo, mut hi) = HAZARD_BRACKET;
+        if value(lo) > 0.0 {
+            return Err(BlackScholesError::no_convergence("CDS quotes imply a negative hazard rate"));
+        }
+        if value(hi) < 0.0 {
+            return Err(BlackScholesError::no_convergence("CDS spread outside the hazard bracket"));
+        }
+        for _ in 0..HAZARD_ITERATIONS {
+            let mid = 0.5 * (lo + hi);
+            if value(mid) < 0.0 {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+            if hi - lo < 1e-15 {
+                break;
+            }
+        }
+        curve.hazards[i] = 0.5 * (lo + hi);
+    }
+    Ok(curve)
+}
+
+/// Credit default swap from today to `maturity`, valued for the protection buyer
+///
+/// Follows the ISDA standard model's integrals: hazard and forward rates
+/// are taken flat between knots (payment dates and hazard pillars), giving
+/// closed forms for the protection leg and for the premium accrued up to
+/// a default, which the buyer pays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreditDefaultSwap {
+    pub maturity: f64,
+    /// Running spread paid by the protection buyer, as decimal
+    pub spread: f64,
+    /// Premium payments per year
+    pub frequency: u32,
+    /// Recovery rate as a fraction of notional
+    pub recovery: f64,
+    pub notional: f64,
+}
+
+impl CreditDefaultSwap {
+    pub fn new(
+        maturity: f64,
+        spread: f64,
+        frequency: u32,
+        recovery: f64,
+        notional: f64,
+    ) -> Result<Self, BlackScholesError> {
+        if frequency == 0 {
+            return Err(BlackScholesError::invalid("CDS needs at least one premium payment a year"));
+        }
+        let recovery = validation::in_range("Recovery rate", recovery, 0.0, 1.0, "in [0, 1)")?;
+        if recovery >= 1.0 {
+            return Err(BlackScholesError::invalid("Recovery rate must be below 100%"));
+        }
+        Ok(CreditDefaultSwap {
+            maturity: validation::positive("Maturity", maturity)?,
+            spread: validation::non_negative("Spread", spread)?,
+            frequency,
+            recovery,
+            notional: validation::positive("Notional", notional)?,
+        })
+    }
+
+    /// Protection leg and risky annuity per unit notional
+    ///
+    /// The annuity is the premium leg per unit spread: scheduled payments
+    /// weighted by survival, plus premium accrued from the last payment to
+    /// a default.
+    fn legs(&self, discount: &dyn DiscountCurve, hazard: &HazardCurve) -> (f64, f64) {
+        let (mut protection, mut annuity) = (0.0, 0.0);
+        for period in swap_schedule(0.0, self.maturity, self.frequency).windows(2) {
+            let (start, end) = (period[0], period[1]);
+            annuity += (end - start) * discount.df(end) * hazard.survival(end);
+
+            let mut knots = vec![start];
+            knots.extend(hazard.times.iter().copied().filter(|&t| t > start && t < end));
+            knots.push(end);
+            for k in knots.windows(2) {
+                let (a, b) = (k[0], k[1]);
+                let dt = b - a;
+                let (da, sa) = (discount.df(a), hazard.survival(a));
+                let lambda = hazard.hazard_rate(b);
+                let forward = (da / discount.df(b)).ln() / dt;
+                let (m0, m1) = exponential_moments(lambda + forward, dt);
+                protection += lambda * da * sa * m0;
+                annuity += lambda * da * sa * ((a - start) * m0 + m1);
+            }
+        }
+        ((1.0 - self.recovery) * protection, annuity)
+    }
+
+    /// Present value of the default payments (1 - R) per unit of notional defaulted
+    pub fn protection_leg(&self, discount: &dyn DiscountCurve, hazard: &HazardCurve) -> f64 {
+        self.notional * self.legs(discount, hazard).0
+    }
+
+    /// Present value of the premium leg at `spread`, including accrual to default
+    pub fn premium_leg(&self, discount: &dyn DiscountCurve, hazard: &HazardCurve) -> f64 {
+        self.notional * self.spread * self.legs(discount, hazard).1
+    }
+
+    /// Premium leg per unit of spread (RPV01 × notional)
+    pub fn risky_annuity(&self, discount: &dyn DiscountCurve, hazard: &HazardCurve) -> f64 {
+        self.notional * self.legs(discount, hazard).1
+    }
+
+    /// Spread at which the contract is worth zero
+    pub fn par_spread(&self, discount: &dyn DiscountCurve, hazard: &HazardCurve) -> f64 {
+        let (protection, annuity) = self.legs(discount, hazard);
+        protection / annuity
+    }
+
+    /// Value to the protection buyer: protection less premium
+    pub fn npv(&self, discount: &dyn DiscountCurve, hazard: &HazardCurve) -> f64 {
+        let (protection, annuity) = self.legs(discount, hazard);
+        self.notional * (protection - self.spread * annuity)
+    }
+
+    /// Upfront the buyer pays for a contract trading at a standard running spread
+    ///
+    /// As a fraction of notional (points upfront); negative when the buyer receives.
+    pub fn upfront(&self, discount: &dyn DiscountCurve, hazard: &HazardCurve) -> f64 {
+        self.npv(discount, hazard) / self.notional
+    }
+
+    /// Change in value to the protection buyer when every quoted spread rises one basis point
+    ///
+    /// The hazard curve is bootstrapped from `quotes` at this contract's
+    /// recovery rate, before and after the bump.
+    pub fn cs01(&self, discount: &dyn DiscountCurve, quotes: &[CdsQuote]) -> Result<f64, BlackScholesError> {
+        let base = bootstrap_hazard(discount, quotes, self.recovery)?;
+        let bumped_quotes: Vec<CdsQuote> = quotes
+            .iter()
+            .map(|q| CdsQuote {
+                spread: q.spread + BASIS_POINT,
+                ..*q
+            })
+            .collect();
+        let bumped = bootstrap_hazard(discount, &bumped_quotes, self.recovery)?;
+        Ok(self.npv(discount, &bumped) - self.npv(discount, &base))
+    }
+}
+
+/// ∫₀^Δ e^(-hu) du and ∫₀^Δ u·e^(-hu) du, with series near h = 0
+fn exponential_moments(h: f64, dt: f64) -> (f64, f64) {
+    let x = h * dt;
+    if x.abs() < 1e-6 {
+        return (dt * (1.0 - 0.5 * x), 0.5 * dt * dt * (1.0 - 2.0 * x / 3.0));
+    }
+    let decay = (-x).exp();
+    ((1.0 - decay) / h, (1.0 - decay * (1.0 + x)) / (h * h))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::FlatCurve;
+
+    fn quotes() -> Vec<CdsQuote> {
+        [(1.0, 0.0060), (3.0, 0.0085), (5.0, 0.0110), (7.0, 0.0125), (10.0, 0.0145)]
+            .iter()
+            .map(|&(maturity, spread)| CdsQuote { maturity, spread })
+            .collect()
+    }
+
+    #[test]
+    fn test_credit_triangle_on_flat_hazard() {
+        let discount = FlatCurve::new(0.03).unwrap();
+        let hazard = HazardCurve::flat(0.02).unwrap();
+        assert!((hazard.survival(2.5) - (-0.05_f64).exp()).abs() < 1e-15);
+        let cds = CreditDefaultSwap::new(5.0, 0.0, 4, 0.4, 1e7).unwrap();
+        // Par spread ≈ λ(1 - R) once accrual on default is paid
+        let par = cds.par_spread(&discount, &hazard);
+        assert!((par - 0.012).abs() < 1e-4, "{par}");
+        let at_par = CreditDefaultSwap { spread: par, ..cds };
+        assert!(at_par.npv(&discount, &hazard).abs() < 1e-6);
+        assert!((at_par.premium_leg(&discount, &hazard) - at_par.protection_leg(&discount, &hazard)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bootstrap_reprices_quotes() {
+        let discount = FlatCurve::new(0.03).unwrap();
+        let curve = bootstrap_hazard(&discount, &quotes(), 0.4).unwrap();
+        assert_eq!(curve.times, vec![1.0, 3.0, 5.0, 7.0, 10.0]);
+        for q in quotes() {
+            let cds = CreditDefaultSwap::new(q.maturity, 0.0, 4, 0.4, 1.0).unwrap();
+            assert!((cds.par_spread(&discount, &curve) - q.spread).abs() < 1e-12);
+        }
+        // Upward-sloping spreads need rising forward hazards
+        assert!(curve.hazards.windows(2).all(|w| w[1] > w[0]), "{:?}", curve.hazards);
+        assert!(curve.survival(10.0) < curve.survival(5.0));
+
+        // A 5y spread far below the 3y one implies negative forward hazard
+        let mut inverted = quotes();
+        inverted[2].spread = 0.002;
+        assert!(bootstrap_hazard(&discount, &inverted, 0.4).is_err());
+        assert!(bootstrap_hazard(&discount, &[], 0.4).is_err());
+        assert!(CreditDefaultSwap::new(5.0, 0.01, 4, 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_upfront_and_cs01() {
+        let discount = FlatCurve::new(0.03).unwrap();
+        let curve = bootstrap_hazard(&discount, &quotes(), 0.4).unwrap();
+        // Buying 5y protection with a 100bp standard coupon against a 110bp par spread
+        let cds = CreditDefaultSwap::new(5.0, 0.01, 4, 0.4, 1e7).unwrap();
+        let annuity = cds.risky_annuity(&discount, &curve);
+        let upfront = cds.upfront(&discount, &curve);
+        assert!((upfront - 0.001 * annuity / 1e7).abs() < 1e-12);
+
+        let cs01 = cds.cs01(&discount, &quotes()).unwrap();
+        assert!(cs01 > 0.0);
+        assert!((cs01 / (annuity * BASIS_POINT) - 1.0).abs() < 0.02, "{cs01} vs {}", annuity * BASIS_POINT);
+    }
+}
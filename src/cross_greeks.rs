@@ -0,0 +1,297 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::math::{Dual, Real};
+use std::ops::Add;
+
+/// Number of pricing inputs in a `CrossGreeks` matrix
+pub const PRICING_INPUTS: usize = 5;
+
+/// Inputs the price is differentiated against, in matrix order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PricingInput {
+    Spot,
+    Volatility,
+    /// Time to expiry in years (both clocks together)
+    Time,
+    Rate,
+    Dividend,
+}
+
+impl PricingInput {
+    /// Every input, in matrix order
+    pub const ALL: [PricingInput; PRICING_INPUTS] = [
+        PricingInput::Spot,
+        PricingInput::Volatility,
+        PricingInput::Time,
+        PricingInput::Rate,
+        PricingInput::Dividend,
+    ];
+
+    /// Row and column of the input in the matrix
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Price with its full gradient and Hessian over spot, vol, time, rate and dividend
+///
+/// Derivatives are raw, per unit of each input (volatility and rates as
+/// decimals, time to expiry in years), unlike the per-1% and per-day
+/// conventions of `Greeks`; in these units a second-order Taylor expansion
+/// needs no rescaling. Positions add up and scale linearly.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrossGreeks {
+    pub price: f64,
+    /// `gradient[i]` = ∂V/∂x_i
+    pub gradient: [f64; PRICING_INPUTS],
+    /// `hessian[i][j]` = ∂²V/∂x_i∂x_j, symmetric
+    pub hessian: [[f64; PRICING_INPUTS]; PRICING_INPUTS],
+}
+
+impl CrossGreeks {
+    /// Exact first and second derivatives of a Black-Scholes price
+    ///
+    /// One evaluation over second-order dual numbers seeded on all five inputs.
+    pub fn new(model: &BlackScholes, option_type: OptionType) -> Self {
+        type Ad = Dual<Dual<f64, PRICING_INPUTS>, PRICING_INPUTS>;
+        let seed = |x: f64, input: PricingInput| Ad::variable(Dual::variable(x, input.index()), input.index());
+        let mut inputs = model.inputs::<Ad>();
+        inputs.spot_price = seed(model.spot_price, PricingInput::Spot);
+        inputs.volatility = seed(model.volatility, PricingInput::Volatility);
+        inputs.time_to_expiry = seed(model.time_to_expiry, PricingInput::Time);
+        // Business time moves in proportion to calendar time
+        let vol_per_calendar = model.vol_time / model.time_to_expiry;
+        inputs.vol_time = inputs.time_to_expiry * Ad::constant(vol_per_calendar);
+        inputs.risk_free_rate = seed(model.risk_free_rate, PricingInput::Rate);
+        inputs.dividend_yield = seed(model.dividend_yield, PricingInput::Dividend);
+        let value = inputs.price(option_type);
+        CrossGreeks {
+            price: value.re.re,
+            gradient: value.eps.map(|e| e.re),
+            hessian: value.eps.map(|e| e.eps),
+        }
+    }
+
+    /// A unit of the underlying itself: value S, delta one
+    pub fn underlying(spot: f64) -> Self {
+        let mut gradient = [0.0; PRICING_INPUTS];
+        gradient[PricingInput::Spot.index()] = 1.0;
+        CrossGreeks {
+            price: spot,
+            gradient,
+            ..CrossGreeks::default()
+        }
+    }
+
+    /// Central finite differences of `value`, a price as a function of input moves
+    ///
+    /// For payoffs without an automatic-differentiation path. `steps[i]` is
+    /// the bump applied to input `i`; the diagonal uses three-point and the
+    /// cross terms four-point stencils (51 evaluations in all).
+    pub fn numerical<F: Fn(&[f64; PRICING_INPUTS]) -> f64>(value: F, steps: &[f64; PRICING_INPUTS]) -> Self {
+        let at = |moves: &[(usize, f64)]| {
+            let mut dx = [0.0; PRICING_INPUTS];
+            for &(i, d) in moves {
+                dx[i] += d;
+            }
+            value(&dx)
+        };
+        let price = at(&[]);
+        let mut result = CrossGreeks {
+            price,
+            ..CrossGreeks::default()
+        };
+        for i in 0..PRICING_INPUTS {
+            let h = steps[i];
+            let (up, down) = (at(&[(i, h)]), at(&[(i, -h)]));
+            result.gradient[i] = (up - down) / (2.0 * h);
+            result.hessian[i][i] = (up - 2.0 * price + down) / (h * h);
+            for (j, &k) in steps.iter().enumerate().take(i) {
+                let cross = (at(&[(i, h), (j, k)]) - at(&[(i, h), (j, -k)]) - at(&[(i, -h), (j, k)])
+                    + at(&[(i, -h), (j, -k)]))
+                    / (4.0 * h * k);
+                result.hessian[i][j] = cross;
+                result.hessian[j][i] = cross;
+            }
+        }
+        result
+    }
+
+    /// ∂V/∂x
+    pub fn first(&self, x: PricingInput) -> f64 {
+        self.gradient[x.index()]
+    }
+
+    /// ∂²V/∂x∂y
+    pub fn second(&self, x: PricingInput, y: PricingInput) -> f64 {
+        self.hessian[x.index()][y.index()]
+    }
+
+    /// ∂²V/∂S∂σ
+    pub fn vanna(&self) -> f64 {
+        self.second(PricingInput::Spot, PricingInput::Volatility)
+    }
+
+    /// ∂²V/∂σ²
+    pub fn volga(&self) -> f64 {
+        self.second(PricingInput::Volatility, PricingInput::Volatility)
+    }
+
+    /// ∂²V/∂S∂T, the change in delta as expiry shortens (with the sign of time to expiry)
+    pub fn charm(&self) -> f64 {
+        self.second(PricingInput::Spot, PricingInput::Time)
+    }
+
+    /// ∂²V/∂σ∂T
+    pub fn veta(&self) -> f64 {
+        self.second(PricingInput::Volatility, PricingInput::Time)
+    }
+
+    /// Second-order Taylor estimate of the value change for input moves `dx`
+    pub fn taylor(&self, dx: &[f64; PRICING_INPUTS]) -> f64 {
+        let mut change = 0.0;
+        for i in 0..PRICING_INPUTS {
+            change += self.gradient[i] * dx[i];
+            for j in 0..PRICING_INPUTS {
+                change += 0.5 * self.hessian[i][j] * dx[i] * dx[j];
+            }
+        }
+        change
+    }
+
+    /// Every entry multiplied by `quantity`
+    pub fn scaled(&self, quantity: f64) -> Self {
+        CrossGreeks {
+            price: self.price * quantity,
+            gradient: self.gradient.map(|g| g * quantity),
+            hessian: self.hessian.map(|row| row.map(|h| h * quantity)),
+        }
+    }
+}
+
+impl Add for CrossGreeks {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let mut sum = self;
+        sum.price += rhs.price;
+        for i in 0..PRICING_INPUTS {
+            sum.gradient[i] += rhs.gradient[i];
+            for j in 0..PRICING_INPUTS {
+                sum.hessian[i][j] += rhs.hessian[i][j];
+            }
+        }
+        sum
+    }
+}
+
+impl std::iter::Sum for CrossGreeks {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(CrossGreeks::default(), Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> BlackScholes {
+        BlackScholes::new(100.0, 105.0, 0.75, 0.03, 0.25, 0.015).unwrap()
+    }
+
+    #[test]
+    fn test_matches_first_order_greeks() {
+        let bs = model();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let cross = CrossGreeks::new(&bs, option_type);
+            let greeks = bs.greeks(option_type);
+            assert!((cross.price - bs.price(option_type)).abs() < 1e-12);
+            assert!((cross.first(PricingInput::Spot) - greeks.delta).abs() < 1e-12);
+            assert!((cross.second(PricingInput::Spot, PricingInput::Spot) - greeks.gamma).abs() < 1e-12);
+            assert!((cross.first(PricingInput::Volatility) - 100.0 * greeks.vega).abs() < 1e-10);
+            assert!((cross.first(PricingInput::Rate) - 100.0 * greeks.rho).abs() < 1e-10);
+            assert!((cross.first(PricingInput::Time) + 365.0 * greeks.theta).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_cross_terms_match_finite_differences() {
+        let bs = model();
+        let cross = CrossGreeks::new(&bs, OptionType::Call);
+        let bump = |ds: f64, dv: f64, dt: f64, dq: f64| {
+            BlackScholes {
+                spot_price: bs.spot_price + ds,
+                volatility: bs.volatility + dv,
+                time_to_expiry: bs.time_to_expiry + dt,
+                vol_time: bs.vol_time + dt,
+                dividend_yield: bs.dividend_yield + dq,
+                ..bs
+            }
+            .price(OptionType::Call)
+        };
+        let (h, e) = (1e-3, 1e-4);
+        let vanna = (bump(h, e, 0.0, 0.0) - bump(h, -e, 0.0, 0.0) - bump(-h, e, 0.0, 0.0) + bump(-h, -e, 0.0, 0.0))
+            / (4.0 * h * e);
+        let veta = (bump(0.0, e, e, 0.0) - bump(0.0, e, -e, 0.0) - bump(0.0, -e, e, 0.0) + bump(0.0, -e, -e, 0.0))
+            / (4.0 * e * e);
+        let dq_ds = (bump(h, 0.0, 0.0, e) - bump(h, 0.0, 0.0, -e) - bump(-h, 0.0, 0.0, e) + bump(-h, 0.0, 0.0, -e))
+            / (4.0 * h * e);
+        assert!((cross.vanna() - vanna).abs() < 1e-5);
+        assert!((cross.veta() - veta).abs() < 1e-3);
+        assert!((cross.second(PricingInput::Dividend, PricingInput::Spot) - dq_ds).abs() < 1e-4);
+        for i in 0..PRICING_INPUTS {
+            for j in 0..PRICING_INPUTS {
+                assert!((cross.hessian[i][j] - cross.hessian[j][i]).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_taylor_expansion_and_aggregation() {
+        let bs = model();
+        let call = CrossGreeks::new(&bs, OptionType::Call);
+        let dx = [2.0, 0.01, -1.0 / 365.0, 0.001, 0.0];
+        let moved = BlackScholes {
+            spot_price: 102.0,
+            volatility: 0.26,
+            time_to_expiry: 0.75 - 1.0 / 365.0,
+            vol_time: 0.75 - 1.0 / 365.0,
+            risk_free_rate: 0.031,
+            ..bs
+        };
+        let actual = moved.price(OptionType::Call) - bs.price(OptionType::Call);
+        assert!((call.taylor(&dx) - actual).abs() < 5e-3 * actual.abs());
+
+        // Put-call parity: the straddle's spot convexity is twice the call's
+        let straddle: CrossGreeks = [call, CrossGreeks::new(&bs, OptionType::Put)].into_iter().sum();
+        assert!((straddle.second(PricingInput::Spot, PricingInput::Spot) - 2.0 * call.hessian[0][0]).abs() < 1e-12);
+        assert_eq!(call.scaled(-2.0).volga(), -2.0 * call.volga());
+    }
+
+    #[test]
+    fn test_numerical_matches_exact() {
+        let bs = model();
+        let exact = CrossGreeks::new(&bs, OptionType::Put);
+        let bumped = |dx: &[f64; PRICING_INPUTS]| {
+            BlackScholes {
+                spot_price: bs.spot_price + dx[0],
+                volatility: bs.volatility + dx[1],
+                time_to_expiry: bs.time_to_expiry + dx[2],
+                vol_time: bs.vol_time + dx[2],
+                risk_free_rate: bs.risk_free_rate + dx[3],
+                dividend_yield: bs.dividend_yield + dx[4],
+                ..bs
+            }
+            .price(OptionType::Put)
+        };
+        let numerical = CrossGreeks::numerical(bumped, &[0.1, 1e-3, 1e-3, 1e-3, 1e-3]);
+        for i in 0..PRICING_INPUTS {
+            assert!((numerical.gradient[i] - exact.gradient[i]).abs() < 1e-3 * exact.gradient[i].abs().max(1.0));
+            for j in 0..PRICING_INPUTS {
+                let scale = exact.hessian[i][j].abs().max(1.0);
+                assert!((numerical.hessian[i][j] - exact.hessian[i][j]).abs() < 1e-2 * scale, "{i} {j}");
+            }
+        }
+        assert_eq!(CrossGreeks::underlying(50.0).first(PricingInput::Spot), 1.0);
+    }
+}
@@ -0,0 +1,334 @@
+use super::{DiscountCurve, InterpolatedCurve, Interpolation};
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Bisection steps when solving one pillar discount factor
+const PILLAR_ITERATIONS: usize = 200;
+
+/// Search range for ln(df) at a pillar
+const LOG_DF_BRACKET: (f64, f64) = (-10.0, 1.0);
+
+/// Passes over all pillars for interpolations where pillars interact
+const MAX_PASSES: usize = 50;
+
+/// Largest repricing error accepted from the bootstrap
+const REPRICING_TOLERANCE: f64 = 1e-10;
+
+/// Market instrument used to bootstrap a discount curve
+///
+/// Times are year fractions from today; accruals are the differences of those
+/// times, so any day-count convention is applied when building the inputs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RateInstrument {
+    /// Money-market deposit from today with simple interest
+    Deposit { maturity: f64, rate: f64 },
+    /// Forward rate agreement over [start, end] with simple interest
+    Fra { start: f64, end: f64, rate: f64 },
+    /// Short-rate future over [start, end] quoted as 100·(1 - rate); the
+    /// convexity adjustment is subtracted from the futures rate
+    Future {
+        start: f64,
+        end: f64,
+        price: f64,
+        convexity_adjustment: f64,
+    },
+    /// Par swap paying a fixed rate `frequency` times a year against a
+    /// floating leg projected and discounted on the same curve
+    Swap { maturity: f64, rate: f64, frequency: u32 },
+}
+
+impl RateInstrument {
+    /// Time of the last cash flow, where the instrument pins the curve
+    pub fn maturity(&self) -> f64 {
+        match *self {
+            RateInstrument::Deposit { maturity, .. } | RateInstrument::Swap { maturity, .. } => maturity,
+            RateInstrument::Fra { end, .. } | RateInstrument::Future { end, .. } => end,
+        }
+    }
+
+    /// Quoted rate (futures converted from price and convexity-adjusted)
+    pub fn quoted_rate(&self) -> f64 {
+        match *self {
+            RateInstrument::Deposit { rate, .. } | RateInstrument::Fra { rate, .. } | RateInstrument::Swap { rate, .. } => {
+                rate
+            }
+            RateInstrument::Future {
+                price,
+                convexity_adjustment,
+                ..
+            } => (100.0 - price) / 100.0 - convexity_adjustment,
+        }
+    }
+
+    /// Rate at which the instrument is worth zero on `curve`
+    pub fn par_rate(&self, curve: &dyn DiscountCurve) -> f64 {
+        match *self {
+            RateInstrument::Deposit { maturity, .. } => (1.0 / curve.df(maturity) - 1.0) / maturity,
+            RateInstrument::Fra { start, end, .. } | RateInstrument::Future { start, end, .. } => {
+                (curve.df(start) / curve.df(end) - 1.0) / (end - start)
+            }
+            RateInstrument::Swap {
+                maturity, frequency, ..
+            } => {
+                let annuity: f64 = swap_schedule(0.0, maturity, frequency)
+                    .windows(2)
+                    .map(|w| (w[1] - w[0]) * curve.df(w[1]))
+                    .sum();
+                (1.0 - curve.df(maturity)) / annuity
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), BlackScholesError> {
+        validation::finite("Instrument rate", self.quoted_rate())?;
+        match *self {
+            RateInstrument::Deposit { maturity, .. } => {
+                validation::positive("Deposit maturity", maturity)?;
+            }
+            RateInstrument::Fra { start, end, .. } | RateInstrument::Future { start, end, .. } => {
+                validation::non_negative("Accrual start", start)?;
+                if end <= start {
+                    return Err(BlackScholesError::invalid("Accrual end must be after its start"));
+                }
+            }
+            RateInstrument::Swap {
+                maturity, frequency, ..
+            } => {
+                validation::positive("Swap maturity", maturity)?;
+                if frequency == 0 {
+                    return Err(BlackScholesError::invalid("Swap needs at least one payment a year"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fixed-leg dates from `start` to `maturity`, with any short stub first
+pub(crate) fn swap_schedule(start: f64, maturity: f64, frequency: u32) -> Vec<f64> {
+    let period = 1.0 / frequency as f64;
+    let mut dates = vec![maturity];
+    let mut t = maturity - period;
+    while t > start + 1e-9 {
+        dates.push(t);
+        t -= period;
+    }
+    dates.push(start);
+    dates.reverse();
+    dates
+}
+
+/// Bootstrap a discount curve that reprices every instrument
+///
+/// Each instrument contributes one pillar at its maturity, solved in order
+/// of maturity. Monotone cubic interpolation couples neighbouring pillars,
+/// so the solve is repeated over all pillars until none moves.
+///
+/// # Arguments
+/// * `instruments` - Deposits, FRAs, futures and swaps with distinct maturities
+/// * `interpolation` - Interpolation of the resulting curve
+pub fn bootstrap(
+    instruments: &[RateInstrument],
+    interpolation: Interpolation,
+) -> Result<InterpolatedCurve, BlackScholesError> {
+    if instruments.is_empty() {
+        return Err(BlackScholesError::invalid("Bootstrap needs at least one instrument"));
+    }
+    for instrument in instruments {
+        instrument.validate()?;
+    }
+    let mut sorted = instruments.to_vec();
+    sorted.sort_by(|a, b| a.maturity().total_cmp(&b.maturity()));
+    if sorted.windows(2).any(|w| w[1].maturity() - w[0].maturity() < 1e-9) {
+        return Err(BlackScholesError::invalid("Two instruments share a maturity"));
+    }
+
+    let mut pillars: Vec<(f64, f64)> = Vec::with_capacity(sorted.len());
+    for instrument in &sorted {
+        pillars.push((instrument.maturity(), 1.0));
+        let i = pillars.len() - 1;
+        pillars[i].1 = solve_pillar(&pillars, i, instrument, interpolation)?;
+    }
+    if interpolation == Interpolation::MonotoneCubic {
+        for _ in 0..MAX_PASSES {
+            let mut moved: f64 = 0.0;
+            for (i, instrument) in sorted.iter().enumerate() {
+                let df = solve_pillar(&pillars, i, instrument, interpolation)?;
+                moved = moved.max((df - pillars[i].1).abs());
+                pillars[i].1 = df;
+            }
+            if moved < 1e-15 {
+                break;
+            }
+        }
+    }
+
+    let curve = InterpolatedCurve::from_discount_factors(&pillars, interpolation)?;
+    let worst = sorted
+        .iter()
+        .map(|inst| (inst.par_rate(&curve) - inst.quoted_rate()).abs())
+        .fold(0.0, f64::max);
+    if worst > REPRICING_TOLERANCE {
+        return Err(BlackScholesError::no_convergence("Bootstrapped curve does not reprice its instruments"));
+    }
+    Ok(curve)
+}
+
+/// Discount factor at pillar `i` that reprices `instrument`, others held fixed
+fn solve_pillar(
+    pillars: &[(f64, f64)],
+    i: usize,
+    instrument: &RateInstrument,
+    interpolation: Interpolation,
+) -> Result<f64, BlackScholesError> {
+    let mut trial = pillars.to_vec();
+    let mut error = |log_df: f64| -> Result<f64, BlackScholesError> {
+        trial[i].1 = log_df.exp();
+        let curve = InterpolatedCurve::from_discount_factors(&trial, interpolation)?;
+        Ok(instrument.par_rate(&curve) - instrument.quoted_rate())
+    };
+    // The par rate falls as the pillar discount factor rises
+    let (mut lo, mut hi) = LOG_DF_BRACKET;
+    if error(lo)? < 0.0 || error(hi)? > 0.0 {
+        return Err(BlackScholesError::no_convergence("Instrument rate outside the bootstrap bracket"));
+    }
+    for _ in 0..PILLAR_ITERATIONS {
+        let mid = 0.5 * (lo + hi);
+        if error(mid)? > 0.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo < 1e-16 {
+            break;
+        }
+    }
+    Ok((0.5 * (lo + hi)).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market() -> Vec<RateInstrument> {
+        vec![
+            RateInstrument::Deposit {
+                maturity: 0.25,
+                rate: 0.030,
+            },
+            RateInstrument::Fra {
+                start: 0.25,
+                end: 0.5,
+                rate: 0.032,
+            },
+            RateInstrument::Future {
+                start: 0.5,
+                end: 0.75,
+                price: 96.6,
+                convexity_adjustment: 0.0001,
+            },
+            RateInstrument::Swap {
+                maturity: 2.0,
+                rate: 0.035,
+                frequency: 2,
+            },
+            RateInstrument::Swap {
+                maturity: 5.0,
+                rate: 0.038,
+                frequency: 1,
+            },
+            RateInstrument::Swap {
+                maturity: 10.0,
+                rate: 0.040,
+                frequency: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_bootstrap_reprices_every_instrument() {
+        for interpolation in [Interpolation::PiecewiseFlat, Interpolation::LogLinear, Interpolation::MonotoneCubic] {
+            let curve = bootstrap(&market(), interpolation).unwrap();
+            for instrument in market() {
+                let error = instrument.par_rate(&curve) - instrument.quoted_rate();
+                assert!(error.abs() < 1e-12, "{:?} {:?} {}", interpolation, instrument, error);
+            }
+            assert_eq!(curve.times.len(), 6);
+        }
+    }
+
+    #[test]
+    fn test_monotone_cubic_keeps_forwards_positive_and_smooth() {
+        let cubic = bootstrap(&market(), Interpolation::MonotoneCubic).unwrap();
+        let linear = bootstrap(&market(), Interpolation::LogLinear).unwrap();
+        let forwards = |curve: &InterpolatedCurve| -> Vec<f64> {
+            (1..200).map(|i| curve.forward_rate(0.05 * i as f64, 0.05 * i as f64 + 0.01)).collect()
+        };
+        let (f_cubic, f_linear) = (forwards(&cubic), forwards(&linear));
+        assert!(f_cubic.iter().all(|&f| f > 0.0));
+        // Log-linear forwards jump at pillars; the cubic's largest step is much smaller
+        let max_jump = |f: &[f64]| f.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, f64::max);
+        assert!(max_jump(&f_cubic) < 0.5 * max_jump(&f_linear));
+    }
+
+    #[test]
+    fn test_bootstrap_recovers_known_curve() {
+        let truth = InterpolatedCurve::from_zero_rates(&[(1.0, 0.02), (3.0, 0.03), (5.0, 0.035)], Interpolation::LogLinear)
+            .unwrap();
+        let instruments: Vec<RateInstrument> = [1.0, 3.0, 5.0]
+            .iter()
+            .map(|&maturity| {
+                let template = RateInstrument::Swap {
+                    maturity,
+                    rate: 0.0,
+                    frequency: 1,
+                };
+                RateInstrument::Swap {
+                    maturity,
+                    rate: template.par_rate(&truth),
+                    frequency: 1,
+                }
+            })
+            .collect();
+        let curve = bootstrap(&instruments, Interpolation::LogLinear).unwrap();
+        for t in [0.5, 1.0, 2.0, 4.5, 5.0] {
+            assert!((curve.df(t) - truth.df(t)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_invalid_instruments() {
+        let duplicate = [
+            RateInstrument::Deposit {
+                maturity: 1.0,
+                rate: 0.03,
+            },
+            RateInstrument::Swap {
+                maturity: 1.0,
+                rate: 0.03,
+                frequency: 1,
+            },
+        ];
+        assert!(bootstrap(&duplicate, Interpolation::LogLinear).is_err());
+        assert!(bootstrap(&[], Interpolation::LogLinear).is_err());
+        let backwards = RateInstrument::Fra {
+            start: 0.5,
+            end: 0.25,
+            rate: 0.03,
+        };
+        assert!(bootstrap(&[backwards], Interpolation::LogLinear).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let json = serde_json::to_string(&market()).unwrap();
+        let restored: Vec<RateInstrument> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, market());
+
+        let curve = bootstrap(&market(), Interpolation::MonotoneCubic).unwrap();
+        let restored: InterpolatedCurve = serde_json::from_str(&serde_json::to_string(&curve).unwrap()).unwrap();
+        assert_eq!(restored.df(7.5), curve.df(7.5));
+    }
+}
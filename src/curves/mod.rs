@@ -0,0 +1,367 @@
+//! Discount and forward curves replacing flat rate and carry inputs
+
+pub mod bootstrap;
+pub mod money_market;
+
+pub use bootstrap::{bootstrap, RateInstrument};
+pub use money_market::{PublicRateInputs, RateCurveCache, SofrFuture, TreasuryBill, DEFAULT_RATE_VOLATILITY};
+
+use crate::error::BlackScholesError;
+use crate::math::{Gradient, Real, Tape, Var};
+use crate::validation;
+
+/// One basis point, the zero rate move bucketed sensitivities are quoted for
+const BASIS_POINT: f64 = 1e-4;
+
+/// Term structure of discount factors
+pub trait DiscountCurve {
+    /// Discount factor from `t` years back to today
+    fn df(&self, t: f64) -> f64;
+
+    /// Continuously compounded zero rate to `t`
+    fn zero_rate(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return self.forward_rate(0.0, 1e-6);
+        }
+        -self.df(t).ln() / t
+    }
+
+    /// Continuously compounded forward rate between `t1` and `t2`
+    fn forward_rate(&self, t1: f64, t2: f64) -> f64 {
+        (self.df(t1) / self.df(t2)).ln() / (t2 - t1)
+    }
+}
+
+/// Forward price of the underlying by delivery time
+pub trait ForwardCurve {
+    /// Forward price for delivery at `t` years
+    fn forward(&self, t: f64) -> f64;
+
+    /// Spot price (the forward for immediate delivery)
+    fn spot(&self) -> f64 {
+        self.forward(0.0)
+    }
+}
+
+/// Curve with a single continuously compounded rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlatCurve {
+    pub rate: f64,
+}
+
+impl FlatCurve {
+    pub fn new(rate: f64) -> Result<Self, BlackScholesError> {
+        Ok(FlatCurve {
+            rate: validation::finite("Curve rate", rate)?,
+        })
+    }
+}
+
+impl DiscountCurve for FlatCurve {
+    fn df(&self, t: f64) -> f64 {
+        (-self.rate * t).exp()
+    }
+
+    fn zero_rate(&self, _t: f64) -> f64 {
+        self.rate
+    }
+}
+
+/// Interpolation scheme between curve pillars
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    /// Zero rate held flat back to the previous pillar (step function)
+    PiecewiseFlat,
+    /// ln(df) linear in time, i.e. flat forward rates between pillars
+    LogLinear,
+    /// Fritsch-Carlson monotone cubic on -ln(df): smooth forwards that stay
+    /// positive wherever the pillar discount factors decrease
+    MonotoneCubic,
+}
+
+/// Discount curve interpolated between pillar discount factors
+///
+/// Beyond the last pillar the curve extrapolates with a flat zero rate
+/// (piecewise-flat) or a flat forward rate (log-linear, monotone cubic).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterpolatedCurve {
+    /// Pillar times in years, strictly increasing and positive
+    pub times: Vec<f64>,
+    /// Discount factors at the pillars
+    pub dfs: Vec<f64>,
+    pub interpolation: Interpolation,
+}
+
+impl InterpolatedCurve {
+    /// Build a curve from `(time, discount factor)` pillars
+    pub fn from_discount_factors(
+        pillars: &[(f64, f64)],
+        interpolation: Interpolation,
+    ) -> Result<Self, BlackScholesError> {
+        if pillars.is_empty() {
+            return Err(BlackScholesError::invalid("Curve needs at least one pillar"));
+        }
+        let mut times = Vec::with_capacity(pillars.len());
+        let mut dfs = Vec::with_capacity(pillars.len());
+        for &(t, df) in pillars {
+            let t = validation::positive("Pillar time", t)?;
+            if times.last().is_some_and(|&last| t <= last) {
+                return Err(BlackScholesError::invalid("Pillar times must be strictly increasing"));
+            }
+            times.push(t);
+            dfs.push(validation::positive("Discount factor", df)?);
+        }
+        Ok(InterpolatedCurve {
+            times,
+            dfs,
+            interpolation,
+        })
+    }
+
+    /// Build a curve from `(time, continuously compounded zero rate)` pillars
+    pub fn from_zero_rates(pillars: &[(f64, f64)], interpolation: Interpolation) -> Result<Self, BlackScholesError> {
+        let dfs = pillars
+            .iter()
+            .map(|&(t, r)| Ok((t, (-validation::finite("Zero rate", r)? * t).exp())))
+            .collect::<Result<Vec<_>, BlackScholesError>>()?;
+        Self::from_discount_factors(&dfs, interpolation)
+    }
+
+    /// Discount factor to `t` interpolated from `pillars` in place of the curve's own
+    ///
+    /// Written over [`Real`] so the pillar discount factors can be dual
+    /// numbers or tape variables; `df` is this with the curve's values.
+    pub fn df_with<T: Real>(&self, pillars: &[T], t: f64) -> T {
+        if t <= 0.0 {
+            return T::constant(1.0);
+        }
+        let n = self.times.len();
+        // First pillar at or after t; n when extrapolating
+        let i = self.times.partition_point(|&p| p < t);
+        match self.interpolation {
+            Interpolation::PiecewiseFlat => {
+                let j = i.min(n - 1);
+                // e^(-zero·t) with zero = -ln(df_j)/t_j
+                (pillars[j].ln() * (t / self.times[j])).exp()
+            }
+            Interpolation::LogLinear => log_linear(&self.times, pillars, T::constant(1.0), t),
+            Interpolation::MonotoneCubic => (-monotone_cubic(&self.times, pillars, t)).exp(),
+        }
+    }
+
+    /// The curve with its pillar discount factors recorded as variables on `tape`
+    pub fn on_tape<'t>(&self, tape: &'t Tape) -> AdjointCurve<'_, 't> {
+        AdjointCurve {
+            curve: self,
+            pillars: self.dfs.iter().map(|&df| tape.variable(df)).collect(),
+        }
+    }
+}
+
+impl DiscountCurve for InterpolatedCurve {
+    fn df(&self, t: f64) -> f64 {
+        self.df_with(&self.dfs, t)
+    }
+}
+
+/// Interpolated curve whose pillar discount factors are variables on a tape
+///
+/// Price any number of instruments off `df`, sweep the tape once from the
+/// total, and `zero_rate_buckets` gives the book's sensitivity to every
+/// pillar without a repricing per bump.
+pub struct AdjointCurve<'c, 't> {
+    curve: &'c InterpolatedCurve,
+    pub pillars: Vec<Var<'t>>,
+}
+
+impl<'t> AdjointCurve<'_, 't> {
+    /// Discount factor to `t` as a tape variable
+    pub fn df(&self, t: f64) -> Var<'t> {
+        self.curve.df_with(&self.pillars, t)
+    }
+
+    /// `(pillar time, value change)` for a one basis point rise in each pillar's zero rate
+    ///
+    /// First order: the derivative to the pillar zero rate times a basis point.
+    pub fn zero_rate_buckets(&self, gradient: &Gradient) -> Vec<(f64, f64)> {
+        self.curve
+            .times
+            .iter()
+            .zip(&self.curve.dfs)
+            .zip(&self.pillars)
+            .map(|((&t, &df), &pillar)| (t, -gradient.wrt(pillar) * t * df * BASIS_POINT))
+            .collect()
+    }
+}
+
+/// Fritsch-Carlson monotone cubic Hermite interpolation of y = -ln(df) through (0, 0)
+///
+/// Beyond the last pillar the last secant slope (forward rate) is extended.
+fn monotone_cubic<T: Real>(times: &[f64], dfs: &[T], t: f64) -> T {
+    let zero = T::constant(0.0);
+    let xs: Vec<f64> = std::iter::once(0.0).chain(times.iter().copied()).collect();
+    let ys: Vec<T> = std::iter::once(zero).chain(dfs.iter().map(|&df| -df.ln())).collect();
+    let n = xs.len();
+    let secants: Vec<T> = (0..n - 1).map(|k| (ys[k + 1] - ys[k]) / (xs[k + 1] - xs[k])).collect();
+    if t >= xs[n - 1] {
+        return ys[n - 1] + secants[n - 2] * (t - xs[n - 1]);
+    }
+
+    let mut slopes = vec![zero; n];
+    slopes[0] = secants[0];
+    slopes[n - 1] = secants[n - 2];
+    for k in 1..n - 1 {
+        if secants[k - 1].value() * secants[k].value() > 0.0 {
+            slopes[k] = (secants[k - 1] + secants[k]) * 0.5;
+        }
+    }
+    for k in 0..n - 1 {
+        if secants[k].value() == 0.0 {
+            slopes[k] = zero;
+            slopes[k + 1] = zero;
+            continue;
+        }
+        let (alpha, beta) = (slopes[k] / secants[k], slopes[k + 1] / secants[k]);
+        let norm = alpha * alpha + beta * beta;
+        if norm.value() > 9.0 {
+            let tau = T::constant(3.0) / norm.sqrt();
+            slopes[k] = tau * alpha * secants[k];
+            slopes[k + 1] = tau * beta * secants[k];
+        }
+    }
+
+    let k = xs.partition_point(|&x| x <= t).saturating_sub(1).min(n - 2);
+    let h = xs[k + 1] - xs[k];
+    let u = (t - xs[k]) / h;
+    let (u2, u3) = (u * u, u * u * u);
+    ys[k] * (2.0 * u3 - 3.0 * u2 + 1.0)
+        + slopes[k] * ((u3 - 2.0 * u2 + u) * h)
+        + ys[k + 1] * (-2.0 * u3 + 3.0 * u2)
+        + slopes[k + 1] * ((u3 - u2) * h)
+}
+
+/// Log-linear interpolation of positive `values` through (0, `origin`) and the pillars
+///
+/// Beyond the last pillar the last segment's log-slope is extended.
+fn log_linear<T: Real>(times: &[f64], values: &[T], origin: T, t: f64) -> T {
+    let i = times.partition_point(|&p| p < t).min(times.len() - 1);
+    let (t0, v0) = if i == 0 { (0.0, origin) } else { (times[i - 1], values[i - 1]) };
+    let (t1, v1) = (times[i], values[i]);
+    v0 * ((v1 / v0).ln() * ((t - t0) / (t1 - t0))).exp()
+}
+
+/// Forward curve interpolated log-linearly between quoted forwards
+///
+/// Log-linear interpolation keeps the implied carry rate constant between
+/// pillars; beyond the last pillar the last carry rate is extended.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterpolatedForward {
+    pub spot: f64,
+    /// Delivery times in years, strictly increasing and positive
+    pub times: Vec<f64>,
+    pub forwards: Vec<f64>,
+}
+
+impl InterpolatedForward {
+    /// Build a forward curve from spot and `(time, forward)` pillars
+    pub fn new(spot: f64, pillars: &[(f64, f64)]) -> Result<Self, BlackScholesError> {
+        let spot = validation::positive("Spot price", spot)?;
+        // A forward is a spot discounted by its carry, so reuse the curve checks
+        let relative: Vec<(f64, f64)> = pillars.iter().map(|&(t, f)| (t, f / spot)).collect();
+        let curve = InterpolatedCurve::from_discount_factors(&relative, Interpolation::LogLinear)?;
+        Ok(InterpolatedForward {
+            spot,
+            times: curve.times,
+            forwards: curve.dfs.iter().map(|g| g * spot).collect(),
+        })
+    }
+}
+
+impl ForwardCurve for InterpolatedForward {
+    fn forward(&self, t: f64) -> f64 {
+        log_linear(&self.times, &self.forwards, self.spot, t.max(0.0))
+    }
+
+    fn spot(&self) -> f64 {
+        self.spot
+    }
+}
+
+/// Forward implied by spot, a discount curve and a dividend/borrow curve
+///
+/// F(t) = S·D_q(t)/D_r(t), where D_q discounts at the dividend yield.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CarryForward<R: DiscountCurve, Q: DiscountCurve> {
+    pub spot: f64,
+    pub discount: R,
+    pub dividend: Q,
+}
+
+impl<R: DiscountCurve, Q: DiscountCurve> ForwardCurve for CarryForward<R, Q> {
+    fn forward(&self, t: f64) -> f64 {
+        self.spot * self.dividend.df(t) / self.discount.df(t)
+    }
+
+    fn spot(&self) -> f64 {
+        self.spot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_linear_has_flat_forwards_between_pillars() {
+        let curve =
+            InterpolatedCurve::from_zero_rates(&[(0.5, 0.02), (1.0, 0.03), (2.0, 0.035)], Interpolation::LogLinear)
+                .unwrap();
+        for &(t, r) in &[(0.5, 0.02), (1.0, 0.03), (2.0, 0.035)] {
+            assert!((curve.zero_rate(t) - r).abs() < 1e-14);
+        }
+        let f1 = curve.forward_rate(1.1, 1.2);
+        let f2 = curve.forward_rate(1.7, 1.9);
+        assert!((f1 - f2).abs() < 1e-12);
+        assert!((f1 - (0.035 * 2.0 - 0.03) / 1.0).abs() < 1e-12);
+        // Extrapolation continues the last forward rate
+        assert!((curve.forward_rate(3.0, 4.0) - f1).abs() < 1e-12);
+        assert_eq!(curve.df(0.0), 1.0);
+    }
+
+    #[test]
+    fn test_piecewise_flat_steps_zero_rates() {
+        let curve =
+            InterpolatedCurve::from_zero_rates(&[(1.0, 0.02), (2.0, 0.04)], Interpolation::PiecewiseFlat).unwrap();
+        assert!((curve.zero_rate(0.25) - 0.02).abs() < 1e-14);
+        assert!((curve.zero_rate(1.5) - 0.04).abs() < 1e-14);
+        assert!((curve.zero_rate(5.0) - 0.04).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_forward_curves() {
+        let carry = CarryForward {
+            spot: 100.0,
+            discount: FlatCurve::new(0.05).unwrap(),
+            dividend: FlatCurve::new(0.02).unwrap(),
+        };
+        assert!((carry.forward(1.0) - 100.0 * 0.03_f64.exp()).abs() < 1e-12);
+
+        let quoted = InterpolatedForward::new(100.0, &[(0.5, 101.0), (1.0, 103.0)]).unwrap();
+        assert!((quoted.forward(0.5) - 101.0).abs() < 1e-12);
+        assert!((quoted.forward(0.75) - (101.0_f64 * 103.0).sqrt()).abs() < 1e-10);
+        assert_eq!(quoted.spot(), 100.0);
+    }
+
+    #[test]
+    fn test_invalid_pillars() {
+        assert!(InterpolatedCurve::from_discount_factors(&[], Interpolation::LogLinear).is_err());
+        assert!(InterpolatedCurve::from_discount_factors(&[(1.0, 0.9), (0.5, 0.95)], Interpolation::LogLinear).is_err());
+        assert!(InterpolatedCurve::from_discount_factors(&[(1.0, -0.9)], Interpolation::LogLinear).is_err());
+        assert!(InterpolatedForward::new(100.0, &[(0.0, 100.0)]).is_err());
+    }
+}
@@ -0,0 +1,273 @@
+use super::bootstrap::{bootstrap, RateInstrument};
+use super::{InterpolatedCurve, Interpolation};
+use crate::error::BlackScholesError;
+use crate::validation;
+use std::collections::BTreeMap;
+
+/// Days per year in Treasury bill maturities
+const BILL_DAYS_PER_YEAR: f64 = 365.0;
+
+/// Day-count denominator of the bank discount basis
+const DISCOUNT_BASIS_DAYS: f64 = 360.0;
+
+/// Normal volatility of short rates assumed for futures convexity, 100bp a year
+pub const DEFAULT_RATE_VOLATILITY: f64 = 0.01;
+
+/// Treasury bill quoted on the bank discount basis
+///
+/// Price per unit face is 1 - d·days/360 with days = 365·maturity, so the
+/// discount factor to maturity is the price itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreasuryBill {
+    /// Maturity in years
+    pub maturity: f64,
+    /// Bank discount yield, e.g. 0.052 for 5.20%
+    pub discount_yield: f64,
+}
+
+impl TreasuryBill {
+    pub fn new(maturity: f64, discount_yield: f64) -> Result<Self, BlackScholesError> {
+        let bill = TreasuryBill {
+            maturity: validation::positive("Bill maturity", maturity)?,
+            discount_yield: validation::finite("Discount yield", discount_yield)?,
+        };
+        if bill.price() <= 0.0 {
+            return Err(BlackScholesError::invalid("Discount yield implies a non-positive bill price"));
+        }
+        Ok(bill)
+    }
+
+    /// Price per unit face value
+    pub fn price(&self) -> f64 {
+        1.0 - self.discount_yield * self.maturity * BILL_DAYS_PER_YEAR / DISCOUNT_BASIS_DAYS
+    }
+
+    /// Simple money-market rate earned to maturity, (1/price - 1)/maturity
+    pub fn money_market_rate(&self) -> f64 {
+        (1.0 / self.price() - 1.0) / self.maturity
+    }
+}
+
+/// Three-month SOFR future over its reference quarter, quoted as 100·(1 - rate)
+///
+/// The contract settles on SOFR compounded over [start, end], whose fair
+/// value is the simple forward rate for the period. Futures settle daily,
+/// so their rate sits above that forward by a convexity adjustment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SofrFuture {
+    /// Start of the reference quarter in years
+    pub start: f64,
+    /// End of the reference quarter in years
+    pub end: f64,
+    pub price: f64,
+}
+
+impl SofrFuture {
+    pub fn new(start: f64, end: f64, price: f64) -> Result<Self, BlackScholesError> {
+        let start = validation::non_negative("Reference period start", start)?;
+        if validation::finite("Reference period end", end)? <= start {
+            return Err(BlackScholesError::invalid("Reference period must end after it starts"));
+        }
+        Ok(SofrFuture {
+            start,
+            end,
+            price: validation::finite("Futures price", price)?,
+        })
+    }
+
+    /// Futures rate less forward rate under Ho-Lee, σ²·start·end/2
+    pub fn convexity_adjustment(&self, rate_volatility: f64) -> f64 {
+        0.5 * rate_volatility * rate_volatility * self.start * self.end
+    }
+}
+
+/// Treasury bill yields and a SOFR futures strip, the public inputs to a discount curve
+///
+/// Bills pin the front end and futures the rest, so bills maturing after
+/// the first futures reference period starts are left out: the futures
+/// strip is the more liquid source there and the two would otherwise
+/// overlap. Futures periods should follow one another without gaps.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PublicRateInputs {
+    pub bills: Vec<TreasuryBill>,
+    pub futures: Vec<SofrFuture>,
+    /// Normal short-rate volatility for the futures convexity adjustment
+    pub rate_volatility: f64,
+    pub interpolation: Interpolation,
+}
+
+impl PublicRateInputs {
+    /// Inputs with the default convexity volatility and monotone cubic interpolation
+    pub fn new(bills: Vec<TreasuryBill>, futures: Vec<SofrFuture>) -> Result<Self, BlackScholesError> {
+        if bills.is_empty() && futures.is_empty() {
+            return Err(BlackScholesError::invalid("Need at least one bill or futures quote"));
+        }
+        Ok(PublicRateInputs {
+            bills,
+            futures,
+            rate_volatility: DEFAULT_RATE_VOLATILITY,
+            interpolation: Interpolation::MonotoneCubic,
+        })
+    }
+
+    pub fn with_rate_volatility(mut self, rate_volatility: f64) -> Result<Self, BlackScholesError> {
+        self.rate_volatility = validation::non_negative("Rate volatility", rate_volatility)?;
+        Ok(self)
+    }
+
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Bootstrap instruments: bills as deposits, futures with their convexity adjustment
+    pub fn instruments(&self) -> Vec<RateInstrument> {
+        let cutoff = self.futures.iter().map(|f| f.start).fold(f64::INFINITY, f64::min);
+        let bills = self
+            .bills
+            .iter()
+            .filter(|bill| bill.maturity <= cutoff)
+            .map(|bill| RateInstrument::Deposit {
+                maturity: bill.maturity,
+                rate: bill.money_market_rate(),
+            });
+        let futures = self.futures.iter().map(|future| RateInstrument::Future {
+            start: future.start,
+            end: future.end,
+            price: future.price,
+            convexity_adjustment: future.convexity_adjustment(self.rate_volatility),
+        });
+        bills.chain(futures).collect()
+    }
+
+    /// Discount curve repricing every bill and future used
+    pub fn build(&self) -> Result<InterpolatedCurve, BlackScholesError> {
+        bootstrap(&self.instruments(), self.interpolation)
+    }
+}
+
+/// Curves built from public inputs, keyed by an as-of label such as a date
+///
+/// A curve is rebuilt only when the inputs stored under its label change,
+/// so pricing code can ask for the day's curve as often as it likes.
+/// Serializable (with the `serde` feature) to persist across runs.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateCurveCache {
+    curves: BTreeMap<String, (PublicRateInputs, InterpolatedCurve)>,
+}
+
+impl RateCurveCache {
+    pub fn new() -> Self {
+        RateCurveCache::default()
+    }
+
+    /// Curve for `as_of`, bootstrapped from `inputs` unless already cached for them
+    pub fn curve(&mut self, as_of: &str, inputs: &PublicRateInputs) -> Result<&InterpolatedCurve, BlackScholesError> {
+        let stale = self.curves.get(as_of).is_none_or(|(cached, _)| cached != inputs);
+        if stale {
+            let curve = inputs.build()?;
+            self.curves.insert(as_of.to_string(), (inputs.clone(), curve));
+        }
+        Ok(&self.curves[as_of].1)
+    }
+
+    /// Cached curve for `as_of`, if any
+    pub fn get(&self, as_of: &str) -> Option<&InterpolatedCurve> {
+        self.curves.get(as_of).map(|(_, curve)| curve)
+    }
+
+    pub fn len(&self) -> usize {
+        self.curves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.curves.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::{DiscountCurve, FlatCurve};
+
+    /// Bills and a two-year quarterly futures strip consistent with `truth`
+    fn inputs_from(truth: &dyn DiscountCurve) -> PublicRateInputs {
+        let bills = [1.0 / 12.0, 2.0 / 12.0, 0.25, 0.5]
+            .iter()
+            .map(|&t| {
+                let discount_yield = (1.0 - truth.df(t)) * DISCOUNT_BASIS_DAYS / (BILL_DAYS_PER_YEAR * t);
+                TreasuryBill::new(t, discount_yield).unwrap()
+            })
+            .collect();
+        let futures = (1..8)
+            .map(|q| {
+                let (start, end) = (0.25 * q as f64, 0.25 * (q + 1) as f64);
+                let forward = (truth.df(start) / truth.df(end) - 1.0) / (end - start);
+                let template = SofrFuture::new(start, end, 100.0).unwrap();
+                let rate = forward + template.convexity_adjustment(DEFAULT_RATE_VOLATILITY);
+                SofrFuture::new(start, end, 100.0 * (1.0 - rate)).unwrap()
+            })
+            .collect();
+        PublicRateInputs::new(bills, futures).unwrap()
+    }
+
+    #[test]
+    fn test_bills_and_futures_recover_the_curve() {
+        let truth = FlatCurve::new(0.045).unwrap();
+        let inputs = inputs_from(&truth).with_interpolation(Interpolation::LogLinear);
+        // The six-month bill overlaps the futures strip and is left out
+        assert_eq!(inputs.instruments().len(), 3 + 7);
+        let curve = inputs.build().unwrap();
+        for t in [0.1, 0.25, 0.9, 1.4, 2.0] {
+            assert!((curve.df(t) - truth.df(t)).abs() < 1e-12, "{t}");
+        }
+    }
+
+    #[test]
+    fn test_bill_prices_are_discount_factors() {
+        let bill = TreasuryBill::new(0.5, 0.05).unwrap();
+        assert!((bill.price() - (1.0 - 0.05 * 182.5 / 360.0)).abs() < 1e-15);
+        let curve = PublicRateInputs::new(vec![bill, TreasuryBill::new(1.0, 0.048).unwrap()], vec![])
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!((curve.df(0.5) - bill.price()).abs() < 1e-12);
+        // Futures trade above the forward, by more the further out they fix
+        let near = SofrFuture::new(0.25, 0.5, 95.0).unwrap();
+        let far = SofrFuture::new(2.0, 2.25, 95.0).unwrap();
+        assert!(far.convexity_adjustment(0.01) > near.convexity_adjustment(0.01));
+    }
+
+    #[test]
+    fn test_cache_rebuilds_only_on_new_inputs() {
+        let mut cache = RateCurveCache::new();
+        let monday = inputs_from(&FlatCurve::new(0.04).unwrap());
+        let tuesday = inputs_from(&FlatCurve::new(0.041).unwrap());
+        let first = cache.curve("2024-06-03", &monday).unwrap().clone();
+        assert_eq!(cache.curve("2024-06-03", &monday).unwrap(), &first);
+        cache.curve("2024-06-04", &tuesday).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("2024-06-04").unwrap().df(1.0) < first.df(1.0));
+
+        // Corrected quotes under an existing label replace its curve
+        let corrected = inputs_from(&FlatCurve::new(0.042).unwrap());
+        let rebuilt = cache.curve("2024-06-03", &corrected).unwrap().df(1.0);
+        assert!((rebuilt - (-0.042_f64).exp()).abs() < 1e-9);
+        assert!(cache.get("2024-06-05").is_none());
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(PublicRateInputs::new(vec![], vec![]).is_err());
+        assert!(TreasuryBill::new(1.0, 1.0).is_err());
+        assert!(TreasuryBill::new(0.0, 0.05).is_err());
+        assert!(SofrFuture::new(0.5, 0.25, 95.0).is_err());
+        assert!(SofrFuture::new(0.25, 0.5, f64::NAN).is_err());
+        let bill = TreasuryBill::new(0.25, 0.05).unwrap();
+        assert!(PublicRateInputs::new(vec![bill], vec![]).unwrap().with_rate_volatility(-0.01).is_err());
+    }
+}
@@ -0,0 +1,348 @@
+//! Risk-neutral densities from option prices and divergence between two of them
+
+use crate::black_scholes::OptionType;
+use crate::error::BlackScholesError;
+use crate::model::EuropeanModel;
+use crate::moments::Moments;
+use crate::validation;
+use std::f64::consts::PI;
+
+/// Strikes on the log-uniform grid extracted from a model
+const DENSITY_POINTS: usize = 801;
+
+/// Half-width of the strike grid in at-the-money standard deviations of ln K
+const DENSITY_WIDTH: f64 = 10.0;
+
+/// Smallest half-width of the strike grid in ln K
+const MIN_DENSITY_WIDTH: f64 = 0.05;
+
+/// Points on the common grid over which two densities are compared
+const COMPARISON_POINTS: usize = 2001;
+
+/// Floor on the second density in a divergence, relative to its peak
+const DENSITY_FLOOR: f64 = 1e-12;
+
+/// Probabilities at which quantile shifts are reported
+const COMPARISON_QUANTILES: [f64; 7] = [0.01, 0.05, 0.25, 0.5, 0.75, 0.95, 0.99];
+
+/// Risk-neutral density of the terminal price on a strike grid
+///
+/// Between grid points the density is linear, and it is zero outside the
+/// grid. The stored density integrates to one; `mass` keeps what it
+/// integrated to before normalisation, which falls short of one when the
+/// grid misses tail probability.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RiskNeutralDensity {
+    /// Spot the log-returns of `moments` are measured from
+    pub spot: f64,
+    /// Horizon in years
+    pub expiry: f64,
+    /// Increasing terminal prices
+    pub strikes: Vec<f64>,
+    pub density: Vec<f64>,
+    /// Integral of the density before normalisation
+    pub mass: f64,
+}
+
+/// Divergence, moment and quantile changes from a reference density to a current one
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DensityComparison {
+    /// KL(current ‖ reference): information lost describing the current density by the reference
+    pub kl_divergence: f64,
+    /// KL(reference ‖ current)
+    pub reverse_kl_divergence: f64,
+    /// Log-return moments of the current density less those of the reference
+    pub moment_change: Moments,
+    pub quantile_shifts: Vec<QuantileShift>,
+}
+
+/// Terminal price at one probability level under both densities
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantileShift {
+    pub probability: f64,
+    pub reference: f64,
+    pub current: f64,
+}
+
+impl QuantileShift {
+    /// Shift in log price, ln(current / reference)
+    pub fn log_shift(&self) -> f64 {
+        (self.current / self.reference).ln()
+    }
+}
+
+/// Trapezoid integral of `f(strike, density)` over the grid
+fn integrate(strikes: &[f64], density: &[f64], f: impl Fn(f64, f64) -> f64) -> f64 {
+    strikes
+        .windows(2)
+        .zip(density.windows(2))
+        .map(|(k, q)| 0.5 * (k[1] - k[0]) * (f(k[0], q[0]) + f(k[1], q[1])))
+        .sum()
+}
+
+impl RiskNeutralDensity {
+    /// Density from tabulated values, normalised to integrate to one
+    ///
+    /// # Arguments
+    /// * `spot` - Price the log-return moments are measured from
+    /// * `expiry` - Horizon in years
+    /// * `strikes` - At least three strictly increasing positive prices
+    /// * `density` - Non-negative density at each strike
+    pub fn new(spot: f64, expiry: f64, strikes: Vec<f64>, density: Vec<f64>) -> Result<Self, BlackScholesError> {
+        let spot = validation::positive("Spot price", spot)?;
+        let expiry = validation::positive("Expiry", expiry)?;
+        if strikes.len() < 3 || strikes.len() != density.len() {
+            return Err(BlackScholesError::invalid("Density needs at least three strikes, one value each"));
+        }
+        if strikes[0] <= 0.0 || strikes.windows(2).any(|k| k[1] <= k[0] || !k[1].is_finite()) {
+            return Err(BlackScholesError::invalid("Density strikes must be positive and strictly increasing"));
+        }
+        if density.iter().any(|q| !q.is_finite() || *q < 0.0) {
+            return Err(BlackScholesError::invalid("Density values must be finite and non-negative"));
+        }
+        let mass = integrate(&strikes, &density, |_, q| q);
+        if mass <= 0.0 {
+            return Err(BlackScholesError::invalid("Density has no mass"));
+        }
+        let density = density.into_iter().map(|q| q / mass).collect();
+        Ok(RiskNeutralDensity {
+            spot,
+            expiry,
+            strikes,
+            density,
+            mass,
+        })
+    }
+
+    /// Breeden-Litzenberger density e^(rT)·∂²C/∂K² of a model's call prices
+    ///
+    /// Second differences on a log-uniform strike grid around the forward,
+    /// wide enough for the at-the-money volatility. Butterflies priced below
+    /// zero, which an arbitrage-free model never produces, are clamped to a
+    /// zero density.
+    pub fn from_model<M: EuropeanModel + ?Sized>(model: &M, expiry: f64) -> Result<Self, BlackScholesError> {
+        let expiry = validation::positive("Expiry", expiry)?;
+        let forward = model.forward(expiry);
+        let growth = (model.rate(expiry) * expiry).exp();
+        // Brenner-Subrahmanyam: an at-the-money call is about F·σ√T/√(2π)
+        let atm = growth * model.price(OptionType::Call, forward, expiry) / forward;
+        let width = (DENSITY_WIDTH * (2.0 * PI).sqrt() * atm).max(MIN_DENSITY_WIDTH);
+        let strikes: Vec<f64> = (0..DENSITY_POINTS + 2)
+            .map(|i| forward * (-width + 2.0 * width * i as f64 / (DENSITY_POINTS + 1) as f64).exp())
+            .collect();
+        let calls: Vec<f64> = strikes.iter().map(|&k| model.price(OptionType::Call, k, expiry)).collect();
+        let density = (1..=DENSITY_POINTS)
+            .map(|i| {
+                let (below, above) = (strikes[i] - strikes[i - 1], strikes[i + 1] - strikes[i]);
+                let slope_above = (calls[i + 1] - calls[i]) / above;
+                let slope_below = (calls[i] - calls[i - 1]) / below;
+                (growth * 2.0 * (slope_above - slope_below) / (below + above)).max(0.0)
+            })
+            .collect();
+        RiskNeutralDensity::new(model.spot(), expiry, strikes[1..=DENSITY_POINTS].to_vec(), density)
+    }
+
+    /// Density at `price`, linear between grid points and zero outside
+    pub fn pdf(&self, price: f64) -> f64 {
+        let n = self.strikes.len();
+        if !(price >= self.strikes[0] && price <= self.strikes[n - 1]) {
+            return 0.0;
+        }
+        let i = self.strikes.partition_point(|&k| k <= price).clamp(1, n - 1);
+        let (k0, k1) = (self.strikes[i - 1], self.strikes[i]);
+        let w = (price - k0) / (k1 - k0);
+        (1.0 - w) * self.density[i - 1] + w * self.density[i]
+    }
+
+    /// Cumulative probability at each grid point
+    fn cumulative(&self) -> Vec<f64> {
+        let mut total = 0.0;
+        let mut cdf = Vec::with_capacity(self.strikes.len());
+        cdf.push(0.0);
+        for (k, q) in self.strikes.windows(2).zip(self.density.windows(2)) {
+            total += 0.5 * (k[1] - k[0]) * (q[0] + q[1]);
+            cdf.push(total);
+        }
+        cdf
+    }
+
+    /// Probability that the terminal price is at most `price`
+    pub fn cdf(&self, price: f64) -> f64 {
+        let n = self.strikes.len();
+        if price <= self.strikes[0] {
+            return 0.0;
+        }
+        if price >= self.strikes[n - 1] {
+            return 1.0;
+        }
+        let cdf = self.cumulative();
+        let i = self.strikes.partition_point(|&k| k <= price);
+        let k0 = self.strikes[i - 1];
+        cdf[i - 1] + 0.5 * (price - k0) * (self.density[i - 1] + self.pdf(price))
+    }
+
+    /// Terminal price below which the probability is `probability`
+    pub fn quantile(&self, probability: f64) -> Result<f64, BlackScholesError> {
+        let p = validation::in_range("Probability", probability, 0.0, 1.0, "in (0, 1)")?;
+        if p <= 0.0 || p >= 1.0 {
+            return Err(BlackScholesError::invalid("Probability must be in (0, 1)"));
+        }
+        let cdf = self.cumulative();
+        let i = cdf.partition_point(|&c| c < p).clamp(1, cdf.len() - 1);
+        let (c0, c1) = (cdf[i - 1], cdf[i]);
+        let w = if c1 > c0 { (p - c0) / (c1 - c0) } else { 0.0 };
+        Ok(self.strikes[i - 1] + w * (self.strikes[i] - self.strikes[i - 1]))
+    }
+
+    /// Expected terminal price, the forward when the grid holds all the mass
+    pub fn mean(&self) -> f64 {
+        integrate(&self.strikes, &self.density, |k, q| k * q)
+    }
+
+    /// Mean, variance, skewness and kurtosis of ln(S_T / spot)
+    pub fn moments(&self) -> Moments {
+        let log_moment = |n: i32| integrate(&self.strikes, &self.density, |k, q| (k / self.spot).ln().powi(n) * q);
+        let mean = log_moment(1);
+        let central = |n: i32| {
+            integrate(&self.strikes, &self.density, |k, q| ((k / self.spot).ln() - mean).powi(n) * q)
+        };
+        let variance = central(2);
+        Moments {
+            mean,
+            variance,
+            skewness: central(3) / variance.powf(1.5),
+            kurtosis: central(4) / (variance * variance),
+        }
+    }
+}
+
+/// Kullback-Leibler divergence KL(p ‖ q) = ∫ p·ln(p/q) dS
+///
+/// Both densities are evaluated on a common log-uniform grid spanning
+/// either's support. Where `p` has mass and `q` has none the divergence is
+/// infinite in theory; `q` is floored at a tiny fraction of its peak so the
+/// result stays finite but large.
+pub fn kl_divergence(p: &RiskNeutralDensity, q: &RiskNeutralDensity) -> f64 {
+    let lo = p.strikes[0].min(q.strikes[0]);
+    let hi = p.strikes[p.strikes.len() - 1].max(q.strikes[q.strikes.len() - 1]);
+    let grid: Vec<f64> = (0..COMPARISON_POINTS)
+        .map(|i| lo * ((hi / lo).ln() * i as f64 / (COMPARISON_POINTS - 1) as f64).exp())
+        .collect();
+    let floor = DENSITY_FLOOR * q.density.iter().cloned().fold(0.0, f64::max);
+    let integrand: Vec<f64> = grid
+        .iter()
+        .map(|&s| {
+            let density = p.pdf(s);
+            if density <= 0.0 {
+                0.0
+            } else {
+                density * (density / q.pdf(s).max(floor)).ln()
+            }
+        })
+        .collect();
+    integrate(&grid, &integrand, |_, f| f).max(0.0)
+}
+
+impl DensityComparison {
+    /// Compare `current` against `reference`, e.g. today against yesterday or model against market
+    pub fn new(reference: &RiskNeutralDensity, current: &RiskNeutralDensity) -> Result<Self, BlackScholesError> {
+        let (before, after) = (reference.moments(), current.moments());
+        let quantile_shifts = COMPARISON_QUANTILES
+            .iter()
+            .map(|&probability| {
+                Ok(QuantileShift {
+                    probability,
+                    reference: reference.quantile(probability)?,
+                    current: current.quantile(probability)?,
+                })
+            })
+            .collect::<Result<Vec<_>, BlackScholesError>>()?;
+        Ok(DensityComparison {
+            kl_divergence: kl_divergence(current, reference),
+            reverse_kl_divergence: kl_divergence(reference, current),
+            moment_change: Moments {
+                mean: after.mean - before.mean,
+                variance: after.variance - before.variance,
+                skewness: after.skewness - before.skewness,
+                kurtosis: after.kurtosis - before.kurtosis,
+            },
+            quantile_shifts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::heston::{Heston, HestonParams};
+    use crate::math::norm_inv_cdf;
+
+    fn lognormal(volatility: f64) -> RiskNeutralDensity {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, volatility, 0.01).unwrap();
+        RiskNeutralDensity::from_model(&bs, 1.0).unwrap()
+    }
+
+    #[test]
+    fn test_black_scholes_density_is_lognormal() {
+        let density = lognormal(0.25);
+        let forward = 100.0 * 0.02_f64.exp();
+        assert!((density.mass - 1.0).abs() < 1e-6, "{}", density.mass);
+        assert!((density.mean() - forward).abs() < 1e-3 * forward);
+        let moments = density.moments();
+        assert!((moments.mean - (0.02 - 0.5 * 0.0625)).abs() < 1e-4);
+        assert!((moments.variance - 0.0625).abs() < 1e-4);
+        assert!(moments.skewness.abs() < 1e-3 && (moments.kurtosis - 3.0).abs() < 1e-2);
+        for p in [0.05, 0.5, 0.95] {
+            let exact = forward * (-0.5 * 0.0625 + 0.25 * norm_inv_cdf(p)).exp();
+            assert!((density.quantile(p).unwrap() - exact).abs() < 1e-3 * exact);
+            assert!((density.cdf(exact) - p).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_kl_divergence_of_lognormals_matches_normals() {
+        let (a, b) = (lognormal(0.2), lognormal(0.3));
+        assert!(kl_divergence(&a, &a) < 1e-10);
+        // ln S_T is normal with variance σ²T and mean -σ²T/2 about ln F
+        let (va, vb): (f64, f64) = (0.04, 0.09);
+        let exact = 0.5 * ((vb / va).ln() + (va + 0.25 * (va - vb).powi(2)) / vb - 1.0);
+        let kl = kl_divergence(&a, &b);
+        assert!((kl - exact).abs() < 1e-3 * exact, "{kl} vs {exact}");
+        assert!((kl_divergence(&b, &a) - kl).abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_comparison_picks_up_skew() {
+        let params = HestonParams::new(0.04, 1.5, 0.04, 0.6, -0.8).unwrap();
+        let heston = Heston::new(100.0, 0.03, 0.01, params).unwrap();
+        let skewed = RiskNeutralDensity::from_model(&heston, 1.0).unwrap();
+        let comparison = DensityComparison::new(&lognormal(0.2), &skewed).unwrap();
+        assert!(comparison.kl_divergence > 1e-3 && comparison.reverse_kl_divergence > 1e-3);
+        assert!(comparison.moment_change.skewness < -0.3, "{:?}", comparison.moment_change);
+        assert!(comparison.moment_change.kurtosis > 0.0);
+        // The left tail moves down, the median up
+        let shifts = &comparison.quantile_shifts;
+        assert_eq!(shifts.len(), COMPARISON_QUANTILES.len());
+        assert!(shifts[0].log_shift() < 0.0 && shifts[3].log_shift() > 0.0, "{shifts:?}");
+
+        let same = DensityComparison::new(&skewed, &skewed).unwrap();
+        assert!(same.kl_divergence < 1e-10 && same.quantile_shifts.iter().all(|s| s.log_shift() == 0.0));
+    }
+
+    #[test]
+    fn test_invalid_densities() {
+        let strikes = vec![90.0, 100.0, 110.0];
+        assert!(RiskNeutralDensity::new(100.0, 1.0, strikes.clone(), vec![0.1, 0.2]).is_err());
+        assert!(RiskNeutralDensity::new(100.0, 1.0, vec![90.0, 90.0, 110.0], vec![0.1, 0.2, 0.1]).is_err());
+        assert!(RiskNeutralDensity::new(100.0, 1.0, strikes.clone(), vec![0.1, -0.2, 0.1]).is_err());
+        assert!(RiskNeutralDensity::new(100.0, 1.0, strikes.clone(), vec![0.0; 3]).is_err());
+        let density = RiskNeutralDensity::new(100.0, 1.0, strikes, vec![0.0, 1.0, 0.0]).unwrap();
+        assert!((density.mass - 10.0).abs() < 1e-12);
+        assert!(density.quantile(0.0).is_err() && density.quantile(1.5).is_err());
+        assert_eq!(density.quantile(0.5).unwrap(), 100.0);
+    }
+}
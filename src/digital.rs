@@ -0,0 +1,264 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::error::BlackScholesError;
+use crate::validation;
+use crate::vol_surface::VolSurface;
+
+/// Payoff paid when a digital option finishes in the money
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DigitalPayoff {
+    /// Pays a fixed cash amount
+    CashOrNothing { cash: f64 },
+    /// Pays one unit of the underlying asset
+    AssetOrNothing,
+}
+
+/// European digital (binary) option
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DigitalOption {
+    /// Underlying market parameters and strike
+    pub model: BlackScholes,
+    /// What the option pays when it finishes in the money
+    pub payoff: DigitalPayoff,
+}
+
+impl DigitalOption {
+    /// Create a new digital option
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes parameters (spot, strike, expiry, rate, vol, dividend)
+    /// * `payoff` - Cash-or-nothing amount or asset-or-nothing
+    pub fn new(model: BlackScholes, payoff: DigitalPayoff) -> Result<Self, BlackScholesError> {
+        if let DigitalPayoff::CashOrNothing { cash } = payoff {
+            validation::positive("Cash payout", cash)?;
+        }
+        Ok(DigitalOption { model, payoff })
+    }
+
+    /// Terms shared by price and Greeks: (φ, base amount, carry rate, d, d1, d2)
+    ///
+    /// The price is base·N(φ·d) with base = Q·e^(-rT) and d = d2 for
+    /// cash-or-nothing, base = S·e^(-qT) and d = d1 for asset-or-nothing.
+    fn terms(&self, option_type: OptionType) -> (f64, f64, f64, f64, f64, f64) {
+        let m = &self.model;
+        let sqrt_v = m.vol_time.sqrt();
+        let d1 = ((m.spot_price / m.strike_price).ln()
+            + (m.risk_free_rate - m.dividend_yield) * m.time_to_expiry
+            + 0.5 * m.volatility.powi(2) * m.vol_time)
+            / (m.volatility * sqrt_v);
+        let d2 = d1 - m.volatility * sqrt_v;
+        let phi = match option_type {
+            OptionType::Call => 1.0,
+            OptionType::Put => -1.0,
+        };
+
+        match self.payoff {
+            DigitalPayoff::CashOrNothing { cash } => {
+                let base = cash * (-m.risk_free_rate * m.time_to_expiry).exp();
+                (phi, base, m.risk_free_rate, d2, d1, d2)
+            }
+            DigitalPayoff::AssetOrNothing => {
+                let base = m.spot_price * (-m.dividend_yield * m.time_to_expiry).exp();
+                (phi, base, m.dividend_yield, d1, d1, d2)
+            }
+        }
+    }
+
+    /// Calculate option price
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    ///
+    /// # Returns
+    /// Option price
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        let (phi, base, _, d, _, _) = self.terms(option_type);
+        base * BlackScholes::norm_cdf(phi * d)
+    }
+
+    /// Calculate all Greeks analytically
+    ///
+    /// Conventions match `BlackScholes::greeks`: vega and rho per 1% move,
+    /// theta per day of the model's time scale.
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        let m = &self.model;
+        let (phi, base, carry, d, d1, d2) = self.terms(option_type);
+        let is_asset = self.payoff == DigitalPayoff::AssetOrNothing;
+        let sigma_sqrt_v = m.volatility * m.vol_time.sqrt();
+        let cdf = BlackScholes::norm_cdf(phi * d);
+        let density = base * BlackScholes::norm_pdf(d);
+
+        // Partial derivatives of d (d1 and d2 differ only in the volatility terms)
+        let dd_dspot = 1.0 / (m.spot_price * sigma_sqrt_v);
+        let dd_dvol = if is_asset { -d2 / m.volatility } else { -d1 / m.volatility };
+        let dd_dvol_time = if is_asset { -d2 / (2.0 * m.vol_time) } else { -d1 / (2.0 * m.vol_time) };
+        let dd_drate_time = (m.risk_free_rate - m.dividend_yield) / sigma_sqrt_v;
+        let dd_drate = m.time_to_expiry / sigma_sqrt_v;
+
+        let spot_level = if is_asset { cdf / m.spot_price * base } else { 0.0 };
+        let delta = spot_level + phi * density * dd_dspot;
+
+        // Differentiate delta again, using n'(d) = -d·n(d)
+        let gamma = if is_asset {
+            phi * density * dd_dspot * (1.0 - d / sigma_sqrt_v) / m.spot_price
+        } else {
+            -phi * density * dd_dspot * (1.0 + d / sigma_sqrt_v) / m.spot_price
+        };
+
+        let vega = phi * density * dd_dvol / 100.0;
+
+        let rate_base = if is_asset { 0.0 } else { -m.time_to_expiry * base * cdf };
+        let rho = (rate_base + phi * density * dd_drate) / 100.0;
+
+        let dvalue_drate_time = -carry * base * cdf + phi * density * dd_drate_time;
+        let dvalue_dvol_time = phi * density * dd_dvol_time;
+        let theta = -(dvalue_drate_time + dvalue_dvol_time) / m.time_scale.days_per_year();
+
+        Greeks {
+            delta,
+            gamma,
+            vega,
+            theta,
+            rho,
+        }
+    }
+
+    /// Price the digital as the limit of a tight vanilla spread on a smile
+    ///
+    /// Replicates the digital with vanillas struck `width / 2` either side of
+    /// the strike, each priced at its own surface volatility, which picks up
+    /// the skew correction -vega·∂σ/∂K that the flat-vol formula misses.
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    /// * `surface` - Implied volatility surface
+    /// * `width` - Distance between the two replicating strikes, below twice the strike
+    pub fn smile_adjusted_price(
+        &self,
+        option_type: OptionType,
+        surface: &dyn VolSurface,
+        width: f64,
+    ) -> Result<f64, BlackScholesError> {
+        let m = &self.model;
+        let width = validation::positive("Spread width", width)?;
+        if width >= 2.0 * m.strike_price {
+            return Err(BlackScholesError::OutOfRange {
+                field: "Spread width",
+                value: width,
+                requirement: "below twice the strike",
+            });
+        }
+        let vanilla = |strike: f64| {
+            let model = BlackScholes {
+                strike_price: strike,
+                volatility: surface.implied_vol(strike, m.time_to_expiry),
+                ..*m
+            };
+            model.price(option_type)
+        };
+
+        let lower = vanilla(m.strike_price - 0.5 * width);
+        let upper = vanilla(m.strike_price + 0.5 * width);
+        // Unit cash digital: -dC/dK for calls, +dP/dK for puts
+        let unit_digital = match option_type {
+            OptionType::Call => (lower - upper) / width,
+            OptionType::Put => (upper - lower) / width,
+        };
+
+        Ok(match self.payoff {
+            DigitalPayoff::CashOrNothing { cash } => cash * unit_digital,
+            DigitalPayoff::AssetOrNothing => {
+                let at_strike = vanilla(m.strike_price);
+                match option_type {
+                    OptionType::Call => at_strike + m.strike_price * unit_digital,
+                    OptionType::Put => m.strike_price * unit_digital - at_strike,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vol_surface::FlatVol;
+
+    struct LinearSkew;
+
+    impl VolSurface for LinearSkew {
+        fn implied_vol(&self, strike: f64, _expiry: f64) -> f64 {
+            0.2 - 0.002 * (strike - 100.0)
+        }
+    }
+
+    fn model() -> BlackScholes {
+        BlackScholes::new(100.0, 105.0, 0.5, 0.04, 0.25, 0.01).unwrap()
+    }
+
+    #[test]
+    fn test_digital_parity() {
+        let bs = model();
+        let cash = DigitalOption::new(bs, DigitalPayoff::CashOrNothing { cash: 1.0 }).unwrap();
+        let asset = DigitalOption::new(bs, DigitalPayoff::AssetOrNothing).unwrap();
+
+        // Call + put cash digitals pay 1 for sure
+        let bond = (-bs.risk_free_rate * bs.time_to_expiry).exp();
+        assert!((cash.price(OptionType::Call) + cash.price(OptionType::Put) - bond).abs() < 1e-12);
+
+        // Asset-or-nothing minus K cash-or-nothing is the vanilla
+        let vanilla = asset.price(OptionType::Call) - bs.strike_price * cash.price(OptionType::Call);
+        assert!((vanilla - bs.price(OptionType::Call)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_greeks_match_finite_differences() {
+        for payoff in [DigitalPayoff::CashOrNothing { cash: 10.0 }, DigitalPayoff::AssetOrNothing] {
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let digital = DigitalOption::new(model(), payoff).unwrap();
+                let analytic = digital.greeks(option_type);
+                let numeric = Greeks::from_finite_differences(&digital.model, |m| {
+                    DigitalOption { model: *m, payoff }.price(option_type)
+                });
+
+                assert!((analytic.delta - numeric.delta).abs() < 1e-4, "{:?}", payoff);
+                assert!((analytic.gamma - numeric.gamma).abs() < 1e-4, "{:?}", payoff);
+                assert!((analytic.vega - numeric.vega).abs() < 1e-4, "{:?}", payoff);
+                assert!((analytic.theta - numeric.theta).abs() < 1e-4, "{:?}", payoff);
+                assert!((analytic.rho - numeric.rho).abs() < 1e-4, "{:?}", payoff);
+            }
+        }
+    }
+
+    #[test]
+    fn test_smile_adjusted_digital() {
+        let bs = BlackScholes { volatility: 0.19, ..model() };
+        let digital = DigitalOption::new(bs, DigitalPayoff::CashOrNothing { cash: 1.0 }).unwrap();
+
+        // Flat smile reproduces the analytic price
+        let flat = digital.smile_adjusted_price(OptionType::Call, &FlatVol::new(0.19), 0.01).unwrap();
+        assert!((flat - digital.price(OptionType::Call)).abs() < 1e-4);
+
+        // Downward skew makes the digital call more expensive
+        let skewed = digital.smile_adjusted_price(OptionType::Call, &LinearSkew, 0.01).unwrap();
+        let analytic = DigitalOption::new(
+            BlackScholes { volatility: LinearSkew.implied_vol(105.0, 0.5), ..bs },
+            DigitalPayoff::CashOrNothing { cash: 1.0 },
+        )
+        .unwrap()
+        .price(OptionType::Call);
+        assert!(skewed > analytic + 0.01);
+
+        // The lower replicating strike must stay positive
+        assert!(digital.smile_adjusted_price(OptionType::Call, &LinearSkew, 0.0).is_err());
+        assert!(digital.smile_adjusted_price(OptionType::Call, &LinearSkew, 2.0 * bs.strike_price).is_err());
+    }
+
+    #[test]
+    fn test_invalid_cash_payout() {
+        assert!(DigitalOption::new(model(), DigitalPayoff::CashOrNothing { cash: 0.0 }).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+//! Hybrid cash and proportional dividends for long-dated equity
+
+use crate::black_scholes::OptionType;
+use crate::error::BlackScholesError;
+use crate::math::Rng;
+use crate::monte_carlo::PathModel;
+use crate::rates::black76;
+use crate::validation;
+
+/// Dividend of `cash + proportional·S(t-)` going ex at `time`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HybridDividend {
+    /// Ex-dividend time in years from today
+    pub time: f64,
+    /// Fixed cash amount per share
+    pub cash: f64,
+    /// Fraction of the pre-dividend price paid
+    pub proportional: f64,
+}
+
+impl HybridDividend {
+    pub fn new(time: f64, cash: f64, proportional: f64) -> Result<Self, BlackScholesError> {
+        Ok(HybridDividend {
+            time: validation::positive("Dividend time", time)?,
+            cash: validation::non_negative("Cash dividend", cash)?,
+            proportional: validation::in_range("Proportional dividend", proportional, 0.0, 1.0, "in [0, 1)")?,
+        })
+    }
+}
+
+/// Affine dividend model (Bühler): near-term cash, long-term proportional
+///
+/// Dividends announced or forecast for the next year or two are firm cash
+/// amounts; far ones scale with the share price. With dividends
+/// α_i + β_i·S(t_i-) the price is S(t) = (F(t) - D(t))·X(t) + D(t), where F
+/// is the forward, D(t) the value at t of the cash dividends still to come
+/// and X a driftless lognormal process started at one. Europeans are
+/// displaced Black-76 on F - D: cash dividends going ex after expiry floor
+/// the share at their value, lowering implied volatility at low strikes
+/// relative to a proportional yield with the same forward.
+///
+/// A positive `dividend_volatility` makes each proportional fraction
+/// lognormal around its expected value, independently of the share and of
+/// the other dividends, applied to the share net of outstanding cash.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HybridDividendModel {
+    pub spot: f64,
+    /// Continuously compounded risk-free rate (annual)
+    pub rate: f64,
+    /// Volatility of the pure process X
+    pub volatility: f64,
+    /// Dividends sorted by ex-date
+    pub dividends: Vec<HybridDividend>,
+    /// Lognormal volatility of each proportional dividend's fraction
+    pub dividend_volatility: f64,
+}
+
+impl HybridDividendModel {
+    pub fn new(
+        spot: f64,
+        rate: f64,
+        volatility: f64,
+        mut dividends: Vec<HybridDividend>,
+    ) -> Result<Self, BlackScholesError> {
+        dividends.sort_by(|a, b| a.time.total_cmp(&b.time));
+        let model = HybridDividendModel {
+            spot: validation::positive("Spot price", spot)?,
+            rate: validation::finite("Risk-free rate", rate)?,
+            volatility: validation::positive("Volatility", volatility)?,
+            dividends,
+            dividend_volatility: 0.0,
+        };
+        if model.cash_pv(0.0) >= model.spot {
+            return Err(BlackScholesError::invalid("Cash dividends are worth more than the share"));
+        }
+        Ok(model)
+    }
+
+    /// Model from expected dividend amounts, cash near term and proportional far out
+    ///
+    /// Amounts going ex by `cash_until` stay cash, those from
+    /// `proportional_from` become the same fraction of the forward just
+    /// before the ex-date, and the weight moves linearly between the two, so
+    /// the forward curve is unchanged by the split.
+    ///
+    /// # Arguments
+    /// * `schedule` - `(ex-date, expected amount)` pairs
+    /// * `cash_until` - Last ex-date fully in cash
+    /// * `proportional_from` - First ex-date fully proportional
+    pub fn blended(
+        spot: f64,
+        rate: f64,
+        volatility: f64,
+        schedule: &[(f64, f64)],
+        cash_until: f64,
+        proportional_from: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let cash_until = validation::non_negative("Cash horizon", cash_until)?;
+        if proportional_from < cash_until {
+            return Err(BlackScholesError::invalid("Proportional dividends must start after the cash horizon"));
+        }
+        let mut sorted = schedule.to_vec();
+        sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut model = HybridDividendModel::new(spot, rate, volatility, Vec::new())?;
+        for (time, amount) in sorted {
+            let cash_weight = if time <= cash_until {
+                1.0
+            } else if time >= proportional_from {
+                0.0
+            } else {
+                (proportional_from - time) / (proportional_from - cash_until)
+            };
+            // Every dividend so far goes ex strictly before this one
+            let forward = model.forward(time);
+            let amount = validation::non_negative("Dividend amount", amount)?;
+            let dividend = HybridDividend::new(time, cash_weight * amount, (1.0 - cash_weight) * amount / forward)?;
+            model.dividends.push(dividend);
+        }
+        HybridDividendModel::new(spot, rate, volatility, model.dividends)
+    }
+
+    pub fn with_dividend_volatility(mut self, dividend_volatility: f64) -> Result<Self, BlackScholesError> {
+        self.dividend_volatility = validation::non_negative("Dividend volatility", dividend_volatility)?;
+        Ok(self)
+    }
+
+    /// Growth of a share over (s, t] before cash dividends: e^(r(t - s))·Π(1 - β)
+    fn growth(&self, s: f64, t: f64) -> f64 {
+        let retained: f64 = self
+            .dividends
+            .iter()
+            .filter(|d| d.time > s && d.time <= t)
+            .map(|d| 1.0 - d.proportional)
+            .product();
+        (self.rate * (t - s)).exp() * retained
+    }
+
+    /// Forward price for delivery at `t`
+    pub fn forward(&self, t: f64) -> f64 {
+        let cash: f64 = self
+            .dividends
+            .iter()
+            .filter(|d| d.time <= t)
+            .map(|d| d.cash * self.growth(d.time, t))
+            .sum();
+        self.spot * self.growth(0.0, t) - cash
+    }
+
+    /// Value at `t` of the cash dividends going ex after `t`
+    pub fn cash_pv(&self, t: f64) -> f64 {
+        self.dividends
+            .iter()
+            .filter(|d| d.time > t)
+            .map(|d| d.cash / self.growth(t, d.time))
+            .sum()
+    }
+
+    /// Log-variance of the proportional dividend noise to `t`, matched on the second moment
+    fn dividend_variance(&self, t: f64) -> f64 {
+        let excess = self.dividend_volatility.powi(2).exp_m1();
+        self.dividends
+            .iter()
+            .filter(|d| d.time <= t && d.proportional > 0.0)
+            .map(|d| (excess * (d.proportional / (1.0 - d.proportional)).powi(2)).ln_1p())
+            .sum()
+    }
+
+    /// European option value: displaced Black-76 on the forward net of later cash dividends
+    pub fn price(&self, strike: f64, expiry: f64, option_type: OptionType) -> Result<f64, BlackScholesError> {
+        let strike = validation::positive("Strike", strike)?;
+        let expiry = validation::positive("Time to expiry", expiry)?;
+        let (forward, cash) = (self.forward(expiry), self.cash_pv(expiry));
+        let discount = (-self.rate * expiry).exp();
+        if strike <= cash {
+            // Always in the money: the share cannot fall below its remaining cash dividends
+            return Ok(match option_type {
+                OptionType::Call => discount * (forward - strike),
+                OptionType::Put => 0.0,
+            });
+        }
+        let variance = self.volatility.powi(2) * expiry + self.dividend_variance(expiry);
+        let value = black76(forward - cash, strike - cash, expiry, (variance / expiry).sqrt(), option_type)?;
+        Ok(discount * value)
+    }
+}
+
+impl PathModel for HybridDividendModel {
+    fn initial_spot(&self) -> f64 {
+        self.spot
+    }
+
+    /// Exact lognormal steps of X with a lognormal draw per proportional dividend
+    fn simulate_path(&self, times: &[f64], rng: &mut Rng, path: &mut [f64]) {
+        let (vol, eta) = (self.volatility, self.dividend_volatility);
+        let (mut pure, mut noise, mut previous) = (1.0, 1.0, 0.0);
+        let mut next = 0;
+        for (&t, s) in times.iter().zip(path.iter_mut()) {
+            let dt = t - previous;
+            pure *= (vol * dt.sqrt() * rng.normal() - 0.5 * vol * vol * dt).exp();
+            while next < self.dividends.len() && self.dividends[next].time <= t {
+                let beta = self.dividends[next].proportional;
+                if eta > 0.0 && beta > 0.0 {
+                    let fraction = beta * (eta * rng.normal() - 0.5 * eta * eta).exp();
+                    noise *= (1.0 - fraction) / (1.0 - beta);
+                }
+                next += 1;
+            }
+            let cash = self.cash_pv(t);
+            *s = (self.forward(t) - cash) * pure * noise + cash;
+            previous = t;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::monte_carlo::MonteCarlo;
+
+    /// Quarterly 1.0 dividends for ten years
+    fn schedule() -> Vec<(f64, f64)> {
+        (1..=40).map(|q| (q as f64 * 0.25 - 0.1, 1.0)).collect()
+    }
+
+    #[test]
+    fn test_blending_keeps_the_forward_and_limits_match_black_scholes() {
+        let cash = HybridDividendModel::blended(100.0, 0.03, 0.2, &schedule(), 20.0, 20.0).unwrap();
+        let hybrid = HybridDividendModel::blended(100.0, 0.03, 0.2, &schedule(), 2.0, 4.0).unwrap();
+        for t in [0.5, 2.0, 3.0, 5.0, 10.0] {
+            assert!((hybrid.forward(t) - cash.forward(t)).abs() < 1e-9, "{t}");
+        }
+        assert!(hybrid.dividends[10].cash > 0.0 && hybrid.dividends[10].proportional > 0.0);
+        assert_eq!(hybrid.dividends[39].cash, 0.0);
+        assert_eq!(hybrid.cash_pv(5.0), 0.0);
+
+        // Purely proportional dividends are Black-Scholes on the forward
+        let proportional = HybridDividendModel::new(
+            100.0,
+            0.03,
+            0.2,
+            vec![HybridDividend::new(0.5, 0.0, 0.02).unwrap(), HybridDividend::new(1.5, 0.0, 0.02).unwrap()],
+        )
+        .unwrap();
+        let q = -(0.98_f64 * 0.98).ln() / 2.0;
+        let bs = BlackScholes::new(100.0, 95.0, 2.0, 0.03, 0.2, q).unwrap();
+        let hybrid_put = proportional.price(95.0, 2.0, OptionType::Put).unwrap();
+        assert!((hybrid_put - bs.price(OptionType::Put)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_later_cash_dividends_floor_the_share() {
+        // Same forwards; at five years the cash model still owes five years of dividends
+        let cash = HybridDividendModel::blended(100.0, 0.03, 0.2, &schedule(), 20.0, 20.0).unwrap();
+        let proportional = HybridDividendModel::blended(100.0, 0.03, 0.2, &schedule(), 0.0, 0.0).unwrap();
+        let expiry = 5.0;
+        let forward = cash.forward(expiry);
+        let implied = |model: &HybridDividendModel, strike: f64| {
+            let price = model.price(strike, expiry, OptionType::Put).unwrap();
+            let q = 0.03 - (forward / 100.0).ln() / expiry;
+            let bs = BlackScholes::new(100.0, strike, expiry, 0.03, 0.2, q).unwrap();
+            bs.implied_volatility(OptionType::Put, price, 100, 1e-10).unwrap()
+        };
+        let (low, high) = (0.6 * forward, 1.2 * forward);
+        let proportional_skew = implied(&proportional, low) - implied(&proportional, high);
+        let cash_skew = implied(&cash, low) - implied(&cash, high);
+        assert!(proportional_skew.abs() < 1e-6);
+        assert!(cash_skew < -0.005, "{cash_skew}");
+    }
+
+    #[test]
+    fn test_paths_match_the_forward_and_european_prices() {
+        let model = HybridDividendModel::blended(100.0, 0.03, 0.2, &schedule(), 2.0, 4.0)
+            .unwrap()
+            .with_dividend_volatility(0.5)
+            .unwrap();
+        let mc = MonteCarlo::new(100_000, 13).unwrap();
+        let forward = mc.price(&model, &[1.0, 6.0], 1.0, |p| p[1]).unwrap();
+        assert!((forward.price - model.forward(6.0)).abs() < 4.0 * forward.std_error);
+        let discount = (-0.03_f64 * 6.0).exp();
+        let put = mc.price(&model, &[6.0], discount, |p| (90.0 - p[0]).max(0.0)).unwrap();
+        let closed = model.price(90.0, 6.0, OptionType::Put).unwrap();
+        assert!((put.price - closed).abs() < 4.0 * put.std_error + 0.002 * closed, "{put:?} vs {closed}");
+        assert!(HybridDividendModel::new(10.0, 0.03, 0.2, vec![HybridDividend::new(1.0, 11.0, 0.0).unwrap()]).is_err());
+        assert!(HybridDividendModel::blended(100.0, 0.03, 0.2, &schedule(), 3.0, 2.0).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt;
+
+/// Error returned by the crate's fallible entry points
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlackScholesError {
+    /// Input was NaN or infinite
+    NotFinite { field: &'static str, value: f64 },
+    /// Input was a subnormal float (rejected under strict validation)
+    Subnormal { field: &'static str, value: f64 },
+    /// Input was finite but outside its allowed domain
+    OutOfRange {
+        field: &'static str,
+        value: f64,
+        requirement: &'static str,
+    },
+    /// Inputs are individually valid but inconsistent or insufficient
+    InvalidInput(String),
+    /// An iterative solver failed to converge
+    NoConvergence(String),
+}
+
+impl BlackScholesError {
+    /// Shorthand for an `InvalidInput` error
+    pub fn invalid(message: &str) -> Self {
+        BlackScholesError::InvalidInput(message.to_string())
+    }
+
+    /// Shorthand for a `NoConvergence` error
+    pub fn no_convergence(message: &str) -> Self {
+        BlackScholesError::NoConvergence(message.to_string())
+    }
+}
+
+impl fmt::Display for BlackScholesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlackScholesError::NotFinite { field, value } => write!(f, "{} must be finite (got {})", field, value),
+            BlackScholesError::Subnormal { field, value } => {
+                write!(f, "{} must not be subnormal (got {:e})", field, value)
+            }
+            BlackScholesError::OutOfRange {
+                field,
+                value,
+                requirement,
+            } => write!(f, "{} must be {} (got {})", field, requirement, value),
+            BlackScholesError::InvalidInput(message) => write!(f, "{}", message),
+            BlackScholesError::NoConvergence(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for BlackScholesError {}
+
+impl From<BlackScholesError> for String {
+    fn from(error: BlackScholesError) -> String {
+        error.to_string()
+    }
+}
@@ -0,0 +1,459 @@
+use crate::black_scholes::BlackScholes;
+use crate::curves::{DiscountCurve, ForwardCurve};
+use crate::error::BlackScholesError;
+use crate::validation;
+use crate::vol_surface::VolSurface;
+
+/// Fixed-point passes mapping a leveraged strike to the index smile
+const STRIKE_MAP_ITERATIONS: usize = 20;
+
+/// One distribution paid by an ETF
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Distribution {
+    /// Declared cash amount per share
+    Cash { time: f64, amount: f64 },
+    /// Projected distribution as a fraction of the NAV on its ex-date
+    Yield { time: f64, fraction: f64 },
+}
+
+impl Distribution {
+    /// Ex-date in years from today
+    pub fn time(&self) -> f64 {
+        match *self {
+            Distribution::Cash { time, .. } | Distribution::Yield { time, .. } => time,
+        }
+    }
+}
+
+/// Exchange-traded fund as an option underlying
+///
+/// Unlike a single stock the fund pays its income out on a fixed calendar
+/// (monthly or quarterly) and charges an expense ratio that accrues daily
+/// against the NAV. Both come off the forward:
+///
+/// F(T) = (S - PV(cash before T))·Π(1 - y_i)·e^(-e·T) / D(T)
+///
+/// Declared cash distributions are escrowed as in `MarketContext`; projected
+/// ones scale with the NAV, so a long-dated forward is not over-stated when
+/// the fund grows. Over a few years the drag is worth several percent of the
+/// forward, which a plain stock with no dividends would miss.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Etf {
+    /// Current price of a share
+    pub spot: f64,
+    /// Annual expense ratio as decimal, deducted continuously from the NAV
+    pub expense_ratio: f64,
+    /// Distributions sorted by ex-date
+    pub distributions: Vec<Distribution>,
+}
+
+impl Etf {
+    /// Create a fund with no distributions scheduled
+    ///
+    /// # Arguments
+    /// * `spot` - Current share price
+    /// * `expense_ratio` - Annual expense ratio as decimal (0.0009 = 9bp)
+    pub fn new(spot: f64, expense_ratio: f64) -> Result<Self, BlackScholesError> {
+        Ok(Etf {
+            spot: validation::positive("Spot price", spot)?,
+            expense_ratio: validation::non_negative("Expense ratio", expense_ratio)?,
+            distributions: Vec::new(),
+        })
+    }
+
+    /// Add distributions, keeping the schedule sorted by ex-date
+    pub fn with_distributions(mut self, distributions: &[Distribution]) -> Result<Self, BlackScholesError> {
+        for d in distributions {
+            validation::positive("Distribution time", d.time())?;
+            match *d {
+                Distribution::Cash { amount, .. } => {
+                    validation::non_negative("Distribution amount", amount)?;
+                }
+                Distribution::Yield { fraction, .. } => {
+                    validation::in_range("Distribution yield", fraction, 0.0, 1.0, "in [0, 1)")?;
+                    if fraction >= 1.0 {
+                        return Err(BlackScholesError::invalid("Distribution yield must be below 100%"));
+                    }
+                }
+            }
+        }
+        self.distributions.extend_from_slice(distributions);
+        self.distributions.sort_by(|a, b| a.time().total_cmp(&b.time()));
+        Ok(self)
+    }
+
+    /// Project a regular payout schedule out to `horizon`
+    ///
+    /// The annual yield is split evenly over `frequency` distributions a year,
+    /// the first going ex at `first` years and the rest every 1/`frequency`.
+    pub fn with_distribution_schedule(
+        self,
+        annual_yield: f64,
+        frequency: u32,
+        first: f64,
+        horizon: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let annual_yield = validation::non_negative("Distribution yield", annual_yield)?;
+        let first = validation::positive("First distribution", first)?;
+        let horizon = validation::finite("Schedule horizon", horizon)?;
+        if frequency == 0 {
+            return Err(BlackScholesError::invalid("Schedule needs at least one distribution a year"));
+        }
+        let fraction = annual_yield / frequency as f64;
+        let schedule: Vec<Distribution> = (0..)
+            .map(|k| first + k as f64 / frequency as f64)
+            .take_while(|&time| time <= horizon)
+            .map(|time| Distribution::Yield { time, fraction })
+            .collect();
+        self.with_distributions(&schedule)
+    }
+
+    /// Present value of declared cash distributions going ex strictly before `expiry`
+    pub fn cash_pv(&self, discount: &dyn DiscountCurve, expiry: f64) -> f64 {
+        self.distributions
+            .iter()
+            .filter_map(|d| match *d {
+                Distribution::Cash { time, amount } if time < expiry => Some(amount * discount.df(time)),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Fraction of the NAV retained by time `t` after projected payouts and expenses
+    pub fn retention(&self, t: f64) -> f64 {
+        let payouts: f64 = self
+            .distributions
+            .iter()
+            .filter_map(|d| match *d {
+                Distribution::Yield { time, fraction } if time < t => Some(1.0 - fraction),
+                _ => None,
+            })
+            .product();
+        payouts * (-self.expense_ratio * t.max(0.0)).exp()
+    }
+
+    /// Forward price for delivery at `t`
+    pub fn forward(&self, discount: &dyn DiscountCurve, t: f64) -> f64 {
+        (self.spot - self.cash_pv(discount, t)) * self.retention(t) / discount.df(t)
+    }
+
+    /// Continuous dividend yield a plain stock would need to have the same forward
+    pub fn implied_dividend_yield(&self, discount: &dyn DiscountCurve, expiry: f64) -> Result<f64, BlackScholesError> {
+        let expiry = validation::positive("Time to expiry", expiry)?;
+        let forward = validation::positive("Forward price", self.forward(discount, expiry))?;
+        Ok(discount.zero_rate(expiry) - (forward / self.spot).ln() / expiry)
+    }
+
+    /// Black-Scholes model of an option on the fund, carrying its distributions and expenses
+    ///
+    /// # Arguments
+    /// * `discount` - Risk-free discount curve
+    /// * `strike` - Strike price
+    /// * `expiry` - Time to expiry in years
+    /// * `volatility` - Volatility of the fund as decimal
+    pub fn model(
+        &self,
+        discount: &dyn DiscountCurve,
+        strike: f64,
+        expiry: f64,
+        volatility: f64,
+    ) -> Result<BlackScholes, BlackScholesError> {
+        let forward = EtfForward { etf: self, discount };
+        BlackScholes::from_curves(strike, expiry, volatility, discount, &forward)
+    }
+}
+
+/// Forward curve of an ETF against a discount curve
+#[derive(Clone, Copy)]
+pub struct EtfForward<'a> {
+    pub etf: &'a Etf,
+    pub discount: &'a dyn DiscountCurve,
+}
+
+impl ForwardCurve for EtfForward<'_> {
+    fn forward(&self, t: f64) -> f64 {
+        self.etf.forward(self.discount, t)
+    }
+
+    fn spot(&self) -> f64 {
+        self.etf.spot
+    }
+}
+
+/// Leveraged or inverse ETF rebalanced daily to `leverage` times an index
+///
+/// Rebalancing daily makes the fund's return each day β times the index's
+/// total return, less financing on the borrowed (β - 1) and the expense
+/// ratio. Risk-neutrally the fund is then lognormal with volatility |β|σ
+/// and yield equal to its expense ratio, and over a horizon
+///
+/// ln(L_T/L_0) = β·ln(S_T/S_0) + (r - e)T - β(r - q)T - ½β(β - 1)σ²T,
+///
+/// where the last term is the variance drag: with the index back where it
+/// started, a 3x fund on a 20% vol index has lost 11% of its value in a year.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeveragedEtf {
+    /// Current price of a fund share
+    pub spot: f64,
+    /// Daily leverage factor β, e.g. 2, 3, -1 or -3
+    pub leverage: f64,
+    /// Annual expense ratio as decimal
+    pub expense_ratio: f64,
+    /// Current level of the reference index
+    pub index_spot: f64,
+    /// Continuous dividend yield of the index
+    pub index_dividend_yield: f64,
+}
+
+impl LeveragedEtf {
+    pub fn new(
+        spot: f64,
+        leverage: f64,
+        expense_ratio: f64,
+        index_spot: f64,
+        index_dividend_yield: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let leverage = validation::finite("Leverage", leverage)?;
+        if leverage == 0.0 {
+            return Err(BlackScholesError::invalid("Leverage must be non-zero"));
+        }
+        Ok(LeveragedEtf {
+            spot: validation::positive("Spot price", spot)?,
+            leverage,
+            expense_ratio: validation::non_negative("Expense ratio", expense_ratio)?,
+            index_spot: validation::positive("Index spot", index_spot)?,
+            index_dividend_yield: validation::finite("Index dividend yield", index_dividend_yield)?,
+        })
+    }
+
+    /// Volatility of the fund, |β|σ
+    pub fn volatility(&self, index_volatility: f64) -> f64 {
+        self.leverage.abs() * index_volatility
+    }
+
+    /// Log-return shortfall against β times the index over `t`, ½β(β - 1)σ²t
+    pub fn variance_drag(&self, index_volatility: f64, t: f64) -> f64 {
+        0.5 * self.leverage * (self.leverage - 1.0) * index_volatility.powi(2) * t
+    }
+
+    /// Fund price at `t` given the index level then, under constant index volatility
+    pub fn value_given_index(&self, index: f64, rate: f64, index_volatility: f64, t: f64) -> f64 {
+        let beta = self.leverage;
+        let carry = (rate - self.expense_ratio) * t - beta * (rate - self.index_dividend_yield) * t;
+        self.spot * (beta * (index / self.index_spot).ln() + carry - self.variance_drag(index_volatility, t)).exp()
+    }
+
+    /// Index level at `expiry` at which the fund ends at `strike`
+    ///
+    /// A call on an inverse fund (β < 0) pays when the index ends below this level.
+    pub fn index_strike(&self, strike: f64, expiry: f64, rate: f64, index_volatility: f64) -> f64 {
+        let beta = self.leverage;
+        let carry = (rate - self.expense_ratio) * expiry - beta * (rate - self.index_dividend_yield) * expiry;
+        let log_index = ((strike / self.spot).ln() - carry + self.variance_drag(index_volatility, expiry)) / beta;
+        self.index_spot * log_index.exp()
+    }
+
+    /// Black-Scholes model of an option on the fund
+    ///
+    /// # Arguments
+    /// * `strike` - Strike price on the fund
+    /// * `expiry` - Time to expiry in years
+    /// * `rate` - Risk-free interest rate as decimal
+    /// * `index_volatility` - Volatility of the reference index as decimal
+    pub fn model(
+        &self,
+        strike: f64,
+        expiry: f64,
+        rate: f64,
+        index_volatility: f64,
+    ) -> Result<BlackScholes, BlackScholesError> {
+        let volatility = validation::positive("Index volatility", index_volatility)? * self.leverage.abs();
+        BlackScholes::new(self.spot, strike, expiry, rate, volatility, self.expense_ratio)
+    }
+
+    /// Implied volatility surface of the fund translated from the index surface
+    pub fn surface<S: VolSurface>(&self, index: S, rate: f64) -> LeveragedSurface<S> {
+        LeveragedSurface {
+            fund: *self,
+            index,
+            rate,
+        }
+    }
+}
+
+/// Fund smile read off the index smile at the matching index strike
+///
+/// To first order in the smile (Leung and Sircar, 2015) the fund's implied
+/// volatility at strike K is |β| times the index's at the index level that
+/// ends the fund at K, the drag in that level taken at the index's own vol
+/// there. The map is solved by fixed-point iteration. Leverage stretches the
+/// smile by β in log-strike, and for β > 1 the drag maps the fund's
+/// at-the-money strike above the index forward; an inverse fund's calls sit
+/// on the index's put wing, so its skew slopes the other way.
+#[derive(Debug, Clone, Copy)]
+pub struct LeveragedSurface<S: VolSurface> {
+    pub fund: LeveragedEtf,
+    /// Implied volatility surface of the reference index
+    pub index: S,
+    /// Risk-free interest rate as decimal
+    pub rate: f64,
+}
+
+impl<S: VolSurface> VolSurface for LeveragedSurface<S> {
+    fn implied_vol(&self, strike: f64, expiry: f64) -> f64 {
+        let atm = self.fund.index_spot * ((self.rate - self.fund.index_dividend_yield) * expiry).exp();
+        let mut vol = self.index.implied_vol(atm, expiry);
+        for _ in 0..STRIKE_MAP_ITERATIONS {
+            let index_strike = self.fund.index_strike(strike, expiry, self.rate, vol);
+            vol = self.index.implied_vol(index_strike, expiry);
+        }
+        self.fund.volatility(vol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::OptionType;
+    use crate::curves::FlatCurve;
+
+    fn fund() -> Etf {
+        // Quarterly payer yielding 2% a year with a 20bp expense ratio
+        Etf::new(400.0, 0.002)
+            .unwrap()
+            .with_distribution_schedule(0.02, 4, 0.1, 5.0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_plain_fund_is_a_non_dividend_stock() {
+        let curve = FlatCurve::new(0.04).unwrap();
+        let etf = Etf::new(100.0, 0.0).unwrap();
+        assert!((etf.forward(&curve, 2.0) - 100.0 * 0.08_f64.exp()).abs() < 1e-12);
+        assert!(etf.implied_dividend_yield(&curve, 2.0).unwrap().abs() < 1e-14);
+        let model = etf.model(&curve, 100.0, 2.0, 0.2).unwrap();
+        let stock = BlackScholes::new(100.0, 100.0, 2.0, 0.04, 0.2, 0.0).unwrap();
+        assert!((model.price(OptionType::Call) - stock.price(OptionType::Call)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_distribution_schedule_and_expense_drag() {
+        let curve = FlatCurve::new(0.04).unwrap();
+        let etf = fund();
+        assert_eq!(etf.distributions.len(), 20);
+        // Two years: eight payouts of 0.5% and two years of expenses
+        let expected = 400.0 * 0.995_f64.powi(8) * (-0.004_f64).exp() * 0.08_f64.exp();
+        assert!((etf.forward(&curve, 2.0) - expected).abs() < 1e-10);
+        // Equivalent continuous yield ≈ distribution yield + expense ratio
+        let q = etf.implied_dividend_yield(&curve, 5.0).unwrap();
+        assert!((q - (0.002 - 4.0 * 0.995_f64.ln())).abs() < 1e-12);
+
+        let declared = etf
+            .clone()
+            .with_distributions(&[Distribution::Cash { time: 0.05, amount: 1.5 }])
+            .unwrap();
+        assert!(matches!(declared.distributions[0], Distribution::Cash { .. }));
+        let drop = etf.forward(&curve, 1.0) - declared.forward(&curve, 1.0);
+        assert!((drop - 1.5 * (-0.04_f64 * 0.05).exp() * etf.retention(1.0) * 0.04_f64.exp()).abs() < 1e-10);
+        assert!(etf.clone().with_distributions(&[Distribution::Yield { time: 1.0, fraction: 1.0 }]).is_err());
+        assert!(Etf::new(100.0, -0.001).is_err());
+    }
+
+    #[test]
+    fn test_long_dated_prices_and_vols_differ_from_plain_stock() {
+        let curve = FlatCurve::new(0.04).unwrap();
+        let etf = fund();
+        let model = etf.model(&curve, 400.0, 3.0, 0.18).unwrap();
+        let call = model.price(OptionType::Call);
+        // Treated as a stock with no dividends, the same premium implies a lower vol
+        let stock = BlackScholes::new(400.0, 400.0, 3.0, 0.04, 0.18, 0.0).unwrap();
+        assert!(stock.price(OptionType::Call) > call * 1.1);
+        let naive_vol = stock.implied_volatility(OptionType::Call, call, 100, 1e-10).unwrap();
+        assert!(naive_vol < 0.18 - 0.02, "{naive_vol}");
+        let etf_vol = model.implied_volatility(OptionType::Call, call, 100, 1e-10).unwrap();
+        assert!((etf_vol - 0.18).abs() < 1e-8);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let etf = fund()
+            .with_distributions(&[Distribution::Cash { time: 0.05, amount: 1.5 }])
+            .unwrap();
+        let restored: Etf = serde_json::from_str(&serde_json::to_string(&etf).unwrap()).unwrap();
+        assert_eq!(restored.distributions, etf.distributions);
+        assert_eq!(restored.spot, etf.spot);
+    }
+
+    /// Index smile falling 0.1 vol points per unit of log-moneyness at 100
+    struct Skew;
+
+    impl VolSurface for Skew {
+        fn implied_vol(&self, strike: f64, _expiry: f64) -> f64 {
+            0.2 - 0.1 * (strike / 100.0).ln()
+        }
+    }
+
+    #[test]
+    fn test_variance_drag_and_terminal_map() {
+        let bull = LeveragedEtf::new(50.0, 3.0, 0.0, 100.0, 0.0).unwrap();
+        assert!((bull.variance_drag(0.2, 1.0) - 0.12).abs() < 1e-15);
+        // Index unchanged with no carry: the fund has lost its drag
+        assert!((bull.value_given_index(100.0, 0.0, 0.2, 1.0) - 50.0 * (-0.12_f64).exp()).abs() < 1e-12);
+        let bear = LeveragedEtf::new(50.0, -1.0, 0.0095, 100.0, 0.015).unwrap();
+        let k = bear.index_strike(55.0, 0.5, 0.04, 0.2);
+        assert!((bear.value_given_index(k, 0.04, 0.2, 0.5) - 55.0).abs() < 1e-12);
+        assert!(k < 100.0);
+        assert!(LeveragedEtf::new(50.0, 0.0, 0.0, 100.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_fund_option_matches_index_expectation() {
+        use crate::math::{gauss_legendre, norm_pdf};
+        let (rate, q, sigma, t) = (0.04, 0.015, 0.22, 1.5);
+        for leverage in [2.0, 3.0, -1.0, -2.0] {
+            let fund = LeveragedEtf::new(40.0, leverage, 0.009, 100.0, q).unwrap();
+            let price = fund.model(42.0, t, rate, sigma).unwrap().price(OptionType::Call);
+            // E[(L_T - K)+] over the index's terminal lognormal, integrated where the call pays
+            let kink = fund.index_strike(42.0, t, rate, sigma);
+            let z_kink = ((kink / 100.0).ln() - (rate - q - 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+            let (lo, hi) = if leverage > 0.0 { (z_kink, 10.0) } else { (-10.0, z_kink) };
+            let (nodes, weights) = gauss_legendre(96);
+            let expected: f64 = nodes
+                .iter()
+                .zip(&weights)
+                .map(|(x, w)| {
+                    let z = 0.5 * (hi - lo) * x + 0.5 * (hi + lo);
+                    let index = 100.0 * ((rate - q - 0.5 * sigma * sigma) * t + sigma * t.sqrt() * z).exp();
+                    0.5 * (hi - lo) * w * norm_pdf(z) * (fund.value_given_index(index, rate, sigma, t) - 42.0).max(0.0)
+                })
+                .sum();
+            let integrated = (-rate * t).exp() * expected;
+            assert!((price - integrated).abs() < 1e-8, "{leverage}: {price} vs {integrated}");
+        }
+    }
+
+    #[test]
+    fn test_surface_translation() {
+        use crate::vol_surface::FlatVol;
+        let bull = LeveragedEtf::new(50.0, 2.0, 0.0, 100.0, 0.0).unwrap();
+        let flat = bull.surface(FlatVol::new(0.18), 0.03);
+        assert!((flat.implied_vol(60.0, 1.0) - 0.36).abs() < 1e-15);
+
+        let skewed = bull.surface(Skew, 0.03);
+        let strikes = [40.0, 50.0 * 0.03_f64.exp(), 60.0];
+        let vols: Vec<f64> = strikes.iter().map(|&k| skewed.implied_vol(k, 1.0)).collect();
+        assert!(vols[0] > vols[1] && vols[1] > vols[2]);
+        // ATM fund vol is 2x the index vol above the index forward, where the drag puts the mapped strike
+        let mapped = bull.index_strike(strikes[1], 1.0, 0.03, vols[1] / 2.0);
+        assert!(mapped > 100.0 * 0.03_f64.exp());
+        assert!((vols[1] - 2.0 * Skew.implied_vol(mapped, 1.0)).abs() < 1e-12);
+
+        // An inverse fund's skew slopes up
+        let bear = LeveragedEtf::new(50.0, -2.0, 0.0, 100.0, 0.0).unwrap().surface(Skew, 0.03);
+        assert!(bear.implied_vol(40.0, 1.0) < bear.implied_vol(60.0, 1.0));
+    }
+}
@@ -0,0 +1,182 @@
+//! Fill model for orders worked inside a bid-ask spread
+
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// What happens to the part of an order that does not fill where it rests
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Remainder {
+    /// Take the rest at the touch straight away
+    Cross,
+    /// Leave the rest working and try again at the next observation
+    Carry,
+}
+
+/// How orders are worked against a quoted spread
+///
+/// An order rests `aggression` of the way from mid to the touch (the bid
+/// for sales, the ask for purchases). Giving up `improvement` = (1 - aggression)
+/// half-spreads of price improvement is paid for in fill probability,
+/// e^(-decay·improvement) with the improvement in the spread's own units, so
+/// wider spreads leave more unfilled at mid. Orders larger than `queue_size`
+/// fill pro rata. Fills are expected quantities, so a backtest stays
+/// deterministic; the unfilled part is crossed or carried per `remainder`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionModel {
+    /// Where orders rest: 0 at mid, 1 at the touch
+    pub aggression: f64,
+    /// Fill probability decay per unit of price improvement requested
+    pub decay: f64,
+    /// Size that fills in full when the price is right; `None` for unlimited
+    pub queue_size: Option<f64>,
+    pub remainder: Remainder,
+}
+
+/// Split of one order into its executed and working parts, all signed like the order
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fill {
+    /// Filled where the order rests, `aggression` of the way to the touch
+    pub resting: f64,
+    /// Filled at the touch
+    pub crossed: f64,
+    /// Left working
+    pub unfilled: f64,
+}
+
+impl Fill {
+    /// Quantity executed at either price
+    pub fn executed(&self) -> f64 {
+        self.resting + self.crossed
+    }
+
+    /// Signed cost against mid when the half-spread is worth `half_spread` per unit at the touch
+    ///
+    /// For spreads quoted in price; a spread in volatility is not linear in
+    /// price, so callers reprice each part at its own offset instead.
+    pub fn cost(&self, model: &ExecutionModel, half_spread: f64) -> f64 {
+        (self.resting.abs() * model.aggression + self.crossed.abs()) * half_spread
+    }
+}
+
+impl Default for ExecutionModel {
+    fn default() -> Self {
+        ExecutionModel::touch()
+    }
+}
+
+impl ExecutionModel {
+    pub fn new(
+        aggression: f64,
+        decay: f64,
+        queue_size: Option<f64>,
+        remainder: Remainder,
+    ) -> Result<Self, BlackScholesError> {
+        Ok(ExecutionModel {
+            aggression: validation::in_range("Aggression", aggression, 0.0, 1.0, "in [0, 1]")?,
+            decay: validation::non_negative("Fill decay", decay)?,
+            queue_size: queue_size.map(|q| validation::positive("Queue size", q)).transpose()?,
+            remainder,
+        })
+    }
+
+    /// Every order fills in full at the touch, paying the whole half-spread
+    pub fn touch() -> Self {
+        ExecutionModel {
+            aggression: 1.0,
+            decay: 0.0,
+            queue_size: None,
+            remainder: Remainder::Cross,
+        }
+    }
+
+    /// Every order fills in full at mid, the optimistic benchmark
+    pub fn mid() -> Self {
+        ExecutionModel {
+            aggression: 0.0,
+            ..ExecutionModel::touch()
+        }
+    }
+
+    /// Probability that an order of `size` fills where it rests
+    ///
+    /// # Arguments
+    /// * `half_spread` - Half the quoted spread, in the units `decay` is quoted in
+    /// * `size` - Order size, either sign
+    pub fn fill_probability(&self, half_spread: f64, size: f64) -> f64 {
+        let improvement = (1.0 - self.aggression) * half_spread;
+        let queue = match self.queue_size {
+            Some(q) if size.abs() > q => q / size.abs(),
+            _ => 1.0,
+        };
+        (-self.decay * improvement).exp() * queue
+    }
+
+    /// Split a signed order of `quantity` into resting, crossed and working parts
+    pub fn fill(&self, quantity: f64, half_spread: f64) -> Fill {
+        let resting = quantity * self.fill_probability(half_spread, quantity);
+        let rest = quantity - resting;
+        match self.remainder {
+            Remainder::Cross => Fill {
+                resting,
+                crossed: rest,
+                unfilled: 0.0,
+            },
+            Remainder::Carry => Fill {
+                resting,
+                crossed: 0.0,
+                unfilled: rest,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_touch_and_mid_fill_in_full() {
+        for model in [ExecutionModel::touch(), ExecutionModel::mid()] {
+            let fill = model.fill(-3.0, 0.5);
+            assert_eq!(fill.resting, -3.0);
+            assert_eq!(fill.unfilled, 0.0);
+        }
+        assert_eq!(ExecutionModel::touch().fill(2.0, 0.5).cost(&ExecutionModel::touch(), 0.5), 1.0);
+        assert_eq!(ExecutionModel::mid().fill(2.0, 0.5).cost(&ExecutionModel::mid(), 0.5), 0.0);
+    }
+
+    #[test]
+    fn test_wider_spreads_and_larger_orders_fill_less() {
+        let model = ExecutionModel::new(0.5, 2.0, Some(10.0), Remainder::Carry).unwrap();
+        let p = model.fill_probability(0.2, 5.0);
+        assert!((p - (-0.2_f64).exp()).abs() < 1e-15);
+        assert!(model.fill_probability(0.4, 5.0) < p);
+        assert!((model.fill_probability(0.2, -20.0) - 0.5 * p).abs() < 1e-15);
+        // Zero spread: resting at mid is resting at the touch
+        assert_eq!(model.fill_probability(0.0, 5.0), 1.0);
+
+        let fill = model.fill(-20.0, 0.2);
+        assert!((fill.resting + 10.0 * p).abs() < 1e-12);
+        assert!((fill.executed() + fill.unfilled + 20.0).abs() < 1e-12);
+        let crossed = ExecutionModel {
+            remainder: Remainder::Cross,
+            ..model
+        }
+        .fill(-20.0, 0.2);
+        assert_eq!(crossed.unfilled, 0.0);
+        assert!((crossed.executed() + 20.0).abs() < 1e-12);
+        // Crossing the remainder costs more than resting it all at the mid-touch midpoint
+        assert!(crossed.cost(&model, 0.2) > 0.5 * 20.0 * 0.2);
+    }
+
+    #[test]
+    fn test_invalid_models() {
+        assert!(ExecutionModel::new(1.5, 1.0, None, Remainder::Cross).is_err());
+        assert!(ExecutionModel::new(0.5, -1.0, None, Remainder::Cross).is_err());
+        assert!(ExecutionModel::new(0.5, 1.0, Some(0.0), Remainder::Carry).is_err());
+        assert_eq!(ExecutionModel::default(), ExecutionModel::touch());
+    }
+}
@@ -0,0 +1,559 @@
+//! Listed option expiry dates per exchange, with holidays and special sessions
+
+use crate::bonds::{days_in_month, Date};
+use crate::error::BlackScholesError;
+use crate::time_scale::{DaySchedule, CALENDAR_DAYS_PER_YEAR};
+use std::collections::BTreeSet;
+
+/// Hours in a day, to turn settlement times into day fractions
+const HOURS_PER_DAY: f64 = 24.0;
+
+/// Remaining life (years) below which a listed expiry counts as expired
+const LISTED_EPSILON: f64 = 1e-9;
+
+/// Local hour of a midday settlement auction
+const MIDDAY_HOUR: f64 = 12.0;
+
+/// Unscheduled full-day closures of the US exchanges (national events and weather)
+const US_SPECIAL_CLOSURES: [(i32, u32, u32); 10] = [
+    (2001, 9, 11),
+    (2001, 9, 12),
+    (2001, 9, 13),
+    (2001, 9, 14),
+    (2004, 6, 11),
+    (2007, 1, 2),
+    (2012, 10, 29),
+    (2012, 10, 30),
+    (2018, 12, 5),
+    (2025, 1, 9),
+];
+
+/// Venue whose holiday calendar and listing rules apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Exchange {
+    /// US equity and index options on the NYSE holiday calendar; AM-settled
+    /// monthlies, PM-settled weeklies on Mondays, Wednesdays and Fridays,
+    /// and quarterlies on the last business day of the quarter
+    Cboe,
+    /// European index options; monthlies settle on the midday auction,
+    /// weeklies list on the other Fridays, quarterlies are the March, June,
+    /// September and December monthlies
+    Eurex,
+}
+
+impl Exchange {
+    /// Local opening, closing and early-closing hours
+    fn session(self) -> (f64, f64, f64) {
+        match self {
+            Exchange::Cboe => (9.5, 16.0, 13.0),
+            Exchange::Eurex => (9.0, 17.5, 14.0),
+        }
+    }
+
+    /// ExpirySettlement of the standard monthly contract
+    fn monthly_settlement(self) -> ExpirySettlement {
+        match self {
+            Exchange::Cboe => ExpirySettlement::Open,
+            Exchange::Eurex => ExpirySettlement::Midday,
+        }
+    }
+
+    /// Weekdays carrying weekly expiries, 0 = Monday
+    fn weekly_days(self) -> &'static [u32] {
+        match self {
+            Exchange::Cboe => &[0, 2, 4],
+            Exchange::Eurex => &[4],
+        }
+    }
+
+    /// Full-day holidays of the regular calendar in `year`
+    fn holidays(self, year: i32) -> Vec<Date> {
+        let date = |month, day| Date { year, month, day };
+        let easter = easter_sunday(year);
+        match self {
+            Exchange::Cboe => {
+                let mut days = vec![
+                    nth_weekday(year, 1, 0, 3),
+                    nth_weekday(year, 2, 0, 3),
+                    easter.add_days(-2),
+                    last_weekday(year, 5, 0),
+                    observed(date(7, 4)),
+                    nth_weekday(year, 9, 0, 1),
+                    nth_weekday(year, 11, 3, 4),
+                    observed(date(12, 25)),
+                ];
+                // New Year's Day on a Saturday is not moved back into December
+                if date(1, 1).weekday() != 5 {
+                    days.push(observed(date(1, 1)));
+                }
+                if year >= 2022 {
+                    days.push(observed(date(6, 19)));
+                }
+                days
+            }
+            Exchange::Eurex => vec![
+                date(1, 1),
+                easter.add_days(-2),
+                easter.add_days(1),
+                date(5, 1),
+                date(12, 24),
+                date(12, 25),
+                date(12, 26),
+                date(12, 31),
+            ],
+        }
+    }
+
+    /// Whether `date` is a scheduled early close
+    fn is_early_close(self, date: Date) -> bool {
+        match self {
+            Exchange::Cboe => {
+                let (month, day, weekday) = (date.month, date.day, date.weekday());
+                // Eves of Independence Day and Christmas falling Monday to Thursday, and Black Friday
+                (month == 7 && day == 3 && weekday < 4)
+                    || (month == 12 && day == 24 && weekday < 4)
+                    || date == nth_weekday(date.year, 11, 3, 4).add_days(1)
+            }
+            Exchange::Eurex => false,
+        }
+    }
+}
+
+/// Listing cycle an expiry belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExpiryCycle {
+    /// Third Friday of each month
+    Monthly,
+    /// Short-dated expiries on the exchange's weekly days
+    Weekly,
+    /// Quarter-end contracts
+    Quarterly,
+    /// Last business day of each month
+    EndOfMonth,
+}
+
+impl ExpiryCycle {
+    /// Precedence when two cycles expire at the same time, lowest first
+    fn rank(self) -> u8 {
+        match self {
+            ExpiryCycle::Quarterly => 0,
+            ExpiryCycle::Monthly => 1,
+            ExpiryCycle::EndOfMonth => 2,
+            ExpiryCycle::Weekly => 3,
+        }
+    }
+}
+
+/// How the final settlement price is struck on the expiry date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExpirySettlement {
+    /// Opening prints of the constituents (AM settlement)
+    Open,
+    /// Midday settlement auction
+    Midday,
+    /// Closing price (PM settlement)
+    Close,
+}
+
+/// One listed expiry
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListedExpiry {
+    pub date: Date,
+    pub cycle: ExpiryCycle,
+    pub settlement: ExpirySettlement,
+    /// Local time of the final settlement in hours, after any early close
+    pub hour: f64,
+    /// Whether the date is a quarterly third-Friday expiry of stock and index options and futures
+    pub triple_witching: bool,
+}
+
+/// Trading calendar and expiry rules of one exchange
+///
+/// Regular holidays and early closes follow the exchange's published rules
+/// (US holidays moved to the nearest weekday when they fall on a weekend).
+/// Unscheduled closures and early closes are added with `with_closure`
+/// and `with_early_close`; the historical US closures are built in. An
+/// expiry falling on a closed day moves to the previous business day, or
+/// to the next for Monday weeklies.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExpiryCalendar {
+    pub exchange: Exchange,
+    closures: BTreeSet<Date>,
+    early_closes: BTreeSet<Date>,
+}
+
+impl ExpiryCalendar {
+    pub fn new(exchange: Exchange) -> Self {
+        let closures = match exchange {
+            Exchange::Cboe => US_SPECIAL_CLOSURES
+                .iter()
+                .map(|&(year, month, day)| Date { year, month, day })
+                .collect(),
+            Exchange::Eurex => BTreeSet::new(),
+        };
+        ExpiryCalendar {
+            exchange,
+            closures,
+            early_closes: BTreeSet::new(),
+        }
+    }
+
+    /// Add an unscheduled full-day closure
+    pub fn with_closure(mut self, date: Date) -> Self {
+        self.closures.insert(date);
+        self
+    }
+
+    /// Add an unscheduled early close
+    pub fn with_early_close(mut self, date: Date) -> Self {
+        self.early_closes.insert(date);
+        self
+    }
+
+    /// Whether the exchange is open on `date`
+    pub fn is_business_day(&self, date: Date) -> bool {
+        date.weekday() < 5 && !self.closures.contains(&date) && !self.exchange.holidays(date.year).contains(&date)
+    }
+
+    /// Whether `date` is a business day with a shortened session
+    pub fn is_early_close(&self, date: Date) -> bool {
+        self.is_business_day(date) && (self.early_closes.contains(&date) || self.exchange.is_early_close(date))
+    }
+
+    /// Last business day on or before `date`
+    pub fn previous_business_day(&self, date: Date) -> Date {
+        let mut day = date;
+        while !self.is_business_day(day) {
+            day = day.add_days(-1);
+        }
+        day
+    }
+
+    /// First business day on or after `date`
+    pub fn next_business_day(&self, date: Date) -> Date {
+        let mut day = date;
+        while !self.is_business_day(day) {
+            day = day.add_days(1);
+        }
+        day
+    }
+
+    /// Business days after `from` up to and including `to`
+    pub fn business_days(&self, from: Date, to: Date) -> Vec<Date> {
+        (from.serial() + 1..=to.serial())
+            .map(Date::from_serial)
+            .filter(|&d| self.is_business_day(d))
+            .collect()
+    }
+
+    /// Standard monthly expiry date in a month: the third Friday, or the business day before it
+    pub fn monthly_expiry(&self, year: i32, month: u32) -> Date {
+        self.previous_business_day(nth_weekday(year, month, 4, 3))
+    }
+
+    /// Expiries of the given cycles after `from` up to and including `to`
+    ///
+    /// Sorted by date and settlement time. Where two cycles expire together
+    /// with the same settlement only one is listed, in the order quarterly,
+    /// monthly, end-of-month, weekly. Weeklies are not listed on the monthly
+    /// date unless the monthly settles at the open.
+    pub fn expiries(&self, from: Date, to: Date, cycles: &[ExpiryCycle]) -> Vec<ListedExpiry> {
+        let first_month = from.year * 12 + from.month as i32 - 1;
+        let last_month = to.year * 12 + to.month as i32 - 1;
+        // Weekly holiday shifts can cross a month end, so look one month either side
+        let months: Vec<(i32, u32)> = (first_month - 1..=last_month + 1)
+            .map(|index| (index.div_euclid(12), index.rem_euclid(12) as u32 + 1))
+            .collect();
+        let monthly_dates: BTreeSet<Date> = months.iter().map(|&(y, m)| self.monthly_expiry(y, m)).collect();
+        let witching: BTreeSet<Date> = months
+            .iter()
+            .filter(|(_, m)| m % 3 == 0)
+            .map(|&(y, m)| self.monthly_expiry(y, m))
+            .collect();
+
+        let mut dated: Vec<(Date, ExpiryCycle, ExpirySettlement)> = Vec::new();
+        for &cycle in cycles {
+            match cycle {
+                ExpiryCycle::Monthly => {
+                    let settlement = self.exchange.monthly_settlement();
+                    dated.extend(monthly_dates.iter().map(|&d| (d, cycle, settlement)));
+                }
+                ExpiryCycle::Quarterly => match self.exchange {
+                    Exchange::Cboe => dated.extend(
+                        months
+                            .iter()
+                            .filter(|(_, m)| m % 3 == 0)
+                            .map(|&(y, m)| (self.month_end(y, m), cycle, ExpirySettlement::Close)),
+                    ),
+                    Exchange::Eurex => dated.extend(witching.iter().map(|&d| (d, cycle, ExpirySettlement::Midday))),
+                },
+                ExpiryCycle::EndOfMonth => {
+                    dated.extend(months.iter().map(|&(y, m)| (self.month_end(y, m), cycle, ExpirySettlement::Close)));
+                }
+                ExpiryCycle::Weekly => {
+                    let (start_year, start_month) = months[0];
+                    let (end_year, end_month) = months[months.len() - 1];
+                    let first = Date {
+                        year: start_year,
+                        month: start_month,
+                        day: 1,
+                    };
+                    let last = Date {
+                        year: end_year,
+                        month: end_month,
+                        day: days_in_month(end_year, end_month),
+                    };
+                    let skip_monthly = self.exchange.monthly_settlement() != ExpirySettlement::Open;
+                    for serial in first.serial()..=last.serial() {
+                        let day = Date::from_serial(serial);
+                        let weekday = day.weekday();
+                        if !self.exchange.weekly_days().contains(&weekday) {
+                            continue;
+                        }
+                        let date = if weekday == 0 {
+                            self.next_business_day(day)
+                        } else {
+                            self.previous_business_day(day)
+                        };
+                        if !(skip_monthly && monthly_dates.contains(&date)) {
+                            dated.push((date, cycle, ExpirySettlement::Close));
+                        }
+                    }
+                }
+            }
+        }
+
+        dated.retain(|&(date, _, _)| date > from && date <= to);
+        dated.sort_by_key(|&(date, cycle, settlement)| (date, settlement, cycle.rank()));
+        dated.dedup_by(|later, earlier| later.0 == earlier.0 && later.2 == earlier.2);
+        dated
+            .into_iter()
+            .map(|(date, cycle, settlement)| ListedExpiry {
+                date,
+                cycle,
+                settlement,
+                hour: self.settlement_hour(date, settlement),
+                triple_witching: witching.contains(&date),
+            })
+            .collect()
+    }
+
+    /// Years from the close on `as_of` to the expiry's settlement, on the calendar clock
+    pub fn year_fraction(&self, as_of: Date, expiry: &ListedExpiry) -> f64 {
+        let (_, close, _) = self.exchange.session();
+        let days = as_of.days_until(expiry.date) as f64 + (expiry.hour - close) / HOURS_PER_DAY;
+        days / CALENDAR_DAYS_PER_YEAR
+    }
+
+    /// Year fractions from `as_of` of the listed expiries up to `to`, for pricing and backtests
+    pub fn expiry_times(&self, as_of: Date, to: Date, cycles: &[ExpiryCycle]) -> Vec<f64> {
+        let mut times: Vec<f64> = self
+            .expiries(as_of, to, cycles)
+            .iter()
+            .map(|e| self.year_fraction(as_of, e))
+            .collect();
+        times.dedup();
+        times
+    }
+
+    /// Variance weights for the days after `from` up to `to`
+    ///
+    /// Full sessions weigh 1, early closes ½, and weekends, holidays and
+    /// closures nothing.
+    pub fn day_schedule(&self, from: Date, to: Date) -> Result<DaySchedule, BlackScholesError> {
+        if to < from {
+            return Err(BlackScholesError::invalid("Schedule must end on or after its start"));
+        }
+        let weights = (from.serial() + 1..=to.serial())
+            .map(Date::from_serial)
+            .map(|d| match (self.is_business_day(d), self.is_early_close(d)) {
+                (true, true) => 0.5,
+                (true, false) => 1.0,
+                _ => 0.0,
+            })
+            .collect();
+        DaySchedule::from_weights(weights)
+    }
+
+    /// Last business day of a month
+    fn month_end(&self, year: i32, month: u32) -> Date {
+        self.previous_business_day(Date {
+            year,
+            month,
+            day: days_in_month(year, month),
+        })
+    }
+
+    /// Local hour of a settlement on `date`
+    fn settlement_hour(&self, date: Date, settlement: ExpirySettlement) -> f64 {
+        let (open, close, early) = self.exchange.session();
+        match settlement {
+            ExpirySettlement::Open => open,
+            ExpirySettlement::Midday => MIDDAY_HOUR,
+            ExpirySettlement::Close if self.is_early_close(date) => early,
+            ExpirySettlement::Close => close,
+        }
+    }
+}
+
+/// Listed expiry nearest `time + tenor` still ahead of `time`; exactly `time + tenor` when none are listed
+pub(crate) fn nearest_listed(expiries: &[f64], time: f64, tenor: f64) -> Result<f64, BlackScholesError> {
+    if expiries.is_empty() {
+        return Ok(time + tenor);
+    }
+    let target = time + tenor;
+    expiries
+        .iter()
+        .copied()
+        .filter(|&e| e - time > LISTED_EPSILON)
+        .min_by(|a, b| (a - target).abs().total_cmp(&(b - target).abs()))
+        .ok_or_else(|| BlackScholesError::invalid("No listed expiry after the observation"))
+}
+
+/// The `n`-th given weekday (0 = Monday) of a month
+fn nth_weekday(year: i32, month: u32, weekday: u32, n: u32) -> Date {
+    let first = Date { year, month, day: 1 };
+    let offset = (weekday + 7 - first.weekday()) % 7;
+    Date {
+        year,
+        month,
+        day: 1 + offset + 7 * (n - 1),
+    }
+}
+
+/// The last given weekday (0 = Monday) of a month
+fn last_weekday(year: i32, month: u32, weekday: u32) -> Date {
+    let last = Date {
+        year,
+        month,
+        day: days_in_month(year, month),
+    };
+    let offset = (last.weekday() + 7 - weekday) % 7;
+    last.add_days(-(offset as i64))
+}
+
+/// US weekend observance: Saturday holidays move to Friday, Sunday ones to Monday
+fn observed(date: Date) -> Date {
+    match date.weekday() {
+        5 => date.add_days(-1),
+        6 => date.add_days(1),
+        _ => date,
+    }
+}
+
+/// Gregorian Easter Sunday (anonymous Gregorian algorithm)
+fn easter_sunday(year: i32) -> Date {
+    let a = year % 19;
+    let (b, c) = (year / 100, year % 100);
+    let (d, e) = (b / 4, b % 4);
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    Date {
+        year,
+        month: month as u32,
+        day: day as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_us_holidays_and_special_sessions() {
+        let cboe = ExpiryCalendar::new(Exchange::Cboe);
+        assert_eq!(easter_sunday(2024), date(2024, 3, 31));
+        assert_eq!(easter_sunday(2025), date(2025, 4, 20));
+        for holiday in [
+            date(2024, 1, 15),
+            date(2024, 3, 29),
+            date(2024, 6, 19),
+            date(2024, 11, 28),
+            date(2022, 12, 26),
+            date(2021, 12, 24),
+            date(2012, 10, 29),
+            date(2025, 1, 9),
+        ] {
+            assert!(!cboe.is_business_day(holiday), "{holiday:?}");
+        }
+        // New Year's Day 2022 fell on a Saturday and was not observed on the Friday before
+        assert!(cboe.is_business_day(date(2021, 12, 31)));
+        assert!(cboe.is_early_close(date(2024, 11, 29)));
+        assert!(cboe.is_early_close(date(2024, 7, 3)));
+        assert!(!cboe.is_early_close(date(2024, 7, 5)));
+
+        // Thanksgiving week: three full sessions, the holiday and a half day
+        let week = cboe.day_schedule(date(2024, 11, 24), date(2024, 11, 30)).unwrap();
+        assert_eq!(week.weighted_days(), 3.5);
+        let closed = cboe.clone().with_closure(date(2024, 11, 27));
+        assert!(!closed.is_business_day(date(2024, 11, 27)));
+    }
+
+    #[test]
+    fn test_cboe_expiries() {
+        let cboe = ExpiryCalendar::new(Exchange::Cboe);
+        let all = [ExpiryCycle::Monthly, ExpiryCycle::Weekly, ExpiryCycle::Quarterly, ExpiryCycle::EndOfMonth];
+        let expiries = cboe.expiries(date(2024, 3, 1), date(2024, 3, 31), &all);
+        let monthly: Vec<&ListedExpiry> = expiries.iter().filter(|e| e.cycle == ExpiryCycle::Monthly).collect();
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly[0].date, date(2024, 3, 15));
+        assert_eq!(monthly[0].settlement, ExpirySettlement::Open);
+        assert!(monthly[0].triple_witching);
+        // PM weekly alongside the AM monthly on the third Friday
+        assert!(expiries.iter().any(|e| e.date == date(2024, 3, 15) && e.cycle == ExpiryCycle::Weekly));
+        // Good Friday moves the weekly to Thursday, which is also quarter end
+        let quarter_end: Vec<&ListedExpiry> = expiries.iter().filter(|e| e.date == date(2024, 3, 28)).collect();
+        assert_eq!(quarter_end.len(), 1);
+        assert_eq!(quarter_end[0].cycle, ExpiryCycle::Quarterly);
+        assert!(expiries.iter().all(|e| e.date != date(2024, 3, 29)));
+        assert!(expiries.windows(2).all(|w| (w[0].date, w[0].settlement) < (w[1].date, w[1].settlement)));
+
+        // A monthly on Good Friday moves to the Thursday before
+        assert_eq!(cboe.monthly_expiry(2022, 4), date(2022, 4, 14));
+        // Half-day weekly settles at the early close
+        let black_friday = cboe.expiries(date(2024, 11, 28), date(2024, 11, 29), &[ExpiryCycle::Weekly]);
+        assert_eq!(black_friday[0].hour, 13.0);
+    }
+
+    #[test]
+    fn test_eurex_expiries_and_times() {
+        let eurex = ExpiryCalendar::new(Exchange::Eurex);
+        assert!(!eurex.is_business_day(date(2024, 4, 1)));
+        assert!(eurex.is_business_day(date(2024, 5, 27)));
+        let cycles = [ExpiryCycle::Monthly, ExpiryCycle::Weekly, ExpiryCycle::Quarterly];
+        let june = eurex.expiries(date(2024, 5, 31), date(2024, 6, 30), &cycles);
+        // Fridays of June 2024, with the third one the quarterly settling at midday
+        assert_eq!(june.len(), 4);
+        assert_eq!(june[2].date, date(2024, 6, 21));
+        assert_eq!(june[2].cycle, ExpiryCycle::Quarterly);
+        assert_eq!(june[2].settlement, ExpirySettlement::Midday);
+
+        let as_of = date(2024, 6, 14);
+        let times = eurex.expiry_times(as_of, date(2024, 6, 30), &cycles);
+        let midday = (7.0 - 5.5 / 24.0) / 365.0;
+        assert!((times[0] - midday).abs() < 1e-12);
+        assert_eq!(times.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        let cboe = ExpiryCalendar::new(Exchange::Cboe);
+        assert!(cboe.day_schedule(date(2024, 6, 3), date(2024, 6, 1)).is_err());
+        assert!(cboe.expiries(date(2024, 6, 30), date(2024, 6, 1), &[ExpiryCycle::Monthly]).is_empty());
+    }
+}
@@ -0,0 +1,400 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::moneyness::{self, DeltaConvention};
+use crate::time_scale::TimeScale;
+use crate::validation;
+
+/// Which strike counts as at-the-money
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AtmConvention {
+    /// Strike at the current spot rate
+    Spot,
+    /// Strike at the outright forward
+    Forward,
+    /// Delta-neutral straddle: call and put deltas cancel under the delta convention
+    DeltaNeutral,
+}
+
+/// FX option Greeks
+///
+/// The price is in domestic currency per unit of foreign notional. Vega and
+/// both rhos are per 1% move, theta per calendar day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FxGreeks {
+    /// Delta under the model's quoting convention
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    /// Sensitivity to the domestic (quote currency) rate
+    pub domestic_rho: f64,
+    /// Sensitivity to the foreign (base currency) rate, sometimes called phi
+    pub foreign_rho: f64,
+}
+
+/// Garman-Kohlhagen model for a European option on a currency pair
+///
+/// The pair is quoted as domestic units per foreign unit (EURUSD: USD per
+/// EUR). The foreign rate plays the role of a continuous dividend yield.
+/// The price matches Black-Scholes with that substitution, but FX desks quote
+/// delta under several conventions, so delta and the at-the-money strike
+/// follow `delta_convention`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GarmanKohlhagen {
+    /// Spot rate, domestic per foreign
+    pub spot: f64,
+    pub strike: f64,
+    /// Time to expiry in years
+    pub expiry: f64,
+    /// Continuously compounded domestic interest rate
+    pub domestic_rate: f64,
+    /// Continuously compounded foreign interest rate
+    pub foreign_rate: f64,
+    pub volatility: f64,
+    pub delta_convention: DeltaConvention,
+}
+
+impl GarmanKohlhagen {
+    /// Create a model quoting spot delta
+    ///
+    /// # Arguments
+    /// * `spot` - Spot rate, domestic per foreign
+    /// * `strike` - Strike rate, domestic per foreign
+    /// * `expiry` - Time to expiry in years
+    /// * `domestic_rate` - Domestic interest rate as decimal
+    /// * `foreign_rate` - Foreign interest rate as decimal
+    /// * `volatility` - Volatility of the exchange rate as decimal
+    pub fn new(
+        spot: f64,
+        strike: f64,
+        expiry: f64,
+        domestic_rate: f64,
+        foreign_rate: f64,
+        volatility: f64,
+    ) -> Result<Self, BlackScholesError> {
+        Ok(GarmanKohlhagen {
+            spot: validation::positive("Spot rate", spot)?,
+            strike: validation::positive("Strike price", strike)?,
+            expiry: validation::positive("Time to expiry", expiry)?,
+            domestic_rate: validation::finite("Domestic rate", domestic_rate)?,
+            foreign_rate: validation::finite("Foreign rate", foreign_rate)?,
+            volatility: validation::positive("Volatility", volatility)?,
+            delta_convention: DeltaConvention::Spot,
+        })
+    }
+
+    /// Quote delta and solve strikes under another convention
+    pub fn with_delta_convention(mut self, delta_convention: DeltaConvention) -> Self {
+        self.delta_convention = delta_convention;
+        self
+    }
+
+    /// The same option struck at `strike`
+    pub fn with_strike(&self, strike: f64) -> Result<Self, BlackScholesError> {
+        Ok(GarmanKohlhagen {
+            strike: validation::positive("Strike price", strike)?,
+            ..*self
+        })
+    }
+
+    /// Equivalent Black-Scholes model, with the foreign rate as dividend yield
+    pub fn black_scholes(&self) -> BlackScholes {
+        BlackScholes {
+            spot_price: self.spot,
+            strike_price: self.strike,
+            time_to_expiry: self.expiry,
+            risk_free_rate: self.domestic_rate,
+            volatility: self.volatility,
+            dividend_yield: self.foreign_rate,
+            vol_time: self.expiry,
+            time_scale: TimeScale::Calendar,
+        }
+    }
+
+    /// Outright forward rate, S·e^((r_d - r_f)T)
+    pub fn forward(&self) -> f64 {
+        self.spot * ((self.domestic_rate - self.foreign_rate) * self.expiry).exp()
+    }
+
+    /// Premium in domestic currency per unit of foreign notional (domestic pips)
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        self.black_scholes().price(option_type)
+    }
+
+    /// Premium in foreign currency per unit of foreign notional (foreign %)
+    pub fn foreign_premium(&self, option_type: OptionType) -> f64 {
+        self.price(option_type) / self.spot
+    }
+
+    /// Delta under the model's quoting convention
+    pub fn delta(&self, option_type: OptionType) -> f64 {
+        moneyness::delta_with_convention(&self.black_scholes(), option_type, self.delta_convention)
+    }
+
+    /// Price sensitivities with delta in the quoting convention and both rates' rhos
+    pub fn greeks(&self, option_type: OptionType) -> FxGreeks {
+        let model = self.black_scholes();
+        let greeks = model.greeks(option_type);
+        // ∂V/∂r_f = -T·S·(spot delta)
+        let foreign_rho = -self.expiry * self.spot * greeks.delta / 100.0;
+        FxGreeks {
+            delta: self.delta(option_type),
+            gamma: greeks.gamma,
+            vega: greeks.vega,
+            theta: greeks.theta,
+            domestic_rho: greeks.rho,
+            foreign_rho,
+        }
+    }
+
+    /// Strike with the given delta under the model's quoting convention
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    /// * `delta` - Target delta, negative for puts (0.25 = 25-delta call)
+    pub fn strike_from_delta(&self, option_type: OptionType, delta: f64) -> Result<f64, BlackScholesError> {
+        moneyness::strike_from_delta(&self.black_scholes(), option_type, delta, self.delta_convention)
+    }
+
+    /// At-the-money strike
+    ///
+    /// The delta-neutral straddle strike is F·e^(σ²T/2) for plain deltas and
+    /// F·e^(-σ²T/2) for premium-adjusted ones.
+    pub fn atm_strike(&self, convention: AtmConvention) -> f64 {
+        let half_variance = 0.5 * self.volatility * self.volatility * self.expiry;
+        match convention {
+            AtmConvention::Spot => self.spot,
+            AtmConvention::Forward => self.forward(),
+            AtmConvention::DeltaNeutral => match self.delta_convention {
+                DeltaConvention::Spot | DeltaConvention::Forward => self.forward() * half_variance.exp(),
+                DeltaConvention::SpotPremiumAdjusted | DeltaConvention::ForwardPremiumAdjusted => {
+                    self.forward() * (-half_variance).exp()
+                }
+            },
+        }
+    }
+
+    /// Volatility that reproduces a domestic premium
+    pub fn implied_volatility(&self, option_type: OptionType, premium: f64) -> Result<f64, BlackScholesError> {
+        self.black_scholes().implied_volatility(option_type, premium, 100, 1e-10)
+    }
+}
+
+/// Forward of a foreign asset as seen by a quanto payoff, F·e^(-ρ·σ_S·σ_X·T)
+///
+/// Paying the foreign asset's price as a number of domestic units removes
+/// the link between its value and the exchange rate, so its forward under
+/// the domestic measure shifts by the covariance of the two log-returns.
+///
+/// # Arguments
+/// * `forward` - Forward of the asset in its own currency
+/// * `asset_vol` - Volatility of the asset (σ_S)
+/// * `fx_vol` - Volatility of the exchange rate, domestic per foreign (σ_X)
+/// * `correlation` - Correlation of the asset with the exchange rate (ρ)
+/// * `expiry` - Time to expiry in years
+pub fn quanto_forward(forward: f64, asset_vol: f64, fx_vol: f64, correlation: f64, expiry: f64) -> f64 {
+    forward * (-correlation * asset_vol * fx_vol * expiry).exp()
+}
+
+/// European option on a foreign asset with the payoff paid in domestic currency
+///
+/// A call pays `fixed_rate`·max(S_T - K, 0) domestic units, with S and K in
+/// the asset's currency: the exchange rate is fixed at inception, so the
+/// holder bears no currency risk. The asset is lognormal in its own market;
+/// under the domestic measure its drift picks up -ρ·σ_S·σ_X.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantoOption {
+    /// The option in the asset's own market, discounted at the foreign rate
+    pub underlying: BlackScholes,
+    /// Continuously compounded domestic interest rate
+    pub domestic_rate: f64,
+    /// Volatility of the exchange rate, domestic per foreign
+    pub fx_volatility: f64,
+    /// Correlation of the asset with the exchange rate
+    pub correlation: f64,
+    /// Domestic units paid per unit of payoff in the asset's currency
+    pub fixed_rate: f64,
+}
+
+impl QuantoOption {
+    /// Quanto of `underlying` paying one domestic unit per foreign unit
+    ///
+    /// # Arguments
+    /// * `underlying` - Option on the asset with the foreign rate as its risk-free rate
+    /// * `domestic_rate` - Domestic interest rate as decimal
+    /// * `fx_volatility` - Volatility of the exchange rate (domestic per foreign)
+    /// * `correlation` - Correlation between the asset and the exchange rate
+    pub fn new(
+        underlying: BlackScholes,
+        domestic_rate: f64,
+        fx_volatility: f64,
+        correlation: f64,
+    ) -> Result<Self, BlackScholesError> {
+        Ok(QuantoOption {
+            underlying,
+            domestic_rate: validation::finite("Domestic rate", domestic_rate)?,
+            fx_volatility: validation::non_negative("FX volatility", fx_volatility)?,
+            correlation: validation::in_range("Correlation", correlation, -1.0, 1.0, "between -1 and 1")?,
+            fixed_rate: 1.0,
+        })
+    }
+
+    /// Convert the payoff at `fixed_rate` domestic units per foreign unit
+    pub fn with_fixed_rate(mut self, fixed_rate: f64) -> Result<Self, BlackScholesError> {
+        self.fixed_rate = validation::positive("Fixed rate", fixed_rate)?;
+        Ok(self)
+    }
+
+    /// Quanto-adjusted forward of the asset, in its own currency
+    pub fn forward(&self) -> f64 {
+        let bs = &self.underlying;
+        let forward = bs.spot_price * ((bs.risk_free_rate - bs.dividend_yield) * bs.time_to_expiry).exp();
+        quanto_forward(forward, bs.volatility, self.fx_volatility, self.correlation, bs.time_to_expiry)
+    }
+
+    /// Equivalent domestic Black-Scholes model
+    ///
+    /// Discounts at the domestic rate, with the dividend yield set so the
+    /// forward is the quanto forward: q' = q + r_d - r_f + ρ·σ_S·σ_X. Greeks
+    /// of this model are the quanto's per unit of `fixed_rate`.
+    pub fn black_scholes(&self) -> BlackScholes {
+        let bs = &self.underlying;
+        let adjustment = self.correlation * bs.volatility * self.fx_volatility;
+        BlackScholes {
+            risk_free_rate: self.domestic_rate,
+            dividend_yield: bs.dividend_yield + self.domestic_rate - bs.risk_free_rate + adjustment,
+            ..*bs
+        }
+    }
+
+    /// Premium in domestic currency
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        self.fixed_rate * self.black_scholes().price(option_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eurusd() -> GarmanKohlhagen {
+        GarmanKohlhagen::new(1.10, 1.12, 0.5, 0.045, 0.03, 0.08).unwrap()
+    }
+
+    #[test]
+    fn test_reference_price_and_parity() {
+        // Haug, The Complete Guide to Option Pricing Formulas: 0.0291
+        let gk = GarmanKohlhagen::new(1.56, 1.60, 0.5, 0.06, 0.08, 0.12).unwrap();
+        assert!((gk.price(OptionType::Call) - 0.0291).abs() < 5e-5);
+
+        let gk = eurusd();
+        let parity = gk.spot * (-gk.foreign_rate * gk.expiry).exp() - gk.strike * (-gk.domestic_rate * gk.expiry).exp();
+        assert!((gk.price(OptionType::Call) - gk.price(OptionType::Put) - parity).abs() < 1e-14);
+        assert!((gk.foreign_premium(OptionType::Call) * gk.spot - gk.price(OptionType::Call)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_rhos_match_finite_differences() {
+        let gk = eurusd();
+        let h = 1e-6;
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let greeks = gk.greeks(option_type);
+            let bump = |domestic: f64, foreign: f64| {
+                GarmanKohlhagen {
+                    domestic_rate: gk.domestic_rate + domestic,
+                    foreign_rate: gk.foreign_rate + foreign,
+                    ..gk
+                }
+                .price(option_type)
+            };
+            let domestic = (bump(h, 0.0) - bump(-h, 0.0)) / (2.0 * h) / 100.0;
+            let foreign = (bump(0.0, h) - bump(0.0, -h)) / (2.0 * h) / 100.0;
+            assert!((greeks.domestic_rho - domestic).abs() < 1e-8);
+            assert!((greeks.foreign_rho - foreign).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_delta_neutral_straddle() {
+        for convention in [
+            DeltaConvention::Spot,
+            DeltaConvention::Forward,
+            DeltaConvention::SpotPremiumAdjusted,
+            DeltaConvention::ForwardPremiumAdjusted,
+        ] {
+            let gk = eurusd().with_delta_convention(convention);
+            let atm = gk.with_strike(gk.atm_strike(AtmConvention::DeltaNeutral)).unwrap();
+            let net = atm.delta(OptionType::Call) + atm.delta(OptionType::Put);
+            assert!(net.abs() < 1e-12, "{convention:?}: {net}");
+        }
+        let gk = eurusd();
+        assert_eq!(gk.atm_strike(AtmConvention::Spot), gk.spot);
+        assert!(gk.atm_strike(AtmConvention::Forward) > gk.spot);
+    }
+
+    #[test]
+    fn test_premium_adjusted_delta_strikes() {
+        let gk = eurusd().with_delta_convention(DeltaConvention::SpotPremiumAdjusted);
+        let strike = gk.strike_from_delta(OptionType::Call, 0.25).unwrap();
+        let at_strike = gk.with_strike(strike).unwrap();
+        assert!((at_strike.delta(OptionType::Call) - 0.25).abs() < 1e-10);
+        // Premium-adjusted delta is spot delta less the foreign premium
+        let spot_delta = at_strike.black_scholes().greeks(OptionType::Call).delta;
+        assert!((at_strike.delta(OptionType::Call) - (spot_delta - at_strike.foreign_premium(OptionType::Call))).abs() < 1e-12);
+
+        let premium = at_strike.price(OptionType::Call);
+        assert!((at_strike.implied_volatility(OptionType::Call, premium).unwrap() - 0.08).abs() < 1e-8);
+        assert!(GarmanKohlhagen::new(1.1, 1.1, 0.5, 0.04, f64::NAN, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_quanto_matches_foreign_measure_simulation() {
+        use crate::monte_carlo::MonteCarlo;
+        use crate::multi_asset::MultiAssetMc;
+
+        // A foreign index paid at 0.01 domestic units per point
+        let (r_d, r_f, q, vol, fx_vol, rho, t) = (0.045, 0.005, 0.02, 0.22, 0.1, -0.3, 2.0);
+        let underlying = BlackScholes::new(100.0, 95.0, t, r_f, vol, q).unwrap();
+        let quanto = QuantoOption::new(underlying, r_d, fx_vol, rho).unwrap().with_fixed_rate(0.01).unwrap();
+        let forward = 100.0 * ((r_f - q - rho * vol * fx_vol) * t).exp();
+        assert!((quanto.forward() - forward).abs() < 1e-12);
+
+        // Under the foreign measure the asset drifts at r_f - q and Y = 1/X at r_f - r_d,
+        // with correlation -ρ; a domestic unit paid at T is worth Y_T/Y_0 foreign units today.
+        let mc = MonteCarlo::new(200_000, 3).unwrap();
+        let correlation = vec![vec![1.0, -rho], vec![-rho, 1.0]];
+        let engine = MultiAssetMc::gbm(r_f, &[100.0, 1.0], &[q, r_d], &[vol, fx_vol], correlation, mc).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let simulated = engine
+                .price(&[t], (-r_f * t).exp(), |paths| {
+                    let payoff = match option_type {
+                        OptionType::Call => (paths[0][0] - 95.0).max(0.0),
+                        OptionType::Put => (95.0 - paths[0][0]).max(0.0),
+                    };
+                    0.01 * payoff * paths[1][0]
+                })
+                .unwrap();
+            let price = quanto.price(option_type);
+            assert!((price - simulated.price).abs() < 4.0 * simulated.std_error, "{price} vs {simulated:?}");
+        }
+    }
+
+    #[test]
+    fn test_uncorrelated_quanto_is_plain_option_at_foreign_forward() {
+        let underlying = BlackScholes::new(100.0, 100.0, 1.0, 0.02, 0.25, 0.01).unwrap();
+        let quanto = QuantoOption::new(underlying, 0.05, 0.12, 0.0).unwrap();
+        let growth = ((0.05 - 0.02) * 1.0_f64).exp();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            // Same forward, discounted domestically instead of at the foreign rate
+            assert!((quanto.price(option_type) - underlying.price(option_type) / growth).abs() < 1e-12);
+        }
+        let positive = QuantoOption::new(underlying, 0.05, 0.12, 0.5).unwrap();
+        assert!(positive.price(OptionType::Call) < quanto.price(OptionType::Call));
+        assert!(QuantoOption::new(underlying, 0.05, 0.12, 1.5).is_err());
+        assert!(quanto.with_fixed_rate(0.0).is_err());
+    }
+}
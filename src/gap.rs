@@ -0,0 +1,128 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::digital::{DigitalOption, DigitalPayoff};
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// European gap option: the trigger deciding payment differs from the strike paid against
+///
+/// A call pays S - K when S > K₂ and a put pays K - S when S < K₂, where K
+/// is the model's strike and K₂ the trigger. The payoff can be negative
+/// when the trigger is on the wrong side of the strike. It is an
+/// asset-or-nothing digital less K cash-or-nothing digitals, both struck at
+/// the trigger, so price and Greeks follow from theirs.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GapOption {
+    /// Underlying market parameters and the payment strike
+    pub model: BlackScholes,
+    /// Level the underlying must cross for the option to pay
+    pub trigger: f64,
+}
+
+impl GapOption {
+    /// Create a new gap option
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes parameters with the payment strike (K)
+    /// * `trigger` - Trigger level (K₂)
+    pub fn new(model: BlackScholes, trigger: f64) -> Result<Self, BlackScholesError> {
+        let trigger = validation::positive("Trigger", trigger)?;
+        Ok(GapOption { model, trigger })
+    }
+
+    /// The asset-or-nothing and unit cash-or-nothing digitals struck at the trigger
+    fn digitals(&self) -> (DigitalOption, DigitalOption) {
+        let model = BlackScholes {
+            strike_price: self.trigger,
+            ..self.model
+        };
+        (
+            DigitalOption {
+                model,
+                payoff: DigitalPayoff::AssetOrNothing,
+            },
+            DigitalOption {
+                model,
+                payoff: DigitalPayoff::CashOrNothing { cash: 1.0 },
+            },
+        )
+    }
+
+    /// Sign of the asset leg: long for calls, short for puts
+    fn sign(option_type: OptionType) -> f64 {
+        match option_type {
+            OptionType::Call => 1.0,
+            OptionType::Put => -1.0,
+        }
+    }
+
+    /// Calculate option price
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        let (asset, cash) = self.digitals();
+        let strike = self.model.strike_price;
+        GapOption::sign(option_type) * (asset.price(option_type) - strike * cash.price(option_type))
+    }
+
+    /// Calculate all Greeks analytically, with the conventions of `BlackScholes::greeks`
+    pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        let (asset, cash) = self.digitals();
+        let (a, c) = (asset.greeks(option_type), cash.greeks(option_type));
+        let (sign, strike) = (GapOption::sign(option_type), self.model.strike_price);
+        let combine = |a: f64, c: f64| sign * (a - strike * c);
+        Greeks {
+            delta: combine(a.delta, c.delta),
+            gamma: combine(a.gamma, c.gamma),
+            vega: combine(a.vega, c.vega),
+            theta: combine(a.theta, c.theta),
+            rho: combine(a.rho, c.rho),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_value() {
+        // Haug, The Complete Guide to Option Pricing Formulas: pays S - 57 above 50, worth -0.0053
+        let model = BlackScholes::new(50.0, 57.0, 0.5, 0.09, 0.2, 0.0).unwrap();
+        let price = GapOption::new(model, 50.0).unwrap().price(OptionType::Call);
+        assert!((price + 0.0053).abs() < 5e-5, "{price}");
+    }
+
+    #[test]
+    fn test_trigger_at_strike_is_vanilla() {
+        let model = BlackScholes::new(100.0, 95.0, 0.75, 0.04, 0.3, 0.01).unwrap();
+        let gap = GapOption::new(model, 95.0).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            assert!((gap.price(option_type) - model.price(option_type)).abs() < 1e-10);
+            let (g, v) = (gap.greeks(option_type), model.greeks(option_type));
+            assert!((g.delta - v.delta).abs() < 1e-10 && (g.gamma - v.gamma).abs() < 1e-10);
+            assert!((g.vega - v.vega).abs() < 1e-10 && (g.rho - v.rho).abs() < 1e-10);
+            assert!((g.theta - v.theta).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_greeks_match_finite_differences() {
+        let model = BlackScholes::new(100.0, 100.0, 0.5, 0.03, 0.25, 0.02).unwrap();
+        for (trigger, option_type) in [(110.0, OptionType::Call), (90.0, OptionType::Put)] {
+            let analytic = GapOption::new(model, trigger).unwrap().greeks(option_type);
+            let numeric =
+                Greeks::from_finite_differences(&model, |m| GapOption { model: *m, trigger }.price(option_type));
+            assert!((analytic.delta - numeric.delta).abs() < 1e-5, "{analytic:?} vs {numeric:?}");
+            assert!((analytic.gamma - numeric.gamma).abs() < 1e-5);
+            assert!((analytic.vega - numeric.vega).abs() < 1e-6);
+            assert!((analytic.theta - numeric.theta).abs() < 1e-6);
+            assert!((analytic.rho - numeric.rho).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_invalid_trigger() {
+        let model = BlackScholes::new(100.0, 100.0, 0.5, 0.03, 0.25, 0.0).unwrap();
+        assert!(GapOption::new(model, 0.0).is_err());
+        assert!(GapOption::new(model, f64::NAN).is_err());
+    }
+}
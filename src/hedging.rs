@@ -0,0 +1,351 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::math::Rng;
+use crate::monte_carlo::PathModel;
+use crate::strategy::MarketObservation;
+use crate::validation;
+
+/// Remaining life below which the option is settled at intrinsic value
+const SETTLE_EPSILON: f64 = 1e-9;
+
+/// When the hedge is brought back to the model delta
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HedgeRule {
+    /// Every `every`-th observation (1 = every observation)
+    Periodic { every: usize },
+    /// Whenever the hedge is more than `band` shares per option away from the target
+    DeltaBand { band: f64 },
+}
+
+/// Cost of trading the underlying
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionCosts {
+    /// Fraction of the traded notional, e.g. 0.0005 for 5 bp
+    pub proportional: f64,
+    /// Fixed charge per rebalance
+    pub per_trade: f64,
+}
+
+impl TransactionCosts {
+    pub fn new(proportional: f64, per_trade: f64) -> Result<Self, BlackScholesError> {
+        Ok(TransactionCosts {
+            proportional: validation::non_negative("Proportional cost", proportional)?,
+            per_trade: validation::non_negative("Cost per trade", per_trade)?,
+        })
+    }
+
+    fn of(&self, shares: f64, spot: f64) -> f64 {
+        self.proportional * shares.abs() * spot + self.per_trade
+    }
+}
+
+/// A European option held against a delta hedge in the underlying
+///
+/// The option is opened on the first observation and marked at each
+/// observation's implied volatility; deltas use `hedge_volatility` when set.
+/// Cash accrues at the observed rate and short or long shares pay or earn
+/// the dividend yield. At expiry the option settles at intrinsic value and
+/// the hedge is unwound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeltaHedge {
+    pub option_type: OptionType,
+    pub strike: f64,
+    /// Expiry in years from the first observation
+    pub expiry: f64,
+    /// Options held (negative for written)
+    pub quantity: f64,
+    /// Volatility used for hedge ratios instead of the observed implied volatility
+    pub hedge_volatility: Option<f64>,
+    pub rule: HedgeRule,
+    pub costs: TransactionCosts,
+}
+
+/// Outcome of one hedging run
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HedgeReport {
+    /// Observation dates in years from the start, up to expiry
+    pub times: Vec<f64>,
+    /// Cumulative mark-to-market P&L, net of costs, on each date
+    pub pnl: Vec<f64>,
+    /// Change in the option's value, including its settlement
+    pub option_pnl: f64,
+    /// Trading, dividend and financing P&L of the hedge and cash account before costs
+    pub hedge_pnl: f64,
+    /// Total paid in transaction costs (the cost drag)
+    pub transaction_costs: f64,
+    /// Number of rebalances, including the opening hedge and the final unwind
+    pub trades: usize,
+    /// Implied volatility the option was bought or sold at
+    pub implied_volatility: f64,
+    /// Annualized volatility of the underlying's log returns over the run
+    pub realized_volatility: f64,
+}
+
+impl HedgeReport {
+    /// Net P&L at the end of the run
+    pub fn total_pnl(&self) -> f64 {
+        self.pnl.last().copied().unwrap_or(0.0)
+    }
+
+    /// Realized minus implied volatility, the gap a delta-hedged option monetizes
+    pub fn vol_gap(&self) -> f64 {
+        self.realized_volatility - self.implied_volatility
+    }
+}
+
+impl DeltaHedge {
+    pub fn new(
+        option_type: OptionType,
+        strike: f64,
+        expiry: f64,
+        quantity: f64,
+        rule: HedgeRule,
+        costs: TransactionCosts,
+    ) -> Result<Self, BlackScholesError> {
+        match rule {
+            HedgeRule::Periodic { every: 0 } => {
+                return Err(BlackScholesError::invalid("Hedge period must be at least one observation"));
+            }
+            HedgeRule::DeltaBand { band } => {
+                validation::non_negative("Delta band", band)?;
+            }
+            HedgeRule::Periodic { .. } => {}
+        }
+        Ok(DeltaHedge {
+            option_type,
+            strike: validation::positive("Strike price", strike)?,
+            expiry: validation::positive("Time to expiry", expiry)?,
+            quantity: validation::finite("Quantity", quantity)?,
+            hedge_volatility: None,
+            rule,
+            costs: TransactionCosts::new(costs.proportional, costs.per_trade)?,
+        })
+    }
+
+    /// Hedge with a fixed volatility, e.g. an estimate of future realized volatility
+    pub fn with_hedge_volatility(mut self, volatility: f64) -> Result<Self, BlackScholesError> {
+        self.hedge_volatility = Some(validation::positive("Hedge volatility", volatility)?);
+        Ok(self)
+    }
+
+    fn model(&self, obs: &MarketObservation, remaining: f64, volatility: f64) -> Result<BlackScholes, BlackScholesError> {
+        BlackScholes::new(obs.spot, self.strike, remaining, obs.rate, volatility, obs.dividend_yield)
+    }
+
+    fn intrinsic(&self, spot: f64) -> f64 {
+        match self.option_type {
+            OptionType::Call => (spot - self.strike).max(0.0),
+            OptionType::Put => (self.strike - spot).max(0.0),
+        }
+    }
+
+    /// Replay the hedge over `history` (chronological, at least two observations)
+    ///
+    /// Observations after expiry are ignored. If the history ends first, the
+    /// option and hedge are marked at the last observation without unwinding.
+    pub fn run(&self, history: &[MarketObservation]) -> Result<HedgeReport, BlackScholesError> {
+        if history.len() < 2 {
+            return Err(BlackScholesError::invalid("Hedging history needs at least two observations"));
+        }
+        if history.iter().any(|obs| !obs.time.is_finite()) || history.windows(2).any(|w| w[1].time <= w[0].time) {
+            return Err(BlackScholesError::invalid("Observation times must be finite and strictly increasing"));
+        }
+        let t0 = history[0].time;
+        let first = &history[0];
+        let opening = self.quantity * self.model(first, self.expiry, first.volatility)?.price(self.option_type);
+        let mut shares = 0.0;
+        let mut cash = -opening;
+        let mut costs = 0.0;
+        let mut trades = 0;
+        let mut option_value = opening;
+        let (mut times, mut pnl, mut log_returns) = (Vec::new(), Vec::new(), Vec::new());
+
+        for (i, obs) in history.iter().enumerate() {
+            validation::positive("Spot", obs.spot)?;
+            let remaining = self.expiry - (obs.time - t0);
+            if i > 0 {
+                let prev = &history[i - 1];
+                let dt = obs.time - prev.time;
+                cash *= (prev.rate * dt).exp();
+                cash += shares * prev.spot * prev.dividend_yield * dt;
+                log_returns.push((obs.spot / prev.spot).ln());
+                if remaining < -SETTLE_EPSILON {
+                    break;
+                }
+            }
+
+            let expired = remaining <= SETTLE_EPSILON;
+            if expired {
+                option_value = self.quantity * self.intrinsic(obs.spot);
+                if shares != 0.0 {
+                    costs += self.costs.of(shares, obs.spot);
+                    cash += shares * obs.spot;
+                    shares = 0.0;
+                    trades += 1;
+                }
+            } else {
+                option_value = self.quantity * self.model(obs, remaining, obs.volatility)?.price(self.option_type);
+                let volatility = self.hedge_volatility.unwrap_or(obs.volatility);
+                let target = -self.quantity * self.model(obs, remaining, volatility)?.greeks(self.option_type).delta;
+                let rebalance = match self.rule {
+                    HedgeRule::Periodic { every } => i % every == 0,
+                    HedgeRule::DeltaBand { band } => (shares - target).abs() > band * self.quantity.abs(),
+                };
+                if i == 0 || rebalance {
+                    let traded = target - shares;
+                    costs += self.costs.of(traded, obs.spot);
+                    cash -= traded * obs.spot;
+                    shares = target;
+                    trades += 1;
+                }
+            }
+            times.push(obs.time - t0);
+            pnl.push(cash - costs + shares * obs.spot + option_value);
+            if expired {
+                break;
+            }
+        }
+
+        let span = times[times.len() - 1];
+        let realized_volatility = if log_returns.len() < 2 {
+            0.0
+        } else {
+            let n = log_returns.len() as f64;
+            let mean = log_returns.iter().sum::<f64>() / n;
+            let variance = log_returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            (variance * n / span).sqrt()
+        };
+        let option_pnl = option_value - opening;
+        let total = pnl[pnl.len() - 1];
+        Ok(HedgeReport {
+            times,
+            pnl,
+            option_pnl,
+            hedge_pnl: total + costs - option_pnl,
+            transaction_costs: costs,
+            trades,
+            implied_volatility: first.volatility,
+            realized_volatility,
+        })
+    }
+}
+
+/// Simulated market history for hedging studies
+///
+/// Spots come from `model` on the grid `times` (strictly increasing, from
+/// today); every observation quotes the same implied volatility, rate and
+/// dividend yield. The first observation is today's spot at time zero.
+pub fn simulate_history<M: PathModel + ?Sized>(
+    model: &M,
+    times: &[f64],
+    implied_volatility: f64,
+    rate: f64,
+    dividend_yield: f64,
+    seed: u64,
+) -> Vec<MarketObservation> {
+    let mut path = vec![0.0; times.len()];
+    model.simulate_path(times, &mut Rng::new(seed), &mut path);
+    let observation = |time, spot| MarketObservation {
+        time,
+        spot,
+        volatility: implied_volatility,
+        rate,
+        dividend_yield,
+    };
+    std::iter::once(observation(0.0, model.initial_spot()))
+        .chain(times.iter().zip(path).map(|(&t, s)| observation(t, s)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monte_carlo::uniform_times;
+
+    fn history(realized: f64, seed: u64) -> Vec<MarketObservation> {
+        let model = BlackScholes::new(100.0, 100.0, 0.25, 0.02, realized, 0.0).unwrap();
+        simulate_history(&model, &uniform_times(0.25, 250), 0.2, 0.02, 0.0, seed)
+    }
+
+    fn hedge(rule: HedgeRule, costs: TransactionCosts) -> DeltaHedge {
+        DeltaHedge::new(OptionType::Call, 100.0, 0.25, 1.0, rule, costs).unwrap()
+    }
+
+    #[test]
+    fn test_hedged_pnl_is_small_when_realized_matches_implied() {
+        let daily = hedge(HedgeRule::Periodic { every: 1 }, TransactionCosts::default());
+        let premium = BlackScholes::new(100.0, 100.0, 0.25, 0.02, 0.2, 0.0).unwrap().price(OptionType::Call);
+        let runs: Vec<HedgeReport> = (0..20).map(|seed| daily.run(&history(0.2, seed)).unwrap()).collect();
+        let mean = runs.iter().map(HedgeReport::total_pnl).sum::<f64>() / runs.len() as f64;
+        assert!(mean.abs() < 0.05 * premium, "{mean}");
+        for run in &runs {
+            assert!(run.total_pnl().abs() < 0.25 * premium);
+            assert!((run.option_pnl + run.hedge_pnl - run.total_pnl()).abs() < 1e-9);
+            assert_eq!(run.transaction_costs, 0.0);
+            assert_eq!(run.times.len(), 251);
+        }
+    }
+
+    #[test]
+    fn test_long_gamma_earns_the_vol_gap() {
+        let daily = hedge(HedgeRule::Periodic { every: 1 }, TransactionCosts::default());
+        let report = daily.run(&history(0.35, 3)).unwrap();
+        assert!((report.vol_gap() - 0.15).abs() < 0.04, "{}", report.vol_gap());
+        assert!(report.total_pnl() > 0.0);
+        // The writer of the same option loses the same amount
+        let short = DeltaHedge { quantity: -1.0, ..daily };
+        assert!((short.run(&history(0.35, 3)).unwrap().total_pnl() + report.total_pnl()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_costs_and_band_hedging() {
+        let path = history(0.2, 5);
+        let costs = TransactionCosts::new(0.001, 0.01).unwrap();
+        let free = hedge(HedgeRule::Periodic { every: 1 }, TransactionCosts::default()).run(&path).unwrap();
+        let daily = hedge(HedgeRule::Periodic { every: 1 }, costs).run(&path).unwrap();
+        assert!((free.total_pnl() - daily.total_pnl() - daily.transaction_costs).abs() < 1e-9);
+
+        let weekly = hedge(HedgeRule::Periodic { every: 5 }, costs).run(&path).unwrap();
+        let band = hedge(HedgeRule::DeltaBand { band: 0.05 }, costs).run(&path).unwrap();
+        assert!(weekly.trades < daily.trades && band.trades < daily.trades);
+        assert!(band.transaction_costs < daily.transaction_costs);
+    }
+
+    #[test]
+    fn test_expiry_settles_and_unwinds() {
+        let path = history(0.2, 9);
+        let short_dated = DeltaHedge {
+            expiry: 0.1,
+            ..hedge(HedgeRule::Periodic { every: 1 }, TransactionCosts::default())
+        };
+        let report = short_dated.run(&path).unwrap();
+        assert!((report.times[report.times.len() - 1] - 0.1).abs() < 1e-9);
+        let settle = path.iter().find(|o| (o.time - 0.1).abs() < 1e-9).unwrap();
+        let opening = BlackScholes::new(100.0, 100.0, 0.1, 0.02, 0.2, 0.0).unwrap().price(OptionType::Call);
+        assert!((report.option_pnl - ((settle.spot - 100.0).max(0.0) - opening)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let costs = TransactionCosts::default();
+        assert!(DeltaHedge::new(OptionType::Put, 100.0, 0.0, 1.0, HedgeRule::Periodic { every: 1 }, costs).is_err());
+        assert!(DeltaHedge::new(OptionType::Put, 100.0, 1.0, 1.0, HedgeRule::Periodic { every: 0 }, costs).is_err());
+        assert!(TransactionCosts::new(-0.001, 0.0).is_err());
+        let daily = hedge(HedgeRule::Periodic { every: 1 }, costs);
+        assert!(daily.run(&history(0.2, 1)[..1]).is_err());
+        let mut reversed = history(0.2, 1);
+        reversed.swap(1, 2);
+        assert!(daily.run(&reversed).is_err());
+        // Disorder after expiry is still rejected, though those dates are never hedged
+        let rule = HedgeRule::Periodic { every: 1 };
+        let short = DeltaHedge::new(OptionType::Call, 100.0, 0.1, 1.0, rule, costs).unwrap();
+        let mut late = history(0.2, 1);
+        let n = late.len();
+        late.swap(n - 2, n - 1);
+        assert!(short.run(&late).is_err());
+    }
+}
@@ -0,0 +1,608 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::chain::OptionChain;
+use crate::characteristic::CharacteristicFunction;
+use crate::error::BlackScholesError;
+use crate::fourier::{CosMethod, TransformPricer};
+use crate::math::{gauss_legendre, Complex, SimplexSearch};
+use crate::model::EuropeanModel;
+use crate::validation;
+
+/// Width of the first Gauss-Legendre panel of the pricing integral
+///
+/// The integrand has poles at ±i/2, so panels start narrow near the origin
+/// and widen geometrically by `PANEL_GROWTH` up to `MAX_PANEL_WIDTH`.
+const PANEL_WIDTH: f64 = 1.0;
+
+/// Width ratio of consecutive panels
+const PANEL_GROWTH: f64 = 1.25;
+
+/// Widest panel used in the tail
+const MAX_PANEL_WIDTH: f64 = 8.0;
+
+/// Nodes per Gauss-Legendre panel
+const PANEL_NODES: usize = 16;
+
+/// Integrand magnitude below which the pricing integral is truncated
+const INTEGRAND_TOLERANCE: f64 = 1e-14;
+
+/// Hard cap on the number of panels
+const MAX_PANELS: usize = 2000;
+
+/// Floor on the vega used to turn price errors into volatility errors
+const MIN_VEGA: f64 = 1e-6;
+
+/// Simplex edge length when recalibrating from previous parameters
+const WARM_START_STEP: f64 = 0.1;
+
+/// Heston (1993) stochastic variance parameters
+///
+/// The instantaneous variance follows dv = κ(θ - v)dt + ξ√v dW_v with
+/// d⟨W_S, W_v⟩ = ρ dt, starting from v₀.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HestonParams {
+    /// Initial variance (v₀)
+    pub v0: f64,
+    /// Mean-reversion speed (κ)
+    pub kappa: f64,
+    /// Long-run variance (θ)
+    pub theta: f64,
+    /// Volatility of variance (ξ)
+    pub vol_of_vol: f64,
+    /// Spot-variance correlation, in [-1, 1]
+    pub rho: f64,
+}
+
+/// Result of calibrating Heston parameters to an option chain
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HestonFit {
+    /// The calibrated parameters
+    pub params: HestonParams,
+    /// Root-mean-square implied volatility error over the quotes
+    pub rmse: f64,
+    /// Largest absolute implied volatility error over the quotes
+    pub max_error: f64,
+}
+
+impl HestonParams {
+    pub fn new(v0: f64, kappa: f64, theta: f64, vol_of_vol: f64, rho: f64) -> Result<Self, BlackScholesError> {
+        Ok(HestonParams {
+            v0: validation::non_negative("Initial variance", v0)?,
+            kappa: validation::positive("Mean reversion", kappa)?,
+            theta: validation::non_negative("Long-run variance", theta)?,
+            vol_of_vol: validation::positive("Vol of vol", vol_of_vol)?,
+            rho: validation::in_range("Correlation", rho, -1.0, 1.0, "in [-1, 1]")?,
+        })
+    }
+
+    /// Whether 2κθ ≥ ξ², so the variance process never reaches zero
+    pub fn feller_satisfied(&self) -> bool {
+        2.0 * self.kappa * self.theta >= self.vol_of_vol * self.vol_of_vol
+    }
+
+    /// Characteristic function of ln(S_T / F_T) at (complex) `u`
+    ///
+    /// Uses the "little trap" form of Albrecher et al. (2007), which stays on
+    /// the principal branch of the logarithm for long expiries.
+    pub fn forward_char_fn(&self, u: Complex, t: f64) -> Complex {
+        let xi2 = self.vol_of_vol * self.vol_of_vol;
+        let iu = Complex::I * u;
+        let a = self.kappa - self.rho * self.vol_of_vol * iu;
+        let d = (a * a + xi2 * (iu + u * u)).sqrt();
+        let g = (a - d) / (a + d);
+        let decay = (-d * t).exp();
+        let c = self.kappa * self.theta / xi2 * ((a - d) * t - 2.0 * ((1.0 - g * decay) / (1.0 - g)).ln());
+        let dv = (a - d) / xi2 * (1.0 - decay) / (1.0 - g * decay);
+        (c + dv * self.v0).exp()
+    }
+
+    /// Undiscounted call prices E[(S_T - K)⁺] for several strikes on one expiry
+    ///
+    /// Lewis (2001) single-integral formula,
+    /// C = F - √(FK)/π ∫₀^∞ Re[e^(iu·ln(F/K)) ψ(u - i/2)] / (u² + ¼) du,
+    /// integrated panel by panel until the integrand is negligible. The
+    /// characteristic function is shared across strikes.
+    pub fn forward_call_prices(&self, expiry: f64, forward: f64, strikes: &[f64]) -> Vec<f64> {
+        let (nodes, weights) = gauss_legendre(PANEL_NODES);
+        let mut terms: Vec<(f64, Complex)> = Vec::new();
+        let mut start = 0.0;
+        let mut width = PANEL_WIDTH;
+        for _ in 0..MAX_PANELS {
+            let mut largest: f64 = 0.0;
+            for (x, w) in nodes.iter().zip(&weights) {
+                let u = start + 0.5 * width * (x + 1.0);
+                let psi = self.forward_char_fn(Complex::new(u, -0.5), expiry);
+                let term = psi * (0.5 * width * w / (u * u + 0.25));
+                largest = largest.max(term.abs());
+                terms.push((u, term));
+            }
+            if largest < INTEGRAND_TOLERANCE {
+                break;
+            }
+            start += width;
+            width = (width * PANEL_GROWTH).min(MAX_PANEL_WIDTH);
+        }
+
+        strikes
+            .iter()
+            .map(|&strike| {
+                let log_moneyness = (forward / strike).ln();
+                let integral: f64 = terms
+                    .iter()
+                    .map(|&(u, term)| (Complex::from_polar(1.0, u * log_moneyness) * term).re)
+                    .sum();
+                (forward - (forward * strike).sqrt() / std::f64::consts::PI * integral).max((forward - strike).max(0.0))
+            })
+            .collect()
+    }
+
+    /// Calibrate to an option chain by least squares on vega-weighted price errors
+    ///
+    /// Uses the out-of-the-money side at every strike whose implied volatility
+    /// solves, each slice with its own forward and discount factor. Weighting
+    /// price errors by 1/vega makes the objective approximately a sum of
+    /// squared implied volatility errors without inverting model prices.
+    /// Nelder-Mead runs over log κ, log θ, log ξ, log v₀ and atanh ρ; the
+    /// Feller condition is not imposed.
+    pub fn calibrate(chain: &OptionChain) -> Result<HestonFit, BlackScholesError> {
+        HestonCalibration::new(chain, None)?.finish()
+    }
+
+    /// Recalibrate from previous parameters (warm start)
+    ///
+    /// A single local search from `previous` replaces the two cold starts of
+    /// `calibrate`, cutting intraday refits when the market has moved little.
+    pub fn calibrate_from(chain: &OptionChain, previous: &HestonParams) -> Result<HestonFit, BlackScholesError> {
+        HestonCalibration::new(chain, Some(previous))?.finish()
+    }
+
+    /// Optimizer coordinates of these parameters (inverse of `from_unconstrained`)
+    fn to_unconstrained(self) -> Vec<f64> {
+        vec![
+            self.v0.max(1e-12).ln(),
+            self.kappa.ln(),
+            self.theta.max(1e-12).ln(),
+            self.vol_of_vol.ln(),
+            self.rho.clamp(-1.0 + 1e-12, 1.0 - 1e-12).atanh(),
+        ]
+    }
+
+    /// Map an unconstrained optimizer vector onto valid parameters
+    fn from_unconstrained(x: &[f64]) -> Self {
+        HestonParams {
+            v0: x[0].exp(),
+            kappa: x[1].exp(),
+            theta: x[2].exp(),
+            vol_of_vol: x[3].exp(),
+            rho: x[4].tanh(),
+        }
+    }
+}
+
+/// Quotes of one expiry prepared for calibration
+#[derive(Debug, Clone)]
+struct CalibrationSlice {
+    expiry: f64,
+    forward: f64,
+    discount: f64,
+    strikes: Vec<f64>,
+    // (option type, mid, market vol, vega)
+    quotes: Vec<(OptionType, f64, f64, f64)>,
+}
+
+impl CalibrationSlice {
+    fn model_prices(&self, p: &HestonParams) -> Vec<f64> {
+        p.forward_call_prices(self.expiry, self.forward, &self.strikes)
+            .iter()
+            .zip(&self.quotes)
+            .zip(&self.strikes)
+            .map(|((call, q), k)| match q.0 {
+                OptionType::Call => self.discount * call,
+                OptionType::Put => self.discount * (call - self.forward + k),
+            })
+            .collect()
+    }
+}
+
+/// Vega-weighted squared price errors at optimizer coordinates `x`
+fn calibration_objective(slices: &[CalibrationSlice], x: &[f64]) -> f64 {
+    let p = HestonParams::from_unconstrained(x);
+    slices
+        .iter()
+        .map(|s| {
+            s.model_prices(&p)
+                .iter()
+                .zip(&s.quotes)
+                .map(|(price, q)| ((price - q.1) / q.3).powi(2))
+                .sum::<f64>()
+        })
+        .sum::<f64>()
+}
+
+/// Heston calibration that runs in installments and can be checkpointed
+///
+/// The optimizer state (`checkpoint`) is plain, serializable data. A job
+/// interrupted after any number of iterations resumes from it against the
+/// same chain and finishes with exactly the parameters an uninterrupted run
+/// would have produced.
+#[derive(Debug, Clone)]
+pub struct HestonCalibration {
+    spot: f64,
+    slices: Vec<CalibrationSlice>,
+    search: SimplexSearch,
+}
+
+impl HestonCalibration {
+    /// Prepare a calibration, cold or warm-started from previous parameters
+    pub fn new(chain: &OptionChain, warm_start: Option<&HestonParams>) -> Result<Self, BlackScholesError> {
+        let slices = Self::prepare(chain)?;
+        // Restarts from each optimum escape simplex collapse
+        let search = match warm_start {
+            None => {
+                let count: usize = slices.iter().map(|s| s.quotes.len()).sum();
+                let atm_variance = slices
+                    .iter()
+                    .flat_map(|s| s.quotes.iter().map(|q| q.2 * q.2))
+                    .sum::<f64>()
+                    / count as f64;
+                let starts = [-0.7_f64, 0.0]
+                    .iter()
+                    .map(|rho| vec![atm_variance.ln(), 1.5_f64.ln(), atm_variance.ln(), 0.5_f64.ln(), rho.atanh()])
+                    .collect();
+                SimplexSearch::new(starts, 0.3, 1e-14, 3000).with_restarts(3, 0.1)
+            }
+            Some(p) => SimplexSearch::new(vec![p.to_unconstrained()], WARM_START_STEP, 1e-14, 3000)
+                .with_restarts(1, WARM_START_STEP),
+        };
+        Ok(HestonCalibration {
+            spot: chain.spot,
+            slices,
+            search,
+        })
+    }
+
+    /// Continue a calibration from a checkpoint taken on the same chain
+    pub fn resume(chain: &OptionChain, checkpoint: SimplexSearch) -> Result<Self, BlackScholesError> {
+        if checkpoint.starts.iter().any(|x| x.len() != 5) {
+            return Err(BlackScholesError::invalid("Checkpoint is not a Heston calibration"));
+        }
+        Ok(HestonCalibration {
+            spot: chain.spot,
+            slices: Self::prepare(chain)?,
+            search: checkpoint,
+        })
+    }
+
+    /// Optimizer state to persist and later pass to `resume`
+    pub fn checkpoint(&self) -> &SimplexSearch {
+        &self.search
+    }
+
+    /// Perform up to `budget` optimizer iterations; the fit once the search is finished
+    pub fn advance(&mut self, budget: usize) -> Result<Option<HestonFit>, BlackScholesError> {
+        let slices = &self.slices;
+        if !self.search.advance(|x| calibration_objective(slices, x), budget) {
+            return Ok(None);
+        }
+        self.fit().map(Some)
+    }
+
+    /// Run the calibration to the end
+    pub fn finish(mut self) -> Result<HestonFit, BlackScholesError> {
+        self.advance(usize::MAX)?
+            .ok_or_else(|| BlackScholesError::no_convergence("Heston calibration failed"))
+    }
+
+    /// Out-of-the-money quotes per expiry with their market vols and vegas
+    fn prepare(chain: &OptionChain) -> Result<Vec<CalibrationSlice>, BlackScholesError> {
+        let analytics = chain.analyze();
+        let mut slices = Vec::new();
+        for slice in &chain.slices {
+            let forward = slice.forward(chain.spot);
+            let discount = (-slice.rate * slice.expiry).exp();
+            let mut strikes = Vec::new();
+            let mut quotes = Vec::new();
+            for row in analytics.iter().filter(|r| r.expiry == slice.expiry) {
+                let otm = match row.option_type {
+                    OptionType::Call => row.strike >= forward,
+                    OptionType::Put => row.strike < forward,
+                };
+                if let (true, Some(vol)) = (otm, row.implied_vol) {
+                    let bs = BlackScholes::new(chain.spot, row.strike, slice.expiry, slice.rate, vol, slice.dividend_yield)?;
+                    strikes.push(row.strike);
+                    quotes.push((row.option_type, row.mid, vol, (bs.greeks(row.option_type).vega * 100.0).max(MIN_VEGA)));
+                }
+            }
+            if !quotes.is_empty() {
+                slices.push(CalibrationSlice {
+                    expiry: slice.expiry,
+                    forward,
+                    discount,
+                    strikes,
+                    quotes,
+                });
+            }
+        }
+        let count: usize = slices.iter().map(|s| s.quotes.len()).sum();
+        if count < 5 {
+            return Err(BlackScholesError::invalid("Heston calibration needs at least five usable quotes"));
+        }
+        Ok(slices)
+    }
+
+    /// Parameters and implied volatility errors of the finished search
+    fn fit(&self) -> Result<HestonFit, BlackScholesError> {
+        let best = self
+            .search
+            .best
+            .as_ref()
+            .filter(|b| b.value.is_finite())
+            .ok_or_else(|| BlackScholesError::no_convergence("Heston calibration failed"))?;
+        let params = HestonParams::from_unconstrained(&best.x);
+
+        let mut errors = Vec::new();
+        for s in &self.slices {
+            let rate = -s.discount.ln() / s.expiry;
+            let dividend_yield = rate - (s.forward / self.spot).ln() / s.expiry;
+            for ((price, q), &strike) in s.model_prices(&params).iter().zip(&s.quotes).zip(&s.strikes) {
+                let bs = BlackScholes::new(self.spot, strike, s.expiry, rate, q.2, dividend_yield)?;
+                let vol = bs.implied_volatility(q.0, *price, 100, 1e-10).unwrap_or(f64::NAN);
+                errors.push((vol - q.2).abs());
+            }
+        }
+        let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        let max_error = errors.iter().copied().fold(0.0, f64::max);
+
+        Ok(HestonFit {
+            params,
+            rmse,
+            max_error,
+        })
+    }
+}
+
+/// Heston stochastic volatility model with flat carry
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Heston {
+    pub spot: f64,
+    pub rate: f64,
+    pub dividend_yield: f64,
+    pub params: HestonParams,
+}
+
+impl Heston {
+    pub fn new(spot: f64, rate: f64, dividend_yield: f64, params: HestonParams) -> Result<Self, BlackScholesError> {
+        Ok(Heston {
+            spot: validation::positive("Spot price", spot)?,
+            rate: validation::finite("Risk-free rate", rate)?,
+            dividend_yield: validation::finite("Dividend yield", dividend_yield)?,
+            params,
+        })
+    }
+}
+
+impl CharacteristicFunction for Heston {
+    /// Forward characteristic function shifted by the carry drift
+    fn char_fn(&self, u: Complex, t: f64) -> Complex {
+        let drift = Complex::I * u * ((self.rate - self.dividend_yield) * t);
+        drift.exp() * self.params.forward_char_fn(u, t)
+    }
+}
+
+impl EuropeanModel for Heston {
+    fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    fn rate(&self, _expiry: f64) -> f64 {
+        self.rate
+    }
+
+    fn dividend_yield(&self, _expiry: f64) -> f64 {
+        self.dividend_yield
+    }
+
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        let forward = self.forward(expiry);
+        let call = self.params.forward_call_prices(expiry, forward, &[strike])[0];
+        let discount = (-self.rate * expiry).exp();
+        match option_type {
+            OptionType::Call => discount * call,
+            OptionType::Put => discount * (call - forward + strike),
+        }
+    }
+}
+
+/// Bates (1996) model: Heston stochastic variance plus Merton lognormal jumps
+///
+/// Jumps arrive at Poisson rate λ independently of the variance and multiply
+/// the spot by e^J, J ~ N(μ_J, σ_J²), with the drift compensated by λk,
+/// k = E[e^J] - 1. The jump term multiplies the Heston characteristic function.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bates {
+    /// Stochastic variance and carry
+    pub heston: Heston,
+    /// Expected number of jumps per year (λ)
+    pub jump_intensity: f64,
+    /// Mean of the log jump size (μ_J)
+    pub jump_mean: f64,
+    /// Standard deviation of the log jump size (σ_J)
+    pub jump_volatility: f64,
+}
+
+impl Bates {
+    pub fn new(
+        heston: Heston,
+        jump_intensity: f64,
+        jump_mean: f64,
+        jump_volatility: f64,
+    ) -> Result<Self, BlackScholesError> {
+        Ok(Bates {
+            heston,
+            jump_intensity: validation::non_negative("Jump intensity", jump_intensity)?,
+            jump_mean: validation::finite("Jump mean", jump_mean)?,
+            jump_volatility: validation::non_negative("Jump volatility", jump_volatility)?,
+        })
+    }
+
+    /// Expected relative jump size k = E[e^J] - 1
+    pub fn mean_jump(&self) -> f64 {
+        (self.jump_mean + 0.5 * self.jump_volatility.powi(2)).exp() - 1.0
+    }
+}
+
+impl CharacteristicFunction for Bates {
+    fn char_fn(&self, u: Complex, t: f64) -> Complex {
+        let iu = Complex::I * u;
+        let jump = (iu * self.jump_mean - 0.5 * self.jump_volatility.powi(2) * u * u).exp() - 1.0;
+        let compensated = (jump - iu * self.mean_jump()) * (self.jump_intensity * t);
+        self.heston.char_fn(u, t) * compensated.exp()
+    }
+}
+
+impl EuropeanModel for Bates {
+    fn spot(&self) -> f64 {
+        self.heston.spot
+    }
+
+    fn rate(&self, _expiry: f64) -> f64 {
+        self.heston.rate
+    }
+
+    fn dividend_yield(&self, _expiry: f64) -> f64 {
+        self.heston.dividend_yield
+    }
+
+    /// COS price; use a [`TransformPricer`] directly to price a whole strike strip
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        CosMethod::default().prices(self, option_type, expiry, &[strike])[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::{ExpirySlice, OptionQuote};
+
+    fn heston() -> Heston {
+        Heston::new(100.0, 0.03, 0.01, HestonParams::new(0.04, 1.5, 0.06, 0.6, -0.7).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_vanishing_vol_of_vol_is_black_scholes() {
+        let params = HestonParams::new(0.04, 2.0, 0.04, 1e-4, 0.0).unwrap();
+        let model = Heston::new(100.0, 0.03, 0.01, params).unwrap();
+        for &strike in &[70.0, 100.0, 130.0] {
+            let bs = BlackScholes::new(100.0, strike, 0.75, 0.03, 0.2, 0.01).unwrap();
+            for ot in [OptionType::Call, OptionType::Put] {
+                assert!((model.price(ot, strike, 0.75) - bs.price(ot)).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_put_call_parity_and_skew() {
+        let model = heston();
+        let call = model.price(OptionType::Call, 110.0, 1.0);
+        let put = model.price(OptionType::Put, 110.0, 1.0);
+        let parity = 100.0 * (-0.01_f64).exp() - 110.0 * (-0.03_f64).exp();
+        assert!((call - put - parity).abs() < 1e-10);
+
+        // Negative correlation puts more variance in the left tail
+        let vol = |ot, strike: f64| {
+            let price = model.price(ot, strike, 1.0);
+            BlackScholes::new(100.0, strike, 1.0, 0.03, 0.2, 0.01)
+                .unwrap()
+                .implied_volatility(ot, price, 100, 1e-10)
+                .unwrap()
+        };
+        assert!(vol(OptionType::Put, 80.0) > vol(OptionType::Call, 100.0));
+        assert!(vol(OptionType::Call, 100.0) > vol(OptionType::Call, 120.0));
+    }
+
+    #[test]
+    fn test_char_fn_martingale() {
+        let model = heston();
+        let phi = model.char_fn(-Complex::I, 2.0);
+        assert!((phi.re - (0.02_f64 * 2.0).exp()).abs() < 1e-12 && phi.im.abs() < 1e-12);
+        let bates = Bates::new(model, 0.5, -0.1, 0.2).unwrap();
+        let phi = bates.char_fn(-Complex::I, 2.0);
+        assert!((phi.re - (0.02_f64 * 2.0).exp()).abs() < 1e-12 && phi.im.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bates_jumps_steepen_the_put_wing() {
+        let model = heston();
+        let no_jumps = Bates::new(model, 0.0, -0.1, 0.2).unwrap();
+        let bates = Bates::new(model, 0.5, -0.1, 0.2).unwrap();
+        for strike in [70.0, 100.0, 130.0] {
+            let lewis = model.price(OptionType::Put, strike, 0.5);
+            assert!((no_jumps.price(OptionType::Put, strike, 0.5) - lewis).abs() < 1e-7);
+        }
+        let put = |m: &dyn EuropeanModel| m.price(OptionType::Put, 70.0, 0.5);
+        assert!(put(&bates) > 1.5 * put(&model));
+        assert!(Bates::new(model, -0.5, 0.0, 0.1).is_err());
+    }
+
+    fn quoted_chain(model: &Heston) -> OptionChain {
+        let slices = [0.25, 1.0]
+            .iter()
+            .map(|&expiry| {
+                let quotes = (0..9)
+                    .flat_map(|i| {
+                        let strike = 80.0 + 5.0 * i as f64;
+                        [OptionType::Call, OptionType::Put].map(|ot| {
+                            let p = model.price(ot, strike, expiry);
+                            OptionQuote::new(strike, ot, p, p).unwrap()
+                        })
+                    })
+                    .collect();
+                ExpirySlice::new(expiry, 0.03, 0.01, quotes).unwrap()
+            })
+            .collect();
+        OptionChain::new(model.spot, slices).unwrap()
+    }
+
+    #[test]
+    fn test_calibration_recovers_parameters() {
+        let chain = quoted_chain(&heston());
+
+        let fit = HestonParams::calibrate(&chain).unwrap();
+        assert!(fit.rmse < 5e-4, "rmse = {}", fit.rmse);
+        assert!((fit.params.v0 - 0.04).abs() < 5e-3);
+        assert!((fit.params.rho + 0.7).abs() < 0.15);
+    }
+
+    #[test]
+    fn test_warm_start_and_resume_from_checkpoint() {
+        let mut cold = HestonCalibration::new(&quoted_chain(&heston()), None).unwrap();
+        let fit = cold.advance(usize::MAX).unwrap().unwrap();
+
+        // The market moves slightly; recalibrate from yesterday's parameters
+        let params = HestonParams { v0: 0.045, rho: -0.65, ..heston().params };
+        let moved = quoted_chain(&Heston::new(101.0, 0.03, 0.01, params).unwrap());
+        let mut warm = HestonCalibration::new(&moved, Some(&fit.params)).unwrap();
+        assert!(warm.advance(50).unwrap().is_none());
+        let checkpoint = warm.checkpoint().clone();
+        let warm_fit = warm.advance(usize::MAX).unwrap().unwrap();
+        assert!(warm_fit.rmse < 5e-4, "rmse = {}", warm_fit.rmse);
+        assert!(warm.checkpoint().iterations < cold.checkpoint().iterations / 2);
+
+        let resumed = HestonCalibration::resume(&moved, checkpoint).unwrap().finish().unwrap();
+        assert_eq!(resumed.params, warm_fit.params);
+        let wrong = SimplexSearch::new(vec![vec![0.0; 3]], 0.1, 1e-8, 10);
+        assert!(HestonCalibration::resume(&moved, wrong).is_err());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(HestonParams::new(-0.01, 1.0, 0.04, 0.5, 0.0).is_err());
+        assert!(HestonParams::new(0.04, 0.0, 0.04, 0.5, 0.0).is_err());
+        assert!(HestonParams::new(0.04, 1.0, 0.04, 0.5, -1.5).is_err());
+        assert!(HestonParams::new(0.04, 2.0, 0.04, 1e-4, 0.0).unwrap().feller_satisfied());
+        assert!(!HestonParams::new(0.04, 0.5, 0.04, 1.0, 0.0).unwrap().feller_satisfied());
+    }
+}
@@ -0,0 +1,805 @@
+use crate::black_scholes::OptionType;
+use crate::curves::bootstrap::swap_schedule;
+use crate::curves::DiscountCurve;
+use crate::error::BlackScholesError;
+use crate::math::{norm_cdf, SimplexSearch};
+use crate::monte_carlo::{validate_tenors, FixingModel, McResult, MonteCarlo};
+use crate::rates::{CapFloor, Caplet, RateVolatility, Swaption};
+use crate::validation;
+
+/// Mean reversion below which closed forms switch to their a → 0 limits
+const SMALL_MEAN_REVERSION: f64 = 1e-8;
+
+/// Half-width of the finite difference for the instantaneous forward rate
+const FORWARD_BUMP: f64 = 1e-5;
+
+/// Bracket for the Jamshidian critical short rate
+const CRITICAL_RATE_BRACKET: (f64, f64) = (-2.0, 2.0);
+
+/// Bisection steps for the Jamshidian critical short rate
+const CRITICAL_RATE_ITERATIONS: usize = 200;
+
+/// Hull-White (1994) truncation: the tree stops widening at j_max = ⌈0.184 / (aΔt)⌉
+const TRUNCATION_FACTOR: f64 = 0.184;
+
+/// Hull-White one-factor short-rate model, dr = (θ(t) - a·r)dt + σ dW
+///
+/// θ(t) is implied by the discount curve passed to each method, so the model
+/// reprices the curve by construction; only the mean reversion a and the
+/// normal short-rate volatility σ are parameters. Zero-coupon bonds, bond
+/// options, caplets and European swaptions (by Jamshidian's decomposition)
+/// have closed forms; callable structures go through a trinomial tree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HullWhite {
+    /// Mean-reversion speed (a); zero gives Ho-Lee
+    pub mean_reversion: f64,
+    /// Normal volatility of the short rate (σ) in rate units
+    pub volatility: f64,
+}
+
+impl HullWhite {
+    pub fn new(mean_reversion: f64, volatility: f64) -> Result<Self, BlackScholesError> {
+        Ok(HullWhite {
+            mean_reversion: validation::non_negative("Mean reversion", mean_reversion)?,
+            volatility: validation::positive("Volatility", volatility)?,
+        })
+    }
+
+    /// B(t, T) = (1 - e^(-a(T - t))) / a, the bond's sensitivity to the short rate
+    pub fn b(&self, t: f64, maturity: f64) -> f64 {
+        let a = self.mean_reversion;
+        let tau = maturity - t;
+        if a < SMALL_MEAN_REVERSION {
+            tau
+        } else {
+            (1.0 - (-a * tau).exp()) / a
+        }
+    }
+
+    /// Variance of the short rate at `t`, (1 - e^(-2at)) / (2a) per unit σ²
+    fn variance_factor(&self, t: f64) -> f64 {
+        let a = self.mean_reversion;
+        if a < SMALL_MEAN_REVERSION {
+            t
+        } else {
+            (1.0 - (-2.0 * a * t).exp()) / (2.0 * a)
+        }
+    }
+
+    /// Variance of ∫₀^Δ x(u) du started from x(0) = 0, per unit σ²
+    fn integral_variance_factor(&self, dt: f64) -> f64 {
+        let a = self.mean_reversion;
+        if a < SMALL_MEAN_REVERSION {
+            dt.powi(3) / 3.0
+        } else {
+            (dt - 2.0 * self.b(0.0, dt) + self.variance_factor(dt)) / (a * a)
+        }
+    }
+
+    /// Bond price at `t` given the centred factor x = r(t) - α(t)
+    ///
+    /// α(t) = f(0, t) + σ²B(0, t)²/2 is the factor's drift-free offset, so
+    /// ln P(t, T) = ln(P(0, T) / P(0, t)) - B·x - σ²B·(B·V(t) + B(0, t)²)/2.
+    fn factor_bond(&self, curve: &dyn DiscountCurve, t: f64, maturity: f64, factor: f64) -> f64 {
+        let b = self.b(t, maturity);
+        let convexity = b * (b * self.variance_factor(t) + self.b(0.0, t).powi(2));
+        ((curve.df(maturity) / curve.df(t)).ln() - b * factor - 0.5 * self.volatility.powi(2) * convexity).exp()
+    }
+
+    /// Price at `t` of a zero-coupon bond maturing at `maturity`, given the short rate r(t)
+    ///
+    /// P(t, T) = A(t, T)·e^(-B(t, T)·r) with the curve's instantaneous
+    /// forward f(0, t) read off by central differences.
+    pub fn zero_coupon_bond(&self, curve: &dyn DiscountCurve, t: f64, maturity: f64, short_rate: f64) -> f64 {
+        let forward = if t > FORWARD_BUMP {
+            curve.forward_rate(t - FORWARD_BUMP, t + FORWARD_BUMP)
+        } else {
+            curve.forward_rate(t, t + FORWARD_BUMP)
+        };
+        let b = self.b(t, maturity);
+        let ln_a = (curve.df(maturity) / curve.df(t)).ln() + b * forward
+            - 0.5 * self.volatility.powi(2) * self.variance_factor(t) * b * b;
+        (ln_a - b * short_rate).exp()
+    }
+
+    /// European option expiring at `expiry` on a zero-coupon bond maturing at `maturity`
+    ///
+    /// # Arguments
+    /// * `curve` - Discount curve today
+    /// * `expiry` - Option expiry in years
+    /// * `maturity` - Bond maturity in years, after the expiry
+    /// * `strike` - Strike price per unit face value
+    /// * `option_type` - Call or Put on the bond
+    pub fn bond_option(
+        &self,
+        curve: &dyn DiscountCurve,
+        expiry: f64,
+        maturity: f64,
+        strike: f64,
+        option_type: OptionType,
+    ) -> Result<f64, BlackScholesError> {
+        let expiry = validation::positive("Option expiry", expiry)?;
+        validation::finite("Bond maturity", maturity)?;
+        if maturity <= expiry {
+            return Err(BlackScholesError::invalid("Bond must mature after the option expiry"));
+        }
+        let strike = validation::positive("Strike", strike)?;
+        let (bond, cash) = (curve.df(maturity), curve.df(expiry));
+        let s = self.volatility * self.b(expiry, maturity) * self.variance_factor(expiry).sqrt();
+        let h = (bond / (cash * strike)).ln() / s + 0.5 * s;
+        Ok(match option_type {
+            OptionType::Call => bond * norm_cdf(h) - strike * cash * norm_cdf(h - s),
+            OptionType::Put => strike * cash * norm_cdf(s - h) - bond * norm_cdf(-h),
+        })
+    }
+
+    /// Caplet (Call) or floorlet (Put) as a put or call on a zero-coupon bond
+    ///
+    /// A caplet paying τ·(L - K) at `end` is worth (1 + Kτ) puts expiring at
+    /// `start` on the bond maturing at `end`, struck at 1 / (1 + Kτ).
+    pub fn caplet(
+        &self,
+        curve: &dyn DiscountCurve,
+        caplet: &Caplet,
+        option_type: OptionType,
+    ) -> Result<f64, BlackScholesError> {
+        let scale = 1.0 + caplet.strike * (caplet.end - caplet.start);
+        if scale <= 0.0 {
+            return Err(BlackScholesError::invalid("Caplet strike implies a non-positive bond strike"));
+        }
+        let bond_type = match option_type {
+            OptionType::Call => OptionType::Put,
+            OptionType::Put => OptionType::Call,
+        };
+        let value = self.bond_option(curve, caplet.start, caplet.end, 1.0 / scale, bond_type)?;
+        Ok(caplet.notional * scale * value)
+    }
+
+    /// Cap (Call) or floor (Put) as the sum of its caplets
+    pub fn cap_floor(
+        &self,
+        curve: &dyn DiscountCurve,
+        cap: &CapFloor,
+        option_type: OptionType,
+    ) -> Result<f64, BlackScholesError> {
+        cap.caplets.iter().map(|c| self.caplet(curve, c, option_type)).sum()
+    }
+
+    /// European payer (Call) or receiver (Put) swaption by Jamshidian's decomposition
+    ///
+    /// The fixed leg is a coupon bond paying c_i = K·τ_i plus the notional at
+    /// maturity. At the short rate r* where it is worth par at expiry, the
+    /// swaption splits into zero-coupon bond options struck at P(T₀, t_i; r*).
+    pub fn swaption(
+        &self,
+        curve: &dyn DiscountCurve,
+        swaption: &Swaption,
+        option_type: OptionType,
+    ) -> Result<f64, BlackScholesError> {
+        let flows = fixed_leg(swaption.expiry, swaption.maturity, swaption.frequency, swaption.strike);
+        if flows.iter().any(|f| f.1 < 0.0) {
+            return Err(BlackScholesError::invalid("Jamshidian decomposition needs a non-negative strike"));
+        }
+        let expiry = swaption.expiry;
+        let excess = |r: f64| -> f64 {
+            flows.iter().map(|&(t, c)| c * self.zero_coupon_bond(curve, expiry, t, r)).sum::<f64>() - 1.0
+        };
+        // The coupon bond falls as the short rate rises
+        let (mut lo, mut hi) = CRITICAL_RATE_BRACKET;
+        if excess(lo) < 0.0 || excess(hi) > 0.0 {
+            return Err(BlackScholesError::no_convergence("Critical short rate outside its bracket"));
+        }
+        for _ in 0..CRITICAL_RATE_ITERATIONS {
+            let mid = 0.5 * (lo + hi);
+            if excess(mid) > 0.0 {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+            if hi - lo < 1e-15 {
+                break;
+            }
+        }
+        let critical = 0.5 * (lo + hi);
+
+        // A payer swaption is a put on the coupon bond struck at par
+        let bond_type = match option_type {
+            OptionType::Call => OptionType::Put,
+            OptionType::Put => OptionType::Call,
+        };
+        let mut value = 0.0;
+        for &(t, c) in &flows {
+            let strike = self.zero_coupon_bond(curve, expiry, t, critical);
+            value += c * self.bond_option(curve, expiry, t, strike, bond_type)?;
+        }
+        Ok(swaption.notional * value)
+    }
+
+    /// Bermudan payer (Call) or receiver (Put) swaption on a trinomial tree
+    ///
+    /// The holder may enter the remaining swap on any fixed-leg reset date
+    /// from `swaption.expiry` on. Dates are snapped to the nearest tree step,
+    /// so choose `steps` to put them on the grid.
+    pub fn bermudan_swaption(
+        &self,
+        curve: &dyn DiscountCurve,
+        swaption: &Swaption,
+        option_type: OptionType,
+        steps: usize,
+    ) -> Result<f64, BlackScholesError> {
+        let tree = self.tree(curve, swaption.maturity, steps)?;
+        let schedule = swap_schedule(swaption.expiry, swaption.maturity, swaption.frequency);
+        let exercise = &schedule[..schedule.len() - 1];
+        let flows = fixed_leg(swaption.expiry, swaption.maturity, swaption.frequency, swaption.strike);
+        let value = match option_type {
+            OptionType::Call => tree.exercisable(&flows, exercise, |bond| 1.0 - bond),
+            OptionType::Put => tree.exercisable(&flows, exercise, |bond| bond - 1.0),
+        };
+        Ok(swaption.notional * value.1)
+    }
+
+    /// Value of a callable bond on a trinomial tree
+    ///
+    /// The issuer's call is a Bermudan call on the straight bond, struck at
+    /// the call price, so the holder owns the bond less that option.
+    pub fn callable_bond(
+        &self,
+        curve: &dyn DiscountCurve,
+        bond: &CallableBond,
+        steps: usize,
+    ) -> Result<f64, BlackScholesError> {
+        let tree = self.tree(curve, bond.maturity, steps)?;
+        let flows = fixed_leg(0.0, bond.maturity, bond.frequency, bond.coupon);
+        let (straight, call) = tree.exercisable(&flows, &bond.call_dates, |value| value - bond.call_price);
+        Ok(bond.notional * (straight - call))
+    }
+
+    /// Trinomial tree of the short rate fitted to `curve` out to `horizon`
+    ///
+    /// Follows Hull and White (1994): nodes r = α_i + j·Δx with
+    /// Δx = σ√(3Δt), branching turned inwards at ±j_max, and α_i solved step
+    /// by step from Arrow-Debreu prices so that the tree reprices every
+    /// discount factor on its grid.
+    pub fn tree(
+        &self,
+        curve: &dyn DiscountCurve,
+        horizon: f64,
+        steps: usize,
+    ) -> Result<HullWhiteTree, BlackScholesError> {
+        let horizon = validation::positive("Tree horizon", horizon)?;
+        if steps == 0 {
+            return Err(BlackScholesError::invalid("Tree needs at least one step"));
+        }
+        let dt = horizon / steps as f64;
+        let j_max = if self.mean_reversion > 0.0 {
+            (TRUNCATION_FACTOR / (self.mean_reversion * dt)).ceil().min(steps as f64) as usize
+        } else {
+            steps
+        };
+        let mut tree = HullWhiteTree {
+            dt,
+            dx: self.volatility * (3.0 * dt).sqrt(),
+            mean_reversion: self.mean_reversion,
+            j_max,
+            alphas: Vec::with_capacity(steps),
+        };
+
+        // Arrow-Debreu prices of the nodes at the current step
+        let mut prices = vec![1.0];
+        for i in 0..steps {
+            let m = tree.half_width(i);
+            let sum: f64 = prices
+                .iter()
+                .enumerate()
+                .map(|(k, q)| q * (-(k as f64 - m as f64) * tree.dx * dt).exp())
+                .sum();
+            let alpha = (sum.ln() - curve.df((i + 1) as f64 * dt).ln()) / dt;
+            tree.alphas.push(alpha);
+
+            let next_m = tree.half_width(i + 1);
+            let mut next = vec![0.0; 2 * next_m + 1];
+            for (k, q) in prices.iter().enumerate() {
+                let j = k as i64 - m as i64;
+                let discounted = q * (-tree.short_rate(i, j) * dt).exp();
+                let (centre, probabilities) = tree.branches(j);
+                for (offset, p) in [1, 0, -1].iter().zip(probabilities) {
+                    next[(centre + offset + next_m as i64) as usize] += p * discounted;
+                }
+            }
+            prices = next;
+        }
+        Ok(tree)
+    }
+
+    /// Monte Carlo paths of the fixings on `tenors`, fitted to `curve`
+    pub fn fixings<'a>(
+        &self,
+        curve: &'a dyn DiscountCurve,
+        tenors: Vec<f64>,
+    ) -> Result<HullWhiteFixings<'a>, BlackScholesError> {
+        validate_tenors(&tenors)?;
+        Ok(HullWhiteFixings {
+            model: *self,
+            curve,
+            tenors,
+        })
+    }
+
+    /// Calibrate a and σ to caplet and swaption quotes
+    ///
+    /// Market prices come from each quote's Black or Bachelier volatility;
+    /// Nelder-Mead over ln a and ln σ minimizes squared relative price errors.
+    pub fn calibrate(curve: &dyn DiscountCurve, quotes: &[RateQuote]) -> Result<HullWhiteFit, BlackScholesError> {
+        if quotes.is_empty() {
+            return Err(BlackScholesError::invalid("Calibration needs at least one quote"));
+        }
+        let mut targets = Vec::with_capacity(quotes.len());
+        let mut normal_vol = 0.0;
+        for quote in quotes {
+            let (price, forward, vol) = match *quote {
+                RateQuote::Caplet { caplet, volatility } => (
+                    caplet.price(curve, volatility, OptionType::Call)?,
+                    caplet.forward(curve)?,
+                    volatility,
+                ),
+                RateQuote::Swaption { swaption, volatility } => (
+                    swaption.price(curve, volatility, OptionType::Call)?,
+                    swaption.forward(curve)?,
+                    volatility,
+                ),
+            };
+            if price <= 0.0 {
+                return Err(BlackScholesError::invalid("Quote has no option value to calibrate to"));
+            }
+            targets.push(price);
+            normal_vol += match vol {
+                RateVolatility::Normal(v) => v,
+                RateVolatility::Lognormal(v) => v * forward.abs(),
+                RateVolatility::ShiftedLognormal { volatility, shift } => volatility * (forward + shift).abs(),
+            };
+        }
+        normal_vol /= quotes.len() as f64;
+
+        let objective = |x: &[f64]| -> f64 {
+            let model = HullWhite {
+                mean_reversion: x[0].exp(),
+                volatility: x[1].exp(),
+            };
+            quotes
+                .iter()
+                .zip(&targets)
+                .map(|(q, target)| match model.quote_price(curve, q) {
+                    Ok(price) => (price / target - 1.0).powi(2),
+                    Err(_) => f64::INFINITY,
+                })
+                .sum()
+        };
+        let starts = [0.01_f64, 0.1]
+            .iter()
+            .map(|a| vec![a.ln(), normal_vol.max(1e-6).ln()])
+            .collect();
+        let mut search = SimplexSearch::new(starts, 0.3, 1e-16, 2000).with_restarts(2, 0.1);
+        search.advance(objective, usize::MAX);
+        let best = search
+            .best
+            .filter(|b| b.value.is_finite())
+            .ok_or_else(|| BlackScholesError::no_convergence("Hull-White calibration failed"))?;
+        let model = HullWhite {
+            mean_reversion: best.x[0].exp(),
+            volatility: best.x[1].exp(),
+        };
+
+        let errors = quotes
+            .iter()
+            .zip(&targets)
+            .map(|(q, target)| Ok((model.quote_price(curve, q)? / target - 1.0).abs()))
+            .collect::<Result<Vec<f64>, BlackScholesError>>()?;
+        Ok(HullWhiteFit {
+            model,
+            rmse: (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt(),
+            max_error: errors.iter().copied().fold(0.0, f64::max),
+        })
+    }
+
+    /// Model price of a calibration quote (caplet or payer swaption)
+    fn quote_price(&self, curve: &dyn DiscountCurve, quote: &RateQuote) -> Result<f64, BlackScholesError> {
+        match quote {
+            RateQuote::Caplet { caplet, .. } => self.caplet(curve, caplet, OptionType::Call),
+            RateQuote::Swaption { swaption, .. } => self.swaption(curve, swaption, OptionType::Call),
+        }
+    }
+}
+
+/// Fixed-leg cash flows per unit notional: coupon·τ_i at each date plus par at maturity
+fn fixed_leg(start: f64, maturity: f64, frequency: u32, coupon: f64) -> Vec<(f64, f64)> {
+    let mut flows: Vec<(f64, f64)> = swap_schedule(start, maturity, frequency)
+        .windows(2)
+        .map(|w| (w[1], coupon * (w[1] - w[0])))
+        .collect();
+    if let Some(last) = flows.last_mut() {
+        last.1 += 1.0;
+    }
+    flows
+}
+
+/// Option quote a Hull-White model is calibrated to, priced as a caplet or payer swaption
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RateQuote {
+    Caplet { caplet: Caplet, volatility: RateVolatility },
+    Swaption { swaption: Swaption, volatility: RateVolatility },
+}
+
+/// Result of calibrating a Hull-White model
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HullWhiteFit {
+    /// The calibrated model
+    pub model: HullWhite,
+    /// Root-mean-square relative price error over the quotes
+    pub rmse: f64,
+    /// Largest relative price error over the quotes
+    pub max_error: f64,
+}
+
+/// Fixed-rate bond the issuer may redeem early
+///
+/// Coupons are paid `frequency` times a year until `maturity`. On each call
+/// date, after that date's coupon, the issuer may redeem at `call_price` per
+/// unit face value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallableBond {
+    /// Annual coupon rate as decimal
+    pub coupon: f64,
+    pub frequency: u32,
+    /// Final maturity in years
+    pub maturity: f64,
+    /// Issuer call dates in years
+    pub call_dates: Vec<f64>,
+    /// Redemption price per unit face value
+    pub call_price: f64,
+    pub notional: f64,
+}
+
+impl CallableBond {
+    pub fn new(
+        coupon: f64,
+        frequency: u32,
+        maturity: f64,
+        call_dates: Vec<f64>,
+        call_price: f64,
+        notional: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let maturity = validation::positive("Bond maturity", maturity)?;
+        if frequency == 0 {
+            return Err(BlackScholesError::invalid("Bond needs at least one coupon a year"));
+        }
+        for &t in &validation::all_finite("Call date", &call_dates)? {
+            if t <= 0.0 || t >= maturity {
+                return Err(BlackScholesError::invalid("Call dates must fall between today and maturity"));
+            }
+        }
+        Ok(CallableBond {
+            coupon: validation::finite("Coupon", coupon)?,
+            frequency,
+            maturity,
+            call_dates,
+            call_price: validation::positive("Call price", call_price)?,
+            notional: validation::positive("Notional", notional)?,
+        })
+    }
+}
+
+/// Hull-White trinomial tree fitted to a discount curve
+///
+/// Node (i, j) sits at time i·Δt with short rate α_i + j·Δx, the
+/// continuously compounded rate over the next step, for |j| ≤ min(i, j_max).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HullWhiteTree {
+    /// Step length in years
+    pub dt: f64,
+    /// Rate spacing between neighbouring nodes
+    pub dx: f64,
+    pub mean_reversion: f64,
+    /// Largest node index before branching turns inwards
+    pub j_max: usize,
+    /// Curve-fitting displacement of each step
+    pub alphas: Vec<f64>,
+}
+
+impl HullWhiteTree {
+    /// Number of time steps
+    pub fn steps(&self) -> usize {
+        self.alphas.len()
+    }
+
+    /// Largest |j| at step `i`
+    pub fn half_width(&self, i: usize) -> usize {
+        i.min(self.j_max)
+    }
+
+    /// Step nearest to time `t`
+    pub fn step_at(&self, t: f64) -> usize {
+        ((t / self.dt).round().max(0.0) as usize).min(self.steps())
+    }
+
+    /// Short rate at node (i, j) for the step after it
+    pub fn short_rate(&self, i: usize, j: i64) -> f64 {
+        self.alphas[i] + j as f64 * self.dx
+    }
+
+    /// Centre node reached from j and the up, middle and down probabilities
+    fn branches(&self, j: i64) -> (i64, [f64; 3]) {
+        let m = -self.mean_reversion * self.dt;
+        let (jf, j_max) = (j as f64, self.j_max as i64);
+        let jm2 = jf * jf * m * m;
+        if j == j_max && j > 0 {
+            let up = 7.0 / 6.0 + 0.5 * (jm2 + 3.0 * jf * m);
+            (j - 1, [up, -1.0 / 3.0 - jm2 - 2.0 * jf * m, 1.0 / 6.0 + 0.5 * (jm2 + jf * m)])
+        } else if j == -j_max && j < 0 {
+            let down = 7.0 / 6.0 + 0.5 * (jm2 - 3.0 * jf * m);
+            (j + 1, [1.0 / 6.0 + 0.5 * (jm2 - jf * m), -1.0 / 3.0 - jm2 + 2.0 * jf * m, down])
+        } else {
+            (j, [1.0 / 6.0 + 0.5 * (jm2 + jf * m), 2.0 / 3.0 - jm2, 1.0 / 6.0 + 0.5 * (jm2 - jf * m)])
+        }
+    }
+
+    /// Discounted expectation at step `i` of `next`, the node values at step i + 1
+    pub fn roll_back(&self, i: usize, next: &[f64]) -> Vec<f64> {
+        let (m, next_m) = (self.half_width(i) as i64, self.half_width(i + 1) as i64);
+        (-m..=m)
+            .map(|j| {
+                let (centre, probabilities) = self.branches(j);
+                let expected: f64 = [1, 0, -1]
+                    .iter()
+                    .zip(probabilities)
+                    .map(|(offset, p)| p * next[(centre + offset + next_m) as usize])
+                    .sum();
+                (-self.short_rate(i, j) * self.dt).exp() * expected
+            })
+            .collect()
+    }
+
+    /// Today's values of a fixed-flow bond and of a Bermudan option on it
+    ///
+    /// `flows` are `(time, amount)` pairs; on each `exercise` date the option
+    /// pays `payoff(bond)` on the bond value excluding that date's flow.
+    fn exercisable<F: Fn(f64) -> f64>(&self, flows: &[(f64, f64)], exercise: &[f64], payoff: F) -> (f64, f64) {
+        let n = self.steps();
+        let mut amounts = vec![0.0; n + 1];
+        for &(t, amount) in flows {
+            amounts[self.step_at(t)] += amount;
+        }
+        let mut exercisable = vec![false; n + 1];
+        for &t in exercise {
+            exercisable[self.step_at(t)] = true;
+        }
+
+        let width = 2 * self.half_width(n) + 1;
+        let mut bond = vec![amounts[n]; width];
+        let mut option = vec![0.0; width];
+        for i in (0..n).rev() {
+            bond = self.roll_back(i, &bond);
+            option = self.roll_back(i, &option);
+            if exercisable[i] {
+                for (v, &b) in option.iter_mut().zip(&bond) {
+                    *v = v.max(payoff(b));
+                }
+            }
+            for b in bond.iter_mut() {
+                *b += amounts[i];
+            }
+        }
+        (bond[0], option[0])
+    }
+}
+
+/// Hull-White fixings of simple forwards on a tenor schedule
+///
+/// The factor x = r - α(t) and its time integral are jointly Gaussian, so
+/// both are sampled exactly from one tenor date to the next under the
+/// risk-neutral measure; each fixing is read off the model's bond price and
+/// each cashflow deflated by the simulated bank account.
+#[derive(Clone)]
+pub struct HullWhiteFixings<'a> {
+    pub model: HullWhite,
+    pub curve: &'a dyn DiscountCurve,
+    /// Tenor dates T_0 < T_1 < ... < T_n in years, the first in the future
+    pub tenors: Vec<f64>,
+}
+
+impl FixingModel for HullWhiteFixings<'_> {
+    fn tenors(&self) -> &[f64] {
+        &self.tenors
+    }
+
+    fn price_fixings<F>(&self, mc: &MonteCarlo, mut payoff: F) -> Result<McResult, BlackScholesError>
+    where
+        F: FnMut(&[f64], &mut [f64]),
+    {
+        let model = &self.model;
+        let variance = model.volatility.powi(2);
+        let n = self.tenors.len() - 1;
+        let (mut fixings, mut deflators, mut cashflows) = (vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+        Ok(mc.estimate(1.0, |rng| {
+            let (mut factor, mut integral, mut previous) = (0.0, 0.0, 0.0);
+            for (k, &t) in self.tenors.iter().enumerate() {
+                let dt = t - previous;
+                let factor_variance = variance * model.variance_factor(dt);
+                let integral_variance = variance * model.integral_variance_factor(dt);
+                let covariance = 0.5 * variance * model.b(0.0, dt).powi(2);
+                let (z1, z2) = (rng.normal(), rng.normal());
+                let beta = covariance / factor_variance.sqrt();
+                let residual = (integral_variance - beta * beta).max(0.0).sqrt();
+                integral += factor * model.b(0.0, dt) + beta * z1 + residual * z2;
+                factor = factor * (1.0 - model.mean_reversion * model.b(0.0, dt)) + factor_variance.sqrt() * z1;
+                previous = t;
+
+                if k < n {
+                    let bond = model.factor_bond(self.curve, t, self.tenors[k + 1], factor);
+                    fixings[k] = (1.0 / bond - 1.0) / (self.tenors[k + 1] - t);
+                }
+                if k > 0 {
+                    let convexity = 0.5 * variance * model.integral_variance_factor(t);
+                    deflators[k - 1] = self.curve.df(t) * (-integral - convexity).exp();
+                }
+            }
+            cashflows.iter_mut().for_each(|c| *c = 0.0);
+            payoff(&fixings, &mut cashflows);
+            cashflows.iter().zip(&deflators).map(|(c, d)| c * d).sum()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::{InterpolatedCurve, Interpolation};
+    use crate::rates::par_swap_rate;
+
+    fn curve() -> InterpolatedCurve {
+        InterpolatedCurve::from_zero_rates(&[(1.0, 0.03), (2.0, 0.035), (5.0, 0.04), (10.0, 0.042)], Interpolation::LogLinear)
+            .unwrap()
+    }
+
+    fn model() -> HullWhite {
+        HullWhite::new(0.05, 0.01).unwrap()
+    }
+
+    #[test]
+    fn test_bonds_reprice_the_curve_and_bond_option_parity() {
+        let (curve, hw) = (curve(), model());
+        // Today's bond at today's short rate is the curve's discount factor
+        let r0 = curve.forward_rate(0.0, FORWARD_BUMP);
+        assert!((hw.zero_coupon_bond(&curve, 0.0, 4.0, r0) - curve.df(4.0)).abs() < 1e-6);
+        // Call - put = P(0, S) - K·P(0, T)
+        let call = hw.bond_option(&curve, 2.0, 5.0, 0.9, OptionType::Call).unwrap();
+        let put = hw.bond_option(&curve, 2.0, 5.0, 0.9, OptionType::Put).unwrap();
+        assert!((call - put - (curve.df(5.0) - 0.9 * curve.df(2.0))).abs() < 1e-14);
+        assert!(hw.bond_option(&curve, 5.0, 2.0, 0.9, OptionType::Call).is_err());
+        assert!(HullWhite::new(-0.1, 0.01).is_err());
+
+        // The tree reprices every discount factor on its grid
+        let tree = hw.tree(&curve, 10.0, 40).unwrap();
+        let mut values = vec![1.0; 2 * tree.half_width(40) + 1];
+        for i in (0..40).rev() {
+            values = tree.roll_back(i, &values);
+        }
+        assert!((values[0] - curve.df(10.0)).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_caps_and_swaptions_close_to_black_at_matched_vol() {
+        let (curve, hw) = (curve(), model());
+        // With normal short-rate vol and modest mean reversion, one-period
+        // rate vols are close to σ·B(t, T)/τ ≈ σ
+        let caplet = Caplet::new(2.0, 2.5, 0.04, 1e6).unwrap();
+        let hw_value = hw.caplet(&curve, &caplet, OptionType::Call).unwrap();
+        let normal = caplet.price(&curve, RateVolatility::Normal(0.01), OptionType::Call).unwrap();
+        assert!((hw_value / normal - 1.0).abs() < 0.05, "{hw_value} vs {normal}");
+        let floor = hw.caplet(&curve, &caplet, OptionType::Put).unwrap();
+        let swap = 1e6 * 0.5 * curve.df(2.5) * (caplet.forward(&curve).unwrap() - 0.04);
+        assert!((hw_value - floor - swap).abs() < 1e-8);
+
+        let atm = par_swap_rate(&curve, 2.0, 7.0, 1).unwrap();
+        let swaption = Swaption::new(2.0, 7.0, 1, atm, 1e6).unwrap();
+        let payer = hw.swaption(&curve, &swaption, OptionType::Call).unwrap();
+        let receiver = hw.swaption(&curve, &swaption, OptionType::Put).unwrap();
+        assert!(payer > 0.0 && (payer - receiver).abs() < 1e-6 * payer);
+    }
+
+    #[test]
+    fn test_tree_matches_jamshidian_and_bermudan_exceeds_european() {
+        let (curve, hw) = (curve(), model());
+        let atm = par_swap_rate(&curve, 1.0, 6.0, 1).unwrap();
+        let swaption = Swaption::new(1.0, 6.0, 1, atm, 1.0).unwrap();
+        let tree = hw.tree(&curve, 6.0, 240).unwrap();
+        let flows = fixed_leg(1.0, 6.0, 1, atm);
+        for (option_type, payoff) in [(OptionType::Call, 1.0), (OptionType::Put, -1.0)] {
+            let european = tree.exercisable(&flows, &[1.0], |bond| payoff * (1.0 - bond)).1;
+            let analytic = hw.swaption(&curve, &swaption, option_type).unwrap();
+            assert!((european / analytic - 1.0).abs() < 0.01, "{european} vs {analytic}");
+            let bermudan = hw.bermudan_swaption(&curve, &swaption, option_type, 240).unwrap();
+            assert!(bermudan > european * 1.05, "{bermudan} vs {european}");
+        }
+    }
+
+    #[test]
+    fn test_callable_bond_worth_less_than_straight() {
+        let (curve, hw) = (curve(), model());
+        let bond = CallableBond::new(0.05, 2, 10.0, (2..10).map(f64::from).collect(), 1.0, 100.0).unwrap();
+        let callable = hw.callable_bond(&curve, &bond, 200).unwrap();
+        let flows = fixed_leg(0.0, 10.0, 2, 0.05);
+        let straight: f64 = 100.0 * flows.iter().map(|&(t, c)| c * curve.df(t)).sum::<f64>();
+        assert!(callable < straight - 1.0, "{callable} vs {straight}");
+        // A call far out of the money is worthless
+        let never = CallableBond { call_price: 10.0, ..bond.clone() };
+        assert!((hw.callable_bond(&curve, &never, 200).unwrap() - straight).abs() < 1e-8);
+        assert!(CallableBond::new(0.05, 2, 10.0, vec![12.0], 1.0, 100.0).is_err());
+    }
+
+    #[test]
+    fn test_simulated_fixings_match_closed_forms() {
+        let curve = curve();
+        let model = HullWhite::new(0.05, 0.012).unwrap();
+        let tenors: Vec<f64> = (0..=8).map(|i| 1.0 + 0.5 * i as f64).collect();
+        let paths = model.fixings(&curve, tenors).unwrap();
+        let mc = MonteCarlo::new(40_000, 9).unwrap();
+        let bond = paths.price_fixings(&mc, |_, cashflows| cashflows[7] = 1.0).unwrap();
+        assert!((bond.price - curve.df(5.0)).abs() < 3.5 * bond.std_error, "{bond:?}");
+
+        let caplet = Caplet::new(3.0, 3.5, 0.045, 1.0).unwrap();
+        let exact = model.caplet(&curve, &caplet, OptionType::Call).unwrap();
+        let simulated = paths
+            .price_fixings(&mc, |fixings, cashflows| cashflows[4] = 0.5 * (fixings[4] - 0.045).max(0.0))
+            .unwrap();
+        assert!((simulated.price - exact).abs() < 3.5 * simulated.std_error, "{simulated:?} vs {exact}");
+        assert!(model.fixings(&curve, vec![1.0]).is_err());
+    }
+
+    #[test]
+    fn test_calibration_recovers_parameters() {
+        let curve = curve();
+        let truth = HullWhite::new(0.08, 0.012).unwrap();
+        // Quote each instrument at the normal vol that reproduces the true model price
+        let implied_normal = |price: &dyn Fn(f64) -> f64, target: f64| {
+            let (mut lo, mut hi) = (1e-5, 0.1);
+            for _ in 0..200 {
+                let mid = 0.5 * (lo + hi);
+                if price(mid) < target {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            RateVolatility::Normal(0.5 * (lo + hi))
+        };
+        let mut quotes = Vec::new();
+        for (expiry, maturity) in [(1.0, 6.0), (5.0, 10.0), (2.0, 3.0)] {
+            let atm = par_swap_rate(&curve, expiry, maturity, 1).unwrap();
+            let swaption = Swaption::new(expiry, maturity, 1, atm, 1.0).unwrap();
+            let target = truth.swaption(&curve, &swaption, OptionType::Call).unwrap();
+            let price = |v: f64| swaption.price(&curve, RateVolatility::Normal(v), OptionType::Call).unwrap();
+            quotes.push(RateQuote::Swaption {
+                swaption,
+                volatility: implied_normal(&price, target),
+            });
+        }
+        let caplet = Caplet::new(3.0, 3.5, 0.045, 1.0).unwrap();
+        let target = truth.caplet(&curve, &caplet, OptionType::Call).unwrap();
+        let price = |v: f64| caplet.price(&curve, RateVolatility::Normal(v), OptionType::Call).unwrap();
+        quotes.push(RateQuote::Caplet {
+            caplet,
+            volatility: implied_normal(&price, target),
+        });
+
+        let fit = HullWhite::calibrate(&curve, &quotes).unwrap();
+        assert!(fit.max_error < 1e-6, "{fit:?}");
+        assert!((fit.model.mean_reversion - 0.08).abs() < 1e-3);
+        assert!((fit.model.volatility - 0.012).abs() < 1e-5);
+        assert!(HullWhite::calibrate(&curve, &[]).is_err());
+    }
+}
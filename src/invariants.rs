@@ -0,0 +1,314 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Default absolute price tolerance before a violation is reported
+const DEFAULT_TOLERANCE: f64 = 1e-9;
+
+/// Expected direction of a price as one input increases
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Monotonicity {
+    Increasing,
+    Decreasing,
+    /// No expectation; the dimension is not checked
+    Any,
+}
+
+/// No-arbitrage shape a pricer is expected to respect
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Expectations {
+    pub spot: Monotonicity,
+    pub strike: Monotonicity,
+    pub volatility: Monotonicity,
+    /// Whether prices must be convex in strike (butterflies non-negative)
+    pub strike_convex: bool,
+}
+
+impl Expectations {
+    /// Shape of a European vanilla: monotone in spot and strike, increasing in vol, convex in strike
+    pub fn vanilla(option_type: OptionType) -> Self {
+        let (spot, strike) = match option_type {
+            OptionType::Call => (Monotonicity::Increasing, Monotonicity::Decreasing),
+            OptionType::Put => (Monotonicity::Decreasing, Monotonicity::Increasing),
+        };
+        Expectations {
+            spot,
+            strike,
+            volatility: Monotonicity::Increasing,
+            strike_convex: true,
+        }
+    }
+}
+
+/// Property that was violated
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Property {
+    SpotMonotonicity,
+    StrikeMonotonicity,
+    VolatilityMonotonicity,
+    StrikeConvexity,
+}
+
+/// One failed check
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Violation {
+    pub property: Property,
+    /// Spot, strike and volatility at the start of the failing step
+    pub spot: f64,
+    pub strike: f64,
+    pub volatility: f64,
+    /// Size of the violation in price units (always positive)
+    pub amount: f64,
+}
+
+/// Outcome of an invariant probe
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InvariantReport {
+    /// Number of individual comparisons performed
+    pub checks: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl InvariantReport {
+    /// True when no check failed
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Violations of one property
+    pub fn of(&self, property: Property) -> impl Iterator<Item = &Violation> {
+        self.violations.iter().filter(move |v| v.property == property)
+    }
+}
+
+/// Spot, strike and volatility points at which a pricer is probed
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProbeGrid {
+    pub spots: Vec<f64>,
+    pub strikes: Vec<f64>,
+    pub volatilities: Vec<f64>,
+    /// Absolute price tolerance before a violation is reported
+    pub tolerance: f64,
+}
+
+impl ProbeGrid {
+    /// Create a grid from explicit points (sorted internally)
+    pub fn new(
+        spots: Vec<f64>,
+        strikes: Vec<f64>,
+        volatilities: Vec<f64>,
+        tolerance: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let sorted = |field: &'static str, values: Vec<f64>| -> Result<Vec<f64>, BlackScholesError> {
+            let mut values = values
+                .into_iter()
+                .map(|v| validation::positive(field, v))
+                .collect::<Result<Vec<_>, _>>()?;
+            if values.is_empty() {
+                return Err(BlackScholesError::invalid("Probe grid dimensions must not be empty"));
+            }
+            values.sort_by(|a, b| a.total_cmp(b));
+            Ok(values)
+        };
+        Ok(ProbeGrid {
+            spots: sorted("Spot price", spots)?,
+            strikes: sorted("Strike price", strikes)?,
+            volatilities: sorted("Volatility", volatilities)?,
+            tolerance: validation::non_negative("Tolerance", tolerance)?,
+        })
+    }
+
+    /// Evenly spaced grid around a model: spot and strike from 50% to 150%, vol from 25% to 200%
+    pub fn around(model: &BlackScholes, points: usize) -> Result<Self, BlackScholesError> {
+        if points < 3 {
+            return Err(BlackScholesError::invalid("Probe grid needs at least three points per dimension"));
+        }
+        let span = |lo: f64, hi: f64| -> Vec<f64> {
+            (0..points)
+                .map(|i| lo + (hi - lo) * i as f64 / (points - 1) as f64)
+                .collect()
+        };
+        ProbeGrid::new(
+            span(0.5 * model.spot_price, 1.5 * model.spot_price),
+            span(0.5 * model.strike_price, 1.5 * model.strike_price),
+            span(0.25 * model.volatility, 2.0 * model.volatility),
+            DEFAULT_TOLERANCE,
+        )
+    }
+}
+
+/// Probe a pricer for monotonicity and strike convexity over a grid
+///
+/// The pricer is called with copies of `base` whose spot, strike and
+/// volatility are set to each grid point; all other fields are kept.
+///
+/// # Arguments
+/// * `base` - Market parameters shared by all probe points
+/// * `grid` - Points to probe
+/// * `expectations` - Shape the pricer should respect
+/// * `pricer` - Function pricing the instrument under a given model
+pub fn probe<F: Fn(&BlackScholes) -> f64>(
+    base: &BlackScholes,
+    grid: &ProbeGrid,
+    expectations: &Expectations,
+    pricer: F,
+) -> InvariantReport {
+    let price = |spot: f64, strike: f64, volatility: f64| {
+        let mut m = *base;
+        m.spot_price = spot;
+        m.strike_price = strike;
+        m.volatility = volatility;
+        pricer(&m)
+    };
+    let mut report = InvariantReport {
+        checks: 0,
+        violations: Vec::new(),
+    };
+    let tol = grid.tolerance;
+
+    for &strike in &grid.strikes {
+        for &vol in &grid.volatilities {
+            let points: Vec<_> = grid.spots.iter().map(|&s| (s, strike, vol)).collect();
+            let values: Vec<f64> = points.iter().map(|p| price(p.0, p.1, p.2)).collect();
+            check_line(&mut report, Property::SpotMonotonicity, expectations.spot, &points, &values, tol);
+        }
+    }
+
+    for &spot in &grid.spots {
+        for &strike in &grid.strikes {
+            let points: Vec<_> = grid.volatilities.iter().map(|&v| (spot, strike, v)).collect();
+            let values: Vec<f64> = points.iter().map(|p| price(p.0, p.1, p.2)).collect();
+            check_line(
+                &mut report,
+                Property::VolatilityMonotonicity,
+                expectations.volatility,
+                &points,
+                &values,
+                tol,
+            );
+        }
+    }
+
+    for &spot in &grid.spots {
+        for &vol in &grid.volatilities {
+            let points: Vec<_> = grid.strikes.iter().map(|&k| (spot, k, vol)).collect();
+            let values: Vec<f64> = points.iter().map(|p| price(p.0, p.1, p.2)).collect();
+            check_line(&mut report, Property::StrikeMonotonicity, expectations.strike, &points, &values, tol);
+
+            if expectations.strike_convex {
+                for i in 1..points.len().saturating_sub(1) {
+                    report.checks += 1;
+                    let (k0, k1, k2) = (points[i - 1].1, points[i].1, points[i + 1].1);
+                    // Non-uniform butterfly: interpolated value minus the middle price
+                    let w = (k2 - k1) / (k2 - k0);
+                    let shortfall = values[i] - (w * values[i - 1] + (1.0 - w) * values[i + 1]);
+                    if shortfall > tol {
+                        report.violations.push(Violation {
+                            property: Property::StrikeConvexity,
+                            spot,
+                            strike: k1,
+                            volatility: vol,
+                            amount: shortfall,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Check monotonicity along one axis: `values` are prices at increasing inputs
+fn check_line(
+    report: &mut InvariantReport,
+    property: Property,
+    direction: Monotonicity,
+    points: &[(f64, f64, f64)],
+    values: &[f64],
+    tol: f64,
+) {
+    let sign = match direction {
+        Monotonicity::Increasing => 1.0,
+        Monotonicity::Decreasing => -1.0,
+        Monotonicity::Any => return,
+    };
+    for i in 1..values.len() {
+        report.checks += 1;
+        let shortfall = -sign * (values[i] - values[i - 1]);
+        if shortfall > tol || !values[i].is_finite() {
+            let (spot, strike, volatility) = points[i - 1];
+            report.violations.push(Violation {
+                property,
+                spot,
+                strike,
+                volatility,
+                amount: shortfall,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digital::{DigitalOption, DigitalPayoff};
+
+    fn model() -> BlackScholes {
+        BlackScholes::new(100.0, 100.0, 0.5, 0.03, 0.2, 0.01).unwrap()
+    }
+
+    #[test]
+    fn test_vanilla_pricer_is_clean() {
+        let bs = model();
+        let grid = ProbeGrid::around(&bs, 9).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let report = probe(&bs, &grid, &Expectations::vanilla(option_type), |m| m.price(option_type));
+            assert!(report.is_clean(), "{:?}", report.violations.first());
+            assert_eq!(report.checks, 3 * 9 * 9 * 8 + 9 * 9 * 7);
+        }
+    }
+
+    #[test]
+    fn test_digital_fails_strike_convexity() {
+        let bs = model();
+        let grid = ProbeGrid::around(&bs, 9).unwrap();
+        let digital = |m: &BlackScholes| {
+            DigitalOption::new(*m, DigitalPayoff::CashOrNothing { cash: 1.0 })
+                .unwrap()
+                .price(OptionType::Call)
+        };
+        let expectations = Expectations {
+            volatility: Monotonicity::Any,
+            ..Expectations::vanilla(OptionType::Call)
+        };
+        let report = probe(&bs, &grid, &expectations, digital);
+
+        assert_eq!(report.of(Property::SpotMonotonicity).count(), 0);
+        assert_eq!(report.of(Property::StrikeMonotonicity).count(), 0);
+        assert!(report.of(Property::StrikeConvexity).count() > 0);
+    }
+
+    #[test]
+    fn test_detects_sign_bug() {
+        let bs = model();
+        let grid = ProbeGrid::around(&bs, 5).unwrap();
+        // A put priced with the call formula rises in spot
+        let report = probe(&bs, &grid, &Expectations::vanilla(OptionType::Put), |m| m.price(OptionType::Call));
+        assert!(report.of(Property::SpotMonotonicity).all(|v| v.amount > 0.0));
+        assert!(report.of(Property::SpotMonotonicity).count() > 0);
+        assert!(report.of(Property::StrikeMonotonicity).count() > 0);
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        assert!(ProbeGrid::new(vec![], vec![100.0], vec![0.2], 0.0).is_err());
+        assert!(ProbeGrid::new(vec![100.0], vec![f64::NAN], vec![0.2], 0.0).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::characteristic::CharacteristicFunction;
+use crate::error::BlackScholesError;
+use crate::math::{Complex, Rng};
+use crate::model::EuropeanModel;
+use crate::monte_carlo::PathModel;
+use crate::validation;
+
+/// Poisson weight below which the Merton series is truncated
+const SERIES_TOLERANCE: f64 = 1e-14;
+
+/// Hard cap on the number of series terms
+const MAX_SERIES_TERMS: usize = 500;
+
+/// Merton (1976) jump-diffusion: geometric Brownian motion plus lognormal jumps
+///
+/// Jumps arrive at Poisson rate λ and multiply the spot by e^J with
+/// J ~ N(μ_J, σ_J²). The drift is compensated so the discounted spot stays
+/// a martingale.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MertonJumpDiffusion {
+    /// Diffusion parameters, strike and expiry
+    pub model: BlackScholes,
+    /// Expected number of jumps per year (λ)
+    pub jump_intensity: f64,
+    /// Mean of the log jump size (μ_J)
+    pub jump_mean: f64,
+    /// Standard deviation of the log jump size (σ_J)
+    pub jump_volatility: f64,
+}
+
+impl MertonJumpDiffusion {
+    /// Create a new jump-diffusion model
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes parameters for the diffusive part
+    /// * `jump_intensity` - Jump arrival rate per year (λ ≥ 0)
+    /// * `jump_mean` - Mean log jump size (μ_J)
+    /// * `jump_volatility` - Log jump size volatility (σ_J ≥ 0)
+    pub fn new(
+        model: BlackScholes,
+        jump_intensity: f64,
+        jump_mean: f64,
+        jump_volatility: f64,
+    ) -> Result<Self, BlackScholesError> {
+        Ok(MertonJumpDiffusion {
+            model,
+            jump_intensity: validation::non_negative("Jump intensity", jump_intensity)?,
+            jump_mean: validation::finite("Jump mean", jump_mean)?,
+            jump_volatility: validation::non_negative("Jump volatility", jump_volatility)?,
+        })
+    }
+
+    /// Expected relative jump size k = E[e^J] - 1
+    pub fn mean_jump(&self) -> f64 {
+        (self.jump_mean + 0.5 * self.jump_volatility.powi(2)).exp() - 1.0
+    }
+
+    /// Calculate option price with Merton's Poisson-weighted Black-Scholes series
+    ///
+    /// Term n is a Black-Scholes price with σ_n² = σ² + n·σ_J²/T and
+    /// r_n = r - λk + n·ln(1 + k)/T, weighted by Poisson(λ(1 + k)T).
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        let m = &self.model;
+        let t = m.time_to_expiry;
+        let sigma = m.calendar_volatility();
+        let k = self.mean_jump();
+        let intensity = self.jump_intensity * (1.0 + k) * t;
+        let log_growth = (1.0 + k).ln();
+
+        let mut total = 0.0;
+        let mut weight = (-intensity).exp();
+        for n in 0..MAX_SERIES_TERMS {
+            if n > 0 {
+                weight *= intensity / n as f64;
+            }
+            let nf = n as f64;
+            let term = BlackScholes {
+                risk_free_rate: m.risk_free_rate - self.jump_intensity * k + nf * log_growth / t,
+                volatility: (sigma * sigma + nf * self.jump_volatility.powi(2) / t).sqrt(),
+                vol_time: t,
+                ..*m
+            };
+            total += weight * term.price(option_type);
+            if nf > intensity && weight < SERIES_TOLERANCE {
+                break;
+            }
+        }
+        total
+    }
+
+    /// Calculate all Greeks by finite differences on the diffusive parameters
+    pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        Greeks::from_finite_differences(&self.model, |m| MertonJumpDiffusion { model: *m, ..*self }.price(option_type))
+    }
+}
+
+impl CharacteristicFunction for MertonJumpDiffusion {
+    /// Gaussian diffusion plus compound Poisson lognormal jumps
+    fn char_fn(&self, u: Complex, t: f64) -> Complex {
+        let sigma = self.model.calendar_volatility();
+        let drift = (self.model.risk_free_rate
+            - self.model.dividend_yield
+            - 0.5 * sigma * sigma
+            - self.jump_intensity * self.mean_jump())
+            * t;
+        let jump = (Complex::I * u * self.jump_mean - 0.5 * self.jump_volatility.powi(2) * u * u).exp() - 1.0;
+        (Complex::I * u * drift - 0.5 * sigma * sigma * t * u * u + self.jump_intensity * t * jump).exp()
+    }
+}
+
+impl PathModel for MertonJumpDiffusion {
+    fn initial_spot(&self) -> f64 {
+        self.model.spot_price
+    }
+
+    /// Exact diffusion steps with a Poisson number of lognormal jumps per step
+    fn simulate_path(&self, times: &[f64], rng: &mut Rng, path: &mut [f64]) {
+        let m = &self.model;
+        let vol = m.calendar_volatility();
+        let drift = m.risk_free_rate - m.dividend_yield - 0.5 * vol * vol - self.jump_intensity * self.mean_jump();
+        let mut log_spot = m.spot_price.ln();
+        let mut previous = 0.0;
+        for (t, s) in times.iter().zip(path.iter_mut()) {
+            let dt = t - previous;
+            log_spot += drift * dt + vol * dt.sqrt() * rng.normal();
+            let jumps = rng.poisson(self.jump_intensity * dt);
+            if jumps > 0 {
+                let n = jumps as f64;
+                log_spot += n * self.jump_mean + self.jump_volatility * n.sqrt() * rng.normal();
+            }
+            *s = log_spot.exp();
+            previous = *t;
+        }
+    }
+}
+
+impl EuropeanModel for MertonJumpDiffusion {
+    fn spot(&self) -> f64 {
+        self.model.spot_price
+    }
+
+    fn rate(&self, _expiry: f64) -> f64 {
+        self.model.risk_free_rate
+    }
+
+    fn dividend_yield(&self, _expiry: f64) -> f64 {
+        self.model.dividend_yield
+    }
+
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        let vol = self.model.calendar_volatility();
+        let model = BlackScholes {
+            strike_price: strike,
+            time_to_expiry: expiry,
+            vol_time: expiry,
+            volatility: vol,
+            ..self.model
+        };
+        MertonJumpDiffusion { model, ..*self }.price(option_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monte_carlo::MonteCarlo;
+
+    fn merton() -> MertonJumpDiffusion {
+        let bs = BlackScholes::new(100.0, 100.0, 0.5, 0.05, 0.2, 0.01).unwrap();
+        MertonJumpDiffusion::new(bs, 1.0, -0.1, 0.15).unwrap()
+    }
+
+    #[test]
+    fn test_no_jumps_reduces_to_black_scholes() {
+        let bs = BlackScholes::new(100.0, 110.0, 1.0, 0.05, 0.25, 0.02).unwrap();
+        let merton = MertonJumpDiffusion::new(bs, 0.0, -0.2, 0.3).unwrap();
+        assert!((merton.price(OptionType::Call) - bs.price(OptionType::Call)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_put_call_parity_and_fat_tails() {
+        let m = merton();
+        let call = m.price(OptionType::Call);
+        let put = m.price(OptionType::Put);
+        let parity = 100.0 * (-0.01_f64 * 0.5).exp() - 100.0 * (-0.05_f64 * 0.5).exp();
+        assert!((call - put - parity).abs() < 1e-10);
+
+        // Negative jumps fatten the left tail: deep OTM puts are worth far more than under GBM
+        let mut otm = m;
+        otm.model.strike_price = 70.0;
+        assert!(otm.price(OptionType::Put) > 5.0 * otm.model.price(OptionType::Put));
+    }
+
+    #[test]
+    fn test_monte_carlo_agrees_with_series() {
+        let m = merton();
+        let engine = MonteCarlo::new(200_000, 11).unwrap();
+        let discount = (-0.05_f64 * 0.5).exp();
+        let mc = engine
+            .price(&m, &[0.25, 0.5], discount, |path| (path[1] - 100.0).max(0.0))
+            .unwrap();
+        let series = m.price(OptionType::Call);
+        assert!((mc.price - series).abs() < 4.0 * mc.std_error, "{} vs {}", mc.price, series);
+    }
+
+    #[test]
+    fn test_char_fn_martingale() {
+        let m = merton();
+        let phi = m.char_fn(-Complex::I, 0.5);
+        assert!((phi.re - (0.04_f64 * 0.5).exp()).abs() < 1e-12);
+        assert!(phi.im.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_greeks_and_validation() {
+        let g = merton().greeks(OptionType::Call);
+        assert!(g.delta > 0.0 && g.delta < 1.0);
+        assert!(g.gamma > 0.0 && g.vega > 0.0);
+
+        let bs = BlackScholes::new(100.0, 100.0, 0.5, 0.05, 0.2, 0.0).unwrap();
+        assert!(MertonJumpDiffusion::new(bs, -1.0, 0.0, 0.1).is_err());
+        assert!(MertonJumpDiffusion::new(bs, 1.0, f64::NAN, 0.1).is_err());
+    }
+}
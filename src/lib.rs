@@ -1,3 +1,175 @@
+pub mod american;
+pub mod asian;
+pub mod autocallable;
+pub mod barrier;
+pub mod basket;
 pub mod black_scholes;
+pub mod bonds;
+pub mod calibration;
+pub mod cev;
+pub mod chain;
+pub mod characteristic;
+pub mod chooser;
+pub mod cliquet;
+pub mod combo;
+pub mod compound;
+pub mod config;
+pub mod credit;
+pub mod cross_greeks;
+pub mod curves;
+pub mod density;
+pub mod digital;
+pub mod dividends;
+pub mod error;
+pub mod etf;
+pub mod execution;
+pub mod explain;
+pub mod expiry_calendar;
+pub mod fourier;
+pub mod fx;
+pub mod gap;
+pub mod hedging;
+pub mod heston;
+pub mod hull_white;
+pub mod invariants;
+pub mod jump_diffusion;
+pub mod libor_market;
+pub mod local_vol;
+pub mod lookback;
+pub mod lsm;
+pub mod market;
+pub mod math;
+pub mod model;
+pub mod moments;
+pub mod moneyness;
+pub mod monte_carlo;
+pub mod multi_asset;
+pub mod parameter_term;
+pub mod pde;
+pub mod pnl_distribution;
+pub mod portfolio;
+pub mod power;
+pub mod rainbow;
+pub mod rate_notes;
+pub mod rates;
+pub mod reference;
+pub mod regime_switching;
+pub mod risk;
+pub mod rolling;
+pub mod scenario;
+pub mod settlement_value;
+pub mod spread;
+pub mod strategy;
+pub mod strategy_index;
+pub mod synthetic;
+pub mod time_scale;
+pub mod timer;
+pub mod tree;
+pub mod validation;
+pub mod vanna_volga;
+pub mod variance_gamma;
+pub mod variance_swap;
+pub mod vol_estimators;
+pub mod vol_index;
+pub mod vol_space;
+pub mod vol_surface;
+pub mod what_if;
 
-pub use black_scholes::{BlackScholes, OptionType, Greeks};
+pub use american::{barone_adesi_whaley, decompose, AmericanEngine, PremiumDecomposition};
+pub use asian::{AsianOption, AverageType, Averaging};
+pub use autocallable::WorstOfAutocallable;
+pub use barrier::{BarrierOption, BarrierType};
+pub use basket::BasketOption;
+pub use black_scholes::{
+    BlackScholes, BlackScholesBuilder, CashGreeks, Leverage, ModelInputs, OptionType, Greeks, PricingResult,
+};
+pub use bonds::{BondAnalytics, Date, DayCount, FixedRateBond};
+pub use calibration::{
+    CalibratedParameters, CalibrationFailure, CalibrationModel, CalibrationRecord, CalibrationRun, CalibrationScheduler,
+    CalibrationStore, FitDiagnostics,
+};
+pub use cev::{Cev, CevFit};
+pub use chain::{ExpirySlice, OptionChain, OptionQuote, QuoteAnalytics};
+pub use characteristic::CharacteristicFunction;
+pub use chooser::ChooserOption;
+pub use cliquet::{Cliquet, ForwardStartOption};
+pub use combo::{ComboLeg, ComboPrice, ComboQuote};
+pub use compound::CompoundOption;
+pub use config::{ModelConfig, PricingRun, CONFIG_VERSION};
+pub use credit::{bootstrap_hazard, CdsQuote, CreditDefaultSwap, HazardCurve};
+pub use cross_greeks::{CrossGreeks, PricingInput, PRICING_INPUTS};
+pub use curves::{
+    bootstrap, AdjointCurve, CarryForward, DiscountCurve, FlatCurve, ForwardCurve, InterpolatedCurve, InterpolatedForward,
+    Interpolation, PublicRateInputs, RateCurveCache, RateInstrument, SofrFuture, TreasuryBill,
+};
+pub use density::{kl_divergence, DensityComparison, QuantileShift, RiskNeutralDensity};
+pub use digital::{DigitalOption, DigitalPayoff};
+pub use dividends::{HybridDividend, HybridDividendModel};
+pub use error::BlackScholesError;
+pub use etf::{Distribution, Etf, EtfForward, LeveragedEtf, LeveragedSurface};
+pub use execution::{ExecutionModel, Fill, Remainder};
+pub use explain::{explain, explain_position, Attribution, MarketSnapshot, MarketState, PnlExplain};
+pub use expiry_calendar::{Exchange, ExpiryCalendar, ExpiryCycle, ExpirySettlement, ListedExpiry};
+pub use fourier::{CarrMadan, CosMethod, TransformPricer};
+pub use fx::{quanto_forward, AtmConvention, FxGreeks, GarmanKohlhagen, QuantoOption};
+pub use gap::GapOption;
+pub use hedging::{simulate_history, DeltaHedge, HedgeReport, HedgeRule, TransactionCosts};
+pub use heston::{Bates, Heston, HestonCalibration, HestonFit, HestonParams};
+pub use hull_white::{CallableBond, HullWhite, HullWhiteFit, HullWhiteFixings, HullWhiteTree, RateQuote};
+pub use invariants::{probe, Expectations, InvariantReport, Monotonicity, ProbeGrid, Property, Violation};
+pub use jump_diffusion::MertonJumpDiffusion;
+pub use libor_market::{LiborMarketModel, Numeraire};
+pub use local_vol::LocalVol;
+pub use lookback::{LookbackOption, LookbackStrike};
+pub use lsm::{BermudanOption, ExercisePolicy, LongstaffSchwartz, LsmResult};
+pub use market::{MarketContext, SharedMarket};
+pub use model::{EuropeanModel, SurfaceModel};
+pub use moments::Moments;
+pub use moneyness::{
+    delta_with_convention, log_moneyness, standardized_moneyness, strike_from_delta, strike_from_log_moneyness,
+    strike_from_standardized_moneyness, DeltaConvention,
+};
+pub use monte_carlo::{
+    AdjointResult, BrownianBridge, FixingModel, GreekMethod, McGreeks, McResult, MonteCarlo, PathModel,
+};
+pub use multi_asset::{Asset, AssetDynamics, CorrelatedPaths, LeverageSurface, MultiAssetMc};
+pub use parameter_term::{HestonTermModel, ParameterHistory, ParameterTermStructure, TermParameters};
+pub use pde::{CashDividend, Exercise, FiniteDifference, PdeResult, Scheme};
+pub use pnl_distribution::PnlDistribution;
+pub use portfolio::{Booking, BookingKind, Exposure, Instrument, ModelTag, Portfolio, Position, Settlement};
+pub use power::PowerOption;
+pub use rainbow::{RainbowOption, RainbowPayoff};
+pub use rate_notes::{NoteValuation, RangeAccrual, TargetRedemptionNote};
+pub use rates::{
+    adjoint_bucketed_dv01, annuity, bachelier, black76, forward_rate, implied_shifted_volatility, par_swap_rate,
+    shifted_black76, BucketedDv01, CapFloor, Caplet, InterestRateSwap, RateVolatility, SwapSide, Swaption,
+};
+pub use reference::{verify, Analytic, ReferenceCase, ReferenceEngine, VerificationReport};
+pub use regime_switching::{Regime, RegimeSwitching};
+pub use risk::{
+    delta_gamma_var, delta_normal_var, monte_carlo_var, nearest_correlation, RiskFactors, RiskMeasure, VarConfig,
+};
+pub use rolling::{RollSchedule, RollingLeg, RollingPosition, RollingSeries};
+pub use scenario::{
+    ApproximationError, CorrelationScenario, CorrelationShock, Revalue, ScenarioGrid, ScenarioResult, Shock,
+    ShockedSurface, SurfaceScenario, SurfaceShock, TaylorExpansion,
+};
+pub use settlement_value::{GapDistribution, SettlementPrint, SettlementRisk};
+pub use spread::SpreadOption;
+pub use strategy::{Extremum, Leg, LegKind, MarketObservation, PnlSurface, PositionSnapshot, Strategy, TailRisk};
+pub use strategy_index::{IndexRule, IndexSeries, IndexStatistics, StrategyIndex};
+pub use synthetic::{generate_chain, ChainSpec, StrikeGrid};
+pub use time_scale::{DaySchedule, TimeScale};
+pub use tree::{BinomialTree, TreeResult};
+pub use timer::{TimerOption, TimerValuation};
+pub use validation::Strictness;
+pub use vanna_volga::{SmileQuotes, VannaVolga};
+pub use variance_gamma::VarianceGamma;
+pub use variance_swap::{
+    flat_fair_variance, volatility_swap_strike, Corridor, OptionStrip, VarianceSwap, VarianceWeighting,
+};
+pub use vol_estimators::{realized_volatility, rolling_volatility, Bar, Estimator};
+pub use vol_index::{cboe_term_variance, IndexMethod, TermVariance, VolIndex, VolIndexHistory, VolIndexPoint, VolRegime};
+pub use vol_space::{VolQuote, VolSpaceGreeks};
+pub use vol_surface::{VarianceDerivatives, VolSurface};
+pub use what_if::{PreTradeCheck, RiskProfile, TradeImpact, VarMethod};
@@ -0,0 +1,334 @@
+//! Lognormal forward LIBOR market model
+
+use crate::black_scholes::OptionType;
+use crate::curves::DiscountCurve;
+use crate::error::BlackScholesError;
+use crate::math::Rng;
+use crate::monte_carlo::{validate_tenors, FixingModel, McResult, MonteCarlo};
+use crate::rates::black76;
+use crate::risk::cholesky;
+use crate::validation;
+
+/// Numeraire the forwards are simulated under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Numeraire {
+    /// Discretely rolled bank account: reinvest at each fixing
+    SpotLibor,
+    /// Zero-coupon bond maturing at the last tenor date
+    Terminal,
+}
+
+/// Lognormal market model of the forwards between consecutive tenor dates
+///
+/// Forward `i` accrues over `[T_i, T_{i+1}]`, fixes at `T_i` and follows
+/// dL_i / L_i = μ_i dt + σ_i dW_i with dW_i·dW_j = e^(-β|T_i - T_j|) dt.
+/// The drift μ_i is the change of measure to the chosen numeraire, so each
+/// forward is lognormal only under its own payment-date measure; caplets
+/// match Black-76 while path-dependent payoffs see the drift corrections.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiborMarketModel {
+    /// Tenor dates T_0 < T_1 < ... < T_n in years, the first in the future
+    pub tenors: Vec<f64>,
+    /// Initial simply compounded forwards, one per accrual period
+    pub forwards: Vec<f64>,
+    /// Lognormal volatility of each forward
+    pub volatilities: Vec<f64>,
+    /// Correlation decay β per year of tenor separation
+    pub correlation_decay: f64,
+    pub numeraire: Numeraire,
+    /// Discount factor to the first tenor date
+    pub initial_discount: f64,
+}
+
+/// Scratch buffers reused across paths
+struct PathState {
+    rates: Vec<f64>,
+    predicted: Vec<f64>,
+    drift: Vec<f64>,
+    corrected: Vec<f64>,
+    normals: Vec<f64>,
+    shocks: Vec<f64>,
+    fixings: Vec<f64>,
+    deflators: Vec<f64>,
+    cashflows: Vec<f64>,
+}
+
+impl LiborMarketModel {
+    /// Model fitted to the forwards of `curve`
+    ///
+    /// # Arguments
+    /// * `curve` - Discount curve the initial forwards are read from
+    /// * `tenors` - Tenor dates, strictly increasing with the first positive
+    /// * `volatilities` - Lognormal volatility of each of the `tenors.len() - 1` forwards
+    /// * `correlation_decay` - β in ρ_ij = e^(-β|T_i - T_j|)
+    /// * `numeraire` - Measure the simulation runs under
+    pub fn new(
+        curve: &dyn DiscountCurve,
+        tenors: Vec<f64>,
+        volatilities: Vec<f64>,
+        correlation_decay: f64,
+        numeraire: Numeraire,
+    ) -> Result<Self, BlackScholesError> {
+        validate_tenors(&tenors)?;
+        if volatilities.len() != tenors.len() - 1 {
+            return Err(BlackScholesError::invalid("Need one volatility per accrual period"));
+        }
+        for &v in &volatilities {
+            validation::non_negative("Forward volatility", v)?;
+        }
+        let forwards = tenors
+            .windows(2)
+            .map(|w| (curve.df(w[0]) / curve.df(w[1]) - 1.0) / (w[1] - w[0]))
+            .collect::<Vec<f64>>();
+        if forwards.iter().any(|&f| f <= 0.0) {
+            return Err(BlackScholesError::invalid("Lognormal forwards must be positive"));
+        }
+        Ok(LiborMarketModel {
+            initial_discount: curve.df(tenors[0]),
+            tenors,
+            forwards,
+            volatilities,
+            correlation_decay: validation::non_negative("Correlation decay", correlation_decay)?,
+            numeraire,
+        })
+    }
+
+    pub fn with_numeraire(mut self, numeraire: Numeraire) -> Self {
+        self.numeraire = numeraire;
+        self
+    }
+
+    /// Number of accrual periods
+    pub fn periods(&self) -> usize {
+        self.forwards.len()
+    }
+
+    /// Length of accrual period `i` in years
+    pub fn accrual(&self, i: usize) -> f64 {
+        self.tenors[i + 1] - self.tenors[i]
+    }
+
+    /// Instantaneous correlation of forwards `i` and `j`
+    pub fn correlation(&self, i: usize, j: usize) -> f64 {
+        (-self.correlation_decay * (self.tenors[i] - self.tenors[j]).abs()).exp()
+    }
+
+    /// Today's discount factor to tenor date `i`
+    pub fn discount_factor(&self, i: usize) -> f64 {
+        (0..i).fold(self.initial_discount, |df, j| df / (1.0 + self.accrual(j) * self.forwards[j]))
+    }
+
+    /// Closed-form caplet or floorlet on forward `i` per unit notional
+    pub fn caplet(&self, i: usize, strike: f64, option_type: OptionType) -> Result<f64, BlackScholesError> {
+        if i >= self.periods() {
+            return Err(BlackScholesError::invalid("No such accrual period"));
+        }
+        let value = black76(self.forwards[i], strike, self.tenors[i], self.volatilities[i], option_type)?;
+        Ok(self.accrual(i) * self.discount_factor(i + 1) * value)
+    }
+
+    /// Price a payoff on the fixings with the Monte Carlo engine
+    ///
+    /// `payoff(fixings, cashflows)` receives every forward's fixing
+    /// L_i(T_i) and fills `cashflows[i]`, paid at `T_{i+1}`; a payment may
+    /// only use fixings up to `i`. Each cashflow is deflated by the
+    /// numeraire on its payment date.
+    pub fn price<F>(&self, mc: &MonteCarlo, mut payoff: F) -> Result<McResult, BlackScholesError>
+    where
+        F: FnMut(&[f64], &mut [f64]),
+    {
+        let n = self.periods();
+        let correlation: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| self.correlation(i, j)).collect()).collect();
+        let factor = cholesky(&correlation)?;
+        let mut state = PathState {
+            rates: vec![0.0; n],
+            predicted: vec![0.0; n],
+            drift: vec![0.0; n],
+            corrected: vec![0.0; n],
+            normals: vec![0.0; n],
+            shocks: vec![0.0; n],
+            fixings: vec![0.0; n],
+            deflators: vec![0.0; n],
+            cashflows: vec![0.0; n],
+        };
+        Ok(mc.estimate(1.0, |rng| {
+            self.simulate(&factor, rng, &mut state);
+            state.cashflows.iter_mut().for_each(|c| *c = 0.0);
+            payoff(&state.fixings, &mut state.cashflows);
+            state.cashflows.iter().zip(&state.deflators).map(|(c, d)| c * d).sum()
+        }))
+    }
+
+    /// Drift of every live forward from reset `k` on, given the current rates
+    fn drifts(&self, rates: &[f64], k: usize, drift: &mut [f64]) {
+        let n = self.periods();
+        let term = |j: usize| {
+            let tau = self.accrual(j);
+            tau * self.volatilities[j] * rates[j] / (1.0 + tau * rates[j])
+        };
+        for (i, d) in drift.iter_mut().enumerate().skip(k) {
+            let sum: f64 = match self.numeraire {
+                Numeraire::SpotLibor => (k..=i).map(|j| self.correlation(i, j) * term(j)).sum(),
+                Numeraire::Terminal => -(i + 1..n).map(|j| self.correlation(i, j) * term(j)).sum::<f64>(),
+            };
+            *d = self.volatilities[i] * sum;
+        }
+    }
+
+    /// One path stepping reset to reset with a predictor-corrector log-Euler scheme
+    ///
+    /// Fills the fixings and, for each payment date, the numeraire-deflated
+    /// value of a unit paid there.
+    fn simulate(&self, factor: &[Vec<f64>], rng: &mut Rng, state: &mut PathState) {
+        let n = self.periods();
+        state.rates.copy_from_slice(&self.forwards);
+        let mut previous = 0.0;
+        for k in 0..n {
+            let dt = self.tenors[k] - previous;
+            state.normals.iter_mut().for_each(|z| *z = rng.normal());
+            for (shock, row) in state.shocks.iter_mut().zip(factor) {
+                *shock = row.iter().zip(&state.normals).map(|(l, z)| l * z).sum();
+            }
+            self.drifts(&state.rates, k, &mut state.drift);
+            for i in k..n {
+                let sigma = self.volatilities[i];
+                let diffusion = sigma * dt.sqrt() * state.shocks[i] - 0.5 * sigma * sigma * dt;
+                state.predicted[i] = state.rates[i] * (state.drift[i] * dt + diffusion).exp();
+            }
+            self.drifts(&state.predicted, k, &mut state.corrected);
+            for i in k..n {
+                let sigma = self.volatilities[i];
+                let drift = 0.5 * (state.drift[i] + state.corrected[i]);
+                let diffusion = sigma * dt.sqrt() * state.shocks[i] - 0.5 * sigma * sigma * dt;
+                state.rates[i] *= (drift * dt + diffusion).exp();
+            }
+            state.fixings[k] = state.rates[k];
+            previous = self.tenors[k];
+
+            // Deflator for the payment at T_k, which needs the live forwards now
+            if self.numeraire == Numeraire::Terminal && k > 0 {
+                let bond: f64 = (k..n).map(|j| 1.0 / (1.0 + self.accrual(j) * state.rates[j])).product();
+                state.deflators[k - 1] = self.discount_factor(n) / bond;
+            }
+        }
+        match self.numeraire {
+            Numeraire::SpotLibor => {
+                let mut account = 1.0 / self.initial_discount;
+                for i in 0..n {
+                    account *= 1.0 + self.accrual(i) * state.fixings[i];
+                    state.deflators[i] = 1.0 / account;
+                }
+            }
+            Numeraire::Terminal => state.deflators[n - 1] = self.discount_factor(n),
+        }
+    }
+}
+
+impl FixingModel for LiborMarketModel {
+    fn tenors(&self) -> &[f64] {
+        &self.tenors
+    }
+
+    fn price_fixings<F>(&self, mc: &MonteCarlo, payoff: F) -> Result<McResult, BlackScholesError>
+    where
+        F: FnMut(&[f64], &mut [f64]),
+    {
+        self.price(mc, payoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::FlatCurve;
+
+    fn model(numeraire: Numeraire) -> LiborMarketModel {
+        let curve = FlatCurve::new(0.04).unwrap();
+        let tenors: Vec<f64> = (0..=8).map(|i| 1.0 + 0.5 * i as f64).collect();
+        LiborMarketModel::new(&curve, tenors, vec![0.25; 8], 0.1, numeraire).unwrap()
+    }
+
+    #[test]
+    fn test_caplets_match_black_under_both_numeraires() {
+        let mc = MonteCarlo::new(40_000, 11).unwrap();
+        for numeraire in [Numeraire::SpotLibor, Numeraire::Terminal] {
+            let lmm = model(numeraire);
+            let strike = lmm.forwards[5];
+            let result = lmm
+                .price(&mc, |fixings, cashflows| {
+                    cashflows[5] = 0.5 * (fixings[5] - strike).max(0.0);
+                })
+                .unwrap();
+            let exact = lmm.caplet(5, strike, OptionType::Call).unwrap();
+            assert!((result.price - exact).abs() < 3.5 * result.std_error, "{numeraire:?}: {result:?} vs {exact}");
+        }
+    }
+
+    #[test]
+    fn test_floating_leg_and_bond_reprice_the_curve() {
+        let mc = MonteCarlo::new(20_000, 5).unwrap();
+        for numeraire in [Numeraire::SpotLibor, Numeraire::Terminal] {
+            let lmm = model(numeraire);
+            let n = lmm.periods();
+            // Floating leg plus principal at T_n is worth par at T_0
+            let result = lmm
+                .price(&mc, |fixings, cashflows| {
+                    for (i, c) in cashflows.iter_mut().enumerate() {
+                        *c = 0.5 * fixings[i];
+                    }
+                    cashflows[n - 1] += 1.0;
+                })
+                .unwrap();
+            assert!((result.price - lmm.initial_discount).abs() < 1e-3, "{numeraire:?}: {result:?}");
+        }
+        let terminal = model(Numeraire::Terminal);
+        let bond = terminal.price(&mc, |_, cashflows| cashflows[7] = 1.0).unwrap();
+        assert!((bond.price - (-0.04_f64 * 5.0).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_tarn_with_unreachable_target_is_a_floorlet_strip() {
+        // Inverse floater coupons max(2K - L, 0) until the total reaches the target
+        let lmm = model(Numeraire::SpotLibor);
+        let mc = MonteCarlo::new(40_000, 3).unwrap();
+        let strike = 0.04;
+        let tarn = |target: f64| {
+            lmm.price(&mc, |fixings, cashflows| {
+                let n = fixings.len();
+                let mut paid = 0.0;
+                for i in 0..n {
+                    let coupon = (0.5 * (2.0 * strike - fixings[i]).max(0.0)).min(target - paid);
+                    paid += coupon;
+                    cashflows[i] = coupon;
+                    if paid >= target - 1e-12 || i == n - 1 {
+                        cashflows[i] += 1.0;
+                        break;
+                    }
+                }
+            })
+            .unwrap()
+        };
+        let unlimited = tarn(f64::INFINITY);
+        let strip: f64 = (0..8)
+            .map(|i| lmm.caplet(i, 2.0 * strike, OptionType::Put).unwrap())
+            .sum::<f64>()
+            + lmm.discount_factor(8);
+        assert!((unlimited.price - strip).abs() < 4.0 * unlimited.std_error, "{unlimited:?} vs {strip}");
+        // Knocking out early redeems at par but gives up the remaining coupons
+        let capped = tarn(0.06);
+        assert!((capped.price - unlimited.price).abs() > 4.0 * unlimited.std_error);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let curve = FlatCurve::new(0.04).unwrap();
+        assert!(LiborMarketModel::new(&curve, vec![1.0], vec![], 0.1, Numeraire::SpotLibor).is_err());
+        assert!(LiborMarketModel::new(&curve, vec![0.0, 1.0], vec![0.2], 0.1, Numeraire::SpotLibor).is_err());
+        assert!(LiborMarketModel::new(&curve, vec![1.0, 2.0], vec![0.2, 0.2], 0.1, Numeraire::SpotLibor).is_err());
+        assert!(LiborMarketModel::new(&curve, vec![1.0, 2.0], vec![-0.2], 0.1, Numeraire::Terminal).is_err());
+        let negative = FlatCurve::new(-0.01).unwrap();
+        assert!(LiborMarketModel::new(&negative, vec![1.0, 2.0], vec![0.2], 0.1, Numeraire::Terminal).is_err());
+    }
+}
@@ -0,0 +1,294 @@
+//! Dupire local volatility implied by an arbitrage-free implied volatility surface
+
+use crate::black_scholes::OptionType;
+use crate::error::BlackScholesError;
+use crate::math::Rng;
+use crate::monte_carlo::PathModel;
+use crate::pde::{boundaries, intrinsic, thomas, Exercise, FiniteDifference, PdeResult, Scheme, RANNACHER_STEPS};
+use crate::time_scale::CALENDAR_DAYS_PER_YEAR;
+use crate::validation;
+use crate::vol_surface::VolSurface;
+
+/// Euler steps per year when simulating paths
+const DEFAULT_STEPS_PER_YEAR: usize = 100;
+
+/// Times below this (years) are read at it, where the surface's expiry derivative is unreliable
+const MIN_EXPIRY: f64 = 1.0 / 365.0;
+
+/// Floor on total variance in Dupire's denominator
+const VARIANCE_FLOOR: f64 = 1e-12;
+
+/// Floor on Dupire's denominator, which only turns negative under butterfly arbitrage
+const DENOMINATOR_FLOOR: f64 = 1e-4;
+
+/// Dupire local volatility σ(S, t) of an implied volatility surface
+///
+/// With y = ln(K/F) against the forward F = S₀·e^((r-q)T) and total
+/// implied variance w(y, T), Dupire's formula reads
+///
+/// σ² = ∂w/∂T / [1 - y/w·∂w/∂y + ¼(-¼ - 1/w + y²/w²)(∂w/∂y)² + ½·∂²w/∂y²]
+///
+/// evaluated at K = S, T = t. Derivatives come from
+/// [`VolSurface::variance_derivatives`]: closed form for SVI and SSVI
+/// surfaces, finite differences for any other. The surface's forwards
+/// should match the carry given here. Calendar arbitrage (∂w/∂T < 0) is
+/// floored to zero local variance.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocalVol<S> {
+    pub spot: f64,
+    /// Continuously compounded risk-free rate
+    pub rate: f64,
+    /// Continuous dividend yield
+    pub dividend_yield: f64,
+    pub surface: S,
+    /// Minimum Euler steps per year of simulated path
+    pub steps_per_year: usize,
+}
+
+impl<S: VolSurface> LocalVol<S> {
+    pub fn new(spot: f64, rate: f64, dividend_yield: f64, surface: S) -> Result<Self, BlackScholesError> {
+        Ok(LocalVol {
+            spot: validation::positive("Spot price", spot)?,
+            rate: validation::finite("Risk-free rate", rate)?,
+            dividend_yield: validation::finite("Dividend yield", dividend_yield)?,
+            surface,
+            steps_per_year: DEFAULT_STEPS_PER_YEAR,
+        })
+    }
+
+    pub fn with_steps_per_year(mut self, steps_per_year: usize) -> Result<Self, BlackScholesError> {
+        if steps_per_year == 0 {
+            return Err(BlackScholesError::invalid("Need at least one step per year"));
+        }
+        self.steps_per_year = steps_per_year;
+        Ok(self)
+    }
+
+    /// Local volatility at spot level `spot` and time `t` in years
+    pub fn local_vol(&self, spot: f64, t: f64) -> f64 {
+        let t = t.max(MIN_EXPIRY);
+        let carry = self.rate - self.dividend_yield;
+        let d = self.surface.variance_derivatives(spot, t);
+        let y = (spot / self.spot).ln() - carry * t;
+        let w = d.total_variance.max(VARIANCE_FLOOR);
+        // Strike derivatives to log-moneyness ones; ∂T at fixed y adds the forward's drift
+        let w_y = spot * d.d_strike;
+        let w_yy = w_y + spot * spot * d.d_strike2;
+        let w_t = d.d_expiry + w_y * carry;
+        let denominator =
+            1.0 - y / w * w_y + 0.25 * (-0.25 - 1.0 / w + y * y / (w * w)) * w_y * w_y + 0.5 * w_yy;
+        (w_t.max(0.0) / denominator.max(DENOMINATOR_FLOOR)).sqrt()
+    }
+
+    /// Price a European option by finite differences on the local volatility PDE
+    ///
+    /// Grid size, time steps and scheme come from `fd`; the grid spans
+    /// `fd.std_devs` at-the-money standard deviations, and local volatility
+    /// is read at the middle of each time step. Theta is per calendar day.
+    ///
+    /// # Arguments
+    /// * `fd` - Grid and time-stepping settings
+    /// * `option_type` - Type of option (Call or Put)
+    /// * `strike` - Strike price
+    /// * `expiry` - Time to expiry in years
+    pub fn solve(
+        &self,
+        fd: &FiniteDifference,
+        option_type: OptionType,
+        strike: f64,
+        expiry: f64,
+    ) -> Result<PdeResult, BlackScholesError> {
+        let k = validation::positive("Strike price", strike)?;
+        let t = validation::positive("Time to expiry", expiry)?;
+        let (r, q) = (self.rate, self.dividend_yield);
+        let carry = r - q;
+        let atm = self.surface.implied_vol(self.spot * (carry * t).exp(), t);
+
+        // Log-spot grid centred on today's spot
+        let n = fd.grid_points;
+        let mid = n / 2;
+        let x0 = self.spot.ln();
+        let half_width = fd.std_devs * atm * t.sqrt() + (carry - 0.5 * atm * atm).abs() * t;
+        let dx = half_width / mid as f64;
+        let spots: Vec<f64> = (0..n).map(|i| (x0 + (i as f64 - mid as f64) * dx).exp()).collect();
+        let dt = t / fd.time_steps as f64;
+
+        let mut values: Vec<f64> = spots.iter().map(|&s| intrinsic(option_type, s, k)).collect();
+        let mut previous = values.clone();
+
+        for step in 1..=fd.time_steps {
+            let tau = dt * step as f64;
+            let weight = if fd.scheme == Scheme::CrankNicolson && step <= RANNACHER_STEPS {
+                1.0
+            } else {
+                fd.scheme.theta()
+            };
+
+            // Operator L V = a V_{i-1} + b V_i + c V_{i+1} on interior nodes, frozen at mid-step
+            let now = t - tau + 0.5 * dt;
+            let operator: Vec<(f64, f64, f64)> = spots[1..n - 1]
+                .iter()
+                .map(|&s| {
+                    let variance = self.local_vol(s, now).powi(2);
+                    let diffusion = 0.5 * variance / (dx * dx);
+                    let convection = (carry - 0.5 * variance) / (2.0 * dx);
+                    (diffusion - convection, -2.0 * diffusion - r, diffusion + convection)
+                })
+                .collect();
+            if fd.scheme == Scheme::Explicit && operator.iter().any(|&(_, b, _)| -b * dt > 1.0) {
+                return Err(BlackScholesError::invalid(
+                    "Explicit scheme is unstable on this grid; increase time steps",
+                ));
+            }
+
+            previous.copy_from_slice(&values);
+            let (lower, upper) = boundaries(option_type, Exercise::European, spots[0], spots[n - 1], k, r, q, tau);
+
+            // (I - θ·dt·L) V^{n+1} = (I + (1-θ)·dt·L) V^n
+            let (explicit, implicit) = ((1.0 - weight) * dt, weight * dt);
+            let mut rhs: Vec<f64> = operator
+                .iter()
+                .zip(previous.windows(3))
+                .map(|(&(a, b, c), v)| v[1] + explicit * (a * v[0] + b * v[1] + c * v[2]))
+                .collect();
+            let sub: Vec<f64> = operator.iter().map(|&(a, _, _)| -implicit * a).collect();
+            let diag: Vec<f64> = operator.iter().map(|&(_, b, _)| 1.0 - implicit * b).collect();
+            let sup: Vec<f64> = operator.iter().map(|&(_, _, c)| -implicit * c).collect();
+            rhs[0] -= sub[0] * lower;
+            rhs[n - 3] -= sup[n - 3] * upper;
+
+            values[0] = lower;
+            values[1..n - 1].copy_from_slice(&thomas(&sub, &diag, &sup, &rhs));
+            values[n - 1] = upper;
+        }
+
+        let price = values[mid];
+        let first = (values[mid + 1] - values[mid - 1]) / (2.0 * dx);
+        let second = (values[mid + 1] - 2.0 * values[mid] + values[mid - 1]) / (dx * dx);
+        let s0 = self.spot;
+
+        Ok(PdeResult {
+            price,
+            delta: first / s0,
+            gamma: (second - first) / (s0 * s0),
+            theta: (previous[mid] - price) / dt / CALENDAR_DAYS_PER_YEAR,
+        })
+    }
+}
+
+impl<S: VolSurface> PathModel for LocalVol<S> {
+    fn initial_spot(&self) -> f64 {
+        self.spot
+    }
+
+    /// Log-Euler steps with local volatility frozen over each, at least `steps_per_year` a year
+    fn simulate_path(&self, times: &[f64], rng: &mut Rng, path: &mut [f64]) {
+        let carry = self.rate - self.dividend_yield;
+        let mut log_spot = self.spot.ln();
+        let mut now = 0.0;
+        for (&target, s) in times.iter().zip(path.iter_mut()) {
+            let steps = ((target - now) * self.steps_per_year as f64).ceil().max(1.0) as usize;
+            let dt = (target - now) / steps as f64;
+            for _ in 0..steps {
+                let vol = self.local_vol(log_spot.exp(), now);
+                log_spot += (carry - 0.5 * vol * vol) * dt + vol * dt.sqrt() * rng.normal();
+                now += dt;
+            }
+            *s = log_spot.exp();
+            now = target;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::monte_carlo::MonteCarlo;
+    use crate::vol_surface::{AtmNode, FlatVol, SsviParams, SsviSurface};
+
+    const SPOT: f64 = 100.0;
+    const RATE: f64 = 0.03;
+    const DIVIDEND: f64 = 0.01;
+
+    /// Equity-like SSVI surface with forwards matching the carry above
+    fn ssvi() -> SsviSurface {
+        let nodes = [(0.25, 0.25), (0.5, 0.24), (1.0, 0.23), (2.0, 0.225)]
+            .iter()
+            .map(|&(expiry, vol): &(f64, f64)| AtmNode {
+                expiry,
+                forward: SPOT * ((RATE - DIVIDEND) * expiry).exp(),
+                atm_variance: vol * vol * expiry,
+            })
+            .collect();
+        SsviSurface::new(SsviParams::new(-0.6, 1.0, 0.4).unwrap(), nodes).unwrap()
+    }
+
+    /// Surface hiding its closed-form derivatives, to exercise the finite-difference default
+    struct Opaque(SsviSurface);
+
+    impl VolSurface for Opaque {
+        fn implied_vol(&self, strike: f64, expiry: f64) -> f64 {
+            self.0.implied_vol(strike, expiry)
+        }
+    }
+
+    #[test]
+    fn test_flat_surface_is_black_scholes() {
+        let model = LocalVol::new(SPOT, RATE, DIVIDEND, FlatVol::new(0.25)).unwrap();
+        for (s, t) in [(70.0, 0.1), (100.0, 0.5), (140.0, 2.0)] {
+            assert!((model.local_vol(s, t) - 0.25).abs() < 1e-12);
+        }
+        let fd = FiniteDifference::new(201, 200, Scheme::CrankNicolson).unwrap();
+        let pde = model.solve(&fd, OptionType::Put, 95.0, 1.0).unwrap();
+        let bs = BlackScholes::new(SPOT, 95.0, 1.0, RATE, 0.25, DIVIDEND).unwrap();
+        assert!((pde.price - bs.price(OptionType::Put)).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_closed_form_matches_finite_differences() {
+        let analytic = LocalVol::new(SPOT, RATE, DIVIDEND, ssvi()).unwrap();
+        let numeric = LocalVol::new(SPOT, RATE, DIVIDEND, Opaque(ssvi())).unwrap();
+        for (s, t) in [(80.0, 0.3), (100.0, 0.75), (115.0, 1.5), (90.0, 2.5)] {
+            let (a, n) = (analytic.local_vol(s, t), numeric.local_vol(s, t));
+            assert!((a - n).abs() < 1e-4 * a, "{s} {t}: {a} vs {n}");
+        }
+        // Negative skew: local volatility falls with spot, faster than implied
+        let (low, high) = (analytic.local_vol(90.0, 0.75), analytic.local_vol(110.0, 0.75));
+        assert!(low > high);
+        let surface = ssvi();
+        assert!(low - high > surface.implied_vol(90.0, 0.75) - surface.implied_vol(110.0, 0.75));
+    }
+
+    #[test]
+    fn test_pde_and_monte_carlo_reprice_the_surface() {
+        let model = LocalVol::new(SPOT, RATE, DIVIDEND, ssvi()).unwrap().with_steps_per_year(50).unwrap();
+        let fd = FiniteDifference::new(201, 200, Scheme::CrankNicolson).unwrap();
+        let mc = MonteCarlo::new(20_000, 7).unwrap();
+        let expiry = 1.0;
+        for strike in [85.0, 100.0, 115.0] {
+            let vol = model.surface.implied_vol(strike, expiry);
+            let market = BlackScholes::new(SPOT, strike, expiry, RATE, vol, DIVIDEND).unwrap().price(OptionType::Put);
+            let pde = model.solve(&fd, OptionType::Put, strike, expiry).unwrap();
+            assert!((pde.price - market).abs() < 0.005 * market.max(1.0), "{strike}: {} vs {market}", pde.price);
+
+            let df = (-RATE * expiry).exp();
+            let sim = mc.price(&model, &[expiry], df, |path| (strike - path[0]).max(0.0)).unwrap();
+            assert!((sim.price - market).abs() < 4.0 * sim.std_error + 0.02 * market, "{strike}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(LocalVol::new(0.0, RATE, DIVIDEND, FlatVol::new(0.2)).is_err());
+        assert!(LocalVol::new(SPOT, f64::NAN, DIVIDEND, FlatVol::new(0.2)).is_err());
+        let model = LocalVol::new(SPOT, RATE, DIVIDEND, FlatVol::new(0.2)).unwrap();
+        assert!(model.clone().with_steps_per_year(0).is_err());
+        let fd = FiniteDifference::new(101, 50, Scheme::Implicit).unwrap();
+        assert!(model.solve(&fd, OptionType::Call, -1.0, 1.0).is_err());
+        assert!(model.solve(&fd, OptionType::Call, 100.0, 0.0).is_err());
+        let explicit = FiniteDifference::new(401, 2, Scheme::Explicit).unwrap();
+        assert!(model.solve(&explicit, OptionType::Call, 100.0, 1.0).is_err());
+    }
+}
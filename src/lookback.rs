@@ -0,0 +1,200 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Cost of carry substituted when b is numerically zero, where the
+/// closed forms have a removable singularity
+const MIN_CARRY: f64 = 1e-7;
+
+/// Strike convention of a lookback option
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LookbackStrike {
+    /// Call pays S_T - min(S), put pays max(S) - S_T (Goldman-Sosin-Gatto)
+    Floating,
+    /// Call pays max(max(S) - K, 0), put pays max(K - min(S), 0) (Conze-Viswanathan)
+    Fixed,
+}
+
+/// Continuously monitored European lookback option
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LookbackOption {
+    /// Underlying market parameters (strike used by fixed-strike options only)
+    pub model: BlackScholes,
+    /// Floating or fixed strike
+    pub strike_type: LookbackStrike,
+    /// Lowest underlying price observed so far
+    pub observed_min: f64,
+    /// Highest underlying price observed so far
+    pub observed_max: f64,
+}
+
+impl LookbackOption {
+    /// Create a lookback option that may already be partway through its life
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes parameters (spot, strike, expiry, rate, vol, dividend)
+    /// * `strike_type` - Floating or fixed strike
+    /// * `observed_min` - Running minimum of the underlying so far
+    /// * `observed_max` - Running maximum of the underlying so far
+    pub fn new(
+        model: BlackScholes,
+        strike_type: LookbackStrike,
+        observed_min: f64,
+        observed_max: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let observed_min = validation::positive("Observed minimum", observed_min)?;
+        let observed_max = validation::positive("Observed maximum", observed_max)?;
+        if observed_min > model.spot_price {
+            return Err(BlackScholesError::invalid("Observed minimum must not be above spot"));
+        }
+        if observed_max < model.spot_price {
+            return Err(BlackScholesError::invalid("Observed maximum must not be below spot"));
+        }
+        Ok(LookbackOption {
+            model,
+            strike_type,
+            observed_min,
+            observed_max,
+        })
+    }
+
+    /// Create a newly issued lookback whose running extremes equal spot
+    pub fn new_issue(model: BlackScholes, strike_type: LookbackStrike) -> Self {
+        LookbackOption {
+            model,
+            strike_type,
+            observed_min: model.spot_price,
+            observed_max: model.spot_price,
+        }
+    }
+
+    /// Calculate option price
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    ///
+    /// # Returns
+    /// Option price
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        Self::price_with(&self.model, self.strike_type, self.observed_min, self.observed_max, option_type)
+    }
+
+    /// Calculate Greeks by finite differences on the closed-form price
+    ///
+    /// # Arguments
+    /// * `option_type` - Type of option (Call or Put)
+    pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        Greeks::from_finite_differences(&self.model, |m| {
+            Self::price_with(m, self.strike_type, self.observed_min, self.observed_max, option_type)
+        })
+    }
+
+    fn price_with(
+        m: &BlackScholes,
+        strike_type: LookbackStrike,
+        observed_min: f64,
+        observed_max: f64,
+        option_type: OptionType,
+    ) -> f64 {
+        let n = BlackScholes::norm_cdf;
+        let s = m.spot_price;
+        let t = m.time_to_expiry;
+        let r = m.risk_free_rate;
+        let v = m.calendar_volatility();
+        let mut b = r - m.dividend_yield;
+        if b.abs() < MIN_CARRY {
+            b = MIN_CARRY.copysign(b);
+        }
+
+        let s_min = observed_min.min(s);
+        let s_max = observed_max.max(s);
+        let v_sqrt_t = v * t.sqrt();
+        let carry = ((b - r) * t).exp();
+        let discount = (-r * t).exp();
+        let reflection = s * discount * v * v / (2.0 * b);
+        let exponent = -2.0 * b / (v * v);
+        let shift = 2.0 * b * t.sqrt() / v;
+        let d = |level: f64| ((s / level).ln() + (b + 0.5 * v * v) * t) / v_sqrt_t;
+
+        match (strike_type, option_type) {
+            (LookbackStrike::Floating, OptionType::Call) => {
+                let a1 = d(s_min);
+                let a2 = a1 - v_sqrt_t;
+                s * carry * n(a1) - s_min * discount * n(a2)
+                    + reflection * ((s / s_min).powf(exponent) * n(-a1 + shift) - (b * t).exp() * n(-a1))
+            }
+            (LookbackStrike::Floating, OptionType::Put) => {
+                let b1 = d(s_max);
+                let b2 = b1 - v_sqrt_t;
+                s_max * discount * n(-b2) - s * carry * n(-b1)
+                    + reflection * (-(s / s_max).powf(exponent) * n(b1 - shift) + (b * t).exp() * n(b1))
+            }
+            (LookbackStrike::Fixed, OptionType::Call) => {
+                let x = m.strike_price;
+                // Once the running maximum exceeds the strike, the excess is locked in
+                let (level, locked) = if x > s_max { (x, 0.0) } else { (s_max, discount * (s_max - x)) };
+                let e1 = d(level);
+                let e2 = e1 - v_sqrt_t;
+                locked + s * carry * n(e1) - level * discount * n(e2)
+                    + reflection * (-(s / level).powf(exponent) * n(e1 - shift) + (b * t).exp() * n(e1))
+            }
+            (LookbackStrike::Fixed, OptionType::Put) => {
+                let x = m.strike_price;
+                let (level, locked) = if x < s_min { (x, 0.0) } else { (s_min, discount * (x - s_min)) };
+                let f1 = d(level);
+                let f2 = f1 - v_sqrt_t;
+                locked + level * discount * n(-f2) - s * carry * n(-f1)
+                    + reflection * ((s / level).powf(exponent) * n(-f1 + shift) - (b * t).exp() * n(-f1))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_haug_floating_strike() {
+        // Haug (2007): S = 120, Smin = 100, T = 0.5, r = 10%, b = 4%, σ = 30%
+        let model = BlackScholes::new(120.0, 120.0, 0.5, 0.10, 0.30, 0.06).unwrap();
+        let option = LookbackOption::new(model, LookbackStrike::Floating, 100.0, 120.0).unwrap();
+        assert!((option.price(OptionType::Call) - 25.3533).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_haug_fixed_strike() {
+        // Haug (2007): S = Smax = 100, K = 95, T = 0.5, r = b = 10%, σ = 10%
+        let model = BlackScholes::new(100.0, 95.0, 0.5, 0.10, 0.10, 0.0).unwrap();
+        let option = LookbackOption::new_issue(model, LookbackStrike::Fixed);
+        assert!((option.price(OptionType::Call) - 13.2687).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_lookbacks_dominate_vanillas() {
+        let model = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.25, 0.0).unwrap();
+        for strike_type in [LookbackStrike::Floating, LookbackStrike::Fixed] {
+            let option = LookbackOption::new_issue(model, strike_type);
+            assert!(option.price(OptionType::Call) > model.price(OptionType::Call));
+            assert!(option.price(OptionType::Put) > model.price(OptionType::Put));
+        }
+    }
+
+    #[test]
+    fn test_zero_carry_is_continuous() {
+        let at = |q: f64| {
+            let model = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.25, q).unwrap();
+            LookbackOption::new_issue(model, LookbackStrike::Floating).price(OptionType::Put)
+        };
+        assert!((at(0.05) - 0.5 * (at(0.0499) + at(0.0501))).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_invalid_extremes() {
+        let model = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.25, 0.0).unwrap();
+        assert!(LookbackOption::new(model, LookbackStrike::Floating, 105.0, 110.0).is_err());
+        assert!(LookbackOption::new(model, LookbackStrike::Floating, 90.0, 95.0).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+//! Longstaff-Schwartz Monte Carlo for Bermudan and American options with a reusable exercise policy
+
+use crate::black_scholes::OptionType;
+use crate::error::BlackScholesError;
+use crate::math::Rng;
+use crate::monte_carlo::{uniform_times, validate_times, McResult, Moments, MonteCarlo, PathModel};
+use crate::validation;
+
+/// Highest polynomial degree of the regression basis
+const MAX_DEGREE: usize = 8;
+
+/// Pivot below which the regression's normal equations are treated as singular
+const SINGULAR_PIVOT: f64 = 1e-14;
+
+/// Option exercisable on a fixed set of dates
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BermudanOption {
+    pub option_type: OptionType,
+    pub strike: f64,
+    /// Exercise dates in years, strictly increasing; the last is the expiry
+    pub exercise_times: Vec<f64>,
+}
+
+/// Least-squares Monte Carlo pricer (Longstaff and Schwartz, 2001)
+///
+/// Continuation values are regressed on the polynomials 1, x, ..., x^degree
+/// of moneyness x = S/K over the in-the-money paths of each exercise date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LongstaffSchwartz {
+    pub engine: MonteCarlo,
+    /// Degree of the regression polynomial
+    pub degree: usize,
+}
+
+/// Exercise rule fitted by [`LongstaffSchwartz::fit`]
+///
+/// Exporting the policy (it is serializable with the `serde` feature) lets
+/// later risk runs revalue the same product on fresh or bumped paths with
+/// [`revalue`](Self::revalue) instead of regressing again.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExercisePolicy {
+    pub option: BermudanOption,
+    /// Continuation value coefficients on 1, x, x², ... for each exercise
+    /// date, as of that date; empty where too few paths were in the money to
+    /// regress, which leaves the date unexercised
+    pub coefficients: Vec<Vec<f64>>,
+}
+
+/// In-sample price and the policy behind it
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LsmResult {
+    /// Price on the paths the policy was fitted to, biased slightly high by the foresight
+    pub price: McResult,
+    pub policy: ExercisePolicy,
+}
+
+impl BermudanOption {
+    pub fn new(option_type: OptionType, strike: f64, exercise_times: Vec<f64>) -> Result<Self, BlackScholesError> {
+        validate_times(&exercise_times)?;
+        Ok(BermudanOption {
+            option_type,
+            strike: validation::positive("Strike price", strike)?,
+            exercise_times,
+        })
+    }
+
+    /// American option approximated by `steps` evenly spaced exercise dates up to `expiry`
+    pub fn american(
+        option_type: OptionType,
+        strike: f64,
+        expiry: f64,
+        steps: usize,
+    ) -> Result<Self, BlackScholesError> {
+        let expiry = validation::positive("Time to expiry", expiry)?;
+        BermudanOption::new(option_type, strike, uniform_times(expiry, steps))
+    }
+
+    fn payoff(&self, spot: f64) -> f64 {
+        match self.option_type {
+            OptionType::Call => (spot - self.strike).max(0.0),
+            OptionType::Put => (self.strike - spot).max(0.0),
+        }
+    }
+}
+
+impl LongstaffSchwartz {
+    /// Create a new pricer
+    ///
+    /// # Arguments
+    /// * `engine` - Number of paths and seed for the fitting simulation
+    /// * `degree` - Regression polynomial degree, 1 to 8 (2 or 3 is typical)
+    pub fn new(engine: MonteCarlo, degree: usize) -> Result<Self, BlackScholesError> {
+        if degree == 0 || degree > MAX_DEGREE {
+            return Err(BlackScholesError::invalid("Regression degree must be between 1 and 8"));
+        }
+        Ok(LongstaffSchwartz { engine, degree })
+    }
+
+    /// Fit the exercise policy by backward induction and price the option on the same paths
+    ///
+    /// # Arguments
+    /// * `option` - The Bermudan or American option
+    /// * `model` - Path model of the underlying
+    /// * `rate` - Continuously compounded discount rate
+    pub fn fit<M: PathModel + ?Sized>(
+        &self,
+        option: &BermudanOption,
+        model: &M,
+        rate: f64,
+    ) -> Result<LsmResult, BlackScholesError> {
+        let rate = validation::finite("Risk-free rate", rate)?;
+        let times = &option.exercise_times;
+        let dates = times.len();
+        let paths = self.engine.paths;
+        let mut spots = vec![0.0; paths * dates];
+        let mut rng = Rng::new(self.engine.seed);
+        for row in spots.chunks_mut(dates) {
+            model.simulate_path(times, &mut rng, row);
+        }
+
+        // Value of each path as of the current date under the policy fitted so far
+        let mut value: Vec<f64> = spots.chunks(dates).map(|row| option.payoff(row[dates - 1])).collect();
+        let mut coefficients = vec![Vec::new(); dates];
+        coefficients[dates - 1] = vec![0.0; self.degree + 1];
+        for i in (0..dates - 1).rev() {
+            let discount = (-rate * (times[i + 1] - times[i])).exp();
+            value.iter_mut().for_each(|v| *v *= discount);
+            let in_the_money: Vec<usize> = (0..paths).filter(|&p| option.payoff(spots[p * dates + i]) > 0.0).collect();
+            let samples: Vec<(f64, f64)> = in_the_money
+                .iter()
+                .map(|&p| (spots[p * dates + i] / option.strike, value[p]))
+                .collect();
+            let Some(beta) = regress(&samples, self.degree) else {
+                continue;
+            };
+            for &p in &in_the_money {
+                let spot = spots[p * dates + i];
+                let exercise = option.payoff(spot);
+                if exercise > continuation(&beta, spot / option.strike) {
+                    value[p] = exercise;
+                }
+            }
+            coefficients[i] = beta;
+        }
+
+        let mut moments = Moments::default();
+        value.iter().for_each(|&v| moments.add(v));
+        Ok(LsmResult {
+            price: moments.result(paths, (-rate * times[0]).exp()),
+            policy: ExercisePolicy {
+                option: option.clone(),
+                coefficients,
+            },
+        })
+    }
+}
+
+impl ExercisePolicy {
+    /// Whether the policy exercises on date `index` at `spot`
+    pub fn exercises(&self, index: usize, spot: f64) -> bool {
+        let payoff = self.option.payoff(spot);
+        match self.coefficients.get(index) {
+            Some(beta) if payoff > 0.0 && !beta.is_empty() => payoff > continuation(beta, spot / self.option.strike),
+            _ => false,
+        }
+    }
+
+    /// Value the option by following the fixed policy on independent paths
+    ///
+    /// No regression is run, so this is fast enough for every scenario of a
+    /// risk run; `model` may be bumped or shocked relative to the one the
+    /// policy was fitted on. On fresh paths the estimate is a low-biased
+    /// bound, as any fixed exercise rule is at most optimal.
+    ///
+    /// # Arguments
+    /// * `engine` - Number of paths and seed, typically not the fitting seed
+    /// * `model` - Path model of the underlying
+    /// * `rate` - Continuously compounded discount rate
+    pub fn revalue<M: PathModel + ?Sized>(
+        &self,
+        engine: &MonteCarlo,
+        model: &M,
+        rate: f64,
+    ) -> Result<McResult, BlackScholesError> {
+        let rate = validation::finite("Risk-free rate", rate)?;
+        let times = &self.option.exercise_times;
+        let mut path = vec![0.0; times.len()];
+        Ok(engine.estimate(1.0, |rng| {
+            model.simulate_path(times, rng, &mut path);
+            path.iter()
+                .zip(times)
+                .enumerate()
+                .find(|&(i, (&spot, _))| self.exercises(i, spot))
+                .map_or(0.0, |(_, (&spot, &t))| self.option.payoff(spot) * (-rate * t).exp())
+        }))
+    }
+}
+
+/// Fitted continuation value Σ β_j·x^j
+fn continuation(beta: &[f64], x: f64) -> f64 {
+    beta.iter().rev().fold(0.0, |acc, &b| acc * x + b)
+}
+
+/// Least-squares polynomial fit of y on x, None when the samples cannot pin down the coefficients
+fn regress(samples: &[(f64, f64)], degree: usize) -> Option<Vec<f64>> {
+    let n = degree + 1;
+    if samples.len() <= n {
+        return None;
+    }
+    // Normal equations [XᵀX | Xᵀy], solved by Gaussian elimination with partial pivoting
+    let mut system = vec![vec![0.0; n + 1]; n];
+    let mut powers = vec![0.0; n];
+    for &(x, y) in samples {
+        let mut p = 1.0;
+        for power in powers.iter_mut() {
+            *power = p;
+            p *= x;
+        }
+        for (row, &pr) in system.iter_mut().zip(&powers) {
+            for (cell, &pc) in row.iter_mut().zip(&powers) {
+                *cell += pr * pc;
+            }
+            row[n] += pr * y;
+        }
+    }
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&a, &b| system[a][col].abs().total_cmp(&system[b][col].abs()))?;
+        if system[pivot][col].abs() < SINGULAR_PIVOT * system[0][0] {
+            return None;
+        }
+        system.swap(col, pivot);
+        let head = system[col].clone();
+        for row in system.iter_mut().skip(col + 1) {
+            let factor = row[col] / head[col];
+            row.iter_mut().zip(&head).skip(col).for_each(|(cell, &h)| *cell -= factor * h);
+        }
+    }
+    let mut beta = vec![0.0; n];
+    for col in (0..n).rev() {
+        let tail: f64 = (col + 1..n).map(|j| system[col][j] * beta[j]).sum();
+        beta[col] = (system[col][n] - tail) / system[col][col];
+    }
+    Some(beta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::pde::Exercise;
+    use crate::tree::BinomialTree;
+
+    /// Longstaff and Schwartz's first example: S = 36, K = 40, r = 6%, σ = 20%, T = 1
+    fn put() -> (BlackScholes, BermudanOption) {
+        let model = BlackScholes::new(36.0, 40.0, 1.0, 0.06, 0.2, 0.0).unwrap();
+        (model, BermudanOption::american(OptionType::Put, 40.0, 1.0, 50).unwrap())
+    }
+
+    #[test]
+    fn test_american_put_matches_tree() {
+        let (model, option) = put();
+        let lsm = LongstaffSchwartz::new(MonteCarlo::new(20_000, 3).unwrap(), 3).unwrap();
+        let fit = lsm.fit(&option, &model, 0.06).unwrap();
+        let tree = BinomialTree::new(2000).unwrap().price(&model, OptionType::Put, Exercise::American).unwrap();
+        assert!((fit.price.price - tree.price).abs() < 4.0 * fit.price.std_error + 0.02, "{:?}", fit.price);
+        assert!(fit.price.price > model.price(OptionType::Put) + 0.3);
+        // Exercise deep in the money early, never out of the money
+        assert!(fit.policy.exercises(25, 28.0));
+        assert!(!fit.policy.exercises(10, 41.0));
+    }
+
+    #[test]
+    fn test_policy_reused_out_of_sample_and_for_scenarios() {
+        let (model, option) = put();
+        let lsm = LongstaffSchwartz::new(MonteCarlo::new(20_000, 3).unwrap(), 3).unwrap();
+        let policy = lsm.fit(&option, &model, 0.06).unwrap().policy;
+        let fresh = MonteCarlo::new(20_000, 17).unwrap();
+        let out_of_sample = policy.revalue(&fresh, &model, 0.06).unwrap();
+        let tree = BinomialTree::new(2000).unwrap().price(&model, OptionType::Put, Exercise::American).unwrap();
+        // A fixed policy is a lower bound, and a good one is close to optimal
+        assert!(out_of_sample.price < tree.price + 3.0 * out_of_sample.std_error);
+        assert!(out_of_sample.price > tree.price - 0.05, "{:?} vs {}", out_of_sample, tree.price);
+
+        // Spot scenarios on common paths give a delta near the tree's
+        let bumped = |spot: f64| {
+            let shocked = BlackScholes { spot_price: spot, ..model };
+            policy.revalue(&fresh, &shocked, 0.06).unwrap().price
+        };
+        let delta = (bumped(36.5) - bumped(35.5)) / 1.0;
+        assert!((delta - tree.delta).abs() < 0.05, "{delta} vs {}", tree.delta);
+    }
+
+    #[test]
+    fn test_bermudan_worth_less_than_american() {
+        let (model, american) = put();
+        let bermudan = BermudanOption::new(OptionType::Put, 40.0, vec![0.25, 0.5, 0.75, 1.0]).unwrap();
+        let lsm = LongstaffSchwartz::new(MonteCarlo::new(20_000, 5).unwrap(), 2).unwrap();
+        let quarterly = lsm.fit(&bermudan, &model, 0.06).unwrap().price.price;
+        let daily = lsm.fit(&american, &model, 0.06).unwrap().price.price;
+        assert!(quarterly < daily && quarterly > model.price(OptionType::Put));
+        assert_eq!(continuation(&[1.0, 2.0, 3.0], 2.0), 17.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let engine = MonteCarlo::new(100, 1).unwrap();
+        assert!(LongstaffSchwartz::new(engine, 0).is_err());
+        assert!(LongstaffSchwartz::new(engine, 9).is_err());
+        assert!(BermudanOption::new(OptionType::Put, -1.0, vec![1.0]).is_err());
+        assert!(BermudanOption::new(OptionType::Put, 40.0, vec![0.5, 0.5]).is_err());
+        assert!(BermudanOption::american(OptionType::Call, 40.0, 0.0, 10).is_err());
+        assert!(regress(&[(1.0, 1.0), (1.0, 2.0), (1.0, 3.0), (1.0, 4.0)], 2).is_none());
+    }
+}
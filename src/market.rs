@@ -0,0 +1,355 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::curves::{DiscountCurve, FlatCurve};
+use crate::error::BlackScholesError;
+use crate::model::EuropeanModel;
+use crate::pde::CashDividend;
+use crate::time_scale::{DaySchedule, TimeScale, CALENDAR_DAYS_PER_YEAR};
+use crate::validation;
+use crate::vol_surface::VolSurface;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+/// One coherent snapshot of the market for a single underlying
+///
+/// Instruments priced against the same context all see the same spot,
+/// carry, dividends, volatility surface and variance calendar, instead of
+/// each `BlackScholes` carrying its own copy of the market.
+///
+/// Cash dividends are handled with the escrowed-dividend approximation: the
+/// present value of dividends paid before an expiry is taken off the spot
+/// for that expiry.
+#[derive(Clone)]
+pub struct MarketContext {
+    /// Current price of the underlying asset
+    pub spot: f64,
+    /// Risk-free discount curve
+    pub discount_curve: Arc<dyn DiscountCurve + Send + Sync>,
+    /// Dividend/borrow yield as a discount curve (D_q(t) = e^(-q(t)·t))
+    pub dividend_curve: Arc<dyn DiscountCurve + Send + Sync>,
+    /// Discrete cash dividends, sorted by ex-date
+    pub dividends: Vec<CashDividend>,
+    /// Implied volatility surface quoted on `time_scale`
+    pub surface: Arc<dyn VolSurface + Send + Sync>,
+    /// Clock on which the surface's volatilities accrue
+    pub time_scale: TimeScale,
+    /// Per-day variance weights from today, used when `time_scale` is not calendar
+    pub calendar: Option<DaySchedule>,
+}
+
+impl MarketContext {
+    /// Create a context with flat carry, a calendar-time surface and no cash dividends
+    ///
+    /// # Arguments
+    /// * `spot` - Current price of the underlying asset
+    /// * `rate` - Risk-free interest rate as decimal
+    /// * `dividend_yield` - Continuous dividend yield as decimal
+    /// * `surface` - Implied volatility surface
+    pub fn new<S: VolSurface + Send + Sync + 'static>(
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        surface: S,
+    ) -> Result<Self, BlackScholesError> {
+        Ok(MarketContext {
+            spot: validation::positive("Spot price", spot)?,
+            discount_curve: Arc::new(FlatCurve::new(rate)?),
+            dividend_curve: Arc::new(FlatCurve::new(dividend_yield)?),
+            dividends: Vec::new(),
+            surface: Arc::new(surface),
+            time_scale: TimeScale::Calendar,
+            calendar: None,
+        })
+    }
+
+    /// Replace the flat carry with term structures
+    ///
+    /// # Arguments
+    /// * `discount_curve` - Risk-free discount curve
+    /// * `dividend_curve` - Dividend/borrow yield expressed as a discount curve
+    pub fn with_curves<R, Q>(mut self, discount_curve: R, dividend_curve: Q) -> Self
+    where
+        R: DiscountCurve + Send + Sync + 'static,
+        Q: DiscountCurve + Send + Sync + 'static,
+    {
+        self.discount_curve = Arc::new(discount_curve);
+        self.dividend_curve = Arc::new(dividend_curve);
+        self
+    }
+
+    /// Add discrete cash dividends
+    pub fn with_dividends(mut self, mut dividends: Vec<CashDividend>) -> Self {
+        dividends.sort_by(|a, b| a.time.total_cmp(&b.time));
+        self.dividends = dividends;
+        self
+    }
+
+    /// Quote the surface on a business-time clock with the given day weights
+    pub fn with_calendar(mut self, time_scale: TimeScale, calendar: DaySchedule) -> Self {
+        self.time_scale = time_scale;
+        self.calendar = Some(calendar);
+        self
+    }
+
+    /// Discount factor to `expiry`
+    pub fn discount(&self, expiry: f64) -> f64 {
+        self.discount_curve.df(expiry)
+    }
+
+    /// Present value of the cash dividends paid strictly before `expiry`
+    pub fn dividend_pv(&self, expiry: f64) -> f64 {
+        self.dividends
+            .iter()
+            .filter(|d| d.time < expiry)
+            .map(|d| d.amount * self.discount(d.time))
+            .sum()
+    }
+
+    /// Implied volatility from the surface
+    pub fn implied_vol(&self, strike: f64, expiry: f64) -> f64 {
+        self.surface.implied_vol(strike, expiry)
+    }
+
+    /// Black-Scholes model for one strike and expiry, read off this snapshot
+    ///
+    /// Rate and dividend yield are the curves' zero rates to `expiry`.
+    /// With a calendar set, the schedule is cut to the whole days to expiry and
+    /// the model is re-timed on it (see `BlackScholes::with_time_scale`).
+    pub fn model(&self, strike: f64, expiry: f64) -> Result<BlackScholes, BlackScholesError> {
+        let spot = self.spot - self.dividend_pv(expiry);
+        if spot <= 0.0 {
+            return Err(BlackScholesError::invalid("Dividends before expiry exceed the spot"));
+        }
+        let vol = self.implied_vol(strike, expiry);
+        let model = BlackScholes::new(spot, strike, expiry, self.rate(expiry), vol, self.dividend_yield(expiry))?;
+        match &self.calendar {
+            Some(calendar) if self.time_scale != TimeScale::Calendar => {
+                let days = (expiry * CALENDAR_DAYS_PER_YEAR).round() as usize;
+                let weights = calendar.weights();
+                if days > weights.len() {
+                    return Err(BlackScholesError::invalid("Expiry is beyond the market calendar"));
+                }
+                model.with_time_scale(self.time_scale, &DaySchedule::from_weights(weights[..days].to_vec())?)
+            }
+            _ => Ok(model),
+        }
+    }
+
+    /// Greeks of a vanilla option against this snapshot
+    pub fn greeks(&self, option_type: OptionType, strike: f64, expiry: f64) -> Result<Greeks, BlackScholesError> {
+        Ok(self.model(strike, expiry)?.greeks(option_type))
+    }
+}
+
+impl fmt::Debug for MarketContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MarketContext")
+            .field("spot", &self.spot)
+            .field("dividends", &self.dividends)
+            .field("time_scale", &self.time_scale)
+            .field("calendar", &self.calendar)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EuropeanModel for MarketContext {
+    fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    fn rate(&self, expiry: f64) -> f64 {
+        self.discount_curve.zero_rate(expiry)
+    }
+
+    fn dividend_yield(&self, expiry: f64) -> f64 {
+        self.dividend_curve.zero_rate(expiry)
+    }
+
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        match self.model(strike, expiry) {
+            Ok(model) => model.price(option_type),
+            Err(_) => f64::NAN,
+        }
+    }
+
+    /// Forward net of the carry yield and the cash dividends before `expiry`
+    fn forward(&self, expiry: f64) -> f64 {
+        (self.spot - self.dividend_pv(expiry)) * self.dividend_curve.df(expiry) / self.discount_curve.df(expiry)
+    }
+}
+
+/// Current market snapshot shared between threads
+///
+/// Readers take a read lock just long enough to clone an `Arc` to an immutable
+/// `MarketContext`, then price against it with no lock held; writers publish a
+/// whole new context in one swap. The lock is only held for the pointer copy,
+/// so a reader always sees either the old or the new snapshot in full, never
+/// a mix of the two.
+#[derive(Debug)]
+pub struct SharedMarket {
+    current: RwLock<(u64, Arc<MarketContext>)>,
+}
+
+impl SharedMarket {
+    pub fn new(market: MarketContext) -> Self {
+        SharedMarket {
+            current: RwLock::new((0, Arc::new(market))),
+        }
+    }
+
+    /// Latest snapshot
+    pub fn snapshot(&self) -> Arc<MarketContext> {
+        self.versioned().1
+    }
+
+    /// Latest snapshot together with its version (incremented by every swap)
+    pub fn versioned(&self) -> (u64, Arc<MarketContext>) {
+        let guard = self.current.read().unwrap_or_else(|e| e.into_inner());
+        (guard.0, Arc::clone(&guard.1))
+    }
+
+    /// Version of the latest snapshot
+    pub fn version(&self) -> u64 {
+        self.versioned().0
+    }
+
+    /// Publish a new snapshot
+    ///
+    /// # Returns
+    /// The snapshot it replaced; readers still holding it are unaffected
+    pub fn swap(&self, market: MarketContext) -> Arc<MarketContext> {
+        let mut guard = self.current.write().unwrap_or_else(|e| e.into_inner());
+        guard.0 += 1;
+        std::mem::replace(&mut guard.1, Arc::new(market))
+    }
+
+    /// Derive a new snapshot from the latest one and publish it atomically
+    ///
+    /// `update` runs outside the lock, so a slow rebuild never blocks readers.
+    /// The result is published only if no other swap happened meanwhile;
+    /// otherwise `update` is rerun on the newer snapshot, so no update is
+    /// lost. If `update` fails the current snapshot is left in place.
+    ///
+    /// # Returns
+    /// Version of the published snapshot
+    pub fn update<F>(&self, mut update: F) -> Result<u64, BlackScholesError>
+    where
+        F: FnMut(&MarketContext) -> Result<MarketContext, BlackScholesError>,
+    {
+        loop {
+            let (version, base) = self.versioned();
+            let next = update(&base)?;
+            let mut guard = self.current.write().unwrap_or_else(|e| e.into_inner());
+            if guard.0 == version {
+                guard.0 += 1;
+                guard.1 = Arc::new(next);
+                return Ok(guard.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vol_surface::FlatVol;
+
+    #[test]
+    fn test_flat_context_matches_black_scholes() {
+        let market = MarketContext::new(100.0, 0.03, 0.01, FlatVol::new(0.2)).unwrap();
+        let bs = BlackScholes::new(100.0, 105.0, 0.5, 0.03, 0.2, 0.01).unwrap();
+        let price = EuropeanModel::price(&market, OptionType::Call, 105.0, 0.5);
+        assert!((price - bs.price(OptionType::Call)).abs() < 1e-12);
+        let greeks = market.greeks(OptionType::Call, 105.0, 0.5).unwrap();
+        assert!((greeks.delta - bs.greeks(OptionType::Call).delta).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cash_dividends_lower_forward_and_calls() {
+        let plain = MarketContext::new(100.0, 0.03, 0.0, FlatVol::new(0.2)).unwrap();
+        let paying = plain.clone().with_dividends(vec![CashDividend::new(0.25, 2.0).unwrap()]);
+        let pv = 2.0 * (-0.03_f64 * 0.25).exp();
+        assert!((paying.forward(0.5) - (100.0 - pv) * (0.03_f64 * 0.5).exp()).abs() < 1e-12);
+        // Dividends after expiry do not matter
+        assert_eq!(paying.forward(0.2), plain.forward(0.2));
+        assert!(paying.price(OptionType::Call, 100.0, 0.5) < plain.price(OptionType::Call, 100.0, 0.5));
+    }
+
+    #[test]
+    fn test_business_calendar_retimes_models() {
+        let market = MarketContext::new(100.0, 0.03, 0.0, FlatVol::new(0.2))
+            .unwrap()
+            .with_calendar(TimeScale::Trading, DaySchedule::weekdays(28, 0));
+        let model = market.model(100.0, 14.0 / 365.0).unwrap();
+        assert!((model.vol_time - 10.0 / 252.0).abs() < 1e-15);
+        assert!(market.model(100.0, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_shared_market_swaps_whole_snapshots() {
+        let market = |i: u32| MarketContext::new(100.0 + i as f64, 0.001 * i as f64, 0.0, FlatVol::new(0.2)).unwrap();
+        let shared = Arc::new(SharedMarket::new(market(0)));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                std::thread::spawn(move || {
+                    for _ in 0..500 {
+                        let snapshot = shared.snapshot();
+                        // Spot and rate always come from the same publication
+                        assert!(((snapshot.spot - 100.0) * 0.001 - snapshot.rate(0.5)).abs() < 1e-12);
+                        assert!(snapshot.price(OptionType::Call, 100.0, 0.5).is_finite());
+                    }
+                })
+            })
+            .collect();
+        for i in 1..=200 {
+            shared.swap(market(i));
+        }
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(shared.version(), 200);
+
+        let held = shared.snapshot();
+        let version = shared.update(|m| MarketContext::new(m.spot * 1.01, m.rate(1.0), 0.0, FlatVol::new(0.25))).unwrap();
+        assert_eq!(version, 201);
+        assert_eq!(held.spot, 300.0);
+        assert!((shared.snapshot().spot - 303.0).abs() < 1e-12);
+        assert!(shared.update(|_| Err(BlackScholesError::invalid("stale quote"))).is_err());
+        assert_eq!(shared.version(), 201);
+    }
+
+    #[test]
+    fn test_update_runs_outside_the_lock_and_retries_on_conflict() {
+        let shared = SharedMarket::new(MarketContext::new(100.0, 0.03, 0.0, FlatVol::new(0.2)).unwrap());
+        let mut attempts = 0;
+        let version = shared
+            .update(|m| {
+                attempts += 1;
+                // Readers are not blocked while the new snapshot is built
+                assert_eq!(shared.snapshot().spot, m.spot);
+                if attempts == 1 {
+                    // Another writer publishes first: this attempt must be discarded
+                    shared.swap(MarketContext::new(110.0, 0.03, 0.0, FlatVol::new(0.2)).unwrap());
+                }
+                MarketContext::new(m.spot + 1.0, 0.03, 0.0, FlatVol::new(0.2))
+            })
+            .unwrap();
+        assert_eq!((attempts, version), (2, 2));
+        assert_eq!(shared.snapshot().spot, 111.0);
+    }
+
+    #[test]
+    fn test_term_structure_carry() {
+        use crate::curves::{InterpolatedCurve, Interpolation};
+        let discount = InterpolatedCurve::from_zero_rates(&[(0.5, 0.02), (1.0, 0.04)], Interpolation::LogLinear).unwrap();
+        let market = MarketContext::new(100.0, 0.0, 0.0, FlatVol::new(0.2))
+            .unwrap()
+            .with_curves(discount, FlatCurve::new(0.01).unwrap());
+        assert!((market.rate(1.0) - 0.04).abs() < 1e-14);
+        assert!((market.forward(1.0) - 100.0 * 0.03_f64.exp()).abs() < 1e-10);
+        let model = market.model(100.0, 0.5).unwrap();
+        assert!((model.risk_free_rate - 0.02).abs() < 1e-14);
+        assert!((model.dividend_yield - 0.01).abs() < 1e-14);
+    }
+}
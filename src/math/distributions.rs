@@ -0,0 +1,437 @@
+//! Univariate and bivariate standard normal distributions to double precision
+
+// Coefficients are kept exactly as published
+#![allow(clippy::excessive_precision)]
+
+use std::f64::consts::{FRAC_2_SQRT_PI, PI, SQRT_2};
+
+/// 1/√π
+const FRAC_1_SQRT_PI: f64 = 0.5 * FRAC_2_SQRT_PI;
+
+/// Evaluate a polynomial with coefficients in ascending order (Horner)
+fn poly(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+}
+
+/// Standard normal density φ(x)
+pub fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+}
+
+/// Standard normal CDF Φ(x)
+///
+/// Computed from Cody's rational approximations to erf/erfc (Algorithm 715),
+/// accurate to double precision with small relative error deep in the lower
+/// tail, where out-of-the-money prices and digital deltas live.
+pub fn norm_cdf(x: f64) -> f64 {
+    let y = x / SQRT_2;
+    if y.abs() <= 0.468_75 {
+        0.5 + 0.5 * erf_small(y)
+    } else if x < 0.0 {
+        0.5 * erfc_large(-y)
+    } else {
+        1.0 - 0.5 * erfc_large(y)
+    }
+}
+
+/// erf(y) for |y| ≤ 0.46875
+fn erf_small(y: f64) -> f64 {
+    const A: [f64; 5] = [
+        3.161_123_743_870_565_6,
+        1.138_641_541_510_501_56e2,
+        3.774_852_376_853_020_21e2,
+        3.209_377_589_138_469_47e3,
+        1.857_777_061_846_031_53e-1,
+    ];
+    const B: [f64; 4] = [
+        2.360_129_095_234_412_09e1,
+        2.440_246_379_344_441_73e2,
+        1.282_616_526_077_372_28e3,
+        2.844_236_833_439_170_62e3,
+    ];
+    let ysq = y * y;
+    let mut num = A[4] * ysq;
+    let mut den = ysq;
+    for i in 0..3 {
+        num = (num + A[i]) * ysq;
+        den = (den + B[i]) * ysq;
+    }
+    y * (num + A[3]) / (den + B[3])
+}
+
+/// erfc(y) for y > 0.46875
+fn erfc_large(y: f64) -> f64 {
+    if y.is_nan() {
+        return f64::NAN;
+    }
+    if y > 26.55 {
+        return 0.0;
+    }
+    let ratio = if y <= 4.0 {
+        const C: [f64; 9] = [
+            5.641_884_969_886_700_89e-1,
+            8.883_149_794_388_375_94,
+            6.611_919_063_714_162_95e1,
+            2.986_351_381_974_001_31e2,
+            8.819_522_212_417_690_9e2,
+            1.712_047_612_634_070_58e3,
+            2.051_078_377_826_071_47e3,
+            1.230_339_354_797_997_25e3,
+            2.153_115_354_744_038_46e-8,
+        ];
+        const D: [f64; 8] = [
+            1.574_492_611_070_983_47e1,
+            1.176_939_508_913_124_99e2,
+            5.371_811_018_620_098_58e2,
+            1.621_389_574_566_690_19e3,
+            3.290_799_235_733_459_63e3,
+            4.362_619_090_143_247_16e3,
+            3.439_367_674_143_721_64e3,
+            1.230_339_354_803_749_42e3,
+        ];
+        let mut num = C[8] * y;
+        let mut den = y;
+        for i in 0..7 {
+            num = (num + C[i]) * y;
+            den = (den + D[i]) * y;
+        }
+        (num + C[7]) / (den + D[7])
+    } else {
+        const P: [f64; 6] = [
+            3.053_266_349_612_323_44e-1,
+            3.603_448_999_498_044_39e-1,
+            1.257_817_261_112_292_46e-1,
+            1.608_378_514_874_227_66e-2,
+            6.587_491_615_298_378_03e-4,
+            1.631_538_713_730_209_78e-2,
+        ];
+        const Q: [f64; 5] = [
+            2.568_520_192_289_822_42,
+            1.872_952_849_923_467_25,
+            5.279_051_029_514_284_12e-1,
+            6.051_834_131_244_131_91e-2,
+            2.335_204_976_268_691_85e-3,
+        ];
+        let ysq = 1.0 / (y * y);
+        let mut num = P[5] * ysq;
+        let mut den = ysq;
+        for i in 0..4 {
+            num = (num + P[i]) * ysq;
+            den = (den + Q[i]) * ysq;
+        }
+        (FRAC_1_SQRT_PI - ysq * (num + P[4]) / (den + Q[4])) / y
+    };
+    // exp(-y²) split so the rounding of y² does not cost relative accuracy
+    let head = (y * 16.0).trunc() / 16.0;
+    let tail = (y - head) * (y + head);
+    ratio * (-head * head).exp() * (-tail).exp()
+}
+
+/// Inverse standard normal CDF Φ⁻¹(p)
+///
+/// Wichura's algorithm AS241 (PPND16), accurate to about 1e-16 relative.
+/// Returns -∞ for p ≤ 0, +∞ for p ≥ 1 and NaN for NaN.
+pub fn norm_inv_cdf(p: f64) -> f64 {
+    if p.is_nan() {
+        return f64::NAN;
+    }
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let q = p - 0.5;
+    if q.abs() <= 0.425 {
+        const A: [f64; 8] = [
+            3.387_132_872_796_366_608,
+            1.331_416_678_917_843_774_5e2,
+            1.971_590_950_306_551_442_7e3,
+            1.373_169_376_550_946_112_5e4,
+            4.592_195_393_154_987_145_7e4,
+            6.726_577_092_700_870_085_3e4,
+            3.343_057_558_358_812_810_5e4,
+            2.509_080_928_730_122_672_7e3,
+        ];
+        const B: [f64; 8] = [
+            1.0,
+            4.231_333_070_160_091_125_2e1,
+            6.871_870_074_920_579_083e2,
+            5.394_196_021_424_751_107_7e3,
+            2.121_379_430_158_659_586_7e4,
+            3.930_789_580_009_271_061e4,
+            2.872_908_573_572_194_267_4e4,
+            5.226_495_278_852_854_561e3,
+        ];
+        let r = 0.180_625 - q * q;
+        return q * poly(&A, r) / poly(&B, r);
+    }
+
+    let r = (-(if q < 0.0 { p } else { 1.0 - p }).ln()).sqrt();
+    let magnitude = if r <= 5.0 {
+        const C: [f64; 8] = [
+            1.423_437_110_749_683_577_34,
+            4.630_337_846_156_545_295_9,
+            5.769_497_221_460_691_405_5,
+            3.647_848_324_763_204_605_04,
+            1.270_458_252_452_368_382_58,
+            2.417_807_251_774_506_117_7e-1,
+            2.272_384_498_926_918_458_33e-2,
+            7.745_450_142_783_414_076_4e-4,
+        ];
+        const D: [f64; 8] = [
+            1.0,
+            2.053_191_626_637_758_821_87,
+            1.676_384_830_183_803_849_4,
+            6.897_673_349_851_000_045_5e-1,
+            1.481_039_764_274_800_745_9e-1,
+            1.519_866_656_361_645_719_66e-2,
+            5.475_938_084_995_344_946e-4,
+            1.050_750_071_644_416_843_24e-9,
+        ];
+        let r = r - 1.6;
+        poly(&C, r) / poly(&D, r)
+    } else {
+        const E: [f64; 8] = [
+            6.657_904_643_501_103_777_2,
+            5.463_784_911_164_114_369_9,
+            1.784_826_539_917_291_335_8,
+            2.965_605_718_285_048_912_3e-1,
+            2.653_218_952_657_612_309_3e-2,
+            1.242_660_947_388_078_438_6e-3,
+            2.711_555_568_743_487_578_15e-5,
+            2.010_334_399_292_288_132_65e-7,
+        ];
+        const F: [f64; 8] = [
+            1.0,
+            5.998_322_065_558_879_376_9e-1,
+            1.369_298_809_227_358_053_1e-1,
+            1.487_536_129_085_061_485_25e-2,
+            7.868_691_311_456_132_591e-4,
+            1.846_318_317_510_054_681_8e-5,
+            1.421_511_758_316_445_888_7e-7,
+            2.044_263_103_389_939_785_64e-15,
+        ];
+        let r = r - 5.0;
+        poly(&E, r) / poly(&F, r)
+    };
+    if q < 0.0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Bivariate standard normal CDF P(X ≤ x, Y ≤ y) with correlation `rho`
+///
+/// Genz's algorithm ("Numerical computation of rectangular bivariate and
+/// trivariate normal and t probabilities", 2004), the refinement of
+/// Drezner-Wesolowsky: Gauss-Legendre quadrature over the correlation with
+/// 6, 12 or 20 points, and an asymptotic expansion for |rho| ≥ 0.925.
+/// Accurate to about 1e-15. Returns NaN when |rho| > 1.
+pub fn bivariate_norm_cdf(x: f64, y: f64, rho: f64) -> f64 {
+    if x.is_nan() || y.is_nan() || rho.is_nan() || rho.abs() > 1.0 {
+        return f64::NAN;
+    }
+    // Genz integrates the upper orthant P(X > h, Y > k)
+    let h = -x;
+    let mut k = -y;
+    let mut hk = h * k;
+    let (weights, abscissae) = gauss_legendre_half(rho.abs());
+    let two_pi = 2.0 * PI;
+    let mut bvn = 0.0;
+
+    if rho.abs() < 0.925 {
+        if rho != 0.0 {
+            let hs = 0.5 * (h * h + k * k);
+            let asr = rho.asin();
+            for (&w, &t) in weights.iter().zip(abscissae) {
+                for sign in [-1.0, 1.0] {
+                    let sn = (0.5 * asr * (sign * t + 1.0)).sin();
+                    bvn += w * ((sn * hk - hs) / (1.0 - sn * sn)).exp();
+                }
+            }
+            bvn *= asr / (2.0 * two_pi);
+        }
+        return bvn + norm_cdf(-h) * norm_cdf(-k);
+    }
+
+    if rho < 0.0 {
+        k = -k;
+        hk = -hk;
+    }
+    if rho.abs() < 1.0 {
+        let a_sq = (1.0 - rho) * (1.0 + rho);
+        let mut a = a_sq.sqrt();
+        let b_sq = (h - k) * (h - k);
+        let c = (4.0 - hk) / 8.0;
+        let d = (12.0 - hk) / 16.0;
+        let asr = -0.5 * (b_sq / a_sq + hk);
+        if asr > -100.0 {
+            bvn = a * asr.exp() * (1.0 - c * (b_sq - a_sq) * (1.0 - d * b_sq / 5.0) / 3.0 + c * d * a_sq * a_sq / 5.0);
+        }
+        if -hk < 100.0 {
+            let b = b_sq.sqrt();
+            bvn -= (-0.5 * hk).exp() * two_pi.sqrt() * norm_cdf(-b / a) * b * (1.0 - c * b_sq * (1.0 - d * b_sq / 5.0) / 3.0);
+        }
+        a *= 0.5;
+        for (&w, &t) in weights.iter().zip(abscissae) {
+            for sign in [-1.0, 1.0] {
+                let xs = (a * (sign * t + 1.0)).powi(2);
+                let rs = (1.0 - xs).sqrt();
+                let asr = -0.5 * (b_sq / xs + hk);
+                if asr > -100.0 {
+                    bvn += a
+                        * w
+                        * asr.exp()
+                        * ((-hk * xs / (2.0 * (1.0 + rs).powi(2))).exp() / rs - (1.0 + c * xs * (1.0 + d * xs)));
+                }
+            }
+        }
+        bvn = -bvn / two_pi;
+    }
+    if rho > 0.0 {
+        bvn + norm_cdf(-h.max(k))
+    } else {
+        let mut bvn = -bvn;
+        if k > h {
+            bvn += if h < 0.0 {
+                norm_cdf(k) - norm_cdf(h)
+            } else {
+                norm_cdf(-h) - norm_cdf(-k)
+            };
+        }
+        // Cancellation can leave a tiny negative probability
+        bvn.max(0.0)
+    }
+}
+
+/// Gauss-Legendre weights and negative abscissae on [-1, 1], finer as |rho| grows
+fn gauss_legendre_half(abs_rho: f64) -> (&'static [f64], &'static [f64]) {
+    const W6: [f64; 3] = [0.171_324_492_379_170_5, 0.360_761_573_048_138_4, 0.467_913_934_572_690_4];
+    const X6: [f64; 3] = [-0.932_469_514_203_152_2, -0.661_209_386_466_264_7, -0.238_619_186_083_197];
+    const W12: [f64; 6] = [
+        0.047_175_336_386_511_77,
+        0.106_939_325_995_318_3,
+        0.160_078_328_543_346_4,
+        0.203_167_426_723_065_9,
+        0.233_492_536_538_354_7,
+        0.249_147_045_813_402_9,
+    ];
+    const X12: [f64; 6] = [
+        -0.981_560_634_246_719_1,
+        -0.904_117_256_370_475,
+        -0.769_902_674_194_305,
+        -0.587_317_954_286_617_1,
+        -0.367_831_498_998_180_2,
+        -0.125_233_408_511_469_2,
+    ];
+    const W20: [f64; 10] = [
+        0.017_614_007_139_152_12,
+        0.040_601_429_800_386_94,
+        0.062_672_048_334_109_06,
+        0.083_276_741_576_704_75,
+        0.101_930_119_817_240_4,
+        0.118_194_531_961_518_4,
+        0.131_688_638_449_176_6,
+        0.142_096_109_318_382_1,
+        0.149_172_986_472_603_7,
+        0.152_753_387_130_725_9,
+    ];
+    const X20: [f64; 10] = [
+        -0.993_128_599_185_094_9,
+        -0.963_971_927_277_913_8,
+        -0.912_234_428_251_325_9,
+        -0.839_116_971_822_218_8,
+        -0.746_331_906_460_150_8,
+        -0.636_053_680_726_515,
+        -0.510_867_001_950_827_1,
+        -0.373_706_088_715_419_6,
+        -0.227_785_851_141_645_1,
+        -0.076_526_521_133_497_33,
+    ];
+    if abs_rho < 0.3 {
+        (&W6, &X6)
+    } else if abs_rho < 0.75 {
+        (&W12, &X12)
+    } else {
+        (&W20, &X20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_norm_cdf_reference_values() {
+        // Values from a 30-digit evaluation of erfc
+        for (x, expected) in [
+            (0.0, 0.5),
+            (1.0, 0.841_344_746_068_542_9),
+            (-1.959_963_984_540_054, 0.025),
+            (-5.0, 2.866_515_718_791_939e-7),
+            (-10.0, 7.619_853_024_160_527e-24),
+            (-20.0, 2.753_624_118_606_233e-89),
+        ] {
+            let got = norm_cdf(x);
+            assert!(((got - expected) / expected).abs() < 1e-13, "Φ({}) = {} vs {}", x, got, expected);
+        }
+        assert_eq!(norm_cdf(-40.0), 0.0);
+        assert_eq!(norm_cdf(40.0), 1.0);
+    }
+
+    #[test]
+    fn test_inverse_round_trip() {
+        for &p in &[1e-20, 1e-8, 0.001, 0.025, 0.3, 0.5, 0.7, 0.975, 0.999_999] {
+            let x = norm_inv_cdf(p);
+            assert!(((norm_cdf(x) - p) / p).abs() < 1e-13, "p = {}", p);
+        }
+        // Far in the tail Φ amplifies relative errors by x², so compare quantiles
+        let x = norm_inv_cdf(1e-300);
+        assert!((norm_inv_cdf(norm_cdf(x)) - x).abs() < 1e-13 * x.abs());
+        assert!((norm_inv_cdf(0.975) - 1.959_963_984_540_054).abs() < 1e-15);
+        assert_eq!(norm_inv_cdf(0.5), 0.0);
+        assert_eq!(norm_inv_cdf(0.0), f64::NEG_INFINITY);
+        assert_eq!(norm_inv_cdf(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_symmetry() {
+        for &x in &[0.1, 0.9, 2.5, 6.0] {
+            assert!((norm_cdf(x) + norm_cdf(-x) - 1.0).abs() < 1e-15);
+            assert_eq!(norm_pdf(x), norm_pdf(-x));
+        }
+    }
+
+    #[test]
+    fn test_bivariate_reference_values() {
+        // Quadrature of φ(t)·Φ((y - ρt)/√(1 - ρ²)) to 25 digits, covering all three rules
+        for (x, y, rho, expected) in [
+            (0.3, -0.4, 0.2, 0.240_808_015_097_644_85),
+            (1.0, 0.5, 0.6, 0.641_828_990_063_871_3),
+            (-1.0, 2.0, -0.8, 0.137_795_669_999_201_5),
+            (0.5, 0.4, 0.95, 0.625_574_743_826_848_3),
+            (1.5, -0.2, 0.999, 0.420_740_290_560_896_96),
+            (-3.0, -3.0, 0.5, 8.188_966_183_219_21e-5),
+        ] {
+            let got = bivariate_norm_cdf(x, y, rho);
+            assert!((got - expected).abs() < 1e-15, "M({}, {}, {}) = {} vs {}", x, y, rho, got, expected);
+        }
+    }
+
+    #[test]
+    fn test_bivariate_special_cases() {
+        // Orthant probability Φ₂(0, 0, ρ) = 1/4 + asin(ρ)/2π
+        for rho in [-0.99, -0.5, 0.0, 0.4, 0.93] {
+            let expected = 0.25 + f64::asin(rho) / (2.0 * PI);
+            assert!((bivariate_norm_cdf(0.0, 0.0, rho) - expected).abs() < 1e-15);
+        }
+        assert!((bivariate_norm_cdf(0.7, -0.3, 0.0) - norm_cdf(0.7) * norm_cdf(-0.3)).abs() < 1e-16);
+        assert!((bivariate_norm_cdf(0.7, -0.3, 1.0) - norm_cdf(-0.3)).abs() < 1e-16);
+        assert!((bivariate_norm_cdf(0.7, -0.3, -1.0) - (norm_cdf(0.7) - norm_cdf(0.3))).abs() < 1e-15);
+        assert_eq!(bivariate_norm_cdf(-2.0, -1.5, -0.97), 0.0);
+        assert!((bivariate_norm_cdf(1.2, 0.4, 0.3) - bivariate_norm_cdf(0.4, 1.2, 0.3)).abs() < 1e-16);
+        assert!(bivariate_norm_cdf(0.0, 0.0, 1.5).is_nan());
+    }
+}
@@ -0,0 +1,260 @@
+use super::distributions;
+use std::f64::consts::PI;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Scalar that pricing formulas can be written over
+///
+/// `f64` is the plain case; `Dual` carries derivatives through the same code,
+/// so a formula written once yields its Greeks exactly.
+pub trait Real:
+    Copy
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Add<f64, Output = Self>
+    + Sub<f64, Output = Self>
+    + Mul<f64, Output = Self>
+    + Div<f64, Output = Self>
+{
+    /// Lift a constant (all derivatives zero)
+    fn constant(x: f64) -> Self;
+
+    /// Plain value, for branching on the scalar
+    fn value(&self) -> f64;
+
+    fn exp(self) -> Self;
+
+    fn ln(self) -> Self;
+
+    fn sqrt(self) -> Self;
+
+    /// Standard normal CDF
+    fn norm_cdf(self) -> Self;
+
+    /// Standard normal density
+    fn norm_pdf(self) -> Self {
+        (self * self * -0.5).exp() / (2.0 * PI).sqrt()
+    }
+
+    /// max(x, 0), with zero derivatives below the kink
+    fn positive_part(self) -> Self {
+        if self.value() > 0.0 {
+            self
+        } else {
+            Self::constant(0.0)
+        }
+    }
+}
+
+impl Real for f64 {
+    fn constant(x: f64) -> Self {
+        x
+    }
+
+    fn value(&self) -> f64 {
+        *self
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn norm_cdf(self) -> Self {
+        distributions::norm_cdf(self)
+    }
+}
+
+/// Forward-mode dual number with `N` tangent directions
+///
+/// `eps[i]` holds the derivative along input `i`, so one evaluation gives
+/// the full gradient. Nesting (`Dual<Dual<f64, 1>, N>`) adds a second order:
+/// `eps[i].eps[0]` is the cross derivative of input `i` with the inner one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dual<T, const N: usize> {
+    pub re: T,
+    pub eps: [T; N],
+}
+
+impl<T: Real, const N: usize> Dual<T, N> {
+    /// Independent variable seeded along direction `index`
+    pub fn variable(re: T, index: usize) -> Self {
+        let mut eps = [T::constant(0.0); N];
+        eps[index] = T::constant(1.0);
+        Dual { re, eps }
+    }
+
+    /// Value with zero derivatives
+    pub fn lift(re: T) -> Self {
+        Dual {
+            re,
+            eps: [T::constant(0.0); N],
+        }
+    }
+
+    /// Apply a scalar function given its value and derivative at `re`
+    fn chain(self, value: T, derivative: T) -> Self {
+        Dual {
+            re: value,
+            eps: self.eps.map(|e| e * derivative),
+        }
+    }
+}
+
+impl<T: Real, const N: usize> Real for Dual<T, N> {
+    fn constant(x: f64) -> Self {
+        Dual::lift(T::constant(x))
+    }
+
+    fn value(&self) -> f64 {
+        self.re.value()
+    }
+
+    fn exp(self) -> Self {
+        let e = self.re.exp();
+        self.chain(e, e)
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.re.ln(), T::constant(1.0) / self.re)
+    }
+
+    fn sqrt(self) -> Self {
+        let s = self.re.sqrt();
+        self.chain(s, T::constant(0.5) / s)
+    }
+
+    fn norm_cdf(self) -> Self {
+        self.chain(self.re.norm_cdf(), self.re.norm_pdf())
+    }
+}
+
+impl<T: Real, const N: usize> Add for Dual<T, N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let mut eps = self.eps;
+        for (e, r) in eps.iter_mut().zip(rhs.eps) {
+            *e = *e + r;
+        }
+        Dual { re: self.re + rhs.re, eps }
+    }
+}
+
+impl<T: Real, const N: usize> Sub for Dual<T, N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<T: Real, const N: usize> Mul for Dual<T, N> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let mut eps = self.eps;
+        for (e, r) in eps.iter_mut().zip(rhs.eps) {
+            *e = *e * rhs.re + self.re * r;
+        }
+        Dual { re: self.re * rhs.re, eps }
+    }
+}
+
+impl<T: Real, const N: usize> Div for Dual<T, N> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let re = self.re / rhs.re;
+        let mut eps = self.eps;
+        for (e, r) in eps.iter_mut().zip(rhs.eps) {
+            *e = (*e - re * r) / rhs.re;
+        }
+        Dual { re, eps }
+    }
+}
+
+impl<T: Real, const N: usize> Neg for Dual<T, N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Dual {
+            re: -self.re,
+            eps: self.eps.map(|e| -e),
+        }
+    }
+}
+
+impl<T: Real, const N: usize> Add<f64> for Dual<T, N> {
+    type Output = Self;
+    fn add(self, rhs: f64) -> Self {
+        Dual {
+            re: self.re + rhs,
+            eps: self.eps,
+        }
+    }
+}
+
+impl<T: Real, const N: usize> Sub<f64> for Dual<T, N> {
+    type Output = Self;
+    fn sub(self, rhs: f64) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<T: Real, const N: usize> Mul<f64> for Dual<T, N> {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Dual {
+            re: self.re * rhs,
+            eps: self.eps.map(|e| e * rhs),
+        }
+    }
+}
+
+impl<T: Real, const N: usize> Div<f64> for Dual<T, N> {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        self * (1.0 / rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient_of_elementary_functions() {
+        // f(x, y) = x·e^y / sqrt(x + y) at (2, 0.5)
+        let x = Dual::<f64, 2>::variable(2.0, 0);
+        let y = Dual::<f64, 2>::variable(0.5, 1);
+        let f = x * y.exp() / (x + y).sqrt();
+        let g = |x: f64, y: f64| x * y.exp() / (x + y).sqrt();
+        let h = 1e-6;
+        assert!((f.re - g(2.0, 0.5)).abs() < 1e-15);
+        assert!((f.eps[0] - (g(2.0 + h, 0.5) - g(2.0 - h, 0.5)) / (2.0 * h)).abs() < 1e-8);
+        assert!((f.eps[1] - (g(2.0, 0.5 + h) - g(2.0, 0.5 - h)) / (2.0 * h)).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_nested_dual_gives_second_derivatives() {
+        // d²/dx² ln(x)·x³ = 5x + 6x·ln(x)
+        let x = Dual::<Dual<f64, 1>, 1>::variable(Dual::variable(1.5, 0), 0);
+        let f = x.ln() * x * x * x;
+        let expected = 5.0 * 1.5 + 6.0 * 1.5 * 1.5_f64.ln();
+        assert!((f.eps[0].eps[0] - expected).abs() < 1e-12);
+        assert!((f.eps[0].re - f.re.eps[0]).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_norm_cdf_derivative_is_density() {
+        let x = Dual::<f64, 1>::variable(0.3, 0);
+        let n = x.norm_cdf();
+        assert_eq!(n.re, 0.3_f64.norm_cdf());
+        assert!((n.eps[0] - 0.3_f64.norm_pdf()).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,70 @@
+use super::complex::Complex;
+use std::f64::consts::PI;
+
+/// In-place discrete Fourier transform X_m = Σ_j x_j·e^(-2πi·jm/N)
+///
+/// Iterative radix-2 Cooley-Tukey, O(N log N).
+///
+/// # Panics
+/// If the length is not a power of two
+pub fn fft(values: &mut [Complex]) {
+    let n = values.len();
+    assert!(n.is_power_of_two(), "FFT length must be a power of two");
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        for start in (0..n).step_by(len) {
+            for k in 0..half {
+                let twiddle = Complex::from_polar(1.0, -2.0 * PI * k as f64 / len as f64);
+                let a = values[start + k];
+                let b = values[start + k + half] * twiddle;
+                values[start + k] = a + b;
+                values[start + k + half] = a - b;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_direct_transform() {
+        let input: Vec<Complex> = (0..16)
+            .map(|j| Complex::new((0.3 * j as f64).sin(), 1.0 / (1.0 + j as f64)))
+            .collect();
+        let mut values = input.clone();
+        fft(&mut values);
+        for (m, value) in values.iter().enumerate() {
+            let direct = input.iter().enumerate().fold(Complex::real(0.0), |acc, (j, x)| {
+                acc + *x * Complex::from_polar(1.0, -2.0 * PI * (j * m) as f64 / 16.0)
+            });
+            assert!((*value - direct).abs() < 1e-13);
+        }
+    }
+
+    #[test]
+    fn test_constant_transforms_to_an_impulse() {
+        let mut values = [Complex::real(2.0); 8];
+        fft(&mut values);
+        assert!((values[0].re - 16.0).abs() < 1e-14);
+        assert!(values[1..].iter().all(|v| v.abs() < 1e-14));
+    }
+}
@@ -0,0 +1,187 @@
+//! Gamma function, regularized incomplete gamma and noncentral chi-square distributions
+
+// Coefficients are kept exactly as published
+#![allow(clippy::excessive_precision)]
+
+use std::f64::consts::PI;
+
+/// Lanczos approximation coefficients (g = 7, n = 9)
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_93,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_13,
+    -176.615_029_162_140_59,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_571_6e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Relative tolerance of the incomplete gamma series and continued fraction
+const GAMMA_TOLERANCE: f64 = 1e-15;
+
+/// Iteration cap of the incomplete gamma expansions
+const GAMMA_MAX_ITERATIONS: usize = 100_000;
+
+/// Poisson weight below which the noncentral chi-square mixture is truncated
+const MIXTURE_TOLERANCE: f64 = 1e-17;
+
+/// ln Γ(x) for x > 0 (Lanczos, about 15 significant digits)
+pub fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection: Γ(x)·Γ(1 - x) = π / sin(πx)
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = LANCZOS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS[0], |acc, (i, &c)| acc + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// Regularized lower incomplete gamma P(a, x) = γ(a, x)/Γ(a)
+pub fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        0.0
+    } else if x < a + 1.0 {
+        gamma_series(a, x)
+    } else {
+        1.0 - gamma_continued_fraction(a, x)
+    }
+}
+
+/// Regularized upper incomplete gamma Q(a, x) = 1 - P(a, x), accurate in the upper tail
+pub fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        1.0
+    } else if x < a + 1.0 {
+        1.0 - gamma_series(a, x)
+    } else {
+        gamma_continued_fraction(a, x)
+    }
+}
+
+/// P(a, x) by its power series, for x < a + 1
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let (mut term, mut sum) = (1.0 / a, 1.0 / a);
+    for n in 1..GAMMA_MAX_ITERATIONS {
+        term *= x / (a + n as f64);
+        sum += term;
+        if term.abs() < sum.abs() * GAMMA_TOLERANCE {
+            break;
+        }
+    }
+    sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+/// Q(a, x) by Lentz's continued fraction, for x ≥ a + 1
+fn gamma_continued_fraction(a: f64, x: f64) -> f64 {
+    let tiny = f64::MIN_POSITIVE / GAMMA_TOLERANCE;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for n in 1..GAMMA_MAX_ITERATIONS {
+        let an = -(n as f64) * (n as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = b + an / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < GAMMA_TOLERANCE {
+            break;
+        }
+    }
+    h * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+/// Noncentral chi-square CDF with `df` degrees of freedom and noncentrality `nc`
+///
+/// Poisson mixture of central chi-square CDFs, Σ e^(-λ/2)(λ/2)^j/j!·P(df/2 + j, x/2),
+/// summed outward from the largest Poisson weight so large noncentralities
+/// stay accurate.
+pub fn noncentral_chi_square_cdf(x: f64, df: f64, nc: f64) -> f64 {
+    poisson_mixture(x, df, nc, gamma_p)
+}
+
+/// Noncentral chi-square survival function 1 - F(x), accurate in the upper tail
+pub fn noncentral_chi_square_sf(x: f64, df: f64, nc: f64) -> f64 {
+    poisson_mixture(x, df, nc, gamma_q)
+}
+
+/// Σ Poisson(j; nc/2)·tail(df/2 + j, x/2) summed outward from the mode
+fn poisson_mixture(x: f64, df: f64, nc: f64, tail: fn(f64, f64) -> f64) -> f64 {
+    let half = 0.5 * nc;
+    if half <= 0.0 {
+        return tail(0.5 * df, 0.5 * x);
+    }
+    let mode = half.floor();
+    let weight_at = |j: f64| (-half + j * half.ln() - ln_gamma(j + 1.0)).exp();
+    let peak = weight_at(mode);
+    let mut total = peak * tail(0.5 * df + mode, 0.5 * x);
+
+    let mut weight = peak;
+    let mut j = mode;
+    while weight > MIXTURE_TOLERANCE {
+        j += 1.0;
+        weight *= half / j;
+        total += weight * tail(0.5 * df + j, 0.5 * x);
+    }
+    let (mut weight, mut j) = (peak, mode);
+    while j > 0.0 && weight > MIXTURE_TOLERANCE {
+        weight *= j / half;
+        j -= 1.0;
+        total += weight * tail(0.5 * df + j, 0.5 * x);
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gamma_function() {
+        assert!(ln_gamma(1.0).abs() < 1e-14);
+        assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-13);
+        assert!((ln_gamma(0.5) - PI.sqrt().ln()).abs() < 1e-14);
+        assert!((ln_gamma(0.1) - 2.252_712_651_734_206).abs() < 1e-13);
+        assert!((ln_gamma(170.5) - 704.004_427_734_204_7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_incomplete_gamma() {
+        // P(1, x) = 1 - e^(-x), and P(½, 2) = erf(√2)
+        for x in [0.1, 1.0, 5.0, 30.0] {
+            assert!((gamma_p(1.0, x) - (1.0 - (-x).exp())).abs() < 1e-15);
+            assert!((gamma_q(1.0, x) / (-x).exp() - 1.0).abs() < 1e-13);
+        }
+        assert!((gamma_p(0.5, 2.0) - 0.954_499_736_103_641_6).abs() < 1e-14);
+        assert!((gamma_p(3.0, 2.5) + gamma_q(3.0, 2.5) - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_noncentral_chi_square() {
+        // Reference values from the mixture summed to 30 digits
+        for (x, df, nc, expected) in [
+            (2.0, 1.0, 1.0, 0.652_756_536_682_269_7),
+            (10.0, 4.0, 5.0, 0.638_228_859_582_310_9),
+            (150.0, 10.0, 120.0, 0.817_079_416_046_584),
+        ] {
+            let got = noncentral_chi_square_cdf(x, df, nc);
+            assert!((got - expected).abs() < 1e-12, "F({x}; {df}, {nc}) = {got} vs {expected}");
+            assert!((got + noncentral_chi_square_sf(x, df, nc) - 1.0).abs() < 1e-13);
+        }
+        assert_eq!(noncentral_chi_square_cdf(3.0, 2.0, 0.0), gamma_p(1.0, 1.5));
+    }
+}
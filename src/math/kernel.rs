@@ -0,0 +1,379 @@
+//! Vectorizable exp, ln and normal CDF over slices for the batch pricing paths
+//!
+//! Each function is written branch-free, as polynomials, bit manipulation and
+//! selects, so the compiler can vectorize the loop over a slice. At runtime the
+//! loop is dispatched to a copy compiled for AVX2 when the CPU supports it,
+//! falling back to the baseline target otherwise. Neither copy uses fused
+//! multiply-adds, so both give bit-identical results.
+//!
+//! Maximum relative errors against the scalar reference, measured by the
+//! tests below:
+//! * `exp` - 2.5e-16 on [-700, 700]; results below e^-707.7 flush to zero
+//! * `ln` - 4e-16 on positive inputs, subnormals included
+//! * `norm_cdf` - 1e-15 on [-37, 8.3], using the same Cody rational
+//!   approximations as `distributions::norm_cdf`
+
+// Coefficients are kept exactly as published
+#![allow(clippy::excessive_precision)]
+
+use std::f64::consts::{FRAC_2_SQRT_PI, LOG2_E, SQRT_2};
+
+/// 1.5·2^52: adding it rounds a double to an integer held in the low mantissa bits
+const ROUNDING_SHIFT: f64 = 6_755_399_441_055_744.0;
+
+/// ln 2 split so n·LN_2_HI is exact for |n| < 2^11 (Cody-Waite reduction)
+const LN_2_HI: f64 = 6.931_471_803_691_238_164_90e-1;
+const LN_2_LO: f64 = 1.908_214_929_270_587_700_02e-10;
+
+/// Largest argument with a finite exponential
+const EXP_OVERFLOW: f64 = 709.782_712_893_384;
+
+/// Smallest argument whose exponential is built as a normal double
+const EXP_UNDERFLOW: f64 = -707.7;
+
+/// 2^52
+const TWO_POW_52: f64 = 4_503_599_627_370_496.0;
+
+/// Elements processed per pass of the multi-pass kernels, sized for stack buffers
+const CHUNK: usize = 256;
+
+/// e^x in place
+pub fn exp(values: &mut [f64]) {
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2, checked just above
+        return unsafe { avx2::exp(values) };
+    }
+    exp_slice(values);
+}
+
+/// Natural logarithm in place
+pub fn ln(values: &mut [f64]) {
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2, checked just above
+        return unsafe { avx2::ln(values) };
+    }
+    ln_slice(values);
+}
+
+/// Standard normal CDF Φ(x) in place
+pub fn norm_cdf(values: &mut [f64]) {
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2, checked just above
+        return unsafe { avx2::norm_cdf(values) };
+    }
+    norm_cdf_slice(values);
+}
+
+/// The kernels compiled for AVX2; callers must check the CPU supports it
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    #[target_feature(enable = "avx2")]
+    pub(super) fn exp(values: &mut [f64]) {
+        super::exp_slice(values);
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) fn ln(values: &mut [f64]) {
+        super::ln_slice(values);
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) fn norm_cdf(values: &mut [f64]) {
+        super::norm_cdf_slice(values);
+    }
+}
+
+#[inline(always)]
+fn exp_slice(values: &mut [f64]) {
+    for value in values.iter_mut() {
+        *value = exp_lane(*value);
+    }
+}
+
+#[inline(always)]
+fn ln_slice(values: &mut [f64]) {
+    for value in values.iter_mut() {
+        *value = ln_lane(*value);
+    }
+}
+
+/// Φ(x) in passes over chunks: the rational part, the Gaussian factor
+/// through `exp`, then the combination, so every loop stays small enough
+/// to vectorize
+#[inline(always)]
+fn norm_cdf_slice(values: &mut [f64]) {
+    let mut ratio = [0.0; CHUNK];
+    let mut head = [0.0; CHUNK];
+    let mut tail = [0.0; CHUNK];
+    for chunk in values.chunks_mut(CHUNK) {
+        let n = chunk.len();
+        for (i, &x) in chunk.iter().enumerate() {
+            // e^(-y²) split so the rounding of y² does not cost relative accuracy
+            let a = (x / SQRT_2).abs().min(ERFC_CUTOFF);
+            let h = (a * 16.0).trunc() / 16.0;
+            ratio[i] = cody_ratio(x);
+            head[i] = -h * h;
+            tail[i] = -(a - h) * (a + h);
+        }
+        exp_slice(&mut head[..n]);
+        exp_slice(&mut tail[..n]);
+        for (i, x) in chunk.iter_mut().enumerate() {
+            *x = combine(*x, ratio[i], head[i] * tail[i]);
+        }
+    }
+}
+
+/// e^x = 2^n·e^r with |r| ≤ ln2/2, e^r by its degree-13 Taylor polynomial
+#[inline(always)]
+fn exp_lane(x: f64) -> f64 {
+    let clamped = x.clamp(EXP_UNDERFLOW, EXP_OVERFLOW);
+    let shifted = clamped * LOG2_E + ROUNDING_SHIFT;
+    let n = shifted - ROUNDING_SHIFT;
+    let r = (clamped - n * LN_2_HI) - n * LN_2_LO;
+    let p = INVERSE_FACTORIALS.iter().rev().fold(0.0, |acc, &c| acc * r + c);
+    // 2^(n - 1), doubled afterwards so n = 1024 stays representable
+    let n_bits = shifted.to_bits().wrapping_sub(ROUNDING_SHIFT.to_bits()) as i64;
+    let scale = f64::from_bits(((n_bits + 1022) as u64) << 52);
+    let y = p * scale * 2.0;
+    if x.is_nan() {
+        x
+    } else if x > EXP_OVERFLOW {
+        f64::INFINITY
+    } else if x < EXP_UNDERFLOW {
+        0.0
+    } else {
+        y
+    }
+}
+
+/// 1/k! for k = 0..=13, the Taylor coefficients of e^r
+const INVERSE_FACTORIALS: [f64; 14] = [
+    1.0,
+    1.0,
+    1.0 / 2.0,
+    1.0 / 6.0,
+    1.0 / 24.0,
+    1.0 / 120.0,
+    1.0 / 720.0,
+    1.0 / 5_040.0,
+    1.0 / 40_320.0,
+    1.0 / 362_880.0,
+    1.0 / 3_628_800.0,
+    1.0 / 39_916_800.0,
+    1.0 / 479_001_600.0,
+    1.0 / 6_227_020_800.0,
+];
+
+/// ln x = e·ln2 + 2·atanh((m - 1)/(m + 1)) with m in [√½, √2)
+#[inline(always)]
+fn ln_lane(x: f64) -> f64 {
+    // Bring subnormals into the normal range first
+    let subnormal = x < f64::MIN_POSITIVE;
+    let scaled = if subnormal { x * TWO_POW_52 } else { x };
+    let bits = scaled.to_bits();
+    // Biased exponent as a double via the 2^52 magic number (no i64 → f64 conversion)
+    let biased = f64::from_bits(0x4330_0000_0000_0000 | (bits >> 52)) - TWO_POW_52;
+    let mut exponent = biased - 1023.0 - if subnormal { 52.0 } else { 0.0 };
+    let mut m = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+    if m > SQRT_2 {
+        m *= 0.5;
+        exponent += 1.0;
+    }
+    let f = (m - 1.0) / (m + 1.0);
+    let s = f * f;
+    // 2·atanh(f) = 2f + 2f·Σ s^k/(2k + 1) over k = 1..=10 (|f| ≤ 0.1716),
+    // the leading term kept separate so the sum's rounding only touches the tail
+    let mut tail = 1.0 / 21.0;
+    for k in (1..10).rev() {
+        tail = tail * s + 1.0 / (2 * k + 1) as f64;
+    }
+    let two_f = 2.0 * f;
+    let e = exponent;
+    let y = e * LN_2_HI + (two_f + (e * LN_2_LO + two_f * s * tail));
+    if x == 0.0 {
+        f64::NEG_INFINITY
+    } else if x < 0.0 || x.is_nan() {
+        f64::NAN
+    } else if x == f64::INFINITY {
+        x
+    } else {
+        y
+    }
+}
+
+/// Argument beyond which erfc underflows to zero
+const ERFC_CUTOFF: f64 = 26.55;
+
+/// Cody's rational approximation for the region of x: erf(y) for
+/// |y| ≤ 0.46875, otherwise erfc(|y|)·e^(y²), with y = x/√2.
+/// All three regions are evaluated and the right one selected.
+#[inline(always)]
+fn cody_ratio(x: f64) -> f64 {
+    const A: [f64; 5] = [
+        3.161_123_743_870_565_6,
+        1.138_641_541_510_501_56e2,
+        3.774_852_376_853_020_21e2,
+        3.209_377_589_138_469_47e3,
+        1.857_777_061_846_031_53e-1,
+    ];
+    const B: [f64; 4] = [
+        2.360_129_095_234_412_09e1,
+        2.440_246_379_344_441_73e2,
+        1.282_616_526_077_372_28e3,
+        2.844_236_833_439_170_62e3,
+    ];
+    const C: [f64; 9] = [
+        5.641_884_969_886_700_89e-1,
+        8.883_149_794_388_375_94,
+        6.611_919_063_714_162_95e1,
+        2.986_351_381_974_001_31e2,
+        8.819_522_212_417_690_9e2,
+        1.712_047_612_634_070_58e3,
+        2.051_078_377_826_071_47e3,
+        1.230_339_354_797_997_25e3,
+        2.153_115_354_744_038_46e-8,
+    ];
+    const D: [f64; 8] = [
+        1.574_492_611_070_983_47e1,
+        1.176_939_508_913_124_99e2,
+        5.371_811_018_620_098_58e2,
+        1.621_389_574_566_690_19e3,
+        3.290_799_235_733_459_63e3,
+        4.362_619_090_143_247_16e3,
+        3.439_367_674_143_721_64e3,
+        1.230_339_354_803_749_42e3,
+    ];
+    const P: [f64; 6] = [
+        3.053_266_349_612_323_44e-1,
+        3.603_448_999_498_044_39e-1,
+        1.257_817_261_112_292_46e-1,
+        1.608_378_514_874_227_66e-2,
+        6.587_491_615_298_378_03e-4,
+        1.631_538_713_730_209_78e-2,
+    ];
+    const Q: [f64; 5] = [
+        2.568_520_192_289_822_42,
+        1.872_952_849_923_467_25,
+        5.279_051_029_514_284_12e-1,
+        6.051_834_131_244_131_91e-2,
+        2.335_204_976_268_691_85e-3,
+    ];
+
+    let y = x / SQRT_2;
+    let a = y.abs();
+
+    // erf(y) for |y| ≤ 0.46875
+    let ysq = y * y;
+    let (mut num, mut den) = (A[4] * ysq, ysq);
+    for i in 0..3 {
+        num = (num + A[i]) * ysq;
+        den = (den + B[i]) * ysq;
+    }
+    let erf_small = y * (num + A[3]) / (den + B[3]);
+
+    // erfc(a)·e^(a²) for 0.46875 < a ≤ 4
+    let (mut num, mut den) = (C[8] * a, a);
+    for i in 0..7 {
+        num = (num + C[i]) * a;
+        den = (den + D[i]) * a;
+    }
+    let middle = (num + C[7]) / (den + D[7]);
+
+    // erfc(a)·e^(a²) for a > 4, clamped so small lanes stay finite
+    let b = a.max(4.0);
+    let bsq = 1.0 / (b * b);
+    let (mut num, mut den) = (P[5] * bsq, bsq);
+    for i in 0..4 {
+        num = (num + P[i]) * bsq;
+        den = (den + Q[i]) * bsq;
+    }
+    let large = (0.5 * FRAC_2_SQRT_PI - bsq * (num + P[4]) / (den + Q[4])) / b;
+
+    if a <= 0.468_75 {
+        erf_small
+    } else if a <= 4.0 {
+        middle
+    } else {
+        large
+    }
+}
+
+/// Φ(x) from the rational part and e^(-y²)
+#[inline(always)]
+fn combine(x: f64, ratio: f64, gaussian: f64) -> f64 {
+    let a = (x / SQRT_2).abs();
+    let erfc = if a > ERFC_CUTOFF { 0.0 } else { ratio * gaussian };
+    if a <= 0.468_75 {
+        0.5 + 0.5 * ratio
+    } else if x < 0.0 {
+        0.5 * erfc
+    } else if x > 0.0 {
+        1.0 - 0.5 * erfc
+    } else {
+        // NaN
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::distributions;
+    use std::f64::consts::LN_2;
+
+    fn max_relative_error(xs: &[f64], kernel: fn(&mut [f64]), reference: fn(f64) -> f64) -> f64 {
+        let mut values = xs.to_vec();
+        kernel(&mut values);
+        xs.iter()
+            .zip(&values)
+            .map(|(&x, &v)| ((v - reference(x)) / reference(x)).abs())
+            .fold(0.0, f64::max)
+    }
+
+    fn grid(lo: f64, hi: f64, n: usize) -> Vec<f64> {
+        (0..=n).map(|i| lo + (hi - lo) * i as f64 / n as f64).collect()
+    }
+
+    #[test]
+    fn test_exp_accuracy_and_limits() {
+        assert!((LN_2_HI + LN_2_LO - LN_2).abs() < 1e-16);
+        let error = max_relative_error(&grid(-700.0, 700.0, 200_001), exp, f64::exp);
+        assert!(error < 2.5e-16, "{error}");
+        let mut special = [0.0, 709.7, 710.0, -800.0, f64::NAN, f64::NEG_INFINITY, f64::INFINITY];
+        exp(&mut special);
+        assert_eq!(special[0], 1.0);
+        assert!(((special[1] - 709.7f64.exp()) / special[1]).abs() < 1e-15);
+        assert_eq!(&special[2..4], &[f64::INFINITY, 0.0]);
+        assert!(special[4].is_nan());
+        assert_eq!(&special[5..], &[0.0, f64::INFINITY]);
+    }
+
+    #[test]
+    fn test_ln_accuracy_and_limits() {
+        let xs: Vec<f64> = grid(-700.0, 700.0, 200_001).into_iter().map(f64::exp).collect();
+        let error = max_relative_error(&xs, ln, f64::ln);
+        assert!(error < 4e-16, "{error}");
+        let near_one: Vec<f64> = grid(0.5, 1.5, 100_001).into_iter().filter(|&x| x != 1.0).collect();
+        let error = max_relative_error(&near_one, ln, f64::ln);
+        assert!(error < 4e-16, "{error}");
+        let mut special = [1.0, 0.0, -1.0, f64::INFINITY, 5e-324, f64::NAN];
+        ln(&mut special);
+        assert_eq!(&special[..2], &[0.0, f64::NEG_INFINITY]);
+        assert!(special[2].is_nan() && special[5].is_nan());
+        assert_eq!(special[3], f64::INFINITY);
+        assert!((special[4] - 5e-324f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_norm_cdf_matches_scalar() {
+        let error = max_relative_error(&grid(-37.0, 8.3, 200_001), norm_cdf, distributions::norm_cdf);
+        assert!(error < 1e-15, "{error}");
+        let mut special = [0.0, -40.0, 40.0, f64::NAN];
+        norm_cdf(&mut special);
+        assert_eq!(&special[..3], &[0.5, 0.0, 1.0]);
+        assert!(special[3].is_nan());
+    }
+}
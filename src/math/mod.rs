@@ -0,0 +1,26 @@
+//! Numerical building blocks shared by the pricing modules
+
+pub mod adjoint;
+pub mod complex;
+pub mod distributions;
+pub mod dual;
+pub mod fft;
+pub mod gamma;
+pub(crate) mod kernel;
+pub mod linalg;
+pub mod optimize;
+pub mod quadrature;
+pub mod random;
+pub mod sobol;
+
+pub use adjoint::{Gradient, Tape, Var};
+pub use complex::Complex;
+pub use distributions::{bivariate_norm_cdf, norm_cdf, norm_inv_cdf, norm_pdf};
+pub use dual::{Dual, Real};
+pub use fft::fft;
+pub use gamma::{gamma_p, gamma_q, ln_gamma, noncentral_chi_square_cdf, noncentral_chi_square_sf};
+pub use linalg::symmetric_eigen;
+pub use optimize::{nelder_mead, Minimum, Simplex, SimplexSearch};
+pub use quadrature::gauss_legendre;
+pub use random::Rng;
+pub use sobol::Sobol;
@@ -0,0 +1,306 @@
+/// Result of an unconstrained minimization
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Minimum {
+    /// Location of the minimum
+    pub x: Vec<f64>,
+    /// Objective value at the minimum
+    pub value: f64,
+    /// Number of iterations performed
+    pub iterations: usize,
+}
+
+/// Derivative-free Nelder-Mead simplex minimization
+///
+/// # Arguments
+/// * `f` - Objective function
+/// * `x0` - Starting point
+/// * `step` - Initial simplex edge length along each axis
+/// * `tolerance` - Stop when the spread of simplex values falls below this
+/// * `max_iterations` - Maximum number of iterations
+///
+/// # Returns
+/// Best point found with its objective value
+pub fn nelder_mead<F: Fn(&[f64]) -> f64>(
+    f: F,
+    x0: &[f64],
+    step: f64,
+    tolerance: f64,
+    max_iterations: usize,
+) -> Minimum {
+    let mut simplex = Simplex::new(&f, x0, step);
+    simplex.advance(&f, tolerance, max_iterations, usize::MAX);
+    simplex.best()
+}
+
+/// Nelder-Mead simplex in flight, advanced a number of iterations at a time
+///
+/// Stopping and resuming a run gives exactly the result of running it
+/// uninterrupted with the same objective.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Simplex {
+    pub vertices: Vec<Vec<f64>>,
+    /// Objective value at each vertex
+    pub values: Vec<f64>,
+    /// Iterations performed so far
+    pub iterations: usize,
+    /// Whether the spread of vertex values has fallen below the tolerance
+    pub converged: bool,
+}
+
+impl Simplex {
+    /// Simplex at `x0` with edges of length `step` along each axis
+    pub fn new<F: Fn(&[f64]) -> f64>(f: F, x0: &[f64], step: f64) -> Self {
+        let mut vertices: Vec<Vec<f64>> = Vec::with_capacity(x0.len() + 1);
+        vertices.push(x0.to_vec());
+        for i in 0..x0.len() {
+            let mut vertex = x0.to_vec();
+            vertex[i] += step;
+            vertices.push(vertex);
+        }
+        let values = vertices.iter().map(|x| f(x)).collect();
+        Simplex {
+            vertices,
+            values,
+            iterations: 0,
+            converged: false,
+        }
+    }
+
+    /// Perform up to `budget` more iterations
+    ///
+    /// Returns whether the run is over: converged within `tolerance` or at
+    /// `max_iterations` in total.
+    pub fn advance<F: Fn(&[f64]) -> f64>(&mut self, f: F, tolerance: f64, max_iterations: usize, budget: usize) -> bool {
+        let n = self.vertices.len() - 1;
+        let mut performed = 0;
+        while !self.converged && self.iterations < max_iterations && performed < budget {
+            self.iterations += 1;
+            performed += 1;
+
+            // Order vertices from best to worst
+            let mut order: Vec<usize> = (0..=n).collect();
+            order.sort_by(|&a, &b| self.values[a].total_cmp(&self.values[b]));
+            let simplex: Vec<Vec<f64>> = order.iter().map(|&i| self.vertices[i].clone()).collect();
+            self.vertices = simplex;
+            self.values = order.iter().map(|&i| self.values[i]).collect();
+            let (simplex, values) = (&mut self.vertices, &mut self.values);
+
+            if (values[n] - values[0]).abs() < tolerance {
+                self.converged = true;
+                break;
+            }
+
+            let centroid: Vec<f64> = (0..n)
+                .map(|j| simplex[..n].iter().map(|v| v[j]).sum::<f64>() / n as f64)
+                .collect();
+            let towards = |coef: f64| -> Vec<f64> {
+                (0..n)
+                    .map(|j| centroid[j] + coef * (simplex[n][j] - centroid[j]))
+                    .collect()
+            };
+
+            let reflected = towards(-1.0);
+            let f_reflected = f(&reflected);
+
+            if f_reflected < values[0] {
+                let expanded = towards(-2.0);
+                let f_expanded = f(&expanded);
+                if f_expanded < f_reflected {
+                    simplex[n] = expanded;
+                    values[n] = f_expanded;
+                } else {
+                    simplex[n] = reflected;
+                    values[n] = f_reflected;
+                }
+            } else if f_reflected < values[n - 1] {
+                simplex[n] = reflected;
+                values[n] = f_reflected;
+            } else {
+                let contracted = if f_reflected < values[n] {
+                    towards(-0.5)
+                } else {
+                    towards(0.5)
+                };
+                let f_contracted = f(&contracted);
+                if f_contracted < values[n].min(f_reflected) {
+                    simplex[n] = contracted;
+                    values[n] = f_contracted;
+                } else {
+                    // Shrink towards the best vertex
+                    let best = simplex[0].clone();
+                    for i in 1..=n {
+                        for (x, b) in simplex[i].iter_mut().zip(&best) {
+                            *x = b + 0.5 * (*x - b);
+                        }
+                        values[i] = f(&simplex[i]);
+                    }
+                }
+            }
+        }
+        self.converged || self.iterations >= max_iterations
+    }
+
+    /// Best vertex so far
+    pub fn best(&self) -> Minimum {
+        let best = (0..self.values.len())
+            .min_by(|&a, &b| self.values[a].total_cmp(&self.values[b]))
+            .unwrap_or(0);
+        Minimum {
+            x: self.vertices[best].clone(),
+            value: self.values[best],
+            iterations: self.iterations,
+        }
+    }
+}
+
+/// Multi-start Nelder-Mead search that runs in installments
+///
+/// Each start is minimized and then restarted `restarts` times from its own
+/// optimum with a smaller simplex to escape simplex collapse; the best start
+/// wins. The search is plain data, so a long calibration can stop after any
+/// number of iterations, be persisted as a checkpoint (with the `serde`
+/// feature) and resume later against the same objective.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimplexSearch {
+    pub starts: Vec<Vec<f64>>,
+    /// Simplex edge length of the first run from each start
+    pub step: f64,
+    /// Simplex edge length of each restart
+    pub restart_step: f64,
+    pub restarts: usize,
+    pub tolerance: f64,
+    /// Iteration cap of each run
+    pub max_iterations: usize,
+    /// Index of the start being minimized
+    pub start: usize,
+    /// Runs completed from the current start
+    pub run: usize,
+    /// Run in progress
+    pub simplex: Option<Simplex>,
+    /// Best result over the finished starts
+    pub best: Option<Minimum>,
+    /// Iterations performed over all runs
+    pub iterations: usize,
+}
+
+impl SimplexSearch {
+    /// Search from `starts` without restarts
+    ///
+    /// # Arguments
+    /// * `starts` - Starting points, all of the same dimension
+    /// * `step` - Initial simplex edge length along each axis
+    /// * `tolerance` - Stop a run when the spread of simplex values falls below this
+    /// * `max_iterations` - Iteration cap of each run
+    pub fn new(starts: Vec<Vec<f64>>, step: f64, tolerance: f64, max_iterations: usize) -> Self {
+        SimplexSearch {
+            starts,
+            step,
+            restart_step: step,
+            restarts: 0,
+            tolerance,
+            max_iterations,
+            start: 0,
+            run: 0,
+            simplex: None,
+            best: None,
+            iterations: 0,
+        }
+    }
+
+    /// Restart each start's optimum `restarts` times with edges of `restart_step`
+    pub fn with_restarts(mut self, restarts: usize, restart_step: f64) -> Self {
+        self.restarts = restarts;
+        self.restart_step = restart_step;
+        self
+    }
+
+    /// Whether every start has been minimized
+    pub fn is_finished(&self) -> bool {
+        self.start >= self.starts.len()
+    }
+
+    /// Perform up to `budget` more iterations and return whether the search is finished
+    pub fn advance<F: Fn(&[f64]) -> f64>(&mut self, f: F, budget: usize) -> bool {
+        let mut remaining = budget;
+        while !self.is_finished() {
+            let (start, step) = (&self.starts[self.start], self.step);
+            let simplex = self.simplex.get_or_insert_with(|| Simplex::new(&f, start, step));
+            let before = simplex.iterations;
+            let done = simplex.advance(&f, self.tolerance, self.max_iterations, remaining);
+            let used = simplex.iterations - before;
+            self.iterations += used;
+            remaining -= used;
+            if !done {
+                return false;
+            }
+            let min = simplex.best();
+            if self.run < self.restarts {
+                self.run += 1;
+                self.simplex = Some(Simplex::new(&f, &min.x, self.restart_step));
+            } else {
+                if self.best.as_ref().is_none_or(|b| min.value < b.value) {
+                    self.best = Some(min);
+                }
+                self.start += 1;
+                self.run = 0;
+                self.simplex = None;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nelder_mead_rosenbrock() {
+        let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let min = nelder_mead(rosenbrock, &[-1.2, 1.0], 0.5, 1e-14, 5000);
+
+        assert!((min.x[0] - 1.0).abs() < 1e-4);
+        assert!((min.x[1] - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_search_resumes_exactly() {
+        let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let search = SimplexSearch::new(vec![vec![-1.2, 1.0], vec![2.0, 2.0]], 0.5, 1e-14, 5000).with_restarts(2, 0.1);
+
+        let mut whole = search.clone();
+        assert!(whole.advance(rosenbrock, usize::MAX));
+        let mut pieces = search;
+        let mut installments = 0;
+        while !pieces.advance(rosenbrock, 7) {
+            // A checkpoint is just a copy of the search
+            pieces = pieces.clone();
+            installments += 1;
+        }
+        assert!(installments > 10);
+        assert_eq!(pieces, whole);
+        let best = whole.best.unwrap();
+        assert!((best.x[0] - 1.0).abs() < 1e-4 && whole.iterations > best.iterations);
+        // The first run matches a plain Nelder-Mead call
+        let single = SimplexSearch::new(vec![vec![-1.2, 1.0]], 0.5, 1e-14, 5000);
+        let mut single_run = single.clone();
+        single_run.advance(rosenbrock, usize::MAX);
+        assert_eq!(single_run.best.unwrap(), nelder_mead(rosenbrock, &[-1.2, 1.0], 0.5, 1e-14, 5000));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_search_checkpoint_round_trips_through_json() {
+        let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let mut search = SimplexSearch::new(vec![vec![-1.2, 1.0]], 0.5, 1e-14, 5000).with_restarts(1, 0.1);
+        assert!(!search.advance(rosenbrock, 20));
+        let json = serde_json::to_string(&search).unwrap();
+        let mut restored: SimplexSearch = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.iterations, 20);
+        assert!(restored.advance(rosenbrock, usize::MAX));
+        assert!((restored.best.unwrap().x[0] - 1.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,62 @@
+use std::f64::consts::PI;
+
+/// Gauss-Legendre nodes and weights on [-1, 1]
+///
+/// Roots of the Legendre polynomial P_n found by Newton iteration from
+/// Chebyshev starting points; exact for polynomials up to degree 2n - 1.
+///
+/// # Returns
+/// `(nodes, weights)` with nodes in ascending order
+pub fn gauss_legendre(n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+    for i in 0..n.div_ceil(2) {
+        let mut x = (PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        let mut derivative = 0.0;
+        for _ in 0..100 {
+            // Three-term recurrence for P_n(x), then P_n'(x) from P_{n-1}
+            let (mut p, mut previous) = (1.0, 0.0);
+            for j in 1..=n {
+                let jf = j as f64;
+                (p, previous) = (((2.0 * jf - 1.0) * x * p - (jf - 1.0) * previous) / jf, p);
+            }
+            derivative = n as f64 * (x * p - previous) / (x * x - 1.0);
+            let step = p / derivative;
+            x -= step;
+            if step.abs() < 1e-16 {
+                break;
+            }
+        }
+        let w = 2.0 / ((1.0 - x * x) * derivative * derivative);
+        nodes[i] = -x;
+        nodes[n - 1 - i] = x;
+        weights[i] = w;
+        weights[n - 1 - i] = w;
+    }
+    (nodes, weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_for_polynomials() {
+        let (x, w) = gauss_legendre(8);
+        assert!((w.iter().sum::<f64>() - 2.0).abs() < 1e-14);
+        // ∫ x^14 dx over [-1, 1] = 2/15, degree 2n - 2
+        let integral: f64 = x.iter().zip(&w).map(|(x, w)| w * x.powi(14)).sum();
+        assert!((integral - 2.0 / 15.0).abs() < 1e-14);
+        assert!(x.windows(2).all(|p| p[0] < p[1]));
+    }
+
+    #[test]
+    fn test_odd_order_has_zero_node() {
+        let (x, w) = gauss_legendre(5);
+        assert!(x[2].abs() < 1e-16);
+        assert!((w[2] - 128.0 / 225.0).abs() < 1e-14);
+        // Error bound for cos is 2^11·(5!)^4 / (11·(10!)^3) ≈ 8e-10
+        let integral: f64 = x.iter().zip(&w).map(|(x, w)| w * x.cos()).sum();
+        assert!((integral - 2.0 * 1.0_f64.sin()).abs() < 1e-9);
+    }
+}
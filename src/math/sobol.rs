@@ -0,0 +1,281 @@
+//! Sobol low-discrepancy sequences
+
+use crate::error::BlackScholesError;
+use crate::math::Rng;
+
+/// Dimensions available: x plus every primitive polynomial over GF(2) up to degree 13
+pub const MAX_DIMENSION: usize = 1111;
+
+/// Bits of precision per coordinate (at most 2³² points)
+const BITS: usize = 32;
+
+/// Initial direction numbers m_1..m_s of dimensions 2, 3, ... from Joe & Kuo's
+/// `new-joe-kuo-6.21201`, chosen for good two-dimensional projections
+///
+/// Row d - 2 belongs to the d-th primitive polynomial of `primitive_polynomials`,
+/// whose order matches the file's. Dimensions past the table fall back to
+/// [`DIRECTION_SEED`]; extend the table from the published file to replace them.
+const JOE_KUO: &[&[u32]] = &[
+    &[1],
+    &[1, 3],
+    &[1, 3, 1],
+    &[1, 1, 1],
+    &[1, 1, 3, 3],
+    &[1, 3, 5, 13],
+    &[1, 1, 5, 5, 17],
+    &[1, 1, 5, 5, 5],
+    &[1, 1, 7, 11, 19],
+    &[1, 1, 5, 1, 1],
+    &[1, 1, 1, 3, 11],
+    &[1, 3, 5, 5, 31],
+    &[1, 3, 3, 9, 7, 49],
+    &[1, 1, 1, 15, 21, 21],
+    &[1, 3, 1, 13, 27, 49],
+    &[1, 1, 1, 15, 7, 5],
+    &[1, 3, 1, 15, 13, 25],
+    &[1, 1, 5, 5, 19, 61],
+    &[1, 3, 7, 11, 23, 15, 103],
+    &[1, 3, 7, 13, 13, 15, 69],
+];
+
+/// Seed of the generator choosing initial direction numbers past [`JOE_KUO`]
+const DIRECTION_SEED: u64 = 0x5EED_50B0;
+
+/// Sobol sequence in up to [`MAX_DIMENSION`] dimensions
+///
+/// Each dimension after the first follows a primitive polynomial, taken in
+/// order of degree. The leading dimensions, where a Brownian bridge puts
+/// most of the variance, use Joe-Kuo initial direction numbers; later ones
+/// use odd integers below 2^k from a fixed-seed generator, which keeps every
+/// one-dimensional projection a (0, 1)-sequence. Points come in Gray-code order starting
+/// after the origin, optionally XORed with a random digital shift for
+/// randomized quasi-Monte Carlo.
+#[derive(Debug, Clone)]
+pub struct Sobol {
+    directions: Vec<[u32; BITS]>,
+    state: Vec<u32>,
+    shift: Vec<u32>,
+    index: u64,
+}
+
+impl Sobol {
+    pub fn new(dimension: usize) -> Result<Self, BlackScholesError> {
+        if dimension == 0 || dimension > MAX_DIMENSION {
+            return Err(BlackScholesError::OutOfRange {
+                field: "Sobol dimension",
+                value: dimension as f64,
+                requirement: "between 1 and 1111",
+            });
+        }
+        let mut rng = Rng::new(DIRECTION_SEED);
+        let mut directions = Vec::with_capacity(dimension);
+        let mut first = [0u32; BITS];
+        for (k, v) in first.iter_mut().enumerate() {
+            *v = 1 << (BITS - 1 - k);
+        }
+        directions.push(first);
+        for (d, polynomial) in primitive_polynomials(dimension - 1).into_iter().enumerate() {
+            let degree = (63 - polynomial.leading_zeros()) as usize;
+            let mut v = [0u32; BITS];
+            for (k, slot) in v.iter_mut().enumerate().take(degree) {
+                // m_k odd and below 2^(k+1)
+                let m = match JOE_KUO.get(d) {
+                    Some(row) => row[k],
+                    None => ((rng.next_u64() >> 40) as u32 % (1 << k)) * 2 + 1,
+                };
+                *slot = m << (BITS - 1 - k);
+            }
+            for k in degree..BITS {
+                let mut value = v[k - degree] ^ (v[k - degree] >> degree);
+                for i in 1..degree {
+                    if (polynomial >> (degree - i)) & 1 == 1 {
+                        value ^= v[k - i];
+                    }
+                }
+                v[k] = value;
+            }
+            directions.push(v);
+        }
+        Ok(Sobol {
+            directions,
+            state: vec![0; dimension],
+            shift: vec![0; dimension],
+            index: 0,
+        })
+    }
+
+    /// Randomize by XORing every coordinate with random bits
+    ///
+    /// Keeps the net structure, so independently shifted copies give
+    /// unbiased estimates whose spread measures the integration error.
+    pub fn with_digital_shift(mut self, rng: &mut Rng) -> Self {
+        for s in self.shift.iter_mut() {
+            *s = (rng.next_u64() >> 32) as u32;
+        }
+        self
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.directions.len()
+    }
+
+    /// Fill `point` with the next point, each coordinate strictly inside (0, 1)
+    pub fn next_point(&mut self, point: &mut [f64]) {
+        let bit = (!self.index).trailing_zeros() as usize;
+        assert!(bit < BITS, "Sobol sequence exhausted after 2^32 points");
+        self.index += 1;
+        for (((x, s), v), shift) in point.iter_mut().zip(self.state.iter_mut()).zip(&self.directions).zip(&self.shift) {
+            *s ^= v[bit];
+            *x = ((*s ^ shift) as f64 + 0.5) / (1u64 << BITS) as f64;
+        }
+    }
+}
+
+/// The first `count` primitive polynomials over GF(2) of degree one and up
+///
+/// Bit i holds the coefficient of x^i; within a degree they come in
+/// increasing order of their middle coefficients.
+fn primitive_polynomials(count: usize) -> Vec<u64> {
+    let mut found = Vec::with_capacity(count);
+    let mut degree = 1;
+    while found.len() < count {
+        let order = (1u64 << degree) - 1;
+        let factors = prime_factors(order);
+        for middle in 0..1u64 << (degree - 1) {
+            let polynomial = (1 << degree) | (middle << 1) | 1;
+            // x generates the multiplicative group of GF(2^d) exactly when p is primitive
+            let primitive = x_power(order, polynomial, degree) == 1
+                && factors.iter().all(|&q| x_power(order / q, polynomial, degree) != 1);
+            if primitive {
+                found.push(polynomial);
+                if found.len() == count {
+                    break;
+                }
+            }
+        }
+        degree += 1;
+    }
+    found
+}
+
+/// a·b modulo the degree-`degree` polynomial p, all over GF(2)
+fn mul_mod(mut a: u64, mut b: u64, p: u64, degree: u32) -> u64 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+        b >>= 1;
+        a <<= 1;
+        if (a >> degree) & 1 == 1 {
+            a ^= p;
+        }
+    }
+    product
+}
+
+/// x^exponent modulo the degree-`degree` polynomial p
+fn x_power(mut exponent: u64, p: u64, degree: u32) -> u64 {
+    // x itself reduces to 1 modulo x + 1
+    let mut base = if degree == 1 { 1 } else { 0b10 };
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, p, degree);
+        }
+        base = mul_mod(base, base, p, degree);
+        exponent >>= 1;
+    }
+    result
+}
+
+fn prime_factors(mut n: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    let mut q = 2;
+    while q * q <= n {
+        if n.is_multiple_of(q) {
+            factors.push(q);
+            while n.is_multiple_of(q) {
+                n /= q;
+            }
+        }
+        q += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive_polynomial_counts() {
+        // φ(2^d - 1) / d primitive polynomials of each degree d
+        let polynomials = primitive_polynomials(MAX_DIMENSION - 1);
+        let mut counts = [0usize; 14];
+        for p in &polynomials {
+            counts[(63 - p.leading_zeros()) as usize] += 1;
+        }
+        assert_eq!(counts, [0, 1, 1, 2, 2, 6, 6, 18, 16, 48, 60, 176, 144, 630]);
+        assert_eq!(&polynomials[..4], &[0b11, 0b111, 0b1011, 0b1101]);
+        assert!(Sobol::new(MAX_DIMENSION).is_ok());
+        assert!(Sobol::new(MAX_DIMENSION + 1).is_err());
+        assert!(Sobol::new(0).is_err());
+    }
+
+    #[test]
+    fn test_joe_kuo_points() {
+        let polynomials = primitive_polynomials(JOE_KUO.len());
+        for (row, p) in JOE_KUO.iter().zip(&polynomials) {
+            assert_eq!(row.len(), (63 - p.leading_zeros()) as usize);
+            assert!(row.iter().enumerate().all(|(k, &m)| m % 2 == 1 && m < 2 << k));
+        }
+        // Unscrambled Sobol points 1..8 in dimensions 1 to 3, Gray-code order
+        let published = [
+            [0.5, 0.5, 0.5],
+            [0.75, 0.25, 0.25],
+            [0.25, 0.75, 0.75],
+            [0.375, 0.375, 0.625],
+            [0.875, 0.875, 0.125],
+            [0.625, 0.125, 0.875],
+            [0.125, 0.625, 0.375],
+        ];
+        let mut sobol = Sobol::new(3).unwrap();
+        let mut point = [0.0; 3];
+        for expected in published {
+            sobol.next_point(&mut point);
+            for (x, e) in point.iter().zip(expected) {
+                assert_eq!(*x, e + 0.5 / 4294967296.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_coordinate_stratifies() {
+        // Points 1..2^m together with the origin put one point in each of 2^m bins
+        let mut sobol = Sobol::new(MAX_DIMENSION).unwrap();
+        let bins = 1024;
+        let mut counts = vec![vec![0u32; bins]; MAX_DIMENSION];
+        let mut point = vec![0.0; MAX_DIMENSION];
+        for _ in 1..bins {
+            sobol.next_point(&mut point);
+            for (c, x) in counts.iter_mut().zip(&point) {
+                c[(x * bins as f64) as usize] += 1;
+            }
+        }
+        for c in &counts {
+            assert_eq!(c[0], 0);
+            assert!(c[1..].iter().all(|&n| n == 1));
+        }
+
+        let mut shifted = Sobol::new(2).unwrap().with_digital_shift(&mut Rng::new(4));
+        let mut first = Sobol::new(2).unwrap();
+        first.next_point(&mut point[..2]);
+        assert_eq!(point[0], 0.5 + 0.5 / 4294967296.0);
+        shifted.next_point(&mut point[..2]);
+        assert!(point.iter().all(|&x| x > 0.0 && x < 1.0));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::validation;
+use crate::vol_surface::VolSurface;
+
+/// A calibrated model able to price European vanilla options
+///
+/// Tools that only need vanilla prices (chain generation, invariant probes,
+/// reference checks) accept any implementor.
+pub trait EuropeanModel {
+    /// Current spot price of the underlying
+    fn spot(&self) -> f64;
+
+    /// Continuously compounded risk-free rate to `expiry`
+    fn rate(&self, expiry: f64) -> f64;
+
+    /// Continuous dividend/borrow yield to `expiry`
+    fn dividend_yield(&self, expiry: f64) -> f64;
+
+    /// Price of a European vanilla option
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64;
+
+    /// Forward price for `expiry`
+    fn forward(&self, expiry: f64) -> f64 {
+        self.spot() * ((self.rate(expiry) - self.dividend_yield(expiry)) * expiry).exp()
+    }
+}
+
+impl EuropeanModel for BlackScholes {
+    fn spot(&self) -> f64 {
+        self.spot_price
+    }
+
+    fn rate(&self, _expiry: f64) -> f64 {
+        self.risk_free_rate
+    }
+
+    fn dividend_yield(&self, _expiry: f64) -> f64 {
+        self.dividend_yield
+    }
+
+    /// Flat-volatility Black-Scholes price (calendar time) at the given strike and expiry
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        let model = BlackScholes {
+            strike_price: strike,
+            time_to_expiry: expiry,
+            vol_time: expiry,
+            ..*self
+        };
+        model.price(option_type)
+    }
+}
+
+/// Black-Scholes pricing off an implied volatility surface
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurfaceModel<S: VolSurface> {
+    pub spot: f64,
+    pub rate: f64,
+    pub dividend_yield: f64,
+    pub surface: S,
+}
+
+impl<S: VolSurface> SurfaceModel<S> {
+    pub fn new(spot: f64, rate: f64, dividend_yield: f64, surface: S) -> Result<Self, BlackScholesError> {
+        let spot = validation::positive("Spot price", spot)?;
+        let rate = validation::finite("Risk-free rate", rate)?;
+        let dividend_yield = validation::finite("Dividend yield", dividend_yield)?;
+        Ok(SurfaceModel {
+            spot,
+            rate,
+            dividend_yield,
+            surface,
+        })
+    }
+}
+
+impl<S: VolSurface> EuropeanModel for SurfaceModel<S> {
+    fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    fn rate(&self, _expiry: f64) -> f64 {
+        self.rate
+    }
+
+    fn dividend_yield(&self, _expiry: f64) -> f64 {
+        self.dividend_yield
+    }
+
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        let vol = self.surface.implied_vol(strike, expiry);
+        match BlackScholes::new(self.spot, strike, expiry, self.rate, vol, self.dividend_yield) {
+            Ok(model) => model.price(option_type),
+            Err(_) => f64::NAN,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vol_surface::FlatVol;
+
+    #[test]
+    fn test_surface_model_matches_black_scholes_on_flat_surface() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.2, 0.01).unwrap();
+        let surface = SurfaceModel::new(100.0, 0.03, 0.01, FlatVol::new(0.2)).unwrap();
+
+        for strike in [80.0, 100.0, 120.0] {
+            let a = EuropeanModel::price(&bs, OptionType::Put, strike, 0.5);
+            let b = surface.price(OptionType::Put, strike, 0.5);
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert!((surface.forward(1.0) - 100.0 * 0.02_f64.exp()).abs() < 1e-12);
+    }
+}
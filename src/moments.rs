@@ -0,0 +1,236 @@
+use crate::characteristic::CharacteristicFunction;
+use crate::error::BlackScholesError;
+use crate::math::Complex;
+use crate::validation;
+use std::f64::consts::PI;
+
+/// Number of nodes on the contour used for cumulant extraction
+const CONTOUR_POINTS: usize = 64;
+
+/// Radius of the contour around the origin of the cumulant generating function
+const CONTOUR_RADIUS: f64 = 0.25;
+
+/// Risk-neutral moments of the terminal log-return
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Moments {
+    /// Expected log-return
+    pub mean: f64,
+    /// Variance of the log-return
+    pub variance: f64,
+    /// Skewness (third standardized moment)
+    pub skewness: f64,
+    /// Kurtosis (fourth standardized moment, equal to 3 for a normal)
+    pub kurtosis: f64,
+}
+
+impl Moments {
+    /// Kurtosis in excess of the normal distribution
+    pub fn excess_kurtosis(&self) -> f64 {
+        self.kurtosis - 3.0
+    }
+
+    /// Annualized volatility implied by the variance over horizon `t`
+    pub fn annualized_volatility(&self, t: f64) -> f64 {
+        (self.variance / t).sqrt()
+    }
+}
+
+/// Extract the first `order` cumulants of ln(S_t / S_0) from a model
+///
+/// The cumulant generating function K(z) = ln φ(-iz) is sampled on a small
+/// circle around the origin and its Taylor coefficients are recovered with
+/// the trapezoidal rule (Cauchy's integral formula), which converges
+/// geometrically for analytic characteristic functions.
+///
+/// # Arguments
+/// * `model` - Any model implementing `CharacteristicFunction`
+/// * `t` - Horizon in years
+/// * `order` - Number of cumulants to return (κ1..κorder)
+///
+/// # Returns
+/// Vector of cumulants, `result[n - 1]` holding κn
+pub fn cumulants<M: CharacteristicFunction + ?Sized>(model: &M, t: f64, order: usize) -> Vec<f64> {
+    let n = CONTOUR_POINTS;
+    let mut cgf = Vec::with_capacity(n);
+    let mut previous_arg = 0.0;
+
+    for j in 0..n {
+        let theta = 2.0 * PI * j as f64 / n as f64;
+        let z = Complex::from_polar(CONTOUR_RADIUS, theta);
+        let phi = model.char_fn(-Complex::I * z, t);
+
+        // Unwrap the phase so ln φ stays on a continuous branch around the contour
+        let mut arg = phi.arg();
+        while arg - previous_arg > PI {
+            arg -= 2.0 * PI;
+        }
+        while arg - previous_arg < -PI {
+            arg += 2.0 * PI;
+        }
+        previous_arg = arg;
+
+        cgf.push(Complex::new(phi.abs().ln(), arg));
+    }
+
+    let mut result = Vec::with_capacity(order);
+    let mut factorial = 1.0;
+    for k in 1..=order {
+        factorial *= k as f64;
+        let mut sum = Complex::real(0.0);
+        for (j, value) in cgf.iter().enumerate() {
+            let theta = 2.0 * PI * j as f64 / n as f64;
+            sum = sum + *value * Complex::from_polar(1.0, -(k as f64) * theta);
+        }
+        result.push(factorial * sum.re / (n as f64 * CONTOUR_RADIUS.powi(k as i32)));
+    }
+
+    result
+}
+
+/// Risk-neutral mean, variance, skewness and kurtosis of ln(S_t / S_0)
+///
+/// # Arguments
+/// * `model` - Any model implementing `CharacteristicFunction`
+/// * `t` - Horizon in years
+pub fn risk_neutral_moments<M: CharacteristicFunction + ?Sized>(model: &M, t: f64) -> Moments {
+    let k = cumulants(model, t, 4);
+    Moments {
+        mean: k[0],
+        variance: k[1],
+        skewness: k[2] / k[1].powf(1.5),
+        kurtosis: 3.0 + k[3] / k[1].powi(2),
+    }
+}
+
+/// Model-free risk-neutral moments from a strip of out-of-the-money options
+/// (Bakshi, Kapadia & Madan, 2003)
+///
+/// The volatility, cubic and quartic contracts are replicated by integrating
+/// OTM call and put prices against the BKM weights with the trapezoidal rule.
+///
+/// # Arguments
+/// * `spot` - Current price of the underlying
+/// * `risk_free_rate` - Continuously compounded risk-free rate
+/// * `time_to_expiry` - Expiry of the strip in years
+/// * `calls` - `(strike, price)` pairs of OTM calls (strikes above spot)
+/// * `puts` - `(strike, price)` pairs of OTM puts (strikes below spot)
+///
+/// # Returns
+/// Moments of the log-return over the life of the strip, or an error if the
+/// strip is too sparse to integrate
+pub fn model_free_moments(
+    spot: f64,
+    risk_free_rate: f64,
+    time_to_expiry: f64,
+    calls: &[(f64, f64)],
+    puts: &[(f64, f64)],
+) -> Result<Moments, BlackScholesError> {
+    let spot = validation::positive("Spot price", spot)?;
+    let risk_free_rate = validation::finite("Risk-free rate", risk_free_rate)?;
+    let time_to_expiry = validation::positive("Time to expiry", time_to_expiry)?;
+    if calls.iter().chain(puts).any(|&(k, p)| !k.is_finite() || !p.is_finite()) {
+        return Err(BlackScholesError::invalid("Strip strikes and prices must be finite"));
+    }
+
+    let mut calls: Vec<(f64, f64)> = calls.iter().copied().filter(|&(k, _)| k >= spot).collect();
+    let mut puts: Vec<(f64, f64)> = puts.iter().copied().filter(|&(k, _)| k <= spot).collect();
+    if calls.len() < 2 || puts.len() < 2 {
+        return Err(BlackScholesError::invalid("Need at least two OTM calls and two OTM puts"));
+    }
+    calls.sort_by(|a, b| a.0.total_cmp(&b.0));
+    puts.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let call_leg = |weight: &dyn Fn(f64) -> f64| {
+        trapezoid(&calls, |k, c| weight((k / spot).ln()) * c / (k * k))
+    };
+    let put_leg = |weight: &dyn Fn(f64) -> f64| {
+        trapezoid(&puts, |k, p| weight((spot / k).ln()) * p / (k * k))
+    };
+
+    let v = call_leg(&|x| 2.0 * (1.0 - x)) + put_leg(&|x| 2.0 * (1.0 + x));
+    let w = call_leg(&|x| 6.0 * x - 3.0 * x * x) - put_leg(&|x| 6.0 * x + 3.0 * x * x);
+    let x = call_leg(&|x| 12.0 * x * x - 4.0 * x.powi(3)) + put_leg(&|x| 12.0 * x * x + 4.0 * x.powi(3));
+
+    let growth = (risk_free_rate * time_to_expiry).exp();
+    let mu = growth - 1.0 - growth * v / 2.0 - growth * w / 6.0 - growth * x / 24.0;
+    let variance = growth * v - mu * mu;
+    if variance <= 0.0 {
+        return Err(BlackScholesError::invalid("Strip implies non-positive variance"));
+    }
+
+    let skewness = (growth * w - 3.0 * mu * growth * v + 2.0 * mu.powi(3)) / variance.powf(1.5);
+    let kurtosis = (growth * x - 4.0 * mu * growth * w + 6.0 * growth * mu * mu * v - 3.0 * mu.powi(4))
+        / variance.powi(2);
+
+    Ok(Moments {
+        mean: mu,
+        variance,
+        skewness,
+        kurtosis,
+    })
+}
+
+/// Trapezoidal integral of `f(strike, price)` over a strike-sorted strip
+fn trapezoid<F: Fn(f64, f64) -> f64>(strip: &[(f64, f64)], f: F) -> f64 {
+    strip
+        .windows(2)
+        .map(|w| 0.5 * (w[1].0 - w[0].0) * (f(w[0].0, w[0].1) + f(w[1].0, w[1].1)))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::{BlackScholes, OptionType};
+
+    #[test]
+    fn test_black_scholes_cumulants() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.01).unwrap();
+        let k = cumulants(&bs, 2.0, 4);
+
+        assert!((k[0] - (0.05 - 0.01 - 0.02) * 2.0).abs() < 1e-10);
+        assert!((k[1] - 0.08).abs() < 1e-10);
+        assert!(k[2].abs() < 1e-10);
+        assert!(k[3].abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_black_scholes_moments_are_gaussian() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.3, 0.0).unwrap();
+        let m = risk_neutral_moments(&bs, 1.0);
+
+        assert!((m.annualized_volatility(1.0) - 0.3).abs() < 1e-10);
+        assert!(m.skewness.abs() < 1e-8);
+        assert!(m.excess_kurtosis().abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_model_free_moments_recover_flat_vol() {
+        let (spot, rate, t, vol) = (100.0, 0.03, 0.5, 0.25);
+        let mut calls = Vec::new();
+        let mut puts = Vec::new();
+        let mut strike: f64 = 20.0;
+        while strike <= 400.0 {
+            let bs = BlackScholes::new(spot, strike, t, rate, vol, 0.0).unwrap();
+            if strike >= spot {
+                calls.push((strike, bs.price(OptionType::Call)));
+            }
+            if strike <= spot {
+                puts.push((strike, bs.price(OptionType::Put)));
+            }
+            strike += 0.25;
+        }
+
+        let m = model_free_moments(spot, rate, t, &calls, &puts).unwrap();
+        assert!((m.annualized_volatility(t) - vol).abs() < 0.005);
+        assert!(m.skewness.abs() < 0.05);
+        assert!((m.kurtosis - 3.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_model_free_moments_rejects_sparse_strip() {
+        let result = model_free_moments(100.0, 0.0, 1.0, &[(110.0, 2.0)], &[(90.0, 2.0)]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::math::{norm_cdf, norm_inv_cdf, norm_pdf};
+use crate::validation;
+
+/// Bisection steps when solving premium-adjusted deltas
+const DELTA_ITERATIONS: usize = 200;
+
+/// Half-width of the d2 range searched for premium-adjusted strikes
+const D2_LIMIT: f64 = 12.0;
+
+/// How a delta quote is defined
+///
+/// Spot deltas carry the foreign (dividend) discount factor e^(-qT);
+/// forward deltas do not. Premium-adjusted deltas subtract the option premium
+/// paid in the underlying, which FX markets use when the premium is paid in
+/// the foreign currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeltaConvention {
+    /// ω·e^(-qT)·N(ω·d1)
+    Spot,
+    /// ω·N(ω·d1)
+    Forward,
+    /// ω·e^(-qT)·(K/F)·N(ω·d2)
+    SpotPremiumAdjusted,
+    /// ω·(K/F)·N(ω·d2)
+    ForwardPremiumAdjusted,
+}
+
+impl DeltaConvention {
+    fn is_premium_adjusted(self) -> bool {
+        matches!(self, DeltaConvention::SpotPremiumAdjusted | DeltaConvention::ForwardPremiumAdjusted)
+    }
+
+    /// Factor converting a forward-style delta into this convention
+    fn discount(self, model: &BlackScholes) -> f64 {
+        match self {
+            DeltaConvention::Spot | DeltaConvention::SpotPremiumAdjusted => {
+                (-model.dividend_yield * model.time_to_expiry).exp()
+            }
+            DeltaConvention::Forward | DeltaConvention::ForwardPremiumAdjusted => 1.0,
+        }
+    }
+}
+
+/// Forward price of the model's underlying to its expiry
+fn forward(model: &BlackScholes) -> f64 {
+    model.spot_price * ((model.risk_free_rate - model.dividend_yield) * model.time_to_expiry).exp()
+}
+
+/// Total standard deviation σ·√T on the volatility clock
+fn std_dev(model: &BlackScholes) -> f64 {
+    model.volatility * model.vol_time.sqrt()
+}
+
+fn sign(option_type: OptionType) -> f64 {
+    match option_type {
+        OptionType::Call => 1.0,
+        OptionType::Put => -1.0,
+    }
+}
+
+/// Delta of the model's option under a quoting convention
+///
+/// # Arguments
+/// * `model` - Black-Scholes model including the strike
+/// * `option_type` - Type of option (Call or Put)
+/// * `convention` - Delta quoting convention
+pub fn delta_with_convention(model: &BlackScholes, option_type: OptionType, convention: DeltaConvention) -> f64 {
+    let w = sign(option_type);
+    let s = std_dev(model);
+    let k = log_moneyness_unchecked(model.strike_price, forward(model));
+    let d1 = -k / s + 0.5 * s;
+    let forward_delta = if convention.is_premium_adjusted() {
+        w * k.exp() * norm_cdf(w * (d1 - s))
+    } else {
+        w * norm_cdf(w * d1)
+    };
+    convention.discount(model) * forward_delta
+}
+
+/// Strike whose delta under `convention` equals `delta`
+///
+/// Plain deltas invert in closed form. Premium-adjusted deltas are solved by
+/// bisection on d2: put deltas are monotone in strike, while call deltas rise
+/// to a maximum and fall back to zero as the strike drops, so the call
+/// solution is taken on the out-of-the-money side of that maximum, the
+/// branch quoted in practice.
+///
+/// # Arguments
+/// * `model` - Market parameters (the strike field is ignored)
+/// * `option_type` - Type of option (Call or Put)
+/// * `delta` - Target delta, positive for calls and negative for puts (0.25 = 25-delta)
+/// * `convention` - Delta quoting convention
+pub fn strike_from_delta(
+    model: &BlackScholes,
+    option_type: OptionType,
+    delta: f64,
+    convention: DeltaConvention,
+) -> Result<f64, BlackScholesError> {
+    let delta = validation::finite("Delta", delta)?;
+    let w = sign(option_type);
+    // Unsigned forward-style delta
+    let target = w * delta / convention.discount(model);
+    if target <= 0.0 {
+        return Err(BlackScholesError::invalid("Delta sign must match the option type"));
+    }
+    let s = std_dev(model);
+    if s <= 0.0 {
+        return Err(BlackScholesError::invalid("Delta needs positive volatility and time to expiry"));
+    }
+    let strike_at_d2 = |d2: f64| forward(model) * (-d2 * s - 0.5 * s * s).exp();
+
+    if !convention.is_premium_adjusted() {
+        if target >= 1.0 {
+            return Err(BlackScholesError::invalid("Delta must be smaller than the discount factor in magnitude"));
+        }
+        let d1 = w * norm_inv_cdf(target);
+        return Ok(strike_at_d2(d1 - s));
+    }
+
+    // Unsigned premium-adjusted forward delta as a function of d2
+    let adjusted = |d2: f64| (-d2 * s - 0.5 * s * s).exp() * norm_cdf(w * d2);
+    let (mut lo, mut hi) = match option_type {
+        OptionType::Put => (-D2_LIMIT, D2_LIMIT),
+        OptionType::Call => {
+            // Maximum of the call delta where s·N(d2) = n(d2), beyond d2 = -s
+            let (mut lo, mut hi) = (-s, D2_LIMIT);
+            for _ in 0..DELTA_ITERATIONS {
+                let mid = 0.5 * (lo + hi);
+                if s * norm_cdf(mid) > norm_pdf(mid) {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            let peak = 0.5 * (lo + hi);
+            if target > adjusted(peak) {
+                return Err(BlackScholesError::invalid("Delta exceeds the maximum premium-adjusted call delta"));
+            }
+            (-D2_LIMIT, peak)
+        }
+    };
+    // Calls rise with d2 below the peak; puts fall with d2 everywhere
+    let rising = option_type == OptionType::Call;
+    for _ in 0..DELTA_ITERATIONS {
+        let mid = 0.5 * (lo + hi);
+        if (adjusted(mid) > target) == rising {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+        if hi - lo < 1e-14 {
+            break;
+        }
+    }
+    Ok(strike_at_d2(0.5 * (lo + hi)))
+}
+
+fn log_moneyness_unchecked(strike: f64, forward: f64) -> f64 {
+    (strike / forward).ln()
+}
+
+/// Log-moneyness ln(K/F)
+pub fn log_moneyness(strike: f64, forward: f64) -> Result<f64, BlackScholesError> {
+    let strike = validation::positive("Strike price", strike)?;
+    let forward = validation::positive("Forward price", forward)?;
+    Ok(log_moneyness_unchecked(strike, forward))
+}
+
+/// Strike at log-moneyness `k`, i.e. F·e^k
+pub fn strike_from_log_moneyness(log_moneyness: f64, forward: f64) -> Result<f64, BlackScholesError> {
+    let log_moneyness = validation::finite("Log-moneyness", log_moneyness)?;
+    let forward = validation::positive("Forward price", forward)?;
+    Ok(forward * log_moneyness.exp())
+}
+
+/// Standardized moneyness ln(K/F) / (σ·√T): log-moneyness in standard deviations
+///
+/// # Arguments
+/// * `strike` - Strike price
+/// * `forward` - Forward price to expiry
+/// * `volatility` - Volatility used to scale, typically at-the-money
+/// * `expiry` - Time to expiry in years
+pub fn standardized_moneyness(strike: f64, forward: f64, volatility: f64, expiry: f64) -> Result<f64, BlackScholesError> {
+    let std_dev = validation::positive("Volatility", volatility)? * validation::positive("Time to expiry", expiry)?.sqrt();
+    Ok(log_moneyness(strike, forward)? / std_dev)
+}
+
+/// Strike at standardized moneyness `z`, i.e. F·e^(z·σ·√T)
+pub fn strike_from_standardized_moneyness(
+    standardized: f64,
+    forward: f64,
+    volatility: f64,
+    expiry: f64,
+) -> Result<f64, BlackScholesError> {
+    let std_dev = validation::positive("Volatility", volatility)? * validation::positive("Time to expiry", expiry)?.sqrt();
+    strike_from_log_moneyness(validation::finite("Standardized moneyness", standardized)? * std_dev, forward)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONVENTIONS: [DeltaConvention; 4] = [
+        DeltaConvention::Spot,
+        DeltaConvention::Forward,
+        DeltaConvention::SpotPremiumAdjusted,
+        DeltaConvention::ForwardPremiumAdjusted,
+    ];
+
+    fn model() -> BlackScholes {
+        BlackScholes::new(1.30, 1.30, 0.75, 0.04, 0.12, 0.02).unwrap()
+    }
+
+    #[test]
+    fn test_strike_from_delta_round_trips() {
+        let model = model();
+        for convention in CONVENTIONS {
+            for (option_type, delta) in [(OptionType::Call, 0.25), (OptionType::Call, 0.1), (OptionType::Put, -0.25)] {
+                let strike = strike_from_delta(&model, option_type, delta, convention).unwrap();
+                let at_strike = BlackScholes { strike_price: strike, ..model };
+                let solved = delta_with_convention(&at_strike, option_type, convention);
+                assert!((solved - delta).abs() < 1e-10, "{convention:?} {option_type:?}: {solved}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_spot_delta_matches_model_greeks() {
+        let model = BlackScholes { strike_price: 1.35, ..model() };
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let delta = delta_with_convention(&model, option_type, DeltaConvention::Spot);
+            assert!((delta - model.greeks(option_type).delta).abs() < 1e-12);
+        }
+        // Premium adjustment subtracts the premium expressed in the underlying
+        let spot_pa = delta_with_convention(&model, OptionType::Call, DeltaConvention::SpotPremiumAdjusted);
+        let premium = model.price(OptionType::Call) / model.spot_price;
+        assert!((spot_pa - (model.greeks(OptionType::Call).delta - premium)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_premium_adjusted_strikes_are_lower() {
+        let model = model();
+        let plain = strike_from_delta(&model, OptionType::Call, 0.25, DeltaConvention::Forward).unwrap();
+        let adjusted = strike_from_delta(&model, OptionType::Call, 0.25, DeltaConvention::ForwardPremiumAdjusted).unwrap();
+        assert!(adjusted < plain && adjusted > forward(&model));
+        // A deep premium-adjusted call delta is out of reach
+        assert!(strike_from_delta(&model, OptionType::Call, 0.99, DeltaConvention::ForwardPremiumAdjusted).is_err());
+        assert!(strike_from_delta(&model, OptionType::Put, 0.25, DeltaConvention::Spot).is_err());
+        assert!(strike_from_delta(&model, OptionType::Call, 1.0, DeltaConvention::Forward).is_err());
+    }
+
+    #[test]
+    fn test_moneyness_conversions() {
+        let forward = 105.0;
+        let k = log_moneyness(110.0, forward).unwrap();
+        assert!((strike_from_log_moneyness(k, forward).unwrap() - 110.0).abs() < 1e-12);
+        let z = standardized_moneyness(110.0, forward, 0.2, 0.5).unwrap();
+        assert!((z - k / (0.2 * 0.5f64.sqrt())).abs() < 1e-14);
+        assert!((strike_from_standardized_moneyness(z, forward, 0.2, 0.5).unwrap() - 110.0).abs() < 1e-12);
+        assert!(log_moneyness(-1.0, forward).is_err());
+        assert!(standardized_moneyness(110.0, forward, 0.0, 0.5).is_err());
+    }
+}
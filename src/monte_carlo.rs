@@ -0,0 +1,783 @@
+use crate::black_scholes::BlackScholes;
+use crate::error::BlackScholesError;
+use crate::math::{norm_inv_cdf, Real, Rng, Sobol, Tape, Var};
+use crate::validation;
+
+/// Relative step for differentiating a payoff along a path direction
+const PATHWISE_STEP: f64 = 1e-6;
+
+/// Relative spot bump for bump-and-reprice Greeks
+const SPOT_BUMP: f64 = 0.01;
+
+/// Absolute volatility bump for bump-and-reprice Greeks
+const VOL_BUMP: f64 = 0.01;
+
+/// A model able to simulate risk-neutral paths of the underlying
+pub trait PathModel {
+    /// Initial spot price of the underlying
+    fn initial_spot(&self) -> f64;
+
+    /// Fill `path[i]` with the simulated spot at `times[i]`
+    ///
+    /// `times` are strictly increasing year fractions measured from today.
+    fn simulate_path(&self, times: &[f64], rng: &mut Rng, path: &mut [f64]);
+}
+
+impl PathModel for BlackScholes {
+    fn initial_spot(&self) -> f64 {
+        self.spot_price
+    }
+
+    /// Exact geometric Brownian motion steps
+    fn simulate_path(&self, times: &[f64], rng: &mut Rng, path: &mut [f64]) {
+        let vol = self.calendar_volatility();
+        let drift = self.risk_free_rate - self.dividend_yield - 0.5 * vol * vol;
+        let mut spot = self.spot_price;
+        let mut previous = 0.0;
+        for (t, s) in times.iter().zip(path.iter_mut()) {
+            let dt = t - previous;
+            spot *= (drift * dt + vol * dt.sqrt() * rng.normal()).exp();
+            *s = spot;
+            previous = *t;
+        }
+    }
+}
+
+/// A rates model able to simulate fixings of simple forwards on a tenor schedule
+///
+/// Fixing `i` is the forward over `[T_i, T_{i+1}]` observed at `T_i`; the
+/// model deflates each cashflow with its own numeraire.
+pub trait FixingModel {
+    /// Tenor dates T_0 < T_1 < ... < T_n in years
+    fn tenors(&self) -> &[f64];
+
+    /// Price cashflows on the simulated fixings
+    ///
+    /// `payoff(fixings, cashflows)` receives every fixing and fills
+    /// `cashflows[i]`, paid at `T_{i+1}`; a payment may only use fixings up
+    /// to `i`. It is called once per path, in path order.
+    fn price_fixings<F>(&self, mc: &MonteCarlo, payoff: F) -> Result<McResult, BlackScholesError>
+    where
+        F: FnMut(&[f64], &mut [f64]);
+}
+
+/// Monte Carlo estimate with its standard error
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct McResult {
+    /// Discounted expected payoff
+    pub price: f64,
+    /// Standard error of the estimate
+    pub std_error: f64,
+    /// Number of simulated paths
+    pub paths: usize,
+}
+
+impl McResult {
+    /// Two-sided confidence interval `price ± z·std_error`
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        (self.price - z * self.std_error, self.price + z * self.std_error)
+    }
+}
+
+/// Estimator of Monte Carlo sensitivities
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GreekMethod {
+    /// Differentiate each path's payoff along the path's own sensitivity
+    ///
+    /// Lowest variance, but biased for payoffs that jump in the path
+    /// (digitals, barriers); no gamma for payoffs piecewise linear in it.
+    Pathwise,
+    /// Weight each payoff by the score of the path density
+    ///
+    /// Unbiased for any payoff, at a higher variance.
+    LikelihoodRatio,
+    /// Central differences on bumped paths built from the same normals
+    BumpAndReprice,
+}
+
+/// Monte Carlo price and sensitivities, each with its standard error
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct McGreeks {
+    pub price: McResult,
+    pub delta: McResult,
+    /// None for pathwise estimates
+    pub gamma: Option<McResult>,
+    /// Per 1% volatility move, as the analytic Greeks
+    pub vega: McResult,
+}
+
+/// Monte Carlo price and its derivative to each input, from adjoint sweeps
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdjointResult {
+    pub price: McResult,
+    /// Derivative to each input, in the order the inputs were given
+    pub sensitivities: Vec<McResult>,
+}
+
+/// Running sums of a per-path estimator
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Moments {
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl Moments {
+    pub(crate) fn add(&mut self, value: f64) {
+        self.sum += value;
+        self.sum_sq += value * value;
+    }
+
+    /// Mean over `paths` samples and its standard error, both times `scale`
+    pub(crate) fn result(&self, paths: usize, scale: f64) -> McResult {
+        let n = paths as f64;
+        let mean = self.sum / n;
+        let variance = (self.sum_sq / n - mean * mean).max(0.0) * n / (n - 1.0);
+        McResult {
+            price: scale * mean,
+            std_error: scale * (variance / n).sqrt(),
+            paths,
+        }
+    }
+}
+
+/// Monte Carlo pricing engine for path-dependent payoffs
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonteCarlo {
+    /// Number of simulated paths
+    pub paths: usize,
+    /// Seed of the random number generator
+    pub seed: u64,
+}
+
+impl MonteCarlo {
+    /// Create a new engine
+    ///
+    /// # Arguments
+    /// * `paths` - Number of simulated paths
+    /// * `seed` - Seed for reproducible results
+    pub fn new(paths: usize, seed: u64) -> Result<Self, BlackScholesError> {
+        if paths < 2 {
+            return Err(BlackScholesError::invalid("Monte Carlo needs at least two paths"));
+        }
+        Ok(MonteCarlo { paths, seed })
+    }
+
+    /// Price a payoff observed on a set of monitoring dates
+    ///
+    /// # Arguments
+    /// * `model` - Path model of the underlying
+    /// * `times` - Strictly increasing monitoring times in years
+    /// * `discount_factor` - Discount factor applied to the payoff
+    /// * `payoff` - Payoff as a function of the spots at `times`
+    ///
+    /// # Returns
+    /// Discounted price estimate with its standard error
+    pub fn price<M, F>(
+        &self,
+        model: &M,
+        times: &[f64],
+        discount_factor: f64,
+        payoff: F,
+    ) -> Result<McResult, BlackScholesError>
+    where
+        M: PathModel + ?Sized,
+        F: Fn(&[f64]) -> f64,
+    {
+        validate_times(times)?;
+        let discount_factor = validation::non_negative("Discount factor", discount_factor)?;
+        let mut path = vec![0.0; times.len()];
+        Ok(self.estimate(discount_factor, |rng| {
+            model.simulate_path(times, rng, &mut path);
+            payoff(&path)
+        }))
+    }
+
+    /// Average a sample drawn once per path, scaled by `discount_factor`
+    ///
+    /// The core of [`price`](Self::price), for models whose state is not a
+    /// single spot: `sample` simulates one path from the shared generator
+    /// and returns its (deflated) payoff.
+    pub fn estimate<F: FnMut(&mut Rng) -> f64>(&self, discount_factor: f64, mut sample: F) -> McResult {
+        let mut rng = Rng::new(self.seed);
+        let mut moments = Moments::default();
+        for _ in 0..self.paths {
+            moments.add(sample(&mut rng));
+        }
+        moments.result(self.paths, discount_factor)
+    }
+
+    /// Price, delta, gamma and vega of a payoff on Black-Scholes paths
+    ///
+    /// Every estimator runs on the same geometric Brownian paths as
+    /// [`price`](Self::price). Pathwise estimates differentiate the payoff
+    /// numerically along each path's sensitivity to spot and volatility, so
+    /// `payoff` need only be a function of the path.
+    ///
+    /// # Arguments
+    /// * `model` - Spot, rate, dividend yield and volatility of the underlying
+    /// * `times` - Strictly increasing monitoring times in years
+    /// * `discount_factor` - Discount factor applied to the payoff
+    /// * `method` - Estimator for the sensitivities
+    /// * `payoff` - Payoff as a function of the spots at `times`
+    pub fn greeks<F>(
+        &self,
+        model: &BlackScholes,
+        times: &[f64],
+        discount_factor: f64,
+        method: GreekMethod,
+        payoff: F,
+    ) -> Result<McGreeks, BlackScholesError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        validate_times(times)?;
+        let discount_factor = validation::non_negative("Discount factor", discount_factor)?;
+        let spot = model.spot_price;
+        let vol = model.calendar_volatility();
+        // d(calendar vol) / d(quoted vol)
+        let vol_scale = vol / model.volatility;
+        let carry = model.risk_free_rate - model.dividend_yield;
+        let steps: Vec<f64> = times.iter().scan(0.0, |previous, &t| Some(t - std::mem::replace(previous, t))).collect();
+        let simulate = |sigma: f64, brownian: &[f64], out: &mut [f64]| {
+            for ((s, &t), &w) in out.iter_mut().zip(times).zip(brownian) {
+                *s = spot * ((carry - 0.5 * sigma * sigma) * t + sigma * w).exp();
+            }
+        };
+
+        let n = times.len();
+        let (mut normals, mut brownian) = (vec![0.0; n], vec![0.0; n]);
+        let (mut path, mut direction, mut buffer) = (vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+        let mut rng = Rng::new(self.seed);
+        let (mut price, mut delta, mut gamma, mut vega) =
+            (Moments::default(), Moments::default(), Moments::default(), Moments::default());
+        for _ in 0..self.paths {
+            let mut w = 0.0;
+            for ((z, b), dt) in normals.iter_mut().zip(brownian.iter_mut()).zip(&steps) {
+                *z = rng.normal();
+                w += dt.sqrt() * *z;
+                *b = w;
+            }
+            simulate(vol, &brownian, &mut path);
+            let value = payoff(&path);
+            price.add(value);
+
+            match method {
+                GreekMethod::Pathwise => {
+                    // ∂S_i/∂S_0 = S_i / S_0 and ∂S_i/∂σ = S_i·(W_i - σt_i)
+                    for (d, s) in direction.iter_mut().zip(&path) {
+                        *d = s / spot;
+                    }
+                    let (up, down) = shifted_values(&payoff, &path, &direction, PATHWISE_STEP * spot, &mut buffer);
+                    delta.add((up - down) / (2.0 * PATHWISE_STEP * spot));
+                    for (((d, s), &w), &t) in direction.iter_mut().zip(&path).zip(&brownian).zip(times) {
+                        *d = s * (w - vol * t) * vol_scale;
+                    }
+                    let (up, down) = shifted_values(&payoff, &path, &direction, PATHWISE_STEP, &mut buffer);
+                    vega.add((up - down) / (2.0 * PATHWISE_STEP));
+                }
+                GreekMethod::LikelihoodRatio => {
+                    // Spot enters the density through the first step only
+                    let (z, sd) = (normals[0], vol * steps[0].sqrt());
+                    delta.add(value * z / (spot * sd));
+                    gamma.add(value * ((z * z - 1.0) / (sd * sd) - z / sd) / (spot * spot));
+                    let score: f64 = normals
+                        .iter()
+                        .zip(&steps)
+                        .map(|(z, dt)| (z * z - 1.0) / vol - z * dt.sqrt())
+                        .sum();
+                    vega.add(value * score * vol_scale);
+                }
+                GreekMethod::BumpAndReprice => {
+                    for (d, s) in direction.iter_mut().zip(&path) {
+                        *d = s / spot;
+                    }
+                    let h = SPOT_BUMP * spot;
+                    let (up, down) = shifted_values(&payoff, &path, &direction, h, &mut buffer);
+                    delta.add((up - down) / (2.0 * h));
+                    gamma.add((up - 2.0 * value + down) / (h * h));
+                    simulate(vol + VOL_BUMP * vol_scale, &brownian, &mut buffer);
+                    let up = payoff(&buffer);
+                    simulate(vol - VOL_BUMP * vol_scale, &brownian, &mut buffer);
+                    vega.add((up - payoff(&buffer)) / (2.0 * VOL_BUMP));
+                }
+            }
+        }
+
+        let paths = self.paths;
+        Ok(McGreeks {
+            price: price.result(paths, discount_factor),
+            delta: delta.result(paths, discount_factor),
+            gamma: (method != GreekMethod::Pathwise).then(|| gamma.result(paths, discount_factor)),
+            vega: vega.result(paths, discount_factor / 100.0),
+        })
+    }
+
+    /// Price and its derivative to every input from one simulation
+    ///
+    /// `sample` simulates one path from the inputs, which are variables on a
+    /// tape, and returns its discounted payoff. Each path is swept backward
+    /// once and the tape rewound, so all sensitivities together cost a small
+    /// multiple of pricing alone, however many inputs (curve pillars, surface
+    /// nodes) there are. Kinks should use `Real::positive_part`; payoffs that
+    /// jump along the path (digitals, barriers) give biased pathwise derivatives.
+    ///
+    /// # Arguments
+    /// * `inputs` - Values of the inputs to differentiate against
+    /// * `sample` - One discounted path payoff from the inputs and the shared generator
+    pub fn adjoint<F>(&self, inputs: &[f64], mut sample: F) -> AdjointResult
+    where
+        F: for<'t> FnMut(&[Var<'t>], &mut Rng) -> Var<'t>,
+    {
+        let tape = Tape::new();
+        let variables: Vec<Var> = inputs.iter().map(|&x| tape.variable(x)).collect();
+        let mark = tape.len();
+        let mut rng = Rng::new(self.seed);
+        let mut price = Moments::default();
+        let mut sensitivities = vec![Moments::default(); inputs.len()];
+        for _ in 0..self.paths {
+            let value = sample(&variables, &mut rng);
+            let gradient = tape.gradient(value);
+            price.add(value.value());
+            for (moments, &x) in sensitivities.iter_mut().zip(&variables) {
+                moments.add(gradient.wrt(x));
+            }
+            tape.rewind(mark);
+        }
+        AdjointResult {
+            price: price.result(self.paths, 1.0),
+            sensitivities: sensitivities.iter().map(|m| m.result(self.paths, 1.0)).collect(),
+        }
+    }
+
+    /// Price, delta and vega of a payoff on Black-Scholes paths by adjoint differentiation
+    ///
+    /// Same paths and estimates as the pathwise [`greeks`](Self::greeks), but
+    /// exact along each path rather than by small shifts; the payoff is
+    /// written over [`Real`] so it can be recorded.
+    ///
+    /// # Arguments
+    /// * `model` - Spot, rate, dividend yield and volatility of the underlying
+    /// * `times` - Strictly increasing monitoring times in years
+    /// * `discount_factor` - Discount factor applied to the payoff
+    /// * `payoff` - Payoff as a function of the spots at `times`
+    pub fn adjoint_greeks<F>(
+        &self,
+        model: &BlackScholes,
+        times: &[f64],
+        discount_factor: f64,
+        payoff: F,
+    ) -> Result<McGreeks, BlackScholesError>
+    where
+        F: for<'t> Fn(&[Var<'t>]) -> Var<'t>,
+    {
+        validate_times(times)?;
+        let discount_factor = validation::non_negative("Discount factor", discount_factor)?;
+        // d(calendar vol) / d(quoted vol)
+        let vol_scale = model.calendar_volatility() / model.volatility;
+        let carry = model.risk_free_rate - model.dividend_yield;
+        let result = self.adjoint(&[model.spot_price, model.calendar_volatility()], |inputs, rng| {
+            let (spot, vol) = (inputs[0], inputs[1]);
+            let (log_spot, drift) = (spot.ln(), vol * vol * -0.5 + carry);
+            let (mut w, mut previous) = (0.0, 0.0);
+            let path: Vec<Var> = times
+                .iter()
+                .map(|&t| {
+                    w += (t - previous).sqrt() * rng.normal();
+                    previous = t;
+                    (log_spot + drift * t + vol * w).exp()
+                })
+                .collect();
+            payoff(&path) * discount_factor
+        });
+        Ok(McGreeks {
+            price: result.price,
+            delta: result.sensitivities[0],
+            gamma: None,
+            vega: scale(result.sensitivities[1], vol_scale / 100.0),
+        })
+    }
+}
+
+/// Estimate and standard error multiplied by `factor`
+fn scale(result: McResult, factor: f64) -> McResult {
+    McResult {
+        price: result.price * factor,
+        std_error: result.std_error * factor.abs(),
+        ..result
+    }
+}
+
+impl MonteCarlo {
+    /// Randomized quasi-Monte Carlo average of `sample` over Sobol points
+    ///
+    /// Each of `replications` independently digit-shifted copies of the
+    /// first `paths` Sobol points is mapped to standard normals and averaged;
+    /// the standard error comes from the spread of the replication means,
+    /// and `paths` in the result counts every point used.
+    ///
+    /// # Arguments
+    /// * `dimension` - Normals per sample, at most 1111
+    /// * `replications` - Independent random shifts, at least two
+    /// * `discount_factor` - Scale applied to the average
+    /// * `sample` - Payoff of one point of standard normals
+    pub fn quasi_estimate<F>(
+        &self,
+        dimension: usize,
+        replications: usize,
+        discount_factor: f64,
+        mut sample: F,
+    ) -> Result<McResult, BlackScholesError>
+    where
+        F: FnMut(&[f64]) -> f64,
+    {
+        if replications < 2 {
+            return Err(BlackScholesError::invalid("Randomized QMC needs at least two replications"));
+        }
+        let sobol = Sobol::new(dimension)?;
+        let mut rng = Rng::new(self.seed);
+        let mut point = vec![0.0; dimension];
+        let mut moments = Moments::default();
+        for _ in 0..replications {
+            let mut shifted = sobol.clone().with_digital_shift(&mut rng);
+            let mut sum = 0.0;
+            for _ in 0..self.paths {
+                shifted.next_point(&mut point);
+                point.iter_mut().for_each(|x| *x = norm_inv_cdf(*x));
+                sum += sample(&point);
+            }
+            moments.add(sum / self.paths as f64);
+        }
+        Ok(McResult {
+            paths: self.paths * replications,
+            ..moments.result(replications, discount_factor)
+        })
+    }
+
+    /// Randomized quasi-Monte Carlo price on Black-Scholes paths built by Brownian bridge
+    ///
+    /// Same arguments as [`price`](Self::price), plus the number of random
+    /// shifts; the engine's `paths` Sobol points are used per shift.
+    pub fn price_quasi<F>(
+        &self,
+        model: &BlackScholes,
+        times: &[f64],
+        discount_factor: f64,
+        replications: usize,
+        payoff: F,
+    ) -> Result<McResult, BlackScholesError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let bridge = BrownianBridge::new(times)?;
+        let discount_factor = validation::non_negative("Discount factor", discount_factor)?;
+        let vol = model.calendar_volatility();
+        let drift = model.risk_free_rate - model.dividend_yield - 0.5 * vol * vol;
+        let (mut brownian, mut path) = (vec![0.0; times.len()], vec![0.0; times.len()]);
+        self.quasi_estimate(times.len(), replications, discount_factor, |normals| {
+            bridge.build(normals, &mut brownian);
+            for ((s, &t), &w) in path.iter_mut().zip(times).zip(&brownian) {
+                *s = model.spot_price * (drift * t + vol * w).exp();
+            }
+            payoff(&path)
+        })
+    }
+}
+
+/// Brownian bridge construction of a Brownian path on fixed times
+///
+/// The first normal sets the terminal value and each later one the
+/// midpoint of a remaining gap, sweeping level by level. The leading
+/// coordinates, which a Sobol sequence spreads best, then carry most of
+/// the path's variance.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrownianBridge {
+    /// Time index filled by each normal in turn
+    order: Vec<usize>,
+    /// Filled neighbour to the left, None for time zero
+    left: Vec<Option<usize>>,
+    /// Filled neighbour to the right
+    right: Vec<usize>,
+    left_weight: Vec<f64>,
+    right_weight: Vec<f64>,
+    std_dev: Vec<f64>,
+}
+
+impl BrownianBridge {
+    pub fn new(times: &[f64]) -> Result<Self, BlackScholesError> {
+        validate_times(times)?;
+        let n = times.len();
+        let mut bridge = BrownianBridge {
+            order: vec![n - 1],
+            left: vec![None],
+            right: vec![n - 1],
+            left_weight: vec![0.0],
+            right_weight: vec![0.0],
+            std_dev: vec![times[n - 1].sqrt()],
+        };
+        let mut filled = vec![false; n];
+        filled[n - 1] = true;
+        let mut j = 0;
+        for _ in 1..n {
+            while filled[j] {
+                j = (j + 1) % n;
+            }
+            let mut k = j;
+            while !filled[k] {
+                k += 1;
+            }
+            let middle = j + (k - 1 - j) / 2;
+            filled[middle] = true;
+            let (before, at, after) = (if j == 0 { 0.0 } else { times[j - 1] }, times[middle], times[k]);
+            bridge.order.push(middle);
+            bridge.left.push(j.checked_sub(1));
+            bridge.right.push(k);
+            bridge.left_weight.push((after - at) / (after - before));
+            bridge.right_weight.push((at - before) / (after - before));
+            bridge.std_dev.push(((at - before) * (after - at) / (after - before)).sqrt());
+            j = (k + 1) % n;
+        }
+        Ok(bridge)
+    }
+
+    /// Brownian values at the times from one standard normal each, in construction order
+    pub fn build(&self, normals: &[f64], path: &mut [f64]) {
+        path[self.order[0]] = self.std_dev[0] * normals[0];
+        for i in 1..self.order.len() {
+            let left = self.left[i].map_or(0.0, |a| path[a]);
+            path[self.order[i]] =
+                self.left_weight[i] * left + self.right_weight[i] * path[self.right[i]] + self.std_dev[i] * normals[i];
+        }
+    }
+}
+
+/// Payoff on `path ± step·direction`
+fn shifted_values<F: Fn(&[f64]) -> f64>(
+    payoff: &F,
+    path: &[f64],
+    direction: &[f64],
+    step: f64,
+    buffer: &mut [f64],
+) -> (f64, f64) {
+    let mut at = |sign: f64| {
+        for ((b, s), d) in buffer.iter_mut().zip(path).zip(direction) {
+            *b = s + sign * step * d;
+        }
+        payoff(buffer)
+    };
+    (at(1.0), at(-1.0))
+}
+
+/// Check monitoring times: at least one, positive and strictly increasing
+pub(crate) fn validate_times(times: &[f64]) -> Result<(), BlackScholesError> {
+    validation::all_finite("Monitoring time", times)?;
+    if times.is_empty() {
+        return Err(BlackScholesError::invalid("Need at least one monitoring time"));
+    }
+    if times[0] <= 0.0 || times.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(BlackScholesError::invalid("Monitoring times must be positive and strictly increasing"));
+    }
+    Ok(())
+}
+
+/// Check a tenor schedule: at least one period, first date in the future, strictly increasing
+pub(crate) fn validate_tenors(tenors: &[f64]) -> Result<(), BlackScholesError> {
+    validation::all_finite("Tenor date", tenors)?;
+    if tenors.len() < 2 {
+        return Err(BlackScholesError::invalid("Tenor schedule needs at least one accrual period"));
+    }
+    if tenors[0] <= 0.0 || tenors.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(BlackScholesError::invalid("Tenor dates must be positive and strictly increasing"));
+    }
+    Ok(())
+}
+
+/// Evenly spaced monitoring times `T/n, 2T/n, ..., T`
+pub fn uniform_times(maturity: f64, steps: usize) -> Vec<f64> {
+    (1..=steps).map(|i| maturity * i as f64 / steps as f64).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::OptionType;
+
+    #[test]
+    fn test_mc_matches_black_scholes() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.01).unwrap();
+        let mc = MonteCarlo::new(100_000, 7).unwrap();
+        let discount = (-bs.risk_free_rate * bs.time_to_expiry).exp();
+        let result = mc
+            .price(&bs, &[1.0], discount, |p| (p[0] - bs.strike_price).max(0.0))
+            .unwrap();
+
+        let exact = bs.price(OptionType::Call);
+        assert!((result.price - exact).abs() < 3.0 * result.std_error);
+    }
+
+    #[test]
+    fn test_greek_estimators_match_analytic_call() {
+        let bs = BlackScholes::new(100.0, 105.0, 1.0, 0.03, 0.25, 0.01).unwrap();
+        let exact = bs.greeks(OptionType::Call);
+        let mc = MonteCarlo::new(100_000, 21).unwrap();
+        let discount = (-bs.risk_free_rate).exp();
+        let call = |p: &[f64]| (p[0] - bs.strike_price).max(0.0);
+        let close = |estimate: McResult, exact: f64| (estimate.price - exact).abs() < 4.0 * estimate.std_error + 1e-3;
+        for method in [GreekMethod::Pathwise, GreekMethod::LikelihoodRatio, GreekMethod::BumpAndReprice] {
+            let greeks = mc.greeks(&bs, &[1.0], discount, method, call).unwrap();
+            assert!(close(greeks.delta, exact.delta), "{method:?} delta {:?} vs {}", greeks.delta, exact.delta);
+            assert!(close(greeks.vega, exact.vega), "{method:?} vega {:?} vs {}", greeks.vega, exact.vega);
+            match greeks.gamma {
+                Some(gamma) => assert!(close(gamma, exact.gamma), "{method:?} gamma {gamma:?} vs {}", exact.gamma),
+                None => assert_eq!(method, GreekMethod::Pathwise),
+            }
+        }
+        // Pathwise differentiation has the smallest error on a continuous payoff
+        let pathwise = mc.greeks(&bs, &[1.0], discount, GreekMethod::Pathwise, call).unwrap();
+        let ratio = mc.greeks(&bs, &[1.0], discount, GreekMethod::LikelihoodRatio, call).unwrap();
+        assert!(pathwise.delta.std_error < ratio.delta.std_error);
+    }
+
+    #[test]
+    fn test_likelihood_ratio_handles_digitals() {
+        let bs = BlackScholes::new(100.0, 100.0, 0.5, 0.03, 0.2, 0.0).unwrap();
+        let mc = MonteCarlo::new(100_000, 3).unwrap();
+        let discount = (-bs.risk_free_rate * bs.time_to_expiry).exp();
+        let digital = |p: &[f64]| if p[0] > bs.strike_price { 1.0 } else { 0.0 };
+        // d(e^(-rT)·N(d2))/dS = e^(-rT)·n(d2) / (Sσ√T)
+        let sd = bs.volatility * bs.time_to_expiry.sqrt();
+        let drift = (bs.risk_free_rate - 0.5 * bs.volatility.powi(2)) * bs.time_to_expiry;
+        let d2 = ((bs.spot_price / bs.strike_price).ln() + drift) / sd;
+        let exact = discount * (-0.5 * d2 * d2).exp() / (2.0 * std::f64::consts::PI).sqrt() / (bs.spot_price * sd);
+
+        let ratio = mc.greeks(&bs, &[0.5], discount, GreekMethod::LikelihoodRatio, digital).unwrap();
+        assert!((ratio.delta.price - exact).abs() < 4.0 * ratio.delta.std_error);
+        // A jump in the payoff is invisible to pathwise differentiation
+        let pathwise = mc.greeks(&bs, &[0.5], discount, GreekMethod::Pathwise, digital).unwrap();
+        assert!(pathwise.delta.price.abs() < 0.1 * exact);
+    }
+
+    #[test]
+    fn test_asian_delta_agrees_across_methods() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.3, 0.0).unwrap();
+        let mc = MonteCarlo::new(50_000, 8).unwrap();
+        let times = uniform_times(1.0, 12);
+        let asian = |p: &[f64]| (p.iter().sum::<f64>() / p.len() as f64 - 100.0).max(0.0);
+        let pathwise = mc.greeks(&bs, &times, 1.0, GreekMethod::Pathwise, asian).unwrap();
+        let bumped = mc.greeks(&bs, &times, 1.0, GreekMethod::BumpAndReprice, asian).unwrap();
+        let ratio = mc.greeks(&bs, &times, 1.0, GreekMethod::LikelihoodRatio, asian).unwrap();
+        assert!((pathwise.delta.price - bumped.delta.price).abs() < 1e-3);
+        let spread = (pathwise.vega.std_error.powi(2) + ratio.vega.std_error.powi(2)).sqrt();
+        assert!((pathwise.vega.price - ratio.vega.price).abs() < 4.0 * spread);
+        assert!(mc.greeks(&bs, &[], 1.0, GreekMethod::Pathwise, asian).is_err());
+    }
+
+    #[test]
+    fn test_brownian_bridge_covariance() {
+        let times = [0.1, 0.25, 0.3, 0.7, 1.0, 1.3, 2.0];
+        let bridge = BrownianBridge::new(&times).unwrap();
+        let n = times.len();
+        // Column k of the linear map from normals to the path
+        let columns: Vec<Vec<f64>> = (0..n)
+            .map(|k| {
+                let mut unit = vec![0.0; n];
+                unit[k] = 1.0;
+                let mut path = vec![0.0; n];
+                bridge.build(&unit, &mut path);
+                path
+            })
+            .collect();
+        for i in 0..n {
+            for j in 0..n {
+                let covariance: f64 = columns.iter().map(|c| c[i] * c[j]).sum();
+                assert!((covariance - times[i].min(times[j])).abs() < 1e-12);
+            }
+        }
+        assert!(BrownianBridge::new(&[]).is_err());
+    }
+
+    #[test]
+    fn test_quasi_monte_carlo_beats_pseudo_random() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.25, 0.0).unwrap();
+        let discount = (-bs.risk_free_rate).exp();
+        let times = uniform_times(1.0, 12);
+        let asian = |p: &[f64]| (p.iter().sum::<f64>() / p.len() as f64 - 100.0).max(0.0);
+        let pseudo = MonteCarlo::new(65_536, 2).unwrap().price(&bs, &times, discount, asian).unwrap();
+        let quasi = MonteCarlo::new(4096, 2).unwrap().price_quasi(&bs, &times, discount, 16, asian).unwrap();
+        assert_eq!(quasi.paths, pseudo.paths);
+        assert!(quasi.std_error < pseudo.std_error / 10.0, "{quasi:?} vs {pseudo:?}");
+        assert!((quasi.price - pseudo.price).abs() < 4.0 * pseudo.std_error);
+
+        let call = MonteCarlo::new(4096, 5)
+            .unwrap()
+            .price_quasi(&bs, &[1.0], discount, 16, |p| (p[0] - 100.0).max(0.0))
+            .unwrap();
+        assert!((call.price - bs.price(OptionType::Call)).abs() < 4.0 * call.std_error + 1e-4);
+        assert!(MonteCarlo::new(16, 1).unwrap().quasi_estimate(3, 1, 1.0, |_| 0.0).is_err());
+    }
+
+    #[test]
+    fn test_invalid_times() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let mc = MonteCarlo::new(10, 1).unwrap();
+        assert!(mc.price(&bs, &[], 1.0, |_| 0.0).is_err());
+        assert!(mc.price(&bs, &[0.5, 0.5], 1.0, |_| 0.0).is_err());
+        assert!(MonteCarlo::new(1, 1).is_err());
+    }
+
+    #[test]
+    fn test_adjoint_greeks_match_pathwise_and_analytic() {
+        let bs = BlackScholes::new(100.0, 105.0, 1.0, 0.03, 0.25, 0.01).unwrap();
+        let exact = bs.greeks(OptionType::Call);
+        let mc = MonteCarlo::new(50_000, 21).unwrap();
+        let discount = (-bs.risk_free_rate).exp();
+        let times = [0.5, 1.0];
+        let adjoint = mc
+            .adjoint_greeks(&bs, &times, discount, |p| (p[1] - bs.strike_price).positive_part())
+            .unwrap();
+        let pathwise = mc
+            .greeks(&bs, &times, discount, GreekMethod::Pathwise, |p| (p[1] - bs.strike_price).max(0.0))
+            .unwrap();
+        // Same paths: identical prices, and pathwise shifts agree with exact path derivatives
+        assert!((adjoint.price.price - pathwise.price.price).abs() < 1e-9);
+        assert!((adjoint.delta.price - pathwise.delta.price).abs() < 1e-4);
+        assert!((adjoint.vega.price - pathwise.vega.price).abs() < 1e-4);
+        assert!((adjoint.delta.price - exact.delta).abs() < 4.0 * adjoint.delta.std_error);
+        assert!((adjoint.vega.price - exact.vega).abs() < 4.0 * adjoint.vega.std_error);
+        assert!(adjoint.gamma.is_none());
+    }
+
+    #[test]
+    fn test_adjoint_sensitivities_to_every_node() {
+        // Call under a term structure of volatility nodes on [0, ½] and [½, 1], and a rate
+        let (spot, strike, nodes, rate) = (100.0, 100.0, [0.3, 0.2], 0.02);
+        let mc = MonteCarlo::new(50_000, 9).unwrap();
+        let result = mc.adjoint(&[nodes[0], nodes[1], rate], |inputs, rng| {
+            let mut log_spot = Var::constant(f64::ln(spot));
+            for vol in &inputs[..2] {
+                let drift = inputs[2] * 0.5 - *vol * *vol * 0.25;
+                log_spot = log_spot + drift + *vol * (0.5_f64.sqrt() * rng.normal());
+            }
+            (log_spot.exp() - strike).positive_part() * (-inputs[2]).exp()
+        });
+        assert_eq!(result.sensitivities.len(), 3);
+
+        let effective = ((nodes[0] * nodes[0] + nodes[1] * nodes[1]) * 0.5).sqrt();
+        let bs = BlackScholes::new(spot, strike, 1.0, rate, effective, 0.0).unwrap();
+        let greeks = bs.greeks(OptionType::Call);
+        assert!((result.price.price - bs.price(OptionType::Call)).abs() < 4.0 * result.price.std_error);
+        for (node, sensitivity) in nodes.iter().zip(&result.sensitivities) {
+            // ∂C/∂σ_i = vega·σ_i·½/σ_eff, vega per unit volatility
+            let expected = greeks.vega * 100.0 * node * 0.5 / effective;
+            let error = (sensitivity.price - expected).abs();
+            assert!(error < 4.0 * sensitivity.std_error, "{sensitivity:?} vs {expected}");
+        }
+        let rho = greeks.rho * 100.0;
+        assert!((result.sensitivities[2].price - rho).abs() < 4.0 * result.sensitivities[2].std_error);
+    }
+}
@@ -0,0 +1,414 @@
+//! Correlated multi-asset paths under lognormal, Heston and local-stochastic dynamics
+
+use crate::error::BlackScholesError;
+use crate::heston::HestonParams;
+use crate::math::Rng;
+use crate::monte_carlo::{validate_times, McResult, MonteCarlo};
+use crate::risk::correlation_factor;
+use crate::validation;
+
+/// Euler steps per year for assets with stochastic variance
+const DEFAULT_STEPS_PER_YEAR: usize = 100;
+
+/// Leverage function L(t, S/S₀) of a local-stochastic volatility model
+///
+/// Values on a grid of times and moneyness, interpolated bilinearly and held
+/// flat beyond the grid. Calibrating it to a smile is left to the caller; a
+/// leverage of one everywhere is pure Heston.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeverageSurface {
+    /// Strictly increasing grid times in years
+    pub times: Vec<f64>,
+    /// Strictly increasing spot over initial spot
+    pub moneyness: Vec<f64>,
+    /// `values[i][j]` applies at `times[i]` and `moneyness[j]`
+    pub values: Vec<Vec<f64>>,
+}
+
+impl LeverageSurface {
+    pub fn new(times: Vec<f64>, moneyness: Vec<f64>, values: Vec<Vec<f64>>) -> Result<Self, BlackScholesError> {
+        validation::all_finite("Leverage time", &times)?;
+        validation::all_finite("Leverage moneyness", &moneyness)?;
+        if times.is_empty() || moneyness.is_empty() {
+            return Err(BlackScholesError::invalid("Leverage grid needs at least one time and moneyness"));
+        }
+        if times.windows(2).any(|w| w[1] <= w[0]) || moneyness.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(BlackScholesError::invalid("Leverage grid must be strictly increasing"));
+        }
+        if values.len() != times.len() || values.iter().any(|row| row.len() != moneyness.len()) {
+            return Err(BlackScholesError::invalid("Leverage values must match the grid"));
+        }
+        for row in &values {
+            for &value in row {
+                validation::non_negative("Leverage", value)?;
+            }
+        }
+        Ok(LeverageSurface { times, moneyness, values })
+    }
+
+    /// The same leverage at every time and spot
+    pub fn flat(value: f64) -> Result<Self, BlackScholesError> {
+        LeverageSurface::new(vec![0.0], vec![1.0], vec![vec![value]])
+    }
+
+    pub fn value(&self, t: f64, moneyness: f64) -> f64 {
+        let (i, wt) = bracket(&self.times, t);
+        let (j, wm) = bracket(&self.moneyness, moneyness);
+        let row = |i: usize| {
+            let next = (j + 1).min(self.moneyness.len() - 1);
+            (1.0 - wm) * self.values[i][j] + wm * self.values[i][next]
+        };
+        (1.0 - wt) * row(i) + wt * row((i + 1).min(self.times.len() - 1))
+    }
+}
+
+/// Lower grid index and weight on the next point, flat outside the grid
+fn bracket(grid: &[f64], x: f64) -> (usize, f64) {
+    let last = grid.len() - 1;
+    if x <= grid[0] {
+        return (0, 0.0);
+    }
+    if x >= grid[last] {
+        return (last, 0.0);
+    }
+    let i = grid.partition_point(|&g| g <= x) - 1;
+    (i, (x - grid[i]) / (grid[i + 1] - grid[i]))
+}
+
+/// Dynamics of one asset's spot
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AssetDynamics {
+    /// Constant volatility, stepped exactly
+    Lognormal { volatility: f64 },
+    /// Heston variance with its own spot-variance correlation
+    Heston(HestonParams),
+    /// Heston variance scaled by a leverage function: σ = L(t, S/S₀)·√v
+    LocalStochastic { params: HestonParams, leverage: LeverageSurface },
+}
+
+/// One underlying of a multi-asset simulation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Asset {
+    pub spot: f64,
+    /// Continuous dividend yield (annual)
+    pub dividend_yield: f64,
+    pub dynamics: AssetDynamics,
+}
+
+impl Asset {
+    pub fn lognormal(spot: f64, dividend_yield: f64, volatility: f64) -> Result<Self, BlackScholesError> {
+        let volatility = validation::non_negative("Volatility", volatility)?;
+        Asset::new(spot, dividend_yield, AssetDynamics::Lognormal { volatility })
+    }
+
+    pub fn heston(spot: f64, dividend_yield: f64, params: HestonParams) -> Result<Self, BlackScholesError> {
+        Asset::new(spot, dividend_yield, AssetDynamics::Heston(params))
+    }
+
+    pub fn local_stochastic(
+        spot: f64,
+        dividend_yield: f64,
+        params: HestonParams,
+        leverage: LeverageSurface,
+    ) -> Result<Self, BlackScholesError> {
+        Asset::new(spot, dividend_yield, AssetDynamics::LocalStochastic { params, leverage })
+    }
+
+    fn new(spot: f64, dividend_yield: f64, dynamics: AssetDynamics) -> Result<Self, BlackScholesError> {
+        Ok(Asset {
+            spot: validation::positive("Spot price", spot)?,
+            dividend_yield: validation::finite("Dividend yield", dividend_yield)?,
+            dynamics,
+        })
+    }
+}
+
+/// Correlated path generator for a basket of assets under one risk-free rate
+///
+/// `correlation` links the assets' spot Brownian motions. A Heston or
+/// local-stochastic asset's variance is driven by ρ times its own spot
+/// shock plus an independent one, so variances are correlated across
+/// assets only through their spots. Stochastic variance is stepped by
+/// full-truncation Euler on log-spot, with at least `steps_per_year` steps
+/// per year between observations; lognormal assets share those steps,
+/// which are exact for them, and a purely lognormal basket takes a single
+/// step per observation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CorrelatedPaths {
+    /// Continuously compounded risk-free rate (annual)
+    pub rate: f64,
+    pub assets: Vec<Asset>,
+    pub correlation: Vec<Vec<f64>>,
+    pub steps_per_year: usize,
+    /// Cholesky factor of `correlation`
+    factor: Vec<Vec<f64>>,
+}
+
+impl CorrelatedPaths {
+    pub fn new(rate: f64, assets: Vec<Asset>, correlation: Vec<Vec<f64>>) -> Result<Self, BlackScholesError> {
+        let n = assets.len();
+        if n == 0 {
+            return Err(BlackScholesError::invalid("Need at least one asset"));
+        }
+        if correlation.len() != n || correlation.iter().any(|row| row.len() != n) {
+            return Err(BlackScholesError::invalid("Correlation must match the assets"));
+        }
+        Ok(CorrelatedPaths {
+            rate: validation::finite("Risk-free rate", rate)?,
+            factor: correlation_factor(&correlation)?,
+            assets,
+            correlation,
+            steps_per_year: DEFAULT_STEPS_PER_YEAR,
+        })
+    }
+
+    /// A single asset
+    pub fn single(rate: f64, asset: Asset) -> Result<Self, BlackScholesError> {
+        CorrelatedPaths::new(rate, vec![asset], vec![vec![1.0]])
+    }
+
+    pub fn with_steps_per_year(mut self, steps_per_year: usize) -> Result<Self, BlackScholesError> {
+        if steps_per_year == 0 {
+            return Err(BlackScholesError::invalid("Need at least one step per year"));
+        }
+        self.steps_per_year = steps_per_year;
+        Ok(self)
+    }
+
+    /// Fill `paths[a][k]` with asset `a`'s spot at `times[k]`
+    ///
+    /// `times` are strictly increasing year fractions measured from today.
+    pub fn simulate(&self, times: &[f64], rng: &mut Rng, paths: &mut [Vec<f64>]) {
+        let n = self.assets.len();
+        let mut log_spot: Vec<f64> = self.assets.iter().map(|a| a.spot.ln()).collect();
+        let mut variance: Vec<f64> = self
+            .assets
+            .iter()
+            .map(|a| match &a.dynamics {
+                AssetDynamics::Lognormal { volatility } => volatility * volatility,
+                AssetDynamics::Heston(p) | AssetDynamics::LocalStochastic { params: p, .. } => p.v0,
+            })
+            .collect();
+        let (mut independent, mut shocks) = (vec![0.0; n], vec![0.0; n]);
+        let lognormal = self.assets.iter().all(|a| matches!(a.dynamics, AssetDynamics::Lognormal { .. }));
+        let mut t = 0.0;
+        for (k, &target) in times.iter().enumerate() {
+            let steps = if lognormal {
+                1
+            } else {
+                ((target - t) * self.steps_per_year as f64).ceil().max(1.0) as usize
+            };
+            let dt = (target - t) / steps as f64;
+            for _ in 0..steps {
+                independent.iter_mut().for_each(|z| *z = rng.normal());
+                for (shock, row) in shocks.iter_mut().zip(&self.factor) {
+                    *shock = row.iter().zip(&independent).map(|(l, z)| l * z).sum();
+                }
+                for (a, asset) in self.assets.iter().enumerate() {
+                    let drift = self.rate - asset.dividend_yield;
+                    let (params, leverage) = match &asset.dynamics {
+                        AssetDynamics::Lognormal { .. } => (None, 1.0),
+                        AssetDynamics::Heston(p) => (Some(p), 1.0),
+                        AssetDynamics::LocalStochastic { params, leverage } => {
+                            (Some(params), leverage.value(t, log_spot[a].exp() / asset.spot))
+                        }
+                    };
+                    let v = variance[a].max(0.0);
+                    let vol = leverage * v.sqrt();
+                    log_spot[a] += (drift - 0.5 * vol * vol) * dt + vol * dt.sqrt() * shocks[a];
+                    if let Some(p) = params {
+                        let z = p.rho * shocks[a] + (1.0 - p.rho * p.rho).sqrt() * rng.normal();
+                        variance[a] += p.kappa * (p.theta - v) * dt + p.vol_of_vol * (v * dt).sqrt() * z;
+                    }
+                }
+                t += dt;
+            }
+            t = target;
+            for (path, &x) in paths.iter_mut().zip(&log_spot) {
+                path[k] = x.exp();
+            }
+        }
+    }
+}
+
+/// Monte Carlo pricing of payoffs on several correlated underlyings
+///
+/// The multi-asset counterpart of [`MonteCarlo::price`] for basket, rainbow
+/// and dispersion payoffs, which see every asset's path: `paths[a][k]` is
+/// asset `a` at `times[k]`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiAssetMc {
+    pub model: CorrelatedPaths,
+    pub engine: MonteCarlo,
+}
+
+impl MultiAssetMc {
+    pub fn new(model: CorrelatedPaths, engine: MonteCarlo) -> Self {
+        MultiAssetMc { model, engine }
+    }
+
+    /// Correlated geometric Brownian motions
+    ///
+    /// # Arguments
+    /// * `rate` - Continuously compounded risk-free rate
+    /// * `spots`, `dividend_yields`, `volatilities` - One entry per asset
+    /// * `correlation` - Correlation matrix of the assets' log-returns
+    /// * `engine` - Path count and seed
+    pub fn gbm(
+        rate: f64,
+        spots: &[f64],
+        dividend_yields: &[f64],
+        volatilities: &[f64],
+        correlation: Vec<Vec<f64>>,
+        engine: MonteCarlo,
+    ) -> Result<Self, BlackScholesError> {
+        if dividend_yields.len() != spots.len() || volatilities.len() != spots.len() {
+            return Err(BlackScholesError::invalid("Spots, dividend yields and volatilities must match"));
+        }
+        let assets = spots
+            .iter()
+            .zip(dividend_yields)
+            .zip(volatilities)
+            .map(|((&s, &q), &vol)| Asset::lognormal(s, q, vol))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(MultiAssetMc::new(CorrelatedPaths::new(rate, assets, correlation)?, engine))
+    }
+
+    /// Price a payoff on all assets' spots at a set of monitoring dates
+    ///
+    /// # Arguments
+    /// * `times` - Strictly increasing monitoring times in years
+    /// * `discount_factor` - Discount factor applied to the payoff
+    /// * `payoff` - Payoff as a function of every asset's spots at `times`
+    pub fn price<F>(&self, times: &[f64], discount_factor: f64, payoff: F) -> Result<McResult, BlackScholesError>
+    where
+        F: Fn(&[Vec<f64>]) -> f64,
+    {
+        validate_times(times)?;
+        let discount_factor = validation::non_negative("Discount factor", discount_factor)?;
+        let mut paths = vec![vec![0.0; times.len()]; self.model.assets.len()];
+        Ok(self.engine.estimate(discount_factor, |rng| {
+            self.model.simulate(times, rng, &mut paths);
+            payoff(&paths)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::{BlackScholes, OptionType};
+    use crate::heston::Heston;
+    use crate::model::EuropeanModel;
+    use crate::spread::SpreadOption;
+
+    fn params() -> HestonParams {
+        HestonParams::new(0.04, 2.0, 0.05, 0.5, -0.7).unwrap()
+    }
+
+    #[test]
+    fn test_heston_marginal_and_leverage_scaling() {
+        let heston = CorrelatedPaths::single(0.03, Asset::heston(100.0, 0.01, params()).unwrap()).unwrap();
+        let mc = MonteCarlo::new(40_000, 3).unwrap();
+        let mut paths = vec![vec![0.0]];
+        let discount = (-0.03_f64).exp();
+        let call = mc.estimate(discount, |rng| {
+            heston.simulate(&[1.0], rng, &mut paths);
+            (paths[0][0] - 100.0).max(0.0)
+        });
+        let exact = Heston::new(100.0, 0.03, 0.01, params()).unwrap().price(OptionType::Call, 100.0, 1.0);
+        assert!((call.price - exact).abs() < 4.0 * call.std_error + 0.02, "{call:?} vs {exact}");
+
+        // Leverage c on variance v/c² is the same Heston path
+        let p = params();
+        let scaled = HestonParams::new(p.v0 / 4.0, p.kappa, p.theta / 4.0, p.vol_of_vol / 2.0, p.rho).unwrap();
+        let asset = Asset::local_stochastic(100.0, 0.01, scaled, LeverageSurface::flat(2.0).unwrap()).unwrap();
+        let lsv = CorrelatedPaths::single(0.03, asset).unwrap();
+        let times = [0.25, 0.5, 1.0];
+        let (mut a, mut b) = (vec![vec![0.0; 3]], vec![vec![0.0; 3]]);
+        heston.simulate(&times, &mut Rng::new(9), &mut a);
+        lsv.simulate(&times, &mut Rng::new(9), &mut b);
+        for (x, y) in a[0].iter().zip(&b[0]) {
+            assert!((x - y).abs() < 1e-9 * x);
+        }
+    }
+
+    #[test]
+    fn test_lognormal_forwards_and_correlation() {
+        let assets = vec![
+            Asset::lognormal(100.0, 0.02, 0.2).unwrap(),
+            Asset::lognormal(50.0, 0.0, 0.3).unwrap(),
+            Asset::heston(80.0, 0.01, params()).unwrap(),
+        ];
+        let correlation = vec![vec![1.0, 0.6, 0.3], vec![0.6, 1.0, 0.2], vec![0.3, 0.2, 1.0]];
+        let model = CorrelatedPaths::new(0.03, assets, correlation).unwrap().with_steps_per_year(20).unwrap();
+        let mut rng = Rng::new(5);
+        let mut paths = vec![vec![0.0; 2]; 3];
+        let count = 40_000;
+        let (mut sums, mut cross, mut squares) = ([0.0; 3], 0.0, [0.0; 2]);
+        for _ in 0..count {
+            model.simulate(&[0.5, 1.0], &mut rng, &mut paths);
+            for (s, p) in sums.iter_mut().zip(&paths) {
+                *s += p[1];
+            }
+            let x = (paths[0][1] / 100.0).ln() - (0.01 - 0.02);
+            let y = (paths[1][1] / 50.0).ln() - (0.03 - 0.045);
+            cross += x * y;
+            squares[0] += x * x;
+            squares[1] += y * y;
+        }
+        let forwards = [100.0 * 0.01_f64.exp(), 50.0 * 0.03_f64.exp(), 80.0 * 0.02_f64.exp()];
+        for (s, f) in sums.iter().zip(forwards) {
+            assert!((s / count as f64 / f - 1.0).abs() < 0.005, "{s} {f}");
+        }
+        assert!((cross / (squares[0] * squares[1]).sqrt() - 0.6).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_gbm_basket_and_exchange_options_match_closed_forms() {
+        let mc = MonteCarlo::new(50_000, 21).unwrap();
+        let discount = (-0.03_f64).exp();
+
+        // Perfectly correlated equal-vol assets: the basket is itself lognormal
+        let perfect = vec![vec![1.0, 1.0], vec![1.0, 1.0]];
+        let basket = MultiAssetMc::gbm(0.03, &[60.0, 40.0], &[0.01, 0.01], &[0.2, 0.2], perfect, mc).unwrap();
+        let call = basket.price(&[1.0], discount, |p| (p[0][0] + p[1][0] - 100.0).max(0.0)).unwrap();
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.2, 0.01).unwrap();
+        assert!((call.price - bs.price(OptionType::Call)).abs() < 4.0 * call.std_error + 1e-9);
+
+        let correlation = vec![vec![1.0, 0.4], vec![0.4, 1.0]];
+        let pair = MultiAssetMc::gbm(0.03, &[100.0, 95.0], &[0.0, 0.02], &[0.3, 0.2], correlation, mc).unwrap();
+        let exchange = pair.price(&[0.5, 1.0], discount, |p| (p[0][1] - p[1][1]).max(0.0)).unwrap();
+        let margrabe = SpreadOption::new(100.0 * 0.03_f64.exp(), 95.0 * 0.01_f64.exp(), 0.3, 0.2, 0.4, 0.0, 1.0, 0.03)
+            .unwrap()
+            .margrabe(OptionType::Call)
+            .unwrap();
+        assert!((exchange.price - margrabe).abs() < 4.0 * exchange.std_error, "{exchange:?} vs {margrabe}");
+
+        // Lognormal steps are exact, so the step count does not change the paths
+        let fine = MultiAssetMc::new(pair.model.clone().with_steps_per_year(500).unwrap(), mc);
+        let terminal = |engine: &MultiAssetMc| engine.price(&[0.5, 1.0], discount, |p| p[0][1]).unwrap().price;
+        assert_eq!(terminal(&fine), terminal(&pair));
+        assert!(pair.price(&[], discount, |_| 0.0).is_err());
+        assert!(MultiAssetMc::gbm(0.03, &[100.0], &[0.0, 0.0], &[0.2], vec![vec![1.0]], mc).is_err());
+    }
+
+    #[test]
+    fn test_invalid_generators() {
+        let asset = || Asset::lognormal(100.0, 0.0, 0.2).unwrap();
+        assert!(CorrelatedPaths::new(0.03, vec![asset(), asset()], vec![vec![1.0]]).is_err());
+        let indefinite = vec![vec![1.0, 0.9, -0.9], vec![0.9, 1.0, 0.9], vec![-0.9, 0.9, 1.0]];
+        assert!(CorrelatedPaths::new(0.03, vec![asset(), asset(), asset()], indefinite).is_err());
+        assert!(CorrelatedPaths::new(0.03, Vec::new(), Vec::new()).is_err());
+        assert!(LeverageSurface::new(vec![0.0, 1.0], vec![1.0], vec![vec![1.0]]).is_err());
+        assert!(LeverageSurface::new(vec![1.0, 0.5], vec![1.0], vec![vec![1.0], vec![1.0]]).is_err());
+        let surface = LeverageSurface::new(vec![0.0, 1.0], vec![0.8, 1.2], vec![vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+        assert!((surface.value(0.5, 1.0) - 2.5).abs() < 1e-12);
+        assert_eq!(surface.value(5.0, 0.1), 3.0);
+    }
+}
@@ -0,0 +1,286 @@
+use crate::black_scholes::OptionType;
+use crate::error::BlackScholesError;
+use crate::heston::{Heston, HestonParams};
+use crate::model::EuropeanModel;
+use crate::validation;
+use crate::vol_surface::SsviParams;
+
+/// Model parameters that can be stored per tenor and interpolated between tenors
+pub trait TermParameters: Copy {
+    /// Blend with `other`: weight 0 gives `self`, weight 1 gives `other`
+    fn interpolate(&self, other: &Self, weight: f64) -> Self;
+
+    /// Sanity constraints every stored or interpolated set must satisfy
+    fn check(&self) -> Result<(), BlackScholesError>;
+}
+
+impl TermParameters for HestonParams {
+    /// Linear in every parameter, which keeps positivity and |ρ| ≤ 1
+    fn interpolate(&self, other: &Self, weight: f64) -> Self {
+        let lerp = |a: f64, b: f64| a + (b - a) * weight;
+        HestonParams {
+            v0: lerp(self.v0, other.v0),
+            kappa: lerp(self.kappa, other.kappa),
+            theta: lerp(self.theta, other.theta),
+            vol_of_vol: lerp(self.vol_of_vol, other.vol_of_vol),
+            rho: lerp(self.rho, other.rho),
+        }
+    }
+
+    fn check(&self) -> Result<(), BlackScholesError> {
+        HestonParams::new(self.v0, self.kappa, self.theta, self.vol_of_vol, self.rho).map(|_| ())
+    }
+}
+
+impl TermParameters for SsviParams {
+    fn interpolate(&self, other: &Self, weight: f64) -> Self {
+        let lerp = |a: f64, b: f64| a + (b - a) * weight;
+        SsviParams {
+            rho: lerp(self.rho, other.rho),
+            eta: lerp(self.eta, other.eta),
+            gamma: lerp(self.gamma, other.gamma),
+        }
+    }
+
+    fn check(&self) -> Result<(), BlackScholesError> {
+        SsviParams::new(self.rho, self.eta, self.gamma).map(|_| ())
+    }
+}
+
+/// Calibrated parameters per tenor, interpolated linearly in expiry
+///
+/// Expiries before the first tenor or after the last take the nearest
+/// tenor's parameters. Every node is checked on construction, so stored
+/// sets always satisfy the model's constraints.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParameterTermStructure<P> {
+    nodes: Vec<(f64, P)>,
+}
+
+impl<P: TermParameters> ParameterTermStructure<P> {
+    /// Build from `(tenor, parameters)` nodes (sorted internally by tenor)
+    pub fn new(mut nodes: Vec<(f64, P)>) -> Result<Self, BlackScholesError> {
+        if nodes.is_empty() {
+            return Err(BlackScholesError::invalid("Parameter term structure needs at least one tenor"));
+        }
+        for (tenor, params) in &nodes {
+            validation::positive("Tenor", *tenor)?;
+            params.check()?;
+        }
+        nodes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if nodes.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err(BlackScholesError::invalid("Duplicate tenor in parameter term structure"));
+        }
+        Ok(ParameterTermStructure { nodes })
+    }
+
+    /// Stored `(tenor, parameters)` nodes, ordered by tenor
+    pub fn nodes(&self) -> &[(f64, P)] {
+        &self.nodes
+    }
+
+    /// Parameters for an arbitrary expiry
+    pub fn at(&self, expiry: f64) -> P {
+        let last = self.nodes.len() - 1;
+        if expiry <= self.nodes[0].0 {
+            return self.nodes[0].1;
+        }
+        if expiry >= self.nodes[last].0 {
+            return self.nodes[last].1;
+        }
+        let hi = self.nodes.iter().position(|n| n.0 >= expiry).unwrap_or(last);
+        let (t0, p0) = self.nodes[hi - 1];
+        let (t1, p1) = self.nodes[hi];
+        p0.interpolate(&p1, (expiry - t0) / (t1 - t0))
+    }
+
+    /// Blend two term structures node by node over the union of their tenors
+    fn interpolate(&self, other: &Self, weight: f64) -> Result<Self, BlackScholesError> {
+        let mut tenors: Vec<f64> = self.nodes.iter().chain(&other.nodes).map(|n| n.0).collect();
+        tenors.sort_by(f64::total_cmp);
+        tenors.dedup();
+        let nodes = tenors
+            .into_iter()
+            .map(|t| (t, self.at(t).interpolate(&other.at(t), weight)))
+            .collect();
+        ParameterTermStructure::new(nodes)
+    }
+}
+
+/// Term structures calibrated at successive times, interpolated through calendar time
+///
+/// Between two calibrations the parameters at each tenor are blended
+/// linearly in time; before the first or after the last calibration the
+/// nearest one is used. Timestamps are seconds since the Unix epoch, as
+/// stamped on calibration records.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParameterHistory<P> {
+    snapshots: Vec<(u64, ParameterTermStructure<P>)>,
+}
+
+impl<P: TermParameters> Default for ParameterHistory<P> {
+    fn default() -> Self {
+        ParameterHistory { snapshots: Vec::new() }
+    }
+}
+
+impl<P: TermParameters> ParameterHistory<P> {
+    pub fn new() -> Self {
+        ParameterHistory::default()
+    }
+
+    /// Store the term structure calibrated at `timestamp`, replacing any at the same time
+    pub fn insert(&mut self, timestamp: u64, term_structure: ParameterTermStructure<P>) {
+        match self.snapshots.binary_search_by_key(&timestamp, |s| s.0) {
+            Ok(i) => self.snapshots[i].1 = term_structure,
+            Err(i) => self.snapshots.insert(i, (timestamp, term_structure)),
+        }
+    }
+
+    /// Stored `(timestamp, term structure)` snapshots, oldest first
+    pub fn snapshots(&self) -> &[(u64, ParameterTermStructure<P>)] {
+        &self.snapshots
+    }
+
+    /// Term structure as of `timestamp`
+    pub fn term_structure_at(&self, timestamp: u64) -> Result<ParameterTermStructure<P>, BlackScholesError> {
+        let first = self
+            .snapshots
+            .first()
+            .ok_or_else(|| BlackScholesError::invalid("Parameter history is empty"))?;
+        let last = &self.snapshots[self.snapshots.len() - 1];
+        if timestamp <= first.0 {
+            return Ok(first.1.clone());
+        }
+        if timestamp >= last.0 {
+            return Ok(last.1.clone());
+        }
+        let hi = self.snapshots.partition_point(|s| s.0 < timestamp);
+        let (t0, s0) = &self.snapshots[hi - 1];
+        let (t1, s1) = &self.snapshots[hi];
+        s0.interpolate(s1, (timestamp - t0) as f64 / (t1 - t0) as f64)
+    }
+
+    /// Parameters as of `timestamp` for `expiry`
+    pub fn at(&self, timestamp: u64, expiry: f64) -> Result<P, BlackScholesError> {
+        let params = self.term_structure_at(timestamp)?.at(expiry);
+        params.check()?;
+        Ok(params)
+    }
+}
+
+/// Heston pricing with parameters taken from a term structure at each expiry
+///
+/// Each expiry is priced with the parameters interpolated to it, as when
+/// every tenor was calibrated on its own.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HestonTermModel {
+    pub spot: f64,
+    pub rate: f64,
+    pub dividend_yield: f64,
+    pub params: ParameterTermStructure<HestonParams>,
+}
+
+impl HestonTermModel {
+    pub fn new(
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        params: ParameterTermStructure<HestonParams>,
+    ) -> Result<Self, BlackScholesError> {
+        Ok(HestonTermModel {
+            spot: validation::positive("Spot price", spot)?,
+            rate: validation::finite("Risk-free rate", rate)?,
+            dividend_yield: validation::finite("Dividend yield", dividend_yield)?,
+            params,
+        })
+    }
+
+    /// Heston model with the parameters for `expiry`
+    pub fn model(&self, expiry: f64) -> Heston {
+        Heston {
+            spot: self.spot,
+            rate: self.rate,
+            dividend_yield: self.dividend_yield,
+            params: self.params.at(expiry),
+        }
+    }
+}
+
+impl EuropeanModel for HestonTermModel {
+    fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    fn rate(&self, _expiry: f64) -> f64 {
+        self.rate
+    }
+
+    fn dividend_yield(&self, _expiry: f64) -> f64 {
+        self.dividend_yield
+    }
+
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        self.model(expiry).price(option_type, strike, expiry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heston(v0: f64, rho: f64) -> HestonParams {
+        HestonParams::new(v0, 2.0, 0.05, 0.5, rho).unwrap()
+    }
+
+    #[test]
+    fn test_interpolation_across_tenors() {
+        let ts = ParameterTermStructure::new(vec![(1.0, heston(0.06, -0.5)), (0.25, heston(0.04, -0.7))]).unwrap();
+        let mid = ts.at(0.625);
+        assert!((mid.v0 - 0.05).abs() < 1e-15);
+        assert!((mid.rho + 0.6).abs() < 1e-15);
+        // Flat outside the calibrated tenors
+        assert_eq!(ts.at(0.1), heston(0.04, -0.7));
+        assert_eq!(ts.at(5.0), heston(0.06, -0.5));
+    }
+
+    #[test]
+    fn test_sanity_constraints_reject_bad_nodes() {
+        let bad = HestonParams {
+            rho: -1.2,
+            ..heston(0.04, 0.0)
+        };
+        assert!(ParameterTermStructure::new(vec![(0.5, bad)]).is_err());
+        assert!(ParameterTermStructure::new(vec![(0.5, heston(0.04, 0.0)), (0.5, heston(0.05, 0.0))]).is_err());
+        assert!(ParameterTermStructure::new(vec![(0.0, heston(0.04, 0.0))]).is_err());
+        assert!(ParameterTermStructure::<HestonParams>::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_history_blends_through_calendar_time() {
+        let ssvi = |rho| SsviParams::new(rho, 1.0, 0.4).unwrap();
+        let mut history = ParameterHistory::new();
+        history.insert(200, ParameterTermStructure::new(vec![(1.0, ssvi(-0.4))]).unwrap());
+        history.insert(100, ParameterTermStructure::new(vec![(0.5, ssvi(-0.8)), (1.0, ssvi(-0.6))]).unwrap());
+
+        assert!((history.at(150, 1.0).unwrap().rho + 0.5).abs() < 1e-15);
+        // The later snapshot is flat at 0.5y, the earlier has its own node there
+        assert!((history.at(150, 0.5).unwrap().rho + 0.6).abs() < 1e-15);
+        assert_eq!(history.at(50, 0.5).unwrap().rho, -0.8);
+        assert_eq!(history.at(300, 0.5).unwrap().rho, -0.4);
+        assert!(ParameterHistory::<SsviParams>::new().at(0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_term_model_prices_each_tenor_with_its_parameters() {
+        let ts = ParameterTermStructure::new(vec![(0.25, heston(0.04, -0.7)), (1.0, heston(0.09, -0.3))]).unwrap();
+        let model = HestonTermModel::new(100.0, 0.03, 0.0, ts).unwrap();
+        let single = Heston::new(100.0, 0.03, 0.0, heston(0.09, -0.3)).unwrap();
+        let price = model.price(OptionType::Call, 100.0, 1.0);
+        assert!((price - single.price(OptionType::Call, 100.0, 1.0)).abs() < 1e-12);
+        assert!(model.price(OptionType::Call, 100.0, 0.5) > model.model(0.25).price(OptionType::Call, 100.0, 0.5));
+    }
+}
@@ -0,0 +1,447 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Grid half-width in standard deviations of ln(S_T) around today's log-spot
+const DEFAULT_STD_DEVS: f64 = 5.0;
+
+/// Over-relaxation factor for projected SOR
+const PSOR_OMEGA: f64 = 1.2;
+
+/// Convergence tolerance (max update) for projected SOR
+const PSOR_TOLERANCE: f64 = 1e-10;
+
+/// Iteration cap for projected SOR
+const PSOR_MAX_ITERATIONS: usize = 10_000;
+
+/// Fully implicit steps taken before Crank-Nicolson to damp payoff-kink oscillations
+pub(crate) const RANNACHER_STEPS: usize = 2;
+
+/// Time-stepping scheme for the Black-Scholes PDE
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scheme {
+    /// Forward Euler; conditionally stable, requires dt ≲ dx²/σ²
+    Explicit,
+    /// Backward Euler; unconditionally stable, first order in time
+    Implicit,
+    /// Trapezoidal rule; second order in time, started with Rannacher steps
+    CrankNicolson,
+}
+
+impl Scheme {
+    /// Implicitness weight θ (0 explicit, 1 implicit, ½ Crank-Nicolson)
+    pub(crate) fn theta(self) -> f64 {
+        match self {
+            Scheme::Explicit => 0.0,
+            Scheme::Implicit => 1.0,
+            Scheme::CrankNicolson => 0.5,
+        }
+    }
+}
+
+/// When the holder may exercise
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Exercise {
+    /// Exercise at expiry only
+    European,
+    /// Exercise at any time up to expiry
+    American,
+}
+
+/// Known cash dividend paid by the underlying
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CashDividend {
+    /// Ex-dividend time in years from today
+    pub time: f64,
+    /// Cash amount per share
+    pub amount: f64,
+}
+
+impl CashDividend {
+    pub fn new(time: f64, amount: f64) -> Result<Self, BlackScholesError> {
+        let time = validation::positive("Dividend time", time)?;
+        let amount = validation::non_negative("Dividend amount", amount)?;
+        Ok(CashDividend { time, amount })
+    }
+}
+
+/// Price and grid Greeks from a PDE solve
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PdeResult {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    /// Time decay per day of the model's time scale
+    pub theta: f64,
+}
+
+/// Finite-difference solver for the Black-Scholes PDE on a log-spot grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FiniteDifference {
+    /// Number of spot nodes (made odd so today's spot sits on a node)
+    pub grid_points: usize,
+    /// Number of uniform time steps (dividend dates add extra nodes)
+    pub time_steps: usize,
+    pub scheme: Scheme,
+    /// Grid half-width in standard deviations of the terminal log-spot
+    pub std_devs: f64,
+}
+
+impl FiniteDifference {
+    /// Create a new solver
+    ///
+    /// # Arguments
+    /// * `grid_points` - Number of spot nodes (at least 11)
+    /// * `time_steps` - Number of time steps (at least 1)
+    /// * `scheme` - Explicit, implicit or Crank-Nicolson time stepping
+    pub fn new(grid_points: usize, time_steps: usize, scheme: Scheme) -> Result<Self, BlackScholesError> {
+        if grid_points < 11 {
+            return Err(BlackScholesError::invalid("PDE grid needs at least 11 spot nodes"));
+        }
+        if time_steps == 0 {
+            return Err(BlackScholesError::invalid("PDE grid needs at least one time step"));
+        }
+        Ok(FiniteDifference {
+            grid_points: grid_points | 1,
+            time_steps,
+            scheme,
+            std_devs: DEFAULT_STD_DEVS,
+        })
+    }
+
+    /// Solve for the option value at today's spot
+    ///
+    /// Volatility is taken on the calendar clock (`calendar_volatility`), the
+    /// dividend yield of the model still applies, and each cash dividend drops
+    /// the spot by its amount on the ex-date.
+    ///
+    /// # Arguments
+    /// * `model` - Market parameters and strike
+    /// * `option_type` - Type of option (Call or Put)
+    /// * `exercise` - European or American exercise
+    /// * `dividends` - Cash dividends before expiry (later ones are ignored)
+    pub fn solve(
+        &self,
+        model: &BlackScholes,
+        option_type: OptionType,
+        exercise: Exercise,
+        dividends: &[CashDividend],
+    ) -> Result<PdeResult, BlackScholesError> {
+        let t = model.time_to_expiry;
+        let sigma = model.calendar_volatility();
+        let (r, q, k) = (model.risk_free_rate, model.dividend_yield, model.strike_price);
+        let drift = r - q - 0.5 * sigma * sigma;
+
+        // Log-spot grid centred on today's spot
+        let n = self.grid_points;
+        let mid = n / 2;
+        let x0 = model.spot_price.ln();
+        let half_width = self.std_devs * sigma * t.sqrt() + drift.abs() * t;
+        let dx = half_width / mid as f64;
+        let spots: Vec<f64> = (0..n).map(|i| (x0 + (i as f64 - mid as f64) * dx).exp()).collect();
+
+        // Time-to-expiry nodes τ, with ex-dividend dates placed exactly on the grid
+        let mut taus: Vec<f64> = (0..=self.time_steps).map(|j| t * j as f64 / self.time_steps as f64).collect();
+        let jumps: Vec<(f64, f64)> = dividends
+            .iter()
+            .filter(|d| d.time < t)
+            .map(|d| (t - d.time, d.amount))
+            .collect();
+        taus.extend(jumps.iter().map(|j| j.0));
+        taus.sort_by(|a, b| a.total_cmp(b));
+        taus.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+
+        let theta_weight = self.scheme.theta();
+        if self.scheme == Scheme::Explicit {
+            let max_dt = taus.windows(2).map(|w| w[1] - w[0]).fold(0.0, f64::max);
+            if max_dt * (sigma * sigma / (dx * dx) + r.abs()) > 1.0 {
+                return Err(BlackScholesError::invalid(
+                    "Explicit scheme is unstable on this grid; increase time steps",
+                ));
+            }
+        }
+
+        let payoff: Vec<f64> = spots.iter().map(|&s| intrinsic(option_type, s, k)).collect();
+        let mut values = payoff.clone();
+        let mut previous = values.clone();
+
+        // Constant operator coefficients for L V = a V_{i-1} + b V_i + c V_{i+1}
+        let diffusion = 0.5 * sigma * sigma / (dx * dx);
+        let convection = drift / (2.0 * dx);
+        let (a, b, c) = (diffusion - convection, -2.0 * diffusion - r, diffusion + convection);
+
+        for step in 1..taus.len() {
+            let tau = taus[step];
+            let dt = tau - taus[step - 1];
+            let weight = if self.scheme == Scheme::CrankNicolson && step <= RANNACHER_STEPS {
+                1.0
+            } else {
+                theta_weight
+            };
+
+            previous.copy_from_slice(&values);
+            let (lower, upper) = boundaries(option_type, exercise, spots[0], spots[n - 1], k, r, q, tau);
+
+            // Right-hand side (I + (1-θ)·dt·L) V^n on interior nodes
+            let explicit = (1.0 - weight) * dt;
+            let mut rhs: Vec<f64> = (1..n - 1)
+                .map(|i| {
+                    previous[i] + explicit * (a * previous[i - 1] + b * previous[i] + c * previous[i + 1])
+                })
+                .collect();
+            // Left-hand side (I - θ·dt·L): sub, diag, super
+            let implicit = weight * dt;
+            let (sub, diag, sup) = (-implicit * a, 1.0 - implicit * b, -implicit * c);
+            rhs[0] -= sub * lower;
+            rhs[n - 3] -= sup * upper;
+
+            let interior = match exercise {
+                Exercise::European => thomas(&vec![sub; n - 2], &vec![diag; n - 2], &vec![sup; n - 2], &rhs),
+                Exercise::American => psor(sub, diag, sup, &rhs, &payoff[1..n - 1], &previous[1..n - 1])?,
+            };
+            values[0] = lower;
+            values[1..n - 1].copy_from_slice(&interior);
+            values[n - 1] = upper;
+
+            for &(jump_tau, amount) in &jumps {
+                if (jump_tau - tau).abs() < 1e-12 {
+                    values = apply_dividend(&values, &spots, x0 - mid as f64 * dx, dx, amount);
+                    if exercise == Exercise::American {
+                        for (v, p) in values.iter_mut().zip(&payoff) {
+                            *v = v.max(*p);
+                        }
+                    }
+                }
+            }
+        }
+
+        let price = values[mid];
+        let first = (values[mid + 1] - values[mid - 1]) / (2.0 * dx);
+        let second = (values[mid + 1] - 2.0 * values[mid] + values[mid - 1]) / (dx * dx);
+        let s0 = model.spot_price;
+        let last_dt = taus[taus.len() - 1] - taus[taus.len() - 2];
+
+        Ok(PdeResult {
+            price,
+            delta: first / s0,
+            gamma: (second - first) / (s0 * s0),
+            theta: (previous[mid] - price) / last_dt / model.time_scale.days_per_year(),
+        })
+    }
+
+    /// Full Greeks: delta, gamma and theta from the grid, vega and rho by re-solving
+    ///
+    /// Vega and rho follow the analytic conventions (per 1% move).
+    pub fn greeks(
+        &self,
+        model: &BlackScholes,
+        option_type: OptionType,
+        exercise: Exercise,
+        dividends: &[CashDividend],
+    ) -> Result<Greeks, BlackScholesError> {
+        let base = self.solve(model, option_type, exercise, dividends)?;
+        let bumped = |f: &dyn Fn(&mut BlackScholes)| -> Result<f64, BlackScholesError> {
+            let mut m = *model;
+            f(&mut m);
+            Ok(self.solve(&m, option_type, exercise, dividends)?.price)
+        };
+
+        let dv = 1e-3;
+        let vega = (bumped(&|m| m.volatility += dv)? - bumped(&|m| m.volatility -= dv)?) / (2.0 * dv) / 100.0;
+        let dr = 1e-4;
+        let rho = (bumped(&|m| m.risk_free_rate += dr)? - bumped(&|m| m.risk_free_rate -= dr)?) / (2.0 * dr) / 100.0;
+
+        Ok(Greeks {
+            delta: base.delta,
+            gamma: base.gamma,
+            vega,
+            theta: base.theta,
+            rho,
+        })
+    }
+}
+
+/// Payoff at exercise
+pub(crate) fn intrinsic(option_type: OptionType, spot: f64, strike: f64) -> f64 {
+    match option_type {
+        OptionType::Call => (spot - strike).max(0.0),
+        OptionType::Put => (strike - spot).max(0.0),
+    }
+}
+
+/// Dirichlet values at the lowest and highest spot nodes with τ to expiry
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn boundaries(
+    option_type: OptionType,
+    exercise: Exercise,
+    s_low: f64,
+    s_high: f64,
+    strike: f64,
+    rate: f64,
+    dividend_yield: f64,
+    tau: f64,
+) -> (f64, f64) {
+    let forward_value = |s: f64| s * (-dividend_yield * tau).exp() - strike * (-rate * tau).exp();
+    match (option_type, exercise) {
+        (OptionType::Call, Exercise::European) => (0.0, forward_value(s_high).max(0.0)),
+        (OptionType::Call, Exercise::American) => (0.0, forward_value(s_high).max(s_high - strike)),
+        (OptionType::Put, Exercise::European) => ((-forward_value(s_low)).max(0.0), 0.0),
+        (OptionType::Put, Exercise::American) => ((-forward_value(s_low)).max(strike - s_low), 0.0),
+    }
+}
+
+/// Solve a tridiagonal system with the Thomas algorithm
+///
+/// Row i reads sub[i]·x[i-1] + diag[i]·x[i] + sup[i]·x[i+1] = rhs[i]; sub[0]
+/// and the last sup are ignored.
+pub(crate) fn thomas(sub: &[f64], diag: &[f64], sup: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let m = rhs.len();
+    let mut c_prime = vec![0.0; m];
+    let mut d_prime = vec![0.0; m];
+    c_prime[0] = sup[0] / diag[0];
+    d_prime[0] = rhs[0] / diag[0];
+    for i in 1..m {
+        let denom = diag[i] - sub[i] * c_prime[i - 1];
+        c_prime[i] = sup[i] / denom;
+        d_prime[i] = (rhs[i] - sub[i] * d_prime[i - 1]) / denom;
+    }
+    let mut x = vec![0.0; m];
+    x[m - 1] = d_prime[m - 1];
+    for i in (0..m - 1).rev() {
+        x[i] = d_prime[i] - c_prime[i] * x[i + 1];
+    }
+    x
+}
+
+/// Projected SOR for the linear complementarity problem A·V ≥ rhs, V ≥ payoff
+fn psor(
+    sub: f64,
+    diag: f64,
+    sup: f64,
+    rhs: &[f64],
+    payoff: &[f64],
+    guess: &[f64],
+) -> Result<Vec<f64>, BlackScholesError> {
+    let m = rhs.len();
+    let mut x: Vec<f64> = guess.iter().zip(payoff).map(|(g, p)| g.max(*p)).collect();
+    for _ in 0..PSOR_MAX_ITERATIONS {
+        let mut max_change: f64 = 0.0;
+        for i in 0..m {
+            let left = if i > 0 { sub * x[i - 1] } else { 0.0 };
+            let right = if i + 1 < m { sup * x[i + 1] } else { 0.0 };
+            let gauss_seidel = (rhs[i] - left - right) / diag;
+            let updated = (x[i] + PSOR_OMEGA * (gauss_seidel - x[i])).max(payoff[i]);
+            max_change = max_change.max((updated - x[i]).abs());
+            x[i] = updated;
+        }
+        if max_change < PSOR_TOLERANCE {
+            return Ok(x);
+        }
+    }
+    Err(BlackScholesError::no_convergence("PSOR did not converge"))
+}
+
+/// Values just before an ex-date: V(S) = V_after(S - D), linear in log-spot
+fn apply_dividend(values: &[f64], spots: &[f64], x_low: f64, dx: f64, amount: f64) -> Vec<f64> {
+    let n = values.len();
+    spots
+        .iter()
+        .map(|&s| {
+            let shifted = s - amount;
+            if shifted <= spots[0] {
+                return values[0];
+            }
+            let pos = ((shifted.ln() - x_low) / dx).min((n - 1) as f64);
+            let i = (pos.floor() as usize).min(n - 2);
+            let w = pos - i as f64;
+            (1.0 - w) * values[i] + w * values[i + 1]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> BlackScholes {
+        BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap()
+    }
+
+    #[test]
+    fn test_schemes_match_analytic_european() {
+        let bs = model();
+        for (scheme, steps) in [
+            (Scheme::CrankNicolson, 200),
+            (Scheme::Implicit, 2000),
+            (Scheme::Explicit, 4000),
+        ] {
+            let fd = FiniteDifference::new(301, steps, scheme).unwrap();
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let result = fd.solve(&bs, option_type, Exercise::European, &[]).unwrap();
+                let exact = bs.price(option_type);
+                assert!((result.price - exact).abs() < 5e-3, "{:?} {} vs {}", scheme, result.price, exact);
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_greeks_match_analytic() {
+        let bs = BlackScholes::new(100.0, 95.0, 0.75, 0.04, 0.25, 0.01).unwrap();
+        let fd = FiniteDifference::new(401, 400, Scheme::CrankNicolson).unwrap();
+        let numeric = fd.greeks(&bs, OptionType::Put, Exercise::European, &[]).unwrap();
+        let analytic = bs.greeks(OptionType::Put);
+
+        assert!((numeric.delta - analytic.delta).abs() < 1e-3);
+        assert!((numeric.gamma - analytic.gamma).abs() < 1e-4);
+        assert!((numeric.theta - analytic.theta).abs() < 1e-3);
+        assert!((numeric.vega - analytic.vega).abs() < 2e-3);
+        assert!((numeric.rho - analytic.rho).abs() < 2e-3);
+    }
+
+    #[test]
+    fn test_american_exercise() {
+        let bs = model();
+        let fd = FiniteDifference::new(401, 400, Scheme::CrankNicolson).unwrap();
+
+        // Reference value from a 10,000-step binomial tree
+        let put = fd.solve(&bs, OptionType::Put, Exercise::American, &[]).unwrap();
+        assert!((put.price - 6.0904).abs() < 0.01, "American put {}", put.price);
+        assert!(put.price > bs.price(OptionType::Put));
+
+        // Early exercise of a call on a non-dividend payer is never optimal
+        let call = fd.solve(&bs, OptionType::Call, Exercise::American, &[]).unwrap();
+        assert!((call.price - bs.price(OptionType::Call)).abs() < 5e-3);
+    }
+
+    #[test]
+    fn test_discrete_dividend_parity() {
+        let bs = model();
+        let fd = FiniteDifference::new(401, 400, Scheme::CrankNicolson).unwrap();
+        let dividends = [CashDividend::new(0.5, 3.0).unwrap()];
+        let call = fd.solve(&bs, OptionType::Call, Exercise::European, &dividends).unwrap().price;
+        let put = fd.solve(&bs, OptionType::Put, Exercise::European, &dividends).unwrap().price;
+
+        let pv_dividend = 3.0 * (-0.05 * 0.5_f64).exp();
+        let parity = 100.0 - pv_dividend - 100.0 * (-0.05_f64).exp();
+        assert!((call - put - parity).abs() < 0.02);
+        assert!(call < bs.price(OptionType::Call));
+
+        // A large dividend makes early exercise of the call worthwhile
+        let big = [CashDividend::new(0.5, 10.0).unwrap()];
+        let european = fd.solve(&bs, OptionType::Call, Exercise::European, &big).unwrap().price;
+        let american = fd.solve(&bs, OptionType::Call, Exercise::American, &big).unwrap().price;
+        assert!(american > european + 0.1);
+    }
+
+    #[test]
+    fn test_explicit_stability_check() {
+        let fd = FiniteDifference::new(301, 10, Scheme::Explicit).unwrap();
+        assert!(fd.solve(&model(), OptionType::Call, Exercise::European, &[]).is_err());
+        assert!(FiniteDifference::new(5, 10, Scheme::Implicit).is_err());
+    }
+}
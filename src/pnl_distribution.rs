@@ -0,0 +1,286 @@
+//! Horizon P&L distributions: quantiles, expected shortfall and loss probabilities
+
+use crate::black_scholes::BlackScholes;
+use crate::density::RiskNeutralDensity;
+use crate::error::BlackScholesError;
+use crate::math::{norm_pdf, Rng};
+use crate::monte_carlo::MonteCarlo;
+use crate::multi_asset::CorrelatedPaths;
+use crate::risk::RiskMeasure;
+use crate::strategy::Strategy;
+use crate::validation;
+
+/// Nodes of the standard normal grid for lognormal horizon prices
+const NORMAL_NODES: usize = 4001;
+
+/// Half-width of the standard normal grid in standard deviations
+const NORMAL_WIDTH: f64 = 8.0;
+
+/// Discrete distribution of P&L at a horizon
+///
+/// Outcomes are sorted ascending with probabilities summing to one.
+/// Quantiles are lower quantiles: the smallest outcome whose cumulative
+/// probability reaches the level, matching `monte_carlo_var` on samples.
+/// Tail averages split the atom at the quantile so that exactly the tail
+/// probability is averaged.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PnlDistribution {
+    pub outcomes: Vec<f64>,
+    pub probabilities: Vec<f64>,
+}
+
+impl PnlDistribution {
+    /// Distribution of outcomes with the given probabilities, which are normalised
+    pub fn new(outcomes: Vec<f64>, probabilities: Vec<f64>) -> Result<Self, BlackScholesError> {
+        if outcomes.is_empty() || outcomes.len() != probabilities.len() {
+            return Err(BlackScholesError::invalid("Need at least one outcome, each with a probability"));
+        }
+        validation::all_finite("P&L outcomes", &outcomes)?;
+        if probabilities.iter().any(|p| !p.is_finite() || *p < 0.0) {
+            return Err(BlackScholesError::invalid("Probabilities must be finite and non-negative"));
+        }
+        let total: f64 = probabilities.iter().sum();
+        if total <= 0.0 {
+            return Err(BlackScholesError::invalid("Probabilities must not all be zero"));
+        }
+        let mut pairs: Vec<(f64, f64)> =
+            outcomes.into_iter().zip(probabilities.into_iter().map(|p| p / total)).collect();
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let (outcomes, probabilities) = pairs.into_iter().unzip();
+        Ok(PnlDistribution {
+            outcomes,
+            probabilities,
+        })
+    }
+
+    /// Equally likely samples, e.g. from a simulation
+    pub fn from_samples(samples: Vec<f64>) -> Result<Self, BlackScholesError> {
+        let probabilities = vec![1.0; samples.len()];
+        PnlDistribution::new(samples, probabilities)
+    }
+
+    /// P&L of a function of the terminal price, pushed through a density
+    ///
+    /// Each grid point of the density carries its trapezoid weight, so the
+    /// result is exact up to the density's grid.
+    pub fn from_density(density: &RiskNeutralDensity, pnl: impl Fn(f64) -> f64) -> Result<Self, BlackScholesError> {
+        let n = density.strikes.len();
+        let weights = (0..n).map(|i| {
+            let left = if i > 0 { density.strikes[i] - density.strikes[i - 1] } else { 0.0 };
+            let right = if i + 1 < n { density.strikes[i + 1] - density.strikes[i] } else { 0.0 };
+            0.5 * (left + right) * density.density[i]
+        });
+        PnlDistribution::new(density.strikes.iter().map(|&s| pnl(s)).collect(), weights.collect())
+    }
+
+    /// P&L of a function of a lognormal price at `horizon`
+    ///
+    /// S_h = S·exp((μ - σ²/2)h + σ√h·Z), with Z on a fine grid weighted by
+    /// the normal density. `drift` is the real-world μ for risk limits, or
+    /// r - q for the pricing measure.
+    pub fn lognormal(
+        spot: f64,
+        drift: f64,
+        volatility: f64,
+        horizon: f64,
+        pnl: impl Fn(f64) -> f64,
+    ) -> Result<Self, BlackScholesError> {
+        let spot = validation::positive("Spot price", spot)?;
+        let drift = validation::finite("Drift", drift)?;
+        let volatility = validation::non_negative("Volatility", volatility)?;
+        let horizon = validation::positive("Horizon", horizon)?;
+        let sd = volatility * horizon.sqrt();
+        let (outcomes, weights) = (0..NORMAL_NODES)
+            .map(|i| {
+                let z = -NORMAL_WIDTH + 2.0 * NORMAL_WIDTH * i as f64 / (NORMAL_NODES - 1) as f64;
+                let end = if i == 0 || i == NORMAL_NODES - 1 { 0.5 } else { 1.0 };
+                (pnl(spot * ((drift - 0.5 * volatility * volatility) * horizon + sd * z).exp()), end * norm_pdf(z))
+            })
+            .unzip();
+        PnlDistribution::new(outcomes, weights)
+    }
+
+    /// Horizon P&L of a strategy with the market's volatility held fixed
+    ///
+    /// Legs are repriced with `Strategy::pnl_at` at a lognormal spot drifting
+    /// at `drift`, so time decay to the horizon and expiries before it are
+    /// included. Entry prices should be marked first.
+    pub fn strategy(
+        strategy: &Strategy,
+        market: &BlackScholes,
+        horizon: f64,
+        drift: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let volatility = market.calendar_volatility();
+        PnlDistribution::lognormal(market.spot_price, drift, volatility, horizon, |spot| {
+            strategy.pnl_at(market, horizon, spot, 0.0)
+        })
+    }
+
+    /// P&L of a function of every asset's price at `horizon`, by simulation
+    pub fn monte_carlo(
+        model: &CorrelatedPaths,
+        horizon: f64,
+        mc: &MonteCarlo,
+        pnl: impl Fn(&[f64]) -> f64,
+    ) -> Result<Self, BlackScholesError> {
+        let horizon = validation::positive("Horizon", horizon)?;
+        let mut rng = Rng::new(mc.seed);
+        let mut paths = vec![vec![0.0; 1]; model.assets.len()];
+        let mut prices = vec![0.0; model.assets.len()];
+        let samples = (0..mc.paths)
+            .map(|_| {
+                model.simulate(&[horizon], &mut rng, &mut paths);
+                prices.iter_mut().zip(&paths).for_each(|(price, path)| *price = path[0]);
+                pnl(&prices)
+            })
+            .collect();
+        PnlDistribution::from_samples(samples)
+    }
+
+    /// Expected P&L
+    pub fn mean(&self) -> f64 {
+        self.outcomes.iter().zip(&self.probabilities).map(|(x, p)| x * p).sum()
+    }
+
+    /// Standard deviation of the P&L
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        self.outcomes.iter().zip(&self.probabilities).map(|(x, p)| p * (x - mean).powi(2)).sum::<f64>().sqrt()
+    }
+
+    /// Lower quantile of the P&L at `probability` in (0, 1]
+    pub fn quantile(&self, probability: f64) -> Result<f64, BlackScholesError> {
+        let level = validation::in_range("Probability", probability, 0.0, 1.0, "in (0, 1]")?;
+        if level == 0.0 {
+            return Err(BlackScholesError::invalid("Probability must be in (0, 1]"));
+        }
+        let mut cumulative = 0.0;
+        for (&x, &p) in self.outcomes.iter().zip(&self.probabilities) {
+            cumulative += p;
+            // Tolerate rounding in the running sum
+            if cumulative >= level * (1.0 - 1e-12) {
+                return Ok(x);
+            }
+        }
+        Ok(self.outcomes[self.outcomes.len() - 1])
+    }
+
+    /// Average P&L over the worst `probability` of outcomes
+    pub fn tail_mean(&self, probability: f64) -> Result<f64, BlackScholesError> {
+        let tail = validation::in_range("Probability", probability, 0.0, 1.0, "in (0, 1]")?;
+        if tail == 0.0 {
+            return Err(BlackScholesError::invalid("Probability must be in (0, 1]"));
+        }
+        let (mut remaining, mut total) = (tail, 0.0);
+        for (&x, &p) in self.outcomes.iter().zip(&self.probabilities) {
+            let taken = p.min(remaining);
+            total += taken * x;
+            remaining -= taken;
+            // Tolerate rounding in the running difference
+            if remaining <= 1e-12 * tail {
+                break;
+            }
+        }
+        Ok(total / (tail - remaining))
+    }
+
+    /// VaR and expected shortfall at `confidence`, as positive losses
+    pub fn risk(&self, confidence: f64) -> Result<RiskMeasure, BlackScholesError> {
+        let confidence = validation::in_range("Confidence", confidence, 0.0, 1.0, "in (0, 1)")?;
+        if confidence == 0.0 || confidence == 1.0 {
+            return Err(BlackScholesError::invalid("Confidence must be in (0, 1)"));
+        }
+        Ok(RiskMeasure {
+            var: -self.quantile(1.0 - confidence)?,
+            expected_shortfall: -self.tail_mean(1.0 - confidence)?,
+        })
+    }
+
+    /// Probability of losing more than `threshold`, P(P&L < -threshold)
+    pub fn probability_of_loss(&self, threshold: f64) -> f64 {
+        self.outcomes.iter().zip(&self.probabilities).filter(|(x, _)| **x < -threshold).map(|(_, p)| p).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::OptionType;
+    use crate::math::{norm_cdf, norm_inv_cdf};
+    use crate::multi_asset::Asset;
+    use crate::strategy::Leg;
+
+    #[test]
+    fn test_stock_position_matches_lognormal_closed_forms() {
+        let (spot, mu, vol, h) = (100.0, 0.08, 0.3, 0.25);
+        let distribution = PnlDistribution::lognormal(spot, mu, vol, h, |s| s - spot).unwrap();
+        let sd = vol * h.sqrt();
+        assert!((distribution.mean() - spot * ((mu * h).exp() - 1.0)).abs() < 1e-6);
+
+        let quantile = |p: f64| spot * ((mu - 0.5 * vol * vol) * h + sd * norm_inv_cdf(p)).exp() - spot;
+        let risk = distribution.risk(0.99).unwrap();
+        assert!((risk.var + quantile(0.01)).abs() < 0.02, "{risk:?}");
+        // E[S | S below its 1% quantile] = S·e^(μh)·N(z - σ√h) / 1%
+        let tail = spot * (mu * h).exp() * norm_cdf(norm_inv_cdf(0.01) - sd) / 0.01 - spot;
+        assert!((risk.expected_shortfall + tail).abs() < 0.02, "{risk:?} vs {tail}");
+        assert!((distribution.probability_of_loss(-quantile(0.2)) - 0.2).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_long_call_loses_at_most_its_premium() {
+        let market = BlackScholes::new(100.0, 105.0, 0.5, 0.03, 0.25, 0.0).unwrap();
+        let mut call = Strategy::new("call", vec![Leg::option(OptionType::Call, 105.0, 0.5, 1.0).unwrap()]).unwrap();
+        call.mark_entry(&market);
+        let premium = call.entry_cost();
+        let at_expiry = PnlDistribution::strategy(&call, &market, 0.5, 0.03).unwrap();
+        assert!(at_expiry.probability_of_loss(premium + 1e-9) == 0.0);
+        // The premium is lost whenever the call finishes out of the money
+        let sd = 0.25 * 0.5_f64.sqrt();
+        let out_of_money = norm_cdf(((105.0_f64 / 100.0).ln() - (0.03 - 0.5 * 0.0625) * 0.5) / sd);
+        let lost = at_expiry.probability_of_loss(premium - 1e-9);
+        assert!((lost - out_of_money).abs() < 1e-3, "{lost} vs {out_of_money}");
+        assert!((at_expiry.risk(0.9).unwrap().var - premium).abs() < 1e-9);
+        // Under the pricing measure the expected payoff is the premium grown at the rate
+        assert!((at_expiry.mean() + premium - premium * (0.03 * 0.5_f64).exp()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_density_and_simulation_agree_with_the_transform() {
+        let market = BlackScholes::new(100.0, 100.0, 0.25, 0.02, 0.2, 0.0).unwrap();
+        let mut straddle = Strategy::straddle(100.0, 0.25).unwrap().scaled(-1.0).unwrap();
+        straddle.mark_entry(&market);
+        let pnl = |s: f64| straddle.pnl_at(&market, 0.1, s, 0.0);
+        let transform = PnlDistribution::lognormal(100.0, 0.02, 0.2, 0.1, pnl).unwrap();
+
+        let density = RiskNeutralDensity::from_model(&market, 0.1).unwrap();
+        let pushed = PnlDistribution::from_density(&density, pnl).unwrap();
+        let model = CorrelatedPaths::single(0.02, Asset::lognormal(100.0, 0.0, 0.2).unwrap()).unwrap();
+        let simulated =
+            PnlDistribution::monte_carlo(&model, 0.1, &MonteCarlo::new(50_000, 13).unwrap(), |s| pnl(s[0])).unwrap();
+        let exact = transform.risk(0.95).unwrap();
+        for other in [pushed.risk(0.95).unwrap(), simulated.risk(0.95).unwrap()] {
+            assert!((other.var - exact.var).abs() < 0.02 * exact.var, "{other:?} vs {exact:?}");
+            assert!((other.expected_shortfall - exact.expected_shortfall).abs() < 0.03 * exact.expected_shortfall);
+        }
+        // Short gamma: a fat left tail and mostly small gains
+        assert!(exact.expected_shortfall > exact.var && transform.quantile(0.5).unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_sample_quantiles_and_invalid_inputs() {
+        let samples = PnlDistribution::from_samples((1..=100).map(|i| i as f64 - 50.0).collect()).unwrap();
+        let risk = samples.risk(0.95).unwrap();
+        assert_eq!(risk.var, 45.0);
+        assert_eq!(risk.expected_shortfall, 47.0);
+        assert_eq!(samples.quantile(1.0).unwrap(), 50.0);
+        assert!((samples.probability_of_loss(45.0) - 0.04).abs() < 1e-12);
+
+        assert!(PnlDistribution::new(vec![], vec![]).is_err());
+        assert!(PnlDistribution::new(vec![1.0], vec![-1.0]).is_err());
+        assert!(PnlDistribution::new(vec![f64::NAN], vec![1.0]).is_err());
+        assert!(samples.quantile(0.0).is_err() && samples.risk(1.0).is_err());
+        assert!(PnlDistribution::lognormal(100.0, 0.0, 0.2, 0.0, |s| s).is_err());
+    }
+}
@@ -0,0 +1,768 @@
+use crate::barrier::BarrierOption;
+use crate::black_scholes::{BlackScholes, CashGreeks, Greeks, OptionType, EXPIRY_EPSILON};
+use crate::calibration::CalibrationRecord;
+use crate::digital::DigitalOption;
+use crate::error::BlackScholesError;
+use crate::strategy::{Leg, LegKind, Strategy, NAKED_BASE_RATE, SHORT_STOCK_RATE};
+use crate::validation;
+use std::collections::BTreeMap;
+
+/// Anything a portfolio can hold, together with the market it is valued in
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Instrument {
+    /// The underlying itself (delta one)
+    Underlying { spot: f64 },
+    Vanilla { model: BlackScholes, option_type: OptionType },
+    Digital { option: DigitalOption, option_type: OptionType },
+    Barrier { option: BarrierOption, option_type: OptionType },
+    /// Multi-leg strategy valued against `market` (strike and expiry ignored)
+    Strategy { strategy: Strategy, market: BlackScholes },
+}
+
+impl Instrument {
+    /// Current underlying price
+    pub fn spot(&self) -> f64 {
+        match self {
+            Instrument::Underlying { spot } => *spot,
+            Instrument::Vanilla { model, .. } => model.spot_price,
+            Instrument::Digital { option, .. } => option.model.spot_price,
+            Instrument::Barrier { option, .. } => option.model.spot_price,
+            Instrument::Strategy { market, .. } => market.spot_price,
+        }
+    }
+
+    /// Value of one unit
+    pub fn price(&self) -> f64 {
+        match self {
+            Instrument::Underlying { spot } => *spot,
+            Instrument::Vanilla { model, option_type } => model.price(*option_type),
+            Instrument::Digital { option, option_type } => option.price(*option_type),
+            Instrument::Barrier { option, option_type } => option.price(*option_type),
+            Instrument::Strategy { strategy, market } => strategy.value(market),
+        }
+    }
+
+    /// Pricing engine behind `price` and `greeks`
+    pub fn engine(&self) -> &'static str {
+        match self {
+            Instrument::Underlying { .. } => "Delta one",
+            Instrument::Vanilla { .. } | Instrument::Digital { .. } | Instrument::Strategy { .. } => "Black-Scholes",
+            Instrument::Barrier { .. } => "Reiner-Rubinstein",
+        }
+    }
+
+    /// Greeks of one unit
+    pub fn greeks(&self) -> Greeks {
+        match self {
+            Instrument::Underlying { .. } => Greeks {
+                delta: 1.0,
+                gamma: 0.0,
+                vega: 0.0,
+                theta: 0.0,
+                rho: 0.0,
+            },
+            Instrument::Vanilla { model, option_type } => model.greeks(*option_type),
+            Instrument::Digital { option, option_type } => option.greeks(*option_type),
+            Instrument::Barrier { option, option_type } => option.greeks(*option_type),
+            Instrument::Strategy { strategy, market } => strategy.greeks(market),
+        }
+    }
+}
+
+/// Model that produced a position's value and Greeks
+///
+/// Ordered by model name, then calibration time, so a risk split keeps
+/// positions marked off different calibrations of one model apart.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelTag {
+    /// Model or engine name, e.g. "Black-Scholes" or "Heston"
+    pub model: String,
+    /// When the model's parameters were calibrated, in seconds since the Unix epoch
+    pub calibrated_at: Option<u64>,
+}
+
+impl ModelTag {
+    /// An uncalibrated model or engine
+    pub fn new(model: &str) -> Self {
+        ModelTag {
+            model: model.to_string(),
+            calibrated_at: None,
+        }
+    }
+
+    pub fn calibrated(model: &str, timestamp: u64) -> Self {
+        ModelTag {
+            model: model.to_string(),
+            calibrated_at: Some(timestamp),
+        }
+    }
+}
+
+impl From<&CalibrationRecord> for ModelTag {
+    fn from(record: &CalibrationRecord) -> Self {
+        ModelTag::calibrated(record.model.name(), record.timestamp)
+    }
+}
+
+/// How an in-the-money option is settled at expiry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Settlement {
+    /// Intrinsic value paid in cash
+    Cash,
+    /// Underlying delivered against the strike
+    #[default]
+    Physical,
+}
+
+/// What happened to an expiring option
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BookingKind {
+    /// Out of the money, removed without cash flows
+    Expired,
+    /// Intrinsic value exchanged in cash
+    CashSettlement,
+    /// Long option exercised into the underlying
+    Exercise,
+    /// Short option assigned into the underlying
+    Assignment,
+}
+
+/// Cash and share flows booked when an option expires
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Booking {
+    pub underlying: String,
+    pub kind: BookingKind,
+    /// Units of the option that expired (negative for short)
+    pub units: f64,
+    /// Cash received (negative when paid)
+    pub cash: f64,
+    /// Shares received (negative when delivered)
+    pub shares: f64,
+}
+
+/// A holding of `quantity` contracts of an instrument on a named underlying
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    /// Underlying identifier used to group exposures, e.g. a ticker
+    pub underlying: String,
+    pub instrument: Instrument,
+    /// Number of contracts (negative for short)
+    pub quantity: f64,
+    /// Units of the instrument per contract
+    pub multiplier: f64,
+    /// Settlement of options at expiry (physical unless set)
+    pub settlement: Settlement,
+    /// Model whose parameters marked the position; the instrument's engine unless set
+    pub model: Option<ModelTag>,
+}
+
+impl Position {
+    pub fn new(
+        underlying: &str,
+        instrument: Instrument,
+        quantity: f64,
+        multiplier: f64,
+    ) -> Result<Self, BlackScholesError> {
+        Ok(Position {
+            underlying: underlying.to_string(),
+            instrument,
+            quantity: validation::finite("Quantity", quantity)?,
+            multiplier: validation::positive("Multiplier", multiplier)?,
+            settlement: Settlement::default(),
+            model: None,
+        })
+    }
+
+    pub fn with_settlement(mut self, settlement: Settlement) -> Self {
+        self.settlement = settlement;
+        self
+    }
+
+    /// Tag the position with the model that marked it, e.g. the calibration its volatility came from
+    pub fn with_model(mut self, model: ModelTag) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    /// Model behind the position's value and Greeks
+    pub fn model_tag(&self) -> ModelTag {
+        self.model.clone().unwrap_or_else(|| ModelTag::new(self.instrument.engine()))
+    }
+
+    /// Units of the instrument held, quantity × multiplier
+    pub fn units(&self) -> f64 {
+        self.quantity * self.multiplier
+    }
+
+    /// Market value of the position
+    pub fn market_value(&self) -> f64 {
+        self.units() * self.instrument.price()
+    }
+
+    /// Per-unit Greeks scaled by the units held
+    pub fn greeks(&self) -> Greeks {
+        let g = self.instrument.greeks();
+        let units = self.units();
+        Greeks {
+            delta: g.delta * units,
+            gamma: g.gamma * units,
+            vega: g.vega * units,
+            theta: g.theta * units,
+            rho: g.rho * units,
+        }
+    }
+
+    /// Greeks in currency
+    pub fn cash_greeks(&self) -> CashGreeks {
+        self.instrument.greeks().to_cash(self.instrument.spot(), self.units())
+    }
+}
+
+/// Aggregated value and risk of a group of positions
+///
+/// `greeks` are in units of the underlying and only meaningful within one
+/// underlying; `cash_greeks` are in currency and add up across underlyings.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exposure {
+    pub market_value: f64,
+    pub greeks: Greeks,
+    pub cash_greeks: CashGreeks,
+}
+
+impl Default for Exposure {
+    fn default() -> Self {
+        Exposure {
+            market_value: 0.0,
+            greeks: Greeks {
+                delta: 0.0,
+                gamma: 0.0,
+                vega: 0.0,
+                theta: 0.0,
+                rho: 0.0,
+            },
+            cash_greeks: CashGreeks::default(),
+        }
+    }
+}
+
+impl Exposure {
+    fn add(&mut self, position: &Position) {
+        let g = position.greeks();
+        self.market_value += position.market_value();
+        self.greeks.delta += g.delta;
+        self.greeks.gamma += g.gamma;
+        self.greeks.vega += g.vega;
+        self.greeks.theta += g.theta;
+        self.greeks.rho += g.rho;
+        self.cash_greeks = self.cash_greeks + position.cash_greeks();
+    }
+}
+
+/// Book of positions across underlyings
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Portfolio {
+    pub positions: Vec<Position>,
+    /// Cash balance from settlements, exercises and assignments
+    pub cash: f64,
+}
+
+/// Advance an option model by `elapsed` years; false once it has expired
+fn age_model(model: &mut BlackScholes, elapsed: f64) -> bool {
+    let remaining = model.time_to_expiry - elapsed;
+    if remaining <= EXPIRY_EPSILON {
+        return false;
+    }
+    *model = model.aged(remaining);
+    true
+}
+
+/// The same model an instant before expiry, where its price is the payoff
+fn at_expiry(model: &BlackScholes) -> BlackScholes {
+    model.aged(EXPIRY_EPSILON)
+}
+
+impl Portfolio {
+    pub fn new(positions: Vec<Position>) -> Self {
+        Portfolio { positions, cash: 0.0 }
+    }
+
+    pub fn add(&mut self, position: Position) {
+        self.positions.push(position);
+    }
+
+    /// Total market value, cash included
+    pub fn market_value(&self) -> f64 {
+        self.cash + self.positions.iter().map(Position::market_value).sum::<f64>()
+    }
+
+    /// Netted Greeks of every position (units of each position's underlying)
+    pub fn greeks(&self) -> Greeks {
+        self.total().greeks
+    }
+
+    /// Netted currency Greeks, comparable across underlyings
+    pub fn cash_greeks(&self) -> CashGreeks {
+        self.positions.iter().map(Position::cash_greeks).sum()
+    }
+
+    /// Book-level value and risk, cash included
+    pub fn total(&self) -> Exposure {
+        let mut total = Exposure {
+            market_value: self.cash,
+            ..Exposure::default()
+        };
+        for position in &self.positions {
+            total.add(position);
+        }
+        total
+    }
+
+    /// Value and risk per underlying, ordered by identifier (cash excluded)
+    pub fn by_underlying(&self) -> BTreeMap<String, Exposure> {
+        let mut breakdown: BTreeMap<String, Exposure> = BTreeMap::new();
+        for position in &self.positions {
+            breakdown.entry(position.underlying.clone()).or_default().add(position);
+        }
+        breakdown
+    }
+
+    /// Value and risk per model, ordered by tag (cash excluded)
+    ///
+    /// Each position counts under [`Position::model_tag`], so untagged
+    /// positions fall under their instrument's engine.
+    pub fn by_model(&self) -> BTreeMap<ModelTag, Exposure> {
+        let mut breakdown: BTreeMap<ModelTag, Exposure> = BTreeMap::new();
+        for position in &self.positions {
+            breakdown.entry(position.model_tag()).or_default().add(position);
+        }
+        breakdown
+    }
+
+    /// Let `elapsed` years pass: shorten every option's life and settle those that expire
+    ///
+    /// Expiring options settle against their instrument's current spot.
+    /// In-the-money cash-settled options pay intrinsic value into `cash`;
+    /// physically settled ones are exercised (long) or assigned (short),
+    /// moving the strike through `cash` and the shares into an underlying
+    /// position, merged with an existing one where present. Digital and
+    /// barrier options always settle in cash. Expired positions and strategy
+    /// legs are removed.
+    pub fn age(&mut self, elapsed: f64) -> Result<Vec<Booking>, BlackScholesError> {
+        let elapsed = validation::non_negative("Elapsed time", elapsed)?;
+        let mut bookings = Vec::new();
+        let mut deliveries = Vec::new();
+        let mut kept = Vec::with_capacity(self.positions.len());
+        for mut position in std::mem::take(&mut self.positions) {
+            let (units, spot, settlement) = (position.units(), position.instrument.spot(), position.settlement);
+            let underlying = position.underlying.clone();
+            let alive = match &mut position.instrument {
+                Instrument::Underlying { .. } => true,
+                Instrument::Vanilla { model, option_type } => {
+                    let alive = age_model(model, elapsed);
+                    if !alive {
+                        bookings.push(settle(&underlying, settlement, *option_type, model.strike_price, spot, units));
+                    }
+                    alive
+                }
+                Instrument::Digital { option, option_type } => {
+                    let alive = age_model(&mut option.model, elapsed);
+                    if !alive {
+                        let payoff = DigitalOption {
+                            model: at_expiry(&option.model),
+                            ..*option
+                        }
+                        .price(*option_type);
+                        bookings.push(cash_settle(&underlying, units, payoff));
+                    }
+                    alive
+                }
+                Instrument::Barrier { option, option_type } => {
+                    let alive = age_model(&mut option.model, elapsed);
+                    if !alive {
+                        let payoff = BarrierOption {
+                            model: at_expiry(&option.model),
+                            ..*option
+                        }
+                        .price(*option_type);
+                        bookings.push(cash_settle(&underlying, units, payoff));
+                    }
+                    alive
+                }
+                Instrument::Strategy { strategy, .. } => {
+                    let mut expired = Vec::new();
+                    strategy.legs.retain_mut(|leg| match &mut leg.kind {
+                        LegKind::Option {
+                            option_type,
+                            strike,
+                            expiry,
+                        } => {
+                            *expiry -= elapsed;
+                            if *expiry <= EXPIRY_EPSILON {
+                                expired.push((*option_type, *strike, leg.quantity));
+                                return false;
+                            }
+                            true
+                        }
+                        LegKind::Underlying => true,
+                    });
+                    for (option_type, strike, quantity) in expired {
+                        bookings.push(settle(&underlying, settlement, option_type, strike, spot, units * quantity));
+                    }
+                    !strategy.legs.is_empty()
+                }
+            };
+            if alive {
+                kept.push(position);
+            }
+        }
+        self.positions = kept;
+
+        for booking in &bookings {
+            self.cash += booking.cash;
+            if booking.shares != 0.0 {
+                deliveries.push((booking.underlying.clone(), booking.shares));
+            }
+        }
+        for (underlying, shares) in deliveries {
+            self.deliver(&underlying, shares)?;
+        }
+        Ok(bookings)
+    }
+
+    /// Simplified initial margin of the book (see `Strategy::margin`)
+    ///
+    /// Vanilla options and strategy legs on one underlying are pooled by
+    /// expiry, so longs cover shorts within an expiry. Shares join the front
+    /// expiry, where long stock covers short calls and short stock is charged
+    /// the short-stock rate. Each short digital or barrier unit is charged
+    /// as naked: its premium plus 20% of spot.
+    pub fn margin(&self) -> Result<f64, BlackScholesError> {
+        let mut books: BTreeMap<&str, MarginBook> = BTreeMap::new();
+        let mut requirement = 0.0;
+        for position in &self.positions {
+            let units = position.units();
+            let book = books.entry(&position.underlying).or_default();
+            book.spot = position.instrument.spot();
+            match &position.instrument {
+                Instrument::Underlying { .. } => book.stock += units,
+                Instrument::Vanilla { model, option_type } => {
+                    book.market.get_or_insert(*model);
+                    let leg = Leg::option(*option_type, model.strike_price, model.time_to_expiry, units)?;
+                    book.options.push(leg.with_volatility(model.volatility)?);
+                }
+                Instrument::Strategy { strategy, market } => {
+                    book.market.get_or_insert(*market);
+                    for leg in &strategy.legs {
+                        match leg.kind {
+                            LegKind::Underlying => book.stock += units * leg.quantity,
+                            LegKind::Option { .. } => book.options.push(Leg {
+                                quantity: units * leg.quantity,
+                                volatility: Some(leg.volatility.unwrap_or(market.volatility)),
+                                ..*leg
+                            }),
+                        }
+                    }
+                }
+                Instrument::Digital { .. } | Instrument::Barrier { .. } => {
+                    requirement += (-units).max(0.0) * (position.instrument.price() + NAKED_BASE_RATE * book.spot);
+                }
+            }
+        }
+        for book in books.into_values() {
+            requirement += book.margin()?;
+        }
+        Ok(requirement)
+    }
+
+    /// Add delivered shares to the book's underlying position
+    fn deliver(&mut self, underlying: &str, shares: f64) -> Result<(), BlackScholesError> {
+        let existing = self.positions.iter_mut().find(|p| {
+            p.underlying == underlying && p.multiplier == 1.0 && matches!(p.instrument, Instrument::Underlying { .. })
+        });
+        match existing {
+            Some(position) => position.quantity += shares,
+            None => {
+                let spot = self
+                    .positions
+                    .iter()
+                    .find(|p| p.underlying == underlying)
+                    .map(|p| p.instrument.spot())
+                    .ok_or_else(|| BlackScholesError::InvalidInput(format!("No spot for underlying {underlying}")))?;
+                self.positions.push(Position::new(underlying, Instrument::Underlying { spot }, shares, 1.0)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Options and shares of one underlying, pooled for margining
+#[derive(Default)]
+struct MarginBook {
+    spot: f64,
+    stock: f64,
+    options: Vec<Leg>,
+    /// Market of the first option seen, used for spot, carry and premiums
+    market: Option<BlackScholes>,
+}
+
+impl MarginBook {
+    fn margin(mut self) -> Result<f64, BlackScholesError> {
+        let Some(mut market) = self.market.filter(|_| !self.options.is_empty()) else {
+            return Ok(SHORT_STOCK_RATE * (-self.stock).max(0.0) * self.spot);
+        };
+        market.spot_price = self.spot;
+        self.options.sort_by(|a, b| a.expiry().unwrap_or(0.0).total_cmp(&b.expiry().unwrap_or(0.0)));
+        let mut groups: Vec<Vec<Leg>> = Vec::new();
+        for leg in self.options {
+            match groups.last_mut() {
+                Some(group) if (group[0].expiry().unwrap_or(0.0) - leg.expiry().unwrap_or(0.0)).abs() < EXPIRY_EPSILON => {
+                    group.push(leg)
+                }
+                _ => groups.push(vec![leg]),
+            }
+        }
+        if self.stock != 0.0 {
+            groups[0].push(Leg::underlying(self.stock)?);
+        }
+        let mut requirement = 0.0;
+        for legs in groups {
+            let mut strategy = Strategy::new("margin", legs)?;
+            strategy.mark_entry(&market);
+            requirement += strategy.margin(&market)?;
+        }
+        Ok(requirement)
+    }
+}
+
+/// Booking for `units` of a vanilla option on `underlying` expiring at `spot`
+fn settle(underlying: &str, settlement: Settlement, option_type: OptionType, strike: f64, spot: f64, units: f64) -> Booking {
+    let payoff = match option_type {
+        OptionType::Call => (spot - strike).max(0.0),
+        OptionType::Put => (strike - spot).max(0.0),
+    };
+    if payoff <= 0.0 || settlement == Settlement::Cash {
+        return cash_settle(underlying, units, payoff);
+    }
+    // The call holder buys the shares at the strike, the put holder sells them
+    let shares = match option_type {
+        OptionType::Call => units,
+        OptionType::Put => -units,
+    };
+    Booking {
+        underlying: underlying.to_string(),
+        kind: if units > 0.0 { BookingKind::Exercise } else { BookingKind::Assignment },
+        units,
+        cash: -shares * strike,
+        shares,
+    }
+}
+
+fn cash_settle(underlying: &str, units: f64, payoff: f64) -> Booking {
+    Booking {
+        underlying: underlying.to_string(),
+        kind: if payoff > 0.0 { BookingKind::CashSettlement } else { BookingKind::Expired },
+        units,
+        cash: units * payoff,
+        shares: 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::barrier::BarrierType;
+    use crate::strategy::Leg;
+
+    fn vanilla(spot: f64, strike: f64, option_type: OptionType) -> Instrument {
+        Instrument::Vanilla {
+            model: BlackScholes::new(spot, strike, 0.5, 0.03, 0.25, 0.0).unwrap(),
+            option_type,
+        }
+    }
+
+    #[test]
+    fn test_totals_scale_by_quantity_and_multiplier() {
+        let call = vanilla(100.0, 105.0, OptionType::Call);
+        let unit_price = call.price();
+        let unit_delta = call.greeks().delta;
+        let portfolio = Portfolio::new(vec![
+            Position::new("AAA", call.clone(), 10.0, 100.0).unwrap(),
+            Position::new("AAA", call, -4.0, 100.0).unwrap(),
+        ]);
+        assert!((portfolio.market_value() - 600.0 * unit_price).abs() < 1e-9);
+        assert!((portfolio.greeks().delta - 600.0 * unit_delta).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_delta_hedge_nets_to_zero() {
+        let put = vanilla(50.0, 50.0, OptionType::Put);
+        let hedge = -100.0 * put.greeks().delta;
+        let mut portfolio = Portfolio::default();
+        portfolio.add(Position::new("BBB", put, 1.0, 100.0).unwrap());
+        portfolio.add(Position::new("BBB", Instrument::Underlying { spot: 50.0 }, hedge, 1.0).unwrap());
+
+        let total = portfolio.total();
+        assert!(total.greeks.delta.abs() < 1e-12);
+        assert!(total.cash_greeks.delta.abs() < 1e-9);
+        assert!(total.greeks.gamma > 0.0);
+    }
+
+    #[test]
+    fn test_breakdown_by_underlying_sums_to_total() {
+        let strategy = Strategy::straddle(100.0, 0.5).unwrap();
+        let market = BlackScholes::new(100.0, 100.0, 0.5, 0.03, 0.2, 0.0).unwrap();
+        let portfolio = Portfolio::new(vec![
+            Position::new("AAA", vanilla(100.0, 95.0, OptionType::Put), -5.0, 100.0).unwrap(),
+            Position::new("BBB", vanilla(20.0, 22.0, OptionType::Call), 30.0, 100.0).unwrap(),
+            Position::new("AAA", Instrument::Strategy { strategy, market }, 2.0, 100.0).unwrap(),
+        ]);
+
+        let breakdown = portfolio.by_underlying();
+        assert_eq!(breakdown.keys().collect::<Vec<_>>(), ["AAA", "BBB"]);
+        let value: f64 = breakdown.values().map(|e| e.market_value).sum();
+        let cash_vega: f64 = breakdown.values().map(|e| e.cash_greeks.vega).sum();
+        assert!((value - portfolio.market_value()).abs() < 1e-9);
+        assert!((cash_vega - portfolio.cash_greeks().vega).abs() < 1e-9);
+        // Dollar delta uses each underlying's own spot
+        let bbb = &breakdown["BBB"];
+        assert!((bbb.cash_greeks.delta - bbb.greeks.delta * 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_breakdown_by_model_tag() {
+        let barrier = BarrierOption::new(
+            BlackScholes::new(100.0, 100.0, 0.5, 0.03, 0.25, 0.0).unwrap(),
+            BarrierType::DownAndOut,
+            80.0,
+            0.0,
+        )
+        .unwrap();
+        let heston = ModelTag::calibrated("Heston", 1_700_000_000);
+        let portfolio = Portfolio::new(vec![
+            Position::new("AAA", vanilla(100.0, 95.0, OptionType::Put), -5.0, 100.0).unwrap(),
+            Position::new("AAA", vanilla(100.0, 110.0, OptionType::Call), 3.0, 100.0)
+                .unwrap()
+                .with_model(heston.clone()),
+            Position::new("BBB", vanilla(20.0, 22.0, OptionType::Call), 30.0, 100.0)
+                .unwrap()
+                .with_model(ModelTag::calibrated("Heston", 1_700_086_400)),
+            Position::new("AAA", Instrument::Barrier { option: barrier, option_type: OptionType::Call }, 1.0, 100.0)
+                .unwrap(),
+        ]);
+
+        let breakdown = portfolio.by_model();
+        let models: Vec<(&str, Option<u64>)> = breakdown.keys().map(|t| (t.model.as_str(), t.calibrated_at)).collect();
+        assert_eq!(
+            models,
+            [
+                ("Black-Scholes", None),
+                ("Heston", Some(1_700_000_000)),
+                ("Heston", Some(1_700_086_400)),
+                ("Reiner-Rubinstein", None)
+            ]
+        );
+        assert!((breakdown[&heston].market_value - portfolio.positions[1].market_value()).abs() < 1e-12);
+        let cash_gamma: f64 = breakdown.values().map(|e| e.cash_greeks.gamma).sum();
+        assert!((cash_gamma - portfolio.cash_greeks().gamma).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_aging_shortens_option_lives() {
+        let call = Position::new("AAA", vanilla(100.0, 100.0, OptionType::Call), 1.0, 100.0).unwrap();
+        let mut portfolio = Portfolio::new(vec![call]);
+        assert!(portfolio.age(0.1).unwrap().is_empty());
+        let Instrument::Vanilla { model, .. } = &portfolio.positions[0].instrument else {
+            panic!("vanilla expected");
+        };
+        assert!((model.time_to_expiry - 0.4).abs() < 1e-15);
+        assert!((model.vol_time - 0.4).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_physical_exercise_and_assignment() {
+        let mut portfolio = Portfolio::new(vec![
+            Position::new("AAA", vanilla(110.0, 100.0, OptionType::Call), 2.0, 100.0).unwrap(),
+            Position::new("AAA", vanilla(110.0, 120.0, OptionType::Put), -1.0, 100.0).unwrap(),
+            Position::new("AAA", vanilla(110.0, 130.0, OptionType::Call), 5.0, 100.0).unwrap(),
+            Position::new("AAA", Instrument::Underlying { spot: 110.0 }, -50.0, 1.0).unwrap(),
+        ]);
+        let bookings = portfolio.age(0.5).unwrap();
+        let kinds: Vec<BookingKind> = bookings.iter().map(|b| b.kind).collect();
+        assert_eq!(kinds, [BookingKind::Exercise, BookingKind::Assignment, BookingKind::Expired]);
+        // Bought 200 at 100, bought 100 at 120 through assignment
+        assert_eq!(portfolio.cash, -200.0 * 100.0 - 100.0 * 120.0);
+        assert_eq!(portfolio.positions.len(), 1);
+        assert_eq!(portfolio.positions[0].quantity, 250.0);
+        // Value is conserved across expiry: intrinsic became shares and cash
+        let intrinsic = 200.0 * 10.0 - 100.0 * 10.0;
+        assert!((portfolio.market_value() - (intrinsic - 50.0 * 110.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cash_settlement_and_strategy_legs() {
+        let market = BlackScholes::new(90.0, 100.0, 1.0, 0.03, 0.2, 0.0).unwrap();
+        let strategy = Strategy::new(
+            "Calendar",
+            vec![
+                Leg::option(OptionType::Put, 95.0, 0.25, 1.0).unwrap(),
+                Leg::option(OptionType::Put, 95.0, 1.0, -1.0).unwrap(),
+            ],
+        )
+        .unwrap();
+        let mut portfolio = Portfolio::new(vec![
+            Position::new("IDX", Instrument::Strategy { strategy, market }, 3.0, 100.0)
+                .unwrap()
+                .with_settlement(Settlement::Cash),
+        ]);
+        let bookings = portfolio.age(0.25).unwrap();
+        assert_eq!(bookings.len(), 1);
+        assert_eq!(bookings[0].kind, BookingKind::CashSettlement);
+        assert!((portfolio.cash - 300.0 * 5.0).abs() < 1e-9);
+        let Instrument::Strategy { strategy, .. } = &portfolio.positions[0].instrument else {
+            panic!("strategy expected");
+        };
+        assert_eq!(strategy.legs.len(), 1);
+        let remaining = LegKind::Option {
+            option_type: OptionType::Put,
+            strike: 95.0,
+            expiry: 0.75,
+        };
+        assert_eq!(strategy.legs[0].kind, remaining);
+        assert!(portfolio.age(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_margin_pools_options_by_underlying() {
+        let (short, long) = (vanilla(100.0, 100.0, OptionType::Call), vanilla(100.0, 105.0, OptionType::Call));
+        let credit = short.price() - long.price();
+        let naked = Portfolio::new(vec![Position::new("AAA", short.clone(), -2.0, 100.0).unwrap()]);
+        let mut spread = naked.clone();
+        spread.add(Position::new("AAA", long.clone(), 2.0, 100.0).unwrap());
+        assert!((spread.margin().unwrap() - 200.0 * (5.0 - credit)).abs() < 1e-9);
+        assert!(naked.margin().unwrap() > spread.margin().unwrap());
+
+        // A long call on another underlying covers nothing and is charged its premium
+        let mut unrelated = naked.clone();
+        unrelated.add(Position::new("BBB", long.clone(), 2.0, 100.0).unwrap());
+        let premium = 200.0 * long.price();
+        assert!((unrelated.margin().unwrap() - naked.margin().unwrap() - premium).abs() < 1e-9);
+
+        let short_stock = Portfolio::new(vec![Position::new("AAA", Instrument::Underlying { spot: 40.0 }, -10.0, 1.0).unwrap()]);
+        assert!((short_stock.margin().unwrap() - 200.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_positions() {
+        let call = vanilla(100.0, 100.0, OptionType::Call);
+        assert!(Position::new("AAA", call.clone(), f64::NAN, 100.0).is_err());
+        assert!(Position::new("AAA", call, 1.0, 0.0).is_err());
+    }
+}
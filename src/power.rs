@@ -0,0 +1,152 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// European power option paying max(Sⁿ - K, 0) for a call or max(K - Sⁿ, 0) for a put
+///
+/// Sⁿ is lognormal with volatility nσ and forward
+/// Sⁿ·exp(n(r - q)T + n(n - 1)σ²T/2), so it is priced by Black-Scholes as
+/// an asset with spot Sⁿ and the dividend yield that reproduces that
+/// forward. The model's strike is the strike on Sⁿ.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerOption {
+    /// Underlying market parameters, with the strike on Sⁿ
+    pub model: BlackScholes,
+    /// Exponent n
+    pub power: f64,
+}
+
+impl PowerOption {
+    /// Create a new power option
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes parameters (spot, strike on Sⁿ, expiry, rate, vol, dividend)
+    /// * `power` - Exponent n, positive
+    pub fn new(model: BlackScholes, power: f64) -> Result<Self, BlackScholesError> {
+        let power = validation::positive("Power", power)?;
+        Ok(PowerOption { model, power })
+    }
+
+    /// Black-Scholes model of Sⁿ
+    fn powered(&self) -> BlackScholes {
+        let m = &self.model;
+        let n = self.power;
+        BlackScholes {
+            spot_price: m.spot_price.powf(n),
+            volatility: n * m.volatility,
+            dividend_yield: self.powered_yield(m.risk_free_rate, m.volatility, m.time_to_expiry, m.vol_time),
+            ..*m
+        }
+    }
+
+    /// Dividend yield of Sⁿ, r - n(r - q) - n(n - 1)σ²·v/(2T) with v the volatility clock
+    fn powered_yield(&self, rate: f64, volatility: f64, time_to_expiry: f64, vol_time: f64) -> f64 {
+        let n = self.power;
+        rate - n * (rate - self.model.dividend_yield)
+            - 0.5 * n * (n - 1.0) * volatility * volatility * vol_time / time_to_expiry
+    }
+
+    /// Calculate option price
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        self.powered().price(option_type)
+    }
+
+    /// Calculate all Greeks analytically, with the conventions of `BlackScholes::greeks`
+    ///
+    /// Greeks of Sⁿ are mapped back by the chain rule; the powered yield
+    /// moves with σ, r and, when the volatility clock differs from the
+    /// calendar, with time, which enters through ∂V/∂q = -T·Sⁿ·Δ.
+    pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        let m = &self.model;
+        let n = self.power;
+        let powered = self.powered();
+        let g = powered.greeks(option_type);
+        let ds = n * m.spot_price.powf(n - 1.0);
+        let d2s = n * (n - 1.0) * m.spot_price.powf(n - 2.0);
+        let psi = -m.time_to_expiry * powered.spot_price * g.delta;
+
+        let dq_dvol = -n * (n - 1.0) * m.volatility * m.vol_time / m.time_to_expiry;
+        let dq_drate = 1.0 - n;
+        // Both clocks advance together as time to expiry grows
+        let dq_dtime = -0.5 * n * (n - 1.0) * m.volatility.powi(2) * (m.time_to_expiry - m.vol_time)
+            / m.time_to_expiry.powi(2);
+        Greeks {
+            delta: g.delta * ds,
+            gamma: g.gamma * ds * ds + g.delta * d2s,
+            vega: n * g.vega + psi * dq_dvol / 100.0,
+            theta: g.theta - psi * dq_dtime / m.time_scale.days_per_year(),
+            rho: g.rho + psi * dq_drate / 100.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::norm_pdf;
+
+    #[test]
+    fn test_unit_power_is_vanilla() {
+        let model = BlackScholes::new(100.0, 105.0, 1.0, 0.05, 0.2, 0.02).unwrap();
+        let power = PowerOption::new(model, 1.0).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            assert!((power.price(option_type) - model.price(option_type)).abs() < 1e-12);
+            assert!((power.greeks(option_type).gamma - model.greeks(option_type).gamma).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_squared_payoff_matches_integration() {
+        let model = BlackScholes::new(10.0, 110.0, 0.5, 0.04, 0.3, 0.01).unwrap();
+        let option = PowerOption::new(model, 2.0).unwrap();
+        let drift = (0.04 - 0.01 - 0.5 * 0.09) * 0.5;
+        let sd = 0.3 * 0.5_f64.sqrt();
+        // Trapezoid over the standard normal driving S(T), ±8 standard deviations
+        let n = 16_000;
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let expected: f64 = (0..=n)
+                .map(|i| {
+                    let z = -8.0 + 16.0 * i as f64 / n as f64;
+                    let squared = (10.0 * (drift + sd * z).exp()).powi(2);
+                    let payoff = match option_type {
+                        OptionType::Call => (squared - 110.0).max(0.0),
+                        OptionType::Put => (110.0 - squared).max(0.0),
+                    };
+                    let weight = if i == 0 || i == n { 0.5 } else { 1.0 };
+                    weight * payoff * norm_pdf(z) * 16.0 / n as f64
+                })
+                .sum::<f64>()
+                * (-0.04 * 0.5_f64).exp();
+            let price = option.price(option_type);
+            assert!((price - expected).abs() < 1e-6 * expected, "{option_type:?}: {price} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn test_greeks_match_finite_differences() {
+        let mut model = BlackScholes::new(20.0, 400.0, 0.75, 0.03, 0.25, 0.02).unwrap();
+        for vol_time in [0.75, 0.6] {
+            model.vol_time = vol_time;
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let option = PowerOption::new(model, 2.0).unwrap();
+                let analytic = option.greeks(option_type);
+                let pricer = |m: &BlackScholes| PowerOption { model: *m, power: 2.0 }.price(option_type);
+                let numeric = Greeks::from_finite_differences(&model, pricer);
+                let close = |a: f64, b: f64| (a - b).abs() < 1e-5 * (1.0 + b.abs());
+                assert!(close(analytic.delta, numeric.delta), "{analytic:?} vs {numeric:?}");
+                assert!(close(analytic.gamma, numeric.gamma), "{analytic:?} vs {numeric:?}");
+                assert!(close(analytic.vega, numeric.vega), "{analytic:?} vs {numeric:?}");
+                assert!(close(analytic.theta, numeric.theta), "{analytic:?} vs {numeric:?}");
+                assert!(close(analytic.rho, numeric.rho), "{analytic:?} vs {numeric:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_power() {
+        let model = BlackScholes::new(100.0, 100.0, 0.5, 0.03, 0.25, 0.0).unwrap();
+        assert!(PowerOption::new(model, 0.0).is_err());
+        assert!(PowerOption::new(model, f64::INFINITY).is_err());
+    }
+}
@@ -0,0 +1,291 @@
+//! Best-of and worst-of (rainbow) options on several lognormal assets
+
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::math::{bivariate_norm_cdf, norm_cdf};
+use crate::monte_carlo::{McResult, MonteCarlo};
+use crate::multi_asset::MultiAssetMc;
+use crate::risk::correlation_factor;
+use crate::validation;
+
+/// Volatility of the asset ratio below which the best and worst assets are known today
+const MIN_TOTAL_VOL: f64 = 1e-12;
+
+/// Which asset's price the option is written on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RainbowPayoff {
+    /// The highest price at expiry, max(S_1, …, S_n)
+    BestOf,
+    /// The lowest price at expiry, min(S_1, …, S_n)
+    WorstOf,
+}
+
+/// European option on the best or worst of several correlated lognormal assets
+///
+/// A call pays max(X - K, 0) and a put max(K - X, 0), where X is the
+/// maximum or minimum of the assets' prices at expiry. Two assets have
+/// Stulz's (1982) closed form; any number can be priced by simulation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RainbowOption {
+    pub payoff: RainbowPayoff,
+    pub spots: Vec<f64>,
+    /// Continuous dividend yield of each asset (annual)
+    pub dividend_yields: Vec<f64>,
+    /// Volatility of each asset (annual)
+    pub volatilities: Vec<f64>,
+    /// Correlation matrix of the assets' log-returns
+    pub correlation: Vec<Vec<f64>>,
+    pub strike: f64,
+    /// Time to expiration in years
+    pub time_to_expiry: f64,
+    /// Continuously compounded risk-free rate (annual)
+    pub risk_free_rate: f64,
+}
+
+impl RainbowOption {
+    /// Create a new rainbow option
+    ///
+    /// # Arguments
+    /// * `payoff` - Best-of or worst-of
+    /// * `spots`, `dividend_yields`, `volatilities` - One entry per asset, at least two
+    /// * `correlation` - Correlation matrix of the assets' log-returns
+    /// * `strike` - Strike on the best or worst price (K); zero for the bare max or min
+    /// * `time_to_expiry` - Time to expiration in years (T)
+    /// * `risk_free_rate` - Continuously compounded rate (r)
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        payoff: RainbowPayoff,
+        spots: Vec<f64>,
+        dividend_yields: Vec<f64>,
+        volatilities: Vec<f64>,
+        correlation: Vec<Vec<f64>>,
+        strike: f64,
+        time_to_expiry: f64,
+        risk_free_rate: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let n = spots.len();
+        if n < 2 {
+            return Err(BlackScholesError::invalid("A rainbow option needs at least two assets"));
+        }
+        if dividend_yields.len() != n || volatilities.len() != n {
+            return Err(BlackScholesError::invalid("Spots, dividend yields and volatilities must match"));
+        }
+        if correlation.len() != n || correlation.iter().any(|row| row.len() != n) {
+            return Err(BlackScholesError::invalid("Correlation must match the assets"));
+        }
+        for i in 0..n {
+            validation::positive("Spot price", spots[i])?;
+            validation::finite("Dividend yield", dividend_yields[i])?;
+            validation::positive("Volatility", volatilities[i])?;
+        }
+        correlation_factor(&correlation)?;
+        Ok(RainbowOption {
+            payoff,
+            spots,
+            dividend_yields,
+            volatilities,
+            correlation,
+            strike: validation::non_negative("Strike price", strike)?,
+            time_to_expiry: validation::positive("Time to expiry", time_to_expiry)?,
+            risk_free_rate: validation::finite("Risk-free rate", risk_free_rate)?,
+        })
+    }
+
+    /// Stulz's closed form for two assets
+    ///
+    /// Calls integrate the bivariate normal over the region where each asset
+    /// is the best (or worst) and finishes above the strike; puts follow from
+    /// the zero-strike call by parity, P(K) = K·e^(-rT) - C(0) + C(K).
+    pub fn stulz(&self, option_type: OptionType) -> Result<f64, BlackScholesError> {
+        if self.spots.len() != 2 {
+            return Err(BlackScholesError::invalid("Stulz's formula is for two assets"));
+        }
+        let t = self.time_to_expiry;
+        let (s1, s2) = (self.spots[0], self.spots[1]);
+        let (v1, v2) = (self.volatilities[0], self.volatilities[1]);
+        let rho = self.correlation[0][1];
+        let discount = (-self.risk_free_rate * t).exp();
+        // Discounted forwards, the prices today of receiving each asset at expiry
+        let (p1, p2) = (s1 * (-self.dividend_yields[0] * t).exp(), s2 * (-self.dividend_yields[1] * t).exp());
+
+        let ratio_vol = (v1 * v1 + v2 * v2 - 2.0 * rho * v1 * v2).max(0.0).sqrt();
+        let total = ratio_vol * t.sqrt();
+        if total < MIN_TOTAL_VOL {
+            // The asset with the larger forward stays ahead on every path
+            let best = if p1 >= p2 { 0 } else { 1 };
+            let chosen = match self.payoff {
+                RainbowPayoff::BestOf => best,
+                RainbowPayoff::WorstOf => 1 - best,
+            };
+            return self.vanilla(chosen, option_type);
+        }
+
+        let d = ((p1 / p2).ln() + 0.5 * total * total) / total;
+        let (rho1, rho2) = ((v1 - rho * v2) / ratio_vol, (v2 - rho * v1) / ratio_vol);
+        let call = |strike: f64| {
+            if strike <= 0.0 {
+                return match self.payoff {
+                    RainbowPayoff::BestOf => p1 * norm_cdf(d) + p2 * norm_cdf(total - d),
+                    RainbowPayoff::WorstOf => p1 * norm_cdf(-d) + p2 * norm_cdf(d - total),
+                };
+            }
+            let (w1, w2) = (v1 * t.sqrt(), v2 * t.sqrt());
+            let y1 = ((p1 / (strike * discount)).ln() + 0.5 * w1 * w1) / w1;
+            let y2 = ((p2 / (strike * discount)).ln() + 0.5 * w2 * w2) / w2;
+            match self.payoff {
+                RainbowPayoff::BestOf => {
+                    p1 * bivariate_norm_cdf(y1, d, rho1) + p2 * bivariate_norm_cdf(y2, total - d, rho2)
+                        - strike * discount * (1.0 - bivariate_norm_cdf(w1 - y1, w2 - y2, rho))
+                }
+                RainbowPayoff::WorstOf => {
+                    p1 * bivariate_norm_cdf(y1, -d, -rho1) + p2 * bivariate_norm_cdf(y2, d - total, -rho2)
+                        - strike * discount * bivariate_norm_cdf(y1 - w1, y2 - w2, rho)
+                }
+            }
+        };
+        Ok(match option_type {
+            OptionType::Call => call(self.strike),
+            OptionType::Put => self.strike * discount - call(0.0) + call(self.strike),
+        })
+    }
+
+    /// Black-Scholes on one of the assets
+    fn vanilla(&self, asset: usize, option_type: OptionType) -> Result<f64, BlackScholesError> {
+        let strike = self.strike.max(f64::MIN_POSITIVE);
+        let bs = BlackScholes::new(
+            self.spots[asset],
+            strike,
+            self.time_to_expiry,
+            self.risk_free_rate,
+            self.volatilities[asset],
+            self.dividend_yields[asset],
+        )?;
+        Ok(bs.price(option_type))
+    }
+
+    /// Monte Carlo price on exact correlated lognormal paths, for any number of assets
+    pub fn monte_carlo(&self, option_type: OptionType, mc: MonteCarlo) -> Result<McResult, BlackScholesError> {
+        let engine = MultiAssetMc::gbm(
+            self.risk_free_rate,
+            &self.spots,
+            &self.dividend_yields,
+            &self.volatilities,
+            self.correlation.clone(),
+            mc,
+        )?;
+        let discount = (-self.risk_free_rate * self.time_to_expiry).exp();
+        engine.price(&[self.time_to_expiry], discount, |paths| {
+            let prices = paths.iter().map(|p| p[0]);
+            let value = match self.payoff {
+                RainbowPayoff::BestOf => prices.fold(f64::NEG_INFINITY, f64::max),
+                RainbowPayoff::WorstOf => prices.fold(f64::INFINITY, f64::min),
+            };
+            match option_type {
+                OptionType::Call => (value - self.strike).max(0.0),
+                OptionType::Put => (self.strike - value).max(0.0),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(payoff: RainbowPayoff, rho: f64, strike: f64) -> RainbowOption {
+        RainbowOption::new(
+            payoff,
+            vec![100.0, 95.0],
+            vec![0.02, 0.0],
+            vec![0.3, 0.2],
+            vec![vec![1.0, rho], vec![rho, 1.0]],
+            strike,
+            1.5,
+            0.04,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_best_plus_worst_is_both_vanillas() {
+        let call1 = BlackScholes::new(100.0, 100.0, 1.5, 0.04, 0.3, 0.02).unwrap();
+        let call2 = BlackScholes::new(95.0, 100.0, 1.5, 0.04, 0.2, 0.0).unwrap();
+        for rho in [-0.6, 0.0, 0.5, 0.95] {
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let best = pair(RainbowPayoff::BestOf, rho, 100.0).stulz(option_type).unwrap();
+                let worst = pair(RainbowPayoff::WorstOf, rho, 100.0).stulz(option_type).unwrap();
+                let vanillas = call1.price(option_type) + call2.price(option_type);
+                assert!((best + worst - vanillas).abs() < 1e-8, "{rho} {option_type:?}");
+            }
+        }
+
+        // At zero strike the best-of call is the second asset plus Margrabe's exchange option
+        let best = pair(RainbowPayoff::BestOf, 0.3, 0.0).stulz(OptionType::Call).unwrap();
+        let (v, t) = ((0.09_f64 + 0.04 - 2.0 * 0.3 * 0.3 * 0.2).sqrt(), 1.5_f64);
+        let (p1, p2) = (100.0 * (-0.02 * t).exp(), 95.0);
+        let d1 = ((p1 / p2).ln() + 0.5 * v * v * t) / (v * t.sqrt());
+        let margrabe = p1 * norm_cdf(d1) - p2 * norm_cdf(d1 - v * t.sqrt());
+        assert!((best - p2 - margrabe).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_stulz_matches_monte_carlo() {
+        let mc = MonteCarlo::new(200_000, 5).unwrap();
+        for payoff in [RainbowPayoff::BestOf, RainbowPayoff::WorstOf] {
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let option = pair(payoff, 0.4, 98.0);
+                let exact = option.stulz(option_type).unwrap();
+                let simulated = option.monte_carlo(option_type, mc).unwrap();
+                assert!((exact - simulated.price).abs() < 4.0 * simulated.std_error, "{exact} vs {simulated:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_perfectly_correlated_equal_vols_pick_one_asset() {
+        let option = RainbowOption::new(
+            RainbowPayoff::WorstOf,
+            vec![100.0, 90.0],
+            vec![0.0, 0.0],
+            vec![0.25, 0.25],
+            vec![vec![1.0, 1.0], vec![1.0, 1.0]],
+            95.0,
+            1.0,
+            0.03,
+        )
+        .unwrap();
+        let second = BlackScholes::new(90.0, 95.0, 1.0, 0.03, 0.25, 0.0).unwrap();
+        assert!((option.stulz(OptionType::Call).unwrap() - second.price(OptionType::Call)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_more_assets_make_the_worst_worse() {
+        let mc = MonteCarlo::new(50_000, 9).unwrap();
+        let rho = |n: usize| (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.5 }).collect()).collect();
+        let worst_of = |n: usize| {
+            let (spots, vols) = (vec![100.0; n], vec![0.25; n]);
+            RainbowOption::new(RainbowPayoff::WorstOf, spots, vec![0.0; n], vols, rho(n), 90.0, 1.0, 0.03).unwrap()
+        };
+        let two = worst_of(2).monte_carlo(OptionType::Call, mc).unwrap();
+        let four = worst_of(4).monte_carlo(OptionType::Call, mc).unwrap();
+        assert!((two.price - worst_of(2).stulz(OptionType::Call).unwrap()).abs() < 4.0 * two.std_error);
+        assert!(four.price < two.price - 4.0 * four.std_error);
+        assert!(worst_of(4).stulz(OptionType::Call).is_err());
+    }
+
+    #[test]
+    fn test_invalid_rainbows() {
+        let build = |spots: Vec<f64>, vols: Vec<f64>, rho: f64| {
+            let n = spots.len();
+            let correlation = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { rho }).collect()).collect();
+            RainbowOption::new(RainbowPayoff::BestOf, spots, vec![0.0; n], vols, correlation, 100.0, 1.0, 0.03)
+        };
+        assert!(build(vec![100.0, 100.0], vec![0.2, 0.3], 0.5).is_ok());
+        assert!(build(vec![100.0], vec![0.2], 0.5).is_err());
+        assert!(build(vec![100.0, 100.0], vec![0.2], 0.5).is_err());
+        assert!(build(vec![100.0, -1.0], vec![0.2, 0.3], 0.5).is_err());
+        assert!(build(vec![100.0, 100.0], vec![0.2, 0.3], 1.2).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! Structured rate notes priced on simulated fixings
+
+use crate::error::BlackScholesError;
+use crate::monte_carlo::{FixingModel, McResult, MonteCarlo};
+use crate::validation;
+
+/// Value of a note with its redemption statistics
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteValuation {
+    /// Present value with its Monte Carlo error
+    pub value: McResult,
+    /// Probability the note redeems before its final payment date
+    pub knock_out_probability: f64,
+    /// Expected redemption time in years
+    pub expected_life: f64,
+}
+
+/// Target redemption note (TARN)
+///
+/// Each period pays τ·max(fixed_rate - gearing·L, floor) on the fixing L
+/// at its start. Once the coupons sum to `target` the note redeems at par
+/// on that payment date, the last coupon cut so the total is exactly the
+/// target; otherwise it redeems at maturity without a make-whole. The
+/// coupon schedule is the pricing model's tenor schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TargetRedemptionNote {
+    pub fixed_rate: f64,
+    /// Multiplier on the fixing; 1 is the classic inverse floater
+    pub gearing: f64,
+    /// Lowest coupon rate
+    pub floor: f64,
+    /// Total coupon, per unit notional, that triggers redemption
+    pub target: f64,
+    pub notional: f64,
+}
+
+impl TargetRedemptionNote {
+    pub fn new(fixed_rate: f64, gearing: f64, floor: f64, target: f64, notional: f64) -> Result<Self, BlackScholesError> {
+        Ok(TargetRedemptionNote {
+            fixed_rate: validation::finite("Fixed rate", fixed_rate)?,
+            gearing: validation::finite("Gearing", gearing)?,
+            floor: validation::finite("Coupon floor", floor)?,
+            target: validation::positive("Target coupon", target)?,
+            notional: validation::positive("Notional", notional)?,
+        })
+    }
+
+    /// Value to the holder under `model`
+    pub fn price<M: FixingModel>(&self, model: &M, mc: &MonteCarlo) -> Result<NoteValuation, BlackScholesError> {
+        let tenors = model.tenors();
+        value_note(model, mc, self.notional, |fixings, cashflows| {
+            let last = fixings.len() - 1;
+            let mut paid = 0.0;
+            for (i, &fixing) in fixings.iter().enumerate() {
+                let tau = tenors[i + 1] - tenors[i];
+                let coupon = tau * (self.fixed_rate - self.gearing * fixing).max(self.floor);
+                if paid + coupon >= self.target || i == last {
+                    cashflows[i] = coupon.min(self.target - paid) + 1.0;
+                    return i;
+                }
+                paid += coupon;
+                cashflows[i] = coupon;
+            }
+            last
+        })
+    }
+}
+
+/// Range accrual note
+///
+/// Coupon periods span `fixings_per_coupon` consecutive fixings of the
+/// pricing model and pay coupon·τ times the fraction of those fixings in
+/// `[lower, upper]`. With a knock-out barrier the note redeems at par on
+/// any coupon date whose last fixing is at or above it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangeAccrual {
+    /// Coupon rate earned while the fixing is in range
+    pub coupon: f64,
+    pub lower: f64,
+    pub upper: f64,
+    /// Fixings observed in each coupon period
+    pub fixings_per_coupon: usize,
+    /// Fixing at or above which the note redeems early
+    pub knock_out: Option<f64>,
+    pub notional: f64,
+}
+
+impl RangeAccrual {
+    pub fn new(
+        coupon: f64,
+        lower: f64,
+        upper: f64,
+        fixings_per_coupon: usize,
+        notional: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let lower = validation::finite("Range lower bound", lower)?;
+        let upper = validation::finite("Range upper bound", upper)?;
+        if upper <= lower {
+            return Err(BlackScholesError::invalid("Range upper bound must exceed the lower bound"));
+        }
+        if fixings_per_coupon == 0 {
+            return Err(BlackScholesError::invalid("Coupon periods need at least one fixing"));
+        }
+        Ok(RangeAccrual {
+            coupon: validation::non_negative("Coupon", coupon)?,
+            lower,
+            upper,
+            fixings_per_coupon,
+            knock_out: None,
+            notional: validation::positive("Notional", notional)?,
+        })
+    }
+
+    pub fn with_knock_out(mut self, barrier: f64) -> Result<Self, BlackScholesError> {
+        self.knock_out = Some(validation::finite("Knock-out barrier", barrier)?);
+        Ok(self)
+    }
+
+    /// Value to the holder under `model`
+    pub fn price<M: FixingModel>(&self, model: &M, mc: &MonteCarlo) -> Result<NoteValuation, BlackScholesError> {
+        let tenors = model.tenors();
+        let m = self.fixings_per_coupon;
+        if !(tenors.len() - 1).is_multiple_of(m) {
+            return Err(BlackScholesError::invalid("Fixings do not divide into whole coupon periods"));
+        }
+        value_note(model, mc, self.notional, |fixings, cashflows| {
+            let last = fixings.len() - 1;
+            for (p, window) in fixings.chunks(m).enumerate() {
+                let pay = (p + 1) * m - 1;
+                let inside = window.iter().filter(|&&l| l >= self.lower && l <= self.upper).count();
+                cashflows[pay] = self.coupon * (tenors[pay + 1] - tenors[p * m]) * inside as f64 / m as f64;
+                if pay == last || self.knock_out.is_some_and(|b| window[m - 1] >= b) {
+                    cashflows[pay] += 1.0;
+                    return pay;
+                }
+            }
+            last
+        })
+    }
+}
+
+/// Price a note whose payoff fills per-unit cashflows and returns the redemption payment index
+fn value_note<M, F>(model: &M, mc: &MonteCarlo, notional: f64, mut note: F) -> Result<NoteValuation, BlackScholesError>
+where
+    M: FixingModel,
+    F: FnMut(&[f64], &mut [f64]) -> usize,
+{
+    let tenors = model.tenors();
+    let last = tenors.len() - 2;
+    let (mut knocked_out, mut life) = (0usize, 0.0);
+    let value = model.price_fixings(mc, |fixings, cashflows| {
+        let redeemed = note(fixings, cashflows);
+        cashflows.iter_mut().for_each(|c| *c *= notional);
+        if redeemed < last {
+            knocked_out += 1;
+        }
+        life += tenors[redeemed + 1];
+    })?;
+    Ok(NoteValuation {
+        value,
+        knock_out_probability: knocked_out as f64 / mc.paths as f64,
+        expected_life: life / mc.paths as f64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::OptionType;
+    use crate::curves::{DiscountCurve, FlatCurve};
+    use crate::hull_white::HullWhite;
+    use crate::libor_market::{LiborMarketModel, Numeraire};
+
+    fn tenors() -> Vec<f64> {
+        (0..=8).map(|i| 1.0 + 0.5 * i as f64).collect()
+    }
+
+    #[test]
+    fn test_tarn_knock_out_and_expected_life() {
+        let curve = FlatCurve::new(0.04).unwrap();
+        let lmm = LiborMarketModel::new(&curve, tenors(), vec![0.25; 8], 0.1, Numeraire::SpotLibor).unwrap();
+        let mc = MonteCarlo::new(20_000, 4).unwrap();
+
+        // Out of reach: a strip of floorlets struck at the fixed rate plus the bond
+        let remote = TargetRedemptionNote::new(0.08, 1.0, 0.0, 10.0, 100.0).unwrap();
+        let valuation = remote.price(&lmm, &mc).unwrap();
+        let strip: f64 = (0..8).map(|i| lmm.caplet(i, 0.08, OptionType::Put).unwrap()).sum::<f64>()
+            + lmm.discount_factor(8);
+        assert!((valuation.value.price - 100.0 * strip).abs() < 4.0 * valuation.value.std_error);
+        assert_eq!(valuation.knock_out_probability, 0.0);
+        assert!((valuation.expected_life - 5.0).abs() < 1e-12);
+
+        // About 2% a period against a 6% target redeems most paths early
+        let tarn = TargetRedemptionNote::new(0.08, 1.0, 0.0, 0.06, 100.0).unwrap();
+        let valuation = tarn.price(&lmm, &mc).unwrap();
+        assert!(valuation.knock_out_probability > 0.5);
+        assert!(valuation.expected_life > 2.0 && valuation.expected_life < 4.0, "{valuation:?}");
+
+        // A floor above the target redeems on the first coupon
+        let immediate = TargetRedemptionNote::new(0.08, 1.0, 0.02, 0.005, 100.0).unwrap();
+        let valuation = immediate.price(&lmm, &mc).unwrap();
+        assert_eq!(valuation.knock_out_probability, 1.0);
+        assert!((valuation.expected_life - 1.5).abs() < 1e-12);
+        assert!((valuation.value.price - 100.5 * curve.df(1.5)).abs() < 4.0 * valuation.value.std_error);
+    }
+
+    #[test]
+    fn test_range_accrual_under_hull_white() {
+        let curve = FlatCurve::new(0.04).unwrap();
+        let model = HullWhite::new(0.05, 0.01).unwrap();
+        let paths = model.fixings(&curve, tenors()).unwrap();
+        let mc = MonteCarlo::new(20_000, 8).unwrap();
+
+        // A range no fixing leaves is a fixed-coupon bond
+        let wide = RangeAccrual::new(0.05, -1.0, 1.0, 2, 100.0).unwrap();
+        let valuation = wide.price(&paths, &mc).unwrap();
+        let bond: f64 = [2.0, 3.0, 4.0, 5.0].iter().map(|&t| 0.05 * curve.df(t)).sum::<f64>() + curve.df(5.0);
+        assert!((valuation.value.price - 100.0 * bond).abs() < 4.0 * valuation.value.std_error);
+        assert_eq!(valuation.knock_out_probability, 0.0);
+
+        let narrow = RangeAccrual::new(0.05, 0.03, 0.05, 2, 100.0).unwrap();
+        assert!(narrow.price(&paths, &mc).unwrap().value.price < valuation.value.price);
+        let callable = narrow.with_knock_out(0.045).unwrap().price(&paths, &mc).unwrap();
+        assert!(callable.knock_out_probability > 0.0 && callable.knock_out_probability < 1.0);
+        assert!(callable.expected_life < 5.0);
+    }
+
+    #[test]
+    fn test_invalid_notes() {
+        let curve = FlatCurve::new(0.04).unwrap();
+        let paths = HullWhite::new(0.05, 0.01).unwrap().fixings(&curve, tenors()).unwrap();
+        let mc = MonteCarlo::new(10, 1).unwrap();
+        let quarterly = RangeAccrual::new(0.05, 0.0, 0.05, 3, 100.0).unwrap();
+        assert!(quarterly.price(&paths, &mc).is_err());
+        assert!(RangeAccrual::new(0.05, 0.05, 0.03, 1, 100.0).is_err());
+        assert!(RangeAccrual::new(0.05, 0.0, 0.05, 0, 100.0).is_err());
+        assert!(TargetRedemptionNote::new(0.08, 1.0, 0.0, 0.0, 100.0).is_err());
+    }
+}
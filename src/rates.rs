@@ -0,0 +1,745 @@
+use crate::black_scholes::OptionType;
+use crate::curves::bootstrap::swap_schedule;
+use crate::curves::{DiscountCurve, InterpolatedCurve};
+use crate::error::BlackScholesError;
+use crate::math::{norm_cdf, norm_pdf, Real, Tape};
+use crate::validation;
+
+/// Price tolerance of the shifted implied volatility solver, relative to the option's upper bound
+const IMPLIED_TOLERANCE: f64 = 1e-13;
+
+/// Iteration cap of the shifted implied volatility solver
+const IMPLIED_MAX_ITERATIONS: usize = 200;
+
+/// Largest lognormal volatility the implied volatility solver searches
+const IMPLIED_MAX_VOL: f64 = 10.0;
+
+/// Volatility quote for a rate option
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RateVolatility {
+    /// Black-76 lognormal volatility as decimal; needs positive forwards and strikes
+    Lognormal(f64),
+    /// Bachelier normal volatility in rate units (0.01 = 100bp a year)
+    Normal(f64),
+    /// Lognormal volatility of forward + shift; handles rates down to -shift
+    ShiftedLognormal { volatility: f64, shift: f64 },
+}
+
+impl RateVolatility {
+    /// Undiscounted option value on a forward rate
+    fn value(&self, forward: f64, strike: f64, expiry: f64, option_type: OptionType) -> Result<f64, BlackScholesError> {
+        match *self {
+            RateVolatility::Lognormal(vol) => black76(forward, strike, expiry, vol, option_type),
+            RateVolatility::Normal(vol) => bachelier(forward, strike, expiry, vol, option_type),
+            RateVolatility::ShiftedLognormal { volatility, shift } => {
+                shifted_black76(forward, strike, shift, expiry, volatility, option_type)
+            }
+        }
+    }
+}
+
+/// Black-76 value of an option on a forward, before discounting
+///
+/// # Arguments
+/// * `forward` - Forward rate or price
+/// * `strike` - Strike rate or price
+/// * `expiry` - Time to expiry in years
+/// * `volatility` - Lognormal volatility as decimal
+/// * `option_type` - Call (caplet, payer) or Put (floorlet, receiver)
+pub fn black76(
+    forward: f64,
+    strike: f64,
+    expiry: f64,
+    volatility: f64,
+    option_type: OptionType,
+) -> Result<f64, BlackScholesError> {
+    let forward = validation::positive("Forward", forward)?;
+    let strike = validation::positive("Strike", strike)?;
+    let s = validation::positive("Volatility", volatility)? * validation::positive("Time to expiry", expiry)?.sqrt();
+    let d1 = ((forward / strike).ln() + 0.5 * s * s) / s;
+    let d2 = d1 - s;
+    Ok(match option_type {
+        OptionType::Call => forward * norm_cdf(d1) - strike * norm_cdf(d2),
+        OptionType::Put => strike * norm_cdf(-d2) - forward * norm_cdf(-d1),
+    })
+}
+
+/// Shifted lognormal (displaced diffusion) value of an option on a forward, before discounting
+///
+/// F + shift is lognormal, so forwards and strikes down to -shift are
+/// allowed: the value is Black-76 on the shifted forward and strike, and a
+/// zero shift is plain Black-76.
+///
+/// # Arguments
+/// * `forward` - Forward rate or price
+/// * `strike` - Strike rate or price
+/// * `shift` - Displacement, e.g. 0.02 for rates down to -2%
+/// * `expiry` - Time to expiry in years
+/// * `volatility` - Lognormal volatility of forward + shift as decimal
+/// * `option_type` - Call (caplet, payer) or Put (floorlet, receiver)
+pub fn shifted_black76(
+    forward: f64,
+    strike: f64,
+    shift: f64,
+    expiry: f64,
+    volatility: f64,
+    option_type: OptionType,
+) -> Result<f64, BlackScholesError> {
+    let (forward, strike) = shifted(forward, strike, shift)?;
+    black76(forward, strike, expiry, volatility, option_type)
+}
+
+/// Shifted lognormal volatility that reproduces an undiscounted option value
+///
+/// Safeguarded Newton iteration: steps that leave the bracket around the
+/// root fall back to bisection, so deep in- and out-of-the-money values
+/// converge too. A zero shift gives the Black-76 implied volatility.
+///
+/// # Arguments
+/// * `forward` - Forward rate or price
+/// * `strike` - Strike rate or price
+/// * `shift` - Displacement the volatility is quoted with
+/// * `expiry` - Time to expiry in years
+/// * `value` - Undiscounted option value, strictly between intrinsic and its upper bound
+/// * `option_type` - Call (caplet, payer) or Put (floorlet, receiver)
+pub fn implied_shifted_volatility(
+    forward: f64,
+    strike: f64,
+    shift: f64,
+    expiry: f64,
+    value: f64,
+    option_type: OptionType,
+) -> Result<f64, BlackScholesError> {
+    let (forward, strike) = shifted(forward, strike, shift)?;
+    let expiry = validation::positive("Time to expiry", expiry)?;
+    let value = validation::finite("Option value", value)?;
+    let (intrinsic, ceiling) = match option_type {
+        OptionType::Call => ((forward - strike).max(0.0), forward),
+        OptionType::Put => ((strike - forward).max(0.0), strike),
+    };
+    if value <= intrinsic || value >= ceiling {
+        return Err(BlackScholesError::invalid("Option value is outside its no-arbitrage bounds"));
+    }
+    if black76(forward, strike, expiry, IMPLIED_MAX_VOL, option_type)? < value {
+        return Err(BlackScholesError::no_convergence("Implied volatility exceeds the search range"));
+    }
+
+    let (mut lo, mut hi) = (0.0, IMPLIED_MAX_VOL);
+    // Brenner-Subrahmanyam at-the-money estimate
+    let mut vol = ((2.0 * std::f64::consts::PI / expiry).sqrt() * value / forward).clamp(1e-4, 0.5 * hi);
+    for _ in 0..IMPLIED_MAX_ITERATIONS {
+        let diff = black76(forward, strike, expiry, vol, option_type)? - value;
+        if diff.abs() < IMPLIED_TOLERANCE * ceiling {
+            return Ok(vol);
+        }
+        if diff > 0.0 {
+            hi = vol;
+        } else {
+            lo = vol;
+        }
+        let s = vol * expiry.sqrt();
+        let d1 = ((forward / strike).ln() + 0.5 * s * s) / s;
+        let vega = forward * norm_pdf(d1) * expiry.sqrt();
+        let newton = vol - diff / vega;
+        vol = if newton > lo && newton < hi { newton } else { 0.5 * (lo + hi) };
+    }
+    Err(BlackScholesError::no_convergence("Shifted implied volatility did not converge"))
+}
+
+/// Forward and strike displaced by `shift`, both of which must then be positive
+fn shifted(forward: f64, strike: f64, shift: f64) -> Result<(f64, f64), BlackScholesError> {
+    let shift = validation::finite("Shift", shift)?;
+    let forward = validation::finite("Forward", forward)? + shift;
+    let strike = validation::finite("Strike", strike)? + shift;
+    if forward <= 0.0 || strike <= 0.0 {
+        return Err(BlackScholesError::invalid("Shifted forward and strike must be positive"));
+    }
+    Ok((forward, strike))
+}
+
+/// Bachelier (normal model) value of an option on a forward, before discounting
+///
+/// Forwards and strikes may be zero or negative.
+///
+/// # Arguments
+/// * `forward` - Forward rate or price
+/// * `strike` - Strike rate or price
+/// * `expiry` - Time to expiry in years
+/// * `volatility` - Normal volatility in the units of the forward
+/// * `option_type` - Call (caplet, payer) or Put (floorlet, receiver)
+pub fn bachelier(
+    forward: f64,
+    strike: f64,
+    expiry: f64,
+    volatility: f64,
+    option_type: OptionType,
+) -> Result<f64, BlackScholesError> {
+    let forward = validation::finite("Forward", forward)?;
+    let strike = validation::finite("Strike", strike)?;
+    let s = validation::positive("Volatility", volatility)? * validation::positive("Time to expiry", expiry)?.sqrt();
+    let w = match option_type {
+        OptionType::Call => 1.0,
+        OptionType::Put => -1.0,
+    };
+    let d = (forward - strike) / s;
+    Ok(w * (forward - strike) * norm_cdf(w * d) + s * norm_pdf(d))
+}
+
+fn validate_period(start: f64, end: f64) -> Result<(), BlackScholesError> {
+    validation::non_negative("Accrual start", start)?;
+    validation::finite("Accrual end", end)?;
+    if end <= start {
+        return Err(BlackScholesError::invalid("Accrual end must be after its start"));
+    }
+    Ok(())
+}
+
+/// Simply compounded forward rate over [start, end]
+pub fn forward_rate(curve: &dyn DiscountCurve, start: f64, end: f64) -> Result<f64, BlackScholesError> {
+    validate_period(start, end)?;
+    Ok((curve.df(start) / curve.df(end) - 1.0) / (end - start))
+}
+
+/// Annuity of a fixed leg from `start` to `maturity`: Σ τ_i·D(t_i)
+///
+/// Payment dates step back from maturity by 1/`frequency`, leaving any short
+/// stub first, as in `RateInstrument::Swap`.
+pub fn annuity(curve: &dyn DiscountCurve, start: f64, maturity: f64, frequency: u32) -> Result<f64, BlackScholesError> {
+    validate_period(start, maturity)?;
+    if frequency == 0 {
+        return Err(BlackScholesError::invalid("Swap needs at least one payment a year"));
+    }
+    Ok(swap_schedule(start, maturity, frequency)
+        .windows(2)
+        .map(|w| (w[1] - w[0]) * curve.df(w[1]))
+        .sum())
+}
+
+/// Par rate of a swap from `start` to `maturity`, (D(start) - D(maturity)) / annuity
+///
+/// The floating leg is projected and discounted on the same curve.
+pub fn par_swap_rate(curve: &dyn DiscountCurve, start: f64, maturity: f64, frequency: u32) -> Result<f64, BlackScholesError> {
+    let annuity = annuity(curve, start, maturity, frequency)?;
+    Ok((curve.df(start) - curve.df(maturity)) / annuity)
+}
+
+/// Option on one simply compounded rate fixing at `start` and paid at `end`
+///
+/// A call is a caplet, a put a floorlet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Caplet {
+    /// Fixing time in years (the option expiry)
+    pub start: f64,
+    /// Payment time in years
+    pub end: f64,
+    pub strike: f64,
+    pub notional: f64,
+}
+
+impl Caplet {
+    pub fn new(start: f64, end: f64, strike: f64, notional: f64) -> Result<Self, BlackScholesError> {
+        validate_period(start, end)?;
+        Ok(Caplet {
+            start: validation::positive("Caplet fixing", start)?,
+            end,
+            strike: validation::finite("Strike", strike)?,
+            notional: validation::positive("Notional", notional)?,
+        })
+    }
+
+    /// Forward rate the caplet fixes on
+    pub fn forward(&self, curve: &dyn DiscountCurve) -> Result<f64, BlackScholesError> {
+        forward_rate(curve, self.start, self.end)
+    }
+
+    /// Present value: notional·τ·D(end)·(Black or Bachelier value)
+    pub fn price(
+        &self,
+        curve: &dyn DiscountCurve,
+        volatility: RateVolatility,
+        option_type: OptionType,
+    ) -> Result<f64, BlackScholesError> {
+        let value = volatility.value(self.forward(curve)?, self.strike, self.start, option_type)?;
+        Ok(self.notional * (self.end - self.start) * curve.df(self.end) * value)
+    }
+}
+
+/// Cap or floor: a strip of caplets at one strike
+///
+/// A call is a cap, a put a floor. Every caplet is priced at the same flat
+/// volatility, as caps are quoted.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CapFloor {
+    pub caplets: Vec<Caplet>,
+}
+
+impl CapFloor {
+    /// Cap or floor from `start` to `maturity` resetting `frequency` times a year
+    ///
+    /// A period fixing today is already known and carries no optionality, so
+    /// a cap starting today omits its first caplet.
+    pub fn new(start: f64, maturity: f64, frequency: u32, strike: f64, notional: f64) -> Result<Self, BlackScholesError> {
+        validate_period(start, maturity)?;
+        if frequency == 0 {
+            return Err(BlackScholesError::invalid("Cap needs at least one reset a year"));
+        }
+        let caplets = swap_schedule(start, maturity, frequency)
+            .windows(2)
+            .filter(|w| w[0] > 0.0)
+            .map(|w| Caplet::new(w[0], w[1], strike, notional))
+            .collect::<Result<Vec<_>, _>>()?;
+        if caplets.is_empty() {
+            return Err(BlackScholesError::invalid("Cap has no caplets after today's fixing"));
+        }
+        Ok(CapFloor { caplets })
+    }
+
+    /// Present value at a flat volatility
+    pub fn price(
+        &self,
+        curve: &dyn DiscountCurve,
+        volatility: RateVolatility,
+        option_type: OptionType,
+    ) -> Result<f64, BlackScholesError> {
+        self.caplets.iter().map(|c| c.price(curve, volatility, option_type)).sum()
+    }
+}
+
+/// European option to enter a swap paying fixed at `strike`
+///
+/// A call is a payer swaption, a put a receiver. The swap starts at the
+/// option expiry and pays `frequency` times a year until `maturity`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Swaption {
+    /// Option expiry and swap start in years
+    pub expiry: f64,
+    /// Swap end in years
+    pub maturity: f64,
+    pub frequency: u32,
+    pub strike: f64,
+    pub notional: f64,
+}
+
+impl Swaption {
+    pub fn new(expiry: f64, maturity: f64, frequency: u32, strike: f64, notional: f64) -> Result<Self, BlackScholesError> {
+        validate_period(expiry, maturity)?;
+        if frequency == 0 {
+            return Err(BlackScholesError::invalid("Swap needs at least one payment a year"));
+        }
+        Ok(Swaption {
+            expiry: validation::positive("Swaption expiry", expiry)?,
+            maturity,
+            frequency,
+            strike: validation::finite("Strike", strike)?,
+            notional: validation::positive("Notional", notional)?,
+        })
+    }
+
+    /// Annuity of the underlying swap
+    pub fn annuity(&self, curve: &dyn DiscountCurve) -> Result<f64, BlackScholesError> {
+        annuity(curve, self.expiry, self.maturity, self.frequency)
+    }
+
+    /// Forward par rate of the underlying swap
+    pub fn forward(&self, curve: &dyn DiscountCurve) -> Result<f64, BlackScholesError> {
+        par_swap_rate(curve, self.expiry, self.maturity, self.frequency)
+    }
+
+    /// Present value: notional·annuity·(Black or Bachelier value on the forward swap rate)
+    pub fn price(
+        &self,
+        curve: &dyn DiscountCurve,
+        volatility: RateVolatility,
+        option_type: OptionType,
+    ) -> Result<f64, BlackScholesError> {
+        let value = volatility.value(self.forward(curve)?, self.strike, self.expiry, option_type)?;
+        Ok(self.notional * self.annuity(curve)? * value)
+    }
+}
+
+/// Which fixed leg a swap holder pays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SwapSide {
+    /// Pay fixed, receive floating
+    Payer,
+    /// Receive fixed, pay floating
+    Receiver,
+}
+
+/// Vanilla fixed-for-floating interest rate swap
+///
+/// Both legs run from `start` to `maturity` on their own frequencies, with
+/// any short stub first. Pricing is dual-curve: floating coupons are
+/// projected on a forward curve and every cash flow is discounted on a
+/// separate discount curve; pass the same curve twice for single-curve
+/// pricing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterestRateSwap {
+    pub start: f64,
+    pub maturity: f64,
+    pub fixed_rate: f64,
+    /// Fixed payments per year
+    pub fixed_frequency: u32,
+    /// Floating resets per year
+    pub float_frequency: u32,
+    pub notional: f64,
+    pub side: SwapSide,
+}
+
+/// Change in swap value for a one basis point rise in each pillar's zero rate
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BucketedDv01 {
+    /// `(pillar time, value change)` for the discount curve
+    pub discount: Vec<(f64, f64)>,
+    /// `(pillar time, value change)` for the forward curve
+    pub forward: Vec<(f64, f64)>,
+}
+
+impl BucketedDv01 {
+    /// Sum over all buckets of both curves
+    pub fn total(&self) -> f64 {
+        self.discount.iter().chain(&self.forward).map(|b| b.1).sum()
+    }
+}
+
+impl InterestRateSwap {
+    pub fn new(
+        start: f64,
+        maturity: f64,
+        fixed_rate: f64,
+        fixed_frequency: u32,
+        float_frequency: u32,
+        notional: f64,
+        side: SwapSide,
+    ) -> Result<Self, BlackScholesError> {
+        validate_period(start, maturity)?;
+        if fixed_frequency == 0 || float_frequency == 0 {
+            return Err(BlackScholesError::invalid("Swap needs at least one payment a year"));
+        }
+        Ok(InterestRateSwap {
+            start,
+            maturity,
+            fixed_rate: validation::finite("Fixed rate", fixed_rate)?,
+            fixed_frequency,
+            float_frequency,
+            notional: validation::positive("Notional", notional)?,
+            side,
+        })
+    }
+
+    /// Present value of the fixed leg (unsigned)
+    pub fn fixed_leg(&self, discount: &dyn DiscountCurve) -> Result<f64, BlackScholesError> {
+        let annuity = annuity(discount, self.start, self.maturity, self.fixed_frequency)?;
+        Ok(self.notional * self.fixed_rate * annuity)
+    }
+
+    /// Present value of the floating leg (unsigned): Σ τ_j·L_j·D(t_j) with L_j off the forward curve
+    pub fn floating_leg(
+        &self,
+        discount: &dyn DiscountCurve,
+        forward: &dyn DiscountCurve,
+    ) -> Result<f64, BlackScholesError> {
+        let mut value = 0.0;
+        for w in swap_schedule(self.start, self.maturity, self.float_frequency).windows(2) {
+            value += (w[1] - w[0]) * forward_rate(forward, w[0], w[1])? * discount.df(w[1]);
+        }
+        Ok(self.notional * value)
+    }
+
+    /// Fixed rate at which the swap is worth zero
+    pub fn par_rate(
+        &self,
+        discount: &dyn DiscountCurve,
+        forward: &dyn DiscountCurve,
+    ) -> Result<f64, BlackScholesError> {
+        let annuity = annuity(discount, self.start, self.maturity, self.fixed_frequency)?;
+        Ok(self.floating_leg(discount, forward)? / (self.notional * annuity))
+    }
+
+    /// Value to the holder: floating less fixed for a payer, the reverse for a receiver
+    pub fn npv(&self, discount: &dyn DiscountCurve, forward: &dyn DiscountCurve) -> Result<f64, BlackScholesError> {
+        let payer = self.floating_leg(discount, forward)? - self.fixed_leg(discount)?;
+        Ok(match self.side {
+            SwapSide::Payer => payer,
+            SwapSide::Receiver => -payer,
+        })
+    }
+
+    /// Change in value for a one basis point parallel rise in both curves' zero rates
+    pub fn dv01(&self, discount: &InterpolatedCurve, forward: &InterpolatedCurve) -> Result<f64, BlackScholesError> {
+        let base = self.npv(discount, forward)?;
+        let bumped = self.npv(&bump_pillars(discount, None), &bump_pillars(forward, None))?;
+        Ok(bumped - base)
+    }
+
+    /// Change in value for a one basis point rise at each pillar of each curve
+    ///
+    /// Each pillar's zero rate is bumped alone with the rest of its curve
+    /// held, so the buckets show where along the curve the swap's risk sits
+    /// and how much comes from discounting versus projection.
+    pub fn bucketed_dv01(
+        &self,
+        discount: &InterpolatedCurve,
+        forward: &InterpolatedCurve,
+    ) -> Result<BucketedDv01, BlackScholesError> {
+        let base = self.npv(discount, forward)?;
+        let discount_buckets = (0..discount.times.len())
+            .map(|i| Ok((discount.times[i], self.npv(&bump_pillars(discount, Some(i)), forward)? - base)))
+            .collect::<Result<Vec<_>, BlackScholesError>>()?;
+        let forward_buckets = (0..forward.times.len())
+            .map(|i| Ok((forward.times[i], self.npv(discount, &bump_pillars(forward, Some(i)))? - base)))
+            .collect::<Result<Vec<_>, BlackScholesError>>()?;
+        Ok(BucketedDv01 {
+            discount: discount_buckets,
+            forward: forward_buckets,
+        })
+    }
+
+    /// Value with the curves given as discount factor functions over any [`Real`]
+    fn npv_with<T: Real>(&self, discount: &dyn Fn(f64) -> T, forward: &dyn Fn(f64) -> T) -> T {
+        let zero = T::constant(0.0);
+        let fixed = swap_schedule(self.start, self.maturity, self.fixed_frequency)
+            .windows(2)
+            .fold(zero, |acc, w| acc + discount(w[1]) * (w[1] - w[0]));
+        // τ·L·D(t1) with L = (P(t0)/P(t1) - 1)/τ on the forward curve
+        let floating = swap_schedule(self.start, self.maturity, self.float_frequency)
+            .windows(2)
+            .fold(zero, |acc, w| acc + (forward(w[0]) / forward(w[1]) - 1.0) * discount(w[1]));
+        let payer = (floating - fixed * self.fixed_rate) * self.notional;
+        match self.side {
+            SwapSide::Payer => payer,
+            SwapSide::Receiver => -payer,
+        }
+    }
+}
+
+/// Bucketed DV01 of a book of swaps from a single adjoint sweep
+///
+/// Every swap is valued on the same recorded curves and the total is
+/// differentiated once, so the cost does not grow with the number of
+/// pillars. Buckets are first order, so they differ from the one basis
+/// point bumps of [`InterestRateSwap::bucketed_dv01`] by the convexity of a
+/// basis point move.
+pub fn adjoint_bucketed_dv01(
+    swaps: &[InterestRateSwap],
+    discount: &InterpolatedCurve,
+    forward: &InterpolatedCurve,
+) -> BucketedDv01 {
+    let tape = Tape::new();
+    let (discount, forward) = (discount.on_tape(&tape), forward.on_tape(&tape));
+    let total = swaps.iter().fold(Real::constant(0.0), |acc, swap| {
+        acc + swap.npv_with(&|t| discount.df(t), &|t| forward.df(t))
+    });
+    let gradient = tape.gradient(total);
+    BucketedDv01 {
+        discount: discount.zero_rate_buckets(&gradient),
+        forward: forward.zero_rate_buckets(&gradient),
+    }
+}
+
+/// Curve with one pillar's zero rate (or every pillar's, for `None`) raised by a basis point
+fn bump_pillars(curve: &InterpolatedCurve, pillar: Option<usize>) -> InterpolatedCurve {
+    let dfs = curve
+        .times
+        .iter()
+        .zip(&curve.dfs)
+        .enumerate()
+        .map(|(i, (t, df))| if pillar.is_none_or(|p| p == i) { df * (-1e-4 * t).exp() } else { *df })
+        .collect();
+    InterpolatedCurve { dfs, ..curve.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::{FlatCurve, InterpolatedCurve, Interpolation, RateInstrument};
+
+    fn curve() -> InterpolatedCurve {
+        InterpolatedCurve::from_zero_rates(&[(1.0, 0.03), (2.0, 0.035), (5.0, 0.04), (10.0, 0.042)], Interpolation::LogLinear)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_black_and_bachelier_formulas() {
+        // Black-76 with F = 7%, K = 8%, σ = 20%, T = 1
+        let black = black76(0.07, 0.08, 1.0, 0.2, OptionType::Call).unwrap();
+        assert!((black - 0.002253).abs() < 2e-6);
+        // ATM Bachelier is σ·√(T/2π), and matches Black at σ_N ≈ F·σ_B for small vols
+        let normal = bachelier(0.03, 0.03, 2.0, 0.006, OptionType::Put).unwrap();
+        assert!((normal - 0.006 * (2.0 / (2.0 * std::f64::consts::PI)).sqrt()).abs() < 1e-15);
+        let lognormal = black76(0.03, 0.03, 2.0, 0.2, OptionType::Put).unwrap();
+        assert!((normal - lognormal).abs() / lognormal < 0.01);
+        // Negative rates need the normal model or a shift
+        assert!(bachelier(-0.005, -0.002, 1.0, 0.005, OptionType::Call).unwrap() > 0.0);
+        assert!(black76(-0.005, 0.01, 1.0, 0.2, OptionType::Call).is_err());
+    }
+
+    #[test]
+    fn test_shifted_lognormal() {
+        let plain = black76(0.07, 0.08, 1.0, 0.2, OptionType::Call).unwrap();
+        assert_eq!(shifted_black76(0.07, 0.08, 0.0, 1.0, 0.2, OptionType::Call).unwrap(), plain);
+        // Negative forwards and strikes above -shift, with put-call parity on the forward
+        let call = shifted_black76(-0.004, -0.002, 0.02, 2.0, 0.3, OptionType::Call).unwrap();
+        let put = shifted_black76(-0.004, -0.002, 0.02, 2.0, 0.3, OptionType::Put).unwrap();
+        assert!((call - put - (-0.004 + 0.002)).abs() < 1e-15);
+        assert!(shifted_black76(-0.004, -0.002, 0.003, 2.0, 0.3, OptionType::Call).is_err());
+        // A large shift tends to the normal model with σ_N = σ·(F + shift)
+        let normal = bachelier(-0.004, -0.002, 2.0, 0.006, OptionType::Call).unwrap();
+        let displaced = shifted_black76(-0.004, -0.002, 10.0, 2.0, 0.006 / 9.996, OptionType::Call).unwrap();
+        assert!((displaced - normal).abs() < 1e-3 * normal, "{displaced} vs {normal}");
+
+        for (forward, strike, option_type) in
+            [(-0.004, -0.002, OptionType::Call), (-0.004, 0.01, OptionType::Put), (0.01, -0.01, OptionType::Call)]
+        {
+            let value = shifted_black76(forward, strike, 0.02, 2.0, 0.35, option_type).unwrap();
+            let vol = implied_shifted_volatility(forward, strike, 0.02, 2.0, value, option_type).unwrap();
+            assert!((vol - 0.35).abs() < 1e-9, "{vol}");
+        }
+        assert!(implied_shifted_volatility(-0.004, -0.002, 0.02, 2.0, 0.0, OptionType::Call).is_err());
+        assert!(implied_shifted_volatility(-0.004, -0.002, 0.02, 2.0, 0.017, OptionType::Call).is_err());
+
+        // Caplet on a negative forward: a 2% shifted caplet is worth the shifted Black value
+        let curve = FlatCurve::new(-0.005).unwrap();
+        let caplet = Caplet::new(1.0, 1.5, -0.004, 1e6).unwrap();
+        let volatility = RateVolatility::ShiftedLognormal { volatility: 0.3, shift: 0.02 };
+        let forward = caplet.forward(&curve).unwrap();
+        let shifted = shifted_black76(forward, -0.004, 0.02, 1.0, 0.3, OptionType::Call).unwrap();
+        let expected = 1e6 * 0.5 * curve.df(1.5) * shifted;
+        assert!((caplet.price(&curve, volatility, OptionType::Call).unwrap() - expected).abs() < 1e-9);
+        assert!(caplet.price(&curve, RateVolatility::Lognormal(0.3), OptionType::Call).is_err());
+    }
+
+    #[test]
+    fn test_annuity_and_par_rate_match_bootstrap() {
+        let curve = curve();
+        let spot_swap = RateInstrument::Swap {
+            maturity: 5.0,
+            rate: 0.0,
+            frequency: 2,
+        };
+        assert!((par_swap_rate(&curve, 0.0, 5.0, 2).unwrap() - spot_swap.par_rate(&curve)).abs() < 1e-15);
+        let flat = FlatCurve::new(0.04).unwrap();
+        let expected: f64 = (1..=4).map(|i| (-0.04 * (1.0 + i as f64)).exp()).sum();
+        assert!((annuity(&flat, 1.0, 5.0, 1).unwrap() - expected).abs() < 1e-14);
+        // A one-period swap rate is the simple forward rate
+        assert!((par_swap_rate(&curve, 2.0, 2.5, 2).unwrap() - forward_rate(&curve, 2.0, 2.5).unwrap()).abs() < 1e-15);
+        assert!(annuity(&curve, 1.0, 5.0, 0).is_err());
+    }
+
+    #[test]
+    fn test_cap_floor_parity() {
+        let curve = curve();
+        let strike = 0.038;
+        let cap = CapFloor::new(0.0, 5.0, 4, strike, 1e6).unwrap();
+        assert_eq!(cap.caplets.len(), 19);
+        for volatility in [RateVolatility::Lognormal(0.25), RateVolatility::Normal(0.009)] {
+            let value = cap.price(&curve, volatility, OptionType::Call).unwrap()
+                - cap.price(&curve, volatility, OptionType::Put).unwrap();
+            // Cap less floor is a swap paying the strike over the caplet periods
+            let swap: f64 = cap
+                .caplets
+                .iter()
+                .map(|c| c.notional * (c.end - c.start) * curve.df(c.end) * (c.forward(&curve).unwrap() - strike))
+                .sum();
+            assert!((value - swap).abs() < 1e-8, "{volatility:?}");
+        }
+    }
+
+    #[test]
+    fn test_swaption_parity_and_atm() {
+        let curve = curve();
+        let atm = par_swap_rate(&curve, 2.0, 7.0, 1).unwrap();
+        for volatility in [RateVolatility::Lognormal(0.3), RateVolatility::Normal(0.012)] {
+            let payer = Swaption::new(2.0, 7.0, 1, atm, 1e6).unwrap();
+            let (p, r) = (
+                payer.price(&curve, volatility, OptionType::Call).unwrap(),
+                payer.price(&curve, volatility, OptionType::Put).unwrap(),
+            );
+            assert!(p > 0.0 && (p - r).abs() < 1e-8);
+
+            let otm = Swaption { strike: atm + 0.01, ..payer };
+            let parity = otm.price(&curve, volatility, OptionType::Call).unwrap()
+                - otm.price(&curve, volatility, OptionType::Put).unwrap();
+            assert!((parity - 1e6 * otm.annuity(&curve).unwrap() * (atm - otm.strike)).abs() < 1e-8);
+        }
+        assert!(Swaption::new(0.0, 5.0, 1, 0.03, 1e6).is_err());
+        assert!(Swaption::new(5.0, 5.0, 1, 0.03, 1e6).is_err());
+    }
+
+    #[test]
+    fn test_swap_single_and_dual_curve() {
+        let curve = curve();
+        let swap = InterestRateSwap::new(1.0, 6.0, 0.04, 1, 1, 1e6, SwapSide::Payer).unwrap();
+        // Single curve: the floating leg telescopes to D(start) - D(end)
+        let float = swap.floating_leg(&curve, &curve).unwrap();
+        assert!((float - 1e6 * (curve.df(1.0) - curve.df(6.0))).abs() < 1e-8);
+        let par = swap.par_rate(&curve, &curve).unwrap();
+        assert!((par - par_swap_rate(&curve, 1.0, 6.0, 1).unwrap()).abs() < 1e-15);
+
+        // Projection 20bp above discounting raises the par rate by about as much
+        let pillars = [(1.0, 0.032), (2.0, 0.037), (5.0, 0.042), (10.0, 0.044)];
+        let projection = InterpolatedCurve::from_zero_rates(&pillars, Interpolation::LogLinear).unwrap();
+        let dual = swap.par_rate(&curve, &projection).unwrap();
+        assert!((dual - par - 0.002).abs() < 3e-4, "{dual} vs {par}");
+        let at_par = InterestRateSwap { fixed_rate: dual, ..swap };
+        assert!(at_par.npv(&curve, &projection).unwrap().abs() < 1e-8);
+        let receiver = InterestRateSwap { side: SwapSide::Receiver, ..swap };
+        assert_eq!(receiver.npv(&curve, &projection).unwrap(), -swap.npv(&curve, &projection).unwrap());
+        assert!(InterestRateSwap::new(0.0, 5.0, 0.04, 0, 4, 1e6, SwapSide::Payer).is_err());
+    }
+
+    #[test]
+    fn test_adjoint_bucketed_dv01_for_a_book() {
+        let discount = curve();
+        let forward = InterpolatedCurve::from_zero_rates(
+            &[(1.0, 0.032), (2.0, 0.037), (5.0, 0.043), (10.0, 0.045)],
+            Interpolation::MonotoneCubic,
+        )
+        .unwrap();
+        let book = [
+            InterestRateSwap::new(0.0, 5.0, 0.04, 1, 4, 1e7, SwapSide::Payer).unwrap(),
+            InterestRateSwap::new(1.0, 7.0, 0.042, 2, 4, 5e6, SwapSide::Receiver).unwrap(),
+            InterestRateSwap::new(0.0, 10.0, 0.045, 1, 2, 2e6, SwapSide::Payer).unwrap(),
+        ];
+        for swap in &book {
+            let npv = swap.npv_with(&|t| discount.df(t), &|t| forward.df(t));
+            assert!((npv - swap.npv(&discount, &forward).unwrap()).abs() < 1e-6);
+        }
+        let adjoint = adjoint_bucketed_dv01(&book, &discount, &forward);
+        let mut bumped = vec![0.0; discount.times.len() + forward.times.len()];
+        for swap in &book {
+            let buckets = swap.bucketed_dv01(&discount, &forward).unwrap();
+            for (total, bucket) in bumped.iter_mut().zip(buckets.discount.iter().chain(&buckets.forward)) {
+                *total += bucket.1;
+            }
+        }
+        for (a, b) in adjoint.discount.iter().chain(&adjoint.forward).zip(&bumped) {
+            assert!((a.1 - b).abs() < 1e-3 * b.abs().max(1.0), "{a:?} vs {b}");
+        }
+    }
+
+    #[test]
+    fn test_bucketed_dv01() {
+        let curve = curve();
+        let par = par_swap_rate(&curve, 0.0, 5.0, 2).unwrap();
+        let swap = InterestRateSwap::new(0.0, 5.0, par, 2, 4, 1e6, SwapSide::Payer).unwrap();
+        let buckets = swap.bucketed_dv01(&curve, &curve).unwrap();
+        assert_eq!(buckets.discount.len(), 4);
+        // A payer gains when rates rise; the 5y projection pillar dominates
+        let parallel = swap.dv01(&curve, &curve).unwrap();
+        assert!(parallel > 400.0 && parallel < 500.0, "{parallel}");
+        assert!((buckets.total() - parallel).abs() < 0.01 * parallel);
+        let largest = buckets.forward.iter().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+        assert_eq!(largest.0, 5.0);
+        // Nothing depends on the 10y pillar; at par, discounting risk is small
+        assert!(buckets.forward[3].1.abs() < 1e-9 && buckets.discount[3].1.abs() < 1e-9);
+        assert!(buckets.discount.iter().map(|b| b.1.abs()).sum::<f64>() < 0.05 * parallel);
+    }
+}
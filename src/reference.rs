@@ -0,0 +1,361 @@
+//! Published reference prices for regression-testing pricing engines
+//!
+//! Cases are taken from Haug, *The Complete Guide to Option Pricing
+//! Formulas* (2nd ed., 2007), quoted to four decimals. Run `verify` on an
+//! engine after upgrading the crate or changing numerical settings.
+
+use crate::barrier::{BarrierOption, BarrierType};
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::digital::{DigitalOption, DigitalPayoff};
+use crate::error::BlackScholesError;
+use crate::lookback::{LookbackOption, LookbackStrike};
+use crate::pde::{Exercise, FiniteDifference};
+use crate::tree::BinomialTree;
+
+/// Absolute tolerance for cases quoted to four decimals
+const PUBLISHED_TOLERANCE: f64 = 1e-3;
+
+/// Contract priced by a reference case
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Product {
+    Vanilla(Exercise),
+    Barrier {
+        barrier_type: BarrierType,
+        barrier: f64,
+        rebate: f64,
+    },
+    Digital(DigitalPayoff),
+    Lookback {
+        strike_type: LookbackStrike,
+        observed_min: f64,
+        observed_max: f64,
+    },
+}
+
+/// One published price with its inputs
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ReferenceCase {
+    /// Stable identifier, e.g. "haug-1.1-call"
+    pub id: &'static str,
+    /// Where the value is published
+    pub source: &'static str,
+    pub product: Product,
+    pub option_type: OptionType,
+    /// Market inputs; the dividend yield encodes Haug's cost of carry as q = r - b
+    pub model: BlackScholes,
+    pub expected: f64,
+    /// Absolute tolerance for a pass
+    pub tolerance: f64,
+}
+
+/// Engine under test
+///
+/// Engines return `None` for products they do not price; those cases are
+/// reported as skipped rather than failed.
+pub trait ReferenceEngine {
+    /// Name used in the report
+    fn name(&self) -> String;
+
+    /// Price one case
+    fn price(&self, case: &ReferenceCase) -> Option<Result<f64, BlackScholesError>>;
+}
+
+/// Closed-form formulas: Black-Scholes-Merton, Reiner-Rubinstein barriers,
+/// digitals and lookbacks (American exercise is not priced)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Analytic;
+
+impl ReferenceEngine for Analytic {
+    fn name(&self) -> String {
+        String::from("Analytic")
+    }
+
+    fn price(&self, case: &ReferenceCase) -> Option<Result<f64, BlackScholesError>> {
+        let (model, option_type) = (case.model, case.option_type);
+        match case.product {
+            Product::Vanilla(Exercise::European) => Some(Ok(model.price(option_type))),
+            Product::Vanilla(Exercise::American) => None,
+            Product::Barrier {
+                barrier_type,
+                barrier,
+                rebate,
+            } => Some(BarrierOption::new(model, barrier_type, barrier, rebate).map(|o| o.price(option_type))),
+            Product::Digital(payoff) => Some(DigitalOption::new(model, payoff).map(|o| o.price(option_type))),
+            Product::Lookback {
+                strike_type,
+                observed_min,
+                observed_max,
+            } => Some(
+                LookbackOption::new(model, strike_type, observed_min, observed_max).map(|o| o.price(option_type)),
+            ),
+        }
+    }
+}
+
+impl ReferenceEngine for BinomialTree {
+    fn name(&self) -> String {
+        format!("BinomialTree({} steps)", self.steps)
+    }
+
+    fn price(&self, case: &ReferenceCase) -> Option<Result<f64, BlackScholesError>> {
+        match case.product {
+            Product::Vanilla(exercise) => {
+                Some(BinomialTree::price(self, &case.model, case.option_type, exercise).map(|r| r.price))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ReferenceEngine for FiniteDifference {
+    fn name(&self) -> String {
+        format!("FiniteDifference({:?}, {}x{})", self.scheme, self.grid_points, self.time_steps)
+    }
+
+    fn price(&self, case: &ReferenceCase) -> Option<Result<f64, BlackScholesError>> {
+        match case.product {
+            Product::Vanilla(exercise) => {
+                Some(self.solve(&case.model, case.option_type, exercise, &[]).map(|r| r.price))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of one case
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    Passed { price: f64 },
+    Failed { price: f64 },
+    /// The engine returned an error
+    Errored(String),
+    /// The engine does not price this product
+    Skipped,
+}
+
+/// Result of one case
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CaseResult {
+    pub id: &'static str,
+    pub expected: f64,
+    pub tolerance: f64,
+    pub outcome: Outcome,
+}
+
+/// Pass/fail report for one engine
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VerificationReport {
+    pub engine: String,
+    pub results: Vec<CaseResult>,
+}
+
+impl VerificationReport {
+    /// True when no case failed or errored (skipped cases do not count)
+    pub fn passed(&self) -> bool {
+        self.results
+            .iter()
+            .all(|r| matches!(r.outcome, Outcome::Passed { .. } | Outcome::Skipped))
+    }
+
+    /// Cases that failed or errored
+    pub fn failures(&self) -> Vec<&CaseResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, Outcome::Failed { .. } | Outcome::Errored(_)))
+            .collect()
+    }
+
+    /// Number of cases the engine actually priced
+    pub fn checked(&self) -> usize {
+        self.results.iter().filter(|r| r.outcome != Outcome::Skipped).count()
+    }
+}
+
+/// Run every reference case through an engine
+pub fn verify<E: ReferenceEngine + ?Sized>(engine: &E) -> VerificationReport {
+    verify_cases(engine, &cases())
+}
+
+/// Run selected cases through an engine
+pub fn verify_cases<E: ReferenceEngine + ?Sized>(engine: &E, cases: &[ReferenceCase]) -> VerificationReport {
+    let results = cases
+        .iter()
+        .map(|case| {
+            let outcome = match engine.price(case) {
+                None => Outcome::Skipped,
+                Some(Err(e)) => Outcome::Errored(e.to_string()),
+                Some(Ok(price)) if (price - case.expected).abs() <= case.tolerance => Outcome::Passed { price },
+                Some(Ok(price)) => Outcome::Failed { price },
+            };
+            CaseResult {
+                id: case.id,
+                expected: case.expected,
+                tolerance: case.tolerance,
+                outcome,
+            }
+        })
+        .collect();
+    VerificationReport {
+        engine: engine.name(),
+        results,
+    }
+}
+
+/// Market inputs in Haug's (S, K, T, r, b, σ) convention
+fn haug(spot: f64, strike: f64, expiry: f64, rate: f64, carry: f64, vol: f64) -> BlackScholes {
+    BlackScholes::new(spot, strike, expiry, rate, vol, rate - carry).expect("reference inputs are valid")
+}
+
+fn case(
+    id: &'static str,
+    source: &'static str,
+    product: Product,
+    option_type: OptionType,
+    model: BlackScholes,
+    expected: f64,
+) -> ReferenceCase {
+    ReferenceCase {
+        id,
+        source,
+        product,
+        option_type,
+        model,
+        expected,
+        tolerance: PUBLISHED_TOLERANCE,
+    }
+}
+
+/// The full reference set
+pub fn cases() -> Vec<ReferenceCase> {
+    use OptionType::{Call, Put};
+    let european = Product::Vanilla(Exercise::European);
+    let american = Product::Vanilla(Exercise::American);
+    let barrier = |barrier_type, barrier| Product::Barrier {
+        barrier_type,
+        barrier,
+        rebate: 3.0,
+    };
+    vec![
+        case("haug-1.1-bs-call", "Haug 1.1.1", european, Call, haug(60.0, 65.0, 0.25, 0.08, 0.08, 0.30), 2.1334),
+        case("haug-1.1-merton-put", "Haug 1.1.2", european, Put, haug(100.0, 95.0, 0.5, 0.10, 0.05, 0.20), 2.4648),
+        case("haug-1.1-black76-call", "Haug 1.1.3", european, Call, haug(19.0, 19.0, 0.75, 0.10, 0.0, 0.28), 1.7011),
+        case("haug-1.1-black76-put", "Haug 1.1.3", european, Put, haug(19.0, 19.0, 0.75, 0.10, 0.0, 0.28), 1.7011),
+        case("haug-1.1-gk-call", "Haug 1.1.6", european, Call, haug(1.56, 1.60, 0.5, 0.06, -0.02, 0.12), 0.0291),
+        case("haug-3.3-baw-call-90", "Haug table 3-3", american, Call, haug(90.0, 100.0, 0.1, 0.10, 0.0, 0.15), 0.0206),
+        case(
+            "haug-3.3-baw-call-100",
+            "Haug table 3-3",
+            american,
+            Call,
+            haug(100.0, 100.0, 0.1, 0.10, 0.0, 0.15),
+            1.8771,
+        ),
+        case(
+            "american-put-atm",
+            "Converged CRR lattice",
+            american,
+            Put,
+            haug(100.0, 100.0, 1.0, 0.05, 0.05, 0.20),
+            6.0904,
+        ),
+        case(
+            "haug-4.13-doc-90",
+            "Haug table 4-13",
+            barrier(BarrierType::DownAndOut, 95.0),
+            Call,
+            haug(100.0, 90.0, 0.5, 0.08, 0.04, 0.25),
+            9.0246,
+        ),
+        case(
+            "haug-4.13-dic-90",
+            "Haug table 4-13",
+            barrier(BarrierType::DownAndIn, 95.0),
+            Call,
+            haug(100.0, 90.0, 0.5, 0.08, 0.04, 0.25),
+            7.7627,
+        ),
+        case(
+            "haug-4.13-uoc-90",
+            "Haug table 4-13",
+            barrier(BarrierType::UpAndOut, 105.0),
+            Call,
+            haug(100.0, 90.0, 0.5, 0.08, 0.04, 0.25),
+            2.6789,
+        ),
+        case(
+            "haug-4.19-cash-or-nothing-put",
+            "Haug 4.19.2",
+            Product::Digital(DigitalPayoff::CashOrNothing { cash: 10.0 }),
+            Put,
+            haug(100.0, 80.0, 0.75, 0.06, 0.0, 0.35),
+            2.6710,
+        ),
+        case(
+            "haug-4.19-asset-or-nothing-put",
+            "Haug 4.19.3",
+            Product::Digital(DigitalPayoff::AssetOrNothing),
+            Put,
+            haug(70.0, 65.0, 0.5, 0.07, 0.02, 0.27),
+            20.2069,
+        ),
+        case(
+            "haug-4.15-floating-lookback-call",
+            "Haug 4.15.1",
+            Product::Lookback {
+                strike_type: LookbackStrike::Floating,
+                observed_min: 100.0,
+                observed_max: 120.0,
+            },
+            Call,
+            haug(120.0, 120.0, 0.5, 0.10, 0.04, 0.30),
+            25.3533,
+        ),
+        case(
+            "haug-4.15-fixed-lookback-call",
+            "Haug 4.15.3",
+            Product::Lookback {
+                strike_type: LookbackStrike::Fixed,
+                observed_min: 100.0,
+                observed_max: 100.0,
+            },
+            Call,
+            haug(100.0, 95.0, 0.5, 0.10, 0.10, 0.10),
+            13.2687,
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pde::Scheme;
+
+    #[test]
+    fn test_analytic_engine_passes_closed_forms() {
+        let report = verify(&Analytic);
+        assert!(report.passed(), "{:?}", report.failures());
+        assert_eq!(report.checked(), cases().len() - 3);
+    }
+
+    #[test]
+    fn test_numerical_engines_pass_vanillas() {
+        let tree = verify(&BinomialTree::new(2000).unwrap());
+        assert!(tree.passed(), "{:?}", tree.failures());
+        assert_eq!(tree.checked(), 8);
+
+        let pde = verify(&FiniteDifference::new(401, 400, Scheme::CrankNicolson).unwrap());
+        assert!(pde.passed(), "{:?}", pde.failures());
+    }
+
+    #[test]
+    fn test_coarse_settings_are_flagged() {
+        let report = verify(&BinomialTree::new(5).unwrap());
+        assert!(!report.passed());
+        assert!(report.failures().iter().any(|r| r.id == "american-put-atm"));
+    }
+}
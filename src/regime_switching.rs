@@ -0,0 +1,338 @@
+use crate::black_scholes::{BlackScholes, Greeks, OptionType};
+use crate::characteristic::CharacteristicFunction;
+use crate::error::BlackScholesError;
+use crate::math::{gauss_legendre, Complex, Rng};
+use crate::model::EuropeanModel;
+use crate::monte_carlo::PathModel;
+use crate::validation;
+
+/// Gauss-Legendre nodes over the time spent in the calm regime
+const OCCUPATION_NODES: usize = 64;
+
+/// Relative size of the last Bessel series term kept
+const SERIES_TOLERANCE: f64 = 1e-17;
+
+/// Hard cap on the number of Bessel series terms
+const MAX_SERIES_TERMS: usize = 500;
+
+/// Volatility regime of a `RegimeSwitching` model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Regime {
+    Calm,
+    Crisis,
+}
+
+/// Black-Scholes with volatility switching between a calm and a crisis regime
+///
+/// The regime is a two-state Markov chain leaving calm at rate λ_c and
+/// crisis at rate λ_x; within a regime the spot follows geometric Brownian
+/// motion at that regime's volatility. Rates and dividends do not switch.
+/// Mixing the two variances fattens both tails without a full stochastic
+/// volatility model.
+///
+/// Prices are semi-analytic (Naik, 1993): conditional on the time τ spent
+/// calm the log-return is Gaussian with variance σ_c²τ + σ_x²(T - τ), so the
+/// price is a Black-Scholes price integrated over the distribution of τ,
+/// whose density has a closed form in modified Bessel functions.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegimeSwitching {
+    /// Market parameters, strike and expiry; `volatility` is the calm-regime volatility
+    pub model: BlackScholes,
+    /// Volatility in the crisis regime, on the calendar clock
+    pub crisis_volatility: f64,
+    /// Expected number of switches from calm to crisis per year of calm (λ_c)
+    pub calm_to_crisis: f64,
+    /// Expected number of switches from crisis to calm per year of crisis (λ_x)
+    pub crisis_to_calm: f64,
+    /// Probability that the market is in crisis today
+    pub crisis_probability: f64,
+}
+
+impl RegimeSwitching {
+    /// Create a model starting in a known regime
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes parameters, with the calm-regime volatility
+    /// * `crisis_volatility` - Volatility in the crisis regime
+    /// * `calm_to_crisis` - Switching rate out of calm per year (λ_c ≥ 0)
+    /// * `crisis_to_calm` - Switching rate out of crisis per year (λ_x ≥ 0)
+    /// * `initial` - Regime today
+    pub fn new(
+        model: BlackScholes,
+        crisis_volatility: f64,
+        calm_to_crisis: f64,
+        crisis_to_calm: f64,
+        initial: Regime,
+    ) -> Result<Self, BlackScholesError> {
+        Ok(RegimeSwitching {
+            model,
+            crisis_volatility: validation::positive("Crisis volatility", crisis_volatility)?,
+            calm_to_crisis: validation::non_negative("Calm-to-crisis rate", calm_to_crisis)?,
+            crisis_to_calm: validation::non_negative("Crisis-to-calm rate", crisis_to_calm)?,
+            crisis_probability: match initial {
+                Regime::Calm => 0.0,
+                Regime::Crisis => 1.0,
+            },
+        })
+    }
+
+    /// The same model when today's regime is uncertain
+    pub fn with_crisis_probability(self, probability: f64) -> Result<Self, BlackScholesError> {
+        Ok(RegimeSwitching {
+            crisis_probability: validation::in_range("Crisis probability", probability, 0.0, 1.0, "must be in [0, 1]")?,
+            ..self
+        })
+    }
+
+    /// Long-run fraction of time spent in crisis, λ_c / (λ_c + λ_x)
+    ///
+    /// Falls back to today's crisis probability when neither regime is left.
+    pub fn stationary_crisis_probability(&self) -> f64 {
+        let total = self.calm_to_crisis + self.crisis_to_calm;
+        if total > 0.0 {
+            self.calm_to_crisis / total
+        } else {
+            self.crisis_probability
+        }
+    }
+
+    /// Calm-regime volatility on the calendar clock
+    fn calm_volatility(&self) -> f64 {
+        self.model.calendar_volatility()
+    }
+
+    /// Distribution of the time spent calm until `t`: `(τ, probability weight)` pairs
+    ///
+    /// Two atoms (never leaving today's regime) plus Gauss-Legendre nodes
+    /// over the continuous part of the density.
+    fn occupation(&self, t: f64) -> Vec<(f64, f64)> {
+        let (a, b) = (self.calm_to_crisis, self.crisis_to_calm);
+        let (p_calm, p_crisis) = (1.0 - self.crisis_probability, self.crisis_probability);
+        let mut points = vec![(t, p_calm * (-a * t).exp()), (0.0, p_crisis * (-b * t).exp())];
+        if a * b == 0.0 && a + b > 0.0 {
+            // One regime is absorbing: exponential holding time in the other
+            let (nodes, weights) = gauss_legendre(OCCUPATION_NODES);
+            for (x, w) in nodes.iter().zip(&weights) {
+                let tau = 0.5 * t * (x + 1.0);
+                let decay = (-a * tau - b * (t - tau)).exp();
+                points.push((tau, 0.5 * t * w * decay * (p_calm * a + p_crisis * b)));
+            }
+        } else if a * b > 0.0 {
+            let (nodes, weights) = gauss_legendre(OCCUPATION_NODES);
+            for (x, w) in nodes.iter().zip(&weights) {
+                let tau = 0.5 * t * (x + 1.0);
+                let (i0, i1_scaled) = bessel_series(a * b * tau * (t - tau));
+                let decay = (-a * tau - b * (t - tau)).exp();
+                let from_calm = a * i0 + a * b * tau * i1_scaled;
+                let from_crisis = b * i0 + a * b * (t - tau) * i1_scaled;
+                points.push((tau, 0.5 * t * w * decay * (p_calm * from_calm + p_crisis * from_crisis)));
+            }
+        }
+        points
+    }
+
+    /// Calculate option price by integrating Black-Scholes over the calm occupation time
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        let m = &self.model;
+        let t = m.time_to_expiry;
+        let (calm, crisis) = (self.calm_volatility().powi(2), self.crisis_volatility.powi(2));
+        self.occupation(t)
+            .iter()
+            .map(|&(tau, weight)| {
+                let conditional = BlackScholes {
+                    volatility: ((calm * tau + crisis * (t - tau)) / t).sqrt(),
+                    vol_time: t,
+                    ..*m
+                };
+                weight * conditional.price(option_type)
+            })
+            .sum()
+    }
+
+    /// Calculate all Greeks by finite differences; vega is to the calm-regime volatility
+    pub fn greeks(&self, option_type: OptionType) -> Greeks {
+        Greeks::from_finite_differences(&self.model, |m| RegimeSwitching { model: *m, ..*self }.price(option_type))
+    }
+}
+
+/// Modified Bessel series in x = (z/2)²: I₀(z) and I₁(z)/(z/2)
+fn bessel_series(x: f64) -> (f64, f64) {
+    let (mut i0, mut i1) = (1.0, 1.0);
+    let mut term = 1.0;
+    for k in 1..MAX_SERIES_TERMS {
+        let kf = k as f64;
+        // term = x^k / (k!)²; the I₁ term divides by (k + 1) once more
+        term *= x / (kf * kf);
+        i0 += term;
+        i1 += term / (kf + 1.0);
+        if term < SERIES_TOLERANCE * i0 {
+            break;
+        }
+    }
+    (i0, i1)
+}
+
+impl CharacteristicFunction for RegimeSwitching {
+    /// Matrix exponential of the Markov generator plus the regime log-return exponents
+    fn char_fn(&self, u: Complex, t: f64) -> Complex {
+        let m = &self.model;
+        let carry = m.risk_free_rate - m.dividend_yield;
+        let exponent = |variance: f64| Complex::I * u * (carry - 0.5 * variance) - 0.5 * variance * u * u;
+        let (a, b) = (self.calm_to_crisis, self.crisis_to_calm);
+        let calm = exponent(self.calm_volatility().powi(2)) - a;
+        let crisis = exponent(self.crisis_volatility.powi(2)) - b;
+        // exp(tM) = e^(ts)·(cosh(tΔ)·I + sinh(tΔ)/Δ·(M - sI)) for M = [[calm, a], [b, crisis]]
+        let s = (calm + crisis) * 0.5;
+        let h = (calm - crisis) * 0.5;
+        let delta = (h * h + a * b).sqrt();
+        let (grow, shrink) = ((delta * t).exp(), (-delta * t).exp());
+        let cosh = (grow + shrink) * 0.5;
+        let sinhc = if delta.abs() < 1e-12 { Complex::real(t) } else { (grow - shrink) * 0.5 / delta };
+        let scale = (s * t).exp();
+        let from_calm = scale * (cosh + sinhc * (h + a));
+        let from_crisis = scale * (cosh + sinhc * (b - h));
+        from_calm * (1.0 - self.crisis_probability) + from_crisis * self.crisis_probability
+    }
+}
+
+impl PathModel for RegimeSwitching {
+    fn initial_spot(&self) -> f64 {
+        self.model.spot_price
+    }
+
+    /// Exact regime switches within each step, then one Gaussian step on the accumulated variance
+    fn simulate_path(&self, times: &[f64], rng: &mut Rng, path: &mut [f64]) {
+        let m = &self.model;
+        let carry = m.risk_free_rate - m.dividend_yield;
+        let variances = [self.calm_volatility().powi(2), self.crisis_volatility.powi(2)];
+        let rates = [self.calm_to_crisis, self.crisis_to_calm];
+        let mut regime = usize::from(rng.uniform() < self.crisis_probability);
+        let holding = |rng: &mut Rng, rate: f64| {
+            if rate > 0.0 {
+                -(1.0 - rng.uniform()).ln() / rate
+            } else {
+                f64::INFINITY
+            }
+        };
+        let mut next_switch = holding(rng, rates[regime]);
+        let mut log_spot = m.spot_price.ln();
+        let mut previous = 0.0;
+        for (&t, s) in times.iter().zip(path.iter_mut()) {
+            let mut variance = 0.0;
+            let mut now = previous;
+            while next_switch < t {
+                variance += variances[regime] * (next_switch - now);
+                now = next_switch;
+                regime = 1 - regime;
+                next_switch = now + holding(rng, rates[regime]);
+            }
+            variance += variances[regime] * (t - now);
+            log_spot += carry * (t - previous) - 0.5 * variance + variance.sqrt() * rng.normal();
+            *s = log_spot.exp();
+            previous = t;
+        }
+    }
+}
+
+impl EuropeanModel for RegimeSwitching {
+    fn spot(&self) -> f64 {
+        self.model.spot_price
+    }
+
+    fn rate(&self, _expiry: f64) -> f64 {
+        self.model.risk_free_rate
+    }
+
+    fn dividend_yield(&self, _expiry: f64) -> f64 {
+        self.model.dividend_yield
+    }
+
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        let model = BlackScholes {
+            strike_price: strike,
+            time_to_expiry: expiry,
+            vol_time: expiry,
+            volatility: self.calm_volatility(),
+            ..self.model
+        };
+        RegimeSwitching { model, ..*self }.price(option_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moments::risk_neutral_moments;
+    use crate::monte_carlo::MonteCarlo;
+
+    fn calm_crisis() -> RegimeSwitching {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.15, 0.01).unwrap();
+        RegimeSwitching::new(bs, 0.45, 0.5, 3.0, Regime::Calm).unwrap()
+    }
+
+    #[test]
+    fn test_occupation_is_a_distribution() {
+        for model in [
+            calm_crisis(),
+            calm_crisis().with_crisis_probability(0.3).unwrap(),
+            RegimeSwitching { crisis_to_calm: 0.0, ..calm_crisis() },
+        ] {
+            let points = model.occupation(2.0);
+            let total: f64 = points.iter().map(|p| p.1).sum();
+            assert!((total - 1.0).abs() < 1e-12, "{total}");
+            assert!(points.iter().all(|&(tau, w)| (0.0..=2.0).contains(&tau) && w >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_degenerate_regimes_are_black_scholes() {
+        let bs = BlackScholes::new(100.0, 110.0, 0.75, 0.04, 0.2, 0.0).unwrap();
+        // Same volatility in both regimes
+        let same = RegimeSwitching::new(bs, 0.2, 1.0, 2.0, Regime::Calm).unwrap();
+        assert!((same.price(OptionType::Call) - bs.price(OptionType::Call)).abs() < 1e-10);
+        // No switching: stays in today's regime
+        let stuck = RegimeSwitching::new(bs, 0.5, 0.0, 0.0, Regime::Crisis).unwrap();
+        let crisis = BlackScholes { volatility: 0.5, ..bs };
+        assert!((stuck.price(OptionType::Put) - crisis.price(OptionType::Put)).abs() < 1e-12);
+        assert!(RegimeSwitching::new(bs, 0.5, -1.0, 0.0, Regime::Calm).is_err());
+        assert!(same.with_crisis_probability(1.5).is_err());
+    }
+
+    #[test]
+    fn test_monte_carlo_agrees_with_semi_analytic() {
+        let model = calm_crisis().with_crisis_probability(0.2).unwrap();
+        let engine = MonteCarlo::new(100_000, 5).unwrap();
+        let discount = (-0.03_f64).exp();
+        let mc = engine
+            .price(&model, &[0.25, 0.5, 0.75, 1.0], discount, |path| (90.0 - path[3]).max(0.0))
+            .unwrap();
+        let put = BlackScholes { strike_price: 90.0, ..model.model };
+        let analytic = RegimeSwitching { model: put, ..model }.price(OptionType::Put);
+        assert!((mc.price - analytic).abs() < 3.0 * mc.std_error, "{} vs {}", mc.price, analytic);
+    }
+
+    #[test]
+    fn test_fat_tails_and_characteristic_function() {
+        let model = calm_crisis();
+        // φ(-i) = e^((r - q)t): the discounted spot is a martingale
+        let phi = model.char_fn(-Complex::I, 1.0);
+        assert!((phi.re - 0.02_f64.exp()).abs() < 1e-12 && phi.im.abs() < 1e-12);
+        let moments = risk_neutral_moments(&model, 1.0);
+        assert!(moments.excess_kurtosis() > 0.1);
+
+        // Against Black-Scholes with the same total variance, both wings are richer
+        let matched = BlackScholes {
+            volatility: moments.annualized_volatility(1.0),
+            ..model.model
+        };
+        for (strike, option_type) in [(70.0, OptionType::Put), (140.0, OptionType::Call)] {
+            let regime = RegimeSwitching { model: BlackScholes { strike_price: strike, ..model.model }, ..model };
+            let flat = BlackScholes { strike_price: strike, ..matched };
+            assert!(regime.price(option_type) > flat.price(option_type), "{strike}");
+        }
+        let call = model.price(OptionType::Call) - model.price(OptionType::Put);
+        assert!((call - (100.0 * (-0.01_f64).exp() - 100.0 * (-0.03_f64).exp())).abs() < 1e-10);
+    }
+}
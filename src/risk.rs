@@ -0,0 +1,489 @@
+use crate::error::BlackScholesError;
+use crate::math::{norm_cdf, norm_inv_cdf, norm_pdf, symmetric_eigen, Rng};
+use crate::monte_carlo::MonteCarlo;
+use crate::portfolio::Portfolio;
+use crate::scenario::{Revalue, Shock};
+use crate::validation;
+
+/// Return volatilities and correlations of the underlyings a portfolio trades
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RiskFactors {
+    /// Underlying identifiers, matched against `Position::underlying`
+    pub underlyings: Vec<String>,
+    /// Annualized volatility of each underlying's returns
+    pub volatilities: Vec<f64>,
+    /// Correlation matrix of returns, ordered as `underlyings`
+    pub correlation: Vec<Vec<f64>>,
+}
+
+impl RiskFactors {
+    pub fn new(
+        underlyings: &[&str],
+        volatilities: Vec<f64>,
+        correlation: Vec<Vec<f64>>,
+    ) -> Result<Self, BlackScholesError> {
+        let n = underlyings.len();
+        if n == 0 {
+            return Err(BlackScholesError::invalid("Risk factors need at least one underlying"));
+        }
+        if volatilities.len() != n || correlation.len() != n || correlation.iter().any(|row| row.len() != n) {
+            return Err(BlackScholesError::invalid("Volatilities and correlation must match the underlyings"));
+        }
+        for &vol in &volatilities {
+            validation::non_negative("Volatility", vol)?;
+        }
+        correlation_factor(&correlation)?;
+        Ok(RiskFactors {
+            underlyings: underlyings.iter().map(|u| u.to_string()).collect(),
+            volatilities,
+            correlation,
+        })
+    }
+
+    /// A single underlying
+    pub fn single(underlying: &str, volatility: f64) -> Result<Self, BlackScholesError> {
+        RiskFactors::new(&[underlying], vec![volatility], vec![vec![1.0]])
+    }
+
+    fn index(&self, underlying: &str) -> Result<usize, BlackScholesError> {
+        self.underlyings
+            .iter()
+            .position(|u| u == underlying)
+            .ok_or_else(|| BlackScholesError::InvalidInput(format!("No risk factor for underlying {underlying}")))
+    }
+
+    /// Covariance of returns over `horizon` years
+    fn covariance(&self, horizon: f64) -> Vec<Vec<f64>> {
+        let vols = &self.volatilities;
+        (0..vols.len())
+            .map(|i| (0..vols.len()).map(|j| self.correlation[i][j] * vols[i] * vols[j] * horizon).collect())
+            .collect()
+    }
+}
+
+/// Horizon and confidence level of a risk measure
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VarConfig {
+    /// Holding period in years
+    pub horizon: f64,
+    /// Confidence level, e.g. 0.99
+    pub confidence: f64,
+}
+
+impl VarConfig {
+    pub fn new(horizon: f64, confidence: f64) -> Result<Self, BlackScholesError> {
+        Ok(VarConfig {
+            horizon: validation::positive("Horizon", horizon)?,
+            confidence: validation::in_range("Confidence", confidence, 0.5, 0.9999, "between 0.5 and 0.9999")?,
+        })
+    }
+
+    /// One trading day (1/252 years) at 99%
+    pub fn one_day_99() -> Self {
+        VarConfig {
+            horizon: 1.0 / 252.0,
+            confidence: 0.99,
+        }
+    }
+
+    /// Probability of the loss tail, 1 - confidence
+    fn tail(&self) -> f64 {
+        1.0 - self.confidence
+    }
+}
+
+/// Value at Risk and Expected Shortfall, both reported as positive losses
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RiskMeasure {
+    /// Loss exceeded with probability 1 - confidence
+    pub var: f64,
+    /// Average loss beyond the VaR
+    pub expected_shortfall: f64,
+}
+
+/// Cholesky factor of a correlation matrix after checking its diagonal, symmetry and bounds
+pub(crate) fn correlation_factor(correlation: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, BlackScholesError> {
+    for (i, row) in correlation.iter().enumerate() {
+        if row[i] != 1.0 {
+            return Err(BlackScholesError::invalid("Correlation matrix must have a unit diagonal"));
+        }
+        for (j, &rho) in row.iter().enumerate().take(i) {
+            validation::in_range("Correlation", rho, -1.0, 1.0, "between -1 and 1")?;
+            if rho != correlation[j][i] {
+                return Err(BlackScholesError::invalid("Correlation matrix must be symmetric"));
+            }
+        }
+    }
+    cholesky(correlation)
+}
+
+/// Cholesky factor L of a positive semi-definite matrix, A = L·Lᵀ
+///
+/// Zero pivots (perfectly correlated factors) are allowed and give a zero column.
+pub(crate) fn cholesky(a: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, BlackScholesError> {
+    let n = a.len();
+    let mut l = vec![vec![0.0; n]; n];
+    for j in 0..n {
+        let pivot = a[j][j] - (0..j).map(|k| l[j][k] * l[j][k]).sum::<f64>();
+        if pivot < -1e-12 {
+            return Err(BlackScholesError::invalid("Correlation matrix is not positive semi-definite"));
+        }
+        l[j][j] = pivot.max(0.0).sqrt();
+        for i in j + 1..n {
+            let s = a[i][j] - (0..j).map(|k| l[i][k] * l[j][k]).sum::<f64>();
+            l[i][j] = if l[j][j] > 0.0 { s / l[j][j] } else { 0.0 };
+        }
+    }
+    Ok(l)
+}
+
+/// Frobenius distance between successive projections at which the repair stops
+const NEAREST_CORRELATION_TOLERANCE: f64 = 1e-12;
+
+/// Maximum alternating projections in the repair
+const NEAREST_CORRELATION_ITERATIONS: usize = 1000;
+
+/// Nearest correlation matrix in the Frobenius norm (Higham, 2002)
+///
+/// Alternates projections onto the positive semi-definite cone (negative
+/// eigenvalues clipped to zero) and onto unit-diagonal matrices, with
+/// Dykstra's correction so the limit is the nearest matrix in both sets
+/// rather than merely a point in them. Used to repair stressed or
+/// pairwise-estimated correlations; a valid matrix is returned unchanged.
+pub fn nearest_correlation(matrix: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, BlackScholesError> {
+    let n = matrix.len();
+    if n == 0 || matrix.iter().any(|row| row.len() != n) {
+        return Err(BlackScholesError::invalid("Correlation matrix must be square and non-empty"));
+    }
+    for (i, row) in matrix.iter().enumerate() {
+        validation::all_finite("Correlation", row)?;
+        if (0..i).any(|j| row[j] != matrix[j][i]) {
+            return Err(BlackScholesError::invalid("Correlation matrix must be symmetric"));
+        }
+    }
+
+    let mut y: Vec<Vec<f64>> = matrix.to_vec();
+    let mut x = y.clone();
+    let mut correction = vec![vec![0.0; n]; n];
+    for _ in 0..NEAREST_CORRELATION_ITERATIONS {
+        let r: Vec<Vec<f64>> =
+            y.iter().zip(&correction).map(|(y, c)| y.iter().zip(c).map(|(y, c)| y - c).collect()).collect();
+        x = clip_negative_eigenvalues(&r);
+        correction = x.iter().zip(&r).map(|(x, r)| x.iter().zip(r).map(|(x, r)| x - r).collect()).collect();
+        let mut next = x.clone();
+        for (i, row) in next.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        let change: f64 = next.iter().flatten().zip(y.iter().flatten()).map(|(a, b)| (a - b).powi(2)).sum();
+        y = next;
+        if change.sqrt() <= NEAREST_CORRELATION_TOLERANCE * n as f64 {
+            break;
+        }
+    }
+    // The last semi-definite iterate, rescaled to a unit diagonal, stays
+    // semi-definite where the unit-diagonal iterate may be slightly indefinite
+    let scale: Vec<f64> = (0..n).map(|i| x[i][i].max(f64::MIN_POSITIVE).sqrt()).collect();
+    Ok((0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| {
+                    if i == j {
+                        1.0
+                    } else {
+                        (0.5 * (x[i][j] + x[j][i]) / (scale[i] * scale[j])).clamp(-1.0, 1.0)
+                    }
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Projection of a symmetric matrix onto the positive semi-definite cone
+fn clip_negative_eigenvalues(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = matrix.len();
+    let (values, vectors) = symmetric_eigen(matrix);
+    (0..n)
+        .map(|i| (0..n).map(|j| (0..n).map(|k| vectors[i][k] * values[k].max(0.0) * vectors[j][k]).sum()).collect())
+        .collect()
+}
+
+fn mat_vec(a: &[Vec<f64>], x: &[f64]) -> Vec<f64> {
+    a.iter().map(|row| row.iter().zip(x).map(|(a, x)| a * x).sum()).collect()
+}
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(x, y)| x * y).sum()
+}
+
+fn mat_mul(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = b.len();
+    a.iter()
+        .map(|row| (0..b[0].len()).map(|j| (0..n).map(|k| row[k] * b[k][j]).sum()).collect())
+        .collect()
+}
+
+fn trace(a: &[Vec<f64>]) -> f64 {
+    (0..a.len()).map(|i| a[i][i]).sum()
+}
+
+/// Cash delta (P&L per unit return) and cash gamma (P&L per unit squared
+/// return) of the portfolio on each risk factor
+fn sensitivities(portfolio: &Portfolio, factors: &RiskFactors) -> Result<(Vec<f64>, Vec<f64>), BlackScholesError> {
+    let n = factors.underlyings.len();
+    let (mut delta, mut gamma) = (vec![0.0; n], vec![0.0; n]);
+    for position in &portfolio.positions {
+        let i = factors.index(&position.underlying)?;
+        let spot = position.instrument.spot();
+        let greeks = position.greeks();
+        delta[i] += greeks.delta * spot;
+        gamma[i] += greeks.gamma * spot * spot;
+    }
+    Ok((delta, gamma))
+}
+
+/// Delta-normal VaR and ES
+///
+/// P&L is linear in normally distributed returns, so both measures are
+/// multiples of the P&L standard deviation σ: VaR = z·σ and ES = φ(z)/(1-c)·σ.
+pub fn delta_normal_var(
+    portfolio: &Portfolio,
+    factors: &RiskFactors,
+    config: &VarConfig,
+) -> Result<RiskMeasure, BlackScholesError> {
+    let (delta, _) = sensitivities(portfolio, factors)?;
+    let sigma = dot(&delta, &mat_vec(&factors.covariance(config.horizon), &delta)).sqrt();
+    let z = norm_inv_cdf(config.confidence);
+    Ok(RiskMeasure {
+        var: z * sigma,
+        expected_shortfall: norm_pdf(z) / config.tail() * sigma,
+    })
+}
+
+/// Delta-gamma VaR and ES from a Cornish-Fisher expansion
+///
+/// P&L is approximated by Δᵀr + ½rᵀΓr with the own-gamma of each
+/// underlying on the diagonal of Γ. Its first four cumulants are exact for
+/// normal returns; the Cornish-Fisher expansion turns them into a quantile,
+/// and ES averages that quantile over the tail. The expansion is reliable
+/// for moderate skew and kurtosis; heavily short-gamma books should be
+/// checked against `monte_carlo_var`.
+pub fn delta_gamma_var(
+    portfolio: &Portfolio,
+    factors: &RiskFactors,
+    config: &VarConfig,
+) -> Result<RiskMeasure, BlackScholesError> {
+    let (delta, gamma) = sensitivities(portfolio, factors)?;
+    let cov = factors.covariance(config.horizon);
+    // M = ΓΣ, with Γ diagonal
+    let m: Vec<Vec<f64>> = cov.iter().zip(&gamma).map(|(row, g)| row.iter().map(|c| g * c).collect()).collect();
+    let m2 = mat_mul(&m, &m);
+    let cov_delta = mat_vec(&cov, &delta);
+    let gamma_cov_delta: Vec<f64> = gamma.iter().zip(&cov_delta).map(|(g, c)| g * c).collect();
+
+    let mean = 0.5 * trace(&m);
+    let variance = dot(&delta, &cov_delta) + 0.5 * trace(&m2);
+    let third = 3.0 * dot(&cov_delta, &gamma_cov_delta) + trace(&mat_mul(&m2, &m));
+    let fourth = 12.0 * dot(&gamma_cov_delta, &mat_vec(&cov, &gamma_cov_delta)) + 3.0 * trace(&mat_mul(&m2, &m2));
+    if variance <= 0.0 {
+        return Ok(RiskMeasure {
+            var: -mean,
+            expected_shortfall: -mean,
+        });
+    }
+    let sigma = variance.sqrt();
+    let skew = third / (variance * sigma);
+    let kurtosis = fourth / (variance * variance);
+    // Cornish-Fisher quantile w(z) = Σ c_k z^k of the standardized P&L
+    let c = [
+        -skew / 6.0,
+        1.0 - kurtosis / 8.0 + 5.0 * skew * skew / 36.0,
+        skew / 6.0,
+        kurtosis / 24.0 - skew * skew / 18.0,
+    ];
+    let z = norm_inv_cdf(config.tail());
+    let w = c.iter().rev().fold(0.0, |acc, c| acc * z + c);
+    // ES averages w over the tail: ∫ z^k φ(z) dz up to z, by the recursion
+    // I_k = -z^(k-1)·φ(z) + (k-1)·I_(k-2)
+    let mut moments = [norm_cdf(z), -norm_pdf(z), 0.0, 0.0];
+    for k in 2..4 {
+        moments[k] = -z.powi(k as i32 - 1) * norm_pdf(z) + (k - 1) as f64 * moments[k - 2];
+    }
+    let tail_w = c.iter().zip(&moments).map(|(c, m)| c * m).sum::<f64>() / config.tail();
+    Ok(RiskMeasure {
+        var: -(mean + sigma * w),
+        expected_shortfall: -(mean + sigma * tail_w),
+    })
+}
+
+/// Full-revaluation Monte Carlo VaR and ES
+///
+/// Draws correlated lognormal returns over the horizon, reprices every
+/// position at its shocked spot with the horizon elapsed (so time decay is
+/// included), and reads VaR and ES off the simulated P&L distribution.
+/// Implied volatilities are held fixed.
+pub fn monte_carlo_var(
+    portfolio: &Portfolio,
+    factors: &RiskFactors,
+    config: &VarConfig,
+    engine: &MonteCarlo,
+) -> Result<RiskMeasure, BlackScholesError> {
+    let indices = portfolio
+        .positions
+        .iter()
+        .map(|p| factors.index(&p.underlying))
+        .collect::<Result<Vec<_>, _>>()?;
+    let base: Vec<f64> = portfolio.positions.iter().map(|p| p.revalue(&Shock::default())).collect();
+    let chol = cholesky(&factors.correlation)?;
+    let n = factors.underlyings.len();
+    let horizon = config.horizon;
+
+    let mut rng = Rng::new(engine.seed);
+    let mut normals = vec![0.0; n];
+    let mut pnl = Vec::with_capacity(engine.paths);
+    for _ in 0..engine.paths {
+        normals.iter_mut().for_each(|z| *z = rng.normal());
+        let returns: Vec<f64> = mat_vec(&chol, &normals)
+            .iter()
+            .zip(&factors.volatilities)
+            .map(|(z, vol)| (-0.5 * vol * vol * horizon + vol * horizon.sqrt() * z).exp() - 1.0)
+            .collect();
+        let total: f64 = portfolio
+            .positions
+            .iter()
+            .zip(&indices)
+            .zip(&base)
+            .map(|((position, &i), base)| {
+                let shock = Shock {
+                    spot: returns[i],
+                    vol: 0.0,
+                    time: horizon,
+                };
+                position.revalue(&shock) - base
+            })
+            .sum();
+        pnl.push(total);
+    }
+
+    pnl.sort_by(f64::total_cmp);
+    let tail = ((config.tail() * engine.paths as f64).ceil() as usize).max(1);
+    Ok(RiskMeasure {
+        var: -pnl[tail - 1],
+        expected_shortfall: -pnl[..tail].iter().sum::<f64>() / tail as f64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::{BlackScholes, OptionType};
+    use crate::portfolio::{Instrument, Position};
+
+    fn stock(underlying: &str, spot: f64, quantity: f64) -> Position {
+        Position::new(underlying, Instrument::Underlying { spot }, quantity, 1.0).unwrap()
+    }
+
+    fn delta_hedged_call(quantity: f64) -> Portfolio {
+        let model = BlackScholes::new(100.0, 100.0, 0.25, 0.0, 0.3, 0.0).unwrap();
+        let hedge = -quantity * model.greeks(OptionType::Call).delta;
+        let call = Instrument::Vanilla {
+            model,
+            option_type: OptionType::Call,
+        };
+        Portfolio::new(vec![
+            Position::new("AAA", call, quantity, 1.0).unwrap(),
+            stock("AAA", 100.0, hedge),
+        ])
+    }
+
+    #[test]
+    fn test_delta_normal_single_stock() {
+        let portfolio = Portfolio::new(vec![stock("AAA", 50.0, 200.0)]);
+        let factors = RiskFactors::single("AAA", 0.2).unwrap();
+        let config = VarConfig::new(0.25, 0.99).unwrap();
+        let risk = delta_normal_var(&portfolio, &factors, &config).unwrap();
+        let sigma = 10_000.0 * 0.2 * 0.5;
+        assert!((risk.var - 2.326_347_874 * sigma).abs() < 1e-5);
+        assert!((risk.expected_shortfall - 2.665_214_220 * sigma).abs() < 1e-5);
+        // Without gamma, Cornish-Fisher collapses to the normal quantile
+        let dg = delta_gamma_var(&portfolio, &factors, &config).unwrap();
+        assert!((dg.var - risk.var).abs() < 1e-9);
+        assert!((dg.expected_shortfall - risk.expected_shortfall).abs() < 1e-6 * sigma);
+    }
+
+    #[test]
+    fn test_correlation_drives_diversification() {
+        let portfolio = Portfolio::new(vec![stock("AAA", 100.0, 10.0), stock("BBB", 100.0, 10.0)]);
+        let config = VarConfig::one_day_99();
+        let alone = Portfolio::new(vec![stock("AAA", 100.0, 10.0)]);
+        let single = delta_normal_var(&alone, &RiskFactors::single("AAA", 0.2).unwrap(), &config).unwrap().var;
+        let var = |rho: f64| {
+            let factors = RiskFactors::new(&["AAA", "BBB"], vec![0.2, 0.2], vec![vec![1.0, rho], vec![rho, 1.0]]).unwrap();
+            delta_normal_var(&portfolio, &factors, &config).unwrap().var
+        };
+        assert!((var(0.0) - 2f64.sqrt() * single).abs() < 1e-9);
+        assert!((var(1.0) - 2.0 * single).abs() < 1e-9);
+        assert!(var(-1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_delta_gamma_sees_short_gamma_risk() {
+        let factors = RiskFactors::single("AAA", 0.3).unwrap();
+        let config = VarConfig::new(10.0 / 252.0, 0.99).unwrap();
+        let short = delta_hedged_call(-100.0);
+        assert!(delta_normal_var(&short, &factors, &config).unwrap().var.abs() < 1e-9);
+        let dg = delta_gamma_var(&short, &factors, &config).unwrap();
+        assert!(dg.var > 0.0 && dg.expected_shortfall > dg.var);
+        // Long gamma only gains under the quadratic approximation
+        assert!(delta_gamma_var(&delta_hedged_call(100.0), &factors, &config).unwrap().var < 0.0);
+
+        // Full revaluation agrees on the scale of the loss (it also includes time decay)
+        let engine = MonteCarlo::new(20_000, 7).unwrap();
+        let mc = monte_carlo_var(&short, &factors, &config, &engine).unwrap();
+        assert!((mc.var / dg.var - 1.0).abs() < 0.25, "mc {} dg {}", mc.var, dg.var);
+    }
+
+    #[test]
+    fn test_monte_carlo_matches_delta_normal_for_linear_book() {
+        let portfolio = Portfolio::new(vec![stock("AAA", 100.0, 10.0), stock("BBB", 40.0, -20.0)]);
+        let factors = RiskFactors::new(&["AAA", "BBB"], vec![0.25, 0.4], vec![vec![1.0, 0.6], vec![0.6, 1.0]]).unwrap();
+        let config = VarConfig::new(1.0 / 252.0, 0.95).unwrap();
+        let normal = delta_normal_var(&portfolio, &factors, &config).unwrap();
+        let mc = monte_carlo_var(&portfolio, &factors, &config, &MonteCarlo::new(50_000, 11).unwrap()).unwrap();
+        assert!((mc.var / normal.var - 1.0).abs() < 0.03);
+        assert!((mc.expected_shortfall / normal.expected_shortfall - 1.0).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_nearest_correlation_repairs_indefinite_matrices() {
+        // Higham (2002), section 4: the nearest correlation to [[1,1,0],[1,1,1],[0,1,1]]
+        let broken = vec![vec![1.0, 1.0, 0.0], vec![1.0, 1.0, 1.0], vec![0.0, 1.0, 1.0]];
+        let repaired = nearest_correlation(&broken).unwrap();
+        let expected = [[1.0, 0.7607, 0.1573], [0.7607, 1.0, 0.7607], [0.1573, 0.7607, 1.0]];
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((repaired[i][j] - expected[i][j]).abs() < 1e-4, "{repaired:?}");
+            }
+        }
+        assert!(correlation_factor(&repaired).is_ok());
+
+        let valid = vec![vec![1.0, 0.3, -0.2], vec![0.3, 1.0, 0.5], vec![-0.2, 0.5, 1.0]];
+        let unchanged = nearest_correlation(&valid).unwrap();
+        assert!(unchanged.iter().flatten().zip(valid.iter().flatten()).all(|(a, b)| (a - b).abs() < 1e-12));
+        assert!(nearest_correlation(&[vec![1.0, 0.2], vec![0.3, 1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(RiskFactors::new(&["A", "B"], vec![0.2, 0.2], vec![vec![1.0, 0.5], vec![0.4, 1.0]]).is_err());
+        let not_psd = vec![vec![1.0, 0.9, -0.9], vec![0.9, 1.0, 0.9], vec![-0.9, 0.9, 1.0]];
+        assert!(RiskFactors::new(&["A", "B", "C"], vec![0.2; 3], not_psd).is_err());
+        assert!(VarConfig::new(0.0, 0.99).is_err());
+        assert!(VarConfig::new(1.0, 1.0).is_err());
+        let portfolio = Portfolio::new(vec![stock("ZZZ", 10.0, 1.0)]);
+        let factors = RiskFactors::single("AAA", 0.2).unwrap();
+        assert!(delta_normal_var(&portfolio, &factors, &VarConfig::one_day_99()).is_err());
+    }
+}
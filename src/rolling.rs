@@ -0,0 +1,597 @@
+//! Rolling and constant-maturity option exposures replayed over a market history
+
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::execution::{ExecutionModel, Fill};
+use crate::expiry_calendar::nearest_listed;
+use crate::strategy::MarketObservation;
+use crate::validation;
+
+/// Remaining life below which a held option is settled at intrinsic value
+const SETTLE_EPSILON: f64 = 1e-9;
+
+/// Option template opened at each roll, struck relative to the spot at that time
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollingLeg {
+    pub option_type: OptionType,
+    /// Strike as spot·(1 + moneyness) when the leg is opened
+    pub moneyness: f64,
+    /// Signed number of options, negative when written
+    pub quantity: f64,
+}
+
+/// When held options are replaced by new ones at the target tenor
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RollSchedule {
+    /// Hold each set of options to expiry, then open the next
+    HoldToExpiry,
+    /// Close the options after `interval` years and open new ones
+    Every { interval: f64 },
+    /// Listed expiries fall every `cycle` years from the first observation.
+    /// The two bracketing today plus the tenor are held in the proportions
+    /// whose average maturity is exactly the tenor, rebalanced on every
+    /// observation; each expiry is struck when it enters the blend
+    ConstantMaturity { cycle: f64 },
+}
+
+/// A strategy of options kept at a target maturity by rolling
+///
+/// Options are marked at the observation's volatility, with the touch
+/// `half_spread` above it for purchases and below it for sales. Orders are
+/// filled by `execution`, at the touch in full unless set otherwise; the
+/// difference from mid is the transaction cost. Premiums are financed from
+/// a cash account that accrues at the observed rate, so the series is the
+/// P&L of the exposure.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollingPosition {
+    pub legs: Vec<RollingLeg>,
+    /// Target time to expiry in years
+    pub tenor: f64,
+    pub schedule: RollSchedule,
+    /// Half the bid-ask spread in volatility (0.005 = half a vol point)
+    pub half_spread: f64,
+    /// Listed expiry times on the history's clock; empty to expire on the schedule's own grid
+    pub listed_expiries: Vec<f64>,
+    /// How orders are worked inside the spread
+    pub execution: ExecutionModel,
+}
+
+/// P&L and roll history of a rolling position
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollingSeries {
+    /// Dates in years from the first observation
+    pub times: Vec<f64>,
+    /// Cash plus options at mid, after the day's trades
+    pub pnl: Vec<f64>,
+    /// Cumulative transaction costs paid
+    pub costs: Vec<f64>,
+    /// Quantity-weighted time to expiry of the options held after the day's trades
+    pub maturity: Vec<f64>,
+    /// Options still working after the day's trades, summed in absolute size
+    pub unfilled: Vec<f64>,
+    /// Dates on which a new expiry was opened
+    pub roll_times: Vec<f64>,
+}
+
+/// One held option
+#[derive(Debug, Clone, Copy)]
+struct Holding {
+    option_type: OptionType,
+    strike: f64,
+    expiry: f64,
+    quantity: f64,
+}
+
+impl Holding {
+    fn same_option(&self, other: &Holding) -> bool {
+        self.option_type == other.option_type && self.strike == other.strike && self.expiry == other.expiry
+    }
+}
+
+/// Options, working orders and cash carried between observations
+struct Book {
+    holdings: Vec<Holding>,
+    /// Targets of orders not yet completely filled
+    pending: Vec<Holding>,
+    cash: f64,
+    costs: f64,
+    half_spread: f64,
+    execution: ExecutionModel,
+}
+
+impl Book {
+    fn price(obs: &MarketObservation, h: &Holding, volatility: f64) -> Result<f64, BlackScholesError> {
+        let remaining = h.expiry - obs.time;
+        let model = BlackScholes::new(obs.spot, h.strike, remaining, obs.rate, volatility, obs.dividend_yield)?;
+        Ok(model.price(h.option_type))
+    }
+
+    fn mark(&self, obs: &MarketObservation) -> Result<f64, BlackScholesError> {
+        let mut value = self.cash;
+        for h in &self.holdings {
+            value += h.quantity * Book::price(obs, h, obs.volatility)?;
+        }
+        Ok(value)
+    }
+
+    /// Work an order taking the holding with `target`'s type, strike and expiry to `target.quantity`
+    ///
+    /// Replaces any order still working in the same option; whatever the
+    /// execution model leaves unfilled is worked again by `retry_pending`.
+    fn trade_to(&mut self, target: Holding, obs: &MarketObservation) -> Result<(), BlackScholesError> {
+        self.pending.retain(|p| !p.same_option(&target));
+        let existing = self.holdings.iter().position(|h| h.same_option(&target));
+        let held = existing.map_or(0.0, |i| self.holdings[i].quantity);
+        let traded = target.quantity - held;
+        if traded == 0.0 {
+            return Ok(());
+        }
+        let Fill { resting, crossed, unfilled } = self.execution.fill(traded, self.half_spread);
+        let shifted = |offset: f64| (obs.volatility + traded.signum() * offset * self.half_spread).max(0.0);
+        let mid = Book::price(obs, &target, obs.volatility)?;
+        let rest_price = Book::price(obs, &target, shifted(self.execution.aggression))?;
+        let touch = Book::price(obs, &target, shifted(1.0))?;
+        let cost = resting * (rest_price - mid) + crossed * (touch - mid);
+        self.cash -= (resting + crossed) * mid + cost;
+        self.costs += cost;
+
+        let quantity = if unfilled == 0.0 {
+            target.quantity
+        } else {
+            self.pending.push(target);
+            held + resting + crossed
+        };
+        match existing {
+            Some(i) if quantity == 0.0 => {
+                self.holdings.remove(i);
+            }
+            Some(i) => self.holdings[i].quantity = quantity,
+            // Kept even when nothing filled, so holdings stay in leg order
+            None => self.holdings.push(Holding { quantity, ..target }),
+        }
+        Ok(())
+    }
+
+    /// Work every order left over from earlier observations, dropping those on expired options
+    fn retry_pending(&mut self, obs: &MarketObservation) -> Result<(), BlackScholesError> {
+        let pending = std::mem::take(&mut self.pending);
+        for target in pending {
+            if target.expiry - obs.time > SETTLE_EPSILON {
+                self.trade_to(target, obs)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Cancel working orders and close every holding
+    fn close_all(&mut self, obs: &MarketObservation) -> Result<(), BlackScholesError> {
+        self.pending.clear();
+        let held = self.holdings.clone();
+        for h in held {
+            self.trade_to(Holding { quantity: 0.0, ..h }, obs)?;
+        }
+        Ok(())
+    }
+
+    fn unfilled(&self) -> f64 {
+        self.pending
+            .iter()
+            .map(|p| {
+                let held = self.holdings.iter().find(|h| h.same_option(p)).map_or(0.0, |h| h.quantity);
+                (p.quantity - held).abs()
+            })
+            .sum()
+    }
+
+    fn maturity(&self, time: f64) -> f64 {
+        let size: f64 = self.holdings.iter().map(|h| h.quantity.abs()).sum();
+        if size == 0.0 {
+            return 0.0;
+        }
+        self.holdings.iter().map(|h| h.quantity.abs() * (h.expiry - time)).sum::<f64>() / size
+    }
+}
+
+impl RollingPosition {
+    pub fn new(
+        legs: Vec<RollingLeg>,
+        tenor: f64,
+        schedule: RollSchedule,
+        half_spread: f64,
+    ) -> Result<Self, BlackScholesError> {
+        if legs.is_empty() {
+            return Err(BlackScholesError::invalid("A rolling position needs at least one leg"));
+        }
+        for leg in &legs {
+            validation::in_range("Moneyness", leg.moneyness, -0.5, 0.5, "in [-0.5, 0.5]")?;
+            validation::finite("Quantity", leg.quantity)?;
+        }
+        let tenor = validation::positive("Tenor", tenor)?;
+        match schedule {
+            RollSchedule::HoldToExpiry => {}
+            RollSchedule::Every { interval: period } | RollSchedule::ConstantMaturity { cycle: period } => {
+                validation::positive("Roll period", period)?;
+                if period > tenor {
+                    return Err(BlackScholesError::invalid("Roll period must not exceed the tenor"));
+                }
+            }
+        }
+        Ok(RollingPosition {
+            legs,
+            tenor,
+            schedule,
+            half_spread: validation::non_negative("Half spread", half_spread)?,
+            listed_expiries: Vec::new(),
+            execution: ExecutionModel::touch(),
+        })
+    }
+
+    /// Work orders with `execution` instead of filling them in full at the touch
+    pub fn with_execution(mut self, execution: ExecutionModel) -> Self {
+        self.execution = execution;
+        self
+    }
+
+    /// Trade only listed expiries, e.g. from `ExpiryCalendar::expiry_times`
+    ///
+    /// Each roll opens the listed expiry nearest the tenor, and a constant
+    /// maturity blend is held in the listed expiries bracketing it instead
+    /// of on the `cycle` grid. Times are measured like the history's.
+    pub fn with_listed_expiries(mut self, expiries: Vec<f64>) -> Result<Self, BlackScholesError> {
+        validation::all_finite("Listed expiry", &expiries)?;
+        if expiries.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(BlackScholesError::invalid("Listed expiries must be strictly increasing"));
+        }
+        self.listed_expiries = expiries;
+        Ok(self)
+    }
+
+    /// Rolling straddle: one call and one put struck at the spot
+    pub fn straddle(tenor: f64, schedule: RollSchedule, half_spread: f64) -> Result<Self, BlackScholesError> {
+        let leg = |option_type| RollingLeg {
+            option_type,
+            moneyness: 0.0,
+            quantity: 1.0,
+        };
+        RollingPosition::new(vec![leg(OptionType::Call), leg(OptionType::Put)], tenor, schedule, half_spread)
+    }
+
+    /// Replay the position over `history` (chronological, at least two observations)
+    pub fn replay(&self, history: &[MarketObservation]) -> Result<RollingSeries, BlackScholesError> {
+        if history.len() < 2 {
+            return Err(BlackScholesError::invalid("Rolling history needs at least two observations"));
+        }
+        if history.iter().any(|obs| !obs.time.is_finite()) || history.windows(2).any(|w| w[1].time <= w[0].time) {
+            return Err(BlackScholesError::invalid("Observation times must be finite and strictly increasing"));
+        }
+        let t0 = history[0].time;
+        let mut book = Book {
+            holdings: Vec::new(),
+            pending: Vec::new(),
+            cash: 0.0,
+            costs: 0.0,
+            half_spread: self.half_spread,
+            execution: self.execution,
+        };
+        let mut series = RollingSeries {
+            times: Vec::with_capacity(history.len()),
+            pnl: Vec::with_capacity(history.len()),
+            costs: Vec::with_capacity(history.len()),
+            maturity: Vec::with_capacity(history.len()),
+            unfilled: Vec::with_capacity(history.len()),
+            roll_times: Vec::new(),
+        };
+        let mut last_roll = t0;
+
+        for (i, obs) in history.iter().enumerate() {
+            validation::positive("Spot", obs.spot)?;
+            if i > 0 {
+                let prev = &history[i - 1];
+                let dt = obs.time - prev.time;
+                book.cash *= (prev.rate * dt).exp();
+                book.holdings.retain(|h| {
+                    if h.expiry - obs.time > SETTLE_EPSILON {
+                        return true;
+                    }
+                    let payoff = match h.option_type {
+                        OptionType::Call => (obs.spot - h.strike).max(0.0),
+                        OptionType::Put => (h.strike - obs.spot).max(0.0),
+                    };
+                    book.cash += h.quantity * payoff;
+                    false
+                });
+                book.retry_pending(obs)?;
+            }
+
+            match self.schedule {
+                RollSchedule::HoldToExpiry => {
+                    if book.holdings.is_empty() && book.pending.is_empty() {
+                        let expiry = nearest_listed(&self.listed_expiries, obs.time, self.tenor)?;
+                        self.open(&mut book, obs, expiry, 1.0)?;
+                        series.roll_times.push(obs.time - t0);
+                    }
+                }
+                RollSchedule::Every { interval } => {
+                    let idle = book.holdings.is_empty() && book.pending.is_empty();
+                    if idle || obs.time - last_roll >= interval - SETTLE_EPSILON {
+                        book.close_all(obs)?;
+                        let expiry = nearest_listed(&self.listed_expiries, obs.time, self.tenor)?;
+                        self.open(&mut book, obs, expiry, 1.0)?;
+                        series.roll_times.push(obs.time - t0);
+                        last_roll = obs.time;
+                    }
+                }
+                RollSchedule::ConstantMaturity { cycle } => {
+                    let target = obs.time + self.tenor;
+                    let (near, far) = self.bracket(obs.time, target, t0, cycle)?;
+                    let near_weight = ((far - target) / (far - near)).clamp(0.0, 1.0);
+                    // Close expiries that have left the blend
+                    let stale: Vec<Holding> = book
+                        .holdings
+                        .iter()
+                        .filter(|h| h.expiry != near && h.expiry != far)
+                        .copied()
+                        .collect();
+                    for h in stale {
+                        book.trade_to(Holding { quantity: 0.0, ..h }, obs)?;
+                    }
+                    for (expiry, weight) in [(near, near_weight), (far, 1.0 - near_weight)] {
+                        if expiry - obs.time <= SETTLE_EPSILON {
+                            continue;
+                        }
+                        if book.holdings.iter().any(|h| h.expiry == expiry) {
+                            self.rebalance(&mut book, obs, expiry, weight)?;
+                        } else if weight > SETTLE_EPSILON {
+                            self.open(&mut book, obs, expiry, weight)?;
+                            series.roll_times.push(obs.time - t0);
+                        }
+                    }
+                }
+            }
+
+            series.times.push(obs.time - t0);
+            series.pnl.push(book.mark(obs)?);
+            series.costs.push(book.costs);
+            series.maturity.push(book.maturity(obs.time));
+            series.unfilled.push(book.unfilled());
+        }
+        Ok(series)
+    }
+
+    /// Expiries either side of `target` for the constant maturity blend
+    fn bracket(&self, time: f64, target: f64, t0: f64, cycle: f64) -> Result<(f64, f64), BlackScholesError> {
+        if self.listed_expiries.is_empty() {
+            // Expiries are t0 + k·cycle, snapped so a target on one is not split by rounding
+            let k = ((target - t0) / cycle + SETTLE_EPSILON).floor();
+            return Ok((t0 + k * cycle, t0 + (k + 1.0) * cycle));
+        }
+        let ahead = || self.listed_expiries.iter().copied().filter(|&e| e - time > SETTLE_EPSILON);
+        let near = ahead().rfind(|&e| e <= target + SETTLE_EPSILON);
+        let far = ahead().find(|&e| e > target + SETTLE_EPSILON);
+        match (near, far) {
+            (Some(near), Some(far)) => Ok((near, far)),
+            _ => Err(BlackScholesError::invalid("Listed expiries do not bracket the target maturity")),
+        }
+    }
+
+    /// Open every leg at `expiry`, struck off today's spot, scaled by `weight`
+    fn open(
+        &self,
+        book: &mut Book,
+        obs: &MarketObservation,
+        expiry: f64,
+        weight: f64,
+    ) -> Result<(), BlackScholesError> {
+        for leg in &self.legs {
+            let holding = Holding {
+                option_type: leg.option_type,
+                strike: obs.spot * (1.0 + leg.moneyness),
+                expiry,
+                quantity: weight * leg.quantity,
+            };
+            book.trade_to(holding, obs)?;
+        }
+        Ok(())
+    }
+
+    /// Resize the legs already held at `expiry` to `weight` times their template quantities
+    fn rebalance(
+        &self,
+        book: &mut Book,
+        obs: &MarketObservation,
+        expiry: f64,
+        weight: f64,
+    ) -> Result<(), BlackScholesError> {
+        // Legs were opened in template order, so the k-th holding at this expiry is the k-th leg
+        let held: Vec<Holding> = book.holdings.iter().filter(|h| h.expiry == expiry).copied().collect();
+        for (h, leg) in held.into_iter().zip(&self.legs) {
+            book.trade_to(Holding { quantity: weight * leg.quantity, ..h }, obs)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bonds::Date;
+    use crate::execution::Remainder;
+    use crate::expiry_calendar::{Exchange, ExpiryCalendar, ExpiryCycle};
+
+    const MONTH: f64 = 21.0 / 252.0;
+
+    /// Daily history with a deterministic oscillating spot
+    fn history(days: usize, swing: f64) -> Vec<MarketObservation> {
+        (0..=days)
+            .map(|d| MarketObservation {
+                time: d as f64 / 252.0,
+                spot: 100.0 * (1.0 + swing * (d as f64 * 0.9).sin()),
+                volatility: 0.2,
+                rate: 0.03,
+                dividend_yield: 0.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_straddle_held_to_expiry_pays_premium_on_a_flat_spot() {
+        let flat = history(252, 0.0);
+        let position = RollingPosition::straddle(MONTH, RollSchedule::HoldToExpiry, 0.0).unwrap();
+        let series = position.replay(&flat).unwrap();
+        assert_eq!(series.roll_times.len(), 13);
+        assert!((series.roll_times[1] - MONTH).abs() < 1e-12);
+        assert!(series.costs.iter().all(|&c| c == 0.0));
+        // Each month's at-the-money straddle expires worthless
+        let straddle = |option_type| {
+            BlackScholes::new(100.0, 100.0, MONTH, 0.03, 0.2, 0.0).unwrap().price(option_type)
+        };
+        let premium = straddle(OptionType::Call) + straddle(OptionType::Put);
+        let growth = (0.03 * MONTH).exp();
+        let lost: f64 = (1..=12).map(|k| premium * growth.powi(k)).sum();
+        // The straddle opened on the last day is still worth its premium
+        assert!((series.pnl[252] + lost).abs() < 1e-9 * lost, "{} vs {}", series.pnl[252], -lost);
+        assert!((series.maturity[10] - (MONTH - 10.0 / 252.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_constant_maturity_blend_holds_the_tenor() {
+        let choppy = history(252, 0.02);
+        let position = RollingPosition::straddle(MONTH, RollSchedule::ConstantMaturity { cycle: MONTH }, 0.0).unwrap();
+        let series = position.replay(&choppy).unwrap();
+        for &maturity in &series.maturity {
+            assert!((maturity - MONTH).abs() < 1e-9, "{maturity}");
+        }
+        // The first listed expiry on day 0, then the next one the day after each expiry
+        assert_eq!(series.roll_times.len(), 13, "{:?}", series.roll_times);
+        assert!((series.roll_times[2] - 22.0 / 252.0).abs() < 1e-12);
+
+        // Long and short books mirror each other without costs
+        let short = RollingPosition::new(
+            position.legs.iter().map(|l| RollingLeg { quantity: -l.quantity, ..*l }).collect(),
+            MONTH,
+            position.schedule,
+            0.0,
+        )
+        .unwrap();
+        let mirrored = short.replay(&choppy).unwrap();
+        for (a, b) in series.pnl.iter().zip(&mirrored.pnl) {
+            assert!((a + b).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_more_frequent_rolls_cost_more() {
+        let choppy = history(252, 0.02);
+        let costs = |schedule| {
+            let series = RollingPosition::straddle(3.0 * MONTH, schedule, 0.005).unwrap().replay(&choppy).unwrap();
+            series.costs[series.costs.len() - 1]
+        };
+        let (expiry, weekly, blend) = (
+            costs(RollSchedule::HoldToExpiry),
+            costs(RollSchedule::Every { interval: 5.0 / 252.0 }),
+            costs(RollSchedule::ConstantMaturity { cycle: MONTH }),
+        );
+        assert!(expiry > 0.0);
+        assert!(weekly > 3.0 * expiry, "{weekly} vs {expiry}");
+        assert!(blend > 0.0);
+    }
+
+    #[test]
+    fn test_execution_model_sets_fill_costs() {
+        let choppy = history(126, 0.02);
+        let schedule = RollSchedule::ConstantMaturity { cycle: MONTH };
+        let replay = |execution| {
+            let position = RollingPosition::straddle(MONTH, schedule, 0.005).unwrap().with_execution(execution);
+            position.replay(&choppy).unwrap()
+        };
+        let touch = replay(ExecutionModel::touch());
+        let mid = replay(ExecutionModel::mid());
+        let free = RollingPosition::straddle(MONTH, schedule, 0.0).unwrap().replay(&choppy).unwrap();
+        assert_eq!(mid.pnl, free.pnl);
+        assert!(mid.costs.iter().all(|&c| c == 0.0));
+
+        // Resting at mid and crossing what does not fill lands between the two
+        let passive = replay(ExecutionModel::new(0.0, 100.0, None, Remainder::Cross).unwrap());
+        let (paid, full) = (passive.costs[126], touch.costs[126]);
+        assert!(paid > 0.0 && paid < full, "{paid} vs {full}");
+        assert!(passive.unfilled.iter().all(|&u| u == 0.0));
+        assert!(passive.pnl[126] > touch.pnl[126]);
+
+        // Carried orders are worked down over the following days
+        let carry = ExecutionModel::new(0.5, 100.0, Some(0.5), Remainder::Carry).unwrap();
+        let held = RollingPosition::straddle(MONTH, RollSchedule::HoldToExpiry, 0.005)
+            .unwrap()
+            .with_execution(carry)
+            .replay(&choppy)
+            .unwrap();
+        assert!(held.unfilled[0] > held.unfilled[1] && held.unfilled[1] > held.unfilled[5], "{:?}", held.unfilled);
+        assert!(held.unfilled[15] < 1e-3);
+        assert_eq!(held.roll_times.len(), 7);
+    }
+
+    #[test]
+    fn test_rolls_on_listed_expiries() {
+        let calendar = ExpiryCalendar::new(Exchange::Cboe);
+        let (as_of, end) = (Date::new(2023, 12, 29).unwrap(), Date::new(2024, 6, 28).unwrap());
+        let days: Vec<Date> = std::iter::once(as_of).chain(calendar.business_days(as_of, end)).collect();
+        let history: Vec<MarketObservation> = days
+            .iter()
+            .enumerate()
+            .map(|(i, d)| MarketObservation {
+                time: as_of.days_until(*d) as f64 / 365.0,
+                spot: 100.0 * (1.0 + 0.02 * (i as f64 * 0.9).sin()),
+                volatility: 0.2,
+                rate: 0.03,
+                dividend_yield: 0.0,
+            })
+            .collect();
+        let listed_until = Date::new(2024, 9, 30).unwrap();
+        let monthlies = calendar.expiry_times(as_of, listed_until, &[ExpiryCycle::Monthly]);
+        let position = RollingPosition::straddle(1.0 / 12.0, RollSchedule::HoldToExpiry, 0.0)
+            .unwrap()
+            .with_listed_expiries(monthlies)
+            .unwrap();
+        let series = position.replay(&history).unwrap();
+        // Opened on the first day, then rolled at the close of each AM-settled monthly
+        let expired = calendar.expiries(as_of, end, &[ExpiryCycle::Monthly]);
+        assert_eq!(series.roll_times.len(), 1 + expired.len());
+        for (roll, expiry) in series.roll_times[1..].iter().zip(&expired) {
+            assert!((roll - as_of.days_until(expiry.date) as f64 / 365.0).abs() < 1e-12);
+        }
+
+        // Weeklies are dense enough to bracket a one-month blend every day
+        let listed = calendar.expiry_times(as_of, listed_until, &[ExpiryCycle::Monthly, ExpiryCycle::Weekly]);
+        let blend = RollingPosition::straddle(1.0 / 12.0, RollSchedule::ConstantMaturity { cycle: 1.0 / 12.0 }, 0.0)
+            .unwrap()
+            .with_listed_expiries(listed)
+            .unwrap();
+        for &maturity in &blend.replay(&history).unwrap().maturity {
+            assert!((maturity - 1.0 / 12.0).abs() < 1e-9, "{maturity}");
+        }
+        assert!(position.with_listed_expiries(vec![0.2, 0.1]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_positions() {
+        let leg = RollingLeg {
+            option_type: OptionType::Call,
+            moneyness: 0.05,
+            quantity: 1.0,
+        };
+        assert!(RollingPosition::new(vec![], MONTH, RollSchedule::HoldToExpiry, 0.0).is_err());
+        assert!(RollingPosition::new(vec![leg], 0.0, RollSchedule::HoldToExpiry, 0.0).is_err());
+        assert!(RollingPosition::new(vec![leg], MONTH, RollSchedule::Every { interval: 2.0 * MONTH }, 0.0).is_err());
+        assert!(RollingPosition::new(vec![leg], MONTH, RollSchedule::HoldToExpiry, -0.01).is_err());
+        let position = RollingPosition::new(vec![leg], MONTH, RollSchedule::HoldToExpiry, 0.0).unwrap();
+        assert!(position.replay(&history(0, 0.0)).is_err());
+        let mut unordered = history(30, 0.0);
+        unordered[10].time = f64::NAN;
+        assert!(position.replay(&unordered).is_err());
+        unordered[10].time = unordered[9].time;
+        assert!(position.replay(&unordered).is_err());
+    }
+}
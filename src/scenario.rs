@@ -0,0 +1,971 @@
+use crate::barrier::BarrierOption;
+use crate::black_scholes::{BlackScholes, OptionType, EXPIRY_EPSILON};
+use crate::cross_greeks::{CrossGreeks, PricingInput, PRICING_INPUTS};
+use crate::digital::DigitalOption;
+use crate::error::BlackScholesError;
+use crate::explain::cross_greeks;
+use crate::market::MarketContext;
+use crate::model::EuropeanModel;
+use crate::portfolio::{Instrument, Portfolio, Position};
+use crate::risk::{cholesky, correlation_factor, nearest_correlation, RiskFactors};
+use crate::strategy::Strategy;
+use crate::validation;
+use crate::vol_surface::VolSurface;
+use std::sync::Arc;
+
+/// Floor applied to shocked volatilities
+const MIN_VOLATILITY: f64 = 1e-8;
+
+/// One market move applied to every position
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shock {
+    /// Relative spot move (0.05 = +5%)
+    pub spot: f64,
+    /// Absolute volatility shift (0.01 = one vol point)
+    pub vol: f64,
+    /// Time elapsed in years
+    pub time: f64,
+}
+
+impl Shock {
+    pub fn new(spot: f64, vol: f64, time: f64) -> Result<Self, BlackScholesError> {
+        let spot = validation::finite("Spot shock", spot)?;
+        if spot <= -1.0 {
+            return Err(BlackScholesError::OutOfRange {
+                field: "Spot shock",
+                value: spot,
+                requirement: "> -1 (spot must stay positive)",
+            });
+        }
+        Ok(Shock {
+            spot,
+            vol: validation::finite("Vol shock", vol)?,
+            time: validation::non_negative("Elapsed time", time)?,
+        })
+    }
+}
+
+/// Anything that can be repriced under a market shock
+pub trait Revalue {
+    /// Value after `shock`; the unshocked value is `revalue(&Shock::default())`
+    fn revalue(&self, shock: &Shock) -> f64;
+}
+
+/// Apply a shock to a Black-Scholes model
+///
+/// The elapsed calendar time ages the volatility clock in proportion,
+/// stopping an instant before expiry.
+fn shocked(model: &BlackScholes, shock: &Shock) -> BlackScholes {
+    BlackScholes {
+        spot_price: model.spot_price * (1.0 + shock.spot),
+        volatility: (model.volatility + shock.vol).max(MIN_VOLATILITY),
+        ..model.aged(model.time_to_expiry - shock.time)
+    }
+}
+
+impl Revalue for Instrument {
+    /// Vanillas and strategy legs pay intrinsic once expired; digitals and
+    /// barriers are valued an instant before expiry, which converges to
+    /// their payoff.
+    fn revalue(&self, shock: &Shock) -> f64 {
+        match self {
+            Instrument::Underlying { spot } => spot * (1.0 + shock.spot),
+            Instrument::Vanilla { model, option_type } => {
+                let m = shocked(model, shock);
+                if model.time_to_expiry - shock.time > EXPIRY_EPSILON {
+                    return m.price(*option_type);
+                }
+                match option_type {
+                    OptionType::Call => (m.spot_price - m.strike_price).max(0.0),
+                    OptionType::Put => (m.strike_price - m.spot_price).max(0.0),
+                }
+            }
+            Instrument::Digital { option, option_type } => DigitalOption {
+                model: shocked(&option.model, shock),
+                ..*option
+            }
+            .price(*option_type),
+            Instrument::Barrier { option, option_type } => BarrierOption {
+                model: shocked(&option.model, shock),
+                ..*option
+            }
+            .price(*option_type),
+            Instrument::Strategy { strategy, market } => {
+                strategy.value_at(market, shock.time, market.spot_price * (1.0 + shock.spot), shock.vol)
+            }
+        }
+    }
+}
+
+impl Revalue for Position {
+    fn revalue(&self, shock: &Shock) -> f64 {
+        self.units() * self.instrument.revalue(shock)
+    }
+}
+
+impl Revalue for Portfolio {
+    fn revalue(&self, shock: &Shock) -> f64 {
+        self.cash + self.positions.iter().map(|p| p.revalue(shock)).sum::<f64>()
+    }
+}
+
+/// Spot × vol × time grid of shocks
+///
+/// Deserializable (with the `serde` feature), so desks can keep custom
+/// ladders in configuration files next to the presets below.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScenarioGrid {
+    pub name: String,
+    /// Relative spot moves (0.01 = +1%)
+    pub spot_shocks: Vec<f64>,
+    /// Absolute volatility shifts (0.01 = one vol point)
+    pub vol_shocks: Vec<f64>,
+    /// Elapsed times in years
+    pub time_steps: Vec<f64>,
+}
+
+/// P&L of one target over a scenario grid
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScenarioResult {
+    pub grid: ScenarioGrid,
+    /// Unshocked value
+    pub base_value: f64,
+    /// `pnl[t][v][s]` is the P&L at `time_steps[t]`, `vol_shocks[v]` and `spot_shocks[s]`
+    pub pnl: Vec<Vec<Vec<f64>>>,
+}
+
+impl ScenarioGrid {
+    pub fn new(
+        name: &str,
+        spot_shocks: Vec<f64>,
+        vol_shocks: Vec<f64>,
+        time_steps: Vec<f64>,
+    ) -> Result<Self, BlackScholesError> {
+        let grid = ScenarioGrid {
+            name: name.to_string(),
+            spot_shocks,
+            vol_shocks,
+            time_steps,
+        };
+        grid.validate()?;
+        Ok(grid)
+    }
+
+    /// Symmetric ladder: spot from -`spot_range` to +`spot_range` in `spot_step`
+    /// increments, vol likewise, evaluated today
+    pub fn ladder(spot_range: f64, spot_step: f64, vol_range: f64, vol_step: f64) -> Result<Self, BlackScholesError> {
+        let spot_step = validation::positive("Spot step", spot_step)?;
+        let vol_step = validation::positive("Vol step", vol_step)?;
+        let steps = |range: f64, step: f64| {
+            let n = (range / step).round() as i64;
+            (-n..=n).map(|i| i as f64 * step).collect::<Vec<f64>>()
+        };
+        ScenarioGrid::new(
+            "Ladder",
+            steps(validation::non_negative("Spot range", spot_range)?, spot_step),
+            steps(validation::non_negative("Vol range", vol_range)?, vol_step),
+            vec![0.0],
+        )
+    }
+
+    /// ±10% spot in 1% steps × ±5 vol points in 1-point steps, today
+    pub fn spot_vol_ladder() -> Self {
+        let mut grid = ScenarioGrid::ladder(0.10, 0.01, 0.05, 0.01).expect("preset ladder is valid");
+        grid.name = String::from("Spot ±10% × vol ±5");
+        grid
+    }
+
+    /// ±20% spot in 5% steps at today, one week and one month
+    pub fn spot_decay_ladder() -> Self {
+        let mut grid = ScenarioGrid::ladder(0.20, 0.05, 0.0, 0.01).expect("preset ladder is valid");
+        grid.name = String::from("Spot ±20% × decay");
+        grid.time_steps = vec![0.0, 7.0 / 365.0, 30.0 / 365.0];
+        grid
+    }
+
+    /// Number of scenarios in the grid
+    pub fn len(&self) -> usize {
+        self.spot_shocks.len() * self.vol_shocks.len() * self.time_steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check every shock, e.g. after loading a custom grid from a file
+    pub fn validate(&self) -> Result<(), BlackScholesError> {
+        if self.is_empty() {
+            return Err(BlackScholesError::invalid("Scenario grid needs at least one spot, vol and time step"));
+        }
+        for &s in &self.spot_shocks {
+            for &v in &self.vol_shocks {
+                for &t in &self.time_steps {
+                    Shock::new(s, v, t)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reprice `target` under every shock in the grid
+    pub fn run<R: Revalue + ?Sized>(&self, target: &R) -> Result<ScenarioResult, BlackScholesError> {
+        self.validate()?;
+        let base_value = target.revalue(&Shock::default());
+        let pnl = self
+            .time_steps
+            .iter()
+            .map(|&time| {
+                self.vol_shocks
+                    .iter()
+                    .map(|&vol| {
+                        self.spot_shocks
+                            .iter()
+                            .map(|&spot| target.revalue(&Shock { spot, vol, time }) - base_value)
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(ScenarioResult {
+            grid: self.clone(),
+            base_value,
+            pnl,
+        })
+    }
+}
+
+impl ScenarioResult {
+    /// Worst P&L in the grid and the shock producing it
+    pub fn worst(&self) -> (Shock, f64) {
+        let mut worst = (Shock::default(), f64::INFINITY);
+        for (t, plane) in self.pnl.iter().enumerate() {
+            for (v, row) in plane.iter().enumerate() {
+                for (s, &pnl) in row.iter().enumerate() {
+                    if pnl < worst.1 {
+                        let shock = Shock {
+                            spot: self.grid.spot_shocks[s],
+                            vol: self.grid.vol_shocks[v],
+                            time: self.grid.time_steps[t],
+                        };
+                        worst = (shock, pnl);
+                    }
+                }
+            }
+        }
+        worst
+    }
+
+    /// Spot × vol P&L matrix at one time step, rows by vol shock
+    pub fn matrix(&self, time_index: usize) -> &[Vec<f64>] {
+        &self.pnl[time_index]
+    }
+}
+
+/// Change in the shape of an implied volatility surface
+///
+/// Shifts are absolute volatilities (0.01 = one vol point) as a function of
+/// log-moneyness k = ln(K/F) against the expiry's forward.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SurfaceShock {
+    /// Every volatility moves by `shift`
+    Parallel { shift: f64 },
+    /// Rotation around the forward: `slope · k`, so a negative slope steepens put skew
+    SkewTwist { slope: f64 },
+    /// Wing bump: `bump · k²`, leaving the at-the-money volatility unchanged
+    Convexity { bump: f64 },
+    /// `front_shift` up to `front_expiry`, `back_shift` from `back_expiry`,
+    /// linear in expiry between them
+    TermSteepener {
+        front_expiry: f64,
+        front_shift: f64,
+        back_expiry: f64,
+        back_shift: f64,
+    },
+}
+
+impl SurfaceShock {
+    /// Volatility shift at log-moneyness `k` and `expiry`
+    pub fn vol_shift(&self, k: f64, expiry: f64) -> f64 {
+        match *self {
+            SurfaceShock::Parallel { shift } => shift,
+            SurfaceShock::SkewTwist { slope } => slope * k,
+            SurfaceShock::Convexity { bump } => bump * k * k,
+            SurfaceShock::TermSteepener {
+                front_expiry,
+                front_shift,
+                back_expiry,
+                back_shift,
+            } => {
+                let weight = ((expiry - front_expiry) / (back_expiry - front_expiry)).clamp(0.0, 1.0);
+                front_shift + (back_shift - front_shift) * weight
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), BlackScholesError> {
+        match *self {
+            SurfaceShock::Parallel { shift: x }
+            | SurfaceShock::SkewTwist { slope: x }
+            | SurfaceShock::Convexity { bump: x } => validation::finite("Surface shock", x).map(|_| ()),
+            SurfaceShock::TermSteepener {
+                front_expiry,
+                front_shift,
+                back_expiry,
+                back_shift,
+            } => {
+                validation::positive("Front expiry", front_expiry)?;
+                validation::finite("Front shift", front_shift)?;
+                validation::finite("Back shift", back_shift)?;
+                if validation::finite("Back expiry", back_expiry)? <= front_expiry {
+                    return Err(BlackScholesError::invalid("Steepener back expiry must follow the front expiry"));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Named combination of surface shocks applied together
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurfaceScenario {
+    pub name: String,
+    pub shocks: Vec<SurfaceShock>,
+}
+
+impl SurfaceScenario {
+    pub fn new(name: &str, shocks: Vec<SurfaceShock>) -> Result<Self, BlackScholesError> {
+        let scenario = SurfaceScenario {
+            name: name.to_string(),
+            shocks,
+        };
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Standard vol-book stresses: parallel ±5 points, skew steepening,
+    /// wings up and a front-down/back-up term steepener
+    pub fn standard_set() -> Vec<SurfaceScenario> {
+        let scenario = |name: &str, shock| SurfaceScenario::new(name, vec![shock]).expect("preset scenario is valid");
+        vec![
+            scenario("Vol +5", SurfaceShock::Parallel { shift: 0.05 }),
+            scenario("Vol -5", SurfaceShock::Parallel { shift: -0.05 }),
+            scenario("Skew steepener", SurfaceShock::SkewTwist { slope: -0.10 }),
+            scenario("Wings up", SurfaceShock::Convexity { bump: 0.20 }),
+            scenario(
+                "Term steepener",
+                SurfaceShock::TermSteepener {
+                    front_expiry: 1.0 / 12.0,
+                    front_shift: -0.03,
+                    back_expiry: 1.0,
+                    back_shift: 0.02,
+                },
+            ),
+        ]
+    }
+
+    /// Check every shock, e.g. after loading a scenario from a file
+    pub fn validate(&self) -> Result<(), BlackScholesError> {
+        self.shocks.iter().try_for_each(SurfaceShock::validate)
+    }
+
+    /// Combined volatility shift at log-moneyness `k` and `expiry`
+    pub fn vol_shift(&self, k: f64, expiry: f64) -> f64 {
+        self.shocks.iter().map(|s| s.vol_shift(k, expiry)).sum()
+    }
+
+    /// The market with its surface shocked; spot and carry are unchanged
+    pub fn apply(&self, market: &MarketContext) -> Result<MarketContext, BlackScholesError> {
+        self.validate()?;
+        let surface = ShockedSurface {
+            market: market.clone(),
+            scenario: self.clone(),
+        };
+        Ok(MarketContext {
+            surface: Arc::new(surface),
+            ..market.clone()
+        })
+    }
+
+    /// Change in a strategy's value when the surface moves
+    pub fn pnl(&self, market: &MarketContext, strategy: &Strategy) -> Result<f64, BlackScholesError> {
+        Ok(strategy.value_in(&self.apply(market)?)? - strategy.value_in(market)?)
+    }
+}
+
+/// A market's surface with a scenario's shifts added on top
+///
+/// Moneyness is measured against the base market's forward for each expiry.
+/// Volatilities are floored just above zero.
+#[derive(Debug, Clone)]
+pub struct ShockedSurface {
+    market: MarketContext,
+    scenario: SurfaceScenario,
+}
+
+impl VolSurface for ShockedSurface {
+    fn implied_vol(&self, strike: f64, expiry: f64) -> f64 {
+        let k = (strike / self.market.forward(expiry)).ln();
+        (self.market.implied_vol(strike, expiry) + self.scenario.vol_shift(k, expiry)).max(MIN_VOLATILITY)
+    }
+}
+
+/// Move in a correlation matrix, applied to the off-diagonal entries
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CorrelationShock {
+    /// Every correlation moves by `shift`, clamped to [-1, 1]
+    Parallel { shift: f64 },
+    /// Correlations between the listed assets move by `shift`; pairs
+    /// with an asset outside the block are unchanged
+    Block { assets: Vec<usize>, shift: f64 },
+    /// Every correlation shrinks towards zero by `factor`: ρ → (1 - factor)·ρ
+    Decorrelation { factor: f64 },
+}
+
+impl CorrelationShock {
+    /// Shocked correlation between assets `i` and `j`
+    pub fn shocked(&self, i: usize, j: usize, rho: f64) -> f64 {
+        let moved = match self {
+            CorrelationShock::Parallel { shift } => rho + shift,
+            CorrelationShock::Block { assets, shift } => {
+                if assets.contains(&i) && assets.contains(&j) {
+                    rho + shift
+                } else {
+                    rho
+                }
+            }
+            CorrelationShock::Decorrelation { factor } => (1.0 - factor) * rho,
+        };
+        moved.clamp(-1.0, 1.0)
+    }
+
+    fn validate(&self) -> Result<(), BlackScholesError> {
+        match self {
+            CorrelationShock::Parallel { shift } => {
+                validation::in_range("Correlation shift", *shift, -2.0, 2.0, "between -2 and 2")?;
+            }
+            CorrelationShock::Block { assets, shift } => {
+                validation::in_range("Correlation shift", *shift, -2.0, 2.0, "between -2 and 2")?;
+                if assets.len() < 2 {
+                    return Err(BlackScholesError::invalid("A correlation block needs at least two assets"));
+                }
+            }
+            CorrelationShock::Decorrelation { factor } => {
+                validation::in_range("Decorrelation factor", *factor, 0.0, 1.0, "between 0 and 1")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Named combination of correlation shocks, applied in order
+///
+/// Shocked matrices are routinely indefinite (a large negative parallel
+/// shift on three or more assets, or a block moving against the rest), so
+/// `apply` repairs them to the nearest valid correlation matrix before
+/// they reach a pricer or a Cholesky factorization.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CorrelationScenario {
+    pub name: String,
+    pub shocks: Vec<CorrelationShock>,
+}
+
+impl CorrelationScenario {
+    pub fn new(name: &str, shocks: Vec<CorrelationShock>) -> Result<Self, BlackScholesError> {
+        let scenario = CorrelationScenario {
+            name: name.to_string(),
+            shocks,
+        };
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Standard correlation stresses for basket and dispersion books:
+    /// parallel ±20 points, full comovement and a 50% decorrelation
+    pub fn standard_set() -> Vec<CorrelationScenario> {
+        let scenario = |name: &str, shock| {
+            CorrelationScenario::new(name, vec![shock]).expect("preset scenario is valid")
+        };
+        vec![
+            scenario("Correlation +20", CorrelationShock::Parallel { shift: 0.2 }),
+            scenario("Correlation -20", CorrelationShock::Parallel { shift: -0.2 }),
+            scenario("Correlation to one", CorrelationShock::Parallel { shift: 2.0 }),
+            scenario("Decorrelation 50%", CorrelationShock::Decorrelation { factor: 0.5 }),
+        ]
+    }
+
+    /// Check every shock, e.g. after loading a scenario from a file
+    pub fn validate(&self) -> Result<(), BlackScholesError> {
+        self.shocks.iter().try_for_each(CorrelationShock::validate)
+    }
+
+    /// The shocked correlation matrix, repaired to the nearest valid one if needed
+    pub fn apply(&self, correlation: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, BlackScholesError> {
+        self.validate()?;
+        let n = correlation.len();
+        if n == 0 || correlation.iter().any(|row| row.len() != n) {
+            return Err(BlackScholesError::invalid("Correlation matrix must be square and non-empty"));
+        }
+        correlation_factor(correlation)?;
+        let block_outside = self.shocks.iter().any(|s| match s {
+            CorrelationShock::Block { assets, .. } => assets.iter().any(|&a| a >= n),
+            _ => false,
+        });
+        if block_outside {
+            return Err(BlackScholesError::invalid("Correlation block refers to an unknown asset"));
+        }
+
+        let shocked: Vec<Vec<f64>> = correlation
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let shock = |j: usize, rho: f64| self.shocks.iter().fold(rho, |rho, s| s.shocked(i, j, rho));
+                row.iter().enumerate().map(|(j, &rho)| if i == j { rho } else { shock(j, rho) }).collect()
+            })
+            .collect();
+        if cholesky(&shocked).is_ok() {
+            return Ok(shocked);
+        }
+        let repaired = nearest_correlation(&shocked)?;
+        correlation_factor(&repaired)?;
+        Ok(repaired)
+    }
+
+    /// Risk factors with the correlation shocked; volatilities are unchanged
+    pub fn apply_to(&self, factors: &RiskFactors) -> Result<RiskFactors, BlackScholesError> {
+        Ok(RiskFactors {
+            correlation: self.apply(&factors.correlation)?,
+            ..factors.clone()
+        })
+    }
+
+    /// Change in value when the correlation moves
+    ///
+    /// # Arguments
+    /// * `correlation` - Base correlation matrix
+    /// * `value` - Values the book under a given correlation matrix
+    pub fn pnl<F>(&self, correlation: &[Vec<f64>], value: F) -> Result<f64, BlackScholesError>
+    where
+        F: Fn(&[Vec<f64>]) -> Result<f64, BlackScholesError>,
+    {
+        Ok(value(&self.apply(correlation)?)? - value(correlation)?)
+    }
+}
+
+/// Second-order expansion of a book's value in the scenario shocks
+///
+/// Built once from per-position cross Greeks, with the spot sensitivities
+/// rescaled to relative moves so the whole book collapses into a single
+/// gradient and Hessian over (spot, vol, time). Any shock is then priced in
+/// constant time regardless of book size. The expansion degrades for large
+/// moves and across expiries, so `validate` it against full revaluation on
+/// a coarse grid to get an error bound before trusting a fine one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaylorExpansion {
+    /// Book value, gradient and Hessian with spot in relative units
+    pub greeks: CrossGreeks,
+}
+
+/// Gap between an expansion and full revaluation over a grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApproximationError {
+    /// Largest absolute P&L error
+    pub max_error: f64,
+    pub rms_error: f64,
+    /// Shock with the largest error
+    pub worst: Shock,
+}
+
+impl TaylorExpansion {
+    pub fn of_position(position: &Position) -> Self {
+        let spot = position.instrument.spot();
+        let mut greeks = cross_greeks(&position.instrument).scaled(position.units());
+        let s = PricingInput::Spot.index();
+        greeks.gradient[s] *= spot;
+        for i in 0..PRICING_INPUTS {
+            greeks.hessian[s][i] *= spot;
+            greeks.hessian[i][s] *= spot;
+        }
+        TaylorExpansion { greeks }
+    }
+
+    pub fn of_portfolio(portfolio: &Portfolio) -> Self {
+        TaylorExpansion {
+            greeks: portfolio.positions.iter().map(|p| TaylorExpansion::of_position(p).greeks).sum(),
+        }
+    }
+
+    /// Estimated P&L under `shock`
+    pub fn pnl(&self, shock: &Shock) -> f64 {
+        let mut dx = [0.0; PRICING_INPUTS];
+        dx[PricingInput::Spot.index()] = shock.spot;
+        dx[PricingInput::Volatility.index()] = shock.vol;
+        dx[PricingInput::Time.index()] = -shock.time;
+        self.greeks.taylor(&dx)
+    }
+
+    /// Approximate `grid.run` without revaluing anything
+    pub fn approximate(&self, grid: &ScenarioGrid) -> Result<ScenarioResult, BlackScholesError> {
+        grid.validate()?;
+        let pnl = grid
+            .time_steps
+            .iter()
+            .map(|&time| {
+                grid.vol_shocks
+                    .iter()
+                    .map(|&vol| grid.spot_shocks.iter().map(|&spot| self.pnl(&Shock { spot, vol, time })).collect())
+                    .collect()
+            })
+            .collect();
+        Ok(ScenarioResult {
+            grid: grid.clone(),
+            base_value: self.greeks.price,
+            pnl,
+        })
+    }
+
+    /// Compare against full revaluation of `target` over `grid`
+    pub fn validate<R: Revalue + ?Sized>(
+        &self,
+        target: &R,
+        grid: &ScenarioGrid,
+    ) -> Result<ApproximationError, BlackScholesError> {
+        let exact = grid.run(target)?;
+        let approx = self.approximate(grid)?;
+        let mut error = ApproximationError {
+            max_error: 0.0,
+            rms_error: 0.0,
+            worst: Shock::default(),
+        };
+        for (t, &time) in grid.time_steps.iter().enumerate() {
+            for (v, &vol) in grid.vol_shocks.iter().enumerate() {
+                for (s, &spot) in grid.spot_shocks.iter().enumerate() {
+                    let gap = (approx.pnl[t][v][s] - exact.pnl[t][v][s]).abs();
+                    error.rms_error += gap * gap;
+                    if gap > error.max_error {
+                        error.max_error = gap;
+                        error.worst = Shock { spot, vol, time };
+                    }
+                }
+            }
+        }
+        error.rms_error = (error.rms_error / grid.len() as f64).sqrt();
+        Ok(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::basket::BasketOption;
+    use crate::digital::DigitalPayoff;
+    use crate::strategy::Leg;
+    use crate::time_scale::{DaySchedule, TimeScale};
+    use crate::vol_surface::FlatVol;
+
+    fn call() -> Instrument {
+        Instrument::Vanilla {
+            model: BlackScholes::new(100.0, 100.0, 0.25, 0.02, 0.2, 0.0).unwrap(),
+            option_type: OptionType::Call,
+        }
+    }
+
+    #[test]
+    fn test_preset_ladder_shape_and_monotonicity() {
+        let grid = ScenarioGrid::spot_vol_ladder();
+        assert_eq!((grid.spot_shocks.len(), grid.vol_shocks.len(), grid.time_steps.len()), (21, 11, 1));
+        let result = grid.run(&call()).unwrap();
+        let matrix = result.matrix(0);
+        // Centre of the grid is the unshocked value
+        assert!(matrix[5][10].abs() < 1e-12);
+        // A long call gains with spot and with vol
+        assert!(matrix[5].windows(2).all(|w| w[1] > w[0]));
+        assert!(matrix.windows(2).all(|w| w[1][10] > w[0][10]));
+        assert_eq!(result.worst().0.spot, -0.10);
+        assert_eq!(result.worst().0.vol, -0.05);
+    }
+
+    #[test]
+    fn test_time_decay_and_expiry() {
+        let grid = ScenarioGrid::new("Decay", vec![0.0, 0.1], vec![0.0], vec![0.0, 0.1, 0.5]).unwrap();
+        let result = grid.run(&call()).unwrap();
+        let base = result.base_value;
+        assert!(result.pnl[1][0][0] < 0.0);
+        // Past expiry the call is worth intrinsic
+        assert!((result.pnl[2][0][0] + base).abs() < 1e-12);
+        assert!((result.pnl[2][0][1] - (10.0 - base)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_time_shock_keeps_calendar_variance_rate() {
+        let schedule = DaySchedule::weekdays(91, 0);
+        let market = BlackScholes::new(100.0, 100.0, 0.25, 0.02, 0.2, 0.0).unwrap();
+        let model = market.with_time_scale(TimeScale::Trading, &schedule).unwrap();
+        let instrument = Instrument::Vanilla { model, option_type: OptionType::Call };
+        let shocked = instrument.revalue(&Shock::new(0.0, 0.0, 0.1).unwrap());
+        let sigma = model.calendar_volatility();
+        let remaining = model.time_to_expiry - 0.1;
+        let expected = BlackScholes::new(100.0, 100.0, remaining, 0.02, sigma, 0.0).unwrap().price(OptionType::Call);
+        assert!((shocked - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_portfolio_matches_sum_of_positions() {
+        let market = BlackScholes::new(100.0, 100.0, 0.5, 0.02, 0.2, 0.0).unwrap();
+        let digital = Instrument::Digital {
+            option: DigitalOption::new(market, DigitalPayoff::CashOrNothing { cash: 10.0 }).unwrap(),
+            option_type: OptionType::Put,
+        };
+        let straddle = Instrument::Strategy {
+            strategy: Strategy::straddle(100.0, 0.5).unwrap(),
+            market,
+        };
+        let positions = vec![
+            Position::new("AAA", call(), -3.0, 100.0).unwrap(),
+            Position::new("AAA", digital, 5.0, 100.0).unwrap(),
+            Position::new("AAA", straddle, 1.0, 100.0).unwrap(),
+        ];
+        let grid = ScenarioGrid::spot_decay_ladder();
+        let book = grid.run(&Portfolio::new(positions.clone())).unwrap();
+        for (t, plane) in book.pnl.iter().enumerate() {
+            for (s, &pnl) in plane[0].iter().enumerate() {
+                let sum: f64 = positions.iter().map(|p| grid.run(p).unwrap().pnl[t][0][s]).sum();
+                assert!((pnl - sum).abs() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_grids() {
+        assert!(ScenarioGrid::new("Empty", vec![], vec![0.0], vec![0.0]).is_err());
+        assert!(ScenarioGrid::new("Wipeout", vec![-1.0], vec![0.0], vec![0.0]).is_err());
+        assert!(ScenarioGrid::new("Past", vec![0.0], vec![0.0], vec![-0.1]).is_err());
+        assert!(ScenarioGrid::ladder(0.1, 0.0, 0.05, 0.01).is_err());
+    }
+
+    fn flat_market() -> MarketContext {
+        MarketContext::new(100.0, 0.03, 0.01, FlatVol::new(0.2)).unwrap()
+    }
+
+    #[test]
+    fn test_surface_shocks_reshape_the_smile() {
+        let market = flat_market();
+        let forward = market.forward(0.5);
+        let scenario = SurfaceScenario::new(
+            "Twist and bump",
+            vec![SurfaceShock::SkewTwist { slope: -0.1 }, SurfaceShock::Convexity { bump: 0.5 }],
+        )
+        .unwrap();
+        let shocked = scenario.apply(&market).unwrap();
+        assert!((shocked.implied_vol(forward, 0.5) - 0.2).abs() < 1e-15);
+        let k: f64 = (80.0 / forward).ln();
+        assert!((shocked.implied_vol(80.0, 0.5) - (0.2 - 0.1 * k + 0.5 * k * k)).abs() < 1e-15);
+        assert!(shocked.implied_vol(80.0, 0.5) > shocked.implied_vol(120.0, 0.5));
+        // Spot and carry are untouched
+        assert_eq!(shocked.spot, market.spot);
+        assert_eq!(shocked.forward(0.5), forward);
+    }
+
+    #[test]
+    fn test_term_steepener_interpolates_in_expiry() {
+        let steepener = SurfaceShock::TermSteepener {
+            front_expiry: 0.25,
+            front_shift: -0.02,
+            back_expiry: 1.25,
+            back_shift: 0.03,
+        };
+        assert_eq!(steepener.vol_shift(0.3, 0.1), -0.02);
+        assert!((steepener.vol_shift(0.0, 0.75) - 0.005).abs() < 1e-15);
+        assert!((steepener.vol_shift(-0.3, 2.0) - 0.03).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_surface_scenarios_propagate_through_pricing() {
+        let market = flat_market();
+        let straddle = Strategy::straddle(100.0, 0.5).unwrap();
+        let vega = straddle.greeks(&market.model(100.0, 0.5).unwrap()).vega;
+        let parallel = &SurfaceScenario::standard_set()[0];
+        let pnl = parallel.pnl(&market, &straddle).unwrap();
+        assert!((pnl / (5.0 * vega) - 1.0).abs() < 0.02);
+
+        // A risk reversal (long 110 call, short 90 put) loses when put skew steepens
+        let risk_reversal = Strategy::new(
+            "Risk reversal",
+            vec![
+                Leg::option(OptionType::Call, 110.0, 0.5, 1.0).unwrap(),
+                Leg::option(OptionType::Put, 90.0, 0.5, -1.0).unwrap(),
+            ],
+        )
+        .unwrap();
+        let skew = &SurfaceScenario::standard_set()[2];
+        assert!(skew.pnl(&market, &risk_reversal).unwrap() < 0.0);
+        // A long strangle gains when the wings are bid
+        let strangle = Strategy::strangle(90.0, 110.0, 0.5).unwrap();
+        assert!(SurfaceScenario::standard_set()[3].pnl(&market, &strangle).unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_invalid_surface_scenarios() {
+        let backwards = SurfaceShock::TermSteepener {
+            front_expiry: 1.0,
+            front_shift: 0.0,
+            back_expiry: 0.5,
+            back_shift: 0.01,
+        };
+        assert!(SurfaceScenario::new("Backwards", vec![backwards]).is_err());
+        assert!(SurfaceScenario::new("NaN", vec![SurfaceShock::Parallel { shift: f64::NAN }]).is_err());
+        // Large downward shifts floor at zero volatility instead of going negative
+        let crush = SurfaceScenario::new("Crush", vec![SurfaceShock::Parallel { shift: -0.5 }]).unwrap();
+        assert!(crush.apply(&flat_market()).unwrap().implied_vol(100.0, 1.0) > 0.0);
+    }
+
+    fn equicorrelation(n: usize, rho: f64) -> Vec<Vec<f64>> {
+        (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { rho }).collect()).collect()
+    }
+
+    #[test]
+    fn test_correlation_shocks() {
+        let base = equicorrelation(3, 0.5);
+        let up = CorrelationScenario::new("Up", vec![CorrelationShock::Parallel { shift: 0.7 }]).unwrap();
+        assert_eq!(up.apply(&base).unwrap(), equicorrelation(3, 1.0));
+
+        let block = CorrelationShock::Block {
+            assets: vec![0, 2],
+            shift: 0.3,
+        };
+        let shocked = CorrelationScenario::new("Block", vec![block]).unwrap().apply(&base).unwrap();
+        assert!((shocked[0][2] - 0.8).abs() < 1e-15 && (shocked[2][0] - 0.8).abs() < 1e-15);
+        assert_eq!((shocked[0][1], shocked[1][2]), (0.5, 0.5));
+
+        let halved = CorrelationScenario::standard_set()[3].apply(&base).unwrap();
+        assert_eq!(halved, equicorrelation(3, 0.25));
+    }
+
+    #[test]
+    fn test_indefinite_stresses_are_repaired() {
+        // Equicorrelation below -1/(n - 1) is not a correlation matrix
+        let base = equicorrelation(3, 0.2);
+        let crash = CorrelationScenario::new("Crash", vec![CorrelationShock::Parallel { shift: -0.9 }]).unwrap();
+        let repaired = crash.apply(&base).unwrap();
+        assert!(correlation_factor(&repaired).is_ok());
+        // The nearest valid matrix is the boundary equicorrelation -1/2
+        assert!(repaired.iter().enumerate().all(|(i, row)| (0..3)
+            .filter(|&j| j != i)
+            .all(|j| (row[j] + 0.5).abs() < 1e-6)));
+
+        let factors = RiskFactors::new(&["A", "B", "C"], vec![0.2, 0.3, 0.25], base).unwrap();
+        assert_eq!(crash.apply_to(&factors).unwrap().correlation, repaired);
+    }
+
+    #[test]
+    fn test_correlation_stress_moves_basket_value() {
+        let value = |correlation: &[Vec<f64>]| {
+            let basket = BasketOption::new(
+                vec![1.0, 1.0],
+                vec![100.0, 100.0],
+                vec![0.0, 0.0],
+                vec![0.2, 0.3],
+                correlation.to_vec(),
+                200.0,
+                1.0,
+                0.03,
+            )?;
+            Ok(basket.ju(OptionType::Call))
+        };
+        let base = equicorrelation(2, 0.5);
+        let [up, down, _, decorrelate] = &CorrelationScenario::standard_set()[..] else {
+            panic!("four presets")
+        };
+        assert!(up.pnl(&base, value).unwrap() > 0.0);
+        assert!(down.pnl(&base, value).unwrap() < 0.0);
+        assert!(decorrelate.pnl(&base, value).unwrap() < 0.0);
+    }
+
+    #[test]
+    fn test_invalid_correlation_scenarios() {
+        let decorrelate = CorrelationShock::Decorrelation { factor: 1.5 };
+        assert!(CorrelationScenario::new("Too far", vec![decorrelate]).is_err());
+        let lone = CorrelationShock::Block {
+            assets: vec![1],
+            shift: 0.1,
+        };
+        assert!(CorrelationScenario::new("Lone", vec![lone]).is_err());
+        let outside = CorrelationShock::Block {
+            assets: vec![0, 5],
+            shift: 0.1,
+        };
+        let scenario = CorrelationScenario::new("Outside", vec![outside]).unwrap();
+        assert!(scenario.apply(&equicorrelation(3, 0.2)).is_err());
+        assert!(CorrelationScenario::standard_set()[0].apply(&[vec![1.0, 0.2], vec![0.3, 1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_taylor_expansion_tracks_full_revaluation() {
+        let market = BlackScholes::new(100.0, 100.0, 0.5, 0.02, 0.2, 0.0).unwrap();
+        let book = Portfolio::new(vec![
+            Position::new("AAA", call(), -10.0, 100.0).unwrap(),
+            Position::new(
+                "AAA",
+                Instrument::Strategy {
+                    strategy: Strategy::straddle(100.0, 0.5).unwrap(),
+                    market,
+                },
+                4.0,
+                100.0,
+            )
+            .unwrap(),
+            Position::new("BBB", Instrument::Underlying { spot: 40.0 }, 300.0, 1.0).unwrap(),
+        ]);
+        let expansion = TaylorExpansion::of_portfolio(&book);
+        assert!((expansion.greeks.price - book.market_value()).abs() < 1e-9);
+
+        let small = ScenarioGrid::ladder(0.02, 0.01, 0.01, 0.01).unwrap();
+        let error = expansion.validate(&book, &small).unwrap();
+        let large = expansion.validate(&book, &ScenarioGrid::spot_vol_ladder()).unwrap();
+        let exact = ScenarioGrid::spot_vol_ladder().run(&book).unwrap();
+        let scale = exact.pnl[0].iter().flatten().fold(0.0_f64, |m, p| m.max(p.abs()));
+        assert!(error.max_error < 1e-3 * scale, "{error:?}");
+        // Errors grow with the size of the move and peak at the corners
+        assert!(large.max_error > error.max_error && large.rms_error <= large.max_error);
+        assert_eq!(large.worst.spot.abs(), 0.10);
+    }
+
+    #[test]
+    fn test_expansion_adds_over_positions() {
+        let positions = [
+            Position::new("AAA", call(), 2.0, 100.0).unwrap(),
+            Position::new("BBB", Instrument::Underlying { spot: 25.0 }, -50.0, 1.0).unwrap(),
+        ];
+        let book = TaylorExpansion::of_portfolio(&Portfolio::new(positions.to_vec()));
+        let shock = Shock::new(0.03, -0.01, 2.0 / 365.0).unwrap();
+        let sum: f64 = positions.iter().map(|p| TaylorExpansion::of_position(p).pnl(&shock)).sum();
+        assert!((book.pnl(&shock) - sum).abs() < 1e-9);
+        // The stock leg is exactly linear
+        let stock = TaylorExpansion::of_position(&positions[1]);
+        assert!((stock.pnl(&shock) - positions[1].revalue(&shock) + positions[1].market_value()).abs() < 1e-9);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_custom_grid_loads_from_json() {
+        let json = r#"{"name": "Crash", "spot_shocks": [-0.3, -0.2], "vol_shocks": [0.1, 0.2], "time_steps": [0.0]}"#;
+        let grid: ScenarioGrid = serde_json::from_str(json).unwrap();
+        grid.validate().unwrap();
+        let result = grid.run(&call()).unwrap();
+        assert_eq!(result.pnl[0].len(), 2);
+        assert!(result.worst().1 < 0.0);
+
+        let json = r#"{"name": "Skew", "shocks": [{"SkewTwist": {"slope": -0.2}}, {"Parallel": {"shift": 0.01}}]}"#;
+        let scenario: SurfaceScenario = serde_json::from_str(json).unwrap();
+        scenario.validate().unwrap();
+        assert!((scenario.vol_shift(-0.1, 1.0) - 0.03).abs() < 1e-15);
+    }
+}
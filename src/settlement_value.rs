@@ -0,0 +1,268 @@
+use crate::black_scholes::OptionType;
+use crate::error::BlackScholesError;
+use crate::math::{norm_inv_cdf, Rng};
+use crate::rates::black76;
+use crate::scenario::{Revalue, Shock};
+use crate::strategy::MarketObservation;
+use crate::validation;
+
+/// Equal-probability gap nodes used to integrate over a lognormal gap
+const GAP_NODES: usize = 400;
+
+/// Distribution of the opening settlement print's log gap to the prior close
+///
+/// AM-settled index options (SET, SOQ) stop trading at the close before
+/// expiry and settle on a value built from each component's opening trade.
+/// That print gaps away from the close on overnight news and opening
+/// auction imbalances, with a distribution of its own that a diffusion over
+/// the few hours to the open understates.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GapDistribution {
+    /// Normal log gap: print = close·e^g with g ~ N(mean, volatility²)
+    Lognormal { mean: f64, volatility: f64 },
+    /// Historical log gaps ln(print / prior close), equally weighted
+    Empirical(Vec<f64>),
+}
+
+/// Opening settlement value observed at `time`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SettlementPrint {
+    /// Expiry date in the time units of the market history
+    pub time: f64,
+    pub value: f64,
+}
+
+/// Distribution of the P&L from the last close through the settlement print
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SettlementRisk {
+    /// Probability-weighted P&L
+    pub expected: f64,
+    /// Loss exceeded with probability 1 - confidence (positive for a loss)
+    pub value_at_risk: f64,
+    /// Average loss beyond the value at risk
+    pub expected_shortfall: f64,
+    /// Largest loss over the gap nodes
+    pub worst: f64,
+}
+
+impl GapDistribution {
+    pub fn lognormal(mean: f64, volatility: f64) -> Result<Self, BlackScholesError> {
+        Ok(GapDistribution::Lognormal {
+            mean: validation::finite("Gap mean", mean)?,
+            volatility: validation::non_negative("Gap volatility", volatility)?,
+        })
+    }
+
+    /// Historical gaps from `(prior close, settlement print)` pairs
+    pub fn from_history(pairs: &[(f64, f64)]) -> Result<Self, BlackScholesError> {
+        if pairs.is_empty() {
+            return Err(BlackScholesError::invalid("Gap history needs at least one print"));
+        }
+        let gaps = pairs
+            .iter()
+            .map(|&(close, print)| {
+                let close = validation::positive("Prior close", close)?;
+                Ok((validation::positive("Settlement print", print)? / close).ln())
+            })
+            .collect::<Result<Vec<f64>, BlackScholesError>>()?;
+        Ok(GapDistribution::Empirical(gaps))
+    }
+
+    /// Log gaps with their probabilities
+    ///
+    /// Lognormal gaps use equal-probability nodes at the bin midpoints.
+    pub fn nodes(&self) -> Vec<(f64, f64)> {
+        match self {
+            GapDistribution::Lognormal { mean, volatility } => (0..GAP_NODES)
+                .map(|k| {
+                    let p = (k as f64 + 0.5) / GAP_NODES as f64;
+                    (mean + volatility * norm_inv_cdf(p), 1.0 / GAP_NODES as f64)
+                })
+                .collect(),
+            GapDistribution::Empirical(gaps) => gaps.iter().map(|&g| (g, 1.0 / gaps.len() as f64)).collect(),
+        }
+    }
+
+    /// Log gap at cumulative probability `p`
+    pub fn quantile(&self, p: f64) -> Result<f64, BlackScholesError> {
+        let p = validation::in_range("Probability", p, 0.0, 1.0, "in [0, 1]")?;
+        Ok(match self {
+            GapDistribution::Lognormal { mean, volatility } => mean + volatility * norm_inv_cdf(p),
+            GapDistribution::Empirical(gaps) => {
+                let mut sorted = gaps.clone();
+                sorted.sort_by(f64::total_cmp);
+                let rank = p * (sorted.len() - 1) as f64;
+                let (i, w) = (rank.floor() as usize, rank.fract());
+                let j = (i + 1).min(sorted.len() - 1);
+                sorted[i] + w * (sorted[j] - sorted[i])
+            }
+        })
+    }
+
+    /// Draw one settlement print given the prior close
+    pub fn sample(&self, close: f64, rng: &mut Rng) -> f64 {
+        let gap = match self {
+            GapDistribution::Lognormal { mean, volatility } => mean + volatility * rng.normal(),
+            GapDistribution::Empirical(gaps) => {
+                gaps[((rng.uniform() * gaps.len() as f64) as usize).min(gaps.len() - 1)]
+            }
+        };
+        close * gap.exp()
+    }
+
+    /// Expected payoff of a vanilla settling on the print, given the prior close
+    ///
+    /// Undiscounted: the print is hours away. Lognormal gaps have Black's
+    /// formula on the expected print; empirical gaps average the payoffs.
+    pub fn expected_payoff(&self, option_type: OptionType, strike: f64, close: f64) -> Result<f64, BlackScholesError> {
+        let close = validation::positive("Prior close", close)?;
+        let strike = validation::positive("Strike", strike)?;
+        let intrinsic = |print: f64| match option_type {
+            OptionType::Call => (print - strike).max(0.0),
+            OptionType::Put => (strike - print).max(0.0),
+        };
+        match *self {
+            GapDistribution::Lognormal { volatility, mean } if volatility > 0.0 => {
+                let forward = close * (mean + 0.5 * volatility * volatility).exp();
+                black76(forward, strike, 1.0, volatility, option_type)
+            }
+            GapDistribution::Lognormal { mean, .. } => Ok(intrinsic(close * mean.exp())),
+            GapDistribution::Empirical(ref gaps) => {
+                Ok(gaps.iter().map(|g| intrinsic(close * g.exp())).sum::<f64>() / gaps.len() as f64)
+            }
+        }
+    }
+
+    /// P&L of `target` from the last close through the print, over the gap distribution
+    ///
+    /// Each node shocks spot by e^g - 1 and advances `time_to_print`, so
+    /// options expiring at the print pay intrinsic on the print itself while
+    /// longer-dated positions are marked at the gapped open.
+    ///
+    /// # Arguments
+    /// * `target` - Position or book to revalue
+    /// * `time_to_print` - Years from the close to the settlement print
+    /// * `confidence` - Confidence level of the value at risk, e.g. 0.99
+    pub fn settlement_risk<R: Revalue + ?Sized>(
+        &self,
+        target: &R,
+        time_to_print: f64,
+        confidence: f64,
+    ) -> Result<SettlementRisk, BlackScholesError> {
+        let time = validation::non_negative("Time to print", time_to_print)?;
+        let confidence = validation::in_range("Confidence", confidence, 0.5, 1.0, "in [0.5, 1)")?;
+        let base = target.revalue(&Shock::default());
+        let mut pnl: Vec<(f64, f64)> = self
+            .nodes()
+            .iter()
+            .map(|&(gap, weight)| {
+                let shock = Shock {
+                    spot: gap.exp() - 1.0,
+                    vol: 0.0,
+                    time,
+                };
+                (target.revalue(&shock) - base, weight)
+            })
+            .collect();
+        pnl.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let expected = pnl.iter().map(|(x, w)| x * w).sum();
+        // Walk up from the worst outcome until the tail holds 1 - confidence
+        let tail = 1.0 - confidence;
+        let (mut mass, mut tail_sum, mut value_at_risk) = (0.0, 0.0, -pnl[0].0);
+        for &(x, w) in &pnl {
+            let take = w.min(tail - mass);
+            if take <= 0.0 {
+                break;
+            }
+            mass += take;
+            tail_sum += take * x;
+            value_at_risk = -x;
+        }
+        Ok(SettlementRisk {
+            expected,
+            value_at_risk,
+            expected_shortfall: if mass > 0.0 { -tail_sum / mass } else { value_at_risk },
+            worst: -pnl[0].0,
+        })
+    }
+
+    /// Simulated print for every observation after the first, gapping from the prior close
+    pub fn simulate_prints(&self, history: &[MarketObservation], rng: &mut Rng) -> Vec<SettlementPrint> {
+        history
+            .windows(2)
+            .map(|w| SettlementPrint {
+                time: w[1].time,
+                value: self.sample(w[0].spot, rng),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::portfolio::{Instrument, Position};
+
+    #[test]
+    fn test_expected_payoff_lognormal_and_empirical() {
+        let gap = GapDistribution::lognormal(-0.001, 0.012).unwrap();
+        let closed = gap.expected_payoff(OptionType::Put, 4000.0, 4000.0).unwrap();
+        // Roughly σ·close/√(2π) at the money
+        assert!((closed - 0.012 * 4000.0 / (2.0 * std::f64::consts::PI).sqrt()).abs() < 2.0);
+        let mut rng = Rng::new(3);
+        let history: Vec<(f64, f64)> = (0..200_000).map(|_| (4000.0, gap.sample(4000.0, &mut rng))).collect();
+        let empirical = GapDistribution::from_history(&history).unwrap();
+        let sampled = empirical.expected_payoff(OptionType::Put, 4000.0, 4000.0).unwrap();
+        assert!((sampled - closed).abs() < 0.01 * closed, "{sampled} vs {closed}");
+        assert!((empirical.quantile(0.5).unwrap() + 0.001).abs() < 2e-4);
+        assert!(GapDistribution::from_history(&[]).is_err());
+        assert!(GapDistribution::lognormal(0.0, -0.01).is_err());
+    }
+
+    #[test]
+    fn test_expiring_short_put_carries_gap_risk() {
+        // Short an at-the-money put that settles on tomorrow's opening print
+        let overnight = 1.0 / 365.0;
+        let model = BlackScholes::new(4000.0, 4000.0, overnight, 0.04, 0.15, 0.0).unwrap();
+        let put = Instrument::Vanilla {
+            model,
+            option_type: OptionType::Put,
+        };
+        let position = Position::new("SPX", put, -10.0, 100.0).unwrap();
+        let calm = GapDistribution::lognormal(0.0, 0.001).unwrap();
+        let jumpy = GapDistribution::lognormal(0.0, 0.02).unwrap();
+        let calm_risk = calm.settlement_risk(&position, overnight, 0.99).unwrap();
+        let jumpy_risk = jumpy.settlement_risk(&position, overnight, 0.99).unwrap();
+        assert!(jumpy_risk.value_at_risk > 5.0 * calm_risk.value_at_risk);
+        assert!(jumpy_risk.expected_shortfall >= jumpy_risk.value_at_risk);
+        assert!(jumpy_risk.worst >= jumpy_risk.expected_shortfall);
+        // The diffusion mark at the close understates the expected settlement against a jumpy open
+        let expected = jumpy.expected_payoff(OptionType::Put, 4000.0, 4000.0).unwrap();
+        assert!(expected > 2.0 * model.price(OptionType::Put));
+        let premium_kept = 1000.0 * (model.price(OptionType::Put) - expected);
+        assert!((jumpy_risk.expected - premium_kept).abs() < 0.02 * 1000.0 * expected);
+    }
+
+    #[test]
+    fn test_simulated_prints_follow_prior_close() {
+        let history: Vec<MarketObservation> = (0..5)
+            .map(|d| MarketObservation {
+                time: d as f64 / 252.0,
+                spot: 100.0 + d as f64,
+                volatility: 0.2,
+                rate: 0.03,
+                dividend_yield: 0.0,
+            })
+            .collect();
+        let flat = GapDistribution::lognormal(0.01, 0.0).unwrap();
+        let prints = flat.simulate_prints(&history, &mut Rng::new(1));
+        assert_eq!(prints.len(), 4);
+        assert!((prints[2].value - 102.0 * 0.01_f64.exp()).abs() < 1e-12);
+        assert_eq!(prints[2].time, history[3].time);
+    }
+}
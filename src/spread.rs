@@ -0,0 +1,262 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Total volatility below which a spread option is priced at intrinsic value
+const MIN_TOTAL_VOL: f64 = 1e-12;
+
+/// European option on the spread between two forwards, F1 - F2 - K
+///
+/// A call pays max(F1 - F2 - K, 0) at expiry and a put pays
+/// max(K - (F1 - F2), 0). Both forwards are lognormal with constant
+/// volatilities and correlation.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpreadOption {
+    /// Forward price of the long leg (F1)
+    pub forward1: f64,
+    /// Forward price of the short leg (F2)
+    pub forward2: f64,
+    /// Volatility of the long leg (annual)
+    pub vol1: f64,
+    /// Volatility of the short leg (annual)
+    pub vol2: f64,
+    /// Correlation between the two legs' log-returns
+    pub correlation: f64,
+    /// Spread strike (may be negative)
+    pub strike: f64,
+    /// Time to expiration in years
+    pub time_to_expiry: f64,
+    /// Risk-free rate used to discount the payoff
+    pub risk_free_rate: f64,
+}
+
+impl SpreadOption {
+    /// Create a new spread option
+    ///
+    /// # Arguments
+    /// * `forward1` - Forward of the long leg (F1)
+    /// * `forward2` - Forward of the short leg (F2)
+    /// * `vol1` - Volatility of the long leg (σ1)
+    /// * `vol2` - Volatility of the short leg (σ2)
+    /// * `correlation` - Correlation of the legs (ρ)
+    /// * `strike` - Spread strike (K); F2 + K must be positive
+    /// * `time_to_expiry` - Time to expiration in years (T)
+    /// * `risk_free_rate` - Discount rate (r)
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        forward1: f64,
+        forward2: f64,
+        vol1: f64,
+        vol2: f64,
+        correlation: f64,
+        strike: f64,
+        time_to_expiry: f64,
+        risk_free_rate: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let forward1 = validation::positive("Forward 1", forward1)?;
+        let forward2 = validation::positive("Forward 2", forward2)?;
+        let vol1 = validation::positive("Volatility 1", vol1)?;
+        let vol2 = validation::positive("Volatility 2", vol2)?;
+        let correlation = validation::in_range("Correlation", correlation, -1.0, 1.0, "in [-1, 1]")?;
+        let strike = validation::finite("Strike price", strike)?;
+        let time_to_expiry = validation::positive("Time to expiry", time_to_expiry)?;
+        let risk_free_rate = validation::finite("Risk-free rate", risk_free_rate)?;
+        if forward2 + strike <= 0.0 {
+            return Err(BlackScholesError::invalid("Spread approximations need forward2 + strike > 0"));
+        }
+
+        Ok(SpreadOption {
+            forward1,
+            forward2,
+            vol1,
+            vol2,
+            correlation,
+            strike,
+            time_to_expiry,
+            risk_free_rate,
+        })
+    }
+
+    /// Discount factor to expiry
+    fn discount(&self) -> f64 {
+        (-self.risk_free_rate * self.time_to_expiry).exp()
+    }
+
+    /// Put from call by parity: C - P = e^(-rT)·(F1 - F2 - K)
+    fn put_call_parity(&self, call: f64, option_type: OptionType) -> f64 {
+        match option_type {
+            OptionType::Call => call,
+            OptionType::Put => call - self.discount() * (self.forward1 - self.forward2 - self.strike),
+        }
+    }
+
+    /// Discounted intrinsic value, used when the spread carries no volatility
+    fn intrinsic(&self, option_type: OptionType) -> f64 {
+        let spread = self.forward1 - self.forward2 - self.strike;
+        let payoff = match option_type {
+            OptionType::Call => spread.max(0.0),
+            OptionType::Put => (-spread).max(0.0),
+        };
+        self.discount() * payoff
+    }
+
+    /// Kirk's approximation
+    ///
+    /// Treats F2 + K as a single lognormal asset and prices the resulting
+    /// exchange option with Margrabe's formula.
+    pub fn kirk(&self, option_type: OptionType) -> f64 {
+        let a = self.forward2 + self.strike;
+        let b = self.forward2 / a;
+        let variance = self.vol1.powi(2) - 2.0 * b * self.correlation * self.vol1 * self.vol2
+            + (b * self.vol2).powi(2);
+        let total_vol = (variance.max(0.0) * self.time_to_expiry).sqrt();
+        if total_vol < MIN_TOTAL_VOL {
+            return self.intrinsic(option_type);
+        }
+
+        let d1 = ((self.forward1 / a).ln() + 0.5 * total_vol * total_vol) / total_vol;
+        let d2 = d1 - total_vol;
+        let call = self.discount()
+            * (self.forward1 * BlackScholes::norm_cdf(d1) - a * BlackScholes::norm_cdf(d2));
+        self.put_call_parity(call, option_type)
+    }
+
+    /// Bjerksund-Stensland (2014) closed-form approximation
+    ///
+    /// Keeps Kirk's effective volatility but uses separate exercise
+    /// probabilities for F1, F2 and K, which removes most of Kirk's bias
+    /// away from small strikes.
+    pub fn bjerksund_stensland(&self, option_type: OptionType) -> f64 {
+        let (s1, s2, rho, t) = (self.vol1, self.vol2, self.correlation, self.time_to_expiry);
+        let a = self.forward2 + self.strike;
+        let b = self.forward2 / a;
+        let variance = s1 * s1 - 2.0 * b * rho * s1 * s2 + b * b * s2 * s2;
+        let total_vol = (variance.max(0.0) * t).sqrt();
+        if total_vol < MIN_TOTAL_VOL {
+            return self.intrinsic(option_type);
+        }
+
+        let log_ratio = (self.forward1 / a).ln();
+        let d1 = (log_ratio + (0.5 * s1 * s1 - b * rho * s1 * s2 + 0.5 * b * b * s2 * s2) * t) / total_vol;
+        let d2 = (log_ratio + (-0.5 * s1 * s1 + rho * s1 * s2 + (0.5 * b * b - b) * s2 * s2) * t) / total_vol;
+        let d3 = (log_ratio + (-0.5 * s1 * s1 + 0.5 * b * b * s2 * s2) * t) / total_vol;
+
+        let call = self.discount()
+            * (self.forward1 * BlackScholes::norm_cdf(d1)
+                - self.forward2 * BlackScholes::norm_cdf(d2)
+                - self.strike * BlackScholes::norm_cdf(d3));
+        self.put_call_parity(call, option_type)
+    }
+
+    /// Margrabe's exact exchange-option price
+    ///
+    /// Only defined for a zero strike, where the call exchanges F2 for F1 and
+    /// the put exchanges F1 for F2.
+    pub fn margrabe(&self, option_type: OptionType) -> Result<f64, BlackScholesError> {
+        if self.strike != 0.0 {
+            return Err(BlackScholesError::invalid("Margrabe's formula requires a zero strike"));
+        }
+        let variance = self.vol1.powi(2) - 2.0 * self.correlation * self.vol1 * self.vol2 + self.vol2.powi(2);
+        let total_vol = (variance.max(0.0) * self.time_to_expiry).sqrt();
+        if total_vol < MIN_TOTAL_VOL {
+            return Ok(self.intrinsic(option_type));
+        }
+
+        let d1 = ((self.forward1 / self.forward2).ln() + 0.5 * total_vol * total_vol) / total_vol;
+        let d2 = d1 - total_vol;
+        let discount = self.discount();
+        Ok(match option_type {
+            OptionType::Call => {
+                discount * (self.forward1 * BlackScholes::norm_cdf(d1) - self.forward2 * BlackScholes::norm_cdf(d2))
+            }
+            OptionType::Put => {
+                discount * (self.forward2 * BlackScholes::norm_cdf(-d2) - self.forward1 * BlackScholes::norm_cdf(-d1))
+            }
+        })
+    }
+
+    /// Best available price: Margrabe at zero strike, Bjerksund-Stensland otherwise
+    pub fn price(&self, option_type: OptionType) -> f64 {
+        match self.margrabe(option_type) {
+            Ok(price) => price,
+            Err(_) => self.bjerksund_stensland(option_type),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Rng;
+
+    fn spread(strike: f64, correlation: f64) -> SpreadOption {
+        SpreadOption::new(110.0, 100.0, 0.30, 0.25, correlation, strike, 0.5, 0.03).unwrap()
+    }
+
+    /// Monte Carlo reference price of the spread call and its standard error
+    fn mc_call(option: &SpreadOption, paths: usize) -> (f64, f64) {
+        let mut rng = Rng::new(7);
+        let t = option.time_to_expiry;
+        let rho_c = (1.0 - option.correlation * option.correlation).sqrt();
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        for _ in 0..paths {
+            let z1 = rng.normal();
+            let z2 = option.correlation * z1 + rho_c * rng.normal();
+            let f1 = option.forward1 * (option.vol1 * t.sqrt() * z1 - 0.5 * option.vol1.powi(2) * t).exp();
+            let f2 = option.forward2 * (option.vol2 * t.sqrt() * z2 - 0.5 * option.vol2.powi(2) * t).exp();
+            let payoff = (f1 - f2 - option.strike).max(0.0);
+            sum += payoff;
+            sum_sq += payoff * payoff;
+        }
+        let n = paths as f64;
+        let mean = sum / n;
+        let std_error = ((sum_sq / n - mean * mean) / (n - 1.0)).sqrt();
+        (option.discount() * mean, option.discount() * std_error)
+    }
+
+    #[test]
+    fn test_approximations_reduce_to_margrabe() {
+        for rho in [-0.5, 0.0, 0.7] {
+            let exchange = spread(0.0, rho);
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let exact = exchange.margrabe(option_type).unwrap();
+                assert!((exchange.kirk(option_type) - exact).abs() < 1e-12);
+                assert!((exchange.bjerksund_stensland(option_type) - exact).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_spread_put_call_parity() {
+        let option = spread(5.0, 0.4);
+        let forward_spread = option.discount() * (option.forward1 - option.forward2 - option.strike);
+        for price in [
+            |o: &SpreadOption, t| o.kirk(t),
+            |o: &SpreadOption, t| o.bjerksund_stensland(t),
+        ] {
+            let parity = price(&option, OptionType::Call) - price(&option, OptionType::Put);
+            assert!((parity - forward_spread).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_against_monte_carlo() {
+        for (strike, rho) in [(5.0, 0.5), (15.0, 0.8), (-5.0, -0.3)] {
+            let option = spread(strike, rho);
+            let (reference, std_error) = mc_call(&option, 400_000);
+            let bs = option.bjerksund_stensland(OptionType::Call);
+            let kirk = option.kirk(OptionType::Call);
+            assert!((bs - reference).abs() < 4.0 * std_error, "BS14 {} vs MC {}", bs, reference);
+            assert!((kirk - reference).abs() < 4.0 * std_error + 0.1, "Kirk {} vs MC {}", kirk, reference);
+        }
+    }
+
+    #[test]
+    fn test_invalid_spread_inputs() {
+        assert!(SpreadOption::new(110.0, 100.0, 0.3, 0.25, 1.5, 5.0, 0.5, 0.03).is_err());
+        assert!(SpreadOption::new(110.0, 100.0, 0.3, 0.25, 0.5, -100.0, 0.5, 0.03).is_err());
+        assert!(SpreadOption::new(110.0, f64::NAN, 0.3, 0.25, 0.5, 5.0, 0.5, 0.03).is_err());
+        assert!(spread(5.0, 0.5).margrabe(OptionType::Call).is_err());
+    }
+}
@@ -0,0 +1,1207 @@
+use crate::black_scholes::{BlackScholes, CashGreeks, Greeks, Leverage, OptionType};
+use crate::cross_greeks::CrossGreeks;
+use crate::error::BlackScholesError;
+use crate::market::MarketContext;
+use crate::pde::intrinsic;
+use crate::validation;
+
+/// Expiry difference below which two legs count as the same expiry
+const EXPIRY_EPSILON: f64 = 1e-9;
+
+/// Terminal P&L slope treated as zero when deciding whether risk is unbounded
+const SLOPE_EPSILON: f64 = 1e-12;
+
+/// Naked short option requirement as a fraction of spot (before OTM reduction)
+pub(crate) const NAKED_BASE_RATE: f64 = 0.20;
+
+/// Minimum naked requirement as a fraction of spot (calls) or strike (puts)
+const NAKED_MIN_RATE: f64 = 0.10;
+
+/// Initial margin on short stock as a fraction of its value
+pub(crate) const SHORT_STOCK_RATE: f64 = 0.50;
+
+/// Instrument held in one leg of a strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LegKind {
+    /// European option with its own strike and expiry
+    Option {
+        option_type: OptionType,
+        strike: f64,
+        /// Expiry in years from today
+        expiry: f64,
+    },
+    /// Shares of the underlying
+    Underlying,
+}
+
+/// One leg of a strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Leg {
+    pub kind: LegKind,
+    /// Signed quantity: positive long, negative short
+    pub quantity: f64,
+    /// Price paid (received when short) per unit at entry
+    pub entry_price: f64,
+    /// Leg-specific implied volatility; falls back to the market volatility
+    pub volatility: Option<f64>,
+}
+
+impl Leg {
+    /// Option leg with zero entry price (see `Strategy::mark_entry`)
+    pub fn option(option_type: OptionType, strike: f64, expiry: f64, quantity: f64) -> Result<Self, BlackScholesError> {
+        let strike = validation::positive("Strike price", strike)?;
+        let expiry = validation::positive("Time to expiry", expiry)?;
+        let quantity = validation::finite("Quantity", quantity)?;
+        Ok(Leg {
+            kind: LegKind::Option {
+                option_type,
+                strike,
+                expiry,
+            },
+            quantity,
+            entry_price: 0.0,
+            volatility: None,
+        })
+    }
+
+    /// Position in the underlying with zero entry price
+    pub fn underlying(quantity: f64) -> Result<Self, BlackScholesError> {
+        let quantity = validation::finite("Quantity", quantity)?;
+        Ok(Leg {
+            kind: LegKind::Underlying,
+            quantity,
+            entry_price: 0.0,
+            volatility: None,
+        })
+    }
+
+    /// Set the per-unit entry price
+    pub fn with_entry_price(mut self, entry_price: f64) -> Result<Self, BlackScholesError> {
+        self.entry_price = validation::finite("Entry price", entry_price)?;
+        Ok(self)
+    }
+
+    /// Price this leg with its own implied volatility
+    pub fn with_volatility(mut self, volatility: f64) -> Result<Self, BlackScholesError> {
+        self.volatility = Some(validation::positive("Volatility", volatility)?);
+        Ok(self)
+    }
+
+    /// Expiry of an option leg
+    pub fn expiry(&self) -> Option<f64> {
+        match self.kind {
+            LegKind::Option { expiry, .. } => Some(expiry),
+            LegKind::Underlying => None,
+        }
+    }
+
+    /// Leg model at `horizon` years from today with the given spot and vol shift
+    ///
+    /// Returns `None` for the underlying and for options already expired.
+    fn model_at(&self, market: &BlackScholes, horizon: f64, spot: f64, vol_shift: f64) -> Option<(OptionType, BlackScholes)> {
+        let LegKind::Option {
+            option_type,
+            strike,
+            expiry,
+        } = self.kind
+        else {
+            return None;
+        };
+        let remaining = expiry - horizon;
+        if remaining <= EXPIRY_EPSILON {
+            return None;
+        }
+        let mut m = market.aged(remaining);
+        m.spot_price = spot;
+        m.strike_price = strike;
+        m.volatility = (self.volatility.unwrap_or(market.volatility) + vol_shift).max(1e-8);
+        Some((option_type, m))
+    }
+
+    /// Per-unit value at `horizon` (intrinsic once expired)
+    fn value_at(&self, market: &BlackScholes, horizon: f64, spot: f64, vol_shift: f64) -> f64 {
+        match (self.kind, self.model_at(market, horizon, spot, vol_shift)) {
+            (LegKind::Underlying, _) => spot,
+            (_, Some((option_type, m))) => m.price(option_type),
+            (
+                LegKind::Option {
+                    option_type, strike, ..
+                },
+                None,
+            ) => intrinsic(option_type, spot, strike),
+        }
+    }
+}
+
+/// Grid of strategy P&L over spot and a parallel implied-volatility shift at one date
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PnlSurface {
+    /// Evaluation date in years from today
+    pub horizon: f64,
+    pub spots: Vec<f64>,
+    /// Parallel shifts applied to every leg's volatility (0.01 = one vol point)
+    pub vol_shifts: Vec<f64>,
+    /// `pnl[i][j]` is the P&L at `vol_shifts[i]` and `spots[j]`
+    pub pnl: Vec<Vec<f64>>,
+}
+
+/// Largest profit or loss of a strategy at expiry
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Extremum {
+    Bounded(f64),
+    /// Grows without limit as the underlying rises
+    Unbounded,
+}
+
+/// Expiry tail-risk profile of a single-expiry strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TailRisk {
+    /// Largest P&L at expiry
+    pub max_profit: Extremum,
+    /// Largest loss at expiry as a positive amount (zero if the strategy cannot lose)
+    pub max_loss: Extremum,
+    /// Size of the move in standard deviations of ln(S_T)
+    pub sigmas: f64,
+    /// Spot after a move of `sigmas` standard deviations down
+    pub spot_down: f64,
+    /// P&L at expiry after the down move
+    pub pnl_down: f64,
+    /// Spot after a move of `sigmas` standard deviations up
+    pub spot_up: f64,
+    /// P&L at expiry after the up move
+    pub pnl_up: f64,
+}
+
+/// Market state on one historical date of a held position
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketObservation {
+    /// Date in years since the position was opened
+    pub time: f64,
+    pub spot: f64,
+    /// Implied volatility applied to legs without their own
+    pub volatility: f64,
+    pub rate: f64,
+    pub dividend_yield: f64,
+}
+
+/// Value and risk of a held position on one historical date
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionSnapshot {
+    /// Date in years since the position was opened
+    pub time: f64,
+    pub spot: f64,
+    pub value: f64,
+    /// Value less entry cost
+    pub pnl: f64,
+    /// Cumulative P&L when the position is delta-hedged at each prior date's delta
+    pub hedged_pnl: f64,
+    pub greeks: Greeks,
+}
+
+/// Options strategy composed of legs, possibly across several expiries
+///
+/// Market parameters (spot, rate, dividend yield and default volatility)
+/// come from a `BlackScholes` model whose strike and expiry are ignored;
+/// each option leg supplies its own.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Strategy {
+    pub name: String,
+    pub legs: Vec<Leg>,
+}
+
+impl Strategy {
+    pub fn new(name: &str, legs: Vec<Leg>) -> Result<Self, BlackScholesError> {
+        if legs.is_empty() {
+            return Err(BlackScholesError::invalid("Strategy needs at least one leg"));
+        }
+        Ok(Strategy {
+            name: name.to_string(),
+            legs,
+        })
+    }
+
+    /// Vertical spread: long one strike, short another, same type and expiry
+    ///
+    /// A bull call spread buys the lower strike; a bear put spread buys the upper.
+    pub fn vertical(option_type: OptionType, long_strike: f64, short_strike: f64, expiry: f64) -> Result<Self, BlackScholesError> {
+        if long_strike == short_strike {
+            return Err(BlackScholesError::invalid("Vertical spread needs two different strikes"));
+        }
+        Strategy::new(
+            "Vertical spread",
+            vec![
+                Leg::option(option_type, long_strike, expiry, 1.0)?,
+                Leg::option(option_type, short_strike, expiry, -1.0)?,
+            ],
+        )
+    }
+
+    /// Long straddle: a call and a put at one strike
+    pub fn straddle(strike: f64, expiry: f64) -> Result<Self, BlackScholesError> {
+        Strategy::new(
+            "Straddle",
+            vec![
+                Leg::option(OptionType::Put, strike, expiry, 1.0)?,
+                Leg::option(OptionType::Call, strike, expiry, 1.0)?,
+            ],
+        )
+    }
+
+    /// Long strangle: an out-of-the-money put and call
+    pub fn strangle(put_strike: f64, call_strike: f64, expiry: f64) -> Result<Self, BlackScholesError> {
+        if put_strike >= call_strike {
+            return Err(BlackScholesError::invalid("Put strike must be below call strike"));
+        }
+        Strategy::new(
+            "Strangle",
+            vec![
+                Leg::option(OptionType::Put, put_strike, expiry, 1.0)?,
+                Leg::option(OptionType::Call, call_strike, expiry, 1.0)?,
+            ],
+        )
+    }
+
+    /// Short iron condor: short put and call spreads around the current price
+    ///
+    /// # Arguments
+    /// * `long_put`, `short_put` - Strikes of the put spread (long below short)
+    /// * `short_call`, `long_call` - Strikes of the call spread (short below long)
+    /// * `expiry` - Common expiry
+    pub fn iron_condor(
+        long_put: f64,
+        short_put: f64,
+        short_call: f64,
+        long_call: f64,
+        expiry: f64,
+    ) -> Result<Self, BlackScholesError> {
+        if !(long_put < short_put && short_put <= short_call && short_call < long_call) {
+            return Err(BlackScholesError::invalid("Iron condor strikes must be increasing"));
+        }
+        Strategy::new(
+            "Iron condor",
+            vec![
+                Leg::option(OptionType::Put, long_put, expiry, 1.0)?,
+                Leg::option(OptionType::Put, short_put, expiry, -1.0)?,
+                Leg::option(OptionType::Call, short_call, expiry, -1.0)?,
+                Leg::option(OptionType::Call, long_call, expiry, 1.0)?,
+            ],
+        )
+    }
+
+    /// Collar: long the underlying, long a protective put, short a covered call
+    pub fn collar(put_strike: f64, call_strike: f64, expiry: f64) -> Result<Self, BlackScholesError> {
+        if put_strike >= call_strike {
+            return Err(BlackScholesError::invalid("Put strike must be below call strike"));
+        }
+        Strategy::new(
+            "Collar",
+            vec![
+                Leg::underlying(1.0)?,
+                Leg::option(OptionType::Put, put_strike, expiry, 1.0)?,
+                Leg::option(OptionType::Call, call_strike, expiry, -1.0)?,
+            ],
+        )
+    }
+
+    /// Long butterfly: long the wings, short two at the body
+    pub fn butterfly(option_type: OptionType, lower: f64, middle: f64, upper: f64, expiry: f64) -> Result<Self, BlackScholesError> {
+        if !(lower < middle && middle < upper) {
+            return Err(BlackScholesError::invalid("Butterfly strikes must be increasing"));
+        }
+        Strategy::new(
+            "Butterfly",
+            vec![
+                Leg::option(option_type, lower, expiry, 1.0)?,
+                Leg::option(option_type, middle, expiry, -2.0)?,
+                Leg::option(option_type, upper, expiry, 1.0)?,
+            ],
+        )
+    }
+
+    /// Multiply every leg's quantity, e.g. by -1 to sell the structure
+    pub fn scaled(mut self, factor: f64) -> Result<Self, BlackScholesError> {
+        let factor = validation::finite("Scale factor", factor)?;
+        if factor == 0.0 {
+            return Err(BlackScholesError::invalid("Scale factor must be non-zero"));
+        }
+        for leg in &mut self.legs {
+            leg.quantity *= factor;
+        }
+        Ok(self)
+    }
+
+    /// Calendar spread: short the near expiry, long the far expiry at one strike
+    pub fn calendar(option_type: OptionType, strike: f64, near: f64, far: f64) -> Result<Self, BlackScholesError> {
+        let mut calendar = Strategy::diagonal(option_type, strike, near, strike, far)?;
+        calendar.name = "Calendar".to_string();
+        Ok(calendar)
+    }
+
+    /// Diagonal spread: short the near expiry, long the far expiry at different strikes
+    pub fn diagonal(
+        option_type: OptionType,
+        near_strike: f64,
+        near: f64,
+        far_strike: f64,
+        far: f64,
+    ) -> Result<Self, BlackScholesError> {
+        if far <= near {
+            return Err(BlackScholesError::invalid("Far expiry must be after near expiry"));
+        }
+        Strategy::new(
+            "Diagonal",
+            vec![
+                Leg::option(option_type, near_strike, near, -1.0)?,
+                Leg::option(option_type, far_strike, far, 1.0)?,
+            ],
+        )
+    }
+
+    /// Double diagonal: short near strangle, long a wider far strangle
+    ///
+    /// # Arguments
+    /// * `near_put`, `near_call` - Strikes of the short near-dated strangle
+    /// * `far_put`, `far_call` - Strikes of the long far-dated strangle
+    /// * `near`, `far` - The two expiries
+    pub fn double_diagonal(
+        near_put: f64,
+        near_call: f64,
+        far_put: f64,
+        far_call: f64,
+        near: f64,
+        far: f64,
+    ) -> Result<Self, BlackScholesError> {
+        if far <= near {
+            return Err(BlackScholesError::invalid("Far expiry must be after near expiry"));
+        }
+        if near_put > near_call || far_put > far_call {
+            return Err(BlackScholesError::invalid("Put strikes must not exceed call strikes"));
+        }
+        Strategy::new(
+            "Double diagonal",
+            vec![
+                Leg::option(OptionType::Put, near_put, near, -1.0)?,
+                Leg::option(OptionType::Call, near_call, near, -1.0)?,
+                Leg::option(OptionType::Put, far_put, far, 1.0)?,
+                Leg::option(OptionType::Call, far_call, far, 1.0)?,
+            ],
+        )
+    }
+
+    /// Ratio spread: long `long_count` options, short a larger `short_count` further out of the money
+    ///
+    /// A 1x2 call ratio spread collects premium but loses without limit on a
+    /// large rally; the put version loses heavily on a crash.
+    pub fn ratio_spread(
+        option_type: OptionType,
+        long_strike: f64,
+        short_strike: f64,
+        expiry: f64,
+        long_count: f64,
+        short_count: f64,
+    ) -> Result<Self, BlackScholesError> {
+        if short_count <= long_count {
+            return Err(BlackScholesError::invalid("Ratio spread sells more options than it buys"));
+        }
+        Strategy::unbalanced("Ratio spread", option_type, long_strike, short_strike, expiry, long_count, short_count)
+    }
+
+    /// Backspread: short `short_count` options, long a larger `long_count` further out of the money
+    ///
+    /// The mirror image of a ratio spread: risk is limited but the worst
+    /// outcome sits at the long strike, not at the extremes.
+    pub fn backspread(
+        option_type: OptionType,
+        short_strike: f64,
+        long_strike: f64,
+        expiry: f64,
+        short_count: f64,
+        long_count: f64,
+    ) -> Result<Self, BlackScholesError> {
+        if long_count <= short_count {
+            return Err(BlackScholesError::invalid("Backspread buys more options than it sells"));
+        }
+        Strategy::unbalanced("Backspread", option_type, long_strike, short_strike, expiry, long_count, short_count)
+    }
+
+    /// Long and short legs of one type and expiry in different quantities
+    fn unbalanced(
+        name: &str,
+        option_type: OptionType,
+        long_strike: f64,
+        short_strike: f64,
+        expiry: f64,
+        long_count: f64,
+        short_count: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let long_count = validation::positive("Long count", long_count)?;
+        let short_count = validation::positive("Short count", short_count)?;
+        Strategy::new(
+            name,
+            vec![
+                Leg::option(option_type, long_strike, expiry, long_count)?,
+                Leg::option(option_type, short_strike, expiry, -short_count)?,
+            ],
+        )
+    }
+
+    /// Set every leg's entry price to its current model value
+    pub fn mark_entry(&mut self, market: &BlackScholes) {
+        for leg in &mut self.legs {
+            leg.entry_price = leg.value_at(market, 0.0, market.spot_price, 0.0);
+        }
+    }
+
+    /// Net premium paid at entry (negative for a credit)
+    pub fn entry_cost(&self) -> f64 {
+        self.legs.iter().map(|l| l.quantity * l.entry_price).sum()
+    }
+
+    /// Distinct option expiries, ascending
+    pub fn expiries(&self) -> Vec<f64> {
+        let mut expiries: Vec<f64> = self.legs.iter().filter_map(Leg::expiry).collect();
+        expiries.sort_by(|a, b| a.total_cmp(b));
+        expiries.dedup_by(|a, b| (*a - *b).abs() < EXPIRY_EPSILON);
+        expiries
+    }
+
+    /// Earliest option expiry, if the strategy holds options
+    pub fn front_expiry(&self) -> Option<f64> {
+        self.expiries().first().copied()
+    }
+
+    /// Current model value of the strategy
+    pub fn value(&self, market: &BlackScholes) -> f64 {
+        self.value_at(market, 0.0, market.spot_price, 0.0)
+    }
+
+    /// Current value of the strategy against a shared market snapshot
+    ///
+    /// Each option leg takes its volatility from the context's surface at its
+    /// own strike and expiry unless the leg overrides it.
+    pub fn value_in(&self, market: &MarketContext) -> Result<f64, BlackScholesError> {
+        let mut total = 0.0;
+        for leg in &self.legs {
+            let unit = match leg.kind {
+                LegKind::Underlying => market.spot,
+                LegKind::Option {
+                    option_type,
+                    strike,
+                    expiry,
+                } => {
+                    let mut model = market.model(strike, expiry)?;
+                    if let Some(vol) = leg.volatility {
+                        model.volatility = vol;
+                    }
+                    model.price(option_type)
+                }
+            };
+            total += leg.quantity * unit;
+        }
+        Ok(total)
+    }
+
+    /// Strategy value at a future date
+    ///
+    /// Legs that have expired by `horizon` are worth intrinsic value; the
+    /// rest are repriced with the model over their remaining life.
+    ///
+    /// # Arguments
+    /// * `market` - Market parameters (rate, dividend yield, default vol)
+    /// * `horizon` - Evaluation date in years from today
+    /// * `spot` - Underlying price at the evaluation date
+    /// * `vol_shift` - Parallel shift added to every leg's volatility
+    pub fn value_at(&self, market: &BlackScholes, horizon: f64, spot: f64, vol_shift: f64) -> f64 {
+        self.legs
+            .iter()
+            .map(|l| l.quantity * l.value_at(market, horizon, spot, vol_shift))
+            .sum()
+    }
+
+    /// P&L versus entry cost at a future date (financing ignored)
+    pub fn pnl_at(&self, market: &BlackScholes, horizon: f64, spot: f64, vol_shift: f64) -> f64 {
+        self.value_at(market, horizon, spot, vol_shift) - self.entry_cost()
+    }
+
+    /// Net Greeks today
+    pub fn greeks(&self, market: &BlackScholes) -> Greeks {
+        self.greeks_at(market, 0.0, market.spot_price)
+    }
+
+    /// Net Greeks today in currency per `multiplier` units of each leg's quantity
+    pub fn cash_greeks(&self, market: &BlackScholes, multiplier: f64) -> CashGreeks {
+        self.greeks(market).to_cash(market.spot_price, multiplier)
+    }
+
+    /// Net Greeks of the surviving legs at a future date and spot
+    pub fn greeks_at(&self, market: &BlackScholes, horizon: f64, spot: f64) -> Greeks {
+        let mut net = Greeks {
+            delta: 0.0,
+            gamma: 0.0,
+            vega: 0.0,
+            theta: 0.0,
+            rho: 0.0,
+        };
+        for leg in &self.legs {
+            match (leg.kind, leg.model_at(market, horizon, spot, 0.0)) {
+                (LegKind::Underlying, _) => net.delta += leg.quantity,
+                (_, Some((option_type, m))) => {
+                    let g = m.greeks(option_type);
+                    net.delta += leg.quantity * g.delta;
+                    net.gamma += leg.quantity * g.gamma;
+                    net.vega += leg.quantity * g.vega;
+                    net.theta += leg.quantity * g.theta;
+                    net.rho += leg.quantity * g.rho;
+                }
+                (_, None) => {}
+            }
+        }
+        net
+    }
+
+    /// Net price gradient and Hessian today over spot, vol, time, rate and dividend
+    pub fn cross_greeks(&self, market: &BlackScholes) -> CrossGreeks {
+        self.legs
+            .iter()
+            .map(|leg| match (leg.kind, leg.model_at(market, 0.0, market.spot_price, 0.0)) {
+                (LegKind::Underlying, _) => CrossGreeks::underlying(market.spot_price).scaled(leg.quantity),
+                (_, Some((option_type, m))) => m.cross_greeks(option_type).scaled(leg.quantity),
+                (_, None) => CrossGreeks::default(),
+            })
+            .sum()
+    }
+
+    /// Elasticity, delta-equivalent exposure and gearing of the whole strategy
+    ///
+    /// Notional counts every leg's quantity at spot, long or short. Fails for
+    /// strategies whose current value is zero.
+    pub fn leverage(&self, market: &BlackScholes) -> Result<Leverage, BlackScholesError> {
+        let notional = self.legs.iter().map(|l| l.quantity.abs()).sum::<f64>() * market.spot_price;
+        Leverage::from_position(self.value(market), self.greeks(market).delta, notional, market.spot_price)
+    }
+
+    /// Realized volatility the delta-hedged strategy needs to break even over `horizon`
+    ///
+    /// Uses the net gamma and theta of the legs alive on each day; a horizon
+    /// of zero gives the instantaneous figure.
+    pub fn breakeven_volatility(&self, market: &BlackScholes, horizon: f64) -> Result<f64, BlackScholesError> {
+        let spot = market.spot_price;
+        let days_per_year = market.time_scale.days_per_year();
+        if horizon == 0.0 {
+            return self.greeks(market).breakeven_volatility(spot, days_per_year);
+        }
+        Greeks::breakeven_volatility_over(spot, days_per_year, horizon, |t| self.greeks_at(market, t, spot))
+    }
+
+    /// Daily value and Greeks of the position over a market history
+    ///
+    /// Expiries shrink with each observation's `time`; legs expired by then are
+    /// worth intrinsic value and drop out of the Greeks. Legs with their own
+    /// volatility keep it rather than the observed one. Financing of the
+    /// premium and the hedge is ignored.
+    ///
+    /// # Arguments
+    /// * `history` - Observations in chronological order
+    pub fn rolling_greeks(&self, history: &[MarketObservation]) -> Result<Vec<PositionSnapshot>, BlackScholesError> {
+        let mut snapshots: Vec<PositionSnapshot> = Vec::with_capacity(history.len());
+        for obs in history {
+            let time = validation::non_negative("Observation time", obs.time)?;
+            if snapshots.last().is_some_and(|prev| time < prev.time) {
+                return Err(BlackScholesError::invalid("Observations must be in chronological order"));
+            }
+            // Strike and expiry of the market model are ignored; each leg supplies its own
+            let market = BlackScholes::new(obs.spot, obs.spot, 1.0, obs.rate, obs.volatility, obs.dividend_yield)?;
+            let value = self.value_at(&market, time, obs.spot, 0.0);
+            let hedged_pnl = match snapshots.last() {
+                Some(prev) => prev.hedged_pnl + (value - prev.value) - prev.greeks.delta * (obs.spot - prev.spot),
+                None => value - self.entry_cost(),
+            };
+            snapshots.push(PositionSnapshot {
+                time,
+                spot: obs.spot,
+                value,
+                pnl: value - self.entry_cost(),
+                hedged_pnl,
+                greeks: self.greeks_at(&market, time, obs.spot),
+            });
+        }
+        Ok(snapshots)
+    }
+
+    /// P&L over spot and parallel vol shifts at a given date
+    pub fn pnl_surface(&self, market: &BlackScholes, horizon: f64, spots: &[f64], vol_shifts: &[f64]) -> PnlSurface {
+        let pnl = vol_shifts
+            .iter()
+            .map(|&shift| spots.iter().map(|&s| self.pnl_at(market, horizon, s, shift)).collect())
+            .collect();
+        PnlSurface {
+            horizon,
+            spots: spots.to_vec(),
+            vol_shifts: vol_shifts.to_vec(),
+            pnl,
+        }
+    }
+
+    /// P&L surface at the front expiry, where multi-expiry risk is usually judged
+    pub fn front_expiry_surface(
+        &self,
+        market: &BlackScholes,
+        spots: &[f64],
+        vol_shifts: &[f64],
+    ) -> Result<PnlSurface, BlackScholesError> {
+        let front = self
+            .front_expiry()
+            .ok_or_else(|| BlackScholesError::invalid("Strategy has no option legs"))?;
+        Ok(self.pnl_surface(market, front, spots, vol_shifts))
+    }
+
+    /// The single option expiry of the strategy, or an error for multi-expiry structures
+    fn single_expiry(&self) -> Result<f64, BlackScholesError> {
+        match self.expiries().as_slice() {
+            [expiry] => Ok(*expiry),
+            [] => Err(BlackScholesError::invalid("Strategy has no option legs")),
+            _ => Err(BlackScholesError::invalid(
+                "Expiry tail metrics need a single expiry; use pnl_surface for multi-expiry risk",
+            )),
+        }
+    }
+
+    /// Extremes of the piecewise-linear expiry P&L
+    ///
+    /// The P&L is linear between strikes, so its extremes on [0, ∞) lie at
+    /// zero, at a strike, or at infinity when the terminal slope is non-zero.
+    fn expiry_extremes(&self) -> (Extremum, Extremum) {
+        let pnl = |spot: f64| self.value_at_expiry(spot) - self.entry_cost();
+        let values: Vec<f64> = self.expiry_nodes().iter().map(|&s| pnl(s)).collect();
+        let slope: f64 = self
+            .legs
+            .iter()
+            .map(|l| match l.kind {
+                LegKind::Option {
+                    option_type: OptionType::Call,
+                    ..
+                }
+                | LegKind::Underlying => l.quantity,
+                LegKind::Option { .. } => 0.0,
+            })
+            .sum();
+
+        let highest = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let lowest = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max_profit = if slope > SLOPE_EPSILON {
+            Extremum::Unbounded
+        } else {
+            Extremum::Bounded(highest)
+        };
+        let max_loss = if slope < -SLOPE_EPSILON {
+            Extremum::Unbounded
+        } else {
+            Extremum::Bounded((-lowest).max(0.0))
+        };
+        (max_profit, max_loss)
+    }
+
+    /// Zero and the distinct strikes, ascending: the kinks of the expiry P&L
+    fn expiry_nodes(&self) -> Vec<f64> {
+        let mut nodes = vec![0.0];
+        nodes.extend(self.legs.iter().filter_map(|l| match l.kind {
+            LegKind::Option { strike, .. } => Some(strike),
+            LegKind::Underlying => None,
+        }));
+        nodes.sort_by(|a, b| a.total_cmp(b));
+        nodes.dedup();
+        nodes
+    }
+
+    /// Value at expiry, when every leg is worth intrinsic
+    fn value_at_expiry(&self, spot: f64) -> f64 {
+        self.legs
+            .iter()
+            .map(|l| {
+                let value = match l.kind {
+                    LegKind::Underlying => spot,
+                    LegKind::Option {
+                        option_type, strike, ..
+                    } => intrinsic(option_type, spot, strike),
+                };
+                l.quantity * value
+            })
+            .sum()
+    }
+
+    /// Value of the strategy at expiry for each spot
+    pub fn payoff_at_expiry(&self, spots: &[f64]) -> Result<Vec<f64>, BlackScholesError> {
+        self.single_expiry()?;
+        Ok(spots.iter().map(|&s| self.value_at_expiry(s)).collect())
+    }
+
+    /// Expiry P&L (payoff less entry cost) for each spot
+    pub fn pnl_at_expiry(&self, spots: &[f64]) -> Result<Vec<f64>, BlackScholesError> {
+        let cost = self.entry_cost();
+        Ok(self.payoff_at_expiry(spots)?.into_iter().map(|v| v - cost).collect())
+    }
+
+    /// Largest profit and largest loss at expiry, possibly unbounded
+    pub fn max_profit_loss(&self) -> Result<(Extremum, Extremum), BlackScholesError> {
+        self.single_expiry()?;
+        Ok(self.expiry_extremes())
+    }
+
+    /// Spots at which the expiry P&L crosses zero, ascending
+    ///
+    /// The P&L is linear between strikes, so each crossing is found exactly
+    /// by interpolating between nodes or extending the terminal slope.
+    pub fn breakevens(&self) -> Result<Vec<f64>, BlackScholesError> {
+        self.single_expiry()?;
+        let pnl = |spot: f64| self.value_at_expiry(spot) - self.entry_cost();
+        let nodes = self.expiry_nodes();
+
+        let mut roots: Vec<f64> = Vec::new();
+        let mut push = |root: f64| {
+            if roots.last().is_none_or(|&last| root - last > EXPIRY_EPSILON) {
+                roots.push(root);
+            }
+        };
+        for pair in nodes.windows(2) {
+            let (a, b) = (pnl(pair[0]), pnl(pair[1]));
+            if a == 0.0 && b == 0.0 {
+                continue;
+            }
+            if a == 0.0 {
+                push(pair[0]);
+            } else if a.signum() != b.signum() && b != 0.0 {
+                push(pair[0] + (pair[1] - pair[0]) * a / (a - b));
+            }
+        }
+        let last = *nodes.last().unwrap_or(&0.0);
+        let end = pnl(last);
+        let slope = pnl(last + 1.0) - end;
+        if end == 0.0 && slope != 0.0 {
+            push(last);
+        } else if end * slope < 0.0 {
+            push(last - end / slope);
+        }
+        Ok(roots)
+    }
+
+    /// Expiry tail risk: max profit/loss (possibly unbounded) and P&L after ±N sigma moves
+    ///
+    /// Sigma moves are lognormal: S·exp(±N·σ√T) with the market volatility.
+    ///
+    /// # Arguments
+    /// * `market` - Market parameters (spot and volatility)
+    /// * `sigmas` - Move size in standard deviations
+    pub fn tail_risk(&self, market: &BlackScholes, sigmas: f64) -> Result<TailRisk, BlackScholesError> {
+        let sigmas = validation::non_negative("Sigmas", sigmas)?;
+        let expiry = self.single_expiry()?;
+        let (max_profit, max_loss) = self.expiry_extremes();
+        let move_size = sigmas * market.calendar_volatility() * expiry.sqrt();
+        let spot_down = market.spot_price * (-move_size).exp();
+        let spot_up = market.spot_price * move_size.exp();
+        Ok(TailRisk {
+            max_profit,
+            max_loss,
+            sigmas,
+            spot_down,
+            pnl_down: self.value_at_expiry(spot_down) - self.entry_cost(),
+            spot_up,
+            pnl_up: self.value_at_expiry(spot_up) - self.entry_cost(),
+        })
+    }
+
+    /// Simplified exchange-style initial margin estimate
+    ///
+    /// Short options are first covered by long options of the same type
+    /// (and short calls also by long stock). Covered structures are charged
+    /// their maximum loss at expiry; each uncovered short is charged the
+    /// naked requirement premium + max(20%·S - OTM amount, 10%·S for calls or
+    /// 10%·K for puts); short stock is charged 50% of its value. The most
+    /// in-the-money shorts are treated as the uncovered ones.
+    pub fn margin(&self, market: &BlackScholes) -> Result<f64, BlackScholesError> {
+        self.single_expiry()?;
+        let spot = market.spot_price;
+        let mut covered = self.clone();
+        let mut requirement = 0.0;
+
+        let long_stock: f64 = self
+            .legs
+            .iter()
+            .filter(|l| l.kind == LegKind::Underlying)
+            .map(|l| l.quantity)
+            .sum();
+        if long_stock < 0.0 {
+            requirement += SHORT_STOCK_RATE * -long_stock * spot;
+            covered.legs.retain(|l| l.kind != LegKind::Underlying);
+        }
+
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let of_type = |l: &&Leg| matches!(l.kind, LegKind::Option { option_type: t, .. } if t == option_type);
+            let longs: f64 = self.legs.iter().filter(of_type).map(|l| l.quantity.max(0.0)).sum();
+            let shorts: f64 = self.legs.iter().filter(of_type).map(|l| (-l.quantity).max(0.0)).sum();
+            let cover = match option_type {
+                OptionType::Call => longs + long_stock.max(0.0),
+                OptionType::Put => longs,
+            };
+            let mut uncovered = (shorts - cover).max(0.0);
+
+            // Most in-the-money shorts first: lowest call strikes, highest put strikes
+            let mut order: Vec<usize> = (0..covered.legs.len())
+                .filter(|&i| of_type(&&covered.legs[i]) && covered.legs[i].quantity < 0.0)
+                .collect();
+            order.sort_by(|&a, &b| {
+                let (ka, kb) = (leg_strike(&covered.legs[a]), leg_strike(&covered.legs[b]));
+                match option_type {
+                    OptionType::Call => ka.total_cmp(&kb),
+                    OptionType::Put => kb.total_cmp(&ka),
+                }
+            });
+            for i in order {
+                if uncovered <= 0.0 {
+                    break;
+                }
+                let leg = &mut covered.legs[i];
+                let naked = uncovered.min(-leg.quantity);
+                let strike = leg_strike(leg);
+                let premium = leg.value_at(market, 0.0, spot, 0.0);
+                let (otm, floor) = match option_type {
+                    OptionType::Call => ((strike - spot).max(0.0), NAKED_MIN_RATE * spot),
+                    OptionType::Put => ((spot - strike).max(0.0), NAKED_MIN_RATE * strike),
+                };
+                requirement += naked * (premium + (NAKED_BASE_RATE * spot - otm).max(floor));
+                leg.quantity += naked;
+                uncovered -= naked;
+            }
+        }
+
+        covered.legs.retain(|l| l.quantity != 0.0);
+        if !covered.legs.is_empty() {
+            if let (_, Extremum::Bounded(loss)) = covered.expiry_extremes() {
+                requirement += loss;
+            }
+        }
+        Ok(requirement)
+    }
+}
+
+/// Strike of an option leg (zero for the underlying)
+fn leg_strike(leg: &Leg) -> f64 {
+    match leg.kind {
+        LegKind::Option { strike, .. } => strike,
+        LegKind::Underlying => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market() -> BlackScholes {
+        BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.2, 0.0).unwrap()
+    }
+
+    #[test]
+    fn test_calendar_is_debit_and_peaks_at_strike() {
+        let market = market();
+        let mut calendar = Strategy::calendar(OptionType::Call, 100.0, 0.25, 0.5).unwrap();
+        calendar.mark_entry(&market);
+        assert!(calendar.entry_cost() > 0.0);
+        assert!(calendar.value(&market) - calendar.entry_cost() < 1e-12);
+
+        // At the front expiry the short leg is intrinsic and the long leg keeps time value
+        let at_strike = calendar.pnl_at(&market, 0.25, 100.0, 0.0);
+        let away = calendar.pnl_at(&market, 0.25, 80.0, 0.0);
+        let far_away = calendar.pnl_at(&market, 0.25, 125.0, 0.0);
+        assert!(at_strike > 0.0);
+        assert!(at_strike > away && at_strike > far_away);
+
+        // Long vega before and at the front expiry
+        assert!(calendar.greeks(&market).vega > 0.0);
+        assert!(calendar.greeks_at(&market, 0.25, 100.0).vega > 0.0);
+    }
+
+    #[test]
+    fn test_legs_age_on_the_business_clock() {
+        use crate::time_scale::{DaySchedule, TimeScale};
+        // A market quoted on trading days carries a different variance per calendar year
+        let market = market().with_time_scale(TimeScale::Trading, &DaySchedule::weekdays(365, 0)).unwrap();
+        let calendar = Strategy::calendar(OptionType::Call, 100.0, 0.25, 0.5).unwrap();
+        // At 0.15 years the legs have 0.1 and 0.35 calendar years left
+        let ratio = market.vol_time / market.time_to_expiry;
+        let leg = |remaining: f64| {
+            BlackScholes {
+                time_to_expiry: remaining,
+                vol_time: remaining * ratio,
+                ..market
+            }
+            .price(OptionType::Call)
+        };
+        assert!((ratio - 1.0).abs() > 0.01);
+        let expected = leg(0.35) - leg(0.1);
+        assert!((calendar.value_at(&market, 0.15, 100.0, 0.0) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cash_greeks_include_underlying_legs() {
+        let market = market();
+        let covered_call = Strategy::new(
+            "covered call",
+            vec![
+                Leg::underlying(1.0).unwrap(),
+                Leg::option(OptionType::Call, 110.0, 0.5, -1.0).unwrap(),
+            ],
+        )
+        .unwrap();
+        let cash = covered_call.cash_greeks(&market, 100.0);
+        let net = covered_call.greeks(&market);
+        assert!((cash.delta - net.delta * 100.0 * 100.0).abs() < 1e-9);
+        assert!(cash.delta > 0.0 && cash.delta < 100.0 * 100.0);
+        assert!(cash.gamma < 0.0 && cash.vega < 0.0 && cash.theta > 0.0);
+    }
+
+    #[test]
+    fn test_rolling_greeks_over_history() {
+        let market = market();
+        let mut straddle = Strategy::new(
+            "straddle",
+            vec![
+                Leg::option(OptionType::Call, 100.0, 20.0 / 365.0, 1.0).unwrap(),
+                Leg::option(OptionType::Put, 100.0, 20.0 / 365.0, 1.0).unwrap(),
+            ],
+        )
+        .unwrap();
+        straddle.mark_entry(&market);
+        let history: Vec<MarketObservation> = (0..=25)
+            .map(|day| MarketObservation {
+                time: day as f64 / 365.0,
+                spot: 100.0 + (day as f64 * 0.7).sin() * 3.0,
+                volatility: 0.2,
+                rate: 0.03,
+                dividend_yield: 0.0,
+            })
+            .collect();
+        let series = straddle.rolling_greeks(&history).unwrap();
+        assert_eq!(series.len(), 26);
+        assert!(series[0].pnl.abs() < 1e-12 && series[0].hedged_pnl.abs() < 1e-12);
+        // Long gamma decays with the shrinking expiry, then the legs expire
+        assert!(series[15].greeks.theta < series[1].greeks.theta);
+        assert_eq!(series[25].greeks.gamma, 0.0);
+        assert!((series[25].value - (series[25].spot - 100.0).abs()).abs() < 1e-12);
+
+        let mut shuffled = history.clone();
+        shuffled.swap(3, 4);
+        assert!(straddle.rolling_greeks(&shuffled).is_err());
+    }
+
+    #[test]
+    fn test_strategy_leverage() {
+        let market = market();
+        let spread = Strategy::new(
+            "bull call spread",
+            vec![
+                Leg::option(OptionType::Call, 100.0, 0.5, 1.0).unwrap(),
+                Leg::option(OptionType::Call, 110.0, 0.5, -1.0).unwrap(),
+            ],
+        )
+        .unwrap();
+        let leverage = spread.leverage(&market).unwrap();
+        let single = market.leverage(OptionType::Call).unwrap();
+        assert!((leverage.notional - 200.0).abs() < 1e-12);
+        assert!(leverage.effective_exposure > 0.0 && leverage.effective_exposure < single.effective_exposure);
+        // Selling the upper strike cheapens the position faster than it cuts delta
+        assert!(leverage.lambda > single.lambda);
+    }
+
+    #[test]
+    fn test_strategy_breakeven_volatility() {
+        let market = BlackScholes::new(100.0, 100.0, 1.0, 0.0, 0.2, 0.0).unwrap();
+        let calendar = Strategy::calendar(OptionType::Call, 100.0, 0.25, 0.5).unwrap();
+        // Short front gamma: the position profits when realized stays below breakeven
+        assert!(calendar.greeks(&market).gamma < 0.0);
+        let today = calendar.breakeven_volatility(&market, 0.0).unwrap();
+        assert!((today - 0.2).abs() < 1e-9);
+        let over_front = calendar.breakeven_volatility(&market, 0.25).unwrap();
+        assert!((over_front - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_named_structures_payoff_and_breakevens() {
+        let market = market();
+        let mut straddle = Strategy::straddle(100.0, 0.5).unwrap();
+        straddle.mark_entry(&market);
+        let premium = straddle.entry_cost();
+        let breakevens = straddle.breakevens().unwrap();
+        assert_eq!(breakevens.len(), 2);
+        assert!((breakevens[0] - (100.0 - premium)).abs() < 1e-9);
+        assert!((breakevens[1] - (100.0 + premium)).abs() < 1e-9);
+        assert_eq!(straddle.max_profit_loss().unwrap(), (Extremum::Unbounded, Extremum::Bounded(premium)));
+
+        let mut condor = Strategy::iron_condor(85.0, 95.0, 105.0, 115.0, 0.5).unwrap();
+        condor.mark_entry(&market);
+        let credit = -condor.entry_cost();
+        assert!(credit > 0.0);
+        let (profit, loss) = condor.max_profit_loss().unwrap();
+        assert_eq!(profit, Extremum::Bounded(credit));
+        let Extremum::Bounded(loss) = loss else { panic!("condor loss is bounded") };
+        assert!((loss - (10.0 - credit)).abs() < 1e-9);
+        let breakevens = condor.breakevens().unwrap();
+        assert!((breakevens[0] - (95.0 - credit)).abs() < 1e-9 && (breakevens[1] - (105.0 + credit)).abs() < 1e-9);
+
+        let payoff = condor.payoff_at_expiry(&[80.0, 100.0, 120.0]).unwrap();
+        assert_eq!(payoff, vec![-10.0, 0.0, -10.0]);
+        let pnl = condor.pnl_at_expiry(&[100.0]).unwrap();
+        assert!((pnl[0] - credit).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_more_named_structures() {
+        let market = market();
+        let mut fly = Strategy::butterfly(OptionType::Call, 90.0, 100.0, 110.0, 0.5).unwrap();
+        fly.mark_entry(&market);
+        assert_eq!(fly.payoff_at_expiry(&[85.0, 100.0, 105.0, 120.0]).unwrap(), vec![0.0, 10.0, 5.0, 0.0]);
+        assert_eq!(fly.breakevens().unwrap().len(), 2);
+
+        let mut collar = Strategy::collar(90.0, 110.0, 0.5).unwrap();
+        collar.legs[0] = collar.legs[0].with_entry_price(100.0).unwrap();
+        let (profit, loss) = collar.max_profit_loss().unwrap();
+        assert_eq!(profit, Extremum::Bounded(10.0));
+        assert_eq!(loss, Extremum::Bounded(10.0));
+
+        let mut bull = Strategy::vertical(OptionType::Call, 100.0, 110.0, 0.5).unwrap();
+        bull.mark_entry(&market);
+        let debit = bull.entry_cost();
+        assert!((bull.breakevens().unwrap()[0] - (100.0 + debit)).abs() < 1e-9);
+        assert!(bull.greeks(&market).delta > 0.0);
+
+        let short_strangle = Strategy::strangle(95.0, 105.0, 0.5).unwrap().scaled(-1.0).unwrap();
+        assert!(short_strangle.greeks(&market).gamma < 0.0);
+        assert!(Strategy::strangle(105.0, 95.0, 0.5).is_err());
+        assert!(Strategy::iron_condor(95.0, 85.0, 105.0, 115.0, 0.5).is_err());
+        assert!(Strategy::calendar(OptionType::Call, 100.0, 0.25, 0.5).unwrap().breakevens().is_err());
+    }
+
+    #[test]
+    fn test_front_expiry_surface() {
+        let market = market();
+        let mut dd = Strategy::double_diagonal(95.0, 105.0, 90.0, 110.0, 0.25, 0.5).unwrap();
+        dd.mark_entry(&market);
+        let spots = [80.0, 90.0, 100.0, 110.0, 120.0];
+        let shifts = [-0.05, 0.0, 0.05];
+        let surface = dd.front_expiry_surface(&market, &spots, &shifts).unwrap();
+
+        assert_eq!(surface.horizon, 0.25);
+        assert_eq!(surface.pnl.len(), 3);
+        // Long far-dated vega: P&L rises with implied volatility at every spot
+        for j in 0..spots.len() {
+            assert!(surface.pnl[2][j] > surface.pnl[1][j]);
+            assert!(surface.pnl[1][j] > surface.pnl[0][j]);
+        }
+        assert_eq!(surface.pnl[1][2], dd.pnl_at(&market, 0.25, 100.0, 0.0));
+    }
+
+    #[test]
+    fn test_leg_volatility_and_expiry_handling() {
+        let market = market();
+        let diagonal = Strategy::diagonal(OptionType::Put, 95.0, 0.25, 90.0, 0.75).unwrap();
+        assert_eq!(diagonal.expiries(), vec![0.25, 0.75]);
+
+        let mut skewed = diagonal.clone();
+        skewed.legs[1] = skewed.legs[1].with_volatility(0.3).unwrap();
+        assert!(skewed.value(&market) > diagonal.value(&market));
+
+        // After every expiry only intrinsic value remains
+        let expired = diagonal.value_at(&market, 1.0, 85.0, 0.0);
+        assert!((expired - (-10.0 + 5.0)).abs() < 1e-12);
+
+        assert!(Strategy::calendar(OptionType::Call, 100.0, 0.5, 0.25).is_err());
+        assert!(Strategy::new("Empty", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_value_in_shared_context() {
+        use crate::vol_surface::FlatVol;
+        let context = MarketContext::new(100.0, 0.03, 0.0, FlatVol::new(0.2)).unwrap();
+        let diagonal = Strategy::diagonal(OptionType::Put, 95.0, 0.25, 90.0, 0.75).unwrap();
+        let value = diagonal.value_in(&context).unwrap();
+        assert!((value - diagonal.value(&market())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ratio_spread_has_unbounded_upside_loss() {
+        let market = market();
+        let mut ratio = Strategy::ratio_spread(OptionType::Call, 100.0, 110.0, 0.5, 1.0, 2.0).unwrap();
+        ratio.mark_entry(&market);
+        let risk = ratio.tail_risk(&market, 3.0).unwrap();
+
+        assert_eq!(risk.max_loss, Extremum::Unbounded);
+        // Best case is finishing at the short strike
+        let peak = 10.0 - ratio.entry_cost();
+        assert_eq!(risk.max_profit, Extremum::Bounded(peak));
+        assert!(risk.pnl_up < 0.0);
+        assert!(risk.spot_up > 100.0 && risk.spot_down < 100.0);
+
+        // One naked short call on top of the covered 100/110 vertical
+        let margin = ratio.margin(&market).unwrap();
+        assert!(margin > 0.1 * market.spot_price);
+    }
+
+    #[test]
+    fn test_backspread_worst_case_at_long_strike() {
+        let market = market();
+        let mut back = Strategy::backspread(OptionType::Put, 100.0, 90.0, 0.5, 1.0, 2.0).unwrap();
+        back.mark_entry(&market);
+        let risk = back.tail_risk(&market, 2.0).unwrap();
+
+        let at_long_strike = back.pnl_at(&market, 0.5, 90.0, 0.0);
+        assert_eq!(risk.max_loss, Extremum::Bounded(-at_long_strike));
+        assert_eq!(risk.max_profit, Extremum::Bounded(back.pnl_at(&market, 0.5, 0.0, 0.0)));
+        assert!(risk.pnl_down > risk.pnl_up);
+
+        // Fully covered: margin is just the maximum loss
+        assert!((back.margin(&market).unwrap() + at_long_strike).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_credit_spread_margin_is_width_less_credit() {
+        let market = market();
+        let mut spread = Strategy::new(
+            "Bull put",
+            vec![
+                Leg::option(OptionType::Put, 100.0, 0.5, -1.0).unwrap(),
+                Leg::option(OptionType::Put, 95.0, 0.5, 1.0).unwrap(),
+            ],
+        )
+        .unwrap();
+        spread.mark_entry(&market);
+        let credit = -spread.entry_cost();
+        assert!((spread.margin(&market).unwrap() - (5.0 - credit)).abs() < 1e-12);
+
+        // Covered call: long stock covers the short call
+        let covered_call = Strategy::new(
+            "Covered call",
+            vec![
+                Leg::underlying(1.0).unwrap().with_entry_price(100.0).unwrap(),
+                Leg::option(OptionType::Call, 105.0, 0.5, -1.0).unwrap(),
+            ],
+        )
+        .unwrap();
+        let risk = covered_call.tail_risk(&market, 2.0).unwrap();
+        assert_eq!(risk.max_loss, Extremum::Bounded(100.0));
+        assert_eq!(risk.max_profit, Extremum::Bounded(5.0));
+    }
+
+    #[test]
+    fn test_tail_metrics_reject_multi_expiry() {
+        let calendar = Strategy::calendar(OptionType::Call, 100.0, 0.25, 0.5).unwrap();
+        assert!(calendar.tail_risk(&market(), 2.0).is_err());
+        assert!(calendar.margin(&market()).is_err());
+        assert!(Strategy::ratio_spread(OptionType::Call, 100.0, 110.0, 0.5, 2.0, 1.0).is_err());
+    }
+}
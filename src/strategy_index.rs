@@ -0,0 +1,492 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::expiry_calendar::nearest_listed;
+use crate::settlement_value::SettlementPrint;
+use crate::strategy::MarketObservation;
+use crate::validation;
+
+/// Remaining life below which a written option is settled at intrinsic value
+const SETTLE_EPSILON: f64 = 1e-9;
+
+/// Largest gap between an observation and a settlement print taken to be the same date
+const PRINT_TOLERANCE: f64 = 1e-6;
+
+/// Systematic overlay an index applies to the underlying
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexRule {
+    /// Long the underlying and short calls struck `moneyness` above spot,
+    /// written for `tenor` years and rolled at expiry (CBOE BXM style)
+    BuyWrite { moneyness: f64, tenor: f64 },
+    /// Puts struck `moneyness` below spot, written against cash equal to
+    /// their notional and rolled at expiry (CBOE PUT style)
+    PutWrite { moneyness: f64, tenor: f64 },
+    /// Underlying exposure scaled daily to `target` volatility from the
+    /// realized volatility of the last `lookback` returns, capped at
+    /// `max_leverage`; the rest is held in cash
+    VolTarget { target: f64, lookback: usize, max_leverage: f64 },
+}
+
+/// Strategy index replayed over a market history
+///
+/// Each day the holdings from the previous day accrue interest and
+/// dividends, expiring options settle, and the position is rebalanced by
+/// the rule. Written options are marked with the observed volatility;
+/// transaction costs are ignored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrategyIndex {
+    pub rule: IndexRule,
+    /// Index level on the first observation
+    pub base: f64,
+}
+
+/// Index levels on each observation date
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexSeries {
+    /// Dates in years from the first observation
+    pub times: Vec<f64>,
+    pub levels: Vec<f64>,
+    /// Average short rate over the history, the Sharpe ratio's hurdle
+    pub average_rate: f64,
+}
+
+/// Performance summary of an index series
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexStatistics {
+    pub total_return: f64,
+    /// Compound annual growth rate
+    pub annualized_return: f64,
+    /// Standard deviation of log returns, annualised
+    pub annualized_volatility: f64,
+    /// Annualized return in excess of the average rate per unit of volatility
+    pub sharpe_ratio: f64,
+    /// Largest peak-to-trough fall as a fraction of the peak
+    pub max_drawdown: f64,
+}
+
+/// Holdings between rebalances
+struct Holdings {
+    shares: f64,
+    cash: f64,
+    /// Written option: type, strike, expiry date and signed quantity
+    option: Option<(OptionType, f64, f64, f64)>,
+}
+
+impl Holdings {
+    fn option_value(&self, obs: &MarketObservation) -> Result<f64, BlackScholesError> {
+        match self.option {
+            Some((option_type, strike, expiry, quantity)) => {
+                let model = BlackScholes::new(obs.spot, strike, expiry - obs.time, obs.rate, obs.volatility, obs.dividend_yield)?;
+                Ok(quantity * model.price(option_type))
+            }
+            None => Ok(0.0),
+        }
+    }
+}
+
+impl StrategyIndex {
+    pub fn new(rule: IndexRule, base: f64) -> Result<Self, BlackScholesError> {
+        let base = validation::positive("Index base", base)?;
+        match rule {
+            IndexRule::BuyWrite { moneyness, tenor } | IndexRule::PutWrite { moneyness, tenor } => {
+                validation::in_range("Moneyness", moneyness, -0.5, 0.5, "in [-0.5, 0.5]")?;
+                validation::positive("Tenor", tenor)?;
+            }
+            IndexRule::VolTarget {
+                target,
+                lookback,
+                max_leverage,
+            } => {
+                validation::positive("Target volatility", target)?;
+                validation::positive("Maximum leverage", max_leverage)?;
+                if lookback < 2 {
+                    return Err(BlackScholesError::invalid("Volatility lookback needs at least two returns"));
+                }
+            }
+        }
+        Ok(StrategyIndex { rule, base })
+    }
+
+    /// Replay the rule over `history` (chronological, at least two observations)
+    pub fn build(&self, history: &[MarketObservation]) -> Result<IndexSeries, BlackScholesError> {
+        self.build_with_prints(history, &[])
+    }
+
+    /// Replay the rule, settling expiring options on opening settlement prints
+    ///
+    /// An option expiring on a date with a print settles against it (the
+    /// SET/SOQ of an AM-settled index option) instead of that day's close;
+    /// dates without one fall back to the close. The index is still marked
+    /// at the close, so the gap between print and close shows up in the
+    /// level on expiry days.
+    pub fn build_with_prints(
+        &self,
+        history: &[MarketObservation],
+        prints: &[SettlementPrint],
+    ) -> Result<IndexSeries, BlackScholesError> {
+        self.build_with_expiries(history, &[], prints)
+    }
+
+    /// Replay the rule writing options on listed expiries
+    ///
+    /// Each roll writes the listed expiry nearest the rule's tenor, so a
+    /// monthly index follows the exchange's actual expiry dates, holiday
+    /// shifts included. `expiries` are on the observations' clock (e.g. from
+    /// [`ExpiryCalendar::expiry_times`](crate::expiry_calendar::ExpiryCalendar::expiry_times));
+    /// an empty list writes exactly `tenor` ahead. Prints settle expiring
+    /// options as in [`build_with_prints`](Self::build_with_prints).
+    pub fn build_with_expiries(
+        &self,
+        history: &[MarketObservation],
+        expiries: &[f64],
+        prints: &[SettlementPrint],
+    ) -> Result<IndexSeries, BlackScholesError> {
+        validation::all_finite("Listed expiry", expiries)?;
+        for print in prints {
+            validation::positive("Settlement print", print.value)?;
+        }
+        if history.len() < 2 {
+            return Err(BlackScholesError::invalid("Index history needs at least two observations"));
+        }
+        if history.iter().any(|obs| !obs.time.is_finite()) || history.windows(2).any(|w| w[1].time <= w[0].time) {
+            return Err(BlackScholesError::invalid("Observation times must be finite and strictly increasing"));
+        }
+        let mut holdings = Holdings {
+            shares: 0.0,
+            cash: self.base,
+            option: None,
+        };
+        let mut levels = Vec::with_capacity(history.len());
+        let mut log_returns: Vec<f64> = Vec::new();
+
+        for (i, obs) in history.iter().enumerate() {
+            validation::positive("Spot", obs.spot)?;
+            if i > 0 {
+                let prev = &history[i - 1];
+                let dt = obs.time - prev.time;
+                holdings.cash *= (prev.rate * dt).exp();
+                holdings.cash += holdings.shares * prev.spot * prev.dividend_yield * dt;
+                log_returns.push((obs.spot / prev.spot).ln() + prev.dividend_yield * dt);
+                if let Some((option_type, strike, expiry, quantity)) = holdings.option {
+                    if expiry - obs.time <= SETTLE_EPSILON {
+                        let settlement = prints
+                            .iter()
+                            .find(|p| (p.time - obs.time).abs() <= PRINT_TOLERANCE)
+                            .map_or(obs.spot, |p| p.value);
+                        let payoff = match option_type {
+                            OptionType::Call => (settlement - strike).max(0.0),
+                            OptionType::Put => (strike - settlement).max(0.0),
+                        };
+                        holdings.cash += quantity * payoff;
+                        holdings.option = None;
+                    }
+                }
+            }
+            let level = holdings.shares * obs.spot + holdings.cash + holdings.option_value(obs)?;
+            levels.push(level);
+            self.rebalance(&mut holdings, history, i, level, &log_returns, expiries)?;
+        }
+
+        let t0 = history[0].time;
+        let span = history[history.len() - 1].time - t0;
+        let average_rate = history.windows(2).map(|w| w[0].rate * (w[1].time - w[0].time)).sum::<f64>() / span;
+        Ok(IndexSeries {
+            times: history.iter().map(|o| o.time - t0).collect(),
+            levels,
+            average_rate,
+        })
+    }
+
+    /// Reset holdings according to the rule at the level on observation `i`
+    fn rebalance(
+        &self,
+        holdings: &mut Holdings,
+        history: &[MarketObservation],
+        i: usize,
+        level: f64,
+        log_returns: &[f64],
+        expiries: &[f64],
+    ) -> Result<(), BlackScholesError> {
+        let obs = &history[i];
+        match self.rule {
+            IndexRule::BuyWrite { moneyness, tenor } => {
+                if holdings.option.is_none() {
+                    let strike = obs.spot * (1.0 + moneyness);
+                    let expiry = nearest_listed(expiries, obs.time, tenor)?;
+                    let life = expiry - obs.time;
+                    let call = BlackScholes::new(obs.spot, strike, life, obs.rate, obs.volatility, obs.dividend_yield)?
+                        .price(OptionType::Call);
+                    // Premium is reinvested, so the whole level buys covered units
+                    let units = level / (obs.spot - call);
+                    holdings.shares = units;
+                    holdings.cash = 0.0;
+                    holdings.option = Some((OptionType::Call, strike, expiry, -units));
+                }
+            }
+            IndexRule::PutWrite { moneyness, tenor } => {
+                if holdings.option.is_none() {
+                    let strike = obs.spot * (1.0 - moneyness);
+                    let expiry = nearest_listed(expiries, obs.time, tenor)?;
+                    let life = expiry - obs.time;
+                    let put = BlackScholes::new(obs.spot, strike, life, obs.rate, obs.volatility, obs.dividend_yield)?
+                        .price(OptionType::Put);
+                    // Notional equals the collateral; premium joins the cash account
+                    let units = level / strike;
+                    holdings.shares = 0.0;
+                    holdings.cash = level + units * put;
+                    holdings.option = Some((OptionType::Put, strike, expiry, -units));
+                }
+            }
+            IndexRule::VolTarget {
+                target,
+                lookback,
+                max_leverage,
+            } => {
+                let window = &log_returns[log_returns.len().saturating_sub(lookback)..];
+                let weight = if window.len() < 2 {
+                    1.0_f64.min(max_leverage)
+                } else {
+                    let n = window.len();
+                    let span = obs.time - history[i - n].time;
+                    let mean = window.iter().sum::<f64>() / n as f64;
+                    let variance = window.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+                    let realized = (variance * n as f64 / span).sqrt();
+                    if realized > 0.0 {
+                        (target / realized).min(max_leverage)
+                    } else {
+                        max_leverage
+                    }
+                };
+                holdings.shares = weight * level / obs.spot;
+                holdings.cash = level - holdings.shares * obs.spot;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IndexSeries {
+    /// Return, volatility, Sharpe ratio and drawdown over the whole series
+    pub fn statistics(&self) -> Result<IndexStatistics, BlackScholesError> {
+        let n = self.levels.len();
+        if n < 3 {
+            return Err(BlackScholesError::invalid("Statistics need at least three index levels"));
+        }
+        let span = self.times[n - 1] - self.times[0];
+        let total_return = self.levels[n - 1] / self.levels[0] - 1.0;
+        let annualized_return = (1.0 + total_return).powf(1.0 / span) - 1.0;
+
+        let returns: Vec<f64> = self.levels.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+        let annualized_volatility = (variance * returns.len() as f64 / span).sqrt();
+
+        let mut peak = f64::NEG_INFINITY;
+        let mut max_drawdown: f64 = 0.0;
+        for &level in &self.levels {
+            peak = peak.max(level);
+            max_drawdown = max_drawdown.max(1.0 - level / peak);
+        }
+
+        let sharpe_ratio = if annualized_volatility > 0.0 {
+            (annualized_return - self.average_rate) / annualized_volatility
+        } else {
+            0.0
+        };
+        Ok(IndexStatistics {
+            total_return,
+            annualized_return,
+            annualized_volatility,
+            sharpe_ratio,
+            max_drawdown,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bonds::Date;
+    use crate::expiry_calendar::{Exchange, ExpiryCalendar, ExpiryCycle};
+
+    /// Daily history with a deterministic oscillating spot
+    fn history(days: usize, drift: f64, swing: f64) -> Vec<MarketObservation> {
+        (0..=days)
+            .map(|d| {
+                let t = d as f64 / 252.0;
+                MarketObservation {
+                    time: t,
+                    spot: 100.0 * (drift * t).exp() * (1.0 + swing * (d as f64 * 0.9).sin()),
+                    volatility: 0.2,
+                    rate: 0.03,
+                    dividend_yield: 0.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_buy_write_collects_premium_when_spot_is_flat() {
+        let flat = history(252, 0.0, 0.0);
+        let index = StrategyIndex::new(
+            IndexRule::BuyWrite {
+                moneyness: 0.0,
+                tenor: 21.0 / 252.0,
+            },
+            100.0,
+        )
+        .unwrap();
+        let series = index.build(&flat).unwrap();
+        assert_eq!(series.levels[0], 100.0);
+        // Twelve at-the-money calls expire worthless: the index banks every premium
+        assert!(series.levels[252] > 115.0);
+        let stats = series.statistics().unwrap();
+        assert!(stats.total_return > 0.15 && stats.max_drawdown < 0.02);
+    }
+
+    #[test]
+    fn test_put_write_is_collateralised() {
+        let falling = history(126, -0.6, 0.0);
+        let index = StrategyIndex::new(
+            IndexRule::PutWrite {
+                moneyness: 0.0,
+                tenor: 21.0 / 252.0,
+            },
+            100.0,
+        )
+        .unwrap();
+        let series = index.build(&falling).unwrap();
+        let stats = series.statistics().unwrap();
+        // Loses with the market but the cash collateral keeps the index positive
+        assert!(stats.total_return < 0.0);
+        assert!(series.levels.iter().all(|&l| l > 0.0));
+        assert!(stats.max_drawdown < 1.0 - falling[126].spot / falling[0].spot);
+    }
+
+    #[test]
+    fn test_buy_write_on_listed_expiries() {
+        let calendar = ExpiryCalendar::new(Exchange::Cboe);
+        let (as_of, end) = (Date::new(2023, 12, 29).unwrap(), Date::new(2024, 6, 28).unwrap());
+        let history: Vec<MarketObservation> = std::iter::once(as_of)
+            .chain(calendar.business_days(as_of, end))
+            .map(|d| MarketObservation {
+                time: as_of.days_until(d) as f64 / 365.0,
+                spot: 100.0,
+                volatility: 0.2,
+                rate: 0.03,
+                dividend_yield: 0.0,
+            })
+            .collect();
+        let expiries = calendar.expiry_times(as_of, Date::new(2024, 9, 30).unwrap(), &[ExpiryCycle::Monthly]);
+        let index = StrategyIndex::new(
+            IndexRule::BuyWrite {
+                moneyness: 0.0,
+                tenor: 1.0 / 12.0,
+            },
+            100.0,
+        )
+        .unwrap();
+        let listed = index.build_with_expiries(&history, &expiries, &[]).unwrap();
+        let gridded = index.build(&history).unwrap();
+        assert_ne!(listed, gridded);
+        // Six monthly calls expire worthless on the flat spot
+        assert!(listed.levels[listed.levels.len() - 1] > 107.0);
+        // Past the last listing there is nothing left to write
+        assert!(index.build_with_expiries(&history, &expiries[..1], &[]).is_err());
+    }
+
+    #[test]
+    fn test_put_write_settles_on_opening_prints() {
+        let flat = history(63, 0.0, 0.0);
+        let index = StrategyIndex::new(
+            IndexRule::PutWrite {
+                moneyness: 0.0,
+                tenor: 21.0 / 252.0,
+            },
+            100.0,
+        )
+        .unwrap();
+        let at_close = index.build(&flat).unwrap();
+        // Prints at the close change nothing
+        let same: Vec<SettlementPrint> = flat.iter().map(|o| SettlementPrint { time: o.time, value: o.spot }).collect();
+        assert_eq!(index.build_with_prints(&flat, &same).unwrap(), at_close);
+        // A 3% gap down at the first expiry's open costs the written puts
+        let gapped = [SettlementPrint {
+            time: flat[21].time,
+            value: 97.0,
+        }];
+        let series = index.build_with_prints(&flat, &gapped).unwrap();
+        assert_eq!(series.levels[20], at_close.levels[20]);
+        let loss = at_close.levels[21] - series.levels[21];
+        // One put per 100 of collateral, struck at the flat spot
+        assert!((loss - 3.0).abs() < 1e-9, "{loss}");
+        assert!(index.build_with_prints(&flat, &[SettlementPrint { time: 0.0, value: -1.0 }]).is_err());
+    }
+
+    #[test]
+    fn test_vol_target_scales_exposure() {
+        let choppy = history(252, 0.05, 0.03);
+        let target = |target: f64| {
+            StrategyIndex::new(
+                IndexRule::VolTarget {
+                    target,
+                    lookback: 20,
+                    max_leverage: 3.0,
+                },
+                100.0,
+            )
+            .unwrap()
+            .build(&choppy)
+            .unwrap()
+            .statistics()
+            .unwrap()
+        };
+        let (low, high) = (target(0.05), target(0.15));
+        assert!(low.annualized_volatility < high.annualized_volatility);
+        assert!(low.max_drawdown < high.max_drawdown);
+        assert!(StrategyIndex::new(
+            IndexRule::VolTarget {
+                target: 0.1,
+                lookback: 1,
+                max_leverage: 1.0,
+            },
+            100.0
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_history_must_be_chronological() {
+        let index = StrategyIndex::new(
+            IndexRule::BuyWrite {
+                moneyness: 0.0,
+                tenor: 21.0 / 252.0,
+            },
+            100.0,
+        )
+        .unwrap();
+        assert!(index.build(&history(1, 0.0, 0.0)).is_ok());
+        let mut unordered = history(30, 0.0, 0.0);
+        unordered[10].time = f64::NAN;
+        assert!(index.build(&unordered).is_err());
+        unordered[10].time = unordered[11].time;
+        assert!(index.build(&unordered).is_err());
+    }
+
+    #[test]
+    fn test_statistics_of_known_series() {
+        let series = IndexSeries {
+            times: vec![0.0, 0.5, 1.0, 1.5, 2.0],
+            levels: vec![100.0, 120.0, 90.0, 110.0, 121.0],
+            average_rate: 0.0,
+        };
+        let stats = series.statistics().unwrap();
+        assert!((stats.total_return - 0.21).abs() < 1e-12);
+        assert!((stats.annualized_return - 0.1).abs() < 1e-12);
+        assert!((stats.max_drawdown - 0.25).abs() < 1e-12);
+        assert!(stats.sharpe_ratio > 0.0);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::black_scholes::OptionType;
+use crate::bonds::Date;
+use crate::chain::{ExpirySlice, OptionChain, OptionQuote};
+use crate::error::BlackScholesError;
+use crate::expiry_calendar::{ExpiryCalendar, ExpiryCycle};
+use crate::model::EuropeanModel;
+
+/// Strike placement for each expiry of a synthetic chain
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StrikeGrid {
+    /// The same absolute strikes for every expiry
+    Absolute(Vec<f64>),
+    /// Strikes as multiples of each expiry's forward (1.0 = ATM forward)
+    Moneyness(Vec<f64>),
+}
+
+/// Layout of a synthetic option chain
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainSpec {
+    /// Expiries in years
+    pub expiries: Vec<f64>,
+    /// Strike placement per expiry
+    pub strikes: StrikeGrid,
+    /// Half bid/ask spread as a fraction of the model price
+    pub relative_half_spread: f64,
+    /// Minimum half bid/ask spread in currency
+    pub min_half_spread: f64,
+}
+
+impl ChainSpec {
+    /// Monthly expiries out to `months` with strikes from 70% to 130% of forward in 5% steps
+    pub fn standard(months: usize) -> Self {
+        ChainSpec {
+            expiries: (1..=months).map(|m| m as f64 / 12.0).collect(),
+            strikes: StrikeGrid::Moneyness((0..=12).map(|i| 0.7 + 0.05 * i as f64).collect()),
+            relative_half_spread: 0.01,
+            min_half_spread: 0.005,
+        }
+    }
+
+    /// The standard layout on an exchange's listed expiries from `as_of` up to `until`
+    ///
+    /// Expiry times run from the close on `as_of` to each settlement, so
+    /// AM-settled and holiday-shifted expiries land where they trade.
+    pub fn listed(calendar: &ExpiryCalendar, as_of: Date, until: Date, cycles: &[ExpiryCycle]) -> Self {
+        ChainSpec {
+            expiries: calendar.expiry_times(as_of, until, cycles),
+            ..ChainSpec::standard(0)
+        }
+    }
+}
+
+/// Generate a full option chain (calls and puts on every strike/expiry) from a model
+///
+/// Quotes are placed symmetrically around the model price (bids floored at
+/// zero), so running `OptionChain::analyze` on the result recovers the
+/// model's implied volatilities and Greeks from the mids.
+///
+/// # Arguments
+/// * `model` - Any calibrated model implementing `EuropeanModel`
+/// * `spec` - Expiries, strikes and spread assumptions
+///
+/// # Returns
+/// Chain carrying each expiry's model rate and dividend yield
+pub fn generate_chain<M: EuropeanModel + ?Sized>(model: &M, spec: &ChainSpec) -> Result<OptionChain, BlackScholesError> {
+    if spec.expiries.is_empty() {
+        return Err(BlackScholesError::invalid("Chain needs at least one expiry"));
+    }
+    if spec.relative_half_spread < 0.0 || spec.min_half_spread < 0.0 {
+        return Err(BlackScholesError::invalid("Spreads must be non-negative"));
+    }
+
+    let mut slices = Vec::with_capacity(spec.expiries.len());
+    for &expiry in &spec.expiries {
+        let strikes: Vec<f64> = match &spec.strikes {
+            StrikeGrid::Absolute(strikes) => strikes.clone(),
+            StrikeGrid::Moneyness(multiples) => {
+                let forward = model.forward(expiry);
+                multiples.iter().map(|m| m * forward).collect()
+            }
+        };
+
+        let mut quotes = Vec::with_capacity(2 * strikes.len());
+        for strike in strikes {
+            for option_type in [OptionType::Call, OptionType::Put] {
+                let price = model.price(option_type, strike, expiry);
+                if !price.is_finite() {
+                    return Err(BlackScholesError::InvalidInput(format!(
+                        "Model produced no price at strike {} expiry {}",
+                        strike, expiry
+                    )));
+                }
+                let half_spread = (spec.relative_half_spread * price).max(spec.min_half_spread);
+                let bid = (price - half_spread).max(0.0);
+                let ask = price + half_spread;
+                quotes.push(OptionQuote::new(strike, option_type, bid, ask)?);
+            }
+        }
+        slices.push(ExpirySlice::new(
+            expiry,
+            model.rate(expiry),
+            model.dividend_yield(expiry),
+            quotes,
+        )?);
+    }
+
+    OptionChain::new(model.spot(), slices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::expiry_calendar::Exchange;
+    use crate::model::SurfaceModel;
+    use crate::vol_surface::{SviParams, SviSlice, SviSurface, VolSurface};
+
+    #[test]
+    fn test_flat_model_chain_round_trips_vol() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.25, 0.01).unwrap();
+        let chain = generate_chain(&bs, &ChainSpec::standard(3)).unwrap();
+
+        assert_eq!(chain.slices.len(), 3);
+        assert_eq!(chain.slices[0].quotes.len(), 26);
+        // Quotes with a floored bid no longer have the model price as mid
+        for row in chain.analyze().iter().filter(|r| r.mid > 0.05) {
+            assert!((row.implied_vol.unwrap() - 0.25).abs() < 1e-4, "{:?}", row);
+        }
+    }
+
+    #[test]
+    fn test_smile_model_chain_reproduces_surface() {
+        let slice = SviSlice::new(0.5, 100.0, SviParams::new(0.01, 0.08, -0.5, 0.0, 0.2).unwrap()).unwrap();
+        let surface = SviSurface::new(vec![slice]).unwrap();
+        let model = SurfaceModel::new(100.0, 0.0, 0.0, surface.clone()).unwrap();
+        let spec = ChainSpec {
+            expiries: vec![0.5],
+            strikes: StrikeGrid::Absolute(vec![80.0, 100.0, 120.0]),
+            relative_half_spread: 0.0,
+            min_half_spread: 0.0,
+        };
+
+        let chain = generate_chain(&model, &spec).unwrap();
+        for row in chain.analyze() {
+            let expected = surface.implied_vol(row.strike, row.expiry);
+            assert!((row.implied_vol.unwrap() - expected).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_quotes_are_ordered() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.25, 0.0).unwrap();
+        let chain = generate_chain(&bs, &ChainSpec::standard(1)).unwrap();
+        assert!(chain.slices[0].quotes.iter().all(|q| q.bid <= q.ask && q.bid >= 0.0));
+        assert!(generate_chain(&bs, &ChainSpec { expiries: vec![], ..ChainSpec::standard(1) }).is_err());
+    }
+
+    #[test]
+    fn test_listed_expiries() {
+        let calendar = ExpiryCalendar::new(Exchange::Cboe);
+        let as_of = Date::new(2024, 3, 1).unwrap();
+        let spec = ChainSpec::listed(&calendar, as_of, Date::new(2024, 6, 30).unwrap(), &[ExpiryCycle::Monthly]);
+        // AM settlement on 15 March, 19 April, 17 May and 21 June, 6.5 hours before the close
+        assert_eq!(spec.expiries.len(), 4);
+        assert!((spec.expiries[0] - (14.0 - 6.5 / 24.0) / 365.0).abs() < 1e-12);
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.25, 0.0).unwrap();
+        assert_eq!(generate_chain(&bs, &spec).unwrap().slices.len(), 4);
+    }
+}
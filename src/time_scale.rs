@@ -0,0 +1,169 @@
+use crate::error::BlackScholesError;
+use crate::validation;
+
+/// Calendar days per year used for discounting and rate accrual
+pub const CALENDAR_DAYS_PER_YEAR: f64 = 365.0;
+
+/// Trading days per year used by business-time volatility conventions
+pub const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// Clock on which volatility (variance) accrues
+///
+/// Interest and dividends always accrue in calendar time; the time scale only
+/// controls how days to expiry are converted into variance time and how many
+/// days a year of theta is spread over.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeScale {
+    /// Every calendar day carries variance, 365 days per year
+    #[default]
+    Calendar,
+    /// Only trading days carry variance, 252 days per year
+    Trading,
+    /// Custom annualization basis for weighted days
+    Custom { days_per_year: f64 },
+}
+
+impl TimeScale {
+    /// Custom annualization basis, validated
+    pub fn custom(days_per_year: f64) -> Result<Self, BlackScholesError> {
+        TimeScale::Custom { days_per_year }.validate()
+    }
+
+    /// Check that a custom basis is positive and finite
+    pub fn validate(self) -> Result<Self, BlackScholesError> {
+        if let TimeScale::Custom { days_per_year } = self {
+            validation::positive("Days per year", days_per_year)?;
+        }
+        Ok(self)
+    }
+
+    /// Number of variance-carrying days in one year on this scale
+    pub fn days_per_year(&self) -> f64 {
+        match self {
+            TimeScale::Calendar => CALENDAR_DAYS_PER_YEAR,
+            TimeScale::Trading => TRADING_DAYS_PER_YEAR,
+            TimeScale::Custom { days_per_year } => *days_per_year,
+        }
+    }
+
+    /// Convert a number of (weighted) days into a variance year fraction
+    pub fn year_fraction(&self, days: f64) -> f64 {
+        days / self.days_per_year()
+    }
+
+    /// Variance year fraction of a day schedule on this scale
+    pub fn vol_time(&self, schedule: &DaySchedule) -> f64 {
+        match self {
+            // Calendar time ignores day weights: every day counts fully
+            TimeScale::Calendar => self.year_fraction(schedule.calendar_days() as f64),
+            _ => self.year_fraction(schedule.weighted_days()),
+        }
+    }
+}
+
+/// Day-by-day variance weights for the calendar days remaining to expiry
+///
+/// A weight of 1.0 is a full trading day, 0.0 a weekend or holiday, 0.5 a
+/// half-day session; weights above 1.0 mark event days such as earnings.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DaySchedule {
+    weights: Vec<f64>,
+}
+
+impl DaySchedule {
+    /// Every remaining calendar day carries full weight
+    pub fn calendar(days: usize) -> Self {
+        DaySchedule {
+            weights: vec![1.0; days],
+        }
+    }
+
+    /// Weekdays carry full weight and weekends none
+    ///
+    /// # Arguments
+    /// * `days` - Calendar days to expiry
+    /// * `first_weekday` - Weekday of the first remaining day (0 = Monday, 6 = Sunday)
+    pub fn weekdays(days: usize, first_weekday: usize) -> Self {
+        let weights = (0..days)
+            .map(|d| if (first_weekday + d) % 7 < 5 { 1.0 } else { 0.0 })
+            .collect();
+        DaySchedule { weights }
+    }
+
+    /// Build a schedule from explicit per-day weights
+    pub fn from_weights(weights: Vec<f64>) -> Result<Self, BlackScholesError> {
+        let weights = weights
+            .into_iter()
+            .map(|w| validation::non_negative("Day weight", w))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(DaySchedule { weights })
+    }
+
+    /// Override the weight of one day (holiday = 0.0, half-day = 0.5)
+    pub fn set_weight(&mut self, day: usize, weight: f64) -> Result<(), BlackScholesError> {
+        let weight = validation::non_negative("Day weight", weight)?;
+        let slot = self
+            .weights
+            .get_mut(day)
+            .ok_or_else(|| BlackScholesError::invalid("Day index beyond schedule"))?;
+        *slot = weight;
+        Ok(())
+    }
+
+    /// Number of calendar days covered
+    pub fn calendar_days(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Sum of variance weights
+    pub fn weighted_days(&self) -> f64 {
+        self.weights.iter().sum()
+    }
+
+    /// Calendar year fraction used for discounting
+    pub fn rate_time(&self) -> f64 {
+        self.calendar_days() as f64 / CALENDAR_DAYS_PER_YEAR
+    }
+
+    /// Per-day weights
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weekday_schedule() {
+        // Two full weeks starting on a Monday
+        let schedule = DaySchedule::weekdays(14, 0);
+        assert_eq!(schedule.calendar_days(), 14);
+        assert_eq!(schedule.weighted_days(), 10.0);
+        assert!((TimeScale::Trading.vol_time(&schedule) - 10.0 / 252.0).abs() < 1e-15);
+        assert!((TimeScale::Calendar.vol_time(&schedule) - 14.0 / 365.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_half_days_and_holidays() {
+        let mut schedule = DaySchedule::weekdays(7, 0);
+        schedule.set_weight(0, 0.0).unwrap();
+        schedule.set_weight(4, 0.5).unwrap();
+        assert_eq!(schedule.weighted_days(), 3.5);
+        assert!(schedule.set_weight(10, 1.0).is_err());
+        assert!(schedule.set_weight(1, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_custom_basis_is_validated() {
+        assert_eq!(TimeScale::custom(260.0).unwrap().days_per_year(), 260.0);
+        for days_per_year in [0.0, -252.0, f64::NAN, f64::INFINITY] {
+            assert!(TimeScale::custom(days_per_year).is_err());
+            assert!(TimeScale::Custom { days_per_year }.validate().is_err());
+        }
+        assert_eq!(TimeScale::Trading.validate().unwrap(), TimeScale::Trading);
+    }
+}
@@ -0,0 +1,168 @@
+//! Timer options: expiry when realized variance exhausts a budget
+
+use crate::black_scholes::OptionType;
+use crate::error::BlackScholesError;
+use crate::monte_carlo::{McResult, MonteCarlo};
+use crate::multi_asset::CorrelatedPaths;
+use crate::validation;
+
+/// Daily fixings for realized variance
+const DEFAULT_OBSERVATIONS_PER_YEAR: usize = 252;
+
+/// European-style option whose expiry is set by realized variance
+///
+/// Realized variance accumulates as Σ ln(S_i/S_{i-1})² over the fixings.
+/// The option pays max(S - K, 0) for a call or max(K - S, 0) for a put on
+/// the first fixing at which the total reaches `variance_budget`, or at
+/// `max_maturity` if the budget is never spent. Buyers pay for a fixed
+/// amount of variance rather than a fixed amount of time, so the premium
+/// does not embed the implied volatility risk premium of a vanilla.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimerOption {
+    pub strike: f64,
+    /// Total realized variance to expiry, σ_target²·T_target
+    pub variance_budget: f64,
+    /// Latest expiry in years
+    pub max_maturity: f64,
+    pub observations_per_year: usize,
+}
+
+/// Monte Carlo value of a timer option with its expiry distribution
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimerValuation {
+    pub value: McResult,
+    /// Probability that the budget is spent before the maximum maturity
+    pub budget_probability: f64,
+    /// Expected expiry in years
+    pub expected_expiry: f64,
+}
+
+impl TimerOption {
+    pub fn new(strike: f64, variance_budget: f64, max_maturity: f64) -> Result<Self, BlackScholesError> {
+        Ok(TimerOption {
+            strike: validation::positive("Strike price", strike)?,
+            variance_budget: validation::positive("Variance budget", variance_budget)?,
+            max_maturity: validation::positive("Maximum maturity", max_maturity)?,
+            observations_per_year: DEFAULT_OBSERVATIONS_PER_YEAR,
+        })
+    }
+
+    /// Budget of a vanilla with volatility `target_volatility` to `target_maturity`
+    pub fn with_target(
+        strike: f64,
+        target_volatility: f64,
+        target_maturity: f64,
+        max_maturity: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let volatility = validation::positive("Target volatility", target_volatility)?;
+        let maturity = validation::positive("Target maturity", target_maturity)?;
+        TimerOption::new(strike, volatility * volatility * maturity, max_maturity)
+    }
+
+    pub fn with_observations_per_year(mut self, observations_per_year: usize) -> Result<Self, BlackScholesError> {
+        if observations_per_year == 0 {
+            return Err(BlackScholesError::invalid("Need at least one observation per year"));
+        }
+        self.observations_per_year = observations_per_year;
+        Ok(self)
+    }
+
+    /// Value on simulated paths of a single asset, discounted at the model's rate from expiry
+    pub fn price(
+        &self,
+        option_type: OptionType,
+        model: &CorrelatedPaths,
+        mc: &MonteCarlo,
+    ) -> Result<TimerValuation, BlackScholesError> {
+        if model.assets.len() != 1 {
+            return Err(BlackScholesError::invalid("Timer options need a single-asset model"));
+        }
+        let fixings = (self.max_maturity * self.observations_per_year as f64).ceil().max(1.0) as usize;
+        let times: Vec<f64> = (1..=fixings).map(|i| self.max_maturity * i as f64 / fixings as f64).collect();
+        let spot = model.assets[0].spot;
+        let mut paths = vec![vec![0.0; fixings]];
+        let (mut spent, mut expiry) = (0usize, 0.0);
+        let value = mc.estimate(1.0, |rng| {
+            model.simulate(&times, rng, &mut paths);
+            let (mut variance, mut previous) = (0.0, spot);
+            let mut exercise = fixings - 1;
+            for (k, &price) in paths[0].iter().enumerate() {
+                variance += (price / previous).ln().powi(2);
+                previous = price;
+                if variance >= self.variance_budget {
+                    spent += 1;
+                    exercise = k;
+                    break;
+                }
+            }
+            let (t, price) = (times[exercise], paths[0][exercise]);
+            expiry += t;
+            let payoff = match option_type {
+                OptionType::Call => (price - self.strike).max(0.0),
+                OptionType::Put => (self.strike - price).max(0.0),
+            };
+            (-model.rate * t).exp() * payoff
+        });
+        Ok(TimerValuation {
+            value,
+            budget_probability: spent as f64 / mc.paths as f64,
+            expected_expiry: expiry / mc.paths as f64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::heston::{Heston, HestonParams};
+    use crate::model::EuropeanModel;
+    use crate::multi_asset::Asset;
+
+    #[test]
+    fn test_zero_rates_timer_is_black_scholes_at_the_budget() {
+        // With no carry the price is a Brownian motion run on realized-variance
+        // time, so any volatility path gives the same price at the budget
+        let params = HestonParams::new(0.06, 1.5, 0.04, 0.3, -0.7).unwrap();
+        let model = CorrelatedPaths::single(0.0, Asset::heston(100.0, 0.0, params).unwrap()).unwrap();
+        let timer = TimerOption::with_target(105.0, 0.2, 1.0, 3.0).unwrap();
+        let mc = MonteCarlo::new(10_000, 21).unwrap();
+        let bs = BlackScholes::new(100.0, 105.0, 1.0, 0.0, 0.2, 0.0).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let valuation = timer.price(option_type, &model, &mc).unwrap();
+            let exact = bs.price(option_type);
+            let tolerance = 4.0 * valuation.value.std_error + 0.01 * exact;
+            assert!((valuation.value.price - exact).abs() < tolerance, "{exact} vs {valuation:?}");
+            assert!(valuation.budget_probability > 0.99, "{valuation:?}");
+            assert!(valuation.expected_expiry > 0.5 && valuation.expected_expiry < 1.2, "{valuation:?}");
+        }
+    }
+
+    #[test]
+    fn test_unreachable_budget_is_a_vanilla_at_the_cap() {
+        let params = HestonParams::new(0.04, 2.0, 0.04, 0.4, -0.6).unwrap();
+        let model = CorrelatedPaths::single(0.03, Asset::heston(100.0, 0.01, params).unwrap()).unwrap();
+        let timer = TimerOption::new(100.0, 5.0, 1.0).unwrap();
+        let valuation = timer.price(OptionType::Call, &model, &MonteCarlo::new(20_000, 4).unwrap()).unwrap();
+        let heston = Heston::new(100.0, 0.03, 0.01, params).unwrap();
+        let exact = heston.price(OptionType::Call, 100.0, 1.0);
+        let tolerance = 4.0 * valuation.value.std_error + 0.01 * exact;
+        assert!((valuation.value.price - exact).abs() < tolerance, "{exact} vs {valuation:?}");
+        assert_eq!(valuation.budget_probability, 0.0);
+        assert!((valuation.expected_expiry - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_timers() {
+        assert!(TimerOption::new(100.0, 0.0, 1.0).is_err());
+        assert!(TimerOption::new(100.0, 0.04, -1.0).is_err());
+        assert!(TimerOption::with_target(100.0, 0.2, 0.0, 1.0).is_err());
+        assert!(TimerOption::new(100.0, 0.04, 1.0).unwrap().with_observations_per_year(0).is_err());
+        let pair = vec![Asset::lognormal(100.0, 0.0, 0.2).unwrap(), Asset::lognormal(100.0, 0.0, 0.2).unwrap()];
+        let basket = CorrelatedPaths::new(0.0, pair, vec![vec![1.0, 0.0], vec![0.0, 1.0]]).unwrap();
+        let timer = TimerOption::new(100.0, 0.04, 1.0).unwrap();
+        assert!(timer.price(OptionType::Call, &basket, &MonteCarlo::new(10, 1).unwrap()).is_err());
+    }
+}
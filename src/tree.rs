@@ -0,0 +1,146 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::pde::Exercise;
+
+/// Price and lattice Greeks from a tree
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeResult {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    /// Time decay per day of the model's time scale
+    pub theta: f64,
+}
+
+/// Cox-Ross-Rubinstein binomial tree
+///
+/// Backward induction rolls between two arrays of node values, so memory
+/// grows linearly with the number of steps. The nodes at the first two time
+/// steps are kept on the way down for delta, gamma and theta.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinomialTree {
+    /// Number of time steps
+    pub steps: usize,
+}
+
+impl BinomialTree {
+    pub fn new(steps: usize) -> Result<Self, BlackScholesError> {
+        if steps < 3 {
+            return Err(BlackScholesError::invalid("Binomial tree needs at least three steps"));
+        }
+        Ok(BinomialTree { steps })
+    }
+
+    /// Price an option on the tree
+    ///
+    /// Volatility is taken on the calendar clock (`calendar_volatility`).
+    ///
+    /// # Arguments
+    /// * `model` - Market parameters and strike
+    /// * `option_type` - Type of option (Call or Put)
+    /// * `exercise` - European or American exercise
+    pub fn price(&self, model: &BlackScholes, option_type: OptionType, exercise: Exercise) -> Result<TreeResult, BlackScholesError> {
+        let n = self.steps;
+        let dt = model.time_to_expiry / n as f64;
+        let sigma = model.calendar_volatility();
+        let up = (sigma * dt.sqrt()).exp();
+        let down = 1.0 / up;
+        let growth = ((model.risk_free_rate - model.dividend_yield) * dt).exp();
+        let p = (growth - down) / (up - down);
+        if !(0.0..=1.0).contains(&p) {
+            return Err(BlackScholesError::invalid(
+                "Tree probabilities out of range; increase the number of steps",
+            ));
+        }
+        let discount = (-model.risk_free_rate * dt).exp();
+        let strike = model.strike_price;
+        let payoff = |spot: f64| match option_type {
+            OptionType::Call => (spot - strike).max(0.0),
+            OptionType::Put => (strike - spot).max(0.0),
+        };
+        let spot_at = |step: usize, j: usize| model.spot_price * up.powi(2 * j as i32 - step as i32);
+
+        let mut values: Vec<f64> = (0..=n).map(|j| payoff(spot_at(n, j))).collect();
+        let mut next = Vec::with_capacity(n + 1);
+        let mut layer_one = [0.0; 2];
+        let mut layer_two = [0.0; 3];
+        for step in (0..n).rev() {
+            std::mem::swap(&mut values, &mut next);
+            values.clear();
+            // Spots at this step rise by up² from the lowest node
+            let mut spot = spot_at(step, 0);
+            for j in 0..=step {
+                let continuation = discount * (p * next[j + 1] + (1.0 - p) * next[j]);
+                values.push(match exercise {
+                    Exercise::European => continuation,
+                    Exercise::American => continuation.max(payoff(spot)),
+                });
+                spot *= up * up;
+            }
+            match step {
+                2 => layer_two.copy_from_slice(&values),
+                1 => layer_one.copy_from_slice(&values),
+                _ => {}
+            }
+        }
+        let price = values[0];
+
+        let (s_down, s_up) = (spot_at(1, 0), spot_at(1, 1));
+        let delta = (layer_one[1] - layer_one[0]) / (s_up - s_down);
+        let (s_dd, s_mid, s_uu) = (spot_at(2, 0), spot_at(2, 1), spot_at(2, 2));
+        let delta_up = (layer_two[2] - layer_two[1]) / (s_uu - s_mid);
+        let delta_down = (layer_two[1] - layer_two[0]) / (s_mid - s_dd);
+        let gamma = (delta_up - delta_down) / (0.5 * (s_uu - s_dd));
+        // The middle node two steps ahead has today's spot
+        let theta = (layer_two[1] - price) / (2.0 * dt) / model.time_scale.days_per_year();
+
+        Ok(TreeResult {
+            price,
+            delta,
+            gamma,
+            theta,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_european_converges_to_analytic() {
+        let bs = BlackScholes::new(100.0, 105.0, 0.75, 0.04, 0.25, 0.01).unwrap();
+        let tree = BinomialTree::new(1000).unwrap();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let result = tree.price(&bs, option_type, Exercise::European).unwrap();
+            let analytic = bs.greeks(option_type);
+            assert!((result.price - bs.price(option_type)).abs() < 0.01);
+            assert!((result.delta - analytic.delta).abs() < 1e-3);
+            assert!((result.gamma - analytic.gamma).abs() < 1e-3);
+            assert!((result.theta - analytic.theta).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_american_put_matches_pde() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.05, 0.2, 0.0).unwrap();
+        let tree = BinomialTree::new(2000).unwrap();
+        let put = tree.price(&bs, OptionType::Put, Exercise::American).unwrap();
+        assert!((put.price - 6.0904).abs() < 0.005, "{}", put.price);
+        assert!(BinomialTree::new(2).is_err());
+    }
+
+    #[test]
+    fn test_fine_tree_tightens_convergence() {
+        // Long-dated American put; node values live in two rolling arrays
+        let bs = BlackScholes::new(100.0, 100.0, 10.0, 0.05, 0.2, 0.0).unwrap();
+        let coarse = BinomialTree::new(1000).unwrap().price(&bs, OptionType::Put, Exercise::American).unwrap();
+        let fine = BinomialTree::new(5_000).unwrap().price(&bs, OptionType::Put, Exercise::American).unwrap();
+        assert!((fine.price - coarse.price).abs() < 0.01);
+        let european = BinomialTree::new(5_000).unwrap().price(&bs, OptionType::Put, Exercise::European).unwrap();
+        assert!((european.price - bs.price(OptionType::Put)).abs() < 5e-3);
+        assert!(fine.price > european.price && fine.delta < 0.0 && fine.gamma > 0.0);
+    }
+}
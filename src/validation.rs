@@ -0,0 +1,171 @@
+//! Centralized input validation shared by every public entry point
+//!
+//! Policy, applied consistently across the crate:
+//!
+//! | Input                          | `Strict` (default)  | `Clamp`                |
+//! |--------------------------------|---------------------|------------------------|
+//! | NaN or ±infinity               | `NotFinite` error   | `NotFinite` error      |
+//! | Subnormal                      | `Subnormal` error   | flushed to zero        |
+//! | Must be positive, but ≤ 0      | `OutOfRange` error  | `OutOfRange` error     |
+//! | Must be non-negative, but < 0  | `OutOfRange` error  | clamped to zero        |
+//! | Outside a closed range         | `OutOfRange` error  | clamped into the range |
+//!
+//! Non-finite values are never repaired because no clamp is meaningful for
+//! them. The strictness is process-wide (`set_strictness`) and can be
+//! overridden for the current thread with `with_strictness`.
+
+use crate::error::BlackScholesError;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How recoverable invalid inputs are treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Strictness {
+    /// Reject every invalid input with an error
+    #[default]
+    Strict,
+    /// Repair subnormal and out-of-range inputs where a clamp is meaningful
+    Clamp,
+}
+
+static GLOBAL_STRICTNESS: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    static THREAD_STRICTNESS: Cell<Option<Strictness>> = const { Cell::new(None) };
+}
+
+/// Set the process-wide validation strictness
+pub fn set_strictness(strictness: Strictness) {
+    let code = match strictness {
+        Strictness::Strict => 0,
+        Strictness::Clamp => 1,
+    };
+    GLOBAL_STRICTNESS.store(code, Ordering::Relaxed);
+}
+
+/// Strictness in effect on the current thread
+pub fn strictness() -> Strictness {
+    THREAD_STRICTNESS.with(|s| s.get()).unwrap_or_else(|| {
+        match GLOBAL_STRICTNESS.load(Ordering::Relaxed) {
+            0 => Strictness::Strict,
+            _ => Strictness::Clamp,
+        }
+    })
+}
+
+/// Run `f` with a strictness override on the current thread only
+pub fn with_strictness<T, F: FnOnce() -> T>(strictness: Strictness, f: F) -> T {
+    struct Restore(Option<Strictness>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            THREAD_STRICTNESS.with(|s| s.set(self.0));
+        }
+    }
+
+    let _restore = Restore(THREAD_STRICTNESS.with(|s| s.replace(Some(strictness))));
+    f()
+}
+
+/// Reject NaN/infinite values; subnormals are rejected or flushed to zero
+pub fn finite(field: &'static str, value: f64) -> Result<f64, BlackScholesError> {
+    if !value.is_finite() {
+        return Err(BlackScholesError::NotFinite { field, value });
+    }
+    if value.is_subnormal() {
+        return match strictness() {
+            Strictness::Strict => Err(BlackScholesError::Subnormal { field, value }),
+            Strictness::Clamp => Ok(0.0),
+        };
+    }
+    Ok(value)
+}
+
+/// Require a finite, strictly positive value
+pub fn positive(field: &'static str, value: f64) -> Result<f64, BlackScholesError> {
+    let value = finite(field, value)?;
+    if value <= 0.0 {
+        return Err(BlackScholesError::OutOfRange {
+            field,
+            value,
+            requirement: "positive",
+        });
+    }
+    Ok(value)
+}
+
+/// Require a finite, non-negative value (negative values clamp to zero in `Clamp` mode)
+pub fn non_negative(field: &'static str, value: f64) -> Result<f64, BlackScholesError> {
+    let value = finite(field, value)?;
+    if value < 0.0 {
+        return match strictness() {
+            Strictness::Strict => Err(BlackScholesError::OutOfRange {
+                field,
+                value,
+                requirement: "non-negative",
+            }),
+            Strictness::Clamp => Ok(0.0),
+        };
+    }
+    Ok(value)
+}
+
+/// Require a finite value inside `[min, max]` (clamped in `Clamp` mode)
+pub fn in_range(field: &'static str, value: f64, min: f64, max: f64, requirement: &'static str) -> Result<f64, BlackScholesError> {
+    let value = finite(field, value)?;
+    if value < min || value > max {
+        return match strictness() {
+            Strictness::Strict => Err(BlackScholesError::OutOfRange {
+                field,
+                value,
+                requirement,
+            }),
+            Strictness::Clamp => Ok(value.clamp(min, max)),
+        };
+    }
+    Ok(value)
+}
+
+/// Apply `finite` to every element of a slice
+pub fn all_finite(field: &'static str, values: &[f64]) -> Result<Vec<f64>, BlackScholesError> {
+    values.iter().map(|&v| finite(field, v)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_finite_always_rejected() {
+        for mode in [Strictness::Strict, Strictness::Clamp] {
+            with_strictness(mode, || {
+                assert!(matches!(finite("x", f64::NAN), Err(BlackScholesError::NotFinite { .. })));
+                assert!(matches!(positive("x", f64::INFINITY), Err(BlackScholesError::NotFinite { .. })));
+                assert!(in_range("x", f64::NEG_INFINITY, 0.0, 1.0, "in [0, 1]").is_err());
+            });
+        }
+    }
+
+    #[test]
+    fn test_strict_rejects_and_clamp_repairs() {
+        let tiny = f64::MIN_POSITIVE / 2.0;
+        assert!(matches!(finite("x", tiny), Err(BlackScholesError::Subnormal { .. })));
+        assert!(non_negative("x", -1.0).is_err());
+        assert!(in_range("x", 1.5, -1.0, 1.0, "in [-1, 1]").is_err());
+
+        with_strictness(Strictness::Clamp, || {
+            assert_eq!(finite("x", tiny), Ok(0.0));
+            assert_eq!(non_negative("x", -1.0), Ok(0.0));
+            assert_eq!(in_range("x", 1.5, -1.0, 1.0, "in [-1, 1]"), Ok(1.0));
+            // Positivity cannot be repaired by clamping
+            assert!(positive("x", tiny).is_err());
+        });
+        assert_eq!(strictness(), Strictness::Strict);
+    }
+
+    #[test]
+    fn test_error_messages() {
+        let err = positive("Spot price", -100.0).unwrap_err();
+        assert_eq!(err.to_string(), "Spot price must be positive (got -100)");
+    }
+}
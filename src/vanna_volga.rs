@@ -0,0 +1,280 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::cross_greeks::{CrossGreeks, PricingInput};
+use crate::error::BlackScholesError;
+use crate::fx::{AtmConvention, GarmanKohlhagen};
+use crate::validation;
+
+/// Relative spot bump for the finite-difference Greeks of an exotic
+const SPOT_BUMP: f64 = 1e-4;
+
+/// Absolute volatility bump for the finite-difference Greeks of an exotic
+const VOL_BUMP: f64 = 1e-4;
+
+/// FX smile quotes at one expiry: at-the-money, 25-delta risk reversal and butterfly
+///
+/// Volatilities are decimals. The 25-delta pillars follow the simple
+/// (smile strangle) reading: σ25C = ATM + BF + RR/2 and σ25P = ATM + BF - RR/2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmileQuotes {
+    pub atm: f64,
+    /// σ25C - σ25P
+    pub risk_reversal: f64,
+    /// Average 25-delta wing volatility over the at-the-money volatility
+    pub butterfly: f64,
+}
+
+impl SmileQuotes {
+    pub fn new(atm: f64, risk_reversal: f64, butterfly: f64) -> Result<Self, BlackScholesError> {
+        let quotes = SmileQuotes {
+            atm: validation::positive("ATM volatility", atm)?,
+            risk_reversal: validation::finite("Risk reversal", risk_reversal)?,
+            butterfly: validation::finite("Butterfly", butterfly)?,
+        };
+        if quotes.call_vol() <= 0.0 || quotes.put_vol() <= 0.0 {
+            return Err(BlackScholesError::invalid("Smile quotes imply a non-positive 25-delta volatility"));
+        }
+        Ok(quotes)
+    }
+
+    /// 25-delta call volatility
+    pub fn call_vol(&self) -> f64 {
+        self.atm + self.butterfly + 0.5 * self.risk_reversal
+    }
+
+    /// 25-delta put volatility
+    pub fn put_vol(&self) -> f64 {
+        self.atm + self.butterfly - 0.5 * self.risk_reversal
+    }
+}
+
+/// Vanna-volga smile built from three pillars (Castagna-Mercurio, 2007)
+///
+/// Every option is priced at the at-the-money volatility and then adjusted
+/// by the cost of the pillar portfolio that hedges its vega, vanna and volga:
+/// the market price of those three vanillas less their flat-volatility price.
+/// Vanillas are repriced exactly at the pillars; exotics use the same hedge
+/// cost with weights solved from their own sensitivities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VannaVolga {
+    /// Market parameters at the at-the-money volatility (strike unused)
+    pub market: GarmanKohlhagen,
+    /// 25-delta put, at-the-money and 25-delta call strikes
+    pub strikes: [f64; 3],
+    /// Market volatility at each pillar strike
+    pub vols: [f64; 3],
+}
+
+impl VannaVolga {
+    /// Build the pillars from desk quotes
+    ///
+    /// # Arguments
+    /// * `market` - Spot, rates and expiry; its delta convention locates the 25-delta strikes
+    /// * `quotes` - At-the-money, risk reversal and butterfly volatilities
+    /// * `atm` - Which strike the at-the-money volatility is quoted at
+    pub fn new(market: &GarmanKohlhagen, quotes: &SmileQuotes, atm: AtmConvention) -> Result<Self, BlackScholesError> {
+        let at_vol = |volatility: f64| GarmanKohlhagen { volatility, ..*market };
+        let atm_model = at_vol(quotes.atm);
+        let strikes = [
+            at_vol(quotes.put_vol()).strike_from_delta(OptionType::Put, -0.25)?,
+            atm_model.atm_strike(atm),
+            at_vol(quotes.call_vol()).strike_from_delta(OptionType::Call, 0.25)?,
+        ];
+        if !(strikes[0] < strikes[1] && strikes[1] < strikes[2]) {
+            return Err(BlackScholesError::invalid("Pillar strikes are not increasing"));
+        }
+        Ok(VannaVolga {
+            market: atm_model,
+            strikes,
+            vols: [quotes.put_vol(), quotes.atm, quotes.call_vol()],
+        })
+    }
+
+    /// Flat at-the-money model struck at `strike`
+    fn flat(&self, strike: f64) -> BlackScholes {
+        BlackScholes {
+            strike_price: strike,
+            ..self.market.black_scholes()
+        }
+    }
+
+    /// Market less flat price of each pillar call (the same for puts by parity)
+    fn pillar_costs(&self) -> [f64; 3] {
+        [0, 1, 2].map(|i| {
+            let flat = self.flat(self.strikes[i]);
+            BlackScholes { volatility: self.vols[i], ..flat }.price(OptionType::Call) - flat.price(OptionType::Call)
+        })
+    }
+
+    /// Lagrange weights in log-strike, one at its own pillar and zero at the others
+    fn log_weights(&self, strike: f64) -> [f64; 3] {
+        let [k1, k2, k3] = self.strikes;
+        let (l1, l2, l3) = ((k1 / strike).ln(), (k2 / strike).ln(), (k3 / strike).ln());
+        [
+            l2 * l3 / ((k2 / k1).ln() * (k3 / k1).ln()),
+            -l1 * l3 / ((k2 / k1).ln() * (k3 / k2).ln()),
+            l1 * l2 / ((k3 / k1).ln() * (k3 / k2).ln()),
+        ]
+    }
+
+    /// Hedge weights of a vanilla at `strike` in the three pillars
+    ///
+    /// Closed form from matching vega, vanna and volga at the flat volatility.
+    pub fn weights(&self, strike: f64) -> Result<[f64; 3], BlackScholesError> {
+        let strike = validation::positive("Strike price", strike)?;
+        let y = self.log_weights(strike);
+        let vega = |k: f64| self.flat(k).greeks(OptionType::Call).vega;
+        let target = vega(strike);
+        Ok([0, 1, 2].map(|i| target / vega(self.strikes[i]) * y[i]))
+    }
+
+    /// Vanna-volga price of a vanilla in domestic currency per unit of foreign notional
+    pub fn price(&self, strike: f64, option_type: OptionType) -> Result<f64, BlackScholesError> {
+        let weights = self.weights(strike)?;
+        let costs = self.pillar_costs();
+        let adjustment: f64 = weights.iter().zip(&costs).map(|(x, c)| x * c).sum();
+        Ok(self.flat(strike).price(option_type) + adjustment)
+    }
+
+    /// Smile volatility at `strike` from the second-order Castagna-Mercurio approximation
+    ///
+    /// Reproduces the three pillar volatilities exactly. Far in the wings the
+    /// approximation can break down, which is reported as an error.
+    pub fn smile_vol(&self, strike: f64) -> Result<f64, BlackScholesError> {
+        let strike = validation::positive("Strike price", strike)?;
+        let y = self.log_weights(strike);
+        let atm = self.vols[1];
+        let s = atm * self.market.expiry.sqrt();
+        let forward = self.market.forward();
+        let d1d2 = |k: f64| {
+            let d1 = ((forward / k).ln() + 0.5 * s * s) / s;
+            d1 * (d1 - s)
+        };
+        let first: f64 = y.iter().zip(&self.vols).map(|(y, v)| y * v).sum::<f64>() - atm;
+        let second: f64 = (0..3).map(|i| y[i] * d1d2(self.strikes[i]) * (self.vols[i] - atm).powi(2)).sum();
+        let dd = d1d2(strike);
+        if dd.abs() < 1e-12 {
+            return Ok(atm + first);
+        }
+        let radicand = atm * atm + dd * (2.0 * atm * first + second);
+        if radicand < 0.0 {
+            return Err(BlackScholesError::invalid("Vanna-volga smile is undefined at this strike"));
+        }
+        Ok(atm + (radicand.sqrt() - atm) / dd)
+    }
+
+    /// Smile cost of a position with the given raw sensitivities at the flat volatility
+    ///
+    /// Solves for the pillar portfolio with the same vega (∂V/∂σ), vanna
+    /// (∂²V/∂S∂σ) and volga (∂²V/∂σ²) and returns its market less flat value.
+    /// Desks often scale this by a survival probability for knock-out options.
+    pub fn smile_cost(&self, vega: f64, vanna: f64, volga: f64) -> Result<f64, BlackScholesError> {
+        let columns = self.strikes.map(|k| {
+            let cross = CrossGreeks::new(&self.flat(k), OptionType::Call);
+            [cross.first(PricingInput::Volatility), cross.vanna(), cross.volga()]
+        });
+        let target = [vega, vanna, volga];
+        // Cramer's rule on the 3×3 system Σ x_i·column_i = target
+        let det = |a: [f64; 3], b: [f64; 3], c: [f64; 3]| {
+            a[0] * (b[1] * c[2] - b[2] * c[1]) - b[0] * (a[1] * c[2] - a[2] * c[1]) + c[0] * (a[1] * b[2] - a[2] * b[1])
+        };
+        let denominator = det(columns[0], columns[1], columns[2]);
+        if denominator.abs() < 1e-300 {
+            return Err(BlackScholesError::invalid("Pillar sensitivities are degenerate"));
+        }
+        let weights = [
+            det(target, columns[1], columns[2]),
+            det(columns[0], target, columns[2]),
+            det(columns[0], columns[1], target),
+        ]
+        .map(|d| d / denominator);
+        Ok(weights.iter().zip(&self.pillar_costs()).map(|(x, c)| x * c).sum())
+    }
+
+    /// Vanna-volga price of an exotic
+    ///
+    /// `flat_price` prices the exotic under a flat Garman-Kohlhagen model; it is
+    /// called at the at-the-money volatility and at bumped spots and volatilities
+    /// to measure vega, vanna and volga.
+    pub fn price_exotic<F: Fn(&GarmanKohlhagen) -> f64>(&self, flat_price: F) -> Result<f64, BlackScholesError> {
+        let h = SPOT_BUMP * self.market.spot;
+        let at = |ds: f64, dv: f64| {
+            flat_price(&GarmanKohlhagen {
+                spot: self.market.spot + ds,
+                volatility: self.market.volatility + dv,
+                ..self.market
+            })
+        };
+        let price = at(0.0, 0.0);
+        let (up, down) = (at(0.0, VOL_BUMP), at(0.0, -VOL_BUMP));
+        let vega = (up - down) / (2.0 * VOL_BUMP);
+        let volga = (up - 2.0 * price + down) / (VOL_BUMP * VOL_BUMP);
+        let vanna = (at(h, VOL_BUMP) - at(h, -VOL_BUMP) - at(-h, VOL_BUMP) + at(-h, -VOL_BUMP)) / (4.0 * h * VOL_BUMP);
+        if !(price.is_finite() && vega.is_finite() && vanna.is_finite() && volga.is_finite()) {
+            return Err(BlackScholesError::invalid("Flat exotic price is not finite"));
+        }
+        Ok(price + self.smile_cost(vega, vanna, volga)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::barrier::{BarrierOption, BarrierType};
+
+    fn smile() -> VannaVolga {
+        let market = GarmanKohlhagen::new(1.10, 1.10, 0.5, 0.045, 0.03, 0.08).unwrap();
+        let quotes = SmileQuotes::new(0.08, -0.01, 0.003).unwrap();
+        VannaVolga::new(&market, &quotes, AtmConvention::DeltaNeutral).unwrap()
+    }
+
+    #[test]
+    fn test_pillars_reprice_exactly() {
+        let vv = smile();
+        for (strike, vol) in vv.strikes.iter().zip(&vv.vols) {
+            let model = vv.market.with_strike(*strike).unwrap();
+            let market_price = GarmanKohlhagen { volatility: *vol, ..model }.price(OptionType::Put);
+            assert!((vv.price(*strike, OptionType::Put).unwrap() - market_price).abs() < 1e-14);
+            assert!((vv.smile_vol(*strike).unwrap() - vol).abs() < 1e-12);
+        }
+        // The 25-delta put sits below the forward, the call above
+        assert!(vv.strikes[0] < vv.market.forward() && vv.strikes[2] > vv.market.forward());
+    }
+
+    #[test]
+    fn test_smile_shape_follows_quotes() {
+        let vv = smile();
+        // Negative risk reversal: downside wing richer than upside
+        let (low, high) = (vv.smile_vol(0.98).unwrap(), vv.smile_vol(1.22).unwrap());
+        assert!(low > vv.vols[0] && high < low);
+        // Between pillars the price-implied vol and the approximation agree closely
+        let strike = 1.07;
+        let price = vv.price(strike, OptionType::Call).unwrap();
+        let implied = vv.market.with_strike(strike).unwrap().implied_volatility(OptionType::Call, price).unwrap();
+        assert!((implied - vv.smile_vol(strike).unwrap()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_exotic_weights_match_vanilla() {
+        let vv = smile();
+        let strike = 1.15;
+        let vanilla = vv.price_exotic(|gk| gk.with_strike(strike).unwrap().price(OptionType::Call)).unwrap();
+        assert!((vanilla - vv.price(strike, OptionType::Call).unwrap()).abs() < 1e-7);
+
+        // Lower upside vols make the up-and-out call less likely to knock out
+        let knock_out = |gk: &GarmanKohlhagen| {
+            let model = gk.with_strike(1.10).unwrap().black_scholes();
+            BarrierOption::new(model, BarrierType::UpAndOut, 1.20, 0.0).unwrap().price(OptionType::Call)
+        };
+        let flat = knock_out(&vv.market);
+        assert!(vv.price_exotic(knock_out).unwrap() > flat);
+    }
+
+    #[test]
+    fn test_invalid_quotes() {
+        assert!(SmileQuotes::new(0.05, 0.2, 0.0).is_err());
+        assert!(SmileQuotes::new(0.0, 0.0, 0.0).is_err());
+        assert!(smile().weights(-1.0).is_err());
+    }
+}
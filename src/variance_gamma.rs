@@ -0,0 +1,147 @@
+//! Variance Gamma pure-jump model priced through its characteristic function
+
+use crate::black_scholes::OptionType;
+use crate::characteristic::CharacteristicFunction;
+use crate::error::BlackScholesError;
+use crate::fourier::{CosMethod, TransformPricer};
+use crate::math::Complex;
+use crate::model::EuropeanModel;
+use crate::validation;
+
+/// Variance Gamma model of Madan, Carr & Chang (1998)
+///
+/// The log-price is a Brownian motion with drift θ and volatility σ run on a
+/// gamma clock of unit mean rate and variance rate ν, plus the compensator
+/// ω = ln(1 - θν - σ²ν/2)/ν that keeps the discounted spot a martingale.
+/// θ < 0 skews returns to the left and ν adds kurtosis; ν → 0 is
+/// Black-Scholes with volatility σ.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VarianceGamma {
+    pub spot: f64,
+    pub rate: f64,
+    pub dividend_yield: f64,
+    /// Volatility of the subordinated Brownian motion (σ)
+    pub volatility: f64,
+    /// Drift of the subordinated Brownian motion (θ)
+    pub drift: f64,
+    /// Variance rate of the gamma clock (ν)
+    pub variance_rate: f64,
+}
+
+impl VarianceGamma {
+    pub fn new(
+        spot: f64,
+        rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+        drift: f64,
+        variance_rate: f64,
+    ) -> Result<Self, BlackScholesError> {
+        let model = VarianceGamma {
+            spot: validation::positive("Spot price", spot)?,
+            rate: validation::finite("Risk-free rate", rate)?,
+            dividend_yield: validation::finite("Dividend yield", dividend_yield)?,
+            volatility: validation::positive("Volatility", volatility)?,
+            drift: validation::finite("Drift", drift)?,
+            variance_rate: validation::positive("Variance rate", variance_rate)?,
+        };
+        if model.moment_base() <= 0.0 {
+            return Err(BlackScholesError::invalid("Variance Gamma needs 1 - θν - σ²ν/2 > 0 for a finite forward"));
+        }
+        Ok(model)
+    }
+
+    /// Martingale correction ω added to the log-price drift
+    pub fn compensator(&self) -> f64 {
+        self.moment_base().ln() / self.variance_rate
+    }
+
+    fn moment_base(&self) -> f64 {
+        1.0 - self.drift * self.variance_rate - 0.5 * self.volatility * self.volatility * self.variance_rate
+    }
+}
+
+impl CharacteristicFunction for VarianceGamma {
+    /// e^(iu(r - q + ω)t)·(1 - iuθν + σ²νu²/2)^(-t/ν)
+    fn char_fn(&self, u: Complex, t: f64) -> Complex {
+        let nu = self.variance_rate;
+        let drift = Complex::I * u * ((self.rate - self.dividend_yield + self.compensator()) * t);
+        let base = 1.0 - Complex::I * u * (self.drift * nu) + 0.5 * self.volatility * self.volatility * nu * u * u;
+        (drift - base.ln() * (t / nu)).exp()
+    }
+}
+
+impl EuropeanModel for VarianceGamma {
+    fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    fn rate(&self, _expiry: f64) -> f64 {
+        self.rate
+    }
+
+    fn dividend_yield(&self, _expiry: f64) -> f64 {
+        self.dividend_yield
+    }
+
+    /// COS price; use a [`TransformPricer`] directly to price a whole strike strip
+    fn price(&self, option_type: OptionType, strike: f64, expiry: f64) -> f64 {
+        CosMethod::default().prices(self, option_type, expiry, &[strike])[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::fourier::CarrMadan;
+    use crate::moments::risk_neutral_moments;
+
+    fn vg() -> VarianceGamma {
+        VarianceGamma::new(100.0, 0.03, 0.01, 0.2, -0.15, 0.25).unwrap()
+    }
+
+    #[test]
+    fn test_small_variance_rate_is_black_scholes() {
+        let model = VarianceGamma::new(100.0, 0.03, 0.01, 0.2, 0.0, 1e-6).unwrap();
+        for strike in [80.0, 100.0, 125.0] {
+            let bs = BlackScholes::new(100.0, strike, 0.5, 0.03, 0.2, 0.01).unwrap();
+            assert!((model.price(OptionType::Call, strike, 0.5) - bs.price(OptionType::Call)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_cos_and_fft_agree_with_parity() {
+        let model = vg();
+        let strikes = [70.0, 90.0, 100.0, 110.0, 140.0];
+        let calls = CosMethod::default().prices(&model, OptionType::Call, 1.0, &strikes);
+        let fft = CarrMadan::default().prices(&model, OptionType::Call, 1.0, &strikes);
+        let puts = CosMethod::default().prices(&model, OptionType::Put, 1.0, &strikes);
+        for (((&strike, call), f), put) in strikes.iter().zip(&calls).zip(&fft).zip(&puts) {
+            assert!((call - f).abs() < 1e-4, "{strike}: {call} vs {f}");
+            let parity = 100.0 * (-0.01_f64).exp() - strike * (-0.03_f64).exp();
+            assert!((call - put - parity).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_moments_and_martingale() {
+        let model = vg();
+        let phi = model.char_fn(-Complex::I, 2.0);
+        assert!((phi.re - (0.02_f64 * 2.0).exp()).abs() < 1e-12 && phi.im.abs() < 1e-12);
+
+        // Var = (σ² + νθ²)t, and a negative θ skews left
+        let moments = risk_neutral_moments(&model, 1.0);
+        assert!((moments.variance - (0.04 + 0.25 * 0.0225)).abs() < 1e-10);
+        assert!(moments.skewness < 0.0 && moments.excess_kurtosis() > 0.0);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(VarianceGamma::new(100.0, 0.03, 0.0, 0.0, 0.0, 0.2).is_err());
+        assert!(VarianceGamma::new(100.0, 0.03, 0.0, 0.2, 0.0, -0.2).is_err());
+        // 1 - θν - σ²ν/2 ≤ 0: E[S_T] is infinite
+        assert!(VarianceGamma::new(100.0, 0.03, 0.0, 0.2, 2.0, 0.5).is_err());
+    }
+}
@@ -0,0 +1,570 @@
+//! Variance, gamma and corridor variance swaps replicated from a strip of European options,
+//! and volatility swap strikes from variance strikes
+
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+use crate::math::gauss_legendre;
+use crate::model::EuropeanModel;
+use crate::monte_carlo::{McResult, MonteCarlo};
+use crate::multi_asset::CorrelatedPaths;
+use crate::validation;
+use std::f64::consts::PI;
+
+/// Gauss-Legendre panels on each side of the forward
+const PANELS: usize = 24;
+
+/// Nodes per panel
+const PANEL_NODES: usize = 16;
+
+/// Half-width of the log-strike range in at-the-money standard deviations
+const STRIP_WIDTH: f64 = 16.0;
+
+/// Smallest half-width of the log-strike range
+const MIN_STRIP_WIDTH: f64 = 0.05;
+
+/// Gauss-Legendre nodes in time for the expected time spent in a corridor
+const OCCUPATION_NODES: usize = 24;
+
+/// Relative strike bump for digital probabilities from put prices
+const DIGITAL_BUMP: f64 = 1e-4;
+
+/// How realized variance is weighted along the path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VarianceWeighting {
+    /// Each squared return counts equally
+    Plain,
+    /// Each squared return is weighted by the price's performance since trade date
+    Gamma,
+}
+
+/// Range of the underlying inside which variance accrues
+///
+/// Monitored on F_t, the forward to the swap's maturity, which is the spot
+/// itself when carry is zero: only then does a fixed range on the forward
+/// have static replication. `upper` may be infinite for down-variance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Corridor {
+    pub lower: f64,
+    pub upper: f64,
+    /// Annualise over the time spent inside the corridor rather than the whole period
+    pub conditional: bool,
+}
+
+/// Variance or gamma swap over [start, maturity]
+///
+/// Pays notional × (realized variance - strike) at maturity, with variance
+/// annualised over the accrual period. A forward-starting swap accrues only
+/// from `start`. Gamma swaps weight each squared return by F_t/F_0, the
+/// performance of the forward since trade date, which is the spot's
+/// performance when carry is zero; the weights are fixed today rather than
+/// reset at `start`, so forward-starting gamma variance is still spanned by
+/// vanillas. A corridor swap accrues only returns starting inside its range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VarianceSwap {
+    /// Start of accrual in years; zero for a spot-starting swap
+    pub start: f64,
+    /// End of accrual and payment time in years
+    pub maturity: f64,
+    pub weighting: VarianceWeighting,
+    pub corridor: Option<Corridor>,
+}
+
+impl VarianceSwap {
+    /// Spot-starting variance swap
+    pub fn new(maturity: f64) -> Result<Self, BlackScholesError> {
+        VarianceSwap::forward_starting(0.0, maturity)
+    }
+
+    /// Variance swap accruing from `start` to `maturity`
+    pub fn forward_starting(start: f64, maturity: f64) -> Result<Self, BlackScholesError> {
+        let start = validation::non_negative("Start time", start)?;
+        let maturity = validation::positive("Maturity", maturity)?;
+        if maturity <= start {
+            return Err(BlackScholesError::invalid("Maturity must be after the start of accrual"));
+        }
+        Ok(VarianceSwap {
+            start,
+            maturity,
+            weighting: VarianceWeighting::Plain,
+            corridor: None,
+        })
+    }
+
+    /// Weight squared returns by price performance
+    pub fn gamma(mut self) -> Self {
+        self.weighting = VarianceWeighting::Gamma;
+        self
+    }
+
+    /// Accrue variance only while the forward is within [lower, upper]
+    pub fn with_corridor(self, lower: f64, upper: f64) -> Result<Self, BlackScholesError> {
+        self.corridor(lower, upper, false)
+    }
+
+    /// Corridor swap annualised over the time spent inside the corridor
+    pub fn with_conditional_corridor(self, lower: f64, upper: f64) -> Result<Self, BlackScholesError> {
+        self.corridor(lower, upper, true)
+    }
+
+    fn corridor(mut self, lower: f64, upper: f64, conditional: bool) -> Result<Self, BlackScholesError> {
+        let lower = validation::non_negative("Corridor lower bound", lower)?;
+        if upper.is_nan() || upper <= lower {
+            return Err(BlackScholesError::invalid("Corridor upper bound must be above the lower bound"));
+        }
+        self.corridor = Some(Corridor {
+            lower,
+            upper,
+            conditional,
+        });
+        Ok(self)
+    }
+
+    /// Fair strike in annualised variance units
+    ///
+    /// Expected weighted variance to each end of the accrual period, from
+    /// the replicating strip at that expiry truncated to the corridor,
+    /// differenced and divided by the period's length. A conditional swap
+    /// divides instead by the expected time inside the corridor, the usual
+    /// approximation that ignores the covariance between the two.
+    pub fn fair_variance<M: EuropeanModel + ?Sized>(&self, model: &M) -> Result<f64, BlackScholesError> {
+        let end = self.replicated_variance(model, self.maturity);
+        let start = if self.start > 0.0 {
+            self.replicated_variance(model, self.start)
+        } else {
+            0.0
+        };
+        let accrual = match self.corridor {
+            Some(corridor) if corridor.conditional => self.expected_occupation(model, &corridor),
+            _ => self.maturity - self.start,
+        };
+        if accrual <= 0.0 {
+            return Err(BlackScholesError::invalid("Corridor is never reached"));
+        }
+        let variance = (end - start) / accrual;
+        if !variance.is_finite() || variance < 0.0 {
+            return Err(BlackScholesError::invalid("Model implies negative forward variance"));
+        }
+        Ok(variance)
+    }
+
+    /// Fair strike quoted as a volatility, √(fair variance)
+    pub fn fair_volatility<M: EuropeanModel + ?Sized>(&self, model: &M) -> Result<f64, BlackScholesError> {
+        Ok(self.fair_variance(model)?.sqrt())
+    }
+
+    /// Value before accrual starts of receiving realized variance against `strike_variance`
+    pub fn value<M: EuropeanModel + ?Sized>(
+        &self,
+        model: &M,
+        strike_variance: f64,
+        notional: f64,
+    ) -> Result<f64, BlackScholesError> {
+        let strike_variance = validation::non_negative("Variance strike", strike_variance)?;
+        let notional = validation::finite("Notional", notional)?;
+        let discount = (-model.rate(self.maturity) * self.maturity).exp();
+        Ok(notional * discount * (self.fair_variance(model)? - strike_variance))
+    }
+
+    /// Fair strike from simulated paths of a single asset, as a cross-check
+    ///
+    /// Realized variance is summed over `observations_per_year` fixings a
+    /// year with the same weights and corridor, so it differs from the
+    /// replicated strike only by discretisation and sampling error. For a
+    /// conditional swap each path is annualised over its own time inside,
+    /// which the replicated strike only approximates.
+    pub fn monte_carlo(
+        &self,
+        model: &CorrelatedPaths,
+        mc: &MonteCarlo,
+        observations_per_year: usize,
+    ) -> Result<McResult, BlackScholesError> {
+        if model.assets.len() != 1 {
+            return Err(BlackScholesError::invalid("Variance swaps need a single-asset model"));
+        }
+        if observations_per_year == 0 {
+            return Err(BlackScholesError::invalid("Need at least one observation per year"));
+        }
+        let asset = &model.assets[0];
+        let carry = model.rate - asset.dividend_yield;
+        let grid = |from: f64, to: f64| ((to - from) * observations_per_year as f64).ceil().max(1.0) as usize;
+        let before = if self.start > 0.0 { grid(0.0, self.start) } else { 0 };
+        let during = grid(self.start, self.maturity);
+        let mut times: Vec<f64> = (1..=before).map(|i| self.start * i as f64 / before as f64).collect();
+        let step = (self.maturity - self.start) / during as f64;
+        times.extend((1..=during).map(|i| self.start + step * i as f64));
+
+        let forward = |spot: f64, t: f64| spot * (carry * (self.maturity - t)).exp();
+        let initial = forward(asset.spot, 0.0);
+        let mut paths = vec![vec![0.0; times.len()]];
+        Ok(mc.estimate(1.0, |rng| {
+            model.simulate(&times, rng, &mut paths);
+            let (mut sum, mut inside) = (0.0, 0usize);
+            let (mut spot, mut t) = (asset.spot, 0.0);
+            for (k, (&next, &time)) in paths[0].iter().zip(&times).enumerate() {
+                let level = forward(spot, t);
+                let counted = k >= before && self.corridor.is_none_or(|c| level >= c.lower && level <= c.upper);
+                if counted {
+                    let weight = match self.weighting {
+                        VarianceWeighting::Plain => 1.0,
+                        VarianceWeighting::Gamma => level / initial,
+                    };
+                    sum += weight * (next / spot).ln().powi(2);
+                    inside += 1;
+                }
+                (spot, t) = (next, time);
+            }
+            match self.corridor {
+                Some(c) if c.conditional => {
+                    if inside == 0 {
+                        0.0
+                    } else {
+                        sum / (inside as f64 * step)
+                    }
+                }
+                _ => sum / (self.maturity - self.start),
+            }
+        }))
+    }
+
+    /// Expected weighted total variance to `expiry` from out-of-the-money options
+    ///
+    /// Plain: 2·e^(rT)·∫ Q(K)/K² dK, the log contract. Gamma: (2/F)·e^(rT)·∫ Q(K)/K dK,
+    /// the S·ln S contract. Q is the put below the forward and the call above;
+    /// the integrals run in log-strike over Gauss-Legendre panels, truncated
+    /// to the corridor rescaled from the maturity forward to this expiry's.
+    fn replicated_variance<M: EuropeanModel + ?Sized>(&self, model: &M, expiry: f64) -> f64 {
+        let forward = model.forward(expiry);
+        let growth = (model.rate(expiry) * expiry).exp();
+        // Brenner-Subrahmanyam: an at-the-money call is about F·σ√T/√(2π)
+        let atm = growth * model.price(OptionType::Call, forward, expiry) / forward;
+        let width = (STRIP_WIDTH * (2.0 * PI).sqrt() * atm).max(MIN_STRIP_WIDTH);
+        let (mut lo, mut hi) = (-width, width);
+        if let Some(corridor) = self.corridor {
+            let scale = forward / model.forward(self.maturity);
+            lo = lo.max((corridor.lower * scale / forward).ln());
+            hi = hi.min((corridor.upper * scale / forward).ln());
+        }
+
+        let (nodes, weights) = gauss_legendre(PANEL_NODES);
+        let mut total = 0.0;
+        // Separate panel sets either side of the forward, where the integrand has a kink
+        for (from, to) in [(lo, hi.min(0.0)), (lo.max(0.0), hi)] {
+            if to <= from {
+                continue;
+            }
+            let panel = (to - from) / PANELS as f64;
+            for p in 0..PANELS {
+                let left = from + p as f64 * panel;
+                for (node, weight) in nodes.iter().zip(&weights) {
+                    let x = left + 0.5 * panel * (node + 1.0);
+                    let strike = forward * x.exp();
+                    let option_type = if x < 0.0 { OptionType::Put } else { OptionType::Call };
+                    let otm = growth * model.price(option_type, strike, expiry);
+                    // dK = K·dx, so Q/K² dK = Q/K dx and Q/K dK = Q dx
+                    let integrand = match self.weighting {
+                        VarianceWeighting::Plain => otm / strike,
+                        VarianceWeighting::Gamma => otm / forward,
+                    };
+                    total += 0.5 * panel * weight * integrand;
+                }
+            }
+        }
+        2.0 * total
+    }
+
+    /// Expected years within [start, maturity] that the maturity forward spends inside the corridor
+    ///
+    /// At each time the corridor maps to a spot range, whose probability
+    /// comes from digital puts: P(S_t < K) = e^(rt)·∂P/∂K.
+    fn expected_occupation<M: EuropeanModel + ?Sized>(&self, model: &M, corridor: &Corridor) -> f64 {
+        let below = |strike: f64, t: f64| {
+            if strike <= 0.0 {
+                return 0.0;
+            }
+            if strike.is_infinite() {
+                return 1.0;
+            }
+            let h = DIGITAL_BUMP * strike;
+            let (up, down) = (model.price(OptionType::Put, strike + h, t), model.price(OptionType::Put, strike - h, t));
+            ((model.rate(t) * t).exp() * (up - down) / (2.0 * h)).clamp(0.0, 1.0)
+        };
+        let (nodes, weights) = gauss_legendre(OCCUPATION_NODES);
+        let half = 0.5 * (self.maturity - self.start);
+        nodes
+            .iter()
+            .zip(&weights)
+            .map(|(node, weight)| {
+                let t = self.start + half * (node + 1.0);
+                let scale = model.forward(t) / model.forward(self.maturity);
+                half * weight * (below(corridor.upper * scale, t) - below(corridor.lower * scale, t))
+            })
+            .sum()
+    }
+}
+
+/// Out-of-the-money options at one expiry, the discrete replicating strip of a variance swap
+///
+/// Quotes are puts below the forward and calls at or above it, as
+/// `(strike, price)` pairs with prices discounted to today.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptionStrip {
+    pub forward: f64,
+    /// Time to expiration in years
+    pub expiry: f64,
+    /// Continuously compounded risk-free rate (annual)
+    pub rate: f64,
+    /// `(strike, out-of-the-money price)` pairs, sorted by strike
+    pub quotes: Vec<(f64, f64)>,
+}
+
+impl OptionStrip {
+    /// Create a strip from out-of-the-money prices (sorted internally by strike)
+    ///
+    /// # Arguments
+    /// * `forward` - Forward price for the expiry
+    /// * `expiry` - Time to expiration in years
+    /// * `rate` - Continuously compounded rate used to discount the prices
+    /// * `quotes` - `(strike, price)` pairs: puts below the forward, calls at or above it
+    pub fn new(forward: f64, expiry: f64, rate: f64, mut quotes: Vec<(f64, f64)>) -> Result<Self, BlackScholesError> {
+        let forward = validation::positive("Forward", forward)?;
+        let expiry = validation::positive("Time to expiry", expiry)?;
+        let rate = validation::finite("Risk-free rate", rate)?;
+        for &(strike, price) in &quotes {
+            validation::positive("Strike price", strike)?;
+            validation::non_negative("Option price", price)?;
+        }
+        quotes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if quotes.len() < 3 {
+            return Err(BlackScholesError::invalid("Need at least three OTM quotes for strip variance"));
+        }
+        if quotes.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err(BlackScholesError::invalid("Duplicate strike in option strip"));
+        }
+        Ok(OptionStrip {
+            forward,
+            expiry,
+            rate,
+            quotes,
+        })
+    }
+
+    /// Strip priced off an implied volatility smile given as `(strike, implied_vol)` pairs
+    pub fn from_smile(forward: f64, expiry: f64, rate: f64, smile: &[(f64, f64)]) -> Result<Self, BlackScholesError> {
+        let forward = validation::positive("Forward", forward)?;
+        let quotes = smile
+            .iter()
+            .map(|&(strike, vol)| {
+                // A dividend yield equal to the rate makes the spot the forward
+                let model = BlackScholes::new(forward, strike, expiry, rate, vol, rate)?;
+                let option_type = if strike < forward { OptionType::Put } else { OptionType::Call };
+                Ok((strike, model.price(option_type)))
+            })
+            .collect::<Result<Vec<_>, BlackScholesError>>()?;
+        OptionStrip::new(forward, expiry, rate, quotes)
+    }
+
+    /// Model-free fair variance strike by discretized Carr-Madan replication
+    ///
+    /// σ² = (2/T)·e^(rT)·Σ ΔK/K²·Q(K) - (1/T)·(F/K₀ - 1)², where K₀ is the
+    /// last strike at or below the forward and Q(K₀) averages the put and
+    /// call there, the call following from the put by parity. ΔK is half
+    /// the gap between neighbouring strikes, the full gap at the ends.
+    /// Truncation of the strip biases the strike down, so the wings should
+    /// reach well into the tails.
+    pub fn fair_variance(&self) -> f64 {
+        let (q, t) = (&self.quotes, self.expiry);
+        let n = q.len();
+        let growth = (self.rate * t).exp();
+        let k0 = q.iter().rev().map(|q| q.0).find(|&k| k <= self.forward).unwrap_or(q[0].0);
+        let weighted: f64 = (0..n)
+            .map(|i| {
+                let dk = match i {
+                    0 => q[1].0 - q[0].0,
+                    _ if i == n - 1 => q[n - 1].0 - q[n - 2].0,
+                    _ => 0.5 * (q[i + 1].0 - q[i - 1].0),
+                };
+                let mut price = q[i].1;
+                if q[i].0 == k0 && k0 < self.forward {
+                    price += 0.5 * (self.forward - k0) / growth;
+                }
+                dk / (q[i].0 * q[i].0) * price
+            })
+            .sum();
+        2.0 / t * growth * weighted - (self.forward / k0 - 1.0).powi(2) / t
+    }
+
+    /// Fair strike quoted as a volatility, √(fair variance)
+    pub fn fair_volatility(&self) -> f64 {
+        self.fair_variance().max(0.0).sqrt()
+    }
+}
+
+/// Fair variance strike under Black-Scholes with flat volatility: σ² for any expiry and carry
+///
+/// The special case every replication should reproduce on a flat smile.
+pub fn flat_fair_variance(volatility: f64) -> Result<f64, BlackScholesError> {
+    Ok(validation::non_negative("Volatility", volatility)?.powi(2))
+}
+
+/// Volatility swap strike from the variance strike with a convexity adjustment
+///
+/// E[√V] ≈ √E[V] - Var[V]/(8·E[V]^(3/2)) (Brockhaus-Long, 2000): realized
+/// volatility is a concave function of realized variance, so the fair
+/// volatility strike sits below the square root of the variance strike by
+/// an amount growing with the variance of realized variance.
+///
+/// # Arguments
+/// * `fair_variance` - Variance swap fair strike E[V], annualised
+/// * `variance_of_variance` - Variance of annualised realized variance Var[V]
+pub fn volatility_swap_strike(fair_variance: f64, variance_of_variance: f64) -> Result<f64, BlackScholesError> {
+    let fair_variance = validation::positive("Fair variance", fair_variance)?;
+    let variance_of_variance = validation::non_negative("Variance of variance", variance_of_variance)?;
+    Ok(fair_variance.sqrt() - variance_of_variance / (8.0 * fair_variance.powf(1.5)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::BlackScholes;
+    use crate::heston::{Heston, HestonParams};
+    use crate::math::norm_cdf;
+    use crate::multi_asset::Asset;
+
+    /// E[∫₀ᵗ v ds] under Heston mean reversion
+    fn expected_variance(v0: f64, kappa: f64, theta: f64, t: f64) -> f64 {
+        theta * t + (v0 - theta) * (1.0 - (-kappa * t).exp()) / kappa
+    }
+
+    #[test]
+    fn test_flat_volatility_strikes_are_sigma_squared() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.25, 0.01).unwrap();
+        let plain = VarianceSwap::new(2.0).unwrap();
+        assert!((plain.fair_variance(&bs).unwrap() - 0.0625).abs() < 1e-6);
+        assert!((plain.gamma().fair_variance(&bs).unwrap() - 0.0625).abs() < 1e-6);
+        let forward = VarianceSwap::forward_starting(0.5, 1.5).unwrap();
+        assert!((forward.fair_volatility(&bs).unwrap() - 0.25).abs() < 1e-6);
+        assert!((plain.value(&bs, 0.0625, 1000.0).unwrap()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_heston_strikes_match_expected_variance() {
+        let (v0, kappa, theta, xi, rho) = (0.06, 1.5, 0.04, 0.6, -0.7);
+        let model = Heston::new(100.0, 0.02, 0.01, HestonParams::new(v0, kappa, theta, xi, rho).unwrap()).unwrap();
+
+        let plain = VarianceSwap::new(1.0).unwrap().fair_variance(&model).unwrap();
+        assert!((plain - expected_variance(v0, kappa, theta, 1.0)).abs() < 1e-4, "{plain}");
+
+        let forward = VarianceSwap::forward_starting(1.0, 2.0).unwrap().fair_variance(&model).unwrap();
+        let exact = expected_variance(v0, kappa, theta, 2.0) - expected_variance(v0, kappa, theta, 1.0);
+        assert!((forward - exact).abs() < 1e-4, "{forward} vs {exact}");
+
+        // Weighting by F_t/F_0 moves to the share measure: κ* = κ - ρξ, κ*θ* = κθ
+        let gamma = VarianceSwap::new(1.0).unwrap().gamma().fair_variance(&model).unwrap();
+        let kappa_star = kappa - rho * xi;
+        let exact = expected_variance(v0, kappa_star, kappa * theta / kappa_star, 1.0);
+        assert!((gamma - exact).abs() < 1e-4, "{gamma} vs {exact}");
+        assert!(gamma < plain);
+    }
+
+    #[test]
+    fn test_corridor_on_flat_volatility_accrues_with_occupation() {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.0, 0.3, 0.0).unwrap();
+        let swap = VarianceSwap::new(1.0).unwrap();
+        // Down- and up-variance split the full strike at any level
+        let down = swap.with_corridor(0.0, 100.0).unwrap().fair_variance(&bs).unwrap();
+        let up = swap.with_corridor(100.0, f64::INFINITY).unwrap().fair_variance(&bs).unwrap();
+        assert!((down + up - 0.09).abs() < 1e-6);
+
+        // σ² times the expected fraction of time in the range: ∫ P(90 ≤ S_t ≤ 110) dt
+        let corridor = swap.with_corridor(90.0, 110.0).unwrap().fair_variance(&bs).unwrap();
+        let occupation: f64 = (0..2000)
+            .map(|i| {
+                let t = (i as f64 + 0.5) / 2000.0;
+                let d = |k: f64| ((k / 100.0).ln() + 0.045 * t) / (0.3 * t.sqrt());
+                (norm_cdf(d(110.0)) - norm_cdf(d(90.0))) / 2000.0
+            })
+            .sum();
+        assert!((corridor - 0.09 * occupation).abs() < 1e-5, "{corridor} vs {}", 0.09 * occupation);
+        let conditional = swap.with_conditional_corridor(90.0, 110.0).unwrap().fair_variance(&bs).unwrap();
+        assert!((conditional - 0.09).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_corridor_replication_matches_monte_carlo_under_heston() {
+        let params = HestonParams::new(0.04, 2.0, 0.05, 0.5, -0.7).unwrap();
+        let model = Heston::new(100.0, 0.03, 0.01, params).unwrap();
+        let paths = CorrelatedPaths::single(0.03, Asset::heston(100.0, 0.01, params).unwrap()).unwrap();
+        let mc = MonteCarlo::new(10_000, 11).unwrap();
+        for swap in [
+            VarianceSwap::new(1.0).unwrap().with_corridor(85.0, 105.0).unwrap(),
+            VarianceSwap::forward_starting(0.5, 1.0).unwrap().gamma().with_corridor(95.0, f64::INFINITY).unwrap(),
+        ] {
+            let fair = swap.fair_variance(&model).unwrap();
+            let simulated = swap.monte_carlo(&paths, &mc, 252).unwrap();
+            let tolerance = 4.0 * simulated.std_error + 0.01 * fair;
+            assert!((fair - simulated.price).abs() < tolerance, "{fair} vs {simulated:?}");
+        }
+
+        // Put skew: variance accrues faster below the forward
+        let swap = VarianceSwap::new(1.0).unwrap();
+        let down = swap.with_conditional_corridor(0.0, 100.0).unwrap().fair_variance(&model).unwrap();
+        let up = swap.with_conditional_corridor(100.0, f64::INFINITY).unwrap().fair_variance(&model).unwrap();
+        assert!(down > up);
+    }
+
+    #[test]
+    fn test_strip_recovers_flat_and_heston_strikes() {
+        let smile: Vec<(f64, f64)> = (20..=400).map(|k| (k as f64, 0.25)).collect();
+        let strip = OptionStrip::from_smile(100.0, 1.0, 0.03, &smile).unwrap();
+        let exact = flat_fair_variance(0.25).unwrap();
+        assert!((strip.fair_variance() - exact).abs() < 2e-4, "{}", strip.fair_variance());
+
+        // Forward off the strike grid exercises the K₀ correction
+        let params = HestonParams::new(0.06, 1.5, 0.04, 0.6, -0.7).unwrap();
+        let model = Heston::new(100.0, 0.02, 0.01, params).unwrap();
+        let forward = model.forward(1.0);
+        let quotes = (20..=400)
+            .map(|k| {
+                let strike = k as f64;
+                let option_type = if strike < forward { OptionType::Put } else { OptionType::Call };
+                (strike, model.price(option_type, strike, 1.0))
+            })
+            .collect();
+        let strip = OptionStrip::new(forward, 1.0, 0.02, quotes).unwrap();
+        let replicated = VarianceSwap::new(1.0).unwrap().fair_variance(&model).unwrap();
+        assert!((strip.fair_variance() - replicated).abs() < 2e-4, "{} vs {replicated}", strip.fair_variance());
+    }
+
+    #[test]
+    fn test_volatility_swap_convexity_adjustment() {
+        // Realized variance uniform on [a, b]: E[√V] = (2/3)(b^1.5 - a^1.5)/(b - a)
+        let (a, b) = (0.03_f64, 0.05_f64);
+        let exact = 2.0 / 3.0 * (b.powf(1.5) - a.powf(1.5)) / (b - a);
+        let adjusted = volatility_swap_strike(0.5 * (a + b), (b - a).powi(2) / 12.0).unwrap();
+        assert!((adjusted - exact).abs() < 1e-5, "{adjusted} vs {exact}");
+        assert!(adjusted < 0.2);
+        assert_eq!(volatility_swap_strike(0.04, 0.0).unwrap(), 0.2);
+        assert!(volatility_swap_strike(0.0, 0.001).is_err());
+        assert!(volatility_swap_strike(0.04, -0.001).is_err());
+    }
+
+    #[test]
+    fn test_invalid_swaps() {
+        assert!(VarianceSwap::new(0.0).is_err());
+        assert!(VarianceSwap::forward_starting(1.0, 1.0).is_err());
+        assert!(VarianceSwap::forward_starting(-0.5, 1.0).is_err());
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, 0.25, 0.01).unwrap();
+        assert!(VarianceSwap::new(1.0).unwrap().value(&bs, -0.01, 1.0).is_err());
+        assert!(VarianceSwap::new(1.0).unwrap().with_corridor(110.0, 90.0).is_err());
+        assert!(VarianceSwap::new(1.0).unwrap().with_corridor(-1.0, 90.0).is_err());
+        let unreachable = VarianceSwap::new(1.0).unwrap().with_conditional_corridor(1e6, 2e6).unwrap();
+        assert!(unreachable.fair_variance(&bs).is_err());
+        assert!(OptionStrip::new(100.0, 1.0, 0.0, vec![(90.0, 1.0), (110.0, 1.0)]).is_err());
+        assert!(OptionStrip::new(100.0, 1.0, 0.0, vec![(90.0, 1.0), (90.0, 1.0), (110.0, 1.0)]).is_err());
+        assert!(OptionStrip::new(100.0, 1.0, 0.0, vec![(90.0, -1.0), (100.0, 1.0), (110.0, 1.0)]).is_err());
+    }
+}
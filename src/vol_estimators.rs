@@ -0,0 +1,223 @@
+use crate::error::BlackScholesError;
+use crate::time_scale::TimeScale;
+use crate::validation;
+use std::f64::consts::LN_2;
+
+/// Open, high, low and close prices of one period
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bar {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Bar {
+    pub fn new(open: f64, high: f64, low: f64, close: f64) -> Result<Self, BlackScholesError> {
+        let bar = Bar {
+            open: validation::positive("Open", open)?,
+            high: validation::positive("High", high)?,
+            low: validation::positive("Low", low)?,
+            close: validation::positive("Close", close)?,
+        };
+        if bar.high < bar.open.max(bar.close) || bar.low > bar.open.min(bar.close) {
+            return Err(BlackScholesError::invalid("Bar high and low must bracket the open and close"));
+        }
+        Ok(bar)
+    }
+}
+
+/// Historical volatility estimator
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Estimator {
+    /// Sample standard deviation of close-to-close log returns
+    CloseToClose,
+    /// Exponentially weighted zero-mean variance of close-to-close returns,
+    /// `σ²ₜ = λ·σ²ₜ₋₁ + (1 - λ)·r²ₜ` (RiskMetrics uses λ = 0.94)
+    Ewma { lambda: f64 },
+    /// High-low range (Parkinson 1980); assumes no drift or opening gaps
+    Parkinson,
+    /// Open-high-low-close (Garman-Klass 1980); assumes no drift or opening gaps
+    GarmanKlass,
+    /// Drift-independent range estimator (Rogers-Satchell 1991)
+    RogersSatchell,
+    /// Overnight, open-to-close and Rogers-Satchell variances combined
+    /// (Yang-Zhang 2000); handles both drift and opening gaps
+    YangZhang,
+}
+
+impl Estimator {
+    /// Fewest bars the estimator needs
+    pub fn min_bars(&self) -> usize {
+        match self {
+            Estimator::Parkinson | Estimator::GarmanKlass | Estimator::RogersSatchell => 1,
+            Estimator::Ewma { .. } => 2,
+            Estimator::CloseToClose | Estimator::YangZhang => 3,
+        }
+    }
+
+    /// Variance per period
+    fn period_variance(&self, bars: &[Bar]) -> f64 {
+        let n = bars.len() as f64;
+        let returns = || bars.windows(2).map(|w| (w[1].close / w[0].close).ln());
+        match *self {
+            Estimator::CloseToClose => sample_variance(&returns().collect::<Vec<_>>()),
+            Estimator::Ewma { lambda } => {
+                let mut returns = returns();
+                let first = returns.next().unwrap_or(0.0);
+                returns.fold(first * first, |variance, r| lambda * variance + (1.0 - lambda) * r * r)
+            }
+            Estimator::Parkinson => bars.iter().map(|b| (b.high / b.low).ln().powi(2)).sum::<f64>() / (4.0 * LN_2 * n),
+            Estimator::GarmanKlass => {
+                bars.iter()
+                    .map(|b| 0.5 * (b.high / b.low).ln().powi(2) - (2.0 * LN_2 - 1.0) * (b.close / b.open).ln().powi(2))
+                    .sum::<f64>()
+                    / n
+            }
+            Estimator::RogersSatchell => bars.iter().map(rogers_satchell).sum::<f64>() / n,
+            Estimator::YangZhang => {
+                let periods = &bars[1..];
+                let m = periods.len() as f64;
+                let overnight: Vec<f64> = bars.windows(2).map(|w| (w[1].open / w[0].close).ln()).collect();
+                let open_to_close: Vec<f64> = periods.iter().map(|b| (b.close / b.open).ln()).collect();
+                let rs = periods.iter().map(rogers_satchell).sum::<f64>() / m;
+                let k = 0.34 / (1.34 + (m + 1.0) / (m - 1.0));
+                sample_variance(&overnight) + k * sample_variance(&open_to_close) + (1.0 - k) * rs
+            }
+        }
+    }
+}
+
+fn rogers_satchell(b: &Bar) -> f64 {
+    (b.high / b.close).ln() * (b.high / b.open).ln() + (b.low / b.close).ln() * (b.low / b.open).ln()
+}
+
+/// Unbiased sample variance (at least two values)
+fn sample_variance(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+}
+
+/// Annualized volatility of a series of bars, oldest first
+///
+/// # Arguments
+/// * `bars` - One bar per day on `time_scale`
+/// * `estimator` - Estimation method
+/// * `time_scale` - Annualization basis: 252 trading or 365 calendar days, or custom
+pub fn realized_volatility(bars: &[Bar], estimator: Estimator, time_scale: TimeScale) -> Result<f64, BlackScholesError> {
+    if bars.len() < estimator.min_bars() {
+        return Err(BlackScholesError::InvalidInput(format!(
+            "{estimator:?} needs at least {} bars",
+            estimator.min_bars()
+        )));
+    }
+    if let Estimator::Ewma { lambda } = estimator {
+        validation::in_range("EWMA decay", lambda, 0.0, 1.0, "between 0 and 1")?;
+    }
+    let days_per_year = validation::positive("Days per year", time_scale.days_per_year())?;
+    Ok((estimator.period_variance(bars).max(0.0) * days_per_year).sqrt())
+}
+
+/// Realized volatility over a trailing window ending at each bar
+///
+/// Entry `i` uses `bars[i + 1 - window..=i]`; the first `window - 1` bars
+/// have no estimate, so the result has `bars.len() - window + 1` entries.
+pub fn rolling_volatility(
+    bars: &[Bar],
+    window: usize,
+    estimator: Estimator,
+    time_scale: TimeScale,
+) -> Result<Vec<f64>, BlackScholesError> {
+    if window < estimator.min_bars() || window > bars.len() {
+        return Err(BlackScholesError::invalid("Window must fit the series and the estimator's minimum"));
+    }
+    bars.windows(window)
+        .map(|w| realized_volatility(w, estimator, time_scale))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Rng;
+
+    /// Daily bars from a driftless intraday random walk, with an optional overnight gap volatility
+    fn simulate(days: usize, daily_vol: f64, gap_vol: f64, seed: u64) -> Vec<Bar> {
+        let steps = 400;
+        let mut rng = Rng::new(seed);
+        let mut close = 100.0;
+        (0..days)
+            .map(|_| {
+                let open: f64 = close * (gap_vol * rng.normal()).exp();
+                let (mut high, mut low, mut price) = (open, open, open);
+                for _ in 0..steps {
+                    price *= (daily_vol / (steps as f64).sqrt() * rng.normal()).exp();
+                    high = high.max(price);
+                    low = low.min(price);
+                }
+                close = price;
+                Bar::new(open, high, low, close).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_estimators_recover_true_volatility() {
+        let bars = simulate(1000, 0.2 / 252f64.sqrt(), 0.0, 17);
+        for estimator in [
+            Estimator::CloseToClose,
+            Estimator::Parkinson,
+            Estimator::GarmanKlass,
+            Estimator::RogersSatchell,
+            Estimator::YangZhang,
+        ] {
+            let vol = realized_volatility(&bars, estimator, TimeScale::Trading).unwrap();
+            // Discrete monitoring understates the range slightly
+            assert!((vol - 0.2).abs() < 0.015, "{estimator:?}: {vol}");
+        }
+        let ewma = realized_volatility(&bars, Estimator::Ewma { lambda: 0.97 }, TimeScale::Trading).unwrap();
+        assert!((ewma - 0.2).abs() < 0.06, "{ewma}");
+    }
+
+    #[test]
+    fn test_yang_zhang_captures_overnight_gaps() {
+        let daily = 0.2 / 252f64.sqrt();
+        let bars = simulate(1000, daily, daily, 5);
+        let total = (2.0f64).sqrt() * 0.2;
+        let yz = realized_volatility(&bars, Estimator::YangZhang, TimeScale::Trading).unwrap();
+        let rs = realized_volatility(&bars, Estimator::RogersSatchell, TimeScale::Trading).unwrap();
+        let cc = realized_volatility(&bars, Estimator::CloseToClose, TimeScale::Trading).unwrap();
+        assert!((yz - total).abs() < 0.02 && (cc - total).abs() < 0.02, "{yz} {cc}");
+        // Range estimators only see the trading session
+        assert!((rs - 0.2).abs() < 0.015);
+    }
+
+    #[test]
+    fn test_annualization_and_rolling() {
+        let bars = simulate(60, 0.01, 0.0, 2);
+        let trading = realized_volatility(&bars, Estimator::Parkinson, TimeScale::Trading).unwrap();
+        let calendar = realized_volatility(&bars, Estimator::Parkinson, TimeScale::Calendar).unwrap();
+        assert!((calendar / trading - (365.0f64 / 252.0).sqrt()).abs() < 1e-12);
+
+        let rolling = rolling_volatility(&bars, 20, Estimator::GarmanKlass, TimeScale::Trading).unwrap();
+        assert_eq!(rolling.len(), 41);
+        let last = realized_volatility(&bars[40..], Estimator::GarmanKlass, TimeScale::Trading).unwrap();
+        assert_eq!(rolling[40], last);
+        // Flat prices carry no volatility
+        let flat = vec![Bar::new(50.0, 50.0, 50.0, 50.0).unwrap(); 5];
+        assert_eq!(realized_volatility(&flat, Estimator::YangZhang, TimeScale::Trading).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(Bar::new(100.0, 99.0, 95.0, 98.0).is_err());
+        assert!(Bar::new(100.0, 101.0, 0.0, 98.0).is_err());
+        let bars = simulate(2, 0.01, 0.0, 1);
+        assert!(realized_volatility(&bars, Estimator::CloseToClose, TimeScale::Trading).is_err());
+        assert!(realized_volatility(&bars, Estimator::Ewma { lambda: 1.5 }, TimeScale::Trading).is_err());
+        assert!(rolling_volatility(&bars, 3, Estimator::Parkinson, TimeScale::Trading).is_err());
+    }
+}
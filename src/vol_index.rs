@@ -0,0 +1,480 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::chain::{ExpirySlice, OptionChain};
+use crate::error::BlackScholesError;
+use crate::validation;
+use std::collections::BTreeMap;
+
+/// Calendar days per year used to convert index tenors
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Maximum Newton iterations when solving quote implied volatilities
+const IV_MAX_ITERATIONS: usize = 100;
+
+/// Price tolerance when solving quote implied volatilities
+const IV_TOLERANCE: f64 = 1e-8;
+
+/// How each expiry's volatility is measured before interpolating to the tenor
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexMethod {
+    /// Implied volatility at the forward, interpolated linearly in strike across OTM quotes
+    AtmForward,
+    /// Model-free variance from the OTM strip around the slice's carry forward
+    VarianceSwap,
+    /// CBOE VIX rules: parity forward, zero-bid strike cutoff, and the two
+    /// terms around the tenor weighted linearly, extrapolating past them
+    Cboe,
+}
+
+/// Constant-maturity implied volatility index (e.g. 30-day, VIX-style)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolIndex {
+    /// Target maturity in calendar days
+    pub tenor_days: f64,
+    pub method: IndexMethod,
+}
+
+impl VolIndex {
+    pub fn new(tenor_days: f64, method: IndexMethod) -> Result<Self, BlackScholesError> {
+        let tenor_days = validation::positive("Index tenor", tenor_days)?;
+        Ok(VolIndex { tenor_days, method })
+    }
+
+    /// The 30-day index computed by the CBOE VIX rules
+    pub fn vix() -> Self {
+        VolIndex {
+            tenor_days: 30.0,
+            method: IndexMethod::Cboe,
+        }
+    }
+
+    /// Target maturity in years
+    pub fn tenor(&self) -> f64 {
+        self.tenor_days / DAYS_PER_YEAR
+    }
+
+    /// Compute the index level (annualised volatility as decimal) from a chain
+    ///
+    /// Each expiry is reduced to a total variance σ²T, which is interpolated
+    /// linearly in time to the tenor. Tenors outside the listed expiries take
+    /// the volatility of the nearest expiry. Expiries with too few usable
+    /// quotes are skipped.
+    pub fn compute(&self, chain: &OptionChain) -> Result<f64, BlackScholesError> {
+        let points: Vec<(f64, f64)> = chain
+            .slices
+            .iter()
+            .filter_map(|slice| {
+                let variance = match self.method {
+                    IndexMethod::AtmForward => atm_forward_vol(slice, chain.spot).map(|v| v * v),
+                    IndexMethod::VarianceSwap => strip_variance(slice, chain.spot),
+                    IndexMethod::Cboe => cboe_term_variance(slice).map(|term| term.variance),
+                };
+                variance.ok().map(|v| (slice.expiry, v * slice.expiry))
+            })
+            .collect();
+        if points.is_empty() {
+            return Err(BlackScholesError::invalid("No expiry has enough quotes for the index"));
+        }
+
+        let tenor = self.tenor();
+        let (first, last) = (points[0], points[points.len() - 1]);
+        let total_variance = if self.method == IndexMethod::Cboe && points.len() > 1 {
+            // The near and next terms around the tenor, or the nearest two outside them
+            let i = points.iter().position(|p| p.0 >= tenor).unwrap_or(points.len() - 1).max(1);
+            let (near, next) = (points[i - 1], points[i]);
+            near.1 + (next.1 - near.1) * (tenor - near.0) / (next.0 - near.0)
+        } else if tenor <= first.0 {
+            first.1 / first.0 * tenor
+        } else if tenor >= last.0 {
+            last.1 / last.0 * tenor
+        } else {
+            let i = points.iter().position(|p| p.0 >= tenor).unwrap_or(points.len() - 1);
+            let (near, far) = (points[i - 1], points[i]);
+            near.1 + (far.1 - near.1) * (tenor - near.0) / (far.0 - near.0)
+        };
+        if total_variance <= 0.0 {
+            return Err(BlackScholesError::invalid("Interpolated variance is not positive"));
+        }
+        Ok((total_variance / tenor).sqrt())
+    }
+}
+
+/// Out-of-the-money mids by strike: puts below the forward, calls at or above it
+fn otm_strip(slice: &ExpirySlice, forward: f64) -> Vec<(f64, f64, OptionType)> {
+    let mut strip: Vec<(f64, f64, OptionType)> = slice
+        .quotes
+        .iter()
+        .filter(|q| q.bid > 0.0)
+        .filter(|q| match q.option_type {
+            OptionType::Call => q.strike >= forward,
+            OptionType::Put => q.strike < forward,
+        })
+        .map(|q| (q.strike, q.mid(), q.option_type))
+        .collect();
+    strip.sort_by(|a, b| a.0.total_cmp(&b.0));
+    strip
+}
+
+/// Implied volatility at the forward from the two OTM quotes bracketing it
+fn atm_forward_vol(slice: &ExpirySlice, spot: f64) -> Result<f64, BlackScholesError> {
+    let forward = slice.forward(spot);
+    let strip = otm_strip(slice, forward);
+    let below = strip.iter().rev().find(|q| q.0 < forward);
+    let above = strip.iter().find(|q| q.0 >= forward);
+    let implied = |&(strike, mid, option_type): &(f64, f64, OptionType)| {
+        BlackScholes::new(spot, strike, slice.expiry, slice.rate, 0.2, slice.dividend_yield)
+            .and_then(|bs| bs.implied_volatility(option_type, mid, IV_MAX_ITERATIONS, IV_TOLERANCE))
+    };
+    match (below, above) {
+        (Some(lo), Some(hi)) => {
+            let (v_lo, v_hi) = (implied(lo)?, implied(hi)?);
+            Ok(v_lo + (v_hi - v_lo) * (forward - lo.0) / (hi.0 - lo.0))
+        }
+        (Some(only), None) | (None, Some(only)) => implied(only),
+        (None, None) => Err(BlackScholesError::invalid("Slice has no OTM quotes")),
+    }
+}
+
+/// CBOE-style model-free variance of one expiry
+///
+/// σ² = (2/T)·e^(rT)·Σ ΔK/K²·Q(K) - (1/T)·(F/K₀ - 1)², where K₀ is the first
+/// strike below the forward and Q(K₀) averages the call and put there.
+fn strip_variance(slice: &ExpirySlice, spot: f64) -> Result<f64, BlackScholesError> {
+    let t = slice.expiry;
+    let forward = slice.forward(spot);
+    let mut strip: Vec<(f64, f64)> = otm_strip(slice, forward).iter().map(|q| (q.0, q.1)).collect();
+    if strip.len() < 3 {
+        return Err(BlackScholesError::invalid("Need at least three OTM quotes for strip variance"));
+    }
+
+    let k0 = strip.iter().rev().map(|q| q.0).find(|&k| k <= forward).unwrap_or(strip[0].0);
+    let mid_at_k0 = |option_type: OptionType| {
+        slice
+            .quotes
+            .iter()
+            .find(|q| q.option_type == option_type && q.strike == k0)
+            .map(|q| q.mid())
+    };
+    if let (Some(call), Some(put)) = (mid_at_k0(OptionType::Call), mid_at_k0(OptionType::Put)) {
+        if let Some(entry) = strip.iter_mut().find(|q| q.0 == k0) {
+            entry.1 = 0.5 * (call + put);
+        }
+    }
+
+    let n = strip.len();
+    let weighted: f64 = (0..n)
+        .map(|i| {
+            let dk = match i {
+                0 => strip[1].0 - strip[0].0,
+                _ if i == n - 1 => strip[n - 1].0 - strip[n - 2].0,
+                _ => 0.5 * (strip[i + 1].0 - strip[i - 1].0),
+            };
+            dk / (strip[i].0 * strip[i].0) * strip[i].1
+        })
+        .sum();
+    Ok(2.0 / t * (slice.rate * t).exp() * weighted - (forward / k0 - 1.0).powi(2) / t)
+}
+
+/// One expiry's contribution to a CBOE-style index
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TermVariance {
+    /// Expiry in years
+    pub expiry: f64,
+    /// Forward implied by put-call parity
+    pub forward: f64,
+    /// First strike at or below the forward (K₀)
+    pub atm_strike: f64,
+    /// Strikes kept by the selection rules, ascending
+    pub strikes: Vec<f64>,
+    /// Annualised model-free variance σ²
+    pub variance: f64,
+}
+
+/// Model-free variance of one expiry by the CBOE VIX rules
+///
+/// The forward is F = K* + e^(rT)·(C - P) at the strike K* where call and
+/// put mids are closest, and K₀ the first strike at or below it. Puts below
+/// K₀ and calls above it are taken moving outwards, skipping zero bids and
+/// stopping after two consecutive zero bids; K₀ contributes the average of
+/// its put and call. Then
+/// σ² = (2/T)·Σ ΔK/K²·e^(rT)·Q(K) - (1/T)·(F/K₀ - 1)².
+/// Only the slice's rate is used; its dividend yield is ignored.
+pub fn cboe_term_variance(slice: &ExpirySlice) -> Result<TermVariance, BlackScholesError> {
+    let t = slice.expiry;
+    let growth = (slice.rate * t).exp();
+    let pairs = slice.parity_pairs();
+    let &(closest, difference) = pairs
+        .iter()
+        .min_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+        .ok_or_else(|| BlackScholesError::invalid("Need a call and put at the same strike"))?;
+    let forward = closest + growth * difference;
+    let (atm_strike, _) = pairs
+        .iter()
+        .filter(|p| p.0 <= forward)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .copied()
+        .ok_or_else(|| BlackScholesError::invalid("No strike at or below the forward"))?;
+
+    let mid_at = |option_type: OptionType| {
+        slice
+            .quotes
+            .iter()
+            .find(|q| q.option_type == option_type && q.strike == atm_strike)
+            .map_or(0.0, |q| q.mid())
+    };
+    let mut puts = outward_quotes(slice, OptionType::Put, atm_strike);
+    puts.reverse();
+    let mut strip = puts;
+    strip.push((atm_strike, 0.5 * (mid_at(OptionType::Call) + mid_at(OptionType::Put))));
+    strip.extend(outward_quotes(slice, OptionType::Call, atm_strike));
+    if strip.len() < 3 {
+        return Err(BlackScholesError::invalid("Need at least three OTM quotes for strip variance"));
+    }
+
+    let n = strip.len();
+    let weighted: f64 = (0..n)
+        .map(|i| {
+            let dk = match i {
+                0 => strip[1].0 - strip[0].0,
+                _ if i == n - 1 => strip[n - 1].0 - strip[n - 2].0,
+                _ => 0.5 * (strip[i + 1].0 - strip[i - 1].0),
+            };
+            dk / (strip[i].0 * strip[i].0) * strip[i].1
+        })
+        .sum();
+    Ok(TermVariance {
+        expiry: t,
+        forward,
+        atm_strike,
+        strikes: strip.iter().map(|q| q.0).collect(),
+        variance: 2.0 / t * growth * weighted - (forward / atm_strike - 1.0).powi(2) / t,
+    })
+}
+
+/// `(strike, mid)` of one side's quotes beyond `atm_strike`, nearest first
+///
+/// Zero bids are skipped and the walk stops at the second in a row.
+fn outward_quotes(slice: &ExpirySlice, option_type: OptionType, atm_strike: f64) -> Vec<(f64, f64)> {
+    let mut side: Vec<_> = slice
+        .quotes
+        .iter()
+        .filter(|q| q.option_type == option_type)
+        .filter(|q| match option_type {
+            OptionType::Call => q.strike > atm_strike,
+            OptionType::Put => q.strike < atm_strike,
+        })
+        .collect();
+    side.sort_by(|a, b| (a.strike - atm_strike).abs().total_cmp(&(b.strike - atm_strike).abs()));
+    let mut kept = Vec::with_capacity(side.len());
+    let mut zero_bids = 0;
+    for quote in side {
+        if quote.bid > 0.0 {
+            zero_bids = 0;
+            kept.push((quote.strike, quote.mid()));
+        } else {
+            zero_bids += 1;
+            if zero_bids == 2 {
+                break;
+            }
+        }
+    }
+    kept
+}
+
+/// One observation of an index
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolIndexPoint {
+    /// Observation time in years
+    pub time: f64,
+    pub value: f64,
+}
+
+/// Where the latest index level sits relative to its own history
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VolRegime {
+    Low,
+    Normal,
+    High,
+}
+
+/// Index time series per underlying
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolIndexHistory {
+    pub index: VolIndex,
+    pub series: BTreeMap<String, Vec<VolIndexPoint>>,
+}
+
+impl VolIndexHistory {
+    pub fn new(index: VolIndex) -> Self {
+        VolIndexHistory {
+            index,
+            series: BTreeMap::new(),
+        }
+    }
+
+    /// Compute the index from a chain snapshot and append it to the underlying's series
+    ///
+    /// # Returns
+    /// The recorded index level
+    pub fn record(&mut self, underlying: &str, time: f64, chain: &OptionChain) -> Result<f64, BlackScholesError> {
+        let time = validation::finite("Observation time", time)?;
+        let series = self.series.entry(underlying.to_string()).or_default();
+        if series.last().is_some_and(|p| p.time >= time) {
+            return Err(BlackScholesError::invalid("Observations must be recorded in time order"));
+        }
+        let value = self.index.compute(chain)?;
+        series.push(VolIndexPoint { time, value });
+        Ok(value)
+    }
+
+    /// Recorded observations for an underlying, oldest first
+    pub fn series(&self, underlying: &str) -> &[VolIndexPoint] {
+        self.series.get(underlying).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn latest(&self, underlying: &str) -> Option<VolIndexPoint> {
+        self.series(underlying).last().copied()
+    }
+
+    /// Fraction of observations at or below the latest level
+    pub fn percentile_rank(&self, underlying: &str) -> Option<f64> {
+        let series = self.series(underlying);
+        let latest = series.last()?.value;
+        Some(series.iter().filter(|p| p.value <= latest).count() as f64 / series.len() as f64)
+    }
+
+    /// Standard score of the latest level against the trailing `lookback` observations
+    pub fn z_score(&self, underlying: &str, lookback: usize) -> Option<f64> {
+        let series = self.series(underlying);
+        let window = &series[series.len().saturating_sub(lookback)..];
+        if window.len() < 2 {
+            return None;
+        }
+        let n = window.len() as f64;
+        let mean = window.iter().map(|p| p.value).sum::<f64>() / n;
+        let variance = window.iter().map(|p| (p.value - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (variance > 0.0).then(|| (window[window.len() - 1].value - mean) / variance.sqrt())
+    }
+
+    /// Classify the latest level by its percentile rank
+    ///
+    /// # Arguments
+    /// * `low` - Percentile rank at or below which the regime is `Low` (e.g. 0.2)
+    /// * `high` - Percentile rank at or above which the regime is `High` (e.g. 0.8)
+    pub fn regime(&self, underlying: &str, low: f64, high: f64) -> Option<VolRegime> {
+        let rank = self.percentile_rank(underlying)?;
+        Some(if rank <= low {
+            VolRegime::Low
+        } else if rank >= high {
+            VolRegime::High
+        } else {
+            VolRegime::Normal
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synthetic::{generate_chain, ChainSpec, StrikeGrid};
+
+    fn flat_chain(vol: f64) -> OptionChain {
+        let bs = BlackScholes::new(100.0, 100.0, 1.0, 0.03, vol, 0.01).unwrap();
+        // 1% strike spacing keeps the discretised variance strip close to the integral
+        let spec = ChainSpec {
+            strikes: StrikeGrid::Moneyness((0..=80).map(|i| 0.6 + 0.01 * i as f64).collect()),
+            ..ChainSpec::standard(3)
+        };
+        generate_chain(&bs, &spec).unwrap()
+    }
+
+    #[test]
+    fn test_flat_surface_recovers_vol() {
+        let chain = flat_chain(0.25);
+        let atm = VolIndex::new(30.0, IndexMethod::AtmForward).unwrap();
+        assert!((atm.compute(&chain).unwrap() - 0.25).abs() < 1e-3);
+
+        let variance = VolIndex::new(30.0, IndexMethod::VarianceSwap).unwrap();
+        let level = variance.compute(&chain).unwrap();
+        assert!((level - 0.25).abs() < 5e-3, "{}", level);
+    }
+
+    #[test]
+    fn test_interpolates_total_variance_between_expiries() {
+        let near = BlackScholes::new(100.0, 100.0, 1.0, 0.0, 0.2, 0.0).unwrap();
+        let far = BlackScholes::new(100.0, 100.0, 1.0, 0.0, 0.3, 0.0).unwrap();
+        let spec = |t: f64| ChainSpec {
+            expiries: vec![t],
+            ..ChainSpec::standard(1)
+        };
+        let mut slices = generate_chain(&near, &spec(20.0 / 365.0)).unwrap().slices;
+        slices.extend(generate_chain(&far, &spec(40.0 / 365.0)).unwrap().slices);
+        let chain = OptionChain::new(100.0, slices).unwrap();
+
+        let index = VolIndex::new(30.0, IndexMethod::AtmForward).unwrap();
+        let expected = ((0.04 * 20.0 + 0.09 * 40.0) / 2.0 / 30.0_f64).sqrt();
+        assert!((index.compute(&chain).unwrap() - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_cboe_rules_use_the_parity_forward() {
+        let mut chain = flat_chain(0.25);
+        // A wrong carry input moves the slice forward but not the quotes
+        for slice in &mut chain.slices {
+            slice.dividend_yield = 0.08;
+        }
+        let level = VolIndex::vix().compute(&chain).unwrap();
+        assert!((level - 0.25).abs() < 5e-3, "{level}");
+        let term = cboe_term_variance(&chain.slices[0]).unwrap();
+        let forward = 100.0 * (0.02_f64 / 12.0).exp();
+        assert!((term.forward - forward).abs() < 1e-9, "{}", term.forward);
+        assert!(term.atm_strike <= term.forward && term.strikes.contains(&term.atm_strike));
+
+        let carry = VolIndex::new(30.0, IndexMethod::VarianceSwap).unwrap().compute(&chain).unwrap();
+        assert!((carry - 0.25).abs() > (level - 0.25).abs());
+    }
+
+    #[test]
+    fn test_two_zero_bids_end_the_strip() {
+        let mut slice = flat_chain(0.25).slices[0].clone();
+        let full = cboe_term_variance(&slice).unwrap();
+        let cutoff = [full.strikes[6], full.strikes[5]];
+        assert!(cutoff[0] < full.atm_strike);
+        // A single zero bid is skipped; two in a row drop everything beyond them
+        for quote in slice.quotes.iter_mut() {
+            if quote.option_type == OptionType::Put && cutoff.contains(&quote.strike) {
+                quote.bid = 0.0;
+            }
+        }
+        let truncated = cboe_term_variance(&slice).unwrap();
+        assert!(truncated.strikes[0] > cutoff[0]);
+        assert!(truncated.variance < full.variance);
+
+        let calls_only = ExpirySlice {
+            quotes: slice.quotes.iter().filter(|q| q.option_type == OptionType::Call).copied().collect(),
+            ..slice
+        };
+        assert!(cboe_term_variance(&calls_only).is_err());
+    }
+
+    #[test]
+    fn test_history_tracks_regime() {
+        let index = VolIndex::new(30.0, IndexMethod::AtmForward).unwrap();
+        let mut history = VolIndexHistory::new(index);
+        for (day, vol) in [0.18, 0.2, 0.19, 0.21, 0.35].iter().enumerate() {
+            history.record("SPX", day as f64 / 365.0, &flat_chain(*vol)).unwrap();
+        }
+        assert_eq!(history.series("SPX").len(), 5);
+        assert!(history.series("NDX").is_empty());
+        assert_eq!(history.percentile_rank("SPX"), Some(1.0));
+        assert_eq!(history.regime("SPX", 0.2, 0.8), Some(VolRegime::High));
+        assert!(history.z_score("SPX", 5).unwrap() > 1.5);
+
+        assert!(history.record("SPX", 0.0, &flat_chain(0.2)).is_err());
+    }
+}
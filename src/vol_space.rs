@@ -0,0 +1,221 @@
+use crate::black_scholes::{BlackScholes, OptionType};
+use crate::error::BlackScholesError;
+
+/// Vega (per vol point) below which price/vol conversions are refused
+const MIN_VEGA: f64 = 1e-10;
+
+/// Option Greeks re-expressed as implied-volatility point equivalents
+///
+/// Each field answers "how many vol points of implied volatility move
+/// produce the same P&L as one unit of this risk", using the option's vega
+/// per vol point as the exchange rate.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolSpaceGreeks {
+    /// Change in implied vol (in vol points) per unit change in option price
+    pub div_dprice: f64,
+    /// Vol points equivalent to a one-unit move in the underlying
+    pub delta: f64,
+    /// Vol points equivalent to one day of time decay
+    pub theta: f64,
+    /// Vol points equivalent to a 1% move in the interest rate
+    pub rho: f64,
+}
+
+/// Bid/ask price quote expressed in implied volatility
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolQuote {
+    pub bid_vol: f64,
+    pub ask_vol: f64,
+    pub mid_vol: f64,
+    /// Width of the market in vol points
+    pub spread_vol_points: f64,
+}
+
+impl VolSpaceGreeks {
+    /// Translate the option's price Greeks into vol-point equivalents
+    ///
+    /// # Arguments
+    /// * `model` - Black-Scholes model at the current implied volatility
+    /// * `option_type` - Type of option (Call or Put)
+    pub fn from_model(model: &BlackScholes, option_type: OptionType) -> Result<Self, BlackScholesError> {
+        let greeks = model.greeks(option_type);
+        let vega = checked_vega(greeks.vega)?;
+
+        Ok(VolSpaceGreeks {
+            div_dprice: 1.0 / vega,
+            delta: greeks.delta / vega,
+            theta: greeks.theta / vega,
+            rho: greeks.rho / vega,
+        })
+    }
+}
+
+/// Convert a price change into the first-order equivalent vol-point change
+///
+/// # Arguments
+/// * `model` - Black-Scholes model at the current implied volatility
+/// * `option_type` - Type of option (Call or Put)
+/// * `price_change` - Change in option price (per unit)
+///
+/// # Returns
+/// Implied volatility change in vol points (1.0 = one volatility point)
+pub fn price_to_vol_points(model: &BlackScholes, option_type: OptionType, price_change: f64) -> Result<f64, BlackScholesError> {
+    Ok(price_change / checked_vega(model.greeks(option_type).vega)?)
+}
+
+/// Convert a vol-point change into the first-order equivalent price change
+///
+/// # Arguments
+/// * `model` - Black-Scholes model at the current implied volatility
+/// * `option_type` - Type of option (Call or Put)
+/// * `vol_points` - Implied volatility change in vol points
+pub fn vol_points_to_price(model: &BlackScholes, option_type: OptionType, vol_points: f64) -> f64 {
+    vol_points * model.greeks(option_type).vega
+}
+
+/// Convert a uniform implied volatility move into the first-order position P&L
+///
+/// The inverse of [`position_pnl_to_vol_points`].
+///
+/// # Arguments
+/// * `model` - Black-Scholes model at the current implied volatility
+/// * `option_type` - Type of option (Call or Put)
+/// * `quantity` - Signed number of contracts
+/// * `multiplier` - Contract multiplier
+/// * `vol_points` - Implied volatility move in vol points
+pub fn position_vol_points_to_pnl(
+    model: &BlackScholes,
+    option_type: OptionType,
+    quantity: f64,
+    multiplier: f64,
+    vol_points: f64,
+) -> f64 {
+    vol_points_to_price(model, option_type, vol_points) * quantity * multiplier
+}
+
+/// Exact implied volatility change (in vol points) caused by a price change,
+/// obtained by re-solving for implied volatility rather than using vega
+///
+/// # Arguments
+/// * `model` - Black-Scholes model at the current implied volatility
+/// * `option_type` - Type of option (Call or Put)
+/// * `price_change` - Change in option price (per unit)
+pub fn exact_vol_change(model: &BlackScholes, option_type: OptionType, price_change: f64) -> Result<f64, BlackScholesError> {
+    let target = model.price(option_type) + price_change;
+    let implied = model.implied_volatility(option_type, target, 100, 1e-10)?;
+    Ok((implied - model.volatility) * 100.0)
+}
+
+/// Express a bid/ask price quote in implied volatility terms
+///
+/// # Arguments
+/// * `model` - Market parameters (the volatility field is ignored)
+/// * `option_type` - Type of option (Call or Put)
+/// * `bid` - Bid price
+/// * `ask` - Ask price
+pub fn quote_in_vol(model: &BlackScholes, option_type: OptionType, bid: f64, ask: f64) -> Result<VolQuote, BlackScholesError> {
+    if bid > ask {
+        return Err(BlackScholesError::invalid("Bid must not exceed ask"));
+    }
+    let bid_vol = model.implied_volatility(option_type, bid, 100, 1e-10)?;
+    let ask_vol = model.implied_volatility(option_type, ask, 100, 1e-10)?;
+    let mid_vol = model.implied_volatility(option_type, 0.5 * (bid + ask), 100, 1e-10)?;
+
+    Ok(VolQuote {
+        bid_vol,
+        ask_vol,
+        mid_vol,
+        spread_vol_points: (ask_vol - bid_vol) * 100.0,
+    })
+}
+
+/// Express a position P&L as the uniform implied volatility move that explains it
+///
+/// # Arguments
+/// * `model` - Black-Scholes model at the current implied volatility
+/// * `option_type` - Type of option (Call or Put)
+/// * `quantity` - Signed number of contracts
+/// * `multiplier` - Contract multiplier
+/// * `pnl` - Position P&L in currency
+///
+/// # Returns
+/// The uniform implied volatility move (vol points) that explains `pnl`
+pub fn position_pnl_to_vol_points(
+    model: &BlackScholes,
+    option_type: OptionType,
+    quantity: f64,
+    multiplier: f64,
+    pnl: f64,
+) -> Result<f64, BlackScholesError> {
+    let position_vega = checked_vega(model.greeks(option_type).vega * quantity * multiplier)?;
+    Ok(pnl / position_vega)
+}
+
+fn checked_vega(vega: f64) -> Result<f64, BlackScholesError> {
+    if vega.abs() < MIN_VEGA {
+        return Err(BlackScholesError::no_convergence("Vega too small, cannot convert between price and vol"));
+    }
+    Ok(vega)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> BlackScholes {
+        BlackScholes::new(100.0, 100.0, 0.5, 0.03, 0.2, 0.0).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_conversion() {
+        let bs = model();
+        let points = price_to_vol_points(&bs, OptionType::Call, 0.5).unwrap();
+        let back = vol_points_to_price(&bs, OptionType::Call, points);
+        assert!((back - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_first_order_matches_exact_for_small_moves() {
+        let bs = model();
+        let approx = price_to_vol_points(&bs, OptionType::Put, 0.05).unwrap();
+        let exact = exact_vol_change(&bs, OptionType::Put, 0.05).unwrap();
+        assert!((approx - exact).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_vol_space_greeks() {
+        let bs = model();
+        let greeks = bs.greeks(OptionType::Call);
+        let vs = VolSpaceGreeks::from_model(&bs, OptionType::Call).unwrap();
+
+        assert!((vs.theta * greeks.vega - greeks.theta).abs() < 1e-12);
+        assert!((vs.delta * greeks.vega - greeks.delta).abs() < 1e-12);
+        assert!(vs.div_dprice > 0.0);
+    }
+
+    #[test]
+    fn test_quote_in_vol() {
+        let bs = model();
+        let bid = BlackScholes { volatility: 0.19, ..bs }.price(OptionType::Call);
+        let ask = BlackScholes { volatility: 0.21, ..bs }.price(OptionType::Call);
+
+        let quote = quote_in_vol(&bs, OptionType::Call, bid, ask).unwrap();
+        assert!((quote.bid_vol - 0.19).abs() < 1e-6);
+        assert!((quote.ask_vol - 0.21).abs() < 1e-6);
+        assert!((quote.spread_vol_points - 2.0).abs() < 1e-4);
+        assert!(quote_in_vol(&bs, OptionType::Call, ask, bid).is_err());
+    }
+
+    #[test]
+    fn test_position_pnl_in_vol_points() {
+        let bs = model();
+        let vega = bs.greeks(OptionType::Call).vega;
+        let points = position_pnl_to_vol_points(&bs, OptionType::Call, 10.0, 100.0, 1000.0 * vega).unwrap();
+        assert!((points - 1.0).abs() < 1e-12);
+        let pnl = position_vol_points_to_pnl(&bs, OptionType::Call, -10.0, 100.0, 2.5);
+        let back = position_pnl_to_vol_points(&bs, OptionType::Call, -10.0, 100.0, pnl).unwrap();
+        assert!((back - 2.5).abs() < 1e-12);
+    }
+}
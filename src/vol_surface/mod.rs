@@ -0,0 +1,101 @@
+//! Implied volatility surfaces and smile parameterizations
+
+pub mod ssvi;
+pub mod svi;
+
+pub use ssvi::{AtmNode, SsviFit, SsviParams, SsviSurface};
+pub use svi::{SviFit, SviParams, SviSlice, SviSurface};
+
+/// Relative strike bump for finite-difference variance derivatives
+const STRIKE_BUMP: f64 = 1e-3;
+
+/// Expiry bump in years for finite-difference variance derivatives
+const EXPIRY_BUMP: f64 = 1e-4;
+
+/// An implied volatility surface queried by absolute strike and expiry
+pub trait VolSurface {
+    /// Black-Scholes implied volatility at `strike` for expiry `expiry` (years)
+    fn implied_vol(&self, strike: f64, expiry: f64) -> f64;
+
+    /// Total implied variance σ²·T at `strike` and `expiry`
+    fn total_variance(&self, strike: f64, expiry: f64) -> f64 {
+        self.implied_vol(strike, expiry).powi(2) * expiry
+    }
+
+    /// Total variance with its strike and expiry derivatives, the inputs to Dupire's formula
+    ///
+    /// Parameterized surfaces override this with closed forms; the default
+    /// differences `total_variance`, centrally in strike and in expiry
+    /// (one-sided for expiries inside the bump).
+    fn variance_derivatives(&self, strike: f64, expiry: f64) -> VarianceDerivatives {
+        let w = |k: f64, t: f64| self.total_variance(k, t);
+        let total_variance = w(strike, expiry);
+        let h = STRIKE_BUMP * strike;
+        let (up, down) = (w(strike + h, expiry), w(strike - h, expiry));
+        let d_expiry = if expiry > EXPIRY_BUMP {
+            (w(strike, expiry + EXPIRY_BUMP) - w(strike, expiry - EXPIRY_BUMP)) / (2.0 * EXPIRY_BUMP)
+        } else {
+            (w(strike, expiry + EXPIRY_BUMP) - total_variance) / EXPIRY_BUMP
+        };
+        VarianceDerivatives {
+            total_variance,
+            d_strike: (up - down) / (2.0 * h),
+            d_strike2: (up - 2.0 * total_variance + down) / (h * h),
+            d_expiry,
+        }
+    }
+}
+
+/// Total implied variance w(K, T) and its partial derivatives at one point
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VarianceDerivatives {
+    pub total_variance: f64,
+    /// ∂w/∂K
+    pub d_strike: f64,
+    /// ∂²w/∂K²
+    pub d_strike2: f64,
+    /// ∂w/∂T at fixed strike
+    pub d_expiry: f64,
+}
+
+/// Implied volatility quotes for one expiry, the input to smile and surface fits
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmileQuotes {
+    /// Expiry in years
+    pub expiry: f64,
+    /// Forward price for the expiry
+    pub forward: f64,
+    /// `(strike, implied_vol)` pairs
+    pub quotes: Vec<(f64, f64)>,
+}
+
+/// Flat surface returning the same volatility everywhere
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlatVol {
+    pub volatility: f64,
+}
+
+impl FlatVol {
+    pub fn new(volatility: f64) -> Self {
+        FlatVol { volatility }
+    }
+}
+
+impl VolSurface for FlatVol {
+    fn implied_vol(&self, _strike: f64, _expiry: f64) -> f64 {
+        self.volatility
+    }
+
+    fn variance_derivatives(&self, _strike: f64, expiry: f64) -> VarianceDerivatives {
+        let variance = self.volatility * self.volatility;
+        VarianceDerivatives {
+            total_variance: variance * expiry,
+            d_strike: 0.0,
+            d_strike2: 0.0,
+            d_expiry: variance,
+        }
+    }
+}
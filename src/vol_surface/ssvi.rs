@@ -0,0 +1,559 @@
+use super::{SmileQuotes, VarianceDerivatives, VolSurface};
+use crate::error::BlackScholesError;
+use crate::math::{nelder_mead, SimplexSearch};
+use crate::validation;
+
+/// Penalty weight applied to constraint violations during calibration
+const PENALTY_WEIGHT: f64 = 1e3;
+
+/// Simplex edge length when refitting from previous parameters
+const WARM_START_STEP: f64 = 0.05;
+
+/// Simplex edge length when updating from a few fresh quotes
+const UPDATE_STEP: f64 = 0.01;
+
+/// Gap below which a quoted expiry is taken to be a node's expiry
+const EXPIRY_TOLERANCE: f64 = 1e-9;
+
+/// Surface SVI parameters with power-law curvature (Gatheral-Jacquier, 2014)
+///
+/// Total variance at log-moneyness k for ATM total variance θ:
+/// w(k, θ) = θ/2·(1 + ρφk + √((φk + ρ)² + 1 - ρ²)), φ(θ) = η / (θ^γ·(1 + θ)^(1-γ))
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SsviParams {
+    /// Skew (spot-vol correlation), in (-1, 1)
+    pub rho: f64,
+    /// Curvature level
+    pub eta: f64,
+    /// Curvature decay with ATM variance, in (0, 1)
+    pub gamma: f64,
+}
+
+impl SsviParams {
+    pub fn new(rho: f64, eta: f64, gamma: f64) -> Result<Self, BlackScholesError> {
+        if !rho.is_finite() || rho.abs() >= 1.0 {
+            return Err(BlackScholesError::OutOfRange {
+                field: "SSVI rho",
+                value: rho,
+                requirement: "in (-1, 1)",
+            });
+        }
+        let eta = validation::positive("SSVI eta", eta)?;
+        if !gamma.is_finite() || gamma <= 0.0 || gamma >= 1.0 {
+            return Err(BlackScholesError::OutOfRange {
+                field: "SSVI gamma",
+                value: gamma,
+                requirement: "in (0, 1)",
+            });
+        }
+        Ok(SsviParams { rho, eta, gamma })
+    }
+
+    /// Curvature φ(θ) at ATM total variance θ
+    pub fn phi(&self, theta: f64) -> f64 {
+        self.eta / (theta.powf(self.gamma) * (1.0 + theta).powf(1.0 - self.gamma))
+    }
+
+    /// Total implied variance at log-moneyness `k` on the slice with ATM total variance `theta`
+    pub fn total_variance(&self, k: f64, theta: f64) -> f64 {
+        let pk = self.phi(theta) * k;
+        0.5 * theta * (1.0 + self.rho * pk + ((pk + self.rho).powi(2) + 1.0 - self.rho * self.rho).sqrt())
+    }
+
+    /// w(k, θ) with ∂w/∂k, ∂²w/∂k² and ∂w/∂θ
+    fn variance_with_derivatives(&self, k: f64, theta: f64) -> (f64, f64, f64, f64) {
+        let rho = self.rho;
+        let phi = self.phi(theta);
+        let shifted = phi * k + rho;
+        let root = (shifted * shifted + 1.0 - rho * rho).sqrt();
+        let w = 0.5 * theta * (1.0 + rho * phi * k + root);
+        let w_k = 0.5 * theta * phi * (rho + shifted / root);
+        let w_kk = 0.5 * theta * phi * phi * (1.0 - rho * rho) / root.powi(3);
+        // φ'(θ) = φ·((γ - 1)/(1 + θ) - γ/θ)
+        let d_phi = phi * ((self.gamma - 1.0) / (1.0 + theta) - self.gamma / theta);
+        let w_theta = w / theta + 0.5 * theta * k * (rho + shifted / root) * d_phi;
+        (w, w_k, w_kk, w_theta)
+    }
+
+    /// Sufficient condition for a surface free of static arbitrage
+    ///
+    /// With power-law φ, η·(1 + |ρ|) ≤ 2 and γ ≤ 1/2 rule out butterfly
+    /// arbitrage at every θ; calendar arbitrage is ruled out for any γ in
+    /// (0, 1) as long as θ is non-decreasing in expiry.
+    pub fn is_arbitrage_free(&self) -> bool {
+        self.eta * (1.0 + self.rho.abs()) <= 2.0 && self.gamma <= 0.5
+    }
+
+    /// Optimizer coordinates of these parameters (inverse of `from_unconstrained`)
+    fn to_unconstrained(self) -> Vec<f64> {
+        let gamma = self.gamma.clamp(1e-12, 0.5 - 1e-12);
+        vec![
+            self.rho.clamp(-1.0 + 1e-12, 1.0 - 1e-12).atanh(),
+            self.eta.max(1e-12).ln(),
+            -(0.5 / gamma - 1.0).ln(),
+        ]
+    }
+
+    /// Map an unconstrained optimizer vector onto valid parameters
+    fn from_unconstrained(x: &[f64]) -> Self {
+        SsviParams {
+            rho: x[0].tanh(),
+            eta: x[1].exp(),
+            gamma: 0.5 / (1.0 + (-x[2]).exp()),
+        }
+    }
+}
+
+/// Quoted expiry of an SSVI surface: its forward and ATM total variance θ
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AtmNode {
+    /// Expiry in years
+    pub expiry: f64,
+    /// Forward price for the expiry
+    pub forward: f64,
+    /// ATM (k = 0) total implied variance θ
+    pub atm_variance: f64,
+}
+
+/// SSVI surface: one set of parameters across expiries, anchored to the ATM term structure
+///
+/// θ is interpolated linearly in expiry between nodes; outside them the ATM
+/// implied volatility is held constant.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SsviSurface {
+    pub params: SsviParams,
+    nodes: Vec<AtmNode>,
+}
+
+/// Result of fitting an SSVI surface to market quotes
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SsviFit {
+    /// The calibrated surface
+    pub surface: SsviSurface,
+    /// Root-mean-square implied volatility error over the quotes
+    pub rmse: f64,
+    /// Largest absolute implied volatility error over the quotes
+    pub max_error: f64,
+}
+
+impl SsviSurface {
+    /// Build a surface from parameters and ATM nodes (sorted internally by expiry)
+    ///
+    /// Rejects duplicate expiries and ATM total variance that decreases with
+    /// expiry, which would be calendar arbitrage.
+    pub fn new(params: SsviParams, mut nodes: Vec<AtmNode>) -> Result<Self, BlackScholesError> {
+        if nodes.is_empty() {
+            return Err(BlackScholesError::invalid("SSVI surface needs at least one expiry"));
+        }
+        for node in &nodes {
+            validation::positive("Time to expiry", node.expiry)?;
+            validation::positive("Forward", node.forward)?;
+            validation::positive("ATM total variance", node.atm_variance)?;
+        }
+        nodes.sort_by(|a, b| a.expiry.total_cmp(&b.expiry));
+        if nodes.windows(2).any(|w| w[0].expiry == w[1].expiry) {
+            return Err(BlackScholesError::invalid("Duplicate expiry in SSVI surface"));
+        }
+        if nodes.windows(2).any(|w| w[1].atm_variance < w[0].atm_variance) {
+            return Err(BlackScholesError::invalid("ATM total variance must be non-decreasing in expiry"));
+        }
+        Ok(SsviSurface { params, nodes })
+    }
+
+    /// ATM nodes making up the surface, ordered by expiry
+    pub fn nodes(&self) -> &[AtmNode] {
+        &self.nodes
+    }
+
+    /// ATM total variance θ at an arbitrary expiry
+    pub fn atm_variance(&self, expiry: f64) -> f64 {
+        let (lo, hi, weight) = self.bracket(expiry);
+        if lo == hi {
+            let node = &self.nodes[lo];
+            return node.atm_variance * expiry / node.expiry;
+        }
+        self.nodes[lo].atm_variance * (1.0 - weight) + self.nodes[hi].atm_variance * weight
+    }
+
+    /// Forward at an arbitrary expiry (log-linear between nodes)
+    pub fn forward(&self, expiry: f64) -> f64 {
+        let (lo, hi, weight) = self.bracket(expiry);
+        (self.nodes[lo].forward.ln() * (1.0 - weight) + self.nodes[hi].forward.ln() * weight).exp()
+    }
+
+    /// Fit SSVI to quotes on several expiries
+    ///
+    /// θ at each expiry is read off the quotes by linear interpolation of
+    /// total variance at k = 0 (floored at the previous expiry's value), then
+    /// ρ, η and γ are fitted jointly by Nelder-Mead on squared total-variance
+    /// errors. γ is restricted to (0, 1/2] and η·(1 + |ρ|) ≤ 2 is enforced by
+    /// penalty, so the fitted surface is free of static arbitrage.
+    ///
+    /// # Arguments
+    /// * `slices` - Quotes for each expiry
+    pub fn fit(slices: &[SmileQuotes]) -> Result<SsviFit, BlackScholesError> {
+        Self::fit_with(slices, None)
+    }
+
+    /// Refit from previous parameters (warm start)
+    ///
+    /// A single local search from `previous` replaces the multi-start search
+    /// of `fit`; the ATM nodes are always re-read from the quotes.
+    pub fn fit_from(slices: &[SmileQuotes], previous: &SsviParams) -> Result<SsviFit, BlackScholesError> {
+        Self::fit_with(slices, Some(previous))
+    }
+
+    fn fit_with(slices: &[SmileQuotes], previous: Option<&SsviParams>) -> Result<SsviFit, BlackScholesError> {
+        let mut market: Vec<(f64, Vec<(f64, f64)>)> = Vec::with_capacity(slices.len());
+        let mut nodes = Vec::with_capacity(slices.len());
+        let mut ordered: Vec<&SmileQuotes> = slices.iter().collect();
+        ordered.sort_by(|a, b| a.expiry.total_cmp(&b.expiry));
+        let mut floor: f64 = 0.0;
+        for smile in ordered {
+            let expiry = validation::positive("Time to expiry", smile.expiry)?;
+            let forward = validation::positive("Forward", smile.forward)?;
+            if smile.quotes.is_empty() {
+                return Err(BlackScholesError::invalid("Every SSVI slice needs at least one quote"));
+            }
+            let mut points = Vec::with_capacity(smile.quotes.len());
+            for &(strike, vol) in &smile.quotes {
+                validation::positive("Strike price", strike)?;
+                validation::positive("Volatility", vol)?;
+                points.push(((strike / forward).ln(), vol * vol * expiry));
+            }
+            points.sort_by(|a, b| a.0.total_cmp(&b.0));
+            floor = floor.max(atm_from_quotes(&points));
+            nodes.push(AtmNode {
+                expiry,
+                forward,
+                atm_variance: floor,
+            });
+            market.push((floor, points));
+        }
+        let count: usize = market.iter().map(|m| m.1.len()).sum();
+        if count < 3 {
+            return Err(BlackScholesError::invalid("SSVI fit needs at least three quotes"));
+        }
+
+        let objective = |x: &[f64]| {
+            let p = SsviParams::from_unconstrained(x);
+            let fit_error: f64 = market
+                .iter()
+                .flat_map(|(theta, points)| points.iter().map(move |&(k, w)| (p.total_variance(k, *theta) - w).powi(2)))
+                .sum();
+            fit_error + PENALTY_WEIGHT * (p.eta * (1.0 + p.rho.abs()) - 2.0).max(0.0).powi(2)
+        };
+        // Restarts from each optimum escape simplex collapse
+        let mut search = match previous {
+            None => {
+                let starts = [-0.5_f64, 0.0, 0.5].iter().map(|rho| vec![rho.atanh(), 0.0, 0.0]).collect();
+                SimplexSearch::new(starts, 0.2, 1e-18, 2000).with_restarts(2, 0.05)
+            }
+            Some(p) => SimplexSearch::new(vec![p.to_unconstrained()], WARM_START_STEP, 1e-18, 2000)
+                .with_restarts(1, WARM_START_STEP),
+        };
+        search.advance(objective, usize::MAX);
+        let x = search
+            .best
+            .map(|b| b.x)
+            .ok_or_else(|| BlackScholesError::no_convergence("SSVI calibration failed"))?;
+        let surface = SsviSurface::new(SsviParams::from_unconstrained(&x), nodes)?;
+
+        let errors: Vec<f64> = slices
+            .iter()
+            .flat_map(|smile| {
+                let surface = &surface;
+                smile
+                    .quotes
+                    .iter()
+                    .map(move |&(strike, vol)| (surface.implied_vol(strike, smile.expiry) - vol).abs())
+            })
+            .collect();
+        let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        let max_error = errors.iter().copied().fold(0.0, f64::max);
+        Ok(SsviFit {
+            surface,
+            rmse,
+            max_error,
+        })
+    }
+
+    /// Adjust the surface to a few fresh quotes without a full recalibration
+    ///
+    /// Intended for live marks between calibration runs. Only ρ, η and θ at
+    /// the quoted expiries move; γ and the other nodes are held. A stiffness
+    /// penalty on the squared change in each (ρ and η in optimizer
+    /// coordinates, θ in log) keeps a handful of quotes, typically ATM and
+    /// one strike in each wing, from swinging the rest of the surface. The
+    /// quoted forwards replace the nodes' forwards, and θ is kept
+    /// non-decreasing in expiry. Run `fit_from` at the next full calibration.
+    ///
+    /// # Arguments
+    /// * `slices` - Fresh quotes, each on an expiry already on the surface
+    /// * `stiffness` - Weight of the parameter change against the mean
+    ///   squared implied volatility error; 0 follows the quotes as closely
+    ///   as possible
+    ///
+    /// # Returns
+    /// Updated surface with diagnostics over the fresh quotes
+    pub fn update(&self, slices: &[SmileQuotes], stiffness: f64) -> Result<SsviFit, BlackScholesError> {
+        let stiffness = validation::non_negative("Stiffness", stiffness)?;
+        let mut nodes = self.nodes.clone();
+        let mut quoted: Vec<usize> = Vec::with_capacity(slices.len());
+        // (index into `quoted`, log-moneyness, implied volatility)
+        let mut market: Vec<(usize, f64, f64)> = Vec::new();
+        for smile in slices {
+            let node = nodes
+                .iter()
+                .position(|n| (n.expiry - smile.expiry).abs() <= EXPIRY_TOLERANCE)
+                .ok_or_else(|| BlackScholesError::invalid("Quoted expiry is not on the SSVI surface"))?;
+            if quoted.contains(&node) {
+                return Err(BlackScholesError::invalid("Duplicate expiry in SSVI update"));
+            }
+            let forward = validation::positive("Forward", smile.forward)?;
+            nodes[node].forward = forward;
+            for &(strike, vol) in &smile.quotes {
+                validation::positive("Strike price", strike)?;
+                validation::positive("Volatility", vol)?;
+                market.push((quoted.len(), (strike / forward).ln(), vol));
+            }
+            quoted.push(node);
+        }
+        if market.is_empty() {
+            return Err(BlackScholesError::invalid("Surface update needs at least one quote"));
+        }
+
+        let gamma = self.params.gamma;
+        let params_at = |x: &[f64]| SsviParams {
+            rho: x[0].tanh(),
+            eta: x[1].exp(),
+            gamma,
+        };
+        let thetas_at = |x: &[f64]| {
+            let mut thetas: Vec<f64> = nodes.iter().map(|n| n.atm_variance).collect();
+            for (slot, &node) in quoted.iter().enumerate() {
+                thetas[node] = x[2 + slot].exp();
+            }
+            thetas
+        };
+        let mut start = self.params.to_unconstrained();
+        start.truncate(2);
+        start.extend(quoted.iter().map(|&node| nodes[node].atm_variance.ln()));
+        // Never penalize the current parameters for a bound they already break
+        let wing_bound = (self.params.eta * (1.0 + self.params.rho.abs())).max(2.0);
+        let objective = |x: &[f64]| {
+            let p = params_at(x);
+            let thetas = thetas_at(x);
+            let fit_error = market
+                .iter()
+                .map(|&(slot, k, vol)| {
+                    let node = quoted[slot];
+                    let model = (p.total_variance(k, thetas[node]).max(0.0) / nodes[node].expiry).sqrt();
+                    (model - vol).powi(2)
+                })
+                .sum::<f64>()
+                / market.len() as f64;
+            let change: f64 = x.iter().zip(&start).map(|(x, s)| (x - s).powi(2)).sum();
+            let calendar: f64 = thetas.windows(2).map(|w| (w[0] - w[1]).max(0.0).powi(2)).sum();
+            let wings = (p.eta * (1.0 + p.rho.abs()) - wing_bound).max(0.0).powi(2);
+            fit_error + stiffness * change + PENALTY_WEIGHT * (calendar + wings)
+        };
+        let best = nelder_mead(objective, &start, UPDATE_STEP, 1e-20, 4000);
+
+        let thetas = thetas_at(&best.x);
+        let mut floor: f64 = 0.0;
+        for (node, theta) in nodes.iter_mut().zip(thetas) {
+            floor = floor.max(theta);
+            node.atm_variance = floor;
+        }
+        let surface = SsviSurface::new(params_at(&best.x), nodes)?;
+        let errors: Vec<f64> = slices
+            .iter()
+            .flat_map(|smile| {
+                let surface = &surface;
+                smile
+                    .quotes
+                    .iter()
+                    .map(move |&(strike, vol)| (surface.implied_vol(strike, smile.expiry) - vol).abs())
+            })
+            .collect();
+        let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        let max_error = errors.iter().copied().fold(0.0, f64::max);
+        Ok(SsviFit {
+            surface,
+            rmse,
+            max_error,
+        })
+    }
+
+    /// Slopes dθ/dT and d ln F/dT of the ATM term structure at `expiry`
+    fn term_slopes(&self, expiry: f64) -> (f64, f64) {
+        let (lo, hi, _) = self.bracket(expiry);
+        let (lower, upper) = (&self.nodes[lo], &self.nodes[hi]);
+        if lo == hi {
+            return (lower.atm_variance / lower.expiry, 0.0);
+        }
+        let span = upper.expiry - lower.expiry;
+        (
+            (upper.atm_variance - lower.atm_variance) / span,
+            (upper.forward / lower.forward).ln() / span,
+        )
+    }
+
+    /// Locate the nodes bracketing `expiry` and the linear weight of the upper one
+    fn bracket(&self, expiry: f64) -> (usize, usize, f64) {
+        let last = self.nodes.len() - 1;
+        if expiry <= self.nodes[0].expiry {
+            return (0, 0, 0.0);
+        }
+        if expiry >= self.nodes[last].expiry {
+            return (last, last, 0.0);
+        }
+        let hi = self.nodes.iter().position(|n| n.expiry >= expiry).unwrap_or(last);
+        let lo = hi - 1;
+        let weight = (expiry - self.nodes[lo].expiry) / (self.nodes[hi].expiry - self.nodes[lo].expiry);
+        (lo, hi, weight)
+    }
+}
+
+impl VolSurface for SsviSurface {
+    fn implied_vol(&self, strike: f64, expiry: f64) -> f64 {
+        (self.total_variance(strike, expiry).max(0.0) / expiry).sqrt()
+    }
+
+    fn total_variance(&self, strike: f64, expiry: f64) -> f64 {
+        let k = (strike / self.forward(expiry)).ln();
+        self.params.total_variance(k, self.atm_variance(expiry))
+    }
+
+    /// Closed-form derivatives of w(ln(K/F(T)), θ(T))
+    fn variance_derivatives(&self, strike: f64, expiry: f64) -> VarianceDerivatives {
+        let k = (strike / self.forward(expiry)).ln();
+        let (w, w_k, w_kk, w_theta) = self.params.variance_with_derivatives(k, self.atm_variance(expiry));
+        let (d_theta, d_log_forward) = self.term_slopes(expiry);
+        VarianceDerivatives {
+            total_variance: w,
+            d_strike: w_k / strike,
+            d_strike2: (w_kk - w_k) / (strike * strike),
+            d_expiry: w_theta * d_theta - w_k * d_log_forward,
+        }
+    }
+}
+
+/// Total variance at k = 0 from `(log_moneyness, total_variance)` points sorted by moneyness
+fn atm_from_quotes(points: &[(f64, f64)]) -> f64 {
+    match points.iter().position(|p| p.0 >= 0.0) {
+        Some(0) => points[0].1,
+        None => points[points.len() - 1].1,
+        Some(i) => {
+            let (k0, w0) = points[i - 1];
+            let (k1, w1) = points[i];
+            w0 + (w1 - w0) * (0.0 - k0) / (k1 - k0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn truth() -> SsviSurface {
+        let params = SsviParams::new(-0.6, 0.9, 0.4).unwrap();
+        let nodes = [(0.25, 100.5, 0.012), (1.0, 102.0, 0.045), (2.0, 104.0, 0.085)]
+            .map(|(expiry, forward, atm_variance)| AtmNode {
+                expiry,
+                forward,
+                atm_variance,
+            })
+            .to_vec();
+        SsviSurface::new(params, nodes).unwrap()
+    }
+
+    #[test]
+    fn test_fit_recovers_parameters() {
+        let surface = truth();
+        let slices: Vec<SmileQuotes> = surface
+            .nodes()
+            .iter()
+            .map(|n| {
+                // Strike grid straddling the forward so θ is interpolated, not read off
+                let quotes = (0..11)
+                    .map(|i| {
+                        let strike = 71.0 + 6.0 * i as f64;
+                        (strike, surface.implied_vol(strike, n.expiry))
+                    })
+                    .collect();
+                SmileQuotes {
+                    expiry: n.expiry,
+                    forward: n.forward,
+                    quotes,
+                }
+            })
+            .collect();
+
+        let fit = SsviSurface::fit(&slices).unwrap();
+        assert!(fit.rmse < 2e-3, "rmse = {}", fit.rmse);
+        assert!((fit.surface.params.rho + 0.6).abs() < 0.05);
+        assert!(fit.surface.params.is_arbitrage_free());
+    }
+
+    #[test]
+    fn test_atm_term_structure() {
+        let surface = truth();
+        assert!((surface.total_variance(100.5, 0.25) - 0.012).abs() < 1e-15);
+        assert!((surface.atm_variance(0.625) - 0.5 * (0.012 + 0.045)).abs() < 1e-15);
+        // Constant ATM vol outside the nodes
+        assert!((surface.atm_variance(4.0) - 0.17).abs() < 1e-15);
+        // Negative rho: downside strikes carry more variance
+        assert!(surface.implied_vol(80.0, 1.0) > surface.implied_vol(120.0, 1.0));
+    }
+
+    #[test]
+    fn test_update_follows_sparse_quotes() {
+        let calibrated = truth();
+        // The one-year ATM vol rallies and the skew steepens
+        let mut nodes = calibrated.nodes().to_vec();
+        nodes[1].atm_variance = 0.05;
+        let moved = SsviSurface::new(SsviParams::new(-0.65, 0.9, 0.4).unwrap(), nodes).unwrap();
+        let fresh = SmileQuotes {
+            expiry: 1.0,
+            forward: 102.0,
+            quotes: [85.0, 102.0, 118.0].iter().map(|&k| (k, moved.implied_vol(k, 1.0))).collect(),
+        };
+
+        let update = calibrated.update(std::slice::from_ref(&fresh), 1e-7).unwrap();
+        assert!(update.max_error < 2e-4, "{update:?}");
+        let surface = &update.surface;
+        assert!((surface.atm_variance(1.0) - 0.05).abs() < 5e-4);
+        assert!((surface.params.rho + 0.65).abs() < 0.02);
+        // Unquoted expiries keep their ATM variance
+        assert_eq!(surface.nodes()[0].atm_variance, 0.012);
+        assert_eq!(surface.nodes()[2].atm_variance, 0.085);
+
+        let stiff = calibrated.update(std::slice::from_ref(&fresh), 1.0).unwrap();
+        assert!(stiff.max_error > 10.0 * update.max_error);
+
+        let elsewhere = SmileQuotes { expiry: 0.5, ..fresh.clone() };
+        assert!(calibrated.update(&[elsewhere], 1e-7).is_err());
+        assert!(calibrated.update(&[fresh.clone(), fresh], 1e-7).is_err());
+    }
+
+    #[test]
+    fn test_invalid_surfaces() {
+        assert!(SsviParams::new(1.0, 0.5, 0.4).is_err());
+        assert!(SsviParams::new(0.0, 0.5, 1.0).is_err());
+        assert!(!SsviParams::new(-0.5, 1.5, 0.4).unwrap().is_arbitrage_free());
+        let params = SsviParams::new(-0.5, 1.0, 0.4).unwrap();
+        let node = |expiry, atm_variance| AtmNode {
+            expiry,
+            forward: 100.0,
+            atm_variance,
+        };
+        assert!(SsviSurface::new(params, vec![node(0.5, 0.03), node(1.0, 0.02)]).is_err());
+        assert!(SsviSurface::new(params, vec![]).is_err());
+    }
+}
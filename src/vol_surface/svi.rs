@@ -0,0 +1,587 @@
+use super::{SmileQuotes, VarianceDerivatives, VolSurface};
+use crate::error::BlackScholesError;
+use crate::math::{nelder_mead, SimplexSearch};
+use crate::validation;
+
+/// Log-moneyness grid half-width (beyond the quoted range) checked for butterfly arbitrage
+const ARBITRAGE_GRID_PADDING: f64 = 1.0;
+
+/// Number of points on the butterfly-arbitrage check grid
+const ARBITRAGE_GRID_POINTS: usize = 81;
+
+/// Penalty weight applied to constraint violations during calibration
+const PENALTY_WEIGHT: f64 = 1e3;
+
+/// Simplex edge length when refitting from previous parameters
+const WARM_START_STEP: f64 = 0.05;
+
+/// Simplex edge length when updating from a few fresh quotes
+const UPDATE_STEP: f64 = 0.01;
+
+/// Gap below which a quoted expiry is taken to be a slice's expiry
+const EXPIRY_TOLERANCE: f64 = 1e-9;
+
+/// Raw SVI parameters (Gatheral, 2004)
+///
+/// Total implied variance as a function of log-moneyness k = ln(K/F):
+/// w(k) = a + b·(ρ·(k - m) + √((k - m)² + σ²))
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SviParams {
+    /// Overall variance level
+    pub a: f64,
+    /// Angle between the asymptotes (wing slope)
+    pub b: f64,
+    /// Rotation of the smile, in (-1, 1)
+    pub rho: f64,
+    /// Horizontal translation of the smile
+    pub m: f64,
+    /// Smoothness of the vertex
+    pub sigma: f64,
+}
+
+impl SviParams {
+    /// Create a validated set of raw SVI parameters
+    ///
+    /// Rejects parameter sets with negative slope, |ρ| ≥ 1, non-positive σ,
+    /// negative minimum variance, or wings violating Lee's moment bound.
+    pub fn new(a: f64, b: f64, rho: f64, m: f64, sigma: f64) -> Result<Self, BlackScholesError> {
+        let a = validation::finite("SVI a", a)?;
+        let m = validation::finite("SVI m", m)?;
+        let b = validation::non_negative("SVI b", b)?;
+        let sigma = validation::positive("SVI sigma", sigma)?;
+        if !rho.is_finite() || rho.abs() >= 1.0 {
+            return Err(BlackScholesError::OutOfRange {
+                field: "SVI rho",
+                value: rho,
+                requirement: "in (-1, 1)",
+            });
+        }
+        let params = SviParams { a, b, rho, m, sigma };
+        if params.min_variance() < 0.0 {
+            return Err(BlackScholesError::invalid("SVI parameters imply negative total variance"));
+        }
+        if b * (1.0 + rho.abs()) > 4.0 {
+            return Err(BlackScholesError::invalid("SVI wings violate Lee's moment bound"));
+        }
+        Ok(params)
+    }
+
+    /// Total implied variance w(k) at log-moneyness `k`
+    pub fn total_variance(&self, k: f64) -> f64 {
+        let d = k - self.m;
+        self.a + self.b * (self.rho * d + (d * d + self.sigma * self.sigma).sqrt())
+    }
+
+    /// Implied volatility at log-moneyness `k` for expiry `t`
+    pub fn implied_vol(&self, k: f64, t: f64) -> f64 {
+        (self.total_variance(k).max(0.0) / t).sqrt()
+    }
+
+    /// Minimum total variance over all strikes, a + b·σ·√(1 - ρ²)
+    pub fn min_variance(&self) -> f64 {
+        self.a + self.b * self.sigma * (1.0 - self.rho * self.rho).sqrt()
+    }
+
+    /// Durrleman's function g(k); the smile is free of butterfly arbitrage where g(k) ≥ 0
+    pub fn durrleman_g(&self, k: f64) -> f64 {
+        let w = self.total_variance(k);
+        let (w1, w2) = self.derivatives(k);
+
+        (1.0 - k * w1 / (2.0 * w)).powi(2) - 0.25 * w1 * w1 * (1.0 / w + 0.25) + 0.5 * w2
+    }
+
+    /// First and second derivatives of total variance in log-moneyness
+    fn derivatives(&self, k: f64) -> (f64, f64) {
+        let d = k - self.m;
+        let s = (d * d + self.sigma * self.sigma).sqrt();
+        (self.b * (self.rho + d / s), self.b * self.sigma * self.sigma / s.powi(3))
+    }
+
+    /// Check Durrleman's condition on an evenly spaced log-moneyness grid
+    pub fn is_butterfly_free(&self, k_min: f64, k_max: f64, points: usize) -> bool {
+        log_moneyness_grid(k_min, k_max, points)
+            .all(|k| self.total_variance(k) > 0.0 && self.durrleman_g(k) >= -1e-12)
+    }
+
+    /// Optimizer coordinates of these parameters (inverse of `from_unconstrained`)
+    fn to_unconstrained(self) -> Vec<f64> {
+        vec![
+            self.a,
+            self.b.max(1e-12).ln(),
+            self.rho.clamp(-1.0 + 1e-12, 1.0 - 1e-12).atanh(),
+            self.m,
+            self.sigma.max(1e-12).ln(),
+        ]
+    }
+
+    /// Map unconstrained optimizer coordinates to SVI parameters
+    fn from_unconstrained(x: &[f64]) -> Self {
+        SviParams {
+            a: x[0],
+            b: x[1].exp(),
+            rho: x[2].tanh(),
+            m: x[3],
+            sigma: x[4].exp(),
+        }
+    }
+}
+
+/// Raw SVI smile for a single expiry
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SviSlice {
+    /// Expiry in years
+    pub expiry: f64,
+    /// Forward price for the expiry
+    pub forward: f64,
+    /// Calibrated raw SVI parameters
+    pub params: SviParams,
+}
+
+/// Result of fitting an SVI slice to market quotes
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SviFit {
+    /// The calibrated slice
+    pub slice: SviSlice,
+    /// Root-mean-square implied volatility error over the quotes
+    pub rmse: f64,
+    /// Largest absolute implied volatility error over the quotes
+    pub max_error: f64,
+    /// Whether the fitted smile satisfies Durrleman's condition
+    pub butterfly_free: bool,
+}
+
+impl SviSlice {
+    pub fn new(expiry: f64, forward: f64, params: SviParams) -> Result<Self, BlackScholesError> {
+        let expiry = validation::positive("Time to expiry", expiry)?;
+        let forward = validation::positive("Forward", forward)?;
+        Ok(SviSlice {
+            expiry,
+            forward,
+            params,
+        })
+    }
+
+    /// Log-moneyness ln(K/F) of a strike
+    pub fn log_moneyness(&self, strike: f64) -> f64 {
+        (strike / self.forward).ln()
+    }
+
+    /// Total implied variance at an absolute strike
+    pub fn total_variance(&self, strike: f64) -> f64 {
+        self.params.total_variance(self.log_moneyness(strike))
+    }
+
+    /// Implied volatility at an absolute strike
+    pub fn implied_vol(&self, strike: f64) -> f64 {
+        self.params.implied_vol(self.log_moneyness(strike), self.expiry)
+    }
+
+    /// Fit raw SVI to `(strike, implied_vol)` quotes with no-butterfly-arbitrage penalties
+    ///
+    /// The fit minimizes squared total-variance errors with Nelder-Mead over a
+    /// transformed parameter space that enforces b > 0, |ρ| < 1 and σ > 0.
+    /// Negative minimum variance, Lee's wing bound and Durrleman's condition
+    /// are enforced by penalties on a grid extending past the quoted strikes.
+    ///
+    /// # Arguments
+    /// * `expiry` - Expiry of the slice in years
+    /// * `forward` - Forward price for the expiry
+    /// * `quotes` - `(strike, implied_vol)` pairs
+    ///
+    /// # Returns
+    /// Calibrated slice with fit diagnostics
+    pub fn fit(expiry: f64, forward: f64, quotes: &[(f64, f64)]) -> Result<SviFit, BlackScholesError> {
+        Self::fit_with(expiry, forward, quotes, None)
+    }
+
+    /// Refit from previous parameters (warm start)
+    ///
+    /// A single local search from `previous` replaces the multi-start search
+    /// of `fit`, which is much faster when the smile has moved only slightly.
+    pub fn fit_from(
+        expiry: f64,
+        forward: f64,
+        quotes: &[(f64, f64)],
+        previous: &SviParams,
+    ) -> Result<SviFit, BlackScholesError> {
+        Self::fit_with(expiry, forward, quotes, Some(previous))
+    }
+
+    fn fit_with(
+        expiry: f64,
+        forward: f64,
+        quotes: &[(f64, f64)],
+        previous: Option<&SviParams>,
+    ) -> Result<SviFit, BlackScholesError> {
+        let expiry = validation::positive("Time to expiry", expiry)?;
+        let forward = validation::positive("Forward", forward)?;
+        if quotes.len() < 5 {
+            return Err(BlackScholesError::invalid("SVI fit needs at least five quotes"));
+        }
+        for &(strike, vol) in quotes {
+            validation::positive("Strike price", strike)?;
+            validation::positive("Volatility", vol)?;
+        }
+
+        let market: Vec<(f64, f64)> = quotes
+            .iter()
+            .map(|&(strike, vol)| ((strike / forward).ln(), vol * vol * expiry))
+            .collect();
+        let k_min = market.iter().map(|q| q.0).fold(f64::INFINITY, f64::min);
+        let k_max = market.iter().map(|q| q.0).fold(f64::NEG_INFINITY, f64::max);
+        let w_min = market.iter().map(|q| q.1).fold(f64::INFINITY, f64::min);
+        let grid: Vec<f64> = log_moneyness_grid(
+            k_min - ARBITRAGE_GRID_PADDING,
+            k_max + ARBITRAGE_GRID_PADDING,
+            ARBITRAGE_GRID_POINTS,
+        )
+        .collect();
+
+        let objective = |x: &[f64]| {
+            let p = SviParams::from_unconstrained(x);
+            let fit_error: f64 = market
+                .iter()
+                .map(|&(k, w)| (p.total_variance(k) - w).powi(2))
+                .sum();
+            fit_error + PENALTY_WEIGHT * arbitrage_penalty(&p, &grid)
+        };
+
+        // Restarts from each optimum escape simplex collapse
+        let mut search = match previous {
+            None => {
+                let starts = [-0.5, 0.0, 0.5]
+                    .iter()
+                    .map(|&rho| vec![0.5 * w_min, (0.1_f64).ln(), rho, 0.0, (0.1_f64).ln()])
+                    .collect();
+                SimplexSearch::new(starts, 0.1, 1e-16, 4000).with_restarts(3, 0.05)
+            }
+            Some(p) => SimplexSearch::new(vec![p.to_unconstrained()], WARM_START_STEP, 1e-16, 4000)
+                .with_restarts(1, WARM_START_STEP),
+        };
+        search.advance(objective, usize::MAX);
+        let x = search
+            .best
+            .map(|b| b.x)
+            .ok_or_else(|| BlackScholesError::no_convergence("SVI calibration failed"))?;
+        let slice = SviSlice::new(expiry, forward, SviParams::from_unconstrained(&x))?;
+        Ok(slice.diagnose(quotes, k_min, k_max))
+    }
+
+    /// Adjust the slice to a few fresh quotes without a full recalibration
+    ///
+    /// Intended for live marks between calibration runs: a handful of
+    /// quotes (typically ATM and one strike in each wing) cannot pin all
+    /// five parameters, so a stiffness penalty on the squared change in
+    /// each optimizer coordinate holds the smile near its calibrated shape
+    /// while a warm-started local search moves it through the quotes. The
+    /// same arbitrage penalties as `fit` apply.
+    ///
+    /// # Arguments
+    /// * `forward` - Current forward price for the expiry
+    /// * `quotes` - Fresh `(strike, implied_vol)` pairs, at least one
+    /// * `stiffness` - Weight of the parameter change against the mean
+    ///   squared implied volatility error; 0 follows the quotes as closely
+    ///   as possible
+    ///
+    /// # Returns
+    /// Updated slice with diagnostics over the fresh quotes
+    pub fn update(&self, forward: f64, quotes: &[(f64, f64)], stiffness: f64) -> Result<SviFit, BlackScholesError> {
+        let forward = validation::positive("Forward", forward)?;
+        let stiffness = validation::non_negative("Stiffness", stiffness)?;
+        if quotes.is_empty() {
+            return Err(BlackScholesError::invalid("Surface update needs at least one quote"));
+        }
+        for &(strike, vol) in quotes {
+            validation::positive("Strike price", strike)?;
+            validation::positive("Volatility", vol)?;
+        }
+
+        let market: Vec<(f64, f64)> = quotes.iter().map(|&(strike, vol)| ((strike / forward).ln(), vol)).collect();
+        let k_min = market.iter().map(|q| q.0).fold(f64::INFINITY, f64::min);
+        let k_max = market.iter().map(|q| q.0).fold(f64::NEG_INFINITY, f64::max);
+        let grid: Vec<f64> = log_moneyness_grid(
+            k_min - ARBITRAGE_GRID_PADDING,
+            k_max + ARBITRAGE_GRID_PADDING,
+            ARBITRAGE_GRID_POINTS,
+        )
+        .collect();
+        let start = self.params.to_unconstrained();
+        let objective = |x: &[f64]| {
+            let p = SviParams::from_unconstrained(x);
+            let fit_error = market
+                .iter()
+                .map(|&(k, vol)| (p.implied_vol(k, self.expiry) - vol).powi(2))
+                .sum::<f64>()
+                / market.len() as f64;
+            let change: f64 = x.iter().zip(&start).map(|(x, s)| (x - s).powi(2)).sum();
+            fit_error + stiffness * change + PENALTY_WEIGHT * arbitrage_penalty(&p, &grid)
+        };
+        let best = nelder_mead(objective, &start, UPDATE_STEP, 1e-20, 4000);
+        let slice = SviSlice::new(self.expiry, forward, SviParams::from_unconstrained(&best.x))?;
+        Ok(slice.diagnose(quotes, k_min, k_max))
+    }
+
+    /// Fit diagnostics of this slice against `(strike, implied_vol)` quotes spanning `k_min..=k_max`
+    fn diagnose(self, quotes: &[(f64, f64)], k_min: f64, k_max: f64) -> SviFit {
+        let errors: Vec<f64> = quotes
+            .iter()
+            .map(|&(strike, vol)| (self.implied_vol(strike) - vol).abs())
+            .collect();
+        let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        let max_error = errors.iter().copied().fold(0.0, f64::max);
+
+        SviFit {
+            slice: self,
+            rmse,
+            max_error,
+            butterfly_free: self.params.is_butterfly_free(
+                k_min - ARBITRAGE_GRID_PADDING,
+                k_max + ARBITRAGE_GRID_PADDING,
+                ARBITRAGE_GRID_POINTS,
+            ),
+        }
+    }
+}
+
+/// Penalty on negative variance, Lee's wing bound and Durrleman's condition over `grid`
+fn arbitrage_penalty(p: &SviParams, grid: &[f64]) -> f64 {
+    let mut penalty = p.min_variance().min(0.0).powi(2);
+    penalty += (p.b * (1.0 + p.rho.abs()) - 4.0).max(0.0).powi(2);
+    for &k in grid {
+        if p.total_variance(k) <= 0.0 {
+            penalty += 1.0;
+        } else {
+            penalty += p.durrleman_g(k).min(0.0).powi(2);
+        }
+    }
+    penalty
+}
+
+/// Surface built from SVI slices, interpolating total variance linearly in
+/// expiry at constant log-forward-moneyness
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SviSurface {
+    slices: Vec<SviSlice>,
+}
+
+impl SviSurface {
+    /// Build a surface from slices (sorted internally by expiry)
+    pub fn new(mut slices: Vec<SviSlice>) -> Result<Self, BlackScholesError> {
+        if slices.is_empty() {
+            return Err(BlackScholesError::invalid("SVI surface needs at least one slice"));
+        }
+        slices.sort_by(|a, b| a.expiry.total_cmp(&b.expiry));
+        if slices.windows(2).any(|w| w[0].expiry == w[1].expiry) {
+            return Err(BlackScholesError::invalid("Duplicate expiry in SVI surface"));
+        }
+        Ok(SviSurface { slices })
+    }
+
+    /// Slices making up the surface, ordered by expiry
+    pub fn slices(&self) -> &[SviSlice] {
+        &self.slices
+    }
+
+    /// Forward at an arbitrary expiry (log-linear between slices)
+    pub fn forward(&self, expiry: f64) -> f64 {
+        let (lo, hi, weight) = self.bracket(expiry);
+        (self.slices[lo].forward.ln() * (1.0 - weight) + self.slices[hi].forward.ln() * weight).exp()
+    }
+
+    /// Adjust the quoted slices to fresh quotes, leaving the others as they are
+    ///
+    /// Each slice moves as in [`SviSlice::update`]; every quoted expiry must
+    /// already be on the surface.
+    pub fn update(&self, slices: &[SmileQuotes], stiffness: f64) -> Result<SviSurface, BlackScholesError> {
+        let mut updated = self.slices.clone();
+        for smile in slices {
+            let slice = updated
+                .iter_mut()
+                .find(|s| (s.expiry - smile.expiry).abs() <= EXPIRY_TOLERANCE)
+                .ok_or_else(|| BlackScholesError::invalid("Quoted expiry is not on the SVI surface"))?;
+            *slice = slice.update(smile.forward, &smile.quotes, stiffness)?.slice;
+        }
+        SviSurface::new(updated)
+    }
+
+    /// Check that total variance is non-decreasing in expiry at every grid moneyness
+    pub fn is_calendar_free(&self, k_min: f64, k_max: f64, points: usize) -> bool {
+        self.slices.windows(2).all(|w| {
+            log_moneyness_grid(k_min, k_max, points)
+                .all(|k| w[1].params.total_variance(k) >= w[0].params.total_variance(k) - 1e-12)
+        })
+    }
+
+    /// Locate the slices bracketing `expiry` and the linear weight of the upper one
+    fn bracket(&self, expiry: f64) -> (usize, usize, f64) {
+        let last = self.slices.len() - 1;
+        if expiry <= self.slices[0].expiry {
+            return (0, 0, 0.0);
+        }
+        if expiry >= self.slices[last].expiry {
+            return (last, last, 0.0);
+        }
+        let hi = self.slices.iter().position(|s| s.expiry >= expiry).unwrap_or(last);
+        let lo = hi - 1;
+        let weight = (expiry - self.slices[lo].expiry) / (self.slices[hi].expiry - self.slices[lo].expiry);
+        (lo, hi, weight)
+    }
+}
+
+impl VolSurface for SviSurface {
+    fn implied_vol(&self, strike: f64, expiry: f64) -> f64 {
+        (self.total_variance(strike, expiry).max(0.0) / expiry).sqrt()
+    }
+
+    fn total_variance(&self, strike: f64, expiry: f64) -> f64 {
+        let k = (strike / self.forward(expiry)).ln();
+        let (lo, hi, weight) = self.bracket(expiry);
+        let lower = &self.slices[lo];
+
+        if lo == hi {
+            // Outside the quoted expiries: hold implied volatility constant
+            return lower.params.total_variance(k) * expiry / lower.expiry;
+        }
+        let upper = &self.slices[hi];
+        lower.params.total_variance(k) * (1.0 - weight) + upper.params.total_variance(k) * weight
+    }
+
+    /// Closed-form derivatives of the interpolated slices at constant log-moneyness
+    fn variance_derivatives(&self, strike: f64, expiry: f64) -> VarianceDerivatives {
+        let k = (strike / self.forward(expiry)).ln();
+        let (lo, hi, weight) = self.bracket(expiry);
+        let lower = &self.slices[lo];
+        let (lower_k, lower_kk) = lower.params.derivatives(k);
+        let (w, w_k, w_kk, w_t) = if lo == hi {
+            let scale = expiry / lower.expiry;
+            let w = lower.params.total_variance(k);
+            (w * scale, lower_k * scale, lower_kk * scale, w / lower.expiry)
+        } else {
+            let upper = &self.slices[hi];
+            let (upper_k, upper_kk) = upper.params.derivatives(k);
+            let (w_lo, w_hi) = (lower.params.total_variance(k), upper.params.total_variance(k));
+            let span = upper.expiry - lower.expiry;
+            // Moving T at fixed strike also moves k through the forward
+            let d_log_forward = (upper.forward / lower.forward).ln() / span;
+            let w_k = lower_k * (1.0 - weight) + upper_k * weight;
+            (
+                w_lo * (1.0 - weight) + w_hi * weight,
+                w_k,
+                lower_kk * (1.0 - weight) + upper_kk * weight,
+                (w_hi - w_lo) / span - w_k * d_log_forward,
+            )
+        };
+        VarianceDerivatives {
+            total_variance: w,
+            d_strike: w_k / strike,
+            d_strike2: (w_kk - w_k) / (strike * strike),
+            d_expiry: w_t,
+        }
+    }
+}
+
+/// Evenly spaced log-moneyness points between `k_min` and `k_max`
+fn log_moneyness_grid(k_min: f64, k_max: f64, points: usize) -> impl Iterator<Item = f64> {
+    let step = if points > 1 {
+        (k_max - k_min) / (points - 1) as f64
+    } else {
+        0.0
+    };
+    (0..points).map(move |i| k_min + step * i as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market_quotes(params: &SviParams, forward: f64, expiry: f64) -> Vec<(f64, f64)> {
+        (0..15)
+            .map(|i| {
+                let strike = 60.0 + 6.0 * i as f64;
+                (strike, params.implied_vol((strike / forward).ln(), expiry))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_svi_fit_recovers_smile() {
+        let truth = SviParams::new(0.02, 0.1, -0.4, 0.05, 0.15).unwrap();
+        let quotes = market_quotes(&truth, 100.0, 0.5);
+
+        let fit = SviSlice::fit(0.5, 100.0, &quotes).unwrap();
+        assert!(fit.rmse < 5e-4, "rmse = {}", fit.rmse);
+        assert!(fit.butterfly_free);
+        for &(strike, vol) in &quotes {
+            assert!((fit.slice.implied_vol(strike) - vol).abs() < 2e-3);
+        }
+    }
+
+    #[test]
+    fn test_update_follows_sparse_quotes() {
+        let calibrated = SviSlice::new(0.5, 100.0, SviParams::new(0.02, 0.1, -0.4, 0.05, 0.15).unwrap()).unwrap();
+        // The market sells off: forward down, level up, skew steeper
+        let moved = SviParams::new(0.024, 0.11, -0.5, 0.05, 0.15).unwrap();
+        let fresh: Vec<(f64, f64)> = [85.0, 98.0, 112.0]
+            .iter()
+            .map(|&strike| (strike, moved.implied_vol((strike / 98.0_f64).ln(), 0.5)))
+            .collect();
+
+        let update = calibrated.update(98.0, &fresh, 1e-6).unwrap();
+        assert!(update.max_error < 2e-4, "{update:?}");
+        assert!(update.butterfly_free);
+        assert_eq!(update.slice.forward, 98.0);
+        // Between the quotes the smile lands close to the moved one
+        let gap = (update.slice.implied_vol(105.0) - moved.implied_vol((105.0 / 98.0_f64).ln(), 0.5)).abs();
+        assert!(gap < 2e-3, "{gap}");
+
+        // A stiff update barely moves
+        let stiff = calibrated.update(98.0, &fresh, 1.0).unwrap();
+        assert!(stiff.max_error > 10.0 * update.max_error);
+
+        let surface = SviSurface::new(vec![calibrated]).unwrap();
+        let smile = SmileQuotes {
+            expiry: 0.5,
+            forward: 98.0,
+            quotes: fresh.clone(),
+        };
+        assert_eq!(surface.update(std::slice::from_ref(&smile), 1e-6).unwrap().slices()[0].params, update.slice.params);
+        let elsewhere = SmileQuotes { expiry: 0.75, ..smile };
+        assert!(surface.update(&[elsewhere], 1e-6).is_err());
+        assert!(calibrated.update(98.0, &[], 1e-6).is_err());
+    }
+
+    #[test]
+    fn test_invalid_svi_parameters() {
+        assert!(SviParams::new(0.02, -0.1, 0.0, 0.0, 0.1).is_err());
+        assert!(SviParams::new(0.02, 0.1, 1.0, 0.0, 0.1).is_err());
+        assert!(SviParams::new(0.02, 0.1, 0.0, 0.0, 0.0).is_err());
+        assert!(SviParams::new(-0.5, 0.1, 0.0, 0.0, 0.1).is_err());
+        assert!(SviParams::new(0.02, 3.0, 0.5, 0.0, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_butterfly_arbitrage_detected() {
+        // Steep wings with a tight vertex create negative densities
+        let params = SviParams::new(0.0001, 1.9, -0.9, 0.0, 0.01).unwrap();
+        assert!(!params.is_butterfly_free(-1.0, 1.0, 81));
+
+        let benign = SviParams::new(0.04, 0.1, -0.3, 0.0, 0.2).unwrap();
+        assert!(benign.is_butterfly_free(-1.5, 1.5, 81));
+    }
+
+    #[test]
+    fn test_svi_surface_interpolation() {
+        let short = SviSlice::new(0.25, 100.0, SviParams::new(0.01, 0.05, -0.3, 0.0, 0.1).unwrap()).unwrap();
+        let long = SviSlice::new(1.0, 100.0, SviParams::new(0.04, 0.1, -0.3, 0.0, 0.1).unwrap()).unwrap();
+        let surface = SviSurface::new(vec![long, short]).unwrap();
+
+        let mid = surface.total_variance(100.0, 0.625);
+        let expected = 0.5 * (short.total_variance(100.0) + long.total_variance(100.0));
+        assert!((mid - expected).abs() < 1e-12);
+        assert!(surface.is_calendar_free(-1.0, 1.0, 41));
+    }
+}
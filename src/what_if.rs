@@ -0,0 +1,241 @@
+use crate::black_scholes::{CashGreeks, Greeks};
+use crate::error::BlackScholesError;
+use crate::monte_carlo::MonteCarlo;
+use crate::portfolio::{Exposure, Portfolio, Position};
+use crate::risk::{delta_gamma_var, delta_normal_var, monte_carlo_var, RiskFactors, RiskMeasure, VarConfig};
+use crate::scenario::{ScenarioGrid, ScenarioResult};
+
+/// How a pre-trade check measures VaR and ES
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VarMethod {
+    DeltaNormal,
+    DeltaGamma,
+    /// Full revaluation; the fixed seed makes before/after draws identical
+    MonteCarlo(MonteCarlo),
+}
+
+/// Risk of a book as seen by a pre-trade check
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RiskProfile {
+    /// Book value and netted Greeks, cash included
+    pub exposure: Exposure,
+    /// Simplified initial margin (see `Portfolio::margin`)
+    pub margin: f64,
+    pub risk: RiskMeasure,
+    pub scenarios: ScenarioResult,
+}
+
+/// Book risk before and after a proposed trade
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeImpact {
+    /// Model value of the trade, paid out of cash
+    pub premium: f64,
+    pub before: RiskProfile,
+    pub after: RiskProfile,
+}
+
+impl TradeImpact {
+    /// Change in netted Greeks (units of each underlying)
+    pub fn greeks_change(&self) -> Greeks {
+        let (a, b) = (self.after.exposure.greeks, self.before.exposure.greeks);
+        Greeks {
+            delta: a.delta - b.delta,
+            gamma: a.gamma - b.gamma,
+            vega: a.vega - b.vega,
+            theta: a.theta - b.theta,
+            rho: a.rho - b.rho,
+        }
+    }
+
+    /// Change in currency Greeks
+    pub fn cash_greeks_change(&self) -> CashGreeks {
+        let (a, b) = (self.after.exposure.cash_greeks, self.before.exposure.cash_greeks);
+        CashGreeks {
+            delta: a.delta - b.delta,
+            gamma: a.gamma - b.gamma,
+            vega: a.vega - b.vega,
+            theta: a.theta - b.theta,
+            rho: a.rho - b.rho,
+        }
+    }
+
+    /// Additional margin the trade consumes (negative when it releases margin)
+    pub fn margin_change(&self) -> f64 {
+        self.after.margin - self.before.margin
+    }
+
+    /// Change in VaR and ES (positive when the trade adds risk)
+    pub fn risk_change(&self) -> RiskMeasure {
+        RiskMeasure {
+            var: self.after.risk.var - self.before.risk.var,
+            expected_shortfall: self.after.risk.expected_shortfall - self.before.risk.expected_shortfall,
+        }
+    }
+
+    /// Scenario P&L the trade adds, `[t][v][s]` as in `ScenarioResult::pnl`
+    pub fn scenario_pnl_change(&self) -> Vec<Vec<Vec<f64>>> {
+        let zip = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(a, b)| a - b).collect::<Vec<_>>();
+        self.after
+            .scenarios
+            .pnl
+            .iter()
+            .zip(&self.before.scenarios.pnl)
+            .map(|(a, b)| a.iter().zip(b).map(|(a, b)| zip(a, b)).collect())
+            .collect()
+    }
+
+    /// Change in the worst scenario P&L (negative when the trade deepens it)
+    pub fn worst_case_change(&self) -> f64 {
+        self.after.scenarios.worst().1 - self.before.scenarios.worst().1
+    }
+}
+
+/// Pre-trade risk check: how a proposed trade would change a book's risk
+///
+/// The book is never modified; the trade is applied to a copy.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PreTradeCheck {
+    pub factors: RiskFactors,
+    pub var_config: VarConfig,
+    pub var_method: VarMethod,
+    pub grid: ScenarioGrid,
+}
+
+impl PreTradeCheck {
+    /// Check with delta-gamma VaR
+    ///
+    /// # Arguments
+    /// * `factors` - Volatilities and correlations of every underlying traded
+    /// * `var_config` - VaR horizon and confidence
+    /// * `grid` - Scenario grid whose P&L is reported
+    pub fn new(factors: RiskFactors, var_config: VarConfig, grid: ScenarioGrid) -> Result<Self, BlackScholesError> {
+        grid.validate()?;
+        Ok(PreTradeCheck {
+            factors,
+            var_config,
+            var_method: VarMethod::DeltaGamma,
+            grid,
+        })
+    }
+
+    pub fn with_var_method(mut self, var_method: VarMethod) -> Self {
+        self.var_method = var_method;
+        self
+    }
+
+    /// Risk profile of a book
+    pub fn profile(&self, portfolio: &Portfolio) -> Result<RiskProfile, BlackScholesError> {
+        let risk = match &self.var_method {
+            VarMethod::DeltaNormal => delta_normal_var(portfolio, &self.factors, &self.var_config)?,
+            VarMethod::DeltaGamma => delta_gamma_var(portfolio, &self.factors, &self.var_config)?,
+            VarMethod::MonteCarlo(engine) => monte_carlo_var(portfolio, &self.factors, &self.var_config, engine)?,
+        };
+        Ok(RiskProfile {
+            exposure: portfolio.total(),
+            margin: portfolio.margin()?,
+            risk,
+            scenarios: self.grid.run(portfolio)?,
+        })
+    }
+
+    /// Risk of `portfolio` before and after adding the positions in `trade`
+    ///
+    /// A multi-leg trade is several positions or one strategy position. The
+    /// trade executes at model value paid from cash, so the book's value is
+    /// unchanged and every difference reflects risk alone.
+    pub fn evaluate(&self, portfolio: &Portfolio, trade: &[Position]) -> Result<TradeImpact, BlackScholesError> {
+        if trade.is_empty() {
+            return Err(BlackScholesError::invalid("Trade needs at least one position"));
+        }
+        let premium: f64 = trade.iter().map(Position::market_value).sum();
+        let mut after = portfolio.clone();
+        after.positions.extend_from_slice(trade);
+        after.cash -= premium;
+        Ok(TradeImpact {
+            premium,
+            before: self.profile(portfolio)?,
+            after: self.profile(&after)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes::{BlackScholes, OptionType};
+    use crate::portfolio::Instrument;
+    use crate::strategy::Strategy;
+
+    fn call(strike: f64) -> Instrument {
+        Instrument::Vanilla {
+            model: BlackScholes::new(100.0, strike, 0.25, 0.03, 0.2, 0.0).unwrap(),
+            option_type: OptionType::Call,
+        }
+    }
+
+    fn book() -> Portfolio {
+        Portfolio::new(vec![Position::new("AAA", call(100.0), -10.0, 100.0).unwrap()])
+    }
+
+    fn check() -> PreTradeCheck {
+        PreTradeCheck::new(
+            RiskFactors::single("AAA", 0.2).unwrap(),
+            VarConfig::one_day_99(),
+            ScenarioGrid::spot_vol_ladder(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hedge_reduces_risk_without_touching_book() {
+        let portfolio = book();
+        let hedge = vec![Position::new("AAA", call(105.0), 10.0, 100.0).unwrap()];
+        let impact = check().evaluate(&portfolio, &hedge).unwrap();
+
+        assert_eq!(portfolio.positions.len(), 1);
+        assert_eq!(portfolio.cash, 0.0);
+        assert!((impact.after.exposure.market_value - impact.before.exposure.market_value).abs() < 1e-9);
+        assert!(impact.greeks_change().delta > 0.0 && impact.greeks_change().gamma > 0.0);
+        // Covering the naked call caps the loss at the strike width
+        assert!(impact.margin_change() < 0.0);
+        assert!(impact.after.margin <= 5.0 * 1000.0 + 1e-9);
+        assert!(impact.risk_change().var < 0.0);
+        assert!(impact.worst_case_change() > 0.0);
+    }
+
+    #[test]
+    fn test_scenario_change_is_trade_pnl() {
+        let portfolio = book();
+        let strategy = Strategy::straddle(100.0, 0.25).unwrap();
+        let market = BlackScholes::new(100.0, 100.0, 0.25, 0.03, 0.2, 0.0).unwrap();
+        let trade = vec![Position::new("AAA", Instrument::Strategy { strategy, market }, 2.0, 100.0).unwrap()];
+        let impact = check().evaluate(&portfolio, &trade).unwrap();
+        let alone = check().grid.run(&Portfolio::new(trade.clone())).unwrap();
+        for (changed, direct) in impact.scenario_pnl_change().iter().flatten().flatten().zip(alone.pnl.iter().flatten().flatten()) {
+            assert!((changed - direct).abs() < 1e-8);
+        }
+        assert!((impact.premium - trade[0].market_value()).abs() < 1e-12);
+        assert!((impact.cash_greeks_change().vega - trade[0].cash_greeks().vega).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_var_methods_and_errors() {
+        let portfolio = book();
+        let trade = vec![Position::new("AAA", Instrument::Underlying { spot: 100.0 }, 500.0, 1.0).unwrap()];
+        for method in [
+            VarMethod::DeltaNormal,
+            VarMethod::DeltaGamma,
+            VarMethod::MonteCarlo(MonteCarlo::new(20_000, 7).unwrap()),
+        ] {
+            let impact = check().with_var_method(method).evaluate(&portfolio, &trade).unwrap();
+            assert!(impact.risk_change().var < 0.0, "{method:?}");
+        }
+        assert!(check().evaluate(&portfolio, &[]).is_err());
+        let other = vec![Position::new("BBB", Instrument::Underlying { spot: 50.0 }, 1.0, 1.0).unwrap()];
+        assert!(check().evaluate(&portfolio, &other).is_err());
+    }
+}